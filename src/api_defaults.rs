@@ -17,11 +17,36 @@ pub const DEFAULT_NUMBER_REQUESTS_MINUTE: u32 = 80;
 // As this is an approximation, we will use 30 if per_page is not provided.
 pub const DEFAULT_PER_PAGE: u32 = 30;
 
+// Number of results per page requested when listing. Max allowed by both
+// Gitlab and Github, so fewer requests are needed on big listings.
+pub const MAX_PER_PAGE: u32 = 100;
+
 pub const EXPIRE_IMMEDIATELY: &str = "0s";
 
+// User identities rarely change. Cached for long unless configured otherwise.
+// The authenticated user is refreshed with `gr cache refresh-user`.
+pub const USER_CACHE_EXPIRATION: &str = "30d";
+
+// Base wait time before retrying an operation the remote is not ready to
+// fulfill yet. Ex. merging a merge request right after its approval.
+pub const DEFAULT_OPERATION_RETRY_WAIT_SECONDS: u64 = 5;
+
+// Maximum size of an HTTP response body read into memory, 10MB. Can be
+// overridden per domain with max_response_body_mb in the config file.
+pub const MAX_RESPONSE_BODY_SIZE: u64 = 10 * 1024 * 1024;
+
+// Consecutive server or transport errors after which requests to a domain
+// are blocked, and for how long in seconds.
+pub const CIRCUIT_BREAKER_FAILURE_THRESHOLD: u32 = 5;
+pub const CIRCUIT_BREAKER_COOL_DOWN_SECONDS: u64 = 60;
+
 // Default jitter values for autorate throttling.
 pub const DEFAULT_JITTER_MAX_MILLISECONDS: u64 = 5000;
 pub const DEFAULT_JITTER_MIN_MILLISECONDS: u64 = 1000;
 
 // Trigger autorate throttling after 3 API calls.
 pub const ENGAGE_AUTORATE_THROTTLING_THRESHOLD: u32 = 3;
+
+// Maximum number of projects or domains queried at the same time when
+// listing across several of them.
+pub const MAX_PARALLEL_REMOTE_QUERIES: usize = 4;
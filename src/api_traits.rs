@@ -1,25 +1,44 @@
 use std::{fmt::Display, str::FromStr};
 
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 use crate::{
+    auth::Credentials,
     cli::browse::BrowseOptions,
     cmds::{
         cicd::{
-            Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs, Runner,
-            RunnerListBodyArgs, RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse,
-            YamlBytes,
+            flaky::TestResult,
+            stats::JobTiming,
+            status::{PipelineRef, PipelineStatus},
+            zip::ByteRange,
+            Artifact, FailedJob, Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs,
+            Runner, RunnerListBodyArgs, RunnerMetadata, RunnerPostDataCliArgs,
+            RunnerRegistrationResponse, RunnerUsage, YamlBytes,
         },
+        contract::{ContractFixture, ContractOperation},
+        deployment::{DeploymentStatus, DeploymentStatusCliArgs, Environment, RollbackJob},
         docker::{DockerListBodyArgs, ImageMetadata, RegistryRepository, RepositoryTag},
         gist::{Gist, GistListBodyArgs},
+        issue::{IssueBodyArgs, IssueCommentBodyArgs, IssueListBodyArgs, IssueResponse},
+        label::{LabelBodyArgs, LabelListBodyArgs, LabelResponse},
         merge_request::{
-            Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs,
-            MergeRequestBodyArgs, MergeRequestListBodyArgs, MergeRequestResponse,
+            review::ReviewFile, Comment, CommentMergeRequestBodyArgs,
+            CommentMergeRequestListBodyArgs, Discussion, DiscussionReplyBodyArgs, FileChange,
+            MergeQueueEntry, MergeRequestBodyArgs, MergeRequestListBodyArgs,
+            MergeRequestMergeBodyArgs, MergeRequestResponse, ReactionBodyArgs, RebaseStatus,
+            ReviewBodyArgs, Suggestion, SuggestionBodyArgs,
+        },
+        project::{
+            BlameLine, Dependency, Invitation, Member, MemberAccessLevel, Membership, Mirror,
+            Project, ProjectAccess, ProjectBlameBodyArgs, ProjectLanguage, ProjectListBodyArgs,
+            Tag,
         },
-        project::{Member, Project, ProjectListBodyArgs, Tag},
         release::{Release, ReleaseAssetListBodyArgs, ReleaseAssetMetadata, ReleaseBodyArgs},
+        remote::RemoteInfo,
+        search::{SearchBodyArgs, SearchResult},
         trending::TrendingProject,
-        user::UserCliArgs,
+        user::{ActivityBodyArgs, ActivityEvent, UserCliArgs, UserProfile},
     },
     io::CmdInfo,
     Result,
@@ -28,16 +47,46 @@ use crate::{
 pub trait MergeRequest {
     fn open(&self, args: MergeRequestBodyArgs) -> Result<MergeRequestResponse>;
     fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>>;
-    fn merge(&self, id: i64) -> Result<MergeRequestResponse>;
+    fn merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse>;
     fn get(&self, id: i64) -> Result<MergeRequestResponse>;
     fn close(&self, id: i64) -> Result<MergeRequestResponse>;
     fn approve(&self, id: i64) -> Result<MergeRequestResponse>;
+    /// Mark the merge request as draft, or as ready for review if `draft` is
+    /// false.
+    fn toggle_draft(&self, id: i64, draft: bool) -> Result<MergeRequestResponse>;
     /// Queries the remote API to get the number of pages available for a given
     /// resource based on list arguments.
     fn num_pages(&self, args: MergeRequestListBodyArgs) -> Result<Option<u32>>;
     fn num_resources(&self, args: MergeRequestListBodyArgs) -> Result<Option<NumberDeltaErr>>;
 }
 
+/// Gitlab merge trains and Github merge queues.
+pub trait MergeQueue {
+    fn add(&self, id: i64) -> Result<MergeQueueEntry>;
+    fn remove(&self, id: i64) -> Result<()>;
+    /// Position, status and estimated time to merge of a queued merge request.
+    fn status(&self, id: i64) -> Result<MergeQueueEntry>;
+}
+
+/// Merge requests that need to be merged before a given one. Gitlab supports
+/// them natively, Github through `Depends on #<id>` markers in the pull
+/// request description.
+pub trait MergeRequestDependency {
+    fn add_dependency(&self, id: i64, depends_on: i64) -> Result<()>;
+    fn list_dependencies(&self, id: i64) -> Result<Vec<MergeRequestResponse>>;
+}
+
+/// Server-side rebase of the source branch of a merge request onto its target
+/// branch. Github updates the pull request branch with the changes of the
+/// base branch instead.
+pub trait MergeRequestRebase {
+    /// Starts the rebase. It runs asynchronously in the remote, poll
+    /// `rebase_status` until it is no longer in progress.
+    fn rebase(&self, id: i64) -> Result<RebaseStatus>;
+    /// Status of the rebase returned by `rebase`.
+    fn rebase_status(&self, started: &RebaseStatus) -> Result<RebaseStatus>;
+}
+
 pub trait RemoteProject {
     /// Get the project data from the remote API. Implementers will need to pass
     /// either an `id` or a `path`. The `path` should be in the format
@@ -60,6 +109,71 @@ pub trait ProjectMember: RemoteProject {
     fn list(&self, args: ProjectListBodyArgs) -> Result<Vec<Member>>;
 }
 
+pub trait ProjectMemberAccess {
+    /// Adds a user to the project with the given access. Github invites the
+    /// user, who becomes a collaborator after accepting the invitation.
+    fn add_member(&self, username: &str, access: MemberAccessLevel) -> Result<Membership>;
+    fn update_member(&self, username: &str, access: MemberAccessLevel) -> Result<Membership>;
+    fn remove_member(&self, username: &str) -> Result<()>;
+    /// Blocks the user. Gitlab blocks the user in the whole instance, which
+    /// requires administrator access. Github blocks the user in the
+    /// organization that owns the repository.
+    fn block_member(&self, username: &str) -> Result<()>;
+    /// Invites someone who is not a member yet by email. Github has no email
+    /// invitations to repositories, so the email is resolved to the user
+    /// that has it as public email.
+    fn invite_member(&self, email: &str, access: MemberAccessLevel) -> Result<Membership>;
+}
+
+pub trait ProjectAccessCheck {
+    /// Resolves the access the user has on the project and where it comes
+    /// from: direct membership, groups in Gitlab and teams or organization
+    /// roles in Github.
+    fn access_check(&self, username: &str) -> Result<ProjectAccess>;
+}
+
+pub trait ProjectInvitation {
+    /// Pending invitations to join the project.
+    fn list_invitations(&self) -> Result<Vec<Invitation>>;
+    /// Revokes the pending invitation of the given invitee, an email in
+    /// Gitlab and a username in Github.
+    fn revoke_invitation(&self, invitee: &str) -> Result<()>;
+}
+
+pub trait ProjectMirror {
+    /// List the pull and push mirrors of the project along with the status of
+    /// their last update.
+    fn list_mirrors(&self) -> Result<Vec<Mirror>>;
+    /// Force an update of all the enabled mirrors of the project. Returns the
+    /// mirrors that have been triggered.
+    fn sync_mirrors(&self) -> Result<Vec<Mirror>>;
+}
+
+pub trait ProjectLanguages {
+    /// Percentage of code written in each language of the project. The
+    /// project is given by id or by owner/repo path, defaulting to the
+    /// current one.
+    fn languages(&self, id: Option<i64>, path: Option<&str>) -> Result<Vec<ProjectLanguage>>;
+}
+
+pub trait ProjectBlame {
+    /// Blame a file in the remote repository at a given git reference. Lines
+    /// are numbered starting at 1.
+    fn blame(&self, args: ProjectBlameBodyArgs) -> Result<Vec<BlameLine>>;
+}
+
+pub trait ProjectDependency {
+    /// List the dependencies of the project as detected by the remote
+    /// dependency graph.
+    fn list_dependencies(&self) -> Result<Vec<Dependency>>;
+}
+
+pub trait Search {
+    fn list(&self, args: SearchBodyArgs) -> Result<Vec<SearchResult>>;
+    fn num_pages(&self, args: SearchBodyArgs) -> Result<Option<u32>>;
+    fn num_resources(&self, args: SearchBodyArgs) -> Result<Option<NumberDeltaErr>>;
+}
+
 pub trait Cicd {
     fn list(&self, args: PipelineBodyArgs) -> Result<Vec<Pipeline>>;
     fn get_pipeline(&self, id: i64) -> Result<Pipeline>;
@@ -78,10 +192,82 @@ pub trait CicdRunner {
     fn num_resources(&self, args: RunnerListBodyArgs) -> Result<Option<NumberDeltaErr>>;
 }
 
+pub trait CicdUsage {
+    /// CI/CD minutes consumed per project and month in a Gitlab group or
+    /// Github organization.
+    fn usage(&self, group: &str) -> Result<Vec<RunnerUsage>>;
+}
+
+pub trait CicdArtifact {
+    /// List the pipeline artifacts of the project that have not expired yet.
+    fn list(&self) -> Result<Vec<Artifact>>;
+    fn delete(&self, id: i64) -> Result<()>;
+    /// Reads a range of bytes of the artifact's zip archive.
+    fn read_archive(&self, id: i64, range: ByteRange) -> Result<Vec<u8>>;
+}
+
+pub trait RemoteCredentials {
+    /// Credentials to authenticate against the remote with clients other
+    /// than gitar, ex. docker or git.
+    fn credentials(&self) -> Result<Credentials>;
+}
+
+pub trait ContractRecorder {
+    /// Raw payload of an API operation for the current project. `id` is the
+    /// merge request for merge request related operations.
+    fn record(&self, operation: ContractOperation, id: i64) -> Result<ContractFixture>;
+}
+
+pub trait CicdJobLog {
+    /// Failed jobs of the most recent `last` failed pipelines.
+    fn list_failed_jobs(&self, last: u32) -> Result<Vec<FailedJob>>;
+    /// Raw log (trace) of a job.
+    fn get_log(&self, job_id: i64) -> Result<String>;
+    /// Raw log of a job from byte `offset` on, to follow a running job.
+    /// Remotes that cannot request part of the log download all of it.
+    fn get_log_from(&self, job_id: i64, offset: u64) -> Result<Vec<u8>> {
+        let log = self.get_log(job_id)?.into_bytes();
+        Ok(log[(offset as usize).min(log.len())..].to_vec())
+    }
+}
+
+pub trait CicdTestReport {
+    /// Test case results of the most recent `last` finished pipelines.
+    fn list_test_results(&self, last: u32) -> Result<Vec<TestResult>>;
+}
+
+pub trait CicdJobTiming {
+    /// Queue and run times of the jobs that ran in the most recent `last`
+    /// finished pipelines.
+    fn list_job_timings(&self, last: u32) -> Result<Vec<JobTiming>>;
+}
+
+pub trait CicdPipelineStatus {
+    /// Latest pipeline of a branch or commit and its jobs. None if no
+    /// pipeline ran for it yet.
+    fn pipeline_status(&self, git_ref: &PipelineRef) -> Result<Option<PipelineStatus>>;
+}
+
 pub trait CicdJob {
     fn list(&self, args: JobListBodyArgs) -> Result<Vec<Job>>;
     fn num_pages(&self, args: JobListBodyArgs) -> Result<Option<u32>>;
     fn num_resources(&self, args: JobListBodyArgs) -> Result<Option<NumberDeltaErr>>;
+    fn get(&self, job_id: i64) -> Result<Job>;
+    /// Runs a finished job again. Returns the job that was queued.
+    fn retry(&self, job_id: i64) -> Result<Job>;
+    fn cancel(&self, job_id: i64) -> Result<Job>;
+    /// Starts a manual job.
+    fn play(&self, job_id: i64) -> Result<Job>;
+}
+
+pub trait Issue {
+    fn create(&self, args: IssueBodyArgs) -> Result<IssueResponse>;
+    fn get(&self, id: i64) -> Result<IssueResponse>;
+    fn list(&self, args: IssueListBodyArgs) -> Result<Vec<IssueResponse>>;
+    fn num_pages(&self, args: IssueListBodyArgs) -> Result<Option<u32>>;
+    fn num_resources(&self, args: IssueListBodyArgs) -> Result<Option<NumberDeltaErr>>;
+    fn comment(&self, args: IssueCommentBodyArgs) -> Result<()>;
+    fn close(&self, id: i64) -> Result<IssueResponse>;
 }
 
 pub trait Deploy {
@@ -96,10 +282,29 @@ pub trait DeployAsset {
     fn num_resources(&self, args: ReleaseAssetListBodyArgs) -> Result<Option<NumberDeltaErr>>;
 }
 
+pub trait Deployment {
+    /// Reports the status of an existing deployment, so external deploy
+    /// scripts can notify the remote of the outcome.
+    fn set_status(&self, args: DeploymentStatusCliArgs) -> Result<DeploymentStatus>;
+    /// Stops the environment with the given name.
+    fn stop_environment(&self, environment: &str) -> Result<Environment>;
+    /// Re-deploys a previous deployment of the given environment.
+    fn rollback(&self, environment: &str, deployment_id: i64) -> Result<RollbackJob>;
+}
+
 pub trait UserInfo {
     /// Get the user's information from the remote API.
     fn get_auth_user(&self) -> Result<Member>;
     fn get(&self, args: &UserCliArgs) -> Result<Member>;
+    /// Get the public profile of a user, including the organizations the user
+    /// belongs to.
+    fn get_profile(&self, args: &UserCliArgs) -> Result<UserProfile>;
+}
+
+pub trait UserActivity {
+    /// List the contribution events of a user. Events not counted as
+    /// contributions are discarded.
+    fn list_events(&self, args: ActivityBodyArgs) -> Result<Vec<ActivityEvent>>;
 }
 
 pub trait CodeGist {
@@ -109,7 +314,12 @@ pub trait CodeGist {
 }
 
 pub trait Timestamp {
-    fn created_at(&self) -> String;
+    fn created_at(&self) -> DateTime<Utc>;
+    /// Last update of resources that track it, used by the updated
+    /// after/before list filters.
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
 }
 
 pub trait ContainerRegistry {
@@ -124,6 +334,9 @@ pub trait ContainerRegistry {
 
 pub trait CommentMergeRequest {
     fn create(&self, args: CommentMergeRequestBodyArgs) -> Result<()>;
+    /// Replace the body of an existing comment of the merge request.
+    fn update(&self, comment_id: i64, args: CommentMergeRequestBodyArgs) -> Result<()>;
+    fn delete(&self, id: i64, comment_id: i64) -> Result<()>;
     fn list(&self, args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>>;
     fn num_pages(&self, args: CommentMergeRequestListBodyArgs) -> Result<Option<u32>>;
     fn num_resources(
@@ -132,6 +345,57 @@ pub trait CommentMergeRequest {
     ) -> Result<Option<NumberDeltaErr>>;
 }
 
+pub trait MergeRequestReaction {
+    /// Award an emoji to a merge request or, if a comment id is given, to
+    /// one of its comments.
+    fn react(&self, args: ReactionBodyArgs) -> Result<()>;
+}
+
+pub trait MergeRequestDiscussion {
+    /// Threads of comments of the merge request, oldest first.
+    fn list_discussions(&self, id: i64) -> Result<Vec<Discussion>>;
+    /// Add a comment to a thread of the merge request.
+    fn reply(&self, args: DiscussionReplyBodyArgs) -> Result<()>;
+}
+
+pub trait MergeRequestSuggestion {
+    /// Comment on a line of the merge request diff suggesting a replacement
+    /// that can be applied from the remote's UI.
+    fn suggest(&self, args: SuggestionBodyArgs) -> Result<()>;
+    /// Suggestions on the merge request that can still be applied.
+    fn list_suggestions(&self, id: i64) -> Result<Vec<Suggestion>>;
+    /// Apply suggestions in a single commit to the source branch.
+    fn apply_suggestions(&self, ids: &[i64], commit_message: Option<String>) -> Result<()>;
+}
+
+pub trait MergeRequestDiff {
+    /// Files changed in the merge request along with their patches.
+    fn list_changes(&self, id: i64) -> Result<Vec<FileChange>>;
+}
+
+pub trait MergeRequestReview {
+    /// Post the comments of a review file as a single review.
+    fn submit_review(&self, review: ReviewFile) -> Result<()>;
+}
+
+/// Review the merge request as a whole, as opposed to the inline comments of
+/// [`MergeRequestReview`].
+pub trait ReviewMergeRequest {
+    fn review(&self, args: ReviewBodyArgs) -> Result<()>;
+}
+
+pub trait Label {
+    fn list(&self, args: LabelListBodyArgs) -> Result<Vec<LabelResponse>>;
+    fn num_pages(&self) -> Result<Option<u32>>;
+    fn num_resources(&self) -> Result<Option<NumberDeltaErr>>;
+    fn create(&self, args: LabelBodyArgs) -> Result<LabelResponse>;
+}
+
+/// Version of the remote instance and the features gitar relies on it lacks.
+pub trait RemoteInstance {
+    fn info(&self) -> Result<RemoteInfo>;
+}
+
 pub trait TrendingProjectURL {
     fn list(&self, language: String) -> Result<Vec<TrendingProject>>;
 }
@@ -141,16 +405,31 @@ pub trait TrendingProjectURL {
 /// available. REST APIs don't carry a count, so that is computed by the total
 /// number of pages available (last page in link header) and the number of
 /// resources per page.
+/// When the remote gives the total count, ex. Gitlab `x-total` header or
+/// Github search `total_count`, the number is exact.
 pub struct NumberDeltaErr {
     /// Possible number of resources = num_pages * resources_per_page
     pub num: u32,
     /// Resources per_page
     pub delta: u32,
+    pub exact: bool,
 }
 
 impl NumberDeltaErr {
     pub fn new(num: u32, delta: u32) -> Self {
-        Self { num, delta }
+        Self {
+            num,
+            delta,
+            exact: false,
+        }
+    }
+
+    pub fn exact(num: u32) -> Self {
+        Self {
+            num,
+            delta: 0,
+            exact: true,
+        }
     }
 
     fn compute_interval(&self) -> (u32, u32) {
@@ -163,8 +442,11 @@ impl NumberDeltaErr {
 
 impl Display for NumberDeltaErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.exact {
+            return write!(f, "{} exact", self.num);
+        }
         let (start, end) = self.compute_interval();
-        write!(f, "({}, {})", start, end)
+        write!(f, "({}, {}) estimated", start, end)
     }
 }
 
@@ -187,6 +469,12 @@ pub enum ApiOperation {
     // Gists
     Gist,
     RepositoryTag,
+    Issue,
+    // User identities, the authenticated user and users looked up by
+    // username. Rarely change, so cached for long.
+    User,
+    // Deployments and their environments.
+    Deployment,
 }
 
 impl Display for ApiOperation {
@@ -200,6 +488,9 @@ impl Display for ApiOperation {
             ApiOperation::SinglePage => write!(f, "single_page"),
             ApiOperation::Gist => write!(f, "gist"),
             ApiOperation::RepositoryTag => write!(f, "repository_tag"),
+            ApiOperation::Issue => write!(f, "issue"),
+            ApiOperation::User => write!(f, "user"),
+            ApiOperation::Deployment => write!(f, "deployment"),
         }
     }
 }
@@ -227,6 +518,9 @@ impl FromStr for ApiOperation {
             "single_page" => Ok(ApiOperation::SinglePage),
             "gist" => Ok(ApiOperation::Gist),
             "repository_tag" => Ok(ApiOperation::RepositoryTag),
+            "issue" => Ok(ApiOperation::Issue),
+            "user" => Ok(ApiOperation::User),
+            "deployment" => Ok(ApiOperation::Deployment),
             _ => Err(format!("Unknown ApiOperation: {}", s)),
         }
     }
@@ -255,7 +549,10 @@ impl Iterator for ApiOperationIterator {
             Some(ApiOperation::Release) => Some(ApiOperation::SinglePage),
             Some(ApiOperation::SinglePage) => Some(ApiOperation::Gist),
             Some(ApiOperation::Gist) => Some(ApiOperation::RepositoryTag),
-            Some(ApiOperation::RepositoryTag) => None,
+            Some(ApiOperation::RepositoryTag) => Some(ApiOperation::Issue),
+            Some(ApiOperation::Issue) => Some(ApiOperation::User),
+            Some(ApiOperation::User) => Some(ApiOperation::Deployment),
+            Some(ApiOperation::Deployment) => None,
         };
         self.current = next.clone();
         next
@@ -288,20 +585,29 @@ mod tests {
     #[test]
     fn test_delta_err_display() {
         let delta_err = NumberDeltaErr::new(40, 20);
-        assert_eq!("(21, 40)", delta_err.to_string());
+        assert_eq!("(21, 40) estimated", delta_err.to_string());
     }
 
     #[test]
     fn test_num_less_than_delta_begins_at_one_up_to_delta() {
         let delta_err = NumberDeltaErr::new(25, 30);
-        assert_eq!("(1, 30)", delta_err.to_string());
+        assert_eq!("(1, 30) estimated", delta_err.to_string());
+    }
+
+    #[test]
+    fn test_exact_number_display() {
+        assert_eq!("22 exact", NumberDeltaErr::exact(22).to_string());
+        assert_eq!("0 exact", NumberDeltaErr::exact(0).to_string());
     }
 
     #[test]
     fn test_api_operation_iterator() {
         let operations: Vec<ApiOperation> = ApiOperation::iter().collect();
-        assert_eq!(operations.len(), 8);
+        assert_eq!(operations.len(), 11);
         assert_eq!(operations[0], ApiOperation::MergeRequest);
         assert_eq!(operations[7], ApiOperation::RepositoryTag);
+        assert_eq!(operations[8], ApiOperation::Issue);
+        assert_eq!(operations[9], ApiOperation::User);
+        assert_eq!(operations[10], ApiOperation::Deployment);
     }
 }
@@ -0,0 +1,326 @@
+//! Authentication of API requests. Each provider holds an `AuthStrategy`,
+//! selected with the `auth` setting of the domain in the config file, that
+//! sets the credentials in the headers of every request.
+//!
+//! ```toml
+//! [gitlab_com]
+//! api_token = "<OAUTH_TOKEN>"
+//! auth = "oauth"
+//! ```
+
+pub mod github_app;
+
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::Deserialize;
+
+use crate::api_traits::ApiOperation;
+use crate::cache::NoCache;
+use crate::config::ConfigProperties;
+use crate::http::{Client, Headers};
+use crate::{log_error, Result};
+
+use self::github_app::GithubAppToken;
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+    /// Personal, project or group access tokens in Gitlab. Personal access
+    /// tokens in Github.
+    #[default]
+    Token,
+    /// OAuth 2.0 access tokens.
+    #[serde(rename = "oauth")]
+    OAuth,
+    /// Gitlab CI/CD job tokens, ex. CI_JOB_TOKEN. Gitlab only.
+    JobToken,
+    /// Installation tokens minted from a Github App private key. Github only.
+    GithubApp,
+}
+
+/// Actions of the credential helper protocols of docker and git. Both get,
+/// store and erase credentials for a host.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CredentialHelperAction {
+    Get,
+    Store,
+    Erase,
+}
+
+/// Username and password for clients authenticating with HTTP basic auth
+/// against the remote, ex. docker login or git over HTTPS.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Credentials {
+    pub username: String,
+    pub secret: String,
+}
+
+impl Credentials {
+    fn new(username: &str, secret: &str) -> Self {
+        Credentials {
+            username: username.to_string(),
+            secret: secret.to_string(),
+        }
+    }
+}
+
+pub trait AuthStrategy: Send + Sync {
+    /// Sets the credentials in the headers of a request.
+    fn apply(&self, headers: &mut Headers);
+    fn credentials(&self) -> Result<Credentials>;
+}
+
+/// Gitlab access token sent in the PRIVATE-TOKEN header.
+pub struct PrivateToken(String);
+
+impl AuthStrategy for PrivateToken {
+    fn apply(&self, headers: &mut Headers) {
+        headers.set("PRIVATE-TOKEN", &self.0);
+    }
+
+    fn credentials(&self) -> Result<Credentials> {
+        // Gitlab ignores the username for access tokens.
+        Ok(Credentials::new("oauth2", &self.0))
+    }
+}
+
+/// Token sent in the Authorization header, ex. OAuth tokens or Github
+/// personal access tokens.
+pub struct BearerToken {
+    scheme: &'static str,
+    token: String,
+    /// Username in basic auth, as expected by the remote for this kind of
+    /// token.
+    username: &'static str,
+}
+
+impl AuthStrategy for BearerToken {
+    fn apply(&self, headers: &mut Headers) {
+        headers.set("Authorization", format!("{} {}", self.scheme, self.token));
+    }
+
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(Credentials::new(self.username, &self.token))
+    }
+}
+
+/// Gitlab CI/CD job token sent in the JOB-TOKEN header.
+pub struct JobToken(String);
+
+impl AuthStrategy for JobToken {
+    fn apply(&self, headers: &mut Headers) {
+        headers.set("JOB-TOKEN", &self.0);
+    }
+
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(Credentials::new("gitlab-ci-token", &self.0))
+    }
+}
+
+pub fn gitlab_strategy(config: &dyn ConfigProperties) -> Arc<dyn AuthStrategy> {
+    let token = config.api_token().to_string();
+    match config.auth_method() {
+        AuthMethod::Token => Arc::new(PrivateToken(token)),
+        AuthMethod::OAuth => Arc::new(BearerToken {
+            scheme: "Bearer",
+            token,
+            username: "oauth2",
+        }),
+        AuthMethod::JobToken => Arc::new(JobToken(token)),
+        AuthMethod::GithubApp => {
+            log_error!("Github Apps are a Github feature, sending the token as a private token");
+            Arc::new(PrivateToken(token))
+        }
+    }
+}
+
+/// API operations Gitlab CI/CD job tokens have access to. Gitlab rejects job
+/// tokens in the rest, ex. merge requests or project members.
+pub fn job_token_allows(operation: &ApiOperation) -> bool {
+    matches!(
+        operation,
+        ApiOperation::Pipeline
+            | ApiOperation::ContainerRegistry
+            | ApiOperation::Release
+            | ApiOperation::Deployment
+    )
+}
+
+/// Bitbucket app password sent with HTTP basic auth along with the username
+/// it belongs to.
+pub struct BasicAuth(Credentials);
+
+impl AuthStrategy for BasicAuth {
+    fn apply(&self, headers: &mut Headers) {
+        let user_password = format!("{}:{}", self.0.username, self.0.secret);
+        headers.set(
+            "Authorization",
+            format!("Basic {}", STANDARD.encode(user_password)),
+        );
+    }
+
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Bitbucket app passwords are configured as `username:app_password`.
+/// Anything else is an access token of the repository or workspace.
+pub fn bitbucket_strategy(config: &dyn ConfigProperties) -> Arc<dyn AuthStrategy> {
+    let token = config.api_token();
+    match config.auth_method() {
+        AuthMethod::JobToken | AuthMethod::GithubApp => {
+            log_error!("Bitbucket supports app passwords and access tokens only");
+        }
+        _ => {}
+    }
+    match token.split_once(':') {
+        Some((username, password)) => Arc::new(BasicAuth(Credentials::new(username, password))),
+        None => Arc::new(BearerToken {
+            scheme: "Bearer",
+            token: token.to_string(),
+            username: "x-token-auth",
+        }),
+    }
+}
+
+/// `api_base_url` and `path`, the repository, are needed to mint Github App
+/// installation tokens.
+pub fn github_strategy(
+    config: Arc<dyn ConfigProperties>,
+    api_base_url: &str,
+    path: &str,
+) -> Arc<dyn AuthStrategy> {
+    let token = config.api_token().to_string();
+    match (config.auth_method(), config.github_app()) {
+        (AuthMethod::GithubApp, Some(app)) => {
+            let runner = Arc::new(Client::new(NoCache, config, false));
+            return Arc::new(GithubAppToken::new(app, api_base_url, path, runner));
+        }
+        (AuthMethod::GithubApp, None) => {
+            log_error!("Github App auth requires a github_app section for the domain");
+        }
+        (AuthMethod::JobToken, _) => {
+            log_error!("Job tokens are a Gitlab feature, sending the token as a bearer token");
+        }
+        _ => {}
+    }
+    // Personal access tokens and OAuth tokens are sent the same way.
+    Arc::new(BearerToken {
+        scheme: "bearer",
+        token,
+        username: "x-access-token",
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct AuthConfig(AuthMethod);
+
+    impl ConfigProperties for AuthConfig {
+        fn api_token(&self) -> &str {
+            "1234"
+        }
+
+        fn cache_location(&self) -> Option<&str> {
+            None
+        }
+
+        fn auth_method(&self) -> AuthMethod {
+            self.0
+        }
+    }
+
+    fn headers(strategy: Arc<dyn AuthStrategy>) -> Headers {
+        let mut headers = Headers::new();
+        strategy.apply(&mut headers);
+        headers
+    }
+
+    #[test]
+    fn test_gitlab_auth_headers() {
+        let token = headers(gitlab_strategy(&AuthConfig(AuthMethod::Token)));
+        assert_eq!("1234", token.get("PRIVATE-TOKEN").unwrap());
+        let oauth = headers(gitlab_strategy(&AuthConfig(AuthMethod::OAuth)));
+        assert_eq!("Bearer 1234", oauth.get("Authorization").unwrap());
+        assert!(oauth.get("PRIVATE-TOKEN").is_none());
+        let job_token = headers(gitlab_strategy(&AuthConfig(AuthMethod::JobToken)));
+        assert_eq!("1234", job_token.get("JOB-TOKEN").unwrap());
+    }
+
+    #[test]
+    fn test_basic_auth_credentials() {
+        let credentials = |strategy: Arc<dyn AuthStrategy>| strategy.credentials().unwrap();
+        assert_eq!(
+            Credentials::new("oauth2", "1234"),
+            credentials(gitlab_strategy(&AuthConfig(AuthMethod::Token)))
+        );
+        assert_eq!(
+            Credentials::new("gitlab-ci-token", "1234"),
+            credentials(gitlab_strategy(&AuthConfig(AuthMethod::JobToken)))
+        );
+        let github = github_strategy(
+            Arc::new(AuthConfig(AuthMethod::Token)),
+            "https://api.github.com",
+            "",
+        );
+        assert_eq!(
+            Credentials::new("x-access-token", "1234"),
+            credentials(github)
+        );
+    }
+
+    #[test]
+    fn test_job_token_access() {
+        assert!(job_token_allows(&ApiOperation::Release));
+        assert!(job_token_allows(&ApiOperation::Pipeline));
+        assert!(job_token_allows(&ApiOperation::Deployment));
+        assert!(!job_token_allows(&ApiOperation::MergeRequest));
+        assert!(!job_token_allows(&ApiOperation::Project));
+    }
+
+    struct AppPasswordConfig;
+
+    impl ConfigProperties for AppPasswordConfig {
+        fn api_token(&self) -> &str {
+            "jordilin:app-password"
+        }
+
+        fn cache_location(&self) -> Option<&str> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_bitbucket_auth_headers() {
+        let app_password = bitbucket_strategy(&AppPasswordConfig);
+        assert_eq!(
+            Credentials::new("jordilin", "app-password"),
+            app_password.credentials().unwrap()
+        );
+        assert_eq!(
+            "Basic am9yZGlsaW46YXBwLXBhc3N3b3Jk",
+            headers(app_password).get("Authorization").unwrap()
+        );
+        let token = bitbucket_strategy(&AuthConfig(AuthMethod::Token));
+        assert_eq!(
+            Credentials::new("x-token-auth", "1234"),
+            token.credentials().unwrap()
+        );
+        assert_eq!("Bearer 1234", headers(token).get("Authorization").unwrap());
+    }
+
+    #[test]
+    fn test_github_auth_headers() {
+        for method in [AuthMethod::Token, AuthMethod::OAuth] {
+            let strategy =
+                github_strategy(Arc::new(AuthConfig(method)), "https://api.github.com", "");
+            let headers = headers(strategy);
+            assert_eq!("bearer 1234", headers.get("Authorization").unwrap());
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! Github App authentication. Requests are authenticated with installation
+//! access tokens minted on demand from the App ID and its private key, so
+//! org automation does not depend on a personal access token. Tokens are
+//! cached until they are about to expire.
+//!
+//! ```toml
+//! [github_com]
+//! auth = "github_app"
+//!
+//! [github_com.github_app]
+//! app_id = 123456
+//! private_key_path = "/home/user/.config/gitar/gitar-app.private-key.pem"
+//! # Optional, looked up from the repository otherwise.
+//! installation_id = 7890
+//! ```
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use ring::rand::SystemRandom;
+use ring::signature::{RsaKeyPair, RSA_PKCS1_SHA256};
+use serde::Deserialize;
+
+use crate::api_traits::ApiOperation;
+use crate::auth::{AuthStrategy, Credentials};
+use crate::error::GRError;
+use crate::http::{Headers, Method};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::contract::Contract;
+use crate::remote::query;
+use crate::time::{now_epoch_seconds, Seconds};
+use crate::{log_debug, log_error, Result};
+
+/// JWTs are issued in the past to allow for clock drift with Github.
+const JWT_CLOCK_DRIFT_SECONDS: u64 = 60;
+/// Github rejects JWTs that expire more than 10 minutes in the future.
+const JWT_EXPIRATION_SECONDS: u64 = 540;
+/// Installation tokens are renewed this long before they expire, so that
+/// requests in flight do not fail.
+const TOKEN_RENEWAL_MARGIN_SECONDS: u64 = 60;
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct GithubAppConfig {
+    pub app_id: i64,
+    pub private_key_path: String,
+    pub installation_id: Option<i64>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct GithubInstallation {
+    id: i64,
+}
+
+impl Contract for GithubInstallation {}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct GithubInstallationToken {
+    token: String,
+    expires_at: String,
+}
+
+impl Contract for GithubInstallationToken {}
+
+struct InstallationToken {
+    token: String,
+    /// Epoch seconds.
+    expires_at: u64,
+}
+
+impl From<GithubInstallationToken> for InstallationToken {
+    fn from(dto: GithubInstallationToken) -> Self {
+        // Unparseable expiration dates make the token be renewed on the next
+        // request.
+        let expires_at = chrono::DateTime::parse_from_rfc3339(&dto.expires_at)
+            .map(|date| date.timestamp().max(0) as u64)
+            .unwrap_or_default();
+        InstallationToken {
+            token: dto.token,
+            expires_at,
+        }
+    }
+}
+
+/// Installation access token of a Github App sent in the Authorization
+/// header.
+pub struct GithubAppToken<R> {
+    app: GithubAppConfig,
+    api_base_url: String,
+    path: String,
+    runner: Arc<R>,
+    now: fn() -> Seconds,
+    token: Mutex<Option<InstallationToken>>,
+}
+
+impl<R> GithubAppToken<R> {
+    /// `path` is the repository, ex. jordilin/gitar, used to find the App
+    /// installation when it is not configured.
+    pub fn new(app: GithubAppConfig, api_base_url: &str, path: &str, runner: Arc<R>) -> Self {
+        GithubAppToken {
+            app,
+            api_base_url: api_base_url.to_string(),
+            path: path.to_string(),
+            runner,
+            now: now_epoch_seconds,
+            token: Mutex::new(None),
+        }
+    }
+
+    pub fn with_now(mut self, now: fn() -> Seconds) -> Self {
+        self.now = now;
+        self
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> GithubAppToken<R> {
+    fn token(&self) -> Result<String> {
+        let mut cached = self.token.lock().unwrap();
+        let now = (self.now)();
+        if let Some(token) = cached.as_ref() {
+            if *now + TOKEN_RENEWAL_MARGIN_SECONDS < token.expires_at {
+                return Ok(token.token.clone());
+            }
+        }
+        let token = self.mint(now)?;
+        let value = token.token.clone();
+        *cached = Some(token);
+        Ok(value)
+    }
+
+    fn mint(&self, now: Seconds) -> Result<InstallationToken> {
+        let mut headers = Headers::new();
+        headers.set("Authorization", format!("Bearer {}", jwt(&self.app, now)?));
+        headers.set("Accept", "application/vnd.github.v3+json");
+        headers.set("User-Agent", "gitar");
+        headers.set("X-GitHub-Api-Version", "2022-11-28");
+        let installation_id = match self.app.installation_id {
+            Some(id) => id,
+            None => {
+                let url = format!("{}/repos/{}/installation", self.api_base_url, self.path);
+                let installation = query::get_as::<_, (), GithubInstallation, GithubInstallation>(
+                    &self.runner,
+                    &url,
+                    None,
+                    headers.clone(),
+                    ApiOperation::Project,
+                )?;
+                installation.id
+            }
+        };
+        log_debug!(
+            "Minting installation token for Github App {} installation {}",
+            self.app.app_id,
+            installation_id
+        );
+        let url = format!(
+            "{}/app/installations/{}/access_tokens",
+            self.api_base_url, installation_id
+        );
+        query::send_as::<_, (), GithubInstallationToken, InstallationToken>(
+            &self.runner,
+            &url,
+            None,
+            headers,
+            ApiOperation::Project,
+            Method::POST,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse> + Send + Sync> AuthStrategy for GithubAppToken<R> {
+    fn apply(&self, headers: &mut Headers) {
+        match self.token() {
+            Ok(token) => headers.set("Authorization", format!("bearer {}", token)),
+            Err(err) => log_error!(
+                "Could not get an installation token for Github App {}: {}",
+                self.app.app_id,
+                err
+            ),
+        }
+    }
+
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(Credentials::new("x-access-token", &self.token()?))
+    }
+}
+
+/// JSON Web Token signed with the App private key, used to authenticate as
+/// the App itself.
+fn jwt(app: &GithubAppConfig, now: Seconds) -> Result<String> {
+    let pem = fs::read_to_string(&app.private_key_path).map_err(|err| {
+        GRError::PreconditionNotMet(format!(
+            "Cannot read Github App private key {}: {}",
+            app.private_key_path, err
+        ))
+    })?;
+    let key = signing_key(&pem)?;
+    let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256","typ":"JWT"}"#);
+    let claims = serde_json::json!({
+        "iat": *now - JWT_CLOCK_DRIFT_SECONDS,
+        "exp": *now + JWT_EXPIRATION_SECONDS,
+        "iss": app.app_id,
+    });
+    let message = format!("{}.{}", header, URL_SAFE_NO_PAD.encode(claims.to_string()));
+    let mut signature = vec![0; key.public().modulus_len()];
+    key.sign(
+        &RSA_PKCS1_SHA256,
+        &SystemRandom::new(),
+        message.as_bytes(),
+        &mut signature,
+    )
+    .map_err(|_| GRError::PreconditionNotMet("Cannot sign Github App JWT".to_string()))?;
+    Ok(format!("{}.{}", message, URL_SAFE_NO_PAD.encode(signature)))
+}
+
+/// Github generates PKCS#1 private keys. PKCS#8 keys, as converted by some
+/// secret managers, are accepted too.
+fn signing_key(pem: &str) -> Result<RsaKeyPair> {
+    let encoded = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<String>();
+    let der = STANDARD.decode(encoded.trim()).map_err(|err| {
+        GRError::PreconditionNotMet(format!("Invalid Github App private key: {}", err))
+    })?;
+    let key = if pem.contains("BEGIN RSA PRIVATE KEY") {
+        RsaKeyPair::from_der(&der)
+    } else {
+        RsaKeyPair::from_pkcs8(&der)
+    };
+    key.map_err(|err| {
+        GRError::PreconditionNotMet(format!("Invalid Github App private key: {}", err)).into()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use ring::signature::{UnparsedPublicKey, RSA_PKCS1_2048_8192_SHA256};
+
+    use crate::test::utils::MockRunner;
+
+    use super::*;
+
+    const PRIVATE_KEY_PATH: &str = "tests/fixtures/github_app_private_key.pem";
+
+    fn app(installation_id: Option<i64>) -> GithubAppConfig {
+        GithubAppConfig {
+            app_id: 1234,
+            private_key_path: PRIVATE_KEY_PATH.to_string(),
+            installation_id,
+        }
+    }
+
+    fn now() -> Seconds {
+        // 2024-01-01T00:00:00Z
+        Seconds::new(1704067200)
+    }
+
+    fn response(status: i32, body: &str) -> HttpResponse {
+        HttpResponse::builder()
+            .status(status)
+            .body(body.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn token_response(token: &str) -> HttpResponse {
+        let body = format!(
+            r#"{{"token": "{}", "expires_at": "2024-01-01T01:00:00Z"}}"#,
+            token
+        );
+        response(201, &body)
+    }
+
+    #[test]
+    fn test_jwt_is_signed_with_app_private_key() {
+        let jwt = jwt(&app(None), now()).unwrap();
+        let (message, signature) = jwt.rsplit_once('.').unwrap();
+        let (_, claims) = message.split_once('.').unwrap();
+        let claims: serde_json::Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).unwrap()).unwrap();
+        assert_eq!(1234, claims["iss"]);
+        assert_eq!(1704067140, claims["iat"]);
+        assert_eq!(1704067740, claims["exp"]);
+        let pem = fs::read_to_string(PRIVATE_KEY_PATH).unwrap();
+        let key = signing_key(&pem).unwrap();
+        let public_key = UnparsedPublicKey::new(&RSA_PKCS1_2048_8192_SHA256, key.public());
+        assert!(public_key
+            .verify(
+                message.as_bytes(),
+                &URL_SAFE_NO_PAD.decode(signature).unwrap()
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_missing_private_key_is_error() {
+        let mut app = app(None);
+        app.private_key_path = "/nonexistent/key.pem".to_string();
+        assert!(jwt(&app, now()).is_err());
+    }
+
+    #[test]
+    fn test_mint_looks_up_installation_from_repository() {
+        // Responses are returned last to first.
+        let runner = Arc::new(MockRunner::new(vec![
+            token_response("ghs_1234"),
+            response(200, r#"{"id": 5678}"#),
+        ]));
+        let auth = GithubAppToken::new(
+            app(None),
+            "https://api.github.com",
+            "jordilin/gitar",
+            runner.clone(),
+        )
+        .with_now(now);
+        assert_eq!("ghs_1234", auth.token().unwrap());
+        assert_eq!(
+            "https://api.github.com/app/installations/5678/access_tokens",
+            *runner.url()
+        );
+        assert_eq!(Method::POST, *runner.http_method.borrow().last().unwrap());
+        assert!(runner
+            .headers()
+            .get("Authorization")
+            .unwrap()
+            .starts_with("Bearer ey"));
+    }
+
+    #[test]
+    fn test_installation_token_is_cached_until_expiry() {
+        fn later() -> Seconds {
+            // 2024-01-01T00:59:30Z, within the renewal margin
+            Seconds::new(1704070770)
+        }
+        let runner = Arc::new(MockRunner::new(vec![
+            token_response("ghs_renewed"),
+            token_response("ghs_1234"),
+        ]));
+        let auth = GithubAppToken::new(
+            app(Some(5678)),
+            "https://api.github.com",
+            "jordilin/gitar",
+            runner.clone(),
+        )
+        .with_now(now);
+        assert_eq!("ghs_1234", auth.token().unwrap());
+        assert_eq!("ghs_1234", auth.token().unwrap());
+        assert_eq!(1, runner.http_method.borrow().len());
+        let auth = auth.with_now(later);
+        assert_eq!("ghs_renewed", auth.token().unwrap());
+        assert_eq!(2, runner.http_method.borrow().len());
+    }
+}
@@ -61,6 +61,7 @@ impl<R: HttpRunner<Response = HttpResponse>> Backoff<'_, R> {
                     self.max_retries
                 );
             }
+            request.set_retry_pending(self.num_retries < self.max_retries);
             match self.runner.run(request) {
                 Ok(response) => return Ok(response),
                 Err(err) => {
@@ -122,6 +123,69 @@ impl<R: HttpRunner<Response = HttpResponse>> Backoff<'_, R> {
     }
 }
 
+/// RetryOperation retries a whole operation when it fails with an error the
+/// caller considers transient. As opposed to `Backoff`, which retries HTTP
+/// requests on transport and rate limit errors, the remote did answer, but
+/// it is not ready to fulfill the operation yet.
+pub struct RetryOperation {
+    max_retries: u32,
+    base_wait: Seconds,
+    strategy: Box<dyn BackOffStrategy>,
+    throttler: Box<dyn ThrottleStrategy>,
+}
+
+impl RetryOperation {
+    pub fn new(
+        max_retries: u32,
+        base_wait: Seconds,
+        strategy: Box<dyn BackOffStrategy>,
+        throttler: Box<dyn ThrottleStrategy>,
+    ) -> Self {
+        RetryOperation {
+            max_retries,
+            base_wait,
+            strategy,
+            throttler,
+        }
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Runs `operation` until it succeeds, it fails with an error that is not
+    /// retryable or max retries are reached. `on_retry` is called with the
+    /// retry number and the wait time before each retry.
+    pub fn run<T>(
+        &self,
+        is_retryable: impl Fn(&anyhow::Error) -> bool,
+        mut on_retry: impl FnMut(u32, Seconds) -> Result<()>,
+        mut operation: impl FnMut() -> Result<T>,
+    ) -> Result<T> {
+        let mut num_retries = 0;
+        loop {
+            let err = match operation() {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            if !is_retryable(&err) || self.max_retries == 0 {
+                return Err(err);
+            }
+            if num_retries == self.max_retries {
+                return Err(GRError::ExponentialBackoffMaxRetriesReached(format!(
+                    "Retried the operation {} times",
+                    self.max_retries
+                )))
+                .err_context(err);
+            }
+            num_retries += 1;
+            let wait_time = self.strategy.wait_time(self.base_wait, num_retries);
+            on_retry(num_retries, wait_time)?;
+            self.throttler.throttle_for(wait_time.into());
+        }
+    }
+}
+
 pub trait BackOffStrategy {
     fn wait_time(&self, base_wait: Seconds, num_retries: u32) -> Seconds;
 }
@@ -246,6 +310,42 @@ mod tests {
         }
     }
 
+    struct TransportErrorRunner {
+        retry_pending: std::sync::Mutex<Vec<bool>>,
+    }
+
+    impl HttpRunner for TransportErrorRunner {
+        type Response = HttpResponse;
+
+        fn run<T: Serialize>(&self, cmd: &mut Request<T>) -> Result<Self::Response> {
+            self.retry_pending.lock().unwrap().push(cmd.retry_pending());
+            Err(GRError::HttpTransportError("connection refused".to_string()).into())
+        }
+
+        fn api_max_pages<T: Serialize>(&self, _cmd: &Request<T>) -> u32 {
+            1
+        }
+    }
+
+    #[test]
+    fn test_only_last_attempt_has_no_retry_pending() {
+        let client = Arc::new(TransportErrorRunner {
+            retry_pending: std::sync::Mutex::new(Vec::new()),
+        });
+        let mut request: Request<()> = Request::builder()
+            .resource(Resource::new("http://localhost", None))
+            .method(http::Method::GET)
+            .build()
+            .unwrap();
+        let strategy = Box::new(Exponential);
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(Rc::new(MockThrottler::new(None)));
+        let mut backoff = Backoff::new(&client, 7, 60, now_mock, strategy, throttler);
+        assert!(backoff.retry_on_error(&mut request).is_err());
+        let mut expected = vec![true; 7];
+        expected.push(false);
+        assert_eq!(expected, *client.retry_pending.lock().unwrap());
+    }
+
     #[test]
     fn test_if_max_retries_is_zero_tries_once() {
         let responses = vec![response_ok()];
@@ -472,4 +572,88 @@ mod tests {
             },
         }
     }
+
+    fn not_mergeable() -> anyhow::Error {
+        GRError::RemoteConflict("405".to_string()).into()
+    }
+
+    fn is_conflict(err: &anyhow::Error) -> bool {
+        matches!(
+            err.downcast_ref::<GRError>(),
+            Some(GRError::RemoteConflict(_))
+        )
+    }
+
+    #[test]
+    fn test_retry_operation_retries_until_success() {
+        let throttler = Rc::new(MockThrottler::new(None));
+        let retry = RetryOperation::new(
+            3,
+            Seconds::new(5),
+            Box::new(Exponential),
+            Box::new(Rc::clone(&throttler)),
+        );
+        let mut attempts = 0;
+        let mut retries = Vec::new();
+        let result = retry.run(
+            is_conflict,
+            |num_retry, wait| {
+                retries.push((num_retry, wait));
+                Ok(())
+            },
+            || {
+                attempts += 1;
+                if attempts < 3 {
+                    return Err(not_mergeable());
+                }
+                Ok(attempts)
+            },
+        );
+        assert_eq!(3, result.unwrap());
+        assert_eq!(vec![(1, Seconds::new(7)), (2, Seconds::new(9))], retries);
+        assert_eq!(2, *throttler.throttled());
+        assert_eq!(
+            Milliseconds::new(16000),
+            *throttler.milliseconds_throttled()
+        );
+    }
+
+    #[test]
+    fn test_retry_operation_max_retries_reached() {
+        let throttler = Rc::new(MockThrottler::new(None));
+        let retry = RetryOperation::new(
+            2,
+            Seconds::new(5),
+            Box::new(Exponential),
+            Box::new(Rc::clone(&throttler)),
+        );
+        let result: Result<()> = retry.run(is_conflict, |_, _| Ok(()), || Err(not_mergeable()));
+        match result {
+            Err(err) => match err.downcast_ref::<GRError>() {
+                Some(GRError::ExponentialBackoffMaxRetriesReached(_)) => {
+                    assert_eq!(2, *throttler.throttled())
+                }
+                _ => panic!("Expected max retries reached error"),
+            },
+            Ok(_) => panic!("Expected error"),
+        }
+    }
+
+    #[test]
+    fn test_retry_operation_does_not_retry_other_errors() {
+        let throttler = Rc::new(MockThrottler::new(None));
+        let retry = RetryOperation::new(
+            3,
+            Seconds::new(5),
+            Box::new(Exponential),
+            Box::new(Rc::clone(&throttler)),
+        );
+        let result: Result<()> = retry.run(
+            is_conflict,
+            |_, _| Ok(()),
+            || Err(GRError::RemoteServerError("500".to_string()).into()),
+        );
+        assert!(result.is_err());
+        assert_eq!(0, *throttler.throttled());
+    }
 }
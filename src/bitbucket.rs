@@ -0,0 +1,218 @@
+use crate::api_traits::{ApiOperation, NumberDeltaErr, RemoteCredentials};
+use crate::auth::{self, AuthStrategy, Credentials};
+use crate::config::ConfigProperties;
+use crate::http::{self, Headers};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::contract::Contract;
+use crate::remote::{query, ListBodyArgs};
+use crate::Result;
+use std::sync::Arc;
+
+pub mod cicd;
+pub mod merge_request;
+pub mod project;
+pub mod user;
+
+/// Bitbucket Cloud caps the page length of most endpoints at 50 resources,
+/// also used as default as the API defaults to 10.
+const MAX_PAGE_LEN: u32 = 50;
+
+#[derive(Clone)]
+pub struct Bitbucket<R> {
+    auth: Arc<dyn AuthStrategy>,
+    domain: String,
+    path: String,
+    rest_api_basepath: String,
+    runner: Arc<R>,
+}
+
+impl<R> Bitbucket<R> {
+    pub fn new(
+        config: Arc<dyn ConfigProperties>,
+        domain: &str,
+        path: &str,
+        runner: Arc<R>,
+    ) -> Self {
+        let rest_api_basepath = match config.api_base_url() {
+            Some(url) => url.to_string(),
+            None => format!("https://api.{}/2.0", domain),
+        };
+        let auth = auth::bitbucket_strategy(config.as_ref());
+        Bitbucket {
+            auth,
+            domain: domain.to_string(),
+            path: path.to_string(),
+            rest_api_basepath,
+            runner,
+        }
+    }
+
+    fn request_headers(&self) -> Headers {
+        let mut headers = Headers::new();
+        self.auth.apply(&mut headers);
+        headers.set("Accept", "application/json");
+        headers.set("User-Agent", "gitar");
+        headers
+    }
+
+    /// API URL of the repository, `path` being `workspace/repo_slug`.
+    fn repo_url(&self) -> String {
+        format!("{}/repositories/{}", self.rest_api_basepath, self.path)
+    }
+
+    fn workspace(&self) -> &str {
+        self.path.split('/').next().unwrap_or_default()
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Bitbucket<R> {
+    /// Bitbucket paginates in the body of the response instead of the Link
+    /// header. `values` holds the resources of the page and `next` the URL of
+    /// the following one.
+    fn paged<D: Contract, T>(
+        &self,
+        url: &str,
+        list_args: Option<ListBodyArgs>,
+        operation: ApiOperation,
+        mapper: impl Fn(D) -> T,
+    ) -> Result<Vec<T>> {
+        let per_page = list_args
+            .as_ref()
+            .and_then(|list_args| list_args.per_page)
+            .or_else(|| self.runner.api_per_page())
+            .map_or(MAX_PAGE_LEN, |per_page| per_page.min(MAX_PAGE_LEN));
+        let mut page_url = with_param(url, "pagelen", Some(per_page));
+        let request = http::Request::<()>::new(&page_url, http::Method::GET)
+            .with_api_operation(operation.clone());
+        let mut max_pages = self.runner.api_max_pages(&request) as i64;
+        if let Some(list_args) = &list_args {
+            if let Some(page) = list_args.page {
+                page_url = with_param(&page_url, "page", Some(page));
+                max_pages = list_args.max_pages.unwrap_or(1);
+            }
+        }
+        let mut resources = Vec::new();
+        let mut num_pages = 0;
+        loop {
+            let page = query::get_json::<_, ()>(
+                &self.runner,
+                &page_url,
+                None,
+                self.request_headers(),
+                operation.clone(),
+            )?;
+            for value in page["values"].as_array().into_iter().flatten() {
+                resources.push(mapper(query::decode::<_, D, D>(&self.runner, value)?));
+            }
+            num_pages += 1;
+            match page["next"].as_str() {
+                Some(next) if num_pages < max_pages => page_url = next.to_string(),
+                _ => break,
+            }
+        }
+        Ok(resources)
+    }
+
+    /// Total number of resources and page length, as reported in the first
+    /// page. Not all endpoints report the total.
+    fn page_size(&self, url: &str, operation: ApiOperation) -> Result<Option<(u32, u32)>> {
+        let page =
+            query::get_json::<_, ()>(&self.runner, url, None, self.request_headers(), operation)?;
+        match (page["size"].as_u64(), page["pagelen"].as_u64()) {
+            (Some(size), Some(pagelen)) if pagelen > 0 => Ok(Some((size as u32, pagelen as u32))),
+            _ => Ok(None),
+        }
+    }
+
+    fn num_pages(&self, url: &str, operation: ApiOperation) -> Result<Option<u32>> {
+        Ok(self
+            .page_size(url, operation)?
+            .map(|(size, pagelen)| size.div_ceil(pagelen).max(1)))
+    }
+
+    fn num_resources(&self, url: &str, operation: ApiOperation) -> Result<Option<NumberDeltaErr>> {
+        Ok(self
+            .page_size(url, operation)?
+            .map(|(size, _)| NumberDeltaErr::exact(size)))
+    }
+}
+
+fn with_param(url: &str, name: &str, value: Option<impl ToString>) -> String {
+    match value {
+        Some(value) => {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            format!("{}{}{}={}", url, separator, name, value.to_string())
+        }
+        None => url.to_string(),
+    }
+}
+
+impl<R> RemoteCredentials for Bitbucket<R> {
+    fn credentials(&self) -> Result<Credentials> {
+        self.auth.credentials()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::io::HttpResponse;
+    use crate::setup_client;
+    use crate::test::utils::{get_contract, ContractType, ResponseContracts};
+
+    use super::*;
+
+    /// Paginated response holding the given contract.
+    pub fn page(contract: &str, next: Option<&str>) -> String {
+        serde_json::json!({
+            "size": 21,
+            "page": 1,
+            "pagelen": 10,
+            "next": next,
+            "values": [serde_json::from_str::<serde_json::Value>(
+                &get_contract(ContractType::Bitbucket, contract)
+            )
+            .unwrap()],
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_paged_follows_next_page_in_body() {
+        let next =
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pullrequests?page=2";
+        let contracts = ResponseContracts::new(ContractType::Bitbucket)
+            .add_body(200, Some(page("pull_request.json", None)), None)
+            .add_body(200, Some(page("pull_request.json", Some(next))), None);
+        let (config, client) = setup_client!(@runner contracts);
+        let bitbucket = Bitbucket::new(config, "bitbucket.org", "jordilin/bitbapi", client.clone());
+        let url = format!("{}/pullrequests", bitbucket.repo_url());
+        let ids = bitbucket
+            .paged(
+                &url,
+                None,
+                ApiOperation::MergeRequest,
+                |pr: merge_request::BitbucketPullRequest| pr.id,
+            )
+            .unwrap();
+        assert_eq!(vec![7, 7], ids);
+        assert_eq!(next, *client.url());
+    }
+
+    #[test]
+    fn test_num_pages_from_size_and_page_length() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_body(
+            200,
+            Some(page("pull_request.json", None)),
+            None,
+        );
+        let (config, client) = setup_client!(@runner contracts);
+        let bitbucket = Bitbucket::new(config, "bitbucket.org", "jordilin/bitbapi", client);
+        let url = format!("{}/pullrequests", bitbucket.repo_url());
+        assert_eq!(
+            Some(3),
+            bitbucket
+                .num_pages(&url, ApiOperation::MergeRequest)
+                .unwrap()
+        );
+    }
+}
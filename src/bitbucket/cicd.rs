@@ -0,0 +1,207 @@
+use super::Bitbucket;
+use crate::api_traits::{ApiOperation, Cicd, NumberDeltaErr};
+use crate::cmds::cicd::{LintResponse, Pipeline, PipelineBodyArgs, YamlBytes};
+use crate::error::GRError;
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::contract::Contract;
+use crate::remote::query;
+use crate::time;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+impl<R> Bitbucket<R> {
+    fn pipelines_url(&self) -> String {
+        format!("{}/pipelines?sort=-created_on", self.repo_url())
+    }
+
+    fn pipeline_web_url(&self, build_number: i64) -> String {
+        format!(
+            "https://{}/{}/pipelines/results/{}",
+            self.domain, self.path, build_number
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Cicd for Bitbucket<R> {
+    fn list(&self, args: PipelineBodyArgs) -> Result<Vec<Pipeline>> {
+        // https://developer.atlassian.com/cloud/bitbucket/rest/api-group-pipelines/#api-repositories-workspace-repo-slug-pipelines-get
+        self.paged(
+            &self.pipelines_url(),
+            args.from_to_page,
+            ApiOperation::Pipeline,
+            |pipeline: BitbucketPipeline| self.pipeline(pipeline),
+        )
+    }
+
+    fn get_pipeline(&self, id: i64) -> Result<Pipeline> {
+        // Pipelines are addressed by UUID or by build number.
+        let url = format!("{}/pipelines/{}", self.repo_url(), id);
+        let pipeline = query::get_as::<_, (), BitbucketPipeline, BitbucketPipeline>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        Ok(self.pipeline(pipeline))
+    }
+
+    fn num_pages(&self) -> Result<Option<u32>> {
+        Bitbucket::num_pages(self, &self.pipelines_url(), ApiOperation::Pipeline)
+    }
+
+    fn num_resources(&self) -> Result<Option<NumberDeltaErr>> {
+        Bitbucket::num_resources(self, &self.pipelines_url(), ApiOperation::Pipeline)
+    }
+
+    fn lint(&self, _body: YamlBytes) -> Result<LintResponse> {
+        Err(GRError::OperationNotSupported(
+            "Bitbucket has no API to lint bitbucket-pipelines.yml".to_string(),
+        )
+        .into())
+    }
+}
+
+impl<R> Bitbucket<R> {
+    fn pipeline(&self, data: BitbucketPipeline) -> Pipeline {
+        // Running pipelines have no completion date yet.
+        let updated_at = data.completed_on.unwrap_or(data.created_on);
+        Pipeline::builder()
+            .id(data.build_number)
+            .status(data.state.status().to_string())
+            .web_url(self.pipeline_web_url(data.build_number))
+            .branch(data.target.ref_name)
+            .sha(data.target.commit.hash)
+            .created_at(data.created_on)
+            .updated_at(updated_at)
+            .duration(data.duration_in_seconds)
+            .build()
+            .unwrap()
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketPipeline {
+    build_number: i64,
+    state: BitbucketPipelineState,
+    target: BitbucketPipelineTarget,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_on: DateTime<Utc>,
+    #[serde(deserialize_with = "time::deserialize_optional_timestamp")]
+    completed_on: Option<DateTime<Utc>>,
+    duration_in_seconds: u64,
+}
+
+impl Contract for BitbucketPipeline {
+    const OPTIONAL_FIELDS: &'static [&'static str] = &["completed_on"];
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketPipelineState {
+    name: String,
+    result: Option<BitbucketPipelineResult>,
+}
+
+impl BitbucketPipelineState {
+    /// Status in the same terms as Gitlab pipelines. Finished pipelines
+    /// carry the outcome in `result`.
+    fn status(&self) -> &str {
+        let name = match &self.result {
+            Some(result) => result.name.as_str(),
+            None => self.name.as_str(),
+        };
+        match name {
+            "SUCCESSFUL" => "success",
+            "FAILED" | "ERROR" => "failed",
+            "STOPPED" => "canceled",
+            "IN_PROGRESS" | "RUNNING" => "running",
+            "PENDING" | "PAUSED" => "pending",
+            _ => "unknown",
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketPipelineResult {
+    name: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketPipelineTarget {
+    ref_name: String,
+    commit: BitbucketPipelineCommit,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketPipelineCommit {
+    hash: String,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bitbucket::test::page;
+    use crate::io::HttpResponse;
+    use crate::setup_client;
+    use crate::test::utils::{ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_list_pipelines() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_body(
+            200,
+            Some(page("pipeline.json", None)),
+            None,
+        );
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn Cicd);
+        let args = PipelineBodyArgs::builder()
+            .from_to_page(None)
+            .build()
+            .unwrap();
+        let pipelines = bitbucket.list(args).unwrap();
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pipelines\
+             ?sort=-created_on&pagelen=50",
+            *client.url()
+        );
+        assert_eq!(1, pipelines.len());
+        assert_eq!("success", pipelines[0].status);
+    }
+
+    #[test]
+    fn test_get_pipeline_by_build_number() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_contract(
+            200,
+            "pipeline.json",
+            None,
+        );
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn Cicd);
+        let pipeline = bitbucket.get_pipeline(42).unwrap();
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pipelines/42",
+            *client.url()
+        );
+        assert_eq!("success", pipeline.status);
+    }
+
+    #[test]
+    fn test_running_pipeline_status() {
+        let state: BitbucketPipelineState =
+            serde_json::from_str(r#"{"name": "IN_PROGRESS", "stage": {"name": "RUNNING"}}"#)
+                .unwrap();
+        assert_eq!("running", state.status());
+    }
+
+    #[test]
+    fn test_lint_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket);
+        let (_, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn Cicd);
+        assert!(bitbucket.lint(YamlBytes::new(b"")).is_err());
+    }
+}
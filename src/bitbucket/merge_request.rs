@@ -0,0 +1,761 @@
+use super::Bitbucket;
+use crate::{
+    api_traits::{ApiOperation, CommentMergeRequest, MergeRequest, NumberDeltaErr, RemoteProject},
+    cli::browse::BrowseOptions,
+    cmds::{
+        merge_request::{
+            Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs, MergeMethod,
+            MergeRequestBodyArgs, MergeRequestListBodyArgs, MergeRequestMergeBodyArgs,
+            MergeRequestResponse, MergeRequestState,
+        },
+        project::MrMemberType,
+    },
+    error::{self, GRError},
+    http::{self, Body},
+    io::{HttpResponse, HttpRunner},
+    remote::{contract::Contract, encode_query_param, query},
+    time, Result,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+impl<R: HttpRunner<Response = HttpResponse>> Bitbucket<R> {
+    fn pull_requests_url(&self, args: &MergeRequestListBodyArgs) -> Result<String> {
+        if args.assignee.is_some() || args.author.is_some() || args.reviewer.is_some() {
+            return Err(GRError::OperationNotSupported(
+                "Filtering pull requests by user is not supported in Bitbucket".to_string(),
+            )
+            .into());
+        }
+        if args.group.is_some() || !args.labels.is_empty() || args.milestone.is_some() {
+            return Err(GRError::OperationNotSupported(
+                "Bitbucket pull requests have no workspace listing, labels nor milestones"
+                    .to_string(),
+            )
+            .into());
+        }
+        let state = match args.state {
+            MergeRequestState::Opened => "OPEN",
+            MergeRequestState::Closed => "DECLINED",
+            MergeRequestState::Merged => "MERGED",
+        };
+        Ok(format!("{}/pullrequests?state={}", self.repo_url(), state))
+    }
+
+    fn pull_request_url(&self, id: i64) -> String {
+        format!("{}/pullrequests/{}", self.repo_url(), id)
+    }
+
+    /// Open pull request from the given source branch, if any.
+    fn existing_pull_request(&self, source_branch: &str) -> Result<Option<BitbucketPullRequest>> {
+        let query = format!(
+            "source.branch.name=\"{}\" AND state=\"OPEN\"",
+            source_branch
+        );
+        let url = format!(
+            "{}/pullrequests?q={}",
+            self.repo_url(),
+            encode_query_param(&query)
+        );
+        let page = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        page["values"]
+            .as_array()
+            .and_then(|values| values.first())
+            .map(|value| query::decode::<_, BitbucketPullRequest, _>(&self.runner, value))
+            .transpose()
+    }
+
+    /// Reviewers are referenced by account ID, resolved from the nicknames
+    /// of the members of the workspace.
+    fn reviewer_account_ids(&self, args: &MergeRequestBodyArgs) -> Result<Vec<String>> {
+        let nicknames = args
+            .reviewers
+            .iter()
+            .filter(|reviewer| reviewer.mr_member_type == MrMemberType::Filled)
+            .map(|reviewer| reviewer.username.as_str())
+            .collect::<Vec<_>>();
+        if nicknames.is_empty() {
+            return Ok(Vec::new());
+        }
+        let members = self.paged(
+            &self.workspace_members_url(),
+            None,
+            ApiOperation::Project,
+            |member: BitbucketWorkspaceMember| member.user,
+        )?;
+        let mut account_ids = Vec::new();
+        for nickname in nicknames {
+            match members.iter().find(|user| user.nickname == nickname) {
+                Some(user) => account_ids.push(user.account_id.clone()),
+                None => eprintln!(
+                    "Warning: reviewer {} is not a member of workspace {}",
+                    nickname,
+                    self.workspace()
+                ),
+            }
+        }
+        Ok(account_ids)
+    }
+
+    pub(super) fn workspace_members_url(&self) -> String {
+        format!(
+            "{}/workspaces/{}/members",
+            self.rest_api_basepath,
+            self.workspace()
+        )
+    }
+
+    fn send_pull_request(
+        &self,
+        url: &str,
+        body: &Body<serde_json::Value>,
+        method: http::Method,
+    ) -> Result<MergeRequestResponse> {
+        query::send_as::<_, _, BitbucketPullRequest, _>(
+            &self.runner,
+            url,
+            Some(body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            method,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Bitbucket<R> {
+    fn open(&self, args: MergeRequestBodyArgs) -> Result<MergeRequestResponse> {
+        // https://developer.atlassian.com/cloud/bitbucket/rest/api-group-pullrequests/#api-repositories-workspace-repo-slug-pullrequests-post
+        if args.assignee.mr_member_type == MrMemberType::Filled {
+            eprintln!("Warning: Bitbucket pull requests have no assignee, ignoring it");
+        }
+        if !args.labels.is_empty() {
+            eprintln!("Warning: Bitbucket pull requests have no labels, ignoring them");
+        }
+        let mut source = serde_json::json!({ "branch": { "name": args.source_branch } });
+        // Pull requests from a fork are opened in the target repository.
+        let url = if args.target_repo.is_empty() {
+            format!("{}/pullrequests", self.repo_url())
+        } else {
+            source["repository"] = serde_json::json!({ "full_name": self.path });
+            format!(
+                "{}/repositories/{}/pullrequests",
+                self.rest_api_basepath, args.target_repo
+            )
+        };
+        let reviewers = self
+            .reviewer_account_ids(&args)?
+            .into_iter()
+            .map(|account_id| serde_json::json!({ "account_id": account_id }))
+            .collect::<Vec<_>>();
+        let mut body = Body::new();
+        body.add("title", serde_json::json!(args.title));
+        body.add("description", serde_json::json!(args.description));
+        body.add("source", source);
+        body.add(
+            "destination",
+            serde_json::json!({ "branch": { "name": args.target_branch } }),
+        );
+        body.add(
+            "close_source_branch",
+            serde_json::json!(args.remove_source_branch == "true"),
+        );
+        body.add("draft", serde_json::json!(args.draft));
+        body.add("reviewers", serde_json::json!(reviewers));
+        let response = query::send_raw_unchecked(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        if response.status == 201 {
+            let pull_request = crate::json_loads(&response.body)?;
+            return query::decode::<_, BitbucketPullRequest, _>(&self.runner, &pull_request);
+        }
+        // Bitbucket rejects a second open pull request for the same branches.
+        let Some(existing) = self.existing_pull_request(&args.source_branch)? else {
+            return Err(query::request_error(
+                &http::Method::POST,
+                &url,
+                &ApiOperation::MergeRequest,
+                &response,
+            ));
+        };
+        if args.amend {
+            let mut body = Body::new();
+            body.add("title", serde_json::json!(args.title));
+            body.add("description", serde_json::json!(args.description));
+            return self.send_pull_request(
+                &self.pull_request_url(existing.id),
+                &body,
+                http::Method::PUT,
+            );
+        }
+        Ok(existing.into())
+    }
+
+    fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
+        let url = self.pull_requests_url(&args)?;
+        self.paged(
+            &url,
+            args.list_args,
+            ApiOperation::MergeRequest,
+            BitbucketPullRequest::into,
+        )
+    }
+
+    fn merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
+        if args.auto_merge {
+            return Err(GRError::OperationNotSupported(
+                "Bitbucket cannot merge a pull request once its pipeline succeeds".to_string(),
+            )
+            .into());
+        }
+        // Bitbucket has no server-side check of the head of the source
+        // branch, so it is checked right before merging.
+        if let Some(sha) = &args.sha {
+            let pull_request = self.get(args.id)?;
+            if pull_request.head_sha.is_empty() || !sha.starts_with(&pull_request.head_sha) {
+                return Err(GRError::RemoteConflict(format!(
+                    "Head of pull request {} is at {}, expected {}",
+                    args.id, pull_request.head_sha, sha
+                ))
+                .into());
+            }
+        }
+        let mut body = Body::new();
+        if let Some(message) = &args.message {
+            body.add("message", serde_json::json!(message));
+        }
+        if let Some(strategy) = merge_strategy(&args) {
+            body.add("merge_strategy", serde_json::json!(strategy));
+        }
+        if args.delete_source_branch {
+            body.add("close_source_branch", serde_json::json!(true));
+        }
+        self.send_pull_request(
+            &format!("{}/merge", self.pull_request_url(args.id)),
+            &body,
+            http::Method::POST,
+        )
+    }
+
+    fn get(&self, id: i64) -> Result<MergeRequestResponse> {
+        query::get_as::<_, (), BitbucketPullRequest, _>(
+            &self.runner,
+            &self.pull_request_url(id),
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )
+    }
+
+    fn close(&self, id: i64) -> Result<MergeRequestResponse> {
+        self.send_pull_request(
+            &format!("{}/decline", self.pull_request_url(id)),
+            &Body::new(),
+            http::Method::POST,
+        )
+    }
+
+    // The approval response is the participant, not the pull request.
+    fn approve(&self, id: i64) -> Result<MergeRequestResponse> {
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &format!("{}/approve", self.pull_request_url(id)),
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(MergeRequestResponse::builder()
+            .id(id)
+            .web_url(self.get_url(BrowseOptions::MergeRequestId(id)))
+            .build()
+            .unwrap())
+    }
+
+    fn toggle_draft(&self, id: i64, draft: bool) -> Result<MergeRequestResponse> {
+        let mut body = Body::new();
+        body.add("draft", serde_json::json!(draft));
+        self.send_pull_request(&self.pull_request_url(id), &body, http::Method::PUT)
+    }
+
+    fn num_pages(&self, args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
+        let url = self.pull_requests_url(&args)?;
+        Bitbucket::num_pages(self, &url, ApiOperation::MergeRequest)
+    }
+
+    fn num_resources(&self, args: MergeRequestListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.pull_requests_url(&args)?;
+        Bitbucket::num_resources(self, &url, ApiOperation::MergeRequest)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Bitbucket<R> {
+    fn create(&self, args: CommentMergeRequestBodyArgs) -> Result<()> {
+        let url = format!("{}/comments", self.pull_request_url(args.id));
+        let mut body = Body::new();
+        body.add("content", serde_json::json!({ "raw": args.comment }));
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    fn update(&self, comment_id: i64, args: CommentMergeRequestBodyArgs) -> Result<()> {
+        let url = format!("{}/comments/{}", self.pull_request_url(args.id), comment_id);
+        let mut body = Body::new();
+        body.add("content", serde_json::json!({ "raw": args.comment }));
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, id: i64, comment_id: i64) -> Result<()> {
+        let url = format!("{}/comments/{}", self.pull_request_url(id), comment_id);
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
+    fn list(&self, args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>> {
+        if args.reactions {
+            return Err(error::GRError::OperationNotSupported(
+                "Bitbucket comments have no reactions".to_string(),
+            )
+            .into());
+        }
+        let url = format!("{}/comments", self.pull_request_url(args.id));
+        let comments = self.paged(
+            &url,
+            args.list_args,
+            ApiOperation::MergeRequest,
+            |comment: BitbucketComment| comment,
+        )?;
+        // Deleted comments are still listed, without content.
+        Ok(comments
+            .into_iter()
+            .filter(|comment| !comment.deleted)
+            .map(Comment::from)
+            .collect())
+    }
+
+    fn num_pages(&self, args: CommentMergeRequestListBodyArgs) -> Result<Option<u32>> {
+        let url = format!("{}/comments", self.pull_request_url(args.id));
+        Bitbucket::num_pages(self, &url, ApiOperation::MergeRequest)
+    }
+
+    fn num_resources(
+        &self,
+        args: CommentMergeRequestListBodyArgs,
+    ) -> Result<Option<NumberDeltaErr>> {
+        let url = format!("{}/comments", self.pull_request_url(args.id));
+        Bitbucket::num_resources(self, &url, ApiOperation::MergeRequest)
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketPullRequest {
+    pub id: i64,
+    title: String,
+    description: String,
+    state: String,
+    author: BitbucketUser,
+    source: BitbucketEndpoint,
+    merge_commit: Option<BitbucketCommit>,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_on: DateTime<Utc>,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    updated_on: DateTime<Utc>,
+    links: BitbucketLinks,
+}
+
+impl Contract for BitbucketPullRequest {
+    const OPTIONAL_FIELDS: &'static [&'static str] = &["merge_commit"];
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketUser {
+    pub display_name: String,
+    pub nickname: String,
+    pub account_id: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketEndpoint {
+    branch: BitbucketBranch,
+    commit: BitbucketCommit,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketBranch {
+    pub name: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketCommit {
+    hash: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketLinks {
+    pub html: BitbucketLink,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketLink {
+    pub href: String,
+}
+
+/// Merge strategy of the pull request. Repository default when not given.
+fn merge_strategy(args: &MergeRequestMergeBodyArgs) -> Option<&'static str> {
+    match args.method {
+        Some(MergeMethod::Merge) => Some("merge_commit"),
+        Some(MergeMethod::Squash) => Some("squash"),
+        Some(MergeMethod::Rebase) => Some("rebase_fast_forward"),
+        None if args.squash => Some("squash"),
+        None => None,
+    }
+}
+
+impl From<BitbucketPullRequest> for MergeRequestResponse {
+    fn from(data: BitbucketPullRequest) -> Self {
+        // There is no merge date, a merged pull request is last updated when
+        // merged.
+        let merged_at = (data.state == "MERGED").then_some(data.updated_on);
+        MergeRequestResponse::builder()
+            .id(data.id)
+            .web_url(data.links.html.href)
+            .source_branch(data.source.branch.name)
+            .sha(
+                data.merge_commit
+                    .map(|commit| commit.hash)
+                    .unwrap_or_default(),
+            )
+            .author(data.author.nickname)
+            .updated_at(data.updated_on)
+            .created_at(data.created_on)
+            .title(data.title)
+            .description(data.description)
+            .merged_at(merged_at)
+            .head_sha(data.source.commit.hash)
+            .build()
+            .unwrap()
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketWorkspaceMember {
+    pub user: BitbucketUser,
+}
+
+impl Contract for BitbucketWorkspaceMember {}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketComment {
+    id: i64,
+    content: BitbucketContent,
+    user: BitbucketUser,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_on: DateTime<Utc>,
+    deleted: bool,
+}
+
+impl Contract for BitbucketComment {}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketContent {
+    raw: String,
+}
+
+impl From<BitbucketComment> for Comment {
+    fn from(data: BitbucketComment) -> Self {
+        Comment::builder()
+            .id(data.id)
+            .body(data.content.raw)
+            .author(data.user.nickname)
+            .created_at(Some(data.created_on))
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bitbucket::test::page;
+    use crate::cmds::project::Member;
+    use crate::io::HttpResponse;
+    use crate::setup_client;
+    use crate::test::utils::{ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_list_open_pull_requests() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_body(
+            200,
+            Some(page("pull_request.json", None)),
+            None,
+        );
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let pull_requests = bitbucket.list(args).unwrap();
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pullrequests\
+             ?state=OPEN&pagelen=50",
+            *client.url()
+        );
+        assert_eq!(1, pull_requests.len());
+        let pull_request = &pull_requests[0];
+        assert_eq!(7, pull_request.id);
+        assert_eq!("feature", pull_request.source_branch);
+        assert_eq!("jordilin", pull_request.author);
+        assert_eq!("8f2a9d1c3b4e", pull_request.head_sha);
+        assert_eq!(
+            "https://bitbucket.org/jordilin/bitbapi/pull-requests/7",
+            pull_request.web_url
+        );
+        assert!(pull_request.merged_at.is_none());
+    }
+
+    #[test]
+    fn test_list_pull_requests_by_user_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket);
+        let (_, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .author(Some(
+                Member::builder()
+                    .username("jordilin".to_string())
+                    .build()
+                    .unwrap(),
+            ))
+            .build()
+            .unwrap();
+        match bitbucket.list(args) {
+            Err(err) => match err.downcast_ref::<GRError>() {
+                Some(GRError::OperationNotSupported(_)) => {}
+                _ => panic!("Expected OperationNotSupported"),
+            },
+            _ => panic!("Expected error"),
+        }
+    }
+
+    #[test]
+    fn test_open_pull_request_with_reviewers() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket)
+            .add_contract(201, "pull_request.json", None)
+            .add_body(200, Some(page("workspace_member.json", None)), None);
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        let reviewer = Member::builder()
+            .username("tsawyer".to_string())
+            .mr_member_type(MrMemberType::Filled)
+            .build()
+            .unwrap();
+        let args = MergeRequestBodyArgs::builder()
+            .title("New Feature".to_string())
+            .source_branch("feature".to_string())
+            .target_branch("main".to_string())
+            .reviewers(vec![reviewer])
+            .build()
+            .unwrap();
+        let pull_request = bitbucket.open(args).unwrap();
+        assert_eq!(7, pull_request.id);
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pullrequests",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("feature", body["source"]["branch"]["name"]);
+        assert_eq!("main", body["destination"]["branch"]["name"]);
+        assert_eq!(true, body["close_source_branch"]);
+        assert_eq!(
+            serde_json::json!([{"account_id": "557058:9f8e7d6c-5b4a-4392-8170-6f5e4d3c2b1a"}]),
+            body["reviewers"]
+        );
+    }
+
+    #[test]
+    fn test_open_pull_request_returns_existing_one() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket)
+            .add_body(200, Some(page("pull_request.json", None)), None)
+            .add_body(409, Some(r#"{"type":"error"}"#.to_string()), None);
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        let args = MergeRequestBodyArgs::builder()
+            .source_branch("feature".to_string())
+            .target_branch("main".to_string())
+            .build()
+            .unwrap();
+        let pull_request = bitbucket.open(args).unwrap();
+        assert_eq!(7, pull_request.id);
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pullrequests\
+             ?q=source.branch.name%3D%22feature%22%20AND%20state%3D%22OPEN%22",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_merge_pull_request_head_moved_is_conflict() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_contract(
+            200,
+            "pull_request.json",
+            None,
+        );
+        let (_, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(7)
+            .sha(Some("0123456789ab".to_string()))
+            .build()
+            .unwrap();
+        match bitbucket.merge(args) {
+            Err(err) => match err.downcast_ref::<GRError>() {
+                Some(GRError::RemoteConflict(_)) => {}
+                _ => panic!("Expected RemoteConflict"),
+            },
+            _ => panic!("Expected error"),
+        }
+    }
+
+    #[test]
+    fn test_merge_pull_request_auto_merge_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket);
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(7)
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        match bitbucket.merge(args) {
+            Err(err) => match err.downcast_ref::<GRError>() {
+                Some(GRError::OperationNotSupported(_)) => {}
+                _ => panic!("Expected OperationNotSupported"),
+            },
+            _ => panic!("Expected error"),
+        }
+        assert!(client.url().is_empty());
+    }
+
+    #[test]
+    fn test_merge_pull_request_strategy_and_close_source_branch() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_contract(
+            200,
+            "pull_request.json",
+            None,
+        );
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(7)
+            .squash(true)
+            .delete_source_branch(true)
+            .build()
+            .unwrap();
+        bitbucket.merge(args).unwrap();
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pullrequests/7/merge",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("squash", body["merge_strategy"]);
+        assert_eq!(true, body["close_source_branch"]);
+    }
+
+    #[test]
+    fn test_merge_pull_request_rebase_method() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_contract(
+            200,
+            "pull_request.json",
+            None,
+        );
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(7)
+            .method(Some(MergeMethod::Rebase))
+            .build()
+            .unwrap();
+        bitbucket.merge(args).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("rebase_fast_forward", body["merge_strategy"]);
+        assert!(body.get("close_source_branch").is_none());
+    }
+
+    #[test]
+    fn test_decline_pull_request() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_contract(
+            200,
+            "pull_request.json",
+            None,
+        );
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn MergeRequest);
+        bitbucket.close(7).unwrap();
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pullrequests/7/decline",
+            *client.url()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_list_pull_request_comments() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_body(
+            200,
+            Some(page("comment.json", None)),
+            None,
+        );
+        let (client, bitbucket) =
+            setup_client!(contracts, default_bitbucket(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestListBodyArgs::builder()
+            .id(7)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let comments = bitbucket.list(args).unwrap();
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi/pullrequests/7/comments\
+             ?pagelen=50",
+            *client.url()
+        );
+        assert_eq!("Looks good to me", comments[0].body);
+        assert_eq!("tsawyer", comments[0].author);
+    }
+}
@@ -0,0 +1,250 @@
+use super::merge_request::{BitbucketLink, BitbucketUser, BitbucketWorkspaceMember};
+use super::Bitbucket;
+use crate::{
+    api_traits::{ApiOperation, NumberDeltaErr, RemoteProject},
+    cli::browse::BrowseOptions,
+    cmds::project::{Member, Project, ProjectListBodyArgs},
+    error::GRError,
+    io::{CmdInfo, HttpResponse, HttpRunner},
+    remote::{contract::Contract, encode_query_param, query},
+    time, Result,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+impl<R: HttpRunner<Response = HttpResponse>> Bitbucket<R> {
+    fn list_project_url(&self, args: &ProjectListBodyArgs) -> Result<String> {
+        if args.stars || args.tags || args.members || args.group.is_some() {
+            return Err(GRError::OperationNotSupported(
+                "Only the repositories of a workspace can be listed in Bitbucket".to_string(),
+            )
+            .into());
+        }
+        // Repositories belong to workspaces. The personal workspace of a
+        // user is named after the user.
+        let workspace = match &args.user {
+            Some(user) => user.username.as_str(),
+            None => self.workspace(),
+        };
+        Ok(format!(
+            "{}/repositories/{}",
+            self.rest_api_basepath, workspace
+        ))
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Bitbucket<R> {
+    fn get_project_data(&self, id: Option<i64>, path: Option<&str>) -> Result<CmdInfo> {
+        // https://developer.atlassian.com/cloud/bitbucket/rest/api-group-repositories/#api-repositories-workspace-repo-slug-get
+        if let Some(id) = id {
+            return Err(GRError::OperationNotSupported(format!(
+                "Getting a repository by ID is not supported in Bitbucket, got ID {}",
+                id
+            ))
+            .into());
+        }
+        let url = match path {
+            Some(path) => format!("{}/repositories/{}", self.rest_api_basepath, path),
+            None => self.repo_url(),
+        };
+        let project = query::get_as::<_, (), BitbucketRepository, _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        Ok(CmdInfo::Project(project))
+    }
+
+    fn get_project_members(&self) -> Result<CmdInfo> {
+        // Permissions are granted per workspace, its members being the
+        // closest to the members of a repository.
+        let members = self.paged(
+            &self.workspace_members_url(),
+            None,
+            ApiOperation::Project,
+            |member: BitbucketWorkspaceMember| member.user.into(),
+        )?;
+        Ok(CmdInfo::Members(members))
+    }
+
+    fn get_url(&self, option: BrowseOptions) -> String {
+        let base_url = format!("https://{}/{}", self.domain, self.path);
+        match option {
+            BrowseOptions::Repo => base_url,
+            BrowseOptions::MergeRequests => format!("{}/pull-requests", base_url),
+            BrowseOptions::MergeRequestId(id) => format!("{}/pull-requests/{}", base_url, id),
+            BrowseOptions::Pipelines => format!("{}/pipelines", base_url),
+            BrowseOptions::PipelineId(id) => format!("{}/pipelines/results/{}", base_url, id),
+            BrowseOptions::Releases => format!("{}/downloads", base_url),
+            // The form has no title nor description parameters.
+            BrowseOptions::NewMergeRequest {
+                source_branch,
+                target_branch,
+                ..
+            } => format!(
+                "{}/pull-requests/new?source={}&dest={}",
+                base_url,
+                encode_query_param(&source_branch),
+                encode_query_param(&target_branch)
+            ),
+            // Manual is only one URL and it's the user guide. Handled in the
+            // browser command.
+            BrowseOptions::Manual => unreachable!(),
+        }
+    }
+
+    fn list(&self, args: ProjectListBodyArgs) -> Result<Vec<Project>> {
+        let url = self.list_project_url(&args)?;
+        self.paged(
+            &url,
+            args.from_to_page,
+            ApiOperation::Project,
+            BitbucketRepository::into,
+        )
+    }
+
+    fn num_pages(&self, args: ProjectListBodyArgs) -> Result<Option<u32>> {
+        let url = self.list_project_url(&args)?;
+        Bitbucket::num_pages(self, &url, ApiOperation::Project)
+    }
+
+    fn num_resources(&self, args: ProjectListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.list_project_url(&args)?;
+        Bitbucket::num_resources(self, &url, ApiOperation::Project)
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BitbucketRepository {
+    description: String,
+    is_private: bool,
+    language: String,
+    mainbranch: BitbucketMainBranch,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_on: DateTime<Utc>,
+    links: BitbucketRepositoryLinks,
+}
+
+impl Contract for BitbucketRepository {}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketMainBranch {
+    name: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BitbucketRepositoryLinks {
+    html: BitbucketLink,
+    avatar: BitbucketLink,
+}
+
+impl From<BitbucketRepository> for Project {
+    fn from(data: BitbucketRepository) -> Self {
+        let visibility = if data.is_private { "private" } else { "public" };
+        // Repositories are identified by UUID, there is no numeric ID.
+        Project::builder()
+            .id(0)
+            .default_branch(data.mainbranch.name)
+            .html_url(data.links.html.href)
+            .created_at(data.created_on)
+            .description(data.description)
+            .language(data.language)
+            .visibility(visibility.to_string())
+            .avatar_url(data.links.avatar.href)
+            .build()
+            .unwrap()
+    }
+}
+
+impl From<BitbucketUser> for Member {
+    fn from(data: BitbucketUser) -> Self {
+        Member::builder()
+            .name(data.display_name)
+            .username(data.nickname)
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bitbucket::test::page;
+    use crate::io::HttpResponse;
+    use crate::setup_client;
+    use crate::test::utils::{ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_get_project_data() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_contract(
+            200,
+            "repository.json",
+            None,
+        );
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn RemoteProject);
+        let CmdInfo::Project(project) = bitbucket.get_project_data(None, None).unwrap() else {
+            panic!("Expected project");
+        };
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/repositories/jordilin/bitbapi",
+            *client.url()
+        );
+        assert_eq!(0, project.id);
+        assert_eq!("main", project.default_branch());
+    }
+
+    #[test]
+    fn test_get_project_data_by_id_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket);
+        let (_, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn RemoteProject);
+        assert!(bitbucket.get_project_data(Some(1), None).is_err());
+    }
+
+    #[test]
+    fn test_get_project_members_from_workspace() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket).add_body(
+            200,
+            Some(page("workspace_member.json", None)),
+            None,
+        );
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn RemoteProject);
+        let CmdInfo::Members(members) = bitbucket.get_project_members().unwrap() else {
+            panic!("Expected members");
+        };
+        assert_eq!(
+            "https://api.bitbucket.org/2.0/workspaces/jordilin/members?pagelen=50",
+            *client.url()
+        );
+        assert_eq!("tsawyer", members[0].username);
+        assert_eq!("Tom Sawyer", members[0].name);
+    }
+
+    #[test]
+    fn test_get_urls() {
+        let contracts = ResponseContracts::new(ContractType::Bitbucket);
+        let (_, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn RemoteProject);
+        assert_eq!(
+            "https://bitbucket.org/jordilin/bitbapi/pull-requests/7",
+            bitbucket.get_url(BrowseOptions::MergeRequestId(7))
+        );
+        assert_eq!(
+            "https://bitbucket.org/jordilin/bitbapi/pipelines/results/42",
+            bitbucket.get_url(BrowseOptions::PipelineId(42))
+        );
+        assert_eq!(
+            "https://bitbucket.org/jordilin/bitbapi/pull-requests/new?source=feature&dest=main",
+            bitbucket.get_url(BrowseOptions::NewMergeRequest {
+                source_branch: "feature".to_string(),
+                target_branch: "main".to_string(),
+                title: "title".to_string(),
+                description: "description".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use super::merge_request::BitbucketUser;
+use super::Bitbucket;
+use crate::api_traits::{ApiOperation, UserInfo};
+use crate::cmds::project::Member;
+use crate::cmds::user::{UserCliArgs, UserProfile};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::contract::Contract;
+use crate::remote::query;
+use crate::Result;
+
+impl Contract for BitbucketUser {}
+
+impl<R: HttpRunner<Response = HttpResponse>> UserInfo for Bitbucket<R> {
+    fn get_auth_user(&self) -> Result<Member> {
+        // https://developer.atlassian.com/cloud/bitbucket/rest/api-group-users/#api-user-get
+        let url = format!("{}/user", self.rest_api_basepath);
+        query::get_as::<_, (), BitbucketUser, _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::User,
+        )
+    }
+
+    fn get(&self, args: &UserCliArgs) -> Result<Member> {
+        // Users can only be looked up by account ID or UUID, nicknames are
+        // not unique.
+        let url = format!("{}/users/{}", self.rest_api_basepath, args.username);
+        query::get_as::<_, (), BitbucketUser, _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::User,
+        )
+    }
+
+    fn get_profile(&self, args: &UserCliArgs) -> Result<UserProfile> {
+        // Bitbucket users have no public profile fields nor organizations
+        // besides their names.
+        Ok(UserInfo::get(self, args)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::io::HttpResponse;
+    use crate::setup_client;
+    use crate::test::utils::{ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_get_auth_user() {
+        let contracts =
+            ResponseContracts::new(ContractType::Bitbucket).add_contract(200, "user.json", None);
+        let (client, bitbucket) = setup_client!(contracts, default_bitbucket(), dyn UserInfo);
+        let user = bitbucket.get_auth_user().unwrap();
+        assert_eq!("https://api.bitbucket.org/2.0/user", *client.url());
+        assert_eq!("jordilin", user.username);
+    }
+}
@@ -1,6 +1,6 @@
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, BufWriter, Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -14,7 +14,7 @@ use crate::time::Seconds;
 
 use super::CacheState;
 
-use crate::config::ConfigProperties;
+use crate::config::{CacheCompression, ConfigProperties};
 
 use crate::error::{self, AddContext, GRError};
 use crate::Result;
@@ -22,10 +22,23 @@ use crate::Result;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 
+/// Leading bytes of a gzip stream. Used to tell compressed cache entries apart
+/// from uncompressed ones.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 pub struct FileCache {
     config: Arc<dyn ConfigProperties>,
 }
 
+/// A cached response stored on disk.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    /// Time elapsed since the entry was last written.
+    pub age: Seconds,
+}
+
 impl FileCache {
     pub fn new(config: Arc<dyn ConfigProperties>) -> Self {
         FileCache { config }
@@ -89,9 +102,45 @@ impl FileCache {
         format!("{}/{:x}", location, hash)
     }
 
+    /// Entries currently stored in the cache location.
+    pub fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let cache_location = self
+            .config
+            .cache_location()
+            .ok_or(GRError::ConfigurationNotFound)?;
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(cache_location)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let age = metadata.modified()?.elapsed().unwrap_or_default().as_secs();
+            entries.push(CacheEntry {
+                path: entry.path(),
+                size: metadata.len(),
+                age: Seconds::new(age),
+            });
+        }
+        Ok(entries)
+    }
+
+    pub fn remove(&self, entry: &CacheEntry) -> Result<()> {
+        fs::remove_file(&entry.path)?;
+        Ok(())
+    }
+
     fn get_cache_data(&self, mut reader: impl BufRead) -> Result<HttpResponse> {
-        let decompressed_data = GzDecoder::new(&mut reader);
-        let mut reader = BufReader::new(decompressed_data);
+        // Entries might have been written with or without compression
+        // depending on the configuration at the time. Check the contents
+        // instead of relying on the current setting.
+        let mut data = Vec::new();
+        if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+            GzDecoder::new(reader).read_to_end(&mut data)?;
+        } else {
+            reader.read_to_end(&mut data)?;
+        }
+        let mut reader = data.as_slice();
         let mut headers = String::new();
         reader.read_line(&mut headers)?;
         let mut status_code = String::new();
@@ -129,13 +178,22 @@ impl FileCache {
         Ok(response)
     }
 
-    fn persist_cache_data(&self, value: &HttpResponse, f: BufWriter<File>) -> Result<()> {
+    fn persist_cache_data(&self, value: &HttpResponse, mut f: impl Write) -> Result<()> {
         let headers_map = value.headers.as_ref().unwrap();
         let headers = serde_json::to_string(headers_map).unwrap();
         let status = value.status.to_string();
         let file_data = format!("{}\n{}\n{}", headers, status, value.body);
-        let mut encoder = GzEncoder::new(f, Compression::default());
-        encoder.write_all(file_data.as_bytes())?;
+        match self.config.cache_compression() {
+            CacheCompression::Gzip => {
+                let mut encoder = GzEncoder::new(f, Compression::default());
+                encoder.write_all(file_data.as_bytes())?;
+                encoder.finish()?;
+            }
+            CacheCompression::None => {
+                f.write_all(file_data.as_bytes())?;
+                f.flush()?;
+            }
+        }
         Ok(())
     }
 
@@ -292,11 +350,19 @@ fn get_file_mtime_elapsed(path: &str) -> Result<Seconds> {
 mod tests {
     use super::*;
 
-    struct ConfigMock;
+    struct ConfigMock {
+        compression: CacheCompression,
+    }
 
     impl ConfigMock {
         fn new() -> Self {
-            ConfigMock {}
+            ConfigMock {
+                compression: CacheCompression::Gzip,
+            }
+        }
+
+        fn with_compression(compression: CacheCompression) -> Self {
+            ConfigMock { compression }
         }
     }
 
@@ -309,6 +375,60 @@ mod tests {
             // should probably be sanitized on the Config struct itself.
             Some("/home/user/.cache")
         }
+        fn cache_compression(&self) -> CacheCompression {
+            self.compression
+        }
+    }
+
+    fn cached_response() -> HttpResponse {
+        let mut headers = Headers::new();
+        headers.set("etag".to_string(), "W/\"9ef5b79701\"".to_string());
+        HttpResponse::builder()
+            .status(200)
+            .body(r#"{"name":"gitar"}"#.to_string())
+            .headers(headers)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_persist_and_get_cache_data_with_compression() {
+        for compression in [CacheCompression::Gzip, CacheCompression::None] {
+            let fc = FileCache::new(Arc::new(ConfigMock::with_compression(compression)));
+            let mut data = Vec::new();
+            fc.persist_cache_data(&cached_response(), &mut data)
+                .unwrap();
+            assert_eq!(
+                compression == CacheCompression::Gzip,
+                data.starts_with(&GZIP_MAGIC)
+            );
+            let response = fc.get_cache_data(data.as_slice()).unwrap();
+            assert_eq!(200, response.status);
+            assert_eq!(r#"{"name":"gitar"}"#, response.body);
+            assert_eq!(
+                "W/\"9ef5b79701\"",
+                response.headers.as_ref().unwrap().get("etag").unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_cache_data_reads_entries_written_with_other_compression() {
+        let fc = FileCache::new(Arc::new(ConfigMock::with_compression(
+            CacheCompression::None,
+        )));
+        let mut gzipped = Vec::new();
+        FileCache::new(Arc::new(ConfigMock::new()))
+            .persist_cache_data(&cached_response(), &mut gzipped)
+            .unwrap();
+        let response = fc.get_cache_data(gzipped.as_slice()).unwrap();
+        assert_eq!(r#"{"name":"gitar"}"#, response.body);
+
+        let fc = FileCache::new(Arc::new(ConfigMock::new()));
+        let plain = "{\"etag\":\"abc\"}\n304\n{\"name\":\"gitar\"}";
+        let response = fc.get_cache_data(plain.as_bytes()).unwrap();
+        assert_eq!(304, response.status);
+        assert_eq!(r#"{"name":"gitar"}"#, response.body);
     }
 
     #[test]
@@ -1,14 +1,25 @@
+pub mod alias;
 pub mod amps;
+pub mod auth;
 pub mod browse;
 pub mod cache;
 pub mod cicd;
 pub mod common;
+pub mod contract;
+pub mod deployment;
 pub mod docker;
+pub mod flow;
 pub mod init;
+pub mod issue;
+pub mod label;
 pub mod merge_request;
 pub mod my;
 pub mod project;
 pub mod release;
+pub mod remote;
+pub mod report;
+pub mod search;
+pub mod serve_mock;
 pub mod star;
 pub mod trending;
 pub mod user;
@@ -17,16 +28,26 @@ use self::browse::BrowseCommand;
 use self::browse::BrowseOptions;
 use self::cicd::{PipelineCommand, PipelineOptions};
 use self::common::validate_domain_project_repo_path;
+use self::contract::{ContractCommand, ContractOptions};
+use self::deployment::{DeploymentCommand, DeploymentOptions};
 use self::docker::{DockerCommand, DockerOptions};
+use self::flow::{FlowCommand, FlowOptions};
 use self::init::{InitCommand, InitCommandOptions};
+use self::issue::{IssueCommand, IssueOptions};
+use self::label::{LabelCommand, LabelOptions};
 use self::my::MyCommand;
 use self::my::MyOptions;
 use self::project::{ProjectCommand, ProjectOptions};
 use self::release::{ReleaseCommand, ReleaseOptions};
+use self::remote::{RemoteCommand, RemoteOptions};
+use self::report::{ReportCommand, ReportOptions};
+use self::search::{SearchCommand, SearchOptions};
+use self::serve_mock::{ServeMockCommand, ServeMockOptions};
 use self::trending::TrendingCommand;
 use self::trending::TrendingOptions;
 use amps::AmpsCommand;
 use amps::AmpsOptions;
+use auth::{AuthCommand, AuthOptions};
 use cache::CacheCommand;
 use cache::CacheOptions;
 use clap::ArgAction;
@@ -34,10 +55,16 @@ use merge_request::{MergeRequestCommand, MergeRequestOptions};
 use user::UserCommand;
 use user::UserOptions;
 
+use std::collections::HashMap;
 use std::option::Option;
+use std::path::PathBuf;
 
 use clap::builder::{styling::AnsiColor, Styles};
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+
+use crate::config::{self, ALIAS_TABLE};
+use crate::{get_default_config_path, Result};
 
 const CLI_STYLE: Styles = Styles::styled()
     .header(AnsiColor::Red.on_default().bold())
@@ -68,6 +95,18 @@ struct Args {
     /// Full path to the config location. Default is $HOME/.config/gitar
     #[clap(long, global = true, value_name = "PATH")]
     pub config: Option<String>,
+    /// Perform the request on behalf of another user. Requires a Gitlab
+    /// administrator token. Gitlab only.
+    #[clap(long, global = true, value_name = "USERNAME")]
+    pub sudo: Option<String>,
+    /// Send requests even if the remote has been failing and its circuit is
+    /// open
+    #[clap(long, global = true)]
+    pub force_circuit: bool,
+    /// Fail when a remote response does not match the expected contract,
+    /// instead of defaulting missing or unexpected fields. For debugging
+    #[clap(long, global = true)]
+    pub strict_contract: bool,
 }
 
 #[derive(Parser)]
@@ -85,35 +124,79 @@ enum Command {
         about = "Handles docker images in Gitlab/Github registries"
     )]
     Docker(DockerCommand),
+    #[clap(name = "issue", about = "Issue operations")]
+    Issue(IssueCommand),
+    #[clap(name = "label", about = "Label operations")]
+    Label(LabelCommand),
     #[clap(name = "rl", about = "Release operations")]
     Release(ReleaseCommand),
+    #[clap(name = "dp", about = "Deployment operations", visible_alias = "deploy")]
+    Deployment(DeploymentCommand),
+    #[clap(name = "search", about = "Search issues, merge requests and commits")]
+    Search(SearchCommand),
     #[clap(
         name = "my",
         about = "Your user information, such as assigned merge requests, etc..."
     )]
     My(MyCommand),
+    #[clap(
+        name = "report",
+        about = "Reports on the project activity, such as weekly merge requests"
+    )]
+    Report(ReportCommand),
     #[clap(name = "tr", about = "Trending repositories. Github.com only.")]
     Trending(TrendingCommand),
     #[clap(name = "us", about = "User operations")]
     User(UserCommand),
+    #[clap(name = "remote", about = "Remote instance information")]
+    Remote(RemoteCommand),
     /// Interactively execute gitar amplifier commands using gitar. gr-in-gr
     #[clap(name = "amps")]
     Amps(AmpsCommand),
+    #[clap(name = "auth", about = "Authentication helpers for git and docker")]
+    Auth(AuthCommand),
+    #[clap(
+        name = "flow",
+        about = "Run sequences of gitar commands defined in the config"
+    )]
+    Flow(FlowCommand),
     #[clap(name = "init", about = "Initialize the config file")]
     Init(InitCommand),
     #[clap(name = "cache", about = "Local cache operations")]
     Cache(CacheCommand),
+    /// Record API contracts used as test fixtures. For gitar development
+    #[clap(name = "contract", hide = true)]
+    Contract(ContractCommand),
+    #[clap(
+        name = "serve-mock",
+        about = "Serve the API contracts as a fake Gitlab/Github API"
+    )]
+    ServeMock(ServeMockCommand),
     #[clap(
         name = "manual",
         about = "Open the user manual in the browser",
         visible_alias = "man"
     )]
     Manual,
+    /// Aliases defined in the config, expanded before parsing again
+    #[command(external_subcommand)]
+    Alias(Vec<String>),
 }
 
 // Parse cli and return CliOptions
 pub fn parse_cli() -> OptionArgs {
-    let args = Args::parse();
+    let args =
+        parse_args(std::env::args().collect(), load_aliases).unwrap_or_else(|err| err.exit());
+    option_args(args)
+}
+
+/// Parses a gitar command line other than the one gitar was called with,
+/// ex. the steps of a flow.
+pub fn try_parse_cli_from(argv: Vec<String>) -> std::result::Result<OptionArgs, clap::Error> {
+    parse_args(argv, load_aliases).map(option_args)
+}
+
+fn option_args(args: Args) -> OptionArgs {
     let options = match args.command {
         Command::MergeRequest(sub_matches) => Some(CliOptions::MergeRequest(sub_matches.into())),
         Command::Browse(sub_matches) => Some(CliOptions::Browse(sub_matches.into())),
@@ -121,18 +204,96 @@ pub fn parse_cli() -> OptionArgs {
         Command::Project(sub_matches) => Some(CliOptions::Project(sub_matches.into())),
         Command::Init(sub_matches) => Some(CliOptions::Init(sub_matches.into())),
         Command::Docker(sub_matches) => Some(CliOptions::Docker(sub_matches.into())),
+        Command::Issue(sub_matches) => Some(CliOptions::Issue(sub_matches.into())),
+        Command::Label(sub_matches) => Some(CliOptions::Label(sub_matches.into())),
         Command::Release(sub_matches) => Some(CliOptions::Release(sub_matches.into())),
+        Command::Deployment(sub_matches) => Some(CliOptions::Deployment(sub_matches.into())),
+        Command::Search(sub_matches) => Some(CliOptions::Search(sub_matches.into())),
         Command::My(sub_matches) => Some(CliOptions::My(sub_matches.into())),
+        Command::Report(sub_matches) => Some(CliOptions::Report(sub_matches.into())),
         Command::Trending(sub_matches) => Some(CliOptions::Trending(sub_matches.into())),
         Command::Cache(sub_matches) => Some(CliOptions::Cache(sub_matches.into())),
+        Command::Contract(sub_matches) => Some(CliOptions::Contract(sub_matches.into())),
+        Command::ServeMock(sub_matches) => Some(CliOptions::ServeMock(sub_matches.into())),
         Command::Manual => Some(CliOptions::Manual),
         Command::Amps(sub_matches) => Some(CliOptions::Amps(sub_matches.into())),
+        Command::Auth(sub_matches) => Some(CliOptions::Auth(sub_matches.into())),
         Command::User(sub_matches) => Some(CliOptions::User(sub_matches.into())),
+        Command::Remote(sub_matches) => Some(CliOptions::Remote(sub_matches.into())),
+        Command::Flow(sub_matches) => Some(CliOptions::Flow(sub_matches.into())),
+        Command::Alias(_) => unreachable!("Aliases are expanded when parsing"),
+    };
+    let cli_args = CliArgs {
+        force_circuit: args.force_circuit,
+        strict_contract: args.strict_contract,
+        ..CliArgs::new(args.verbose, args.repo, args.domain, args.config, args.sudo)
+    };
+    OptionArgs::new(options, cli_args)
+}
+
+/// Parses the command line, expanding the alias it starts with, if any.
+/// `load_aliases` gets the config directory given in the command line.
+fn parse_args(
+    argv: Vec<String>,
+    load_aliases: impl FnOnce(Option<&str>) -> Result<HashMap<String, String>>,
+) -> std::result::Result<Args, clap::Error> {
+    let args = Args::try_parse_from(&argv)?;
+    let Command::Alias(alias_args) = &args.command else {
+        return Ok(args);
     };
-    OptionArgs::new(
-        options,
-        CliArgs::new(args.verbose, args.repo, args.domain, args.config),
-    )
+    let aliases = load_aliases(args.config.as_deref())
+        .map_err(|err| cli_error(ErrorKind::InvalidValue, err))?;
+    let (name, alias_args) = alias_args.split_first().unwrap();
+    let Some(definition) = aliases.get(name) else {
+        return Err(cli_error(
+            ErrorKind::InvalidSubcommand,
+            format!("unrecognized subcommand or alias '{}'", name),
+        ));
+    };
+    let expanded = alias::expand(name, definition, alias_args)
+        .map_err(|err| cli_error(ErrorKind::MissingRequiredArgument, err))?;
+    // Global options given before the alias are kept.
+    let argv = argv[..argv.len() - alias_args.len() - 1]
+        .iter()
+        .cloned()
+        .chain(expanded)
+        .collect::<Vec<_>>();
+    let args = Args::try_parse_from(&argv)?;
+    if let Command::Alias(alias_args) = &args.command {
+        return Err(cli_error(
+            ErrorKind::InvalidSubcommand,
+            format!(
+                "alias '{}' expands to unknown subcommand '{}'. Aliases cannot \
+                 reference other aliases",
+                name, alias_args[0]
+            ),
+        ));
+    }
+    Ok(args)
+}
+
+fn cli_error(kind: ErrorKind, message: impl std::fmt::Display) -> clap::Error {
+    Args::command().bin_name("gr").error(kind, message)
+}
+
+/// Config files declaring aliases and flows: the gitar config file and the
+/// config file of the repository the current directory belongs to.
+pub fn command_config_files(config_dir: Option<&str>) -> Vec<PathBuf> {
+    let config_dir = config_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(|| get_default_config_path().clone());
+    let mut files = vec![config_dir.join("gitar.toml")];
+    if let Some(file) = std::env::current_dir()
+        .ok()
+        .and_then(|dir| alias::repo_config_file(&dir))
+    {
+        files.push(file);
+    }
+    files
+}
+
+fn load_aliases(config_dir: Option<&str>) -> Result<HashMap<String, String>> {
+    config::read_table(&command_config_files(config_dir), ALIAS_TABLE)
 }
 
 pub enum CliOptions {
@@ -142,13 +303,23 @@ pub enum CliOptions {
     Project(ProjectOptions),
     Init(InitCommandOptions),
     Docker(DockerOptions),
+    Issue(IssueOptions),
+    Label(LabelOptions),
     Release(ReleaseOptions),
+    Deployment(DeploymentOptions),
+    Search(SearchOptions),
     My(MyOptions),
+    Report(ReportOptions),
     Trending(TrendingOptions),
     Cache(CacheOptions),
+    Contract(ContractOptions),
+    ServeMock(ServeMockOptions),
     Manual,
     Amps(AmpsOptions),
+    Auth(AuthOptions),
     User(UserOptions),
+    Remote(RemoteOptions),
+    Flow(FlowOptions),
 }
 
 #[derive(Clone, Default)]
@@ -157,6 +328,9 @@ pub struct CliArgs {
     pub repo: Option<String>,
     pub domain: Option<String>,
     pub config: Option<String>,
+    pub sudo: Option<String>,
+    pub force_circuit: bool,
+    pub strict_contract: bool,
 }
 
 impl CliArgs {
@@ -165,12 +339,30 @@ impl CliArgs {
         repo: Option<String>,
         domain: Option<String>,
         config: Option<String>,
+        sudo: Option<String>,
     ) -> Self {
         CliArgs {
             verbose,
             repo,
             domain,
             config,
+            sudo,
+            force_circuit: false,
+            strict_contract: false,
+        }
+    }
+
+    /// Global options of a command run on behalf of another one, ex. a flow
+    /// step. Options not given in the command are taken from `parent`.
+    pub fn inherit(self, parent: &CliArgs) -> Self {
+        CliArgs {
+            verbose: self.verbose.max(parent.verbose),
+            repo: self.repo.or_else(|| parent.repo.clone()),
+            domain: self.domain.or_else(|| parent.domain.clone()),
+            config: self.config.or_else(|| parent.config.clone()),
+            sudo: self.sudo.or_else(|| parent.sudo.clone()),
+            force_circuit: self.force_circuit || parent.force_circuit,
+            strict_contract: self.strict_contract || parent.strict_contract,
         }
     }
 }
@@ -188,3 +380,64 @@ impl OptionArgs {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_expands_alias_keeping_global_options() {
+        let mut config_dir = None;
+        let args = parse_args(
+            argv(&["gr", "--config", "/tmp/gitar", "prs", "--num-pages"]),
+            |dir| {
+                config_dir = dir.map(|dir| dir.to_string());
+                Ok(HashMap::from([(
+                    "prs".to_string(),
+                    "mr list opened".to_string(),
+                )]))
+            },
+        )
+        .unwrap();
+        assert_eq!(Some("/tmp/gitar".to_string()), config_dir);
+        assert_eq!(Some("/tmp/gitar".to_string()), args.config);
+        match args.command {
+            Command::MergeRequest(_) => {}
+            _ => panic!("Expected MergeRequest command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_args_inherit_missing_global_options() {
+        let parent = CliArgs {
+            strict_contract: true,
+            ..CliArgs::new(
+                1,
+                Some("gitlab.com/jordilin/gitar".to_string()),
+                None,
+                Some("/tmp/gitar".to_string()),
+                None,
+            )
+        };
+        let args =
+            CliArgs::new(0, None, None, Some("/tmp/other".to_string()), None).inherit(&parent);
+        assert_eq!(1, args.verbose);
+        assert_eq!(Some("gitlab.com/jordilin/gitar".to_string()), args.repo);
+        assert_eq!(Some("/tmp/other".to_string()), args.config);
+        assert!(args.strict_contract);
+        assert!(!args.force_circuit);
+    }
+
+    #[test]
+    fn test_parse_args_without_alias_does_not_load_aliases() {
+        let args = parse_args(argv(&["gr", "mr", "list", "opened"]), |_| {
+            panic!("Aliases should not be loaded")
+        })
+        .unwrap();
+        assert!(matches!(args.command, Command::MergeRequest(_)));
+    }
+}
@@ -0,0 +1,180 @@
+//! User defined aliases of gitar commands. Aliases are declared in the
+//! `[alias]` table of the gitar config file or of a `.gitar.toml` file at the
+//! root of a repository, so teams can share them along with the code.
+//! Repository aliases take precedence.
+//!
+//! ```toml
+//! [alias]
+//! prs = "mr list opened --format csv"
+//! lgtm = "mr comment --id $1 'LGTM, merging once the pipeline passes'"
+//! ```
+//!
+//! `$1`, `$2`, ... are replaced by the arguments following the alias. The
+//! rest of the arguments are appended to the expanded command.
+
+use std::path::{Path, PathBuf};
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use crate::error::GRError;
+use crate::Result;
+
+pub const REPO_CONFIG_FILE: &str = ".gitar.toml";
+
+lazy_static! {
+    static ref PLACEHOLDER: Regex = Regex::new(r"\$(\d+)").unwrap();
+}
+
+/// Repository config file of the repository `dir` belongs to, if any.
+pub fn repo_config_file(dir: &Path) -> Option<PathBuf> {
+    let root = dir.ancestors().find(|dir| dir.join(".git").exists())?;
+    Some(root.join(REPO_CONFIG_FILE)).filter(|file| file.is_file())
+}
+
+/// Expands an alias definition with the arguments given after the alias.
+pub fn expand(name: &str, definition: &str, args: &[String]) -> Result<Vec<String>> {
+    let mut used = vec![false; args.len()];
+    let mut missing = None;
+    let mut expanded = split_words(definition)
+        .into_iter()
+        .map(|word| {
+            PLACEHOLDER
+                .replace_all(&word, |caps: &Captures| {
+                    let position = caps[1].parse::<usize>().unwrap_or_default();
+                    match position.checked_sub(1).and_then(|i| args.get(i)) {
+                        Some(arg) => {
+                            used[position - 1] = true;
+                            arg.clone()
+                        }
+                        None => {
+                            missing.get_or_insert(position);
+                            String::new()
+                        }
+                    }
+                })
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    if let Some(position) = missing {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Alias {} expects argument ${}: {}",
+            name, position, definition
+        ))
+        .into());
+    }
+    expanded.extend(
+        args.iter()
+            .zip(used)
+            .filter(|(_, used)| !used)
+            .map(|(arg, _)| arg.clone()),
+    );
+    Ok(expanded)
+}
+
+/// Splits a command line into words. Single and double quotes group words
+/// with spaces.
+pub fn split_words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in command.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_words_with_quotes() {
+        assert_eq!(
+            args(&["mr", "create", "--title", "Release notes", "-y"]),
+            split_words(r#"mr create  --title "Release notes" -y"#)
+        );
+        assert_eq!(args(&["--body", ""]), split_words("--body ''"));
+    }
+
+    #[test]
+    fn test_expand_appends_arguments() {
+        let expanded = expand(
+            "prs",
+            "mr list --state opened --author @me",
+            &args(&["--format", "csv"]),
+        )
+        .unwrap();
+        assert_eq!(
+            args(&["mr", "list", "--state", "opened", "--author", "@me", "--format", "csv"]),
+            expanded
+        );
+    }
+
+    #[test]
+    fn test_expand_substitutes_positional_arguments() {
+        let expanded = expand(
+            "mrs-to",
+            "mr list --target-branch $1 --state=$2",
+            &args(&["main", "merged", "--refresh"]),
+        )
+        .unwrap();
+        assert_eq!(
+            args(&[
+                "mr",
+                "list",
+                "--target-branch",
+                "main",
+                "--state=merged",
+                "--refresh"
+            ]),
+            expanded
+        );
+    }
+
+    #[test]
+    fn test_expand_missing_argument_is_error() {
+        assert!(expand("mrs-to", "mr list --target-branch $1", &[]).is_err());
+    }
+
+    #[test]
+    fn test_repo_config_file_at_repository_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("src/cmds");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        assert_eq!(None, repo_config_file(&nested));
+        fs::write(dir.path().join(REPO_CONFIG_FILE), "[alias]\n").unwrap();
+        assert_eq!(
+            Some(dir.path().join(REPO_CONFIG_FILE)),
+            repo_config_file(&nested)
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use clap::Parser;
+
+use crate::auth::CredentialHelperAction;
+
+use super::common::CredentialHelperActionCli;
+
+#[derive(Parser)]
+pub struct AuthCommand {
+    #[clap(subcommand)]
+    subcommand: AuthSubCommand,
+}
+
+#[derive(Parser)]
+enum AuthSubCommand {
+    #[clap(
+        name = "git-credential",
+        about = "Git credential helper serving the API token of configured domains"
+    )]
+    GitCredential(GitCredential),
+}
+
+/// Called by git for HTTPS remotes once configured as a credential helper,
+/// ex. `git config --global credential.https://gitlab.com.helper "!gr auth
+/// git-credential"`
+#[derive(Parser)]
+struct GitCredential {
+    /// Credential helper action, as sent by git
+    #[clap()]
+    action: CredentialHelperActionCli,
+}
+
+impl From<AuthCommand> for AuthOptions {
+    fn from(options: AuthCommand) -> Self {
+        match options.subcommand {
+            AuthSubCommand::GitCredential(options) => {
+                AuthOptions::GitCredential(options.action.into())
+            }
+        }
+    }
+}
+
+pub enum AuthOptions {
+    GitCredential(CredentialHelperAction),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_auth_git_credential_cli_args() {
+        let args = Args::parse_from(vec!["gr", "auth", "git-credential", "get"]);
+        let options: AuthOptions = match args.command {
+            Command::Auth(options) => options.into(),
+            _ => panic!("Expected AuthCommand"),
+        };
+        match options {
+            AuthOptions::GitCredential(action) => {
+                assert_eq!(CredentialHelperAction::Get, action)
+            }
+        }
+    }
+}
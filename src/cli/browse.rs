@@ -59,6 +59,13 @@ pub enum BrowseOptions {
     PipelineId(i64),
     Releases,
     Manual,
+    /// Form to open a new merge request with its fields filled in.
+    NewMergeRequest {
+        source_branch: String,
+        target_branch: String,
+        title: String,
+        description: String,
+    },
 }
 
 #[derive(Parser)]
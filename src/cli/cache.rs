@@ -1,4 +1,9 @@
-use clap::Parser;
+use clap::{ArgGroup, Parser};
+
+use crate::cmds::cache::PruneOptions;
+use crate::time::Seconds;
+
+use super::common::parse_age;
 
 #[derive(Parser)]
 pub struct CacheCommand {
@@ -10,16 +15,132 @@ pub struct CacheCommand {
 enum CacheSubcommand {
     #[clap(name = "info", about = "Get local cache size and location")]
     Info,
+    #[clap(
+        name = "prune",
+        about = "Remove cached responses by age or to keep the cache under a size"
+    )]
+    Prune(Prune),
+    #[clap(
+        name = "refresh-user",
+        about = "Fetch the authenticated user again, replacing the cached one"
+    )]
+    RefreshUser,
+}
+
+#[derive(Parser)]
+#[clap(group(ArgGroup::new("criteria").required(true).multiple(true).args(["older_than", "max_size"])))]
+struct Prune {
+    /// Remove entries not refreshed for longer than this. Ex. 7d, 12h
+    #[clap(long, value_name = "AGE", value_parser = parse_age)]
+    older_than: Option<Seconds>,
+    /// Remove the oldest entries until the cache fits in this size. Ex.
+    /// 200MB, 1GB
+    #[clap(long, value_name = "SIZE", value_parser = parse_size)]
+    max_size: Option<u64>,
+    /// List the number of entries and space to be reclaimed without removing
+    /// them
+    #[clap(long)]
+    dry_run: bool,
+}
+
+/// Parses a size in bytes with an optional B, KB, MB or GB suffix. Units are
+/// powers of 1024 as displayed by `gr cache info`.
+fn parse_size(size: &str) -> Result<u64, String> {
+    let trimmed = size.trim().to_uppercase();
+    let digits = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(digits);
+    let multiplier = match unit.trim() {
+        "" | "B" => 1,
+        "K" | "KB" => 1024,
+        "M" | "MB" => 1024 * 1024,
+        "G" | "GB" => 1024 * 1024 * 1024,
+        _ => return Err(format!("Invalid size {}. Ex. 200MB, 1GB", size)),
+    };
+    let number = number
+        .parse::<u64>()
+        .map_err(|_| format!("Invalid size {}. Ex. 200MB, 1GB", size))?;
+    Ok(number * multiplier)
 }
 
 pub enum CacheOptions {
     Info,
+    Prune(PruneOptions),
+    RefreshUser,
 }
 
 impl From<CacheCommand> for CacheOptions {
     fn from(options: CacheCommand) -> Self {
         match options.subcommand {
             CacheSubcommand::Info => CacheOptions::Info,
+            CacheSubcommand::RefreshUser => CacheOptions::RefreshUser,
+            CacheSubcommand::Prune(options) => CacheOptions::Prune(
+                PruneOptions::builder()
+                    .older_than(options.older_than)
+                    .max_size(options.max_size)
+                    .dry_run(options.dry_run)
+                    .build()
+                    .unwrap(),
+            ),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(Ok(100), parse_size("100"));
+        assert_eq!(Ok(512 * 1024), parse_size("512KB"));
+        assert_eq!(Ok(200 * 1024 * 1024), parse_size("200MB"));
+        assert_eq!(Ok(200 * 1024 * 1024), parse_size("200 mb"));
+        assert_eq!(Ok(1024 * 1024 * 1024), parse_size("1G"));
+        assert!(parse_size("MB").is_err());
+        assert!(parse_size("10TB").is_err());
+    }
+
+    #[test]
+    fn test_cache_prune_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "cache",
+            "prune",
+            "--older-than",
+            "7d",
+            "--max-size",
+            "200MB",
+        ]);
+        let options = match args.command {
+            Command::Cache(options) => options.into(),
+            _ => panic!("Expected CacheCommand"),
+        };
+        match options {
+            CacheOptions::Prune(options) => {
+                assert_eq!(Some(Seconds::new(7 * 86400)), options.older_than);
+                assert_eq!(Some(200 * 1024 * 1024), options.max_size);
+                assert!(!options.dry_run);
+            }
+            _ => panic!("Expected CacheOptions::Prune"),
+        }
+    }
+
+    #[test]
+    fn test_cache_prune_requires_a_criteria() {
+        assert!(Args::try_parse_from(vec!["gr", "cache", "prune", "--dry-run"]).is_err());
+    }
+
+    #[test]
+    fn test_cache_refresh_user_cli_args() {
+        let args = Args::parse_from(vec!["gr", "cache", "refresh-user"]);
+        let options = match args.command {
+            Command::Cache(options) => options.into(),
+            _ => panic!("Expected CacheCommand"),
+        };
+        assert!(matches!(options, CacheOptions::RefreshUser));
+    }
+}
@@ -2,13 +2,19 @@ use clap::{Parser, ValueEnum};
 
 use crate::{
     cmds::cicd::{
-        mermaid::ChartType, JobListCliArgs, LintFilePathArgs, RunnerListCliArgs,
-        RunnerMetadataGetCliArgs, RunnerPostDataCliArgs, RunnerStatus, RunnerType,
+        mermaid::{ChartCliArgs, ChartFormat, ChartType},
+        rules::PipelineEvent,
+        status::PipelineStatusCliArgs,
+        ArtifactListFilesCliArgs, ArtifactPruneCliArgs, FlakyTestsCliArgs, JobAction,
+        JobActionCliArgs, JobListCliArgs, JobLogCliArgs, JobLogGrepCliArgs, JobStatsCliArgs,
+        LintFilePathArgs, RunnerListCliArgs, RunnerMetadataGetCliArgs, RunnerPostDataCliArgs,
+        RunnerStatus, RunnerType, RunnerUsageCliArgs,
     },
     remote::ListRemoteCliArgs,
+    time::{Milliseconds, Seconds},
 };
 
-use super::common::{GetArgs, ListArgs};
+use super::common::{parse_age, GetArgs, ListArgs};
 
 #[derive(Parser)]
 pub struct PipelineCommand {
@@ -18,7 +24,9 @@ pub struct PipelineCommand {
 
 #[derive(Parser)]
 enum PipelineSubcommand {
-    #[clap(about = "Lint ci yml files. Default is .gitlab-ci.yml")]
+    #[clap(
+        about = "Lint ci yml files. Default is .gitlab-ci.yml. Github workflows are validated locally"
+    )]
     Lint(FilePathArgs),
     #[clap(
         about = "Get merged .gitlab-ci.yml. Total .gitlab-ci.yml result of merging included yaml pipeline files in the repository"
@@ -26,18 +34,170 @@ enum PipelineSubcommand {
     MergedCi,
     #[clap(about = "Create a Mermaid diagram of the .gitlab-ci.yml pipeline")]
     Chart(ChartArgs),
+    #[clap(about = "List the jobs of .gitlab-ci.yml a pipeline for a ref would run")]
+    Simulate(SimulateArgs),
     #[clap(about = "List pipelines")]
     List(ListArgs),
-    #[clap(subcommand, name = "jb", about = "Job operations")]
+    #[clap(
+        about = "Status of the latest pipeline of the current branch or a commit. Exits with an error if the pipeline failed"
+    )]
+    Status(PipelineStatusArgs),
+    #[clap(
+        subcommand,
+        name = "jb",
+        visible_alias = "job",
+        about = "Job operations"
+    )]
     Jobs(JobsSubCommand),
     #[clap(subcommand, name = "rn", about = "Runner operations")]
     Runners(RunnerSubCommand),
+    #[clap(subcommand, name = "artifacts", about = "Pipeline artifact operations")]
+    Artifacts(ArtifactsSubCommand),
+    #[clap(about = "Search the logs of failed jobs in recent failed pipelines")]
+    Grep(GrepJobLogs),
+    #[clap(about = "Report tests that alternate between pass and fail in recent pipelines")]
+    Flaky(FlakyTests),
+}
+
+#[derive(Parser)]
+struct PipelineStatusArgs {
+    /// Commit SHA of the pipeline. Defaults to the current branch
+    #[clap(long)]
+    sha: Option<String>,
+    /// Poll the pipeline and print job status changes until it finishes
+    #[clap(long, short)]
+    follow: bool,
+    /// Seconds to wait between polls when following the pipeline
+    #[clap(long, default_value = "10", value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+impl From<PipelineStatusArgs> for PipelineOptions {
+    fn from(options: PipelineStatusArgs) -> Self {
+        PipelineOptions::Status(
+            PipelineStatusCliArgs::builder()
+                .sha(options.sha)
+                .follow(options.follow)
+                .interval(Seconds::from(options.interval))
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+#[derive(Parser)]
+struct GrepJobLogs {
+    /// Regular expression to search for. Ex. OOMKilled
+    #[clap()]
+    pattern: String,
+    /// Number of most recent failed pipelines to search
+    #[clap(long, default_value = "20", value_name = "PIPELINES")]
+    last: u32,
+    /// Maximum number of job logs downloaded in parallel
+    #[clap(long, default_value = "4", value_parser = clap::value_parser!(u16).range(1..))]
+    parallel: u16,
+    /// Wait time in milliseconds between each batch of parallel downloads
+    #[clap(long, value_name = "MILLISECONDS")]
+    throttle: Option<u64>,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct FlakyTests {
+    /// Number of most recent finished pipelines to analyze. Gitlab reads
+    /// the JUnit test reports, Github parses the test output in job logs.
+    #[clap(long, default_value = "20", value_name = "PIPELINES")]
+    last: u32,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+enum ArtifactsSubCommand {
+    #[clap(about = "Delete artifacts older than a given age")]
+    Prune(PruneArtifacts),
+    #[clap(about = "List the files of an artifact archive without downloading it")]
+    Ls(ListArtifactFiles),
+}
+
+#[derive(Parser)]
+struct ListArtifactFiles {
+    /// Artifact ID. Job ID in Gitlab
+    #[clap()]
+    id: i64,
+    /// Write this file of the archive to STDOUT instead of listing
+    #[clap(long)]
+    path: Option<String>,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct PruneArtifacts {
+    /// Delete artifacts older than this. Ex. 30d, 12h
+    #[clap(long, value_name = "AGE", value_parser = parse_age)]
+    older_than: Seconds,
+    /// List the artifacts to be deleted and the space to be reclaimed
+    /// without deleting them
+    #[clap(long)]
+    dry_run: bool,
+    /// Delete without prompting for confirmation
+    #[clap(long, short)]
+    yes: bool,
+    #[clap(flatten)]
+    get_args: GetArgs,
 }
 
 #[derive(Parser)]
 enum JobsSubCommand {
     #[clap(about = "List jobs")]
     List(ListJob),
+    #[clap(about = "Queue wait and run time per job over recent pipelines")]
+    Stats(JobStats),
+    #[clap(about = "Print the log of a job")]
+    Log(JobLog),
+    #[clap(about = "Retry a job")]
+    Retry(JobId),
+    #[clap(about = "Cancel a job. Github cancels the workflow run of the job")]
+    Cancel(JobId),
+    #[clap(about = "Start a manual job. Gitlab only")]
+    Play(JobId),
+}
+
+#[derive(Parser)]
+struct JobLog {
+    /// Job ID
+    #[clap()]
+    id: i64,
+    /// Keep printing the log as it grows until the job finishes
+    #[clap(long, short)]
+    follow: bool,
+    /// Seconds to wait between polls when following the log
+    #[clap(long, default_value = "5", value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+}
+
+#[derive(Parser)]
+struct JobId {
+    /// Job ID
+    #[clap()]
+    id: i64,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct JobStats {
+    /// Number of most recent finished pipelines to analyze. Long queue
+    /// times point to a lack of runners for a job
+    #[clap(long, default_value = "20", value_name = "PIPELINES")]
+    last: u32,
+    #[clap(flatten)]
+    get_args: GetArgs,
 }
 
 #[derive(Parser)]
@@ -58,6 +218,31 @@ struct ChartArgs {
     /// Chart variant. Stages with jobs, stages or just jobs
     #[clap(long, default_value = "stageswithjobs")]
     chart_type: ChartTypeCli,
+    /// Output format. Json emits the parsed stages, jobs and rules instead of
+    /// the mermaid diagram
+    #[clap(long, default_value = "mermaid")]
+    format: ChartFormatCli,
+    /// Simulate a push to this branch. Jobs its `workflow:` and `rules:`
+    /// exclude are left out and manual jobs are annotated
+    #[clap(long = "ref", value_name = "REF")]
+    git_ref: Option<String>,
+}
+
+#[derive(Parser)]
+struct SimulateArgs {
+    /// Branch the pipeline runs for
+    #[clap(long = "ref", value_name = "REF")]
+    git_ref: String,
+    /// Simulate a merge request pipeline from the branch to the default
+    /// branch instead of a push to it
+    #[clap(long)]
+    mr: bool,
+}
+
+#[derive(ValueEnum, Clone, PartialEq, Debug)]
+enum ChartFormatCli {
+    Mermaid,
+    Json,
 }
 
 #[derive(ValueEnum, Clone, PartialEq, Debug)]
@@ -69,6 +254,7 @@ enum ChartTypeCli {
 }
 
 #[derive(Parser)]
+#[allow(clippy::large_enum_variant)]
 enum RunnerSubCommand {
     #[clap(about = "List runners")]
     List(ListRunner),
@@ -76,6 +262,8 @@ enum RunnerSubCommand {
     Get(RunnerMetadata),
     #[clap(about = "Create a new runner")]
     Create(RunnerPostData),
+    #[clap(about = "Report CI/CD minutes consumed per project in a group or organization")]
+    Usage(RunnerUsageArgs),
 }
 
 #[derive(ValueEnum, Clone, PartialEq, Debug)]
@@ -96,12 +284,25 @@ struct ListRunner {
     #[clap(long, value_delimiter = ',', help_heading = "Runner options")]
     tags: Option<Vec<String>>,
     /// List all runners available across all projects. Gitlab admins only.
-    #[clap(long, help_heading = "Runner options")]
+    #[clap(long, help_heading = "Runner options", conflicts_with = "group")]
     all: bool,
+    /// List the runners available to a group, including its shared runners.
+    /// Gitlab only.
+    #[clap(long, value_name = "GROUP", help_heading = "Runner options")]
+    group: Option<String>,
     #[command(flatten)]
     list_args: ListArgs,
 }
 
+#[derive(Parser)]
+struct RunnerUsageArgs {
+    /// Gitlab group or Github organization
+    #[clap(long, value_name = "GROUP")]
+    group: String,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
 #[derive(Parser)]
 struct RunnerMetadata {
     /// Runner ID
@@ -170,9 +371,32 @@ impl From<ChartTypeCli> for ChartType {
     }
 }
 
-impl From<ChartArgs> for ChartType {
+impl From<ChartFormatCli> for ChartFormat {
+    fn from(format: ChartFormatCli) -> Self {
+        match format {
+            ChartFormatCli::Mermaid => ChartFormat::Mermaid,
+            ChartFormatCli::Json => ChartFormat::Json,
+        }
+    }
+}
+
+impl From<ChartArgs> for ChartCliArgs {
     fn from(args: ChartArgs) -> Self {
-        args.chart_type.into()
+        ChartCliArgs {
+            chart_type: args.chart_type.into(),
+            format: args.format.into(),
+            git_ref: args.git_ref,
+        }
+    }
+}
+
+impl From<SimulateArgs> for PipelineEvent {
+    fn from(args: SimulateArgs) -> Self {
+        if args.mr {
+            PipelineEvent::merge_request(&args.git_ref)
+        } else {
+            PipelineEvent::push(&args.git_ref)
+        }
     }
 }
 
@@ -188,9 +412,14 @@ impl From<PipelineCommand> for PipelineOptions {
             PipelineSubcommand::Lint(options) => options.into(),
             PipelineSubcommand::MergedCi => PipelineOptions::MergedCi,
             PipelineSubcommand::Chart(options) => PipelineOptions::Chart(options.into()),
+            PipelineSubcommand::Simulate(options) => PipelineOptions::Simulate(options.into()),
             PipelineSubcommand::List(options) => options.into(),
+            PipelineSubcommand::Status(options) => options.into(),
             PipelineSubcommand::Runners(options) => options.into(),
             PipelineSubcommand::Jobs(options) => options.into(),
+            PipelineSubcommand::Artifacts(options) => options.into(),
+            PipelineSubcommand::Grep(options) => options.into(),
+            PipelineSubcommand::Flaky(options) => options.into(),
         }
     }
 }
@@ -222,6 +451,7 @@ impl From<RunnerSubCommand> for PipelineOptions {
             RunnerSubCommand::List(options) => PipelineOptions::Runners(options.into()),
             RunnerSubCommand::Get(options) => PipelineOptions::Runners(options.into()),
             RunnerSubCommand::Create(options) => PipelineOptions::Runners(options.into()),
+            RunnerSubCommand::Usage(options) => PipelineOptions::Runners(options.into()),
         }
     }
 }
@@ -245,6 +475,7 @@ impl From<ListRunner> for RunnerOptions {
                 .status(options.status.into())
                 .tags(options.tags.map(|tags| tags.join(",").to_string()))
                 .all(options.all)
+                .group(options.group)
                 .list_args(options.list_args.into())
                 .build()
                 .unwrap(),
@@ -252,6 +483,18 @@ impl From<ListRunner> for RunnerOptions {
     }
 }
 
+impl From<RunnerUsageArgs> for RunnerOptions {
+    fn from(options: RunnerUsageArgs) -> Self {
+        RunnerOptions::Usage(
+            RunnerUsageCliArgs::builder()
+                .group(options.group)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 impl From<RunnerMetadata> for RunnerOptions {
     fn from(options: RunnerMetadata) -> Self {
         RunnerOptions::Get(
@@ -305,31 +548,156 @@ impl From<ListJob> for JobOptions {
     }
 }
 
+impl From<JobStats> for JobOptions {
+    fn from(options: JobStats) -> Self {
+        JobOptions::Stats(
+            JobStatsCliArgs::builder()
+                .last(options.last)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<JobLog> for JobOptions {
+    fn from(options: JobLog) -> Self {
+        JobOptions::Log(
+            JobLogCliArgs::builder()
+                .id(options.id)
+                .follow(options.follow)
+                .interval(Seconds::from(options.interval))
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+fn job_action(action: JobAction, options: JobId) -> JobOptions {
+    JobOptions::Action(
+        JobActionCliArgs::builder()
+            .action(action)
+            .id(options.id)
+            .get_args(options.get_args.into())
+            .build()
+            .unwrap(),
+    )
+}
+
 impl From<JobsSubCommand> for PipelineOptions {
     fn from(options: JobsSubCommand) -> Self {
         match options {
             JobsSubCommand::List(options) => PipelineOptions::Jobs(options.into()),
+            JobsSubCommand::Stats(options) => PipelineOptions::Jobs(options.into()),
+            JobsSubCommand::Log(options) => PipelineOptions::Jobs(options.into()),
+            JobsSubCommand::Retry(options) => {
+                PipelineOptions::Jobs(job_action(JobAction::Retry, options))
+            }
+            JobsSubCommand::Cancel(options) => {
+                PipelineOptions::Jobs(job_action(JobAction::Cancel, options))
+            }
+            JobsSubCommand::Play(options) => {
+                PipelineOptions::Jobs(job_action(JobAction::Play, options))
+            }
+        }
+    }
+}
+
+impl From<GrepJobLogs> for PipelineOptions {
+    fn from(options: GrepJobLogs) -> Self {
+        PipelineOptions::Grep(
+            JobLogGrepCliArgs::builder()
+                .pattern(options.pattern)
+                .last(options.last)
+                .parallel(options.parallel as usize)
+                .throttle(options.throttle.map(Milliseconds::from))
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<FlakyTests> for PipelineOptions {
+    fn from(options: FlakyTests) -> Self {
+        PipelineOptions::Flaky(
+            FlakyTestsCliArgs::builder()
+                .last(options.last)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<ArtifactsSubCommand> for PipelineOptions {
+    fn from(options: ArtifactsSubCommand) -> Self {
+        match options {
+            ArtifactsSubCommand::Prune(options) => PipelineOptions::Artifacts(options.into()),
+            ArtifactsSubCommand::Ls(options) => PipelineOptions::Artifacts(options.into()),
         }
     }
 }
 
+impl From<ListArtifactFiles> for ArtifactOptions {
+    fn from(options: ListArtifactFiles) -> Self {
+        ArtifactOptions::ListFiles(
+            ArtifactListFilesCliArgs::builder()
+                .id(options.id)
+                .path(options.path)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<PruneArtifacts> for ArtifactOptions {
+    fn from(options: PruneArtifacts) -> Self {
+        ArtifactOptions::Prune(
+            ArtifactPruneCliArgs::builder()
+                .older_than(options.older_than)
+                .dry_run(options.dry_run)
+                .yes(options.yes)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 pub enum PipelineOptions {
     Lint(LintFilePathArgs),
     List(ListRemoteCliArgs),
+    Status(PipelineStatusCliArgs),
     Runners(RunnerOptions),
     MergedCi,
-    Chart(ChartType),
+    Chart(ChartCliArgs),
+    Simulate(PipelineEvent),
     Jobs(JobOptions),
+    Artifacts(ArtifactOptions),
+    Grep(JobLogGrepCliArgs),
+    Flaky(FlakyTestsCliArgs),
+}
+
+pub enum ArtifactOptions {
+    Prune(ArtifactPruneCliArgs),
+    ListFiles(ArtifactListFilesCliArgs),
 }
 
 pub enum JobOptions {
     List(JobListCliArgs),
+    Stats(JobStatsCliArgs),
+    Log(JobLogCliArgs),
+    Action(JobActionCliArgs),
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum RunnerOptions {
     List(RunnerListCliArgs),
     Get(RunnerMetadataGetCliArgs),
     Create(RunnerPostDataCliArgs),
+    Usage(RunnerUsageCliArgs),
 }
 
 #[cfg(test)]
@@ -393,7 +761,7 @@ mod test {
                     options.tags,
                     Some(vec!["tag1".to_string(), "tag2".to_string()])
                 );
-                assert_eq!(options.all, true);
+                assert!(options.all);
                 assert_eq!(options.list_args.from_page, Some(1));
                 assert_eq!(options.list_args.to_page, Some(2));
                 options
@@ -405,7 +773,7 @@ mod test {
             RunnerOptions::List(args) => {
                 assert_eq!(args.status, RunnerStatus::Online);
                 assert_eq!(args.tags, Some("tag1,tag2".to_string()));
-                assert_eq!(args.all, true);
+                assert!(args.all);
                 assert_eq!(args.list_args.from_page, Some(1));
                 assert_eq!(args.list_args.to_page, Some(2));
             }
@@ -413,6 +781,174 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_pipeline_cli_group_runners_list() {
+        let args = Args::parse_from(vec!["gr", "pp", "rn", "list", "all", "--group", "gitar"]);
+        let list_args = match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Runners(RunnerSubCommand::List(options)),
+            }) => options,
+            _ => panic!("Expected PipelineCommand"),
+        };
+        let options: RunnerOptions = list_args.into();
+        match options {
+            RunnerOptions::List(args) => {
+                assert_eq!(args.group, Some("gitar".to_string()));
+                assert!(!args.all);
+            }
+            _ => panic!("Expected RunnerOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_cli_group_and_all_runners_conflict() {
+        assert!(Args::try_parse_from(vec![
+            "gr", "pp", "rn", "list", "all", "--group", "gitar", "--all"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_pipeline_cli_runners_usage() {
+        let args = Args::parse_from(vec!["gr", "pp", "rn", "usage", "--group", "gitar"]);
+        let usage_args = match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Runners(RunnerSubCommand::Usage(options)),
+            }) => options,
+            _ => panic!("Expected PipelineCommand"),
+        };
+        let options: RunnerOptions = usage_args.into();
+        match options {
+            RunnerOptions::Usage(args) => {
+                assert_eq!(args.group, "gitar");
+            }
+            _ => panic!("Expected RunnerOptions::Usage"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_artifacts_prune_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pp",
+            "artifacts",
+            "prune",
+            "--older-than",
+            "30d",
+            "--dry-run",
+        ]);
+        let prune_args = match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Artifacts(ArtifactsSubCommand::Prune(options)),
+            }) => options,
+            _ => panic!("Expected PipelineCommand"),
+        };
+        let options: PipelineOptions = ArtifactsSubCommand::Prune(prune_args).into();
+        match options {
+            PipelineOptions::Artifacts(ArtifactOptions::Prune(args)) => {
+                assert_eq!(Seconds::new(30 * 86400), args.older_than);
+                assert!(args.dry_run);
+                assert!(!args.yes);
+            }
+            _ => panic!("Expected PipelineOptions::Artifacts"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_artifacts_ls_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pp",
+            "artifacts",
+            "ls",
+            "23",
+            "--path",
+            "target/report.txt",
+        ]);
+        let ls_args = match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Artifacts(ArtifactsSubCommand::Ls(options)),
+            }) => options,
+            _ => panic!("Expected PipelineCommand"),
+        };
+        let options: PipelineOptions = ArtifactsSubCommand::Ls(ls_args).into();
+        match options {
+            PipelineOptions::Artifacts(ArtifactOptions::ListFiles(args)) => {
+                assert_eq!(23, args.id);
+                assert_eq!(Some("target/report.txt".to_string()), args.path);
+            }
+            _ => panic!("Expected PipelineOptions::Artifacts"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_grep_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pp",
+            "grep",
+            "OOMKilled",
+            "--last",
+            "10",
+            "--throttle",
+            "500",
+        ]);
+        let grep_args = match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Grep(options),
+            }) => options,
+            _ => panic!("Expected PipelineCommand"),
+        };
+        let options: PipelineOptions = grep_args.into();
+        match options {
+            PipelineOptions::Grep(args) => {
+                assert_eq!("OOMKilled", args.pattern);
+                assert_eq!(10, args.last);
+                assert_eq!(4, args.parallel);
+                assert_eq!(Some(Milliseconds::new(500)), args.throttle);
+            }
+            _ => panic!("Expected PipelineOptions::Grep"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_flaky_cli_args() {
+        let args = Args::parse_from(vec!["gr", "pp", "flaky", "--last", "50"]);
+        let flaky_args = match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Flaky(options),
+            }) => options,
+            _ => panic!("Expected PipelineCommand"),
+        };
+        let options: PipelineOptions = flaky_args.into();
+        match options {
+            PipelineOptions::Flaky(args) => assert_eq!(50, args.last),
+            _ => panic!("Expected PipelineOptions::Flaky"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_grep_zero_parallel_is_error() {
+        assert!(Args::try_parse_from(vec!["gr", "pp", "grep", "OOM", "--parallel", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_pipeline_artifacts_prune_invalid_age() {
+        assert!(Args::try_parse_from(vec![
+            "gr",
+            "pp",
+            "artifacts",
+            "prune",
+            "--older-than",
+            "30x"
+        ])
+        .is_err());
+        assert!(
+            Args::try_parse_from(vec!["gr", "pp", "artifacts", "prune", "--older-than", "0d"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_get_gitlab_runner_metadata() {
         let args = Args::parse_from(vec!["gr", "pp", "rn", "get", "123"]);
@@ -545,12 +1081,54 @@ mod test {
         let options: PipelineOptions = options.into();
         match options {
             PipelineOptions::Chart(args) => {
-                assert_eq!(args, ChartType::StagesWithJobs);
+                assert_eq!(args.chart_type, ChartType::StagesWithJobs);
+                assert_eq!(args.format, ChartFormat::Mermaid);
             }
             _ => panic!("Expected PipelineOptions::Chart"),
         }
     }
 
+    #[test]
+    fn test_chart_cli_args_json_format() {
+        let args = Args::parse_from(vec!["gr", "pp", "chart", "--format", "json"]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Chart(args) => assert_eq!(args.format, ChartFormat::Json),
+            _ => panic!("Expected PipelineOptions::Chart"),
+        }
+    }
+
+    #[test]
+    fn test_chart_cli_args_simulate_ref() {
+        let args = Args::parse_from(vec!["gr", "pp", "chart", "--ref", "main"]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Chart(args) => assert_eq!(Some("main".to_string()), args.git_ref),
+            _ => panic!("Expected PipelineOptions::Chart"),
+        }
+    }
+
+    #[test]
+    fn test_simulate_merge_request_pipeline_cli_args() {
+        let args = Args::parse_from(vec!["gr", "pp", "simulate", "--ref", "feature/x", "--mr"]);
+        let options: PipelineOptions = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Simulate(event) => {
+                assert_eq!(PipelineEvent::merge_request("feature/x"), event)
+            }
+            _ => panic!("Expected PipelineOptions::Simulate"),
+        }
+    }
+
     #[test]
     fn test_pipeline_cli_jobs_list() {
         let args = Args::parse_from(vec![
@@ -580,6 +1158,97 @@ mod test {
                 assert_eq!(args.list_args.from_page, Some(1));
                 assert_eq!(args.list_args.to_page, Some(2));
             }
+            _ => panic!("Expected JobOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_job_stats_cli_args() {
+        let args = Args::parse_from(vec!["gr", "pp", "jb", "stats", "--last", "5"]);
+        let options = match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Jobs(options),
+            }) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Jobs(JobOptions::Stats(args)) => assert_eq!(5, args.last),
+            _ => panic!("Expected JobOptions::Stats"),
+        }
+    }
+
+    #[test]
+    fn test_pipeline_status_cli_args() {
+        let args = Args::parse_from(vec!["gr", "pp", "status", "--sha", "abc123", "-f"]);
+        let options = match args.command {
+            Command::Pipeline(options) => options.into(),
+            _ => panic!("Expected PipelineCommand"),
+        };
+        match options {
+            PipelineOptions::Status(args) => {
+                assert_eq!(Some("abc123".to_string()), args.sha);
+                assert!(args.follow);
+                assert_eq!(Seconds::new(10), args.interval);
+            }
+            _ => panic!("Expected PipelineOptions::Status"),
+        }
+    }
+
+    fn job_options(args: Vec<&str>) -> JobOptions {
+        let args = Args::parse_from(args);
+        match args.command {
+            Command::Pipeline(PipelineCommand {
+                subcommand: PipelineSubcommand::Jobs(options),
+            }) => match options.into() {
+                PipelineOptions::Jobs(options) => options,
+                _ => panic!("Expected PipelineOptions::Jobs"),
+            },
+            _ => panic!("Expected PipelineCommand"),
+        }
+    }
+
+    #[test]
+    fn test_job_log_cli_args() {
+        match job_options(vec![
+            "gr",
+            "pp",
+            "job",
+            "log",
+            "42",
+            "-f",
+            "--interval",
+            "2",
+        ]) {
+            JobOptions::Log(args) => {
+                assert_eq!(42, args.id);
+                assert!(args.follow);
+                assert_eq!(Seconds::new(2), args.interval);
+            }
+            _ => panic!("Expected JobOptions::Log"),
+        }
+        match job_options(vec!["gr", "pp", "jb", "log", "42"]) {
+            JobOptions::Log(args) => {
+                assert!(!args.follow);
+                assert_eq!(Seconds::new(5), args.interval);
+            }
+            _ => panic!("Expected JobOptions::Log"),
+        }
+    }
+
+    #[test]
+    fn test_job_action_cli_args() {
+        for (command, action) in [
+            ("retry", JobAction::Retry),
+            ("cancel", JobAction::Cancel),
+            ("play", JobAction::Play),
+        ] {
+            match job_options(vec!["gr", "pp", "job", command, "42"]) {
+                JobOptions::Action(args) => {
+                    assert_eq!(action, args.action);
+                    assert_eq!(42, args.id);
+                }
+                _ => panic!("Expected JobOptions::Action"),
+            }
         }
     }
 
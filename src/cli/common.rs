@@ -3,9 +3,11 @@ use std::fmt::{self, Display, Formatter};
 use clap::{Parser, ValueEnum};
 
 use crate::{
-    display::Format,
-    remote::{CacheCliArgs, GetRemoteCliArgs, ListRemoteCliArgs, ListSortMode},
-    time::Milliseconds,
+    api_defaults::MAX_PER_PAGE,
+    auth::CredentialHelperAction,
+    display::{self, ColumnTransform, Format},
+    remote::{CacheCliArgs, GetRemoteCliArgs, ListOrderBy, ListRemoteCliArgs, ListSortMode},
+    time::{self, Milliseconds, Seconds},
 };
 
 #[derive(Clone, Parser)]
@@ -23,17 +25,30 @@ pub struct ListArgs {
     /// How many pages are available
     #[clap(long)]
     num_pages: bool,
-    /// How many resources are available. Result is an approximation depending
-    /// on total pages and default per_page query param. Total given as an
-    /// interval (min, max)
+    /// How many resources are available. Exact when the remote gives the
+    /// total count. Otherwise an approximation depending on total pages and
+    /// default per_page query param, given as an interval (min, max)
     #[clap(long)]
     pub num_resources: bool,
-    /// Created after date (ISO 8601 YYYY-MM-DDTHH:MM:SSZ)
-    #[clap(long)]
+    /// Number of results per page. Defaults to the per_page set in the
+    /// config file for the domain or to 100, the max allowed by the remote
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=MAX_PER_PAGE as i64), conflicts_with_all = ["num_pages", "num_resources"])]
+    per_page: Option<u32>,
+    /// Created after date. ISO 8601 YYYY-MM-DDTHH:MM:SSZ, YYYY-MM-DD,
+    /// YYYY-MM, today, yesterday or relative, ex. "2 weeks ago"
+    #[clap(long, value_name = "DATE", value_parser = parse_date)]
     created_after: Option<String>,
-    /// Created before date (ISO 8601 YYYY-MM-DDTHH:MM:SSZ)
-    #[clap(long)]
+    /// Created before date. Same formats as --created-after
+    #[clap(long, value_name = "DATE", value_parser = parse_date)]
     created_before: Option<String>,
+    /// Updated after date. Same formats as --created-after. Merge requests
+    /// and pipelines only
+    #[clap(long, value_name = "DATE", value_parser = parse_date)]
+    updated_after: Option<String>,
+    /// Updated before date. Same formats as --created-after. Merge requests
+    /// and pipelines only
+    #[clap(long, value_name = "DATE", value_parser = parse_date)]
+    updated_before: Option<String>,
     /// Flush results to STDOUT as they are received. No sorting and no date
     /// filtering is applied
     #[clap(long, visible_alias = "flush")]
@@ -85,6 +100,19 @@ pub struct FormatArgs {
     /// Display additional fields
     #[clap(visible_short_alias = 'o', long)]
     pub more_output: bool,
+    /// Transform the values of a column. Transforms are truncate:N,
+    /// strip-markdown and url-shorten. Can be repeated. Ex. --transform
+    /// title=truncate:40 --transform url=url-shorten
+    #[clap(long = "transform", value_name = "COLUMN=TRANSFORM", value_parser = parse_transform)]
+    pub transforms: Vec<(String, ColumnTransform)>,
+    /// Print values as they are, ignoring any --transform. Handy when
+    /// transforms are set in a shell alias
+    #[clap(long)]
+    pub full: bool,
+    /// Print durations in seconds and counts as plain numbers instead of
+    /// 4m 32s or 1.2k
+    #[clap(long)]
+    pub raw_numbers: bool,
 }
 
 #[derive(Clone, Parser)]
@@ -104,8 +132,17 @@ pub struct RetryArgs {
 
 #[derive(ValueEnum, Clone, Debug)]
 pub enum FormatCli {
+    /// RFC 4180 CSV, to be imported in spreadsheets
     Csv,
+    /// Lists are an array of objects, one per line, and get commands an
+    /// object. Keys are the lowercase field names with spaces replaced by
+    /// underscores, ex. "created_at"
     Json,
+    /// One JSON object per line, keys are the lowercase field names as they
+    /// are, ex. "created at". This was the json format before it printed
+    /// arrays
+    Ndjson,
+    /// Fields separated by |, for reading in the terminal
     Pipe,
     Toml,
 }
@@ -116,17 +153,42 @@ impl Display for FormatCli {
             FormatCli::Csv => write!(f, "csv"),
             FormatCli::Pipe => write!(f, "pipe"),
             FormatCli::Json => write!(f, "json"),
+            FormatCli::Ndjson => write!(f, "ndjson"),
             FormatCli::Toml => write!(f, "toml"),
         }
     }
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum CredentialHelperActionCli {
+    Get,
+    Store,
+    Erase,
+}
+
+impl From<CredentialHelperActionCli> for CredentialHelperAction {
+    fn from(action: CredentialHelperActionCli) -> Self {
+        match action {
+            CredentialHelperActionCli::Get => CredentialHelperAction::Get,
+            CredentialHelperActionCli::Store => CredentialHelperAction::Store,
+            CredentialHelperActionCli::Erase => CredentialHelperAction::Erase,
+        }
+    }
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 enum SortModeCli {
     Asc,
     Desc,
 }
 
+#[derive(ValueEnum, Clone, Debug, PartialEq)]
+pub enum OrderByCli {
+    Created,
+    Updated,
+    Title,
+}
+
 impl Display for SortModeCli {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -146,6 +208,9 @@ impl From<ListArgs> for ListRemoteCliArgs {
             .num_resources(args.num_resources)
             .created_after(args.created_after)
             .created_before(args.created_before)
+            .updated_after(args.updated_after)
+            .updated_before(args.updated_before)
+            .per_page(args.per_page)
             .sort(args.sort.into())
             .get_args(args.get_args.into())
             .flush(args.stream)
@@ -159,12 +224,27 @@ impl From<ListArgs> for ListRemoteCliArgs {
     }
 }
 
+impl ListArgs {
+    /// List options for lists the remote can order by the given field.
+    pub fn ordered_by(self, order_by: Option<OrderByCli>) -> ListRemoteCliArgs {
+        let mut list_args: ListRemoteCliArgs = self.into();
+        list_args.order_by = order_by.map(|order_by| order_by.into());
+        list_args
+    }
+}
+
 impl From<GetArgs> for GetRemoteCliArgs {
     fn from(args: GetArgs) -> Self {
         GetRemoteCliArgs::builder()
             .no_headers(args.format_args.no_headers)
             .format(args.format_args.format.into())
             .display_optional(args.format_args.more_output)
+            .transforms(if args.format_args.full {
+                Vec::new()
+            } else {
+                args.format_args.transforms
+            })
+            .raw_numbers(args.format_args.raw_numbers)
             .cache_args(args.cache_args.into())
             .backoff_max_retries(args.retry_args.max_retries)
             .backoff_retry_after(args.retry_args.retry_after)
@@ -188,12 +268,23 @@ impl From<FormatCli> for Format {
         match format {
             FormatCli::Csv => Format::CSV,
             FormatCli::Json => Format::JSON,
+            FormatCli::Ndjson => Format::NDJSON,
             FormatCli::Pipe => Format::PIPE,
             FormatCli::Toml => Format::TOML,
         }
     }
 }
 
+impl From<OrderByCli> for ListOrderBy {
+    fn from(order_by: OrderByCli) -> Self {
+        match order_by {
+            OrderByCli::Created => ListOrderBy::Created,
+            OrderByCli::Updated => ListOrderBy::Updated,
+            OrderByCli::Title => ListOrderBy::Title,
+        }
+    }
+}
+
 impl From<SortModeCli> for ListSortMode {
     fn from(sort: SortModeCli) -> Self {
         match sort {
@@ -221,12 +312,36 @@ pub fn validate_domain_project_repo_path(path: &str) -> Result<String, String> {
     }
 }
 
-fn fields(path: &str) -> (std::str::Split<char>, usize) {
+fn fields(path: &str) -> (std::str::Split<'_, char>, usize) {
     let fields = path.split('/');
     let empty_fields = fields.clone().filter(|f| f.is_empty()).count();
     (fields, empty_fields)
 }
 
+pub fn validate_date(date: &str) -> Result<String, String> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|_| date.to_string())
+        .map_err(|_| format!("Invalid date {}. Expected format is YYYY-MM-DD", date))
+}
+
+/// Parses a non-zero age. Ex. 30d, 12h
+pub fn parse_age(age: &str) -> Result<Seconds, String> {
+    let seconds = Seconds::try_from(age).map_err(|e| e.to_string())?;
+    if *seconds == 0 {
+        return Err(format!("Invalid age {}. Ex. 30d, 12h", age));
+    }
+    Ok(seconds)
+}
+
+fn parse_transform(arg: &str) -> Result<(String, ColumnTransform), String> {
+    display::parse_column_transform(arg).map_err(|e| e.to_string())
+}
+
+/// Parses a date filter into RFC 3339. Ex. 2024-06, yesterday, 2 weeks ago
+fn parse_date(date: &str) -> Result<String, String> {
+    time::parse_human_date(date, chrono::Local::now()).map_err(|e| e.to_string())
+}
+
 fn parse_throttle_range(s: &str) -> Result<(u64, u64), String> {
     let parts: Vec<&str> = s.split('-').collect();
     if parts.len() != 2 {
@@ -244,6 +359,13 @@ fn parse_throttle_range(s: &str) -> Result<(u64, u64), String> {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_validate_date() {
+        assert!(validate_date("2024-02-12").is_ok());
+        assert!(validate_date("2024-02-30").is_err());
+        assert!(validate_date("12-02-2024").is_err());
+    }
+
     #[test]
     fn test_validate_project_repo_path() {
         assert!(validate_project_repo_path("owner/project").is_ok());
@@ -265,6 +387,17 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            Ok("2024-01-01T00:00:00Z".to_string()),
+            parse_date("2024-01-01T00:00:00Z")
+        );
+        assert!(parse_date("2 weeks ago").is_ok());
+        let err = parse_date("01/02/2024").unwrap_err();
+        assert!(err.contains("use YYYY-MM-DD"));
+    }
+
     #[test]
     fn test_valid_throttle_range() {
         assert_eq!(parse_throttle_range("100-500"), Ok((100, 500)));
@@ -311,4 +444,33 @@ mod test {
             Err("MIN must be less than MAX".to_string())
         );
     }
+
+    #[derive(Parser)]
+    struct GetCommand {
+        #[clap(flatten)]
+        get_args: GetArgs,
+    }
+
+    #[test]
+    fn test_transforms_are_ignored_with_full() {
+        let argv = vec![
+            "get",
+            "--transform",
+            "title=truncate:10",
+            "--transform",
+            "url=url-shorten",
+        ];
+        let args: GetRemoteCliArgs = GetCommand::parse_from(argv.clone()).get_args.into();
+        assert_eq!(
+            vec![
+                ("title".to_string(), ColumnTransform::Truncate(10)),
+                ("url".to_string(), ColumnTransform::UrlShorten)
+            ],
+            args.transforms
+        );
+        let args: GetRemoteCliArgs = GetCommand::parse_from([argv, vec!["--full"]].concat())
+            .get_args
+            .into();
+        assert!(args.transforms.is_empty());
+    }
 }
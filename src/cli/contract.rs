@@ -0,0 +1,96 @@
+use clap::{Parser, ValueEnum};
+
+use crate::cmds::contract::{ContractOperation, RecordContractCliArgs};
+
+#[derive(Parser)]
+pub struct ContractCommand {
+    #[clap(subcommand)]
+    subcommand: ContractSubCommand,
+}
+
+#[derive(Parser)]
+enum ContractSubCommand {
+    #[clap(about = "Record the JSON contract of an API operation from the live remote")]
+    Record(RecordContract),
+}
+
+#[derive(Parser)]
+struct RecordContract {
+    /// API operation to record
+    #[clap()]
+    operation: ContractOperationCli,
+    /// Id of the merge request. Required for merge-request and comment
+    #[clap(long)]
+    id: Option<i64>,
+    /// Directory holding the contracts of each provider
+    #[clap(long, value_name = "PATH", default_value = "contracts")]
+    dir: String,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum ContractOperationCli {
+    Project,
+    MergeRequest,
+    Comment,
+    AuthUser,
+}
+
+impl From<ContractOperationCli> for ContractOperation {
+    fn from(operation: ContractOperationCli) -> Self {
+        match operation {
+            ContractOperationCli::Project => ContractOperation::Project,
+            ContractOperationCli::MergeRequest => ContractOperation::MergeRequest,
+            ContractOperationCli::Comment => ContractOperation::Comment,
+            ContractOperationCli::AuthUser => ContractOperation::AuthUser,
+        }
+    }
+}
+
+impl From<ContractCommand> for ContractOptions {
+    fn from(cmd: ContractCommand) -> Self {
+        match cmd.subcommand {
+            ContractSubCommand::Record(options) => options.into(),
+        }
+    }
+}
+
+impl From<RecordContract> for ContractOptions {
+    fn from(options: RecordContract) -> Self {
+        ContractOptions::Record(
+            RecordContractCliArgs::builder()
+                .operation(options.operation.into())
+                .id(options.id)
+                .dir(options.dir)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+pub enum ContractOptions {
+    Record(RecordContractCliArgs),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_contract_record_command() {
+        let args = Args::parse_from(["gr", "contract", "record", "merge-request", "--id", "33"]);
+        let contract_command = match args.command {
+            Command::Contract(cmd) => cmd,
+            _ => panic!("Expected contract command"),
+        };
+        let options: ContractOptions = contract_command.into();
+        match options {
+            ContractOptions::Record(args) => {
+                assert_eq!(ContractOperation::MergeRequest, args.operation);
+                assert_eq!(Some(33), args.id);
+                assert_eq!("contracts", args.dir);
+            }
+        }
+    }
+}
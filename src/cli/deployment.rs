@@ -0,0 +1,242 @@
+use clap::{Parser, ValueEnum};
+
+use crate::cmds::deployment::{
+    DeploymentState, DeploymentStatusCliArgs, EnvironmentRollbackCliArgs, EnvironmentStopCliArgs,
+};
+
+#[derive(Parser)]
+pub struct DeploymentCommand {
+    #[clap(subcommand)]
+    subcommand: DeploymentSubcommand,
+}
+
+#[derive(Parser)]
+enum DeploymentSubcommand {
+    #[clap(about = "Report the status of a deployment")]
+    SetStatus(SetDeploymentStatus),
+    #[clap(about = "Stop an environment. Gitlab only")]
+    Stop(StopEnvironment),
+    #[clap(about = "Rollback an environment to a previous deployment. Gitlab only")]
+    Rollback(RollbackEnvironment),
+}
+
+#[derive(Parser)]
+struct StopEnvironment {
+    /// Name of the environment
+    #[clap()]
+    environment: String,
+    /// Stop the environment without prompting for confirmation
+    #[clap(long, short)]
+    yes: bool,
+}
+
+#[derive(Parser)]
+struct RollbackEnvironment {
+    /// Name of the environment
+    #[clap()]
+    environment: String,
+    /// ID of the previous deployment to re-deploy
+    #[clap(long, value_name = "DEPLOYMENT_ID")]
+    to: i64,
+    /// Rollback without prompting for confirmation
+    #[clap(long, short)]
+    yes: bool,
+}
+
+#[derive(Parser)]
+struct SetDeploymentStatus {
+    /// Deployment ID
+    #[clap()]
+    id: i64,
+    /// State of the deployment. Gitlab maps pending, queued and in-progress to
+    /// running, failure and error to failed and inactive to canceled.
+    #[clap(long)]
+    state: DeploymentStateCli,
+    /// URL of the deployed environment. Github only.
+    #[clap(long)]
+    url: Option<String>,
+    /// Short description of the status. Github only.
+    #[clap(long)]
+    description: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Debug)]
+enum DeploymentStateCli {
+    Pending,
+    Queued,
+    InProgress,
+    Success,
+    Failure,
+    Error,
+    Inactive,
+}
+
+impl From<DeploymentStateCli> for DeploymentState {
+    fn from(state: DeploymentStateCli) -> Self {
+        match state {
+            DeploymentStateCli::Pending => DeploymentState::Pending,
+            DeploymentStateCli::Queued => DeploymentState::Queued,
+            DeploymentStateCli::InProgress => DeploymentState::InProgress,
+            DeploymentStateCli::Success => DeploymentState::Success,
+            DeploymentStateCli::Failure => DeploymentState::Failure,
+            DeploymentStateCli::Error => DeploymentState::Error,
+            DeploymentStateCli::Inactive => DeploymentState::Inactive,
+        }
+    }
+}
+
+impl From<DeploymentCommand> for DeploymentOptions {
+    fn from(options: DeploymentCommand) -> Self {
+        match options.subcommand {
+            DeploymentSubcommand::SetStatus(options) => options.into(),
+            DeploymentSubcommand::Stop(options) => options.into(),
+            DeploymentSubcommand::Rollback(options) => options.into(),
+        }
+    }
+}
+
+impl From<SetDeploymentStatus> for DeploymentOptions {
+    fn from(options: SetDeploymentStatus) -> Self {
+        DeploymentOptions::SetStatus(
+            DeploymentStatusCliArgs::builder()
+                .id(options.id)
+                .state(options.state.into())
+                .url(options.url)
+                .description(options.description)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<StopEnvironment> for DeploymentOptions {
+    fn from(options: StopEnvironment) -> Self {
+        DeploymentOptions::Stop(
+            EnvironmentStopCliArgs::builder()
+                .environment(options.environment)
+                .yes(options.yes)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<RollbackEnvironment> for DeploymentOptions {
+    fn from(options: RollbackEnvironment) -> Self {
+        DeploymentOptions::Rollback(
+            EnvironmentRollbackCliArgs::builder()
+                .environment(options.environment)
+                .deployment_id(options.to)
+                .yes(options.yes)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+pub enum DeploymentOptions {
+    SetStatus(DeploymentStatusCliArgs),
+    Stop(EnvironmentStopCliArgs),
+    Rollback(EnvironmentRollbackCliArgs),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_deployment_set_status_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "deploy",
+            "set-status",
+            "123",
+            "--state",
+            "success",
+            "--url",
+            "https://app",
+        ]);
+        let set_status = match args.command {
+            Command::Deployment(DeploymentCommand {
+                subcommand: DeploymentSubcommand::SetStatus(options),
+            }) => {
+                assert_eq!(options.id, 123);
+                assert_eq!(options.state, DeploymentStateCli::Success);
+                options
+            }
+            _ => panic!("Expected DeploymentCommand"),
+        };
+        let options: DeploymentOptions = set_status.into();
+        match options {
+            DeploymentOptions::SetStatus(args) => {
+                assert_eq!(args.id, 123);
+                assert_eq!(args.state, DeploymentState::Success);
+                assert_eq!(args.url, Some("https://app".to_string()));
+                assert_eq!(args.description, None);
+            }
+            _ => panic!("Expected DeploymentOptions::SetStatus"),
+        }
+    }
+
+    #[test]
+    fn test_deployment_set_status_in_progress() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "dp",
+            "set-status",
+            "1",
+            "--state",
+            "in-progress",
+        ]);
+        match args.command {
+            Command::Deployment(DeploymentCommand {
+                subcommand: DeploymentSubcommand::SetStatus(options),
+            }) => {
+                assert_eq!(options.state, DeploymentStateCli::InProgress);
+                assert_eq!(options.url, None);
+            }
+            _ => panic!("Expected DeploymentCommand"),
+        }
+    }
+
+    #[test]
+    fn test_deployment_stop_cli_args() {
+        let args = Args::parse_from(vec!["gr", "deploy", "stop", "production", "-y"]);
+        let stop = match args.command {
+            Command::Deployment(DeploymentCommand {
+                subcommand: DeploymentSubcommand::Stop(options),
+            }) => options,
+            _ => panic!("Expected DeploymentCommand"),
+        };
+        let options: DeploymentOptions = stop.into();
+        match options {
+            DeploymentOptions::Stop(args) => {
+                assert_eq!(args.environment, "production");
+                assert!(args.yes);
+            }
+            _ => panic!("Expected DeploymentOptions::Stop"),
+        }
+    }
+
+    #[test]
+    fn test_deployment_rollback_cli_args() {
+        let args = Args::parse_from(vec!["gr", "deploy", "rollback", "production", "--to", "41"]);
+        let rollback = match args.command {
+            Command::Deployment(DeploymentCommand {
+                subcommand: DeploymentSubcommand::Rollback(options),
+            }) => options,
+            _ => panic!("Expected DeploymentCommand"),
+        };
+        let options: DeploymentOptions = rollback.into();
+        match options {
+            DeploymentOptions::Rollback(args) => {
+                assert_eq!(args.environment, "production");
+                assert_eq!(args.deployment_id, 41);
+                assert!(!args.yes);
+            }
+            _ => panic!("Expected DeploymentOptions::Rollback"),
+        }
+    }
+}
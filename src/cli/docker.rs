@@ -1,8 +1,9 @@
 use clap::Parser;
 
+use crate::auth::CredentialHelperAction;
 use crate::cmds::docker::{DockerImageCliArgs, DockerListCliArgs};
 
-use super::common::{GetArgs, ListArgs};
+use super::common::{CredentialHelperActionCli, GetArgs, ListArgs};
 
 #[derive(Parser)]
 pub struct DockerCommand {
@@ -16,6 +17,21 @@ enum DockerSubCommand {
     List(ListDockerImages),
     #[clap(about = "Get docker image metadata")]
     Image(DockerImageMetadata),
+    #[clap(
+        name = "login-helper",
+        about = "Docker credential helper serving the API token of the registry's domain"
+    )]
+    LoginHelper(DockerLoginHelper),
+}
+
+/// Called by docker through a `docker-credential-gitar` executable in the
+/// PATH running `gr docker login-helper "$@"`, with `"credsStore": "gitar"`
+/// in ~/.docker/config.json.
+#[derive(Parser)]
+struct DockerLoginHelper {
+    /// Credential helper action, as sent by docker
+    #[clap()]
+    action: CredentialHelperActionCli,
 }
 
 #[derive(Parser)]
@@ -51,6 +67,9 @@ impl From<DockerCommand> for DockerOptions {
         match options.subcommand {
             DockerSubCommand::List(options) => options.into(),
             DockerSubCommand::Image(options) => options.into(),
+            DockerSubCommand::LoginHelper(options) => {
+                DockerOptions::LoginHelper(options.action.into())
+            }
         }
     }
 }
@@ -85,6 +104,7 @@ impl From<ListDockerImages> for DockerOptions {
 pub enum DockerOptions {
     List(DockerListCliArgs),
     Get(DockerImageCliArgs),
+    LoginHelper(CredentialHelperAction),
 }
 
 #[cfg(test)]
@@ -131,6 +151,19 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_docker_login_helper_cli_args() {
+        let args = Args::parse_from(vec!["gr", "dk", "login-helper", "get"]);
+        let options: DockerOptions = match args.command {
+            Command::Docker(options) => options.into(),
+            _ => panic!("Expected DockerCommand"),
+        };
+        match options {
+            DockerOptions::LoginHelper(action) => assert_eq!(CredentialHelperAction::Get, action),
+            _ => panic!("Expected DockerOptions::LoginHelper"),
+        }
+    }
+
     #[test]
     fn test_docker_get_image_metadata_cli_args() {
         let args = Args::parse_from(vec![
@@ -0,0 +1,95 @@
+use clap::Parser;
+
+#[derive(Parser)]
+pub struct FlowCommand {
+    #[clap(subcommand)]
+    subcommand: FlowSubCommand,
+}
+
+#[derive(Parser)]
+enum FlowSubCommand {
+    #[clap(about = "Run the steps of a flow defined in the config file")]
+    Run(RunFlow),
+}
+
+#[derive(Parser)]
+struct RunFlow {
+    /// Name of the flow, as declared in the [flow] table of the config file
+    #[clap()]
+    name: String,
+    /// Value of a variable referenced as {KEY} in the flow steps. Can be
+    /// given multiple times
+    #[clap(long = "var", value_name = "KEY=VALUE", value_parser = parse_variable)]
+    vars: Vec<(String, String)>,
+}
+
+fn parse_variable(var: &str) -> Result<(String, String), String> {
+    match var.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("Invalid variable {}. Ex. version=1.2.0", var)),
+    }
+}
+
+impl From<FlowCommand> for FlowOptions {
+    fn from(options: FlowCommand) -> Self {
+        match options.subcommand {
+            FlowSubCommand::Run(options) => FlowOptions::Run(FlowRunCliArgs {
+                name: options.name,
+                vars: options.vars,
+            }),
+        }
+    }
+}
+
+pub enum FlowOptions {
+    Run(FlowRunCliArgs),
+}
+
+pub struct FlowRunCliArgs {
+    pub name: String,
+    pub vars: Vec<(String, String)>,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_flow_run_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "flow",
+            "run",
+            "release",
+            "--var",
+            "version=1.2.0",
+            "--var",
+            "notes=a=b",
+        ]);
+        let options: FlowOptions = match args.command {
+            Command::Flow(options) => options.into(),
+            _ => panic!("Expected FlowCommand"),
+        };
+        match options {
+            FlowOptions::Run(args) => {
+                assert_eq!("release", args.name);
+                assert_eq!(
+                    vec![
+                        ("version".to_string(), "1.2.0".to_string()),
+                        ("notes".to_string(), "a=b".to_string())
+                    ],
+                    args.vars
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_flow_run_invalid_variable_is_error() {
+        assert!(
+            Args::try_parse_from(vec!["gr", "flow", "run", "release", "--var", "1.2.0"]).is_err()
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use clap::{Parser, ValueEnum};
+
+use crate::cmds::issue::{IssueBodyArgs, IssueGetCliArgs, IssueListCliArgs, IssueState};
+
+use super::common::{GetArgs, ListArgs, OrderByCli};
+
+#[derive(Parser)]
+pub struct IssueCommand {
+    #[clap(subcommand)]
+    subcommand: IssueSubcommand,
+}
+
+#[derive(Parser)]
+enum IssueSubcommand {
+    #[clap(about = "List issues")]
+    List(ListIssue),
+    #[clap(about = "Gets an issue by ID")]
+    Get(GetIssue),
+    #[clap(about = "Creates an issue")]
+    Create(CreateIssue),
+    #[clap(about = "Comment on an issue")]
+    Comment(CommentIssue),
+    #[clap(about = "Close an issue")]
+    Close(CloseIssue),
+}
+
+#[derive(ValueEnum, Clone, PartialEq, Debug)]
+enum IssueStateCli {
+    Opened,
+    Closed,
+    All,
+}
+
+impl From<IssueStateCli> for IssueState {
+    fn from(state: IssueStateCli) -> Self {
+        match state {
+            IssueStateCli::Opened => IssueState::Opened,
+            IssueStateCli::Closed => IssueState::Closed,
+            IssueStateCli::All => IssueState::All,
+        }
+    }
+}
+
+#[derive(Parser)]
+struct ListIssue {
+    #[clap()]
+    state: IssueStateCli,
+    /// Order by this field on the remote, in the --sort direction. Github
+    /// does not order by title
+    #[clap(long, value_name = "FIELD")]
+    order_by: Option<OrderByCli>,
+    #[command(flatten)]
+    list_args: ListArgs,
+}
+
+#[derive(Parser)]
+struct GetIssue {
+    /// Id of the issue
+    #[clap()]
+    id: i64,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct CreateIssue {
+    /// Title of the issue
+    #[clap(long)]
+    title: String,
+    /// Description of the issue
+    #[clap(long)]
+    description: Option<String>,
+    /// Comma separated list of labels. Ex. bug,ui
+    #[clap(long, value_delimiter = ',')]
+    label: Vec<String>,
+}
+
+#[derive(Parser)]
+struct CommentIssue {
+    /// Id of the issue
+    #[clap()]
+    id: i64,
+    /// Comment to add to the issue
+    #[clap(group = "comment_msg", required = true)]
+    comment: Option<String>,
+    /// Gather comment from the specified file. If "-" is provided, read from STDIN
+    #[clap(long, value_name = "FILE", group = "comment_msg")]
+    comment_from_file: Option<String>,
+}
+
+#[derive(Parser)]
+struct CloseIssue {
+    /// Id of the issue
+    #[clap()]
+    id: i64,
+}
+
+impl From<IssueCommand> for IssueOptions {
+    fn from(options: IssueCommand) -> Self {
+        match options.subcommand {
+            IssueSubcommand::List(options) => IssueOptions::List(
+                IssueListCliArgs::builder()
+                    .state(options.state.into())
+                    .list_args(options.list_args.ordered_by(options.order_by))
+                    .build()
+                    .unwrap(),
+            ),
+            IssueSubcommand::Get(options) => IssueOptions::Get(
+                IssueGetCliArgs::builder()
+                    .id(options.id)
+                    .get_args(options.get_args.into())
+                    .build()
+                    .unwrap(),
+            ),
+            IssueSubcommand::Create(options) => IssueOptions::Create(
+                IssueBodyArgs::builder()
+                    .title(options.title)
+                    .description(options.description.unwrap_or_default())
+                    .labels(options.label)
+                    .build()
+                    .unwrap(),
+            ),
+            IssueSubcommand::Comment(options) => IssueOptions::Comment {
+                id: options.id,
+                comment: options.comment,
+                comment_from_file: options.comment_from_file,
+            },
+            IssueSubcommand::Close(options) => IssueOptions::Close { id: options.id },
+        }
+    }
+}
+
+pub enum IssueOptions {
+    List(IssueListCliArgs),
+    Get(IssueGetCliArgs),
+    Create(IssueBodyArgs),
+    Comment {
+        id: i64,
+        comment: Option<String>,
+        comment_from_file: Option<String>,
+    },
+    Close {
+        id: i64,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    fn issue_options(args: Vec<&str>) -> IssueOptions {
+        let args = Args::parse_from(args);
+        match args.command {
+            Command::Issue(options) => options.into(),
+            _ => panic!("Expected IssueCommand"),
+        }
+    }
+
+    #[test]
+    fn test_issue_list_cli_args() {
+        match issue_options(vec!["gr", "issue", "list", "closed", "--from-page", "2"]) {
+            IssueOptions::List(args) => {
+                assert_eq!(IssueState::Closed, args.state);
+                assert_eq!(Some(2), args.list_args.from_page);
+            }
+            _ => panic!("Expected IssueOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_issue_create_cli_args() {
+        match issue_options(vec![
+            "gr",
+            "issue",
+            "create",
+            "--title",
+            "Crash on start",
+            "--label",
+            "bug,ui",
+        ]) {
+            IssueOptions::Create(args) => {
+                assert_eq!("Crash on start", args.title);
+                assert_eq!("", args.description);
+                assert_eq!(vec!["bug", "ui"], args.labels);
+            }
+            _ => panic!("Expected IssueOptions::Create"),
+        }
+    }
+
+    #[test]
+    fn test_issue_comment_requires_comment() {
+        match issue_options(vec!["gr", "issue", "comment", "24", "Cannot reproduce"]) {
+            IssueOptions::Comment { id, comment, .. } => {
+                assert_eq!(24, id);
+                assert_eq!(Some("Cannot reproduce".to_string()), comment);
+            }
+            _ => panic!("Expected IssueOptions::Comment"),
+        }
+        assert!(Args::try_parse_from(vec!["gr", "issue", "comment", "24"]).is_err());
+    }
+}
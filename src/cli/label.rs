@@ -0,0 +1,89 @@
+use clap::Parser;
+
+use crate::cmds::label::LabelBodyArgs;
+use crate::remote::ListRemoteCliArgs;
+
+use super::common::ListArgs;
+
+#[derive(Parser)]
+pub struct LabelCommand {
+    #[clap(subcommand)]
+    subcommand: LabelSubcommand,
+}
+
+#[derive(Parser)]
+enum LabelSubcommand {
+    #[clap(about = "List project labels")]
+    List(ListArgs),
+    #[clap(about = "Create a project label")]
+    Create(CreateLabel),
+}
+
+#[derive(Parser)]
+struct CreateLabel {
+    /// Name of the label
+    #[clap()]
+    name: String,
+    /// Hex color of the label
+    #[clap(long, default_value = "#428BCA")]
+    color: String,
+    /// Description of the label
+    #[clap(long)]
+    description: Option<String>,
+}
+
+impl From<LabelCommand> for LabelOptions {
+    fn from(options: LabelCommand) -> Self {
+        match options.subcommand {
+            LabelSubcommand::List(options) => LabelOptions::List(options.into()),
+            LabelSubcommand::Create(options) => LabelOptions::Create(
+                LabelBodyArgs::builder()
+                    .name(options.name)
+                    .color(options.color)
+                    .description(options.description.unwrap_or_default())
+                    .build()
+                    .unwrap(),
+            ),
+        }
+    }
+}
+
+pub enum LabelOptions {
+    List(ListRemoteCliArgs),
+    Create(LabelBodyArgs),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    fn options(argv: Vec<&str>) -> LabelOptions {
+        let args = Args::parse_from(argv);
+        match args.command {
+            Command::Label(options) => options.into(),
+            _ => panic!("Expected LabelCommand"),
+        }
+    }
+
+    #[test]
+    fn test_label_list_cli_args() {
+        match options(vec!["gr", "label", "list", "--num-pages"]) {
+            LabelOptions::List(args) => assert!(args.num_pages),
+            _ => panic!("Expected LabelOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_label_create_cli_args() {
+        match options(vec!["gr", "label", "create", "bug", "--color", "#d9534f"]) {
+            LabelOptions::Create(args) => {
+                assert_eq!("bug", args.name);
+                assert_eq!("#d9534f", args.color);
+                assert_eq!("", args.description);
+            }
+            _ => panic!("Expected LabelOptions::Create"),
+        }
+    }
+}
@@ -3,11 +3,17 @@ use std::option::Option;
 use clap::{Parser, ValueEnum};
 
 use crate::cmds::merge_request::{
-    CommentMergeRequestCliArgs, CommentMergeRequestListCliArgs, MergeRequestCliArgs,
-    MergeRequestGetCliArgs, MergeRequestListCliArgs, MergeRequestState, SummaryOptions,
+    CommentMergeRequestCliArgs, CommentMergeRequestListCliArgs, DiscussionReplyBodyArgs,
+    MergeMethod, MergeRequestCliArgs, MergeRequestGetCliArgs, MergeRequestListCliArgs,
+    MergeRequestNagCliArgs, MergeRequestRebaseCliArgs, MergeRequestState, MergeRequestUser,
+    ReactionBodyArgs, ReviewBodyArgs, ReviewEvent, SuggestionApplyCliArgs, SuggestionBodyArgs,
+    SummaryOptions,
 };
+use crate::time::Seconds;
 
-use super::common::{validate_project_repo_path, CacheArgs, GetArgs, ListArgs};
+use super::common::{
+    parse_age, validate_project_repo_path, CacheArgs, GetArgs, ListArgs, OrderByCli,
+};
 
 #[derive(Parser)]
 pub struct MergeRequestCommand {
@@ -21,10 +27,19 @@ enum MergeRequestSubcommand {
     Create(CreateMergeRequest),
     #[clap(about = "Approve a merge request", visible_alias = "ap")]
     Approve(ApproveMergeRequest),
-    #[clap(about = "Merge a merge request")]
+    #[clap(
+        about = "Merge a merge request",
+        long_about = "Merge a merge request\n\n\
+            Merge commits are created by the remote. Github signs them with its \
+            own key and Gitlab does when the instance is set up to sign web \
+            commits, so there is nothing to configure in gitar for signed merge \
+            commits."
+    )]
     Merge(MergeMergeRequest),
     #[clap(about = "Git checkout a merge request branch for review")]
     Checkout(CheckoutMergeRequest),
+    #[clap(about = "Rebase a merge request onto its target branch in the remote")]
+    Rebase(RebaseMergeRequest),
     #[clap(
         subcommand,
         about = "Merge request comment operations",
@@ -33,10 +48,293 @@ enum MergeRequestSubcommand {
     Comment(CommentSubCommand),
     #[clap(about = "Close a merge request")]
     Close(CloseMergeRequest),
+    #[clap(about = "Mark a merge request as draft")]
+    Draft(DraftMergeRequest),
+    #[clap(about = "Mark a draft merge request as ready for review")]
+    Ready(DraftMergeRequest),
     /// Get a merge request
     Get(GetMergeRequest),
+    #[clap(about = "Show the changes of a merge request as a unified diff")]
+    Diff(DiffMergeRequest),
     #[clap(about = "List merge requests", visible_alias = "ls")]
-    List(ListMergeRequest),
+    List(ListRepoMergeRequest),
+    #[clap(
+        subcommand,
+        about = "Gitlab merge train and Github merge queue operations"
+    )]
+    Queue(QueueSubCommand),
+    #[clap(about = "Remind about open merge requests awaiting review")]
+    Nag(NagMergeRequests),
+    #[clap(about = "React with an emoji to a merge request or one of its comments")]
+    React(ReactMergeRequest),
+    #[clap(subcommand, about = "Threads of comments of a merge request")]
+    Discussion(DiscussionSubCommand),
+    #[clap(about = "Suggest a change to a line of a merge request")]
+    Suggest(SuggestMergeRequest),
+    #[clap(subcommand, about = "Pending suggested changes of a merge request")]
+    Suggestions(SuggestionsSubCommand),
+    #[clap(subcommand, about = "Draft a review offline and submit it in batch")]
+    Review(ReviewSubCommand),
+}
+
+#[derive(Parser)]
+enum ReviewSubCommand {
+    /// Print a review file for the merge request to STDOUT. Edit it to add
+    /// inline comments
+    Export(ExportReview),
+    /// Submit the comments of a review file as a single review
+    Submit(SubmitReview),
+    /// Approve the merge request, optionally with a comment
+    Approve(ApproveReview),
+    /// Request changes on the merge request. Gitlab adds the comment and
+    /// revokes your approval
+    RequestChanges(CommentReview),
+    /// Review the merge request with a comment, without approving it
+    Comment(CommentReview),
+}
+
+#[derive(Parser)]
+struct ApproveReview {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Comment posted along with the approval
+    #[clap(long)]
+    body: Option<String>,
+}
+
+#[derive(Parser)]
+struct CommentReview {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Review comment
+    #[clap(long)]
+    body: String,
+}
+
+#[derive(Parser)]
+struct ExportReview {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+}
+
+#[derive(Parser)]
+struct SubmitReview {
+    /// Review file. If "-" is provided, read from STDIN
+    #[clap(value_name = "FILE")]
+    file: String,
+    /// Submit even if the merge request has new commits since the review
+    /// was exported
+    #[clap(long)]
+    force: bool,
+}
+
+impl From<ReviewSubCommand> for MergeRequestOptions {
+    fn from(options: ReviewSubCommand) -> Self {
+        match options {
+            ReviewSubCommand::Export(options) => {
+                MergeRequestOptions::ExportReview { id: options.id }
+            }
+            ReviewSubCommand::Submit(options) => MergeRequestOptions::SubmitReview {
+                file: options.file,
+                force: options.force,
+            },
+            ReviewSubCommand::Approve(options) => MergeRequestOptions::Review(
+                ReviewBodyArgs::builder()
+                    .id(options.id)
+                    .event(ReviewEvent::Approve)
+                    .body(options.body.unwrap_or_default())
+                    .build()
+                    .unwrap(),
+            ),
+            ReviewSubCommand::RequestChanges(options) => MergeRequestOptions::Review(
+                ReviewBodyArgs::builder()
+                    .id(options.id)
+                    .event(ReviewEvent::RequestChanges)
+                    .body(options.body)
+                    .build()
+                    .unwrap(),
+            ),
+            ReviewSubCommand::Comment(options) => MergeRequestOptions::Review(
+                ReviewBodyArgs::builder()
+                    .id(options.id)
+                    .event(ReviewEvent::Comment)
+                    .body(options.body)
+                    .build()
+                    .unwrap(),
+            ),
+        }
+    }
+}
+
+#[derive(Parser)]
+enum DiscussionSubCommand {
+    /// List discussion threads, one comment per row
+    List(GetMergeRequest),
+    /// Reply to a discussion thread
+    Reply(ReplyDiscussion),
+}
+
+#[derive(Parser)]
+struct ReplyDiscussion {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Id of the discussion as shown by `gr mr discussion list`
+    #[clap()]
+    discussion_id: String,
+    /// Reply to add to the discussion
+    #[clap(long)]
+    body: String,
+}
+
+impl From<DiscussionSubCommand> for MergeRequestOptions {
+    fn from(options: DiscussionSubCommand) -> Self {
+        match options {
+            DiscussionSubCommand::List(options) => MergeRequestOptions::ListDiscussions(
+                MergeRequestGetCliArgs::builder()
+                    .id(options.id)
+                    .get_args(options.get_args.into())
+                    .build()
+                    .unwrap(),
+            ),
+            DiscussionSubCommand::Reply(options) => MergeRequestOptions::ReplyDiscussion(
+                DiscussionReplyBodyArgs::builder()
+                    .id(options.id)
+                    .discussion_id(options.discussion_id)
+                    .body(options.body)
+                    .build()
+                    .unwrap(),
+            ),
+        }
+    }
+}
+
+#[derive(Parser)]
+enum SuggestionsSubCommand {
+    /// List suggestions that can be applied
+    List(GetMergeRequest),
+    /// Apply suggestions in a single commit. Prompts for the suggestions to
+    /// apply unless given
+    Apply(ApplySuggestions),
+}
+
+#[derive(Parser)]
+struct ApplySuggestions {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Ids of the suggestions to apply, comma separated
+    #[clap(long, value_delimiter = ',', value_name = "ID", conflicts_with = "all")]
+    suggestion: Vec<i64>,
+    /// Apply all pending suggestions
+    #[clap(long)]
+    all: bool,
+    /// Commit message. Defaults to the remote's message
+    #[clap(long, short)]
+    message: Option<String>,
+}
+
+impl From<SuggestionsSubCommand> for MergeRequestOptions {
+    fn from(options: SuggestionsSubCommand) -> Self {
+        match options {
+            SuggestionsSubCommand::List(options) => MergeRequestOptions::ListSuggestions(
+                MergeRequestGetCliArgs::builder()
+                    .id(options.id)
+                    .get_args(options.get_args.into())
+                    .build()
+                    .unwrap(),
+            ),
+            SuggestionsSubCommand::Apply(options) => MergeRequestOptions::ApplySuggestions(
+                SuggestionApplyCliArgs::builder()
+                    .id(options.id)
+                    .suggestion_ids(options.suggestion)
+                    .all(options.all)
+                    .message(options.message)
+                    .build()
+                    .unwrap(),
+            ),
+        }
+    }
+}
+
+#[derive(Parser)]
+struct SuggestMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Path of the changed file. Ex. src/main.rs
+    #[clap(long)]
+    file: String,
+    /// Line number in the new version of the file
+    #[clap(long, value_parser = clap::value_parser!(u32).range(1..))]
+    line: u32,
+    /// Code replacing the line. Can span multiple lines
+    #[clap(long)]
+    replacement: String,
+    /// Message shown above the suggested change
+    #[clap(long, short)]
+    message: Option<String>,
+}
+
+#[derive(Parser)]
+struct ReactMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Emoji to react with. Ex. :thumbsup:, heart, tada
+    #[clap(value_parser = parse_emoji)]
+    emoji: String,
+    /// React to the comment with this id instead of the merge request
+    #[clap(long, value_name = "COMMENT_ID")]
+    comment: Option<i64>,
+}
+
+fn parse_emoji(emoji: &str) -> Result<String, String> {
+    let name = emoji.trim_matches(':');
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '+' || c == '-')
+    {
+        return Err(format!("Invalid emoji {}. Ex. :thumbsup:", emoji));
+    }
+    Ok(name.to_string())
+}
+
+#[derive(Parser)]
+struct NagMergeRequests {
+    /// Open merge requests with no updates for longer than this. Ex. 3d, 12h
+    #[clap(long, value_name = "AGE", value_parser = parse_age)]
+    older_than: Seconds,
+    /// Print a report of the merge requests awaiting review without posting
+    /// reminder comments
+    #[clap(long)]
+    report: bool,
+    /// Post the reminder comments without prompting for confirmation
+    #[clap(long, short)]
+    yes: bool,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+enum QueueSubCommand {
+    /// Add a merge request to the merge queue
+    Add(QueueMergeRequest),
+    /// Remove a merge request from the merge queue
+    Remove(QueueMergeRequest),
+    /// Get the position and estimated time to merge of a queued merge request
+    Status(GetMergeRequest),
+}
+
+#[derive(Parser)]
+struct QueueMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
 }
 
 #[derive(Parser)]
@@ -48,12 +346,54 @@ struct GetMergeRequest {
     get_args: GetArgs,
 }
 
+#[derive(Parser)]
+struct DiffMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Summary of the lines added and deleted per file instead of the diff
+    #[clap(long)]
+    stat: bool,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
 #[derive(Parser)]
 enum CommentSubCommand {
     /// Create a comment to a given merge request
     Create(CreateCommentMergeRequest),
     /// List comments of a given merge request
     List(ListCommentMergeRequest),
+    /// Edit a comment of a given merge request
+    Edit(EditCommentMergeRequest),
+    /// Delete a comment of a given merge request
+    Delete(DeleteCommentMergeRequest),
+}
+
+#[derive(Parser)]
+struct EditCommentMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    pub id: i64,
+    /// Id of the comment
+    #[clap()]
+    pub comment_id: i64,
+    /// New body of the comment
+    #[clap(long)]
+    pub body: String,
+}
+
+#[derive(Parser)]
+struct DeleteCommentMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    pub id: i64,
+    /// Id of the comment
+    #[clap()]
+    pub comment_id: i64,
+    /// Delete without prompting for confirmation
+    #[clap(long, short)]
+    pub yes: bool,
 }
 
 #[derive(Parser)]
@@ -67,6 +407,10 @@ struct CreateCommentMergeRequest {
     /// Gather comment from the specified file. If "-" is provided, read from STDIN
     #[clap(long, value_name = "FILE", group = "comment_msg")]
     pub comment_from_file: Option<String>,
+    /// Retry up to this number of times on network or server errors. The
+    /// comment is not posted twice if a failed attempt reached the remote
+    #[clap(long, default_value = "0", value_name = "RETRIES")]
+    pub retry: u32,
 }
 
 #[derive(Parser)]
@@ -126,13 +470,16 @@ struct CreateMergeRequest {
     /// Assignee username
     #[clap(long, short = 'A', value_name = "USERNAME")]
     pub assignee: Option<String>,
-    /// Reviewer username
+    /// Reviewer username. Repeat to request reviews from several users
     #[clap(long, short = 'R', value_name = "USERNAME", group = "reviewer_args")]
-    pub reviewer: Option<String>,
+    pub reviewer: Vec<String>,
     /// Randomly assigns a reviewer from the list of members defined in the merge requests
     /// configuration section.
     #[clap(long, group = "reviewer_args")]
     pub rand_reviewer: bool,
+    /// Label to add to the merge request. Repeat to add several labels
+    #[clap(long, value_name = "LABEL")]
+    pub label: Vec<String>,
     /// Provides a list of outgoing commit SHAs and messages with subject
     /// (short) and body (long) to STDOUT, then exits. No merge request is created.
     #[clap(short, long, group = "summary_args", value_name = "OPTION")]
@@ -181,9 +528,19 @@ struct CreateMergeRequest {
     /// Set up the merge request as draft
     #[clap(long, visible_alias = "wip")]
     pub draft: bool,
-    /// Dry run. Does not push the branch and does not create the merge request
+    /// Dry run. Goes through the creation without committing, rebasing,
+    /// pushing or creating the merge request and prints what would be done
     #[clap(long)]
     pub dry_run: bool,
+    /// Push the branch and open the new merge request form in the browser with
+    /// the title and description filled in instead of creating it through the
+    /// API. Useful with tokens that cannot create merge requests
+    #[clap(long, conflicts_with_all = ["depends_on", "target_repo"])]
+    pub web: bool,
+    /// Id of a merge request that needs to be merged before this one. Can be
+    /// specified multiple times
+    #[clap(long, value_name = "ID")]
+    pub depends_on: Vec<i64>,
     #[clap(flatten)]
     pub cache_args: CacheArgs,
 }
@@ -209,15 +566,108 @@ impl From<MergeRequestStateStateCli> for MergeRequestState {
 pub struct ListMergeRequest {
     #[clap()]
     pub state: MergeRequestStateStateCli,
+    /// Order by this field on the remote, in the --sort direction. Github
+    /// does not order by title
+    #[clap(long, value_name = "FIELD")]
+    pub order_by: Option<OrderByCli>,
     #[command(flatten)]
     pub list_args: ListArgs,
 }
 
+#[derive(Parser)]
+struct ListRepoMergeRequest {
+    #[clap(flatten)]
+    list_merge_request: ListMergeRequest,
+    /// List the merge requests of a set of projects declared in the [repos]
+    /// table of the config file instead. Projects are queried in parallel
+    /// and a Repo column is added
+    #[clap(long, value_name = "NAME", conflicts_with_all = ["num_pages", "num_resources", "stream"])]
+    repos: Option<String>,
+    /// List the merge requests of a Gitlab group, including its subgroups,
+    /// or of a Github organization. Ex. team/subgroup
+    #[clap(long, value_name = "GROUP", conflicts_with = "repos")]
+    group: Option<String>,
+    /// List merge requests with this label. Repeat to require several labels
+    #[clap(long, value_name = "LABEL")]
+    label: Vec<String>,
+    /// List merge requests of this milestone. Github also accepts the
+    /// milestone number
+    #[clap(long, value_name = "MILESTONE")]
+    milestone: Option<String>,
+    /// List merge requests authored by this user. @me is the authenticated
+    /// user
+    #[clap(long, value_name = "USERNAME", value_parser = parse_user, group = "user_filter")]
+    author: Option<MergeRequestUser>,
+    /// List merge requests assigned to this user. @me is the authenticated
+    /// user
+    #[clap(long, value_name = "USERNAME", value_parser = parse_user, group = "user_filter")]
+    assignee: Option<MergeRequestUser>,
+    /// List merge requests this user is a reviewer of. @me is the
+    /// authenticated user. Gitlab only
+    #[clap(long, value_name = "USERNAME", value_parser = parse_user, group = "user_filter")]
+    reviewer: Option<MergeRequestUser>,
+    /// List merge requests authored by the authenticated user. Shorthand for
+    /// --author @me
+    #[clap(long, group = "user_filter")]
+    mine: bool,
+}
+
+/// Parses a user filter, where @me is the authenticated user.
+fn parse_user(user: &str) -> Result<MergeRequestUser, String> {
+    match user.trim() {
+        "" => Err("Username cannot be empty".to_string()),
+        "@me" => Ok(MergeRequestUser::Me),
+        username => Ok(MergeRequestUser::Other(username.to_string())),
+    }
+}
+
 #[derive(Parser)]
 struct MergeMergeRequest {
     /// Id of the merge request
     #[clap()]
     pub id: i64,
+    /// Retry up to this number of times if the merge request is not mergeable
+    /// yet. Ex. pipeline still running or approval just given
+    #[clap(long, default_value = "0", value_name = "RETRIES")]
+    pub retry: u32,
+    /// Custom merge commit message
+    #[clap(long, short)]
+    pub message: Option<String>,
+    /// Merge only if the head of the merge request is at this commit SHA.
+    /// Guards against merging changes pushed after the review
+    #[clap(long)]
+    pub sha: Option<String>,
+    /// Merge once the pipeline succeeds instead of right away. Gitlab merge
+    /// when pipeline succeeds, Github auto-merge
+    #[clap(long, visible_alias = "auto", conflicts_with = "retry")]
+    pub when_pipeline_succeeds: bool,
+    /// Squash the commits of the merge request into a single one
+    #[clap(long)]
+    pub squash: bool,
+    /// Delete the source branch once merged
+    #[clap(long)]
+    pub delete_branch: bool,
+    /// Merge method. Github only, Gitlab uses the one set in the project
+    /// settings
+    #[clap(long, value_name = "METHOD", conflicts_with = "squash")]
+    pub method: Option<MergeMethodCli>,
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Debug)]
+enum MergeMethodCli {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl From<MergeMethodCli> for MergeMethod {
+    fn from(method: MergeMethodCli) -> Self {
+        match method {
+            MergeMethodCli::Merge => MergeMethod::Merge,
+            MergeMethodCli::Squash => MergeMethod::Squash,
+            MergeMethodCli::Rebase => MergeMethod::Rebase,
+        }
+    }
 }
 
 #[derive(Parser)]
@@ -227,6 +677,32 @@ struct CheckoutMergeRequest {
     pub id: i64,
 }
 
+#[derive(Parser)]
+struct RebaseMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    id: i64,
+    /// Seconds to wait between polls of the rebase status
+    #[clap(long, default_value = "2", value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+    /// Seconds to wait for the rebase to finish
+    #[clap(long, default_value = "300", value_name = "SECONDS")]
+    timeout: u64,
+}
+
+impl From<RebaseMergeRequest> for MergeRequestOptions {
+    fn from(options: RebaseMergeRequest) -> Self {
+        MergeRequestOptions::Rebase(
+            MergeRequestRebaseCliArgs::builder()
+                .id(options.id)
+                .interval(Seconds::from(options.interval))
+                .timeout(Seconds::from(options.timeout))
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 #[derive(Parser)]
 struct CloseMergeRequest {
     /// Id of the merge request
@@ -234,6 +710,13 @@ struct CloseMergeRequest {
     pub id: i64,
 }
 
+#[derive(Parser)]
+struct DraftMergeRequest {
+    /// Id of the merge request
+    #[clap()]
+    pub id: i64,
+}
+
 #[derive(Parser)]
 struct ApproveMergeRequest {
     /// Id of the merge request
@@ -241,18 +724,45 @@ struct ApproveMergeRequest {
     pub id: i64,
 }
 
-impl From<ListMergeRequest> for MergeRequestOptions {
-    fn from(options: ListMergeRequest) -> Self {
-        MergeRequestOptions::List(MergeRequestListCliArgs::new(
-            options.state.into(),
-            options.list_args.into(),
-        ))
+impl From<ListRepoMergeRequest> for MergeRequestOptions {
+    fn from(options: ListRepoMergeRequest) -> Self {
+        let mut cli_args = MergeRequestListCliArgs::new(
+            options.list_merge_request.state.into(),
+            options
+                .list_merge_request
+                .list_args
+                .ordered_by(options.list_merge_request.order_by),
+        );
+        cli_args.group = options.group;
+        cli_args.labels = options.label;
+        cli_args.milestone = options.milestone;
+        cli_args.author = if options.mine {
+            Some(MergeRequestUser::Me)
+        } else {
+            options.author
+        };
+        cli_args.assignee = options.assignee;
+        cli_args.reviewer = options.reviewer;
+        cli_args.project_only = true;
+        match options.repos {
+            Some(name) => MergeRequestOptions::ListRepoSet { name, cli_args },
+            None => MergeRequestOptions::List(cli_args),
+        }
     }
 }
 
 impl From<MergeMergeRequest> for MergeRequestOptions {
     fn from(options: MergeMergeRequest) -> Self {
-        MergeRequestOptions::Merge { id: options.id }
+        MergeRequestOptions::Merge {
+            id: options.id,
+            retry: options.retry,
+            message: options.message,
+            sha: options.sha,
+            auto_merge: options.when_pipeline_succeeds,
+            squash: options.squash || options.method == Some(MergeMethodCli::Squash),
+            delete_source_branch: options.delete_branch,
+            method: options.method.map(|method| method.into()),
+        }
     }
 }
 
@@ -268,6 +778,34 @@ impl From<CloseMergeRequest> for MergeRequestOptions {
     }
 }
 
+impl From<SuggestMergeRequest> for MergeRequestOptions {
+    fn from(options: SuggestMergeRequest) -> Self {
+        MergeRequestOptions::Suggest(
+            SuggestionBodyArgs::builder()
+                .id(options.id)
+                .file(options.file)
+                .line(options.line)
+                .replacement(options.replacement)
+                .message(options.message)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<ReactMergeRequest> for MergeRequestOptions {
+    fn from(options: ReactMergeRequest) -> Self {
+        MergeRequestOptions::React(
+            ReactionBodyArgs::builder()
+                .id(options.id)
+                .comment_id(options.comment)
+                .emoji(options.emoji)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 impl From<ApproveMergeRequest> for MergeRequestOptions {
     fn from(options: ApproveMergeRequest) -> Self {
         MergeRequestOptions::Approve { id: options.id }
@@ -281,19 +819,77 @@ impl From<MergeRequestCommand> for MergeRequestOptions {
             MergeRequestSubcommand::List(options) => options.into(),
             MergeRequestSubcommand::Merge(options) => options.into(),
             MergeRequestSubcommand::Checkout(options) => options.into(),
+            MergeRequestSubcommand::Rebase(options) => options.into(),
             MergeRequestSubcommand::Close(options) => options.into(),
+            MergeRequestSubcommand::Draft(options) => MergeRequestOptions::ToggleDraft {
+                id: options.id,
+                draft: true,
+            },
+            MergeRequestSubcommand::Ready(options) => MergeRequestOptions::ToggleDraft {
+                id: options.id,
+                draft: false,
+            },
             MergeRequestSubcommand::Comment(options) => options.into(),
             MergeRequestSubcommand::Get(options) => options.into(),
+            MergeRequestSubcommand::Diff(options) => options.into(),
             MergeRequestSubcommand::Approve(options) => options.into(),
+            MergeRequestSubcommand::Queue(options) => options.into(),
+            MergeRequestSubcommand::Nag(options) => options.into(),
+            MergeRequestSubcommand::React(options) => options.into(),
+            MergeRequestSubcommand::Discussion(options) => options.into(),
+            MergeRequestSubcommand::Suggest(options) => options.into(),
+            MergeRequestSubcommand::Suggestions(options) => options.into(),
+            MergeRequestSubcommand::Review(options) => options.into(),
         }
     }
 }
 
+impl From<NagMergeRequests> for MergeRequestOptions {
+    fn from(options: NagMergeRequests) -> Self {
+        MergeRequestOptions::Nag(
+            MergeRequestNagCliArgs::builder()
+                .older_than(options.older_than)
+                .report(options.report)
+                .yes(options.yes)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<QueueSubCommand> for MergeRequestOptions {
+    fn from(options: QueueSubCommand) -> Self {
+        let options = match options {
+            QueueSubCommand::Add(options) => MergeQueueOptions::Add { id: options.id },
+            QueueSubCommand::Remove(options) => MergeQueueOptions::Remove { id: options.id },
+            QueueSubCommand::Status(options) => MergeQueueOptions::Status(
+                MergeRequestGetCliArgs::builder()
+                    .id(options.id)
+                    .get_args(options.get_args.into())
+                    .build()
+                    .unwrap(),
+            ),
+        };
+        MergeRequestOptions::Queue(options)
+    }
+}
+
 impl From<CommentSubCommand> for MergeRequestOptions {
     fn from(options: CommentSubCommand) -> Self {
         match options {
             CommentSubCommand::Create(options) => options.into(),
             CommentSubCommand::List(options) => options.into(),
+            CommentSubCommand::Edit(options) => MergeRequestOptions::EditComment {
+                id: options.id,
+                comment_id: options.comment_id,
+                body: options.body,
+            },
+            CommentSubCommand::Delete(options) => MergeRequestOptions::DeleteComment {
+                id: options.id,
+                comment_id: options.comment_id,
+                yes: options.yes,
+            },
         }
     }
 }
@@ -308,8 +904,9 @@ impl From<CreateMergeRequest> for MergeRequestOptions {
                 .description(options.description)
                 .description_from_file(options.description_from_file)
                 .assignee(options.assignee)
-                .reviewer(options.reviewer)
+                .reviewers(options.reviewer)
                 .rand_reviewer(options.rand_reviewer)
+                .labels(options.label)
                 .target_branch(options.target_branch)
                 .target_repo(options.target_repo)
                 .fetch(options.fetch)
@@ -323,9 +920,11 @@ impl From<CreateMergeRequest> for MergeRequestOptions {
                 .amend(options.amend)
                 .force(options.force)
                 .dry_run(options.dry_run)
+                .web(options.web)
                 .summary(options.summary.into())
                 .patch(options.patch)
                 .gpt_prompt(options.gpt_prompt)
+                .depends_on(options.depends_on)
                 .build()
                 .unwrap(),
         )
@@ -351,12 +950,26 @@ impl From<CreateCommentMergeRequest> for MergeRequestOptions {
                 .id(options.id)
                 .comment(options.comment)
                 .comment_from_file(options.comment_from_file)
+                .retry(options.retry)
                 .build()
                 .unwrap(),
         )
     }
 }
 
+impl From<DiffMergeRequest> for MergeRequestOptions {
+    fn from(options: DiffMergeRequest) -> Self {
+        MergeRequestOptions::Diff {
+            cli_args: MergeRequestGetCliArgs::builder()
+                .id(options.id)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+            stat: options.stat,
+        }
+    }
+}
+
 impl From<GetMergeRequest> for MergeRequestOptions {
     fn from(options: GetMergeRequest) -> Self {
         MergeRequestOptions::Get(
@@ -372,22 +985,103 @@ impl From<GetMergeRequest> for MergeRequestOptions {
 pub enum MergeRequestOptions {
     Create(MergeRequestCliArgs),
     Get(MergeRequestGetCliArgs),
+    Diff {
+        cli_args: MergeRequestGetCliArgs,
+        stat: bool,
+    },
     List(MergeRequestListCliArgs),
     CreateComment(CommentMergeRequestCliArgs),
     ListComment(CommentMergeRequestListCliArgs),
-    Approve { id: i64 },
-    Merge { id: i64 },
+    EditComment {
+        id: i64,
+        comment_id: i64,
+        body: String,
+    },
+    DeleteComment {
+        id: i64,
+        comment_id: i64,
+        yes: bool,
+    },
+    /// List merge requests of a set of projects declared in the config
+    ListRepoSet {
+        name: String,
+        cli_args: MergeRequestListCliArgs,
+    },
+    Approve {
+        id: i64,
+    },
+    Merge {
+        id: i64,
+        retry: u32,
+        message: Option<String>,
+        sha: Option<String>,
+        auto_merge: bool,
+        squash: bool,
+        delete_source_branch: bool,
+        method: Option<MergeMethod>,
+    },
     // TODO: Checkout is a read operation, so we should propagate MergeRequestGetCliArgs
-    Checkout { id: i64 },
-    Close { id: i64 },
+    Checkout {
+        id: i64,
+    },
+    Close {
+        id: i64,
+    },
+    Rebase(MergeRequestRebaseCliArgs),
+    ToggleDraft {
+        id: i64,
+        draft: bool,
+    },
+    Queue(MergeQueueOptions),
+    Nag(MergeRequestNagCliArgs),
+    React(ReactionBodyArgs),
+    ListDiscussions(MergeRequestGetCliArgs),
+    ReplyDiscussion(DiscussionReplyBodyArgs),
+    Suggest(SuggestionBodyArgs),
+    ListSuggestions(MergeRequestGetCliArgs),
+    ApplySuggestions(SuggestionApplyCliArgs),
+    ExportReview {
+        id: i64,
+    },
+    SubmitReview {
+        file: String,
+        force: bool,
+    },
+    Review(ReviewBodyArgs),
+}
+
+pub enum MergeQueueOptions {
+    Add { id: i64 },
+    Remove { id: i64 },
+    Status(MergeRequestGetCliArgs),
 }
 
 #[cfg(test)]
 mod test {
     use crate::cli::{Args, Command};
+    use crate::remote::{ListOrderBy, ListSortMode};
 
     use super::*;
 
+    #[test]
+    fn test_rebase_merge_request_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "rebase", "23", "--interval", "5"]);
+        let options = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Rebase(options),
+            }) => options,
+            _ => panic!("Expected MergeRequestSubcommand::Rebase"),
+        };
+        match options.into() {
+            MergeRequestOptions::Rebase(args) => {
+                assert_eq!(23, args.id);
+                assert_eq!(Seconds::new(5), args.interval);
+                assert_eq!(Seconds::new(300), args.timeout);
+            }
+            _ => panic!("Expected MergeRequestOptions::Rebase"),
+        }
+    }
+
     #[test]
     fn test_list_merge_requests_cli_args() {
         let args = Args::parse_from(vec!["gr", "mr", "list", "opened"]);
@@ -395,7 +1089,10 @@ mod test {
             Command::MergeRequest(MergeRequestCommand {
                 subcommand: MergeRequestSubcommand::List(options),
             }) => {
-                assert_eq!(options.state, MergeRequestStateStateCli::Opened);
+                assert_eq!(
+                    options.list_merge_request.state,
+                    MergeRequestStateStateCli::Opened
+                );
                 options
             }
             _ => panic!("Expected MergeRequestCommand::List"),
@@ -411,54 +1108,352 @@ mod test {
     }
 
     #[test]
-    fn test_merge_merge_request_cli_args() {
-        let args = Args::parse_from(vec!["gr", "mr", "merge", "123"]);
-        let merge_merge_request = match args.command {
+    fn test_list_merge_requests_repo_set_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "list", "opened", "--repos", "watchlist"]);
+        let options: MergeRequestOptions = match args.command {
             Command::MergeRequest(MergeRequestCommand {
-                subcommand: MergeRequestSubcommand::Merge(options),
-            }) => {
-                assert_eq!(options.id, 123);
-                options
-            }
-            _ => panic!("Expected MergeRequestCommand::Merge"),
+                subcommand: MergeRequestSubcommand::List(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::List"),
         };
-
-        let options: MergeRequestOptions = merge_merge_request.into();
         match options {
-            MergeRequestOptions::Merge { id } => {
-                assert_eq!(id, 123);
+            MergeRequestOptions::ListRepoSet { name, cli_args } => {
+                assert_eq!("watchlist", name);
+                assert_eq!(cli_args.state, MergeRequestState::Opened);
             }
-            _ => panic!("Expected MergeRequestOptions::Merge"),
+            _ => panic!("Expected MergeRequestOptions::ListRepoSet"),
         }
     }
 
-    #[test]
-    fn test_checkout_merge_request_cli_args() {
-        let args = Args::parse_from(vec!["gr", "mr", "checkout", "123"]);
-        let checkout_merge_request = match args.command {
+    fn list_options(args: Vec<&str>) -> MergeRequestListCliArgs {
+        let args = Args::parse_from(args);
+        let options: MergeRequestOptions = match args.command {
             Command::MergeRequest(MergeRequestCommand {
-                subcommand: MergeRequestSubcommand::Checkout(options),
-            }) => {
-                assert_eq!(options.id, 123);
-                options
-            }
-            _ => panic!("Expected MergeRequestCommand::Checkout"),
+                subcommand: MergeRequestSubcommand::List(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::List"),
         };
-
-        let options: MergeRequestOptions = checkout_merge_request.into();
         match options {
-            MergeRequestOptions::Checkout { id } => {
-                assert_eq!(id, 123);
-            }
-            _ => panic!("Expected MergeRequestOptions::Checkout"),
+            MergeRequestOptions::List(cli_args) => cli_args,
+            _ => panic!("Expected MergeRequestOptions::List"),
         }
     }
 
     #[test]
-    fn test_close_merge_request_cli_args() {
-        let args = Args::parse_from(vec!["gr", "mr", "close", "123"]);
-        let close_merge_request = match args.command {
-            Command::MergeRequest(MergeRequestCommand {
+    fn test_list_merge_requests_by_user_cli_args() {
+        let cli_args = list_options(vec!["gr", "mr", "list", "opened", "--author", "@me"]);
+        assert_eq!(Some(MergeRequestUser::Me), cli_args.author);
+        assert!(cli_args.project_only);
+        let cli_args = list_options(vec!["gr", "mr", "list", "opened", "--assignee", "jdoe"]);
+        assert_eq!(
+            Some(MergeRequestUser::Other("jdoe".to_string())),
+            cli_args.assignee
+        );
+        assert_eq!(None, cli_args.author);
+        let cli_args = list_options(vec!["gr", "mr", "list", "opened", "--reviewer", "@me"]);
+        assert_eq!(Some(MergeRequestUser::Me), cli_args.reviewer);
+    }
+
+    #[test]
+    fn test_list_merge_requests_mine_is_author_me() {
+        let cli_args = list_options(vec!["gr", "mr", "list", "opened", "--mine"]);
+        assert_eq!(Some(MergeRequestUser::Me), cli_args.author);
+        assert_eq!(None, cli_args.assignee);
+        assert_eq!(None, cli_args.reviewer);
+    }
+
+    #[test]
+    fn test_list_merge_requests_user_filters_are_exclusive() {
+        let result = Args::try_parse_from(vec![
+            "gr",
+            "mr",
+            "list",
+            "opened",
+            "--mine",
+            "--assignee",
+            "jdoe",
+        ]);
+        assert!(result.is_err());
+        let result = Args::try_parse_from(vec!["gr", "mr", "list", "opened", "--author", ""]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_list_merge_requests_order_by_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "list",
+            "opened",
+            "--order-by",
+            "updated",
+            "--sort",
+            "desc",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::List(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::List"),
+        };
+        match options {
+            MergeRequestOptions::List(cli_args) => {
+                assert_eq!(Some(ListOrderBy::Updated), cli_args.list_args.order_by);
+                assert_eq!(ListSortMode::Desc, cli_args.list_args.sort);
+            }
+            _ => panic!("Expected MergeRequestOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_list_merge_requests_by_labels_and_milestone_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "list",
+            "opened",
+            "--label",
+            "bug",
+            "--label",
+            "ui",
+            "--milestone",
+            "v1.0",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::List(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::List"),
+        };
+        match options {
+            MergeRequestOptions::List(cli_args) => {
+                assert_eq!(vec!["bug", "ui"], cli_args.labels);
+                assert_eq!(Some("v1.0".to_string()), cli_args.milestone);
+            }
+            _ => panic!("Expected MergeRequestOptions::List"),
+        }
+    }
+
+    #[test]
+    fn test_list_merge_requests_of_group_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "list",
+            "opened",
+            "--group",
+            "team/subgroup",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::List(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::List"),
+        };
+        match options {
+            MergeRequestOptions::List(cli_args) => {
+                assert_eq!(Some("team/subgroup".to_string()), cli_args.group);
+            }
+            _ => panic!("Expected MergeRequestOptions::List"),
+        }
+        assert!(Args::try_parse_from(vec![
+            "gr",
+            "mr",
+            "list",
+            "opened",
+            "--group",
+            "team",
+            "--repos",
+            "watchlist"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_merge_merge_request_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "merge", "123"]);
+        let merge_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Merge(options),
+            }) => {
+                assert_eq!(options.id, 123);
+                options
+            }
+            _ => panic!("Expected MergeRequestCommand::Merge"),
+        };
+
+        let options: MergeRequestOptions = merge_merge_request.into();
+        match options {
+            MergeRequestOptions::Merge {
+                id,
+                retry,
+                message,
+                sha,
+                auto_merge,
+                squash,
+                delete_source_branch,
+                method,
+            } => {
+                assert_eq!(id, 123);
+                assert_eq!(retry, 0);
+                assert_eq!(message, None);
+                assert_eq!(sha, None);
+                assert!(!auto_merge);
+                assert!(!squash);
+                assert!(!delete_source_branch);
+                assert_eq!(method, None);
+            }
+            _ => panic!("Expected MergeRequestOptions::Merge"),
+        }
+    }
+
+    #[test]
+    fn test_merge_merge_request_with_retry() {
+        let args = Args::parse_from(vec!["gr", "mr", "merge", "123", "--retry", "3"]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Merge { id, retry, .. } => {
+                assert_eq!(id, 123);
+                assert_eq!(retry, 3);
+            }
+            _ => panic!("Expected MergeRequestOptions::Merge"),
+        }
+    }
+
+    #[test]
+    fn test_merge_merge_request_with_message_and_sha() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "merge",
+            "123",
+            "-m",
+            "Release 1.2",
+            "--sha",
+            "9c5bf00e",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Merge { message, sha, .. } => {
+                assert_eq!(Some("Release 1.2".to_string()), message);
+                assert_eq!(Some("9c5bf00e".to_string()), sha);
+            }
+            _ => panic!("Expected MergeRequestOptions::Merge"),
+        }
+    }
+
+    #[test]
+    fn test_merge_merge_request_when_pipeline_succeeds() {
+        for flag in ["--when-pipeline-succeeds", "--auto"] {
+            let args = Args::parse_from(vec!["gr", "mr", "merge", "123", flag]);
+            let options: MergeRequestOptions = match args.command {
+                Command::MergeRequest(options) => options.into(),
+                _ => panic!("Expected MergeRequestCommand"),
+            };
+            match options {
+                MergeRequestOptions::Merge { auto_merge, .. } => assert!(auto_merge),
+                _ => panic!("Expected MergeRequestOptions::Merge"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_merge_request_squash_and_delete_branch() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "merge",
+            "123",
+            "--squash",
+            "--delete-branch",
+            "-m",
+            "Release 1.2",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Merge {
+                message,
+                squash,
+                delete_source_branch,
+                method,
+                ..
+            } => {
+                assert_eq!(Some("Release 1.2".to_string()), message);
+                assert!(squash);
+                assert!(delete_source_branch);
+                assert_eq!(None, method);
+            }
+            _ => panic!("Expected MergeRequestOptions::Merge"),
+        }
+    }
+
+    #[test]
+    fn test_merge_merge_request_method() {
+        let options: MergeRequestOptions = match Args::parse_from(vec![
+            "gr", "mr", "merge", "123", "--method", "rebase",
+        ])
+        .command
+        {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Merge { squash, method, .. } => {
+                assert!(!squash);
+                assert_eq!(Some(MergeMethod::Rebase), method);
+            }
+            _ => panic!("Expected MergeRequestOptions::Merge"),
+        }
+        assert!(Args::try_parse_from(vec![
+            "gr", "mr", "merge", "123", "--squash", "--method", "merge"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_merge_merge_request_when_pipeline_succeeds_conflicts_with_retry() {
+        assert!(
+            Args::try_parse_from(vec!["gr", "mr", "merge", "123", "--auto", "--retry", "3"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_checkout_merge_request_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "checkout", "123"]);
+        let checkout_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Checkout(options),
+            }) => {
+                assert_eq!(options.id, 123);
+                options
+            }
+            _ => panic!("Expected MergeRequestCommand::Checkout"),
+        };
+
+        let options: MergeRequestOptions = checkout_merge_request.into();
+        match options {
+            MergeRequestOptions::Checkout { id } => {
+                assert_eq!(id, 123);
+            }
+            _ => panic!("Expected MergeRequestOptions::Checkout"),
+        }
+    }
+
+    #[test]
+    fn test_close_merge_request_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "close", "123"]);
+        let close_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
                 subcommand: MergeRequestSubcommand::Close(options),
             }) => {
                 assert_eq!(options.id, 123);
@@ -476,6 +1471,258 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_discussion_reply_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "discussion",
+            "reply",
+            "23",
+            "6a9c17",
+            "--body",
+            "Done",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Discussion(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::Discussion"),
+        };
+        match options {
+            MergeRequestOptions::ReplyDiscussion(args) => {
+                assert_eq!(23, args.id);
+                assert_eq!("6a9c17", args.discussion_id);
+                assert_eq!("Done", args.body);
+            }
+            _ => panic!("Expected MergeRequestOptions::ReplyDiscussion"),
+        }
+    }
+
+    #[test]
+    fn test_edit_and_delete_comment_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr", "mr", "cm", "edit", "23", "301", "--body", "LGTM!",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Comment(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::Comment"),
+        };
+        match options {
+            MergeRequestOptions::EditComment {
+                id,
+                comment_id,
+                body,
+            } => {
+                assert_eq!(23, id);
+                assert_eq!(301, comment_id);
+                assert_eq!("LGTM!", body);
+            }
+            _ => panic!("Expected MergeRequestOptions::EditComment"),
+        }
+        let args = Args::parse_from(vec!["gr", "mr", "cm", "delete", "23", "301", "-y"]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Comment(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::Comment"),
+        };
+        match options {
+            MergeRequestOptions::DeleteComment {
+                id,
+                comment_id,
+                yes,
+            } => {
+                assert_eq!(23, id);
+                assert_eq!(301, comment_id);
+                assert!(yes);
+            }
+            _ => panic!("Expected MergeRequestOptions::DeleteComment"),
+        }
+    }
+
+    #[test]
+    fn test_edit_comment_requires_comment_id() {
+        let result = Args::try_parse_from(vec!["gr", "mr", "cm", "edit", "23", "--body", "LGTM!"]);
+        assert!(result.is_err());
+        let result = Args::try_parse_from(vec![
+            "gr", "mr", "cm", "edit", "301", "--id", "23", "--body", "LGTM!",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_react_merge_request_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "react",
+            "23",
+            ":thumbsup:",
+            "--comment",
+            "5",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::React(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::React"),
+        };
+        match options {
+            MergeRequestOptions::React(args) => {
+                assert_eq!(23, args.id);
+                assert_eq!(Some(5), args.comment_id);
+                assert_eq!("thumbsup", args.emoji);
+            }
+            _ => panic!("Expected MergeRequestOptions::React"),
+        }
+    }
+
+    #[test]
+    fn test_suggest_merge_request_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "suggest",
+            "23",
+            "--file",
+            "src/main.rs",
+            "--line",
+            "10",
+            "--replacement",
+            "let x = 1;",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Suggest(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::Suggest"),
+        };
+        match options {
+            MergeRequestOptions::Suggest(args) => {
+                assert_eq!(23, args.id);
+                assert_eq!("src/main.rs", args.file);
+                assert_eq!(10, args.line);
+                assert_eq!("let x = 1;", args.replacement);
+                assert_eq!(None, args.message);
+            }
+            _ => panic!("Expected MergeRequestOptions::Suggest"),
+        }
+        assert!(Args::try_parse_from(vec![
+            "gr",
+            "mr",
+            "suggest",
+            "23",
+            "--file",
+            "src/main.rs",
+            "--line",
+            "0",
+            "--replacement",
+            "let x = 1;",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_apply_suggestions_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "suggestions",
+            "apply",
+            "23",
+            "--suggestion",
+            "5,6",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Suggestions(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::Suggestions"),
+        };
+        match options {
+            MergeRequestOptions::ApplySuggestions(args) => {
+                assert_eq!(23, args.id);
+                assert_eq!(vec![5, 6], args.suggestion_ids);
+                assert!(!args.all);
+            }
+            _ => panic!("Expected MergeRequestOptions::ApplySuggestions"),
+        }
+        assert!(Args::try_parse_from(vec![
+            "gr",
+            "mr",
+            "suggestions",
+            "apply",
+            "23",
+            "--suggestion",
+            "5",
+            "--all",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_submit_review_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "review",
+            "submit",
+            "review.toml",
+            "--force",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Review(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::Review"),
+        };
+        match options {
+            MergeRequestOptions::SubmitReview { file, force } => {
+                assert_eq!("review.toml", file);
+                assert!(force);
+            }
+            _ => panic!("Expected MergeRequestOptions::SubmitReview"),
+        }
+    }
+
+    #[test]
+    fn test_review_request_changes_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "review",
+            "request-changes",
+            "23",
+            "--body",
+            "Needs tests",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Review(options),
+            }) => options.into(),
+            _ => panic!("Expected MergeRequestCommand::Review"),
+        };
+        match options {
+            MergeRequestOptions::Review(args) => {
+                assert_eq!(23, args.id);
+                assert_eq!(ReviewEvent::RequestChanges, args.event);
+                assert_eq!("Needs tests", args.body);
+            }
+            _ => panic!("Expected MergeRequestOptions::Review"),
+        }
+        // Requesting changes without saying which is not allowed.
+        assert!(Args::try_parse_from(vec!["gr", "mr", "review", "request-changes", "23"]).is_err());
+    }
+
+    #[test]
+    fn test_react_merge_request_invalid_emoji() {
+        assert!(Args::try_parse_from(vec!["gr", "mr", "react", "23", "::"]).is_err());
+        assert!(Args::try_parse_from(vec!["gr", "mr", "react", "23", ":thumbs up:"]).is_err());
+    }
+
     #[test]
     fn test_comment_merge_request_cli_args() {
         let args = Args::parse_from(vec!["gr", "mr", "comment", "create", "--id", "123", "LGTM"]);
@@ -498,11 +1745,30 @@ mod test {
             MergeRequestOptions::CreateComment(args) => {
                 assert_eq!(args.id, 123);
                 assert_eq!(args.comment, Some("LGTM".to_string()));
+                assert_eq!(args.retry, 0);
             }
             _ => panic!("Expected MergeRequestOptions::Comment"),
         }
     }
 
+    #[test]
+    fn test_comment_merge_request_with_retries_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr", "mr", "comment", "create", "--id", "123", "--retry", "3", "LGTM",
+        ]);
+        let comment_merge_request = match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Comment(CommentSubCommand::Create(args)),
+            }) => args,
+            _ => panic!("Expected CommentSubCommand::Create"),
+        };
+        let options: MergeRequestOptions = comment_merge_request.into();
+        match options {
+            MergeRequestOptions::CreateComment(args) => assert_eq!(args.retry, 3),
+            _ => panic!("Expected MergeRequestOptions::CreateComment"),
+        }
+    }
+
     #[test]
     fn test_list_all_comments_in_merge_request_cli_args() {
         let args = Args::parse_from(vec!["gr", "mr", "comment", "list", "123"]);
@@ -554,6 +1820,44 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_create_merge_request_web_cannot_depend_on_others() {
+        let args = Args::try_parse_from(vec!["gr", "mr", "create", "--web"]).unwrap();
+        match args.command {
+            Command::MergeRequest(MergeRequestCommand {
+                subcommand: MergeRequestSubcommand::Create(options),
+            }) => assert!(options.web),
+            _ => panic!("Expected MergeRequestCommand::Create"),
+        }
+        assert!(
+            Args::try_parse_from(vec!["gr", "mr", "create", "--web", "--depends-on", "21"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_create_merge_request_depends_on() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "create",
+            "--depends-on",
+            "31",
+            "--depends-on",
+            "32",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Create(args) => {
+                assert_eq!(vec![31, 32], args.depends_on);
+            }
+            _ => panic!("Expected MergeRequestOptions::Create"),
+        }
+    }
+
     #[test]
     fn test_get_merge_request_details_cli_args() {
         let args = Args::parse_from(vec!["gr", "mr", "get", "123"]);
@@ -717,13 +2021,13 @@ mod test {
             Command::MergeRequest(MergeRequestCommand {
                 subcommand: MergeRequestSubcommand::Create(options),
             }) => {
-                assert_eq!(options.reviewer, Some("john_doe".to_string()));
+                assert_eq!(options.reviewer, vec!["john_doe".to_string()]);
                 assert!(!options.rand_reviewer);
 
                 let mr_options: MergeRequestOptions = options.into();
                 match mr_options {
                     MergeRequestOptions::Create(args) => {
-                        assert_eq!(args.reviewer, Some("john_doe".to_string()));
+                        assert_eq!(args.reviewers, vec!["john_doe".to_string()]);
                         assert!(!args.rand_reviewer);
                     }
                     _ => panic!("Expected MergeRequestOptions::Create"),
@@ -742,13 +2046,13 @@ mod test {
                 subcommand: MergeRequestSubcommand::Create(options),
             }) => {
                 assert!(options.rand_reviewer);
-                assert_eq!(options.reviewer, None);
+                assert!(options.reviewer.is_empty());
 
                 let mr_options: MergeRequestOptions = options.into();
                 match mr_options {
                     MergeRequestOptions::Create(args) => {
                         assert!(args.rand_reviewer);
-                        assert_eq!(args.reviewer, None);
+                        assert!(args.reviewers.is_empty());
                     }
                     _ => panic!("Expected MergeRequestOptions::Create"),
                 }
@@ -757,6 +2061,44 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_multiple_reviewer_flags() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "mr",
+            "create",
+            "-R",
+            "jane_doe",
+            "--reviewer",
+            "john_doe",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Create(args) => {
+                assert_eq!(vec!["jane_doe", "john_doe"], args.reviewers);
+            }
+            _ => panic!("Expected MergeRequestOptions::Create"),
+        }
+    }
+
+    #[test]
+    fn test_multiple_label_flags() {
+        let args = Args::parse_from(vec![
+            "gr", "mr", "create", "--label", "bug", "--label", "ui",
+        ]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Create(args) => assert_eq!(vec!["bug", "ui"], args.labels),
+            _ => panic!("Expected MergeRequestOptions::Create"),
+        }
+    }
+
     #[test]
     fn test_mutually_exclusive_reviewer_flags() {
         let result = Args::try_parse_from(vec![
@@ -779,13 +2121,13 @@ mod test {
             Command::MergeRequest(MergeRequestCommand {
                 subcommand: MergeRequestSubcommand::Create(options),
             }) => {
-                assert_eq!(options.reviewer, Some("jane_doe".to_string()));
+                assert_eq!(options.reviewer, vec!["jane_doe".to_string()]);
                 assert!(!options.rand_reviewer);
 
                 let mr_options: MergeRequestOptions = options.into();
                 match mr_options {
                     MergeRequestOptions::Create(args) => {
-                        assert_eq!(args.reviewer, Some("jane_doe".to_string()));
+                        assert_eq!(args.reviewers, vec!["jane_doe".to_string()]);
                         assert!(!args.rand_reviewer);
                     }
                     _ => panic!("Expected MergeRequestOptions::Create"),
@@ -794,4 +2136,49 @@ mod test {
             _ => panic!("Expected MergeRequestCommand::Create"),
         }
     }
+
+    #[test]
+    fn test_merge_request_queue_status_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "queue", "status", "23"]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Queue(MergeQueueOptions::Status(args)) => {
+                assert_eq!(23, args.id);
+            }
+            _ => panic!("Expected MergeQueueOptions::Status"),
+        }
+    }
+
+    #[test]
+    fn test_merge_request_queue_remove_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "queue", "remove", "23"]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Queue(MergeQueueOptions::Remove { id }) => assert_eq!(23, id),
+            _ => panic!("Expected MergeQueueOptions::Remove"),
+        }
+    }
+
+    #[test]
+    fn test_merge_request_nag_cli_args() {
+        let args = Args::parse_from(vec!["gr", "mr", "nag", "--older-than", "3d", "--report"]);
+        let options: MergeRequestOptions = match args.command {
+            Command::MergeRequest(options) => options.into(),
+            _ => panic!("Expected MergeRequestCommand"),
+        };
+        match options {
+            MergeRequestOptions::Nag(args) => {
+                assert_eq!(Seconds::new(3 * 86400), args.older_than);
+                assert!(args.report);
+                assert!(!args.yes);
+            }
+            _ => panic!("Expected MergeRequestOptions::Nag"),
+        }
+    }
 }
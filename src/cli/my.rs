@@ -4,9 +4,14 @@ use crate::cmds::{
     gist::GistListCliArgs,
     merge_request::{MergeRequestListCliArgs, MergeRequestUser},
     project::ProjectListCliArgs,
+    user::ContributionsCliArgs,
 };
 
-use super::{common::ListArgs, merge_request::ListMergeRequest, project::ListProject};
+use super::{
+    common::{validate_date, GetArgs, ListArgs},
+    merge_request::ListMergeRequest,
+    project::ListProject,
+};
 
 #[derive(Parser)]
 pub struct MyCommand {
@@ -22,11 +27,30 @@ enum MySubcommand {
     )]
     MergeRequest(ListMyMergeRequest),
     #[clap(about = "Lists your projects", name = "pj")]
-    Project(ListProject),
+    Project(ListMyProject),
     #[clap(about = "Lists your starred projects", name = "st")]
     Star(ListStar),
     #[clap(about = "Lists your gists", name = "gs")]
     Gist(ListGist),
+    #[clap(
+        about = "Summary of your contributions per day: merge requests opened and merged, reviews, issues opened and pushes",
+        name = "contributions"
+    )]
+    Contributions(ListContributions),
+}
+
+#[derive(Parser)]
+struct ListContributions {
+    /// Only count contributions after this date (exclusive). Format is
+    /// YYYY-MM-DD
+    #[clap(long, value_name = "DATE", value_parser = validate_date)]
+    after: Option<String>,
+    /// Only count contributions before this date (exclusive). Format is
+    /// YYYY-MM-DD
+    #[clap(long, value_name = "DATE", value_parser = validate_date)]
+    before: Option<String>,
+    #[clap(flatten)]
+    get_args: GetArgs,
 }
 
 #[derive(Parser)]
@@ -41,14 +65,21 @@ struct ListMyMergeRequest {
     /// Filter merge requests where you are the reviewer. Gitlab only.
     #[clap(long, group = "merge_request")]
     reviewer: bool,
+    /// List merge requests in every domain configured in the config
+    /// directory. Domains are queried in parallel and a Domain column is
+    /// added
+    #[clap(long, conflicts_with_all = ["num_pages", "num_resources", "stream"])]
+    all_domains: bool,
     #[clap(flatten)]
     list_merge_request: ListMergeRequest,
 }
 
 pub enum MyOptions {
     MergeRequest(MergeRequestListCliArgs),
+    MergeRequestAllDomains(MergeRequestListCliArgs),
     Project(ProjectListCliArgs),
     Gist(GistListCliArgs),
+    Contributions(ContributionsCliArgs),
 }
 
 impl From<MyCommand> for MyOptions {
@@ -58,45 +89,65 @@ impl From<MyCommand> for MyOptions {
             MySubcommand::Project(options) => options.into(),
             MySubcommand::Star(options) => options.into(),
             MySubcommand::Gist(options) => options.into(),
+            MySubcommand::Contributions(options) => options.into(),
         }
     }
 }
 
 impl From<ListMyMergeRequest> for MyOptions {
     fn from(options: ListMyMergeRequest) -> Self {
-        MyOptions::MergeRequest(
-            MergeRequestListCliArgs::builder()
-                .state(options.list_merge_request.state.into())
-                .list_args(options.list_merge_request.list_args.into())
-                .assignee(if options.assignee {
-                    Some(MergeRequestUser::Me)
-                } else {
-                    None
-                })
-                // Author is the default if none is provided.
-                .author(
-                    if options.author || (!options.assignee && !options.reviewer) {
-                        Some(MergeRequestUser::Me)
-                    } else {
-                        None
-                    },
-                )
-                .reviewer(if options.reviewer {
+        let cli_args = MergeRequestListCliArgs::builder()
+            .state(options.list_merge_request.state.into())
+            .list_args(
+                options
+                    .list_merge_request
+                    .list_args
+                    .ordered_by(options.list_merge_request.order_by),
+            )
+            .assignee(if options.assignee {
+                Some(MergeRequestUser::Me)
+            } else {
+                None
+            })
+            // Author is the default if none is provided.
+            .author(
+                if options.author || (!options.assignee && !options.reviewer) {
                     Some(MergeRequestUser::Me)
                 } else {
                     None
-                })
-                .build()
-                .unwrap(),
-        )
+                },
+            )
+            .reviewer(if options.reviewer {
+                Some(MergeRequestUser::Me)
+            } else {
+                None
+            })
+            .build()
+            .unwrap();
+        if options.all_domains {
+            MyOptions::MergeRequestAllDomains(cli_args)
+        } else {
+            MyOptions::MergeRequest(cli_args)
+        }
     }
 }
 
-impl From<ListProject> for MyOptions {
-    fn from(options: ListProject) -> Self {
+#[derive(Parser)]
+struct ListMyProject {
+    /// List the projects of a Gitlab group, including its subgroups, or the
+    /// repositories of a Github organization instead. Ex. team/subgroup
+    #[clap(long, value_name = "GROUP")]
+    group: Option<String>,
+    #[clap(flatten)]
+    list_project: ListProject,
+}
+
+impl From<ListMyProject> for MyOptions {
+    fn from(options: ListMyProject) -> Self {
         MyOptions::Project(
             ProjectListCliArgs::builder()
-                .list_args(options.list_args.into())
+                .list_args(options.list_project.list_args.into())
+                .group(options.group)
                 .build()
                 .unwrap(),
         )
@@ -138,6 +189,19 @@ impl From<ListGist> for MyOptions {
     }
 }
 
+impl From<ListContributions> for MyOptions {
+    fn from(options: ListContributions) -> Self {
+        MyOptions::Contributions(
+            ContributionsCliArgs::builder()
+                .after(options.after)
+                .before(options.before)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,6 +313,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_my_merge_request_cli_args_all_domains() {
+        let args = Args::parse_from(vec!["gr", "my", "mr", "opened", "--all-domains"]);
+        let options: MyOptions = match args.command {
+            Command::My(options) => options.into(),
+            _ => panic!("Expected MyCommand"),
+        };
+        match options {
+            MyOptions::MergeRequestAllDomains(options) => {
+                assert_eq!(options.state, MergeRequestState::Opened);
+                assert_eq!(options.author, Some(MergeRequestUser::Me));
+            }
+            _ => panic!("Expected MyOptions::MergeRequestAllDomains"),
+        }
+    }
+
+    #[test]
+    fn test_my_merge_request_all_domains_conflicts_with_num_pages() {
+        let result = Args::try_parse_from(vec![
+            "gr",
+            "my",
+            "mr",
+            "opened",
+            "--all-domains",
+            "--num-pages",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_my_projects_cli_args() {
         let args = Args::parse_from(vec!["gr", "my", "pj"]);
@@ -265,6 +358,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_my_projects_of_group_cli_args() {
+        let args = Args::parse_from(vec!["gr", "my", "pj", "--group", "team/subgroup"]);
+        let my_command = match args.command {
+            Command::My(MyCommand {
+                subcommand: MySubcommand::Project(options),
+            }) => options,
+            _ => panic!("Expected MyCommand"),
+        };
+        match my_command.into() {
+            MyOptions::Project(cli_args) => {
+                assert_eq!(Some("team/subgroup".to_string()), cli_args.group)
+            }
+            _ => panic!("Expected MyOptions::Project"),
+        }
+    }
+
     #[test]
     fn test_my_stars_cli_args() {
         let args = Args::parse_from(vec!["gr", "my", "st"]);
@@ -296,4 +406,37 @@ mod tests {
             _ => panic!("Expected MyOptions::Gist"),
         }
     }
+
+    #[test]
+    fn test_my_contributions_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "my",
+            "contributions",
+            "--after",
+            "2024-02-01",
+            "--before",
+            "2024-03-01",
+        ]);
+        let my_command = match args.command {
+            Command::My(MyCommand {
+                subcommand: MySubcommand::Contributions(options),
+            }) => options,
+            _ => panic!("Expected MyCommand"),
+        };
+        let options: MyOptions = my_command.into();
+        match options {
+            MyOptions::Contributions(args) => {
+                assert_eq!(Some("2024-02-01".to_string()), args.after);
+                assert_eq!(Some("2024-03-01".to_string()), args.before);
+            }
+            _ => panic!("Expected MyOptions::Contributions"),
+        }
+    }
+
+    #[test]
+    fn test_my_contributions_invalid_date() {
+        let result = Args::try_parse_from(vec!["gr", "my", "contributions", "--after", "02/2024"]);
+        assert!(result.is_err());
+    }
 }
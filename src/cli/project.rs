@@ -1,8 +1,17 @@
-use clap::Parser;
+use std::fmt::{self, Display, Formatter};
 
-use crate::cmds::project::{ProjectListCliArgs, ProjectMetadataGetCliArgs};
+use clap::{Parser, ValueEnum};
 
-use super::common::{validate_domain_project_repo_path, GetArgs, ListArgs};
+use crate::cmds::project::{
+    InvitationAction, MemberAccessLevel, MemberAction, ProjectAccessCheckCliArgs,
+    ProjectBlameCliArgs, ProjectDependencyCliArgs, ProjectInvitationCliArgs, ProjectListCliArgs,
+    ProjectMemberCliArgs, ProjectMetadataGetCliArgs, ProjectMirrorCliArgs,
+};
+use crate::remote::GetRemoteCliArgs;
+
+use super::common::{
+    validate_domain_project_repo_path, CacheArgs, FormatCli, GetArgs, ListArgs, RetryArgs,
+};
 
 #[derive(Parser)]
 pub struct ProjectCommand {
@@ -16,8 +25,305 @@ enum ProjectSubcommand {
     Info(ProjectInfo),
     #[clap(about = "List project members")]
     Members(ListMembers),
+    #[clap(subcommand, about = "Add, update, remove and block project members")]
+    Member(MemberSubcommand),
+    #[clap(
+        subcommand,
+        about = "List and revoke pending invitations to the project"
+    )]
+    Invites(InvitesSubcommand),
+    #[clap(
+        about = "Show the effective access of a user and where it comes from: direct membership, groups or teams"
+    )]
+    AccessCheck(AccessCheck),
     #[clap(about = "List project/repository tags")]
     Tags(ListProject),
+    #[clap(about = "Blame a file in the remote repository")]
+    Blame(BlameFile),
+    #[clap(about = "List project dependencies from the dependency graph")]
+    Deps(ListDependencies),
+    #[clap(subcommand, about = "Project mirror operations")]
+    Mirror(MirrorSubcommand),
+}
+
+#[derive(Parser)]
+struct BlameFile {
+    /// Path of the file in the repository
+    #[clap()]
+    path: String,
+    /// Branch, tag or commit SHA to blame the file at
+    #[clap(long = "ref", default_value = "HEAD", value_name = "REF")]
+    git_ref: String,
+    /// Blame only the given inclusive range of lines
+    #[clap(long, value_name = "START,END", value_parser = parse_line_range)]
+    range: Option<(usize, usize)>,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+fn parse_line_range(range: &str) -> Result<(usize, usize), String> {
+    let (start, end) = range
+        .split_once(',')
+        .ok_or_else(|| "Line range must be in the format START,END".to_string())?;
+    let start = start
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid start line: {}", start))?;
+    let end = end
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid end line: {}", end))?;
+    if start == 0 || start > end {
+        return Err(format!(
+            "Invalid line range {},{}. Lines start at 1 and START must not exceed END",
+            start, end
+        ));
+    }
+    Ok((start, end))
+}
+
+#[derive(Parser)]
+struct ListDependencies {
+    /// Output format. cyclonedx outputs a CycloneDX JSON SBOM document
+    #[clap(long, default_value_t=DependencyFormatCli::Pipe, help_heading = "Formatting options")]
+    format: DependencyFormatCli,
+    /// Do not print headers
+    #[clap(long, help_heading = "Formatting options")]
+    no_headers: bool,
+    /// Display additional fields
+    #[clap(visible_short_alias = 'o', long, help_heading = "Formatting options")]
+    more_output: bool,
+    #[clap(flatten)]
+    cache_args: CacheArgs,
+    #[clap(flatten)]
+    retry_args: RetryArgs,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum DependencyFormatCli {
+    Csv,
+    Json,
+    Pipe,
+    Toml,
+    Cyclonedx,
+}
+
+impl Display for DependencyFormatCli {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyFormatCli::Csv => write!(f, "csv"),
+            DependencyFormatCli::Json => write!(f, "json"),
+            DependencyFormatCli::Pipe => write!(f, "pipe"),
+            DependencyFormatCli::Toml => write!(f, "toml"),
+            DependencyFormatCli::Cyclonedx => write!(f, "cyclonedx"),
+        }
+    }
+}
+
+#[derive(Parser)]
+enum MemberSubcommand {
+    #[clap(about = "Add a user to the project. Github sends an invitation to the user")]
+    Add(MemberAccess),
+    #[clap(about = "Change the access level of a project member")]
+    Update(MemberAccess),
+    #[clap(about = "Remove a user from the project")]
+    Remove(MemberUser),
+    #[clap(
+        about = "Block a user. Gitlab blocks the user in the instance, admin only. Github blocks the user in the organization"
+    )]
+    Block(MemberBlock),
+    #[clap(about = "Invite someone by email. Github invites the user with that public email")]
+    Invite(MemberInvite),
+}
+
+#[derive(Parser)]
+struct MemberInvite {
+    /// Email of the invitee
+    #[clap()]
+    email: String,
+    /// Access level. Github permissions are pull, triage, push, maintain
+    /// and admin respectively
+    #[clap(long, value_name = "LEVEL")]
+    access: AccessLevelCli,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct MemberAccess {
+    /// Username of the member
+    #[clap()]
+    username: String,
+    /// Access level. Github permissions are pull, triage, push, maintain
+    /// and admin respectively
+    #[clap(long, value_name = "LEVEL")]
+    access: AccessLevelCli,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct MemberUser {
+    /// Username of the member
+    #[clap()]
+    username: String,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct MemberBlock {
+    /// Username of the member
+    #[clap()]
+    username: String,
+    /// Block without prompting for confirmation
+    #[clap(long, short)]
+    yes: bool,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum AccessLevelCli {
+    Guest,
+    Reporter,
+    Developer,
+    Maintainer,
+    Owner,
+}
+
+impl From<AccessLevelCli> for MemberAccessLevel {
+    fn from(access: AccessLevelCli) -> Self {
+        match access {
+            AccessLevelCli::Guest => MemberAccessLevel::Guest,
+            AccessLevelCli::Reporter => MemberAccessLevel::Reporter,
+            AccessLevelCli::Developer => MemberAccessLevel::Developer,
+            AccessLevelCli::Maintainer => MemberAccessLevel::Maintainer,
+            AccessLevelCli::Owner => MemberAccessLevel::Owner,
+        }
+    }
+}
+
+impl From<MemberSubcommand> for ProjectOptions {
+    fn from(options: MemberSubcommand) -> Self {
+        let (username, action, yes, get_args) = match options {
+            MemberSubcommand::Add(options) => (
+                options.username,
+                MemberAction::Add(options.access.into()),
+                false,
+                options.get_args,
+            ),
+            MemberSubcommand::Update(options) => (
+                options.username,
+                MemberAction::Update(options.access.into()),
+                false,
+                options.get_args,
+            ),
+            MemberSubcommand::Remove(options) => (
+                options.username,
+                MemberAction::Remove,
+                false,
+                options.get_args,
+            ),
+            MemberSubcommand::Block(options) => (
+                options.username,
+                MemberAction::Block,
+                options.yes,
+                options.get_args,
+            ),
+            MemberSubcommand::Invite(options) => (
+                options.email,
+                MemberAction::Invite(options.access.into()),
+                false,
+                options.get_args,
+            ),
+        };
+        ProjectOptions::Member(
+            ProjectMemberCliArgs::builder()
+                .username(username)
+                .action(action)
+                .yes(yes)
+                .get_args(get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+#[derive(Parser)]
+struct AccessCheck {
+    /// Username to check
+    #[clap()]
+    username: String,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+impl From<AccessCheck> for ProjectOptions {
+    fn from(options: AccessCheck) -> Self {
+        ProjectOptions::AccessCheck(
+            ProjectAccessCheckCliArgs::builder()
+                .username(options.username)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+#[derive(Parser)]
+enum InvitesSubcommand {
+    #[clap(about = "List pending invitations")]
+    List(InvitesList),
+    #[clap(about = "Revoke a pending invitation")]
+    Revoke(InviteRevoke),
+}
+
+#[derive(Parser)]
+struct InvitesList {
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+#[derive(Parser)]
+struct InviteRevoke {
+    /// Email of the invitee in Gitlab, username in Github
+    #[clap()]
+    invitee: String,
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+impl From<InvitesSubcommand> for ProjectOptions {
+    fn from(options: InvitesSubcommand) -> Self {
+        let (action, get_args) = match options {
+            InvitesSubcommand::List(options) => (InvitationAction::List, options.get_args),
+            InvitesSubcommand::Revoke(options) => {
+                (InvitationAction::Revoke(options.invitee), options.get_args)
+            }
+        };
+        ProjectOptions::Invitations(
+            ProjectInvitationCliArgs::builder()
+                .action(action)
+                .get_args(get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+#[derive(Parser)]
+enum MirrorSubcommand {
+    #[clap(about = "Get the status of the project pull and push mirrors")]
+    Status(MirrorArgs),
+    #[clap(about = "Force an update of the project mirrors")]
+    Sync(MirrorArgs),
+}
+
+#[derive(Parser)]
+struct MirrorArgs {
+    #[clap(flatten)]
+    pub get_args: GetArgs,
 }
 
 #[derive(Parser)]
@@ -35,6 +341,10 @@ struct ProjectInfo {
 
 #[derive(Parser)]
 pub struct ListMembers {
+    /// List the members of a Gitlab group, including its subgroups, or of a
+    /// Github organization instead. Ex. team/subgroup
+    #[clap(long, value_name = "GROUP")]
+    pub group: Option<String>,
     #[clap(flatten)]
     pub list_args: ListArgs,
 }
@@ -51,10 +361,78 @@ impl From<ProjectCommand> for ProjectOptions {
             ProjectSubcommand::Info(options) => options.into(),
             ProjectSubcommand::Tags(options) => options.into(),
             ProjectSubcommand::Members(options) => options.into(),
+            ProjectSubcommand::Member(options) => options.into(),
+            ProjectSubcommand::Invites(options) => options.into(),
+            ProjectSubcommand::AccessCheck(options) => options.into(),
+            ProjectSubcommand::Blame(options) => options.into(),
+            ProjectSubcommand::Deps(options) => options.into(),
+            ProjectSubcommand::Mirror(options) => options.into(),
+        }
+    }
+}
+
+impl From<BlameFile> for ProjectOptions {
+    fn from(options: BlameFile) -> Self {
+        ProjectOptions::Blame(
+            ProjectBlameCliArgs::builder()
+                .path(options.path)
+                .git_ref(options.git_ref)
+                .range(options.range)
+                .get_args(options.get_args.into())
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<ListDependencies> for ProjectOptions {
+    fn from(options: ListDependencies) -> Self {
+        // CycloneDX is handled as a document on its own. The listing
+        // format is irrelevant in that case.
+        let format = match options.format {
+            DependencyFormatCli::Csv => FormatCli::Csv,
+            DependencyFormatCli::Json => FormatCli::Json,
+            DependencyFormatCli::Toml => FormatCli::Toml,
+            DependencyFormatCli::Pipe | DependencyFormatCli::Cyclonedx => FormatCli::Pipe,
+        };
+        ProjectOptions::Dependencies(
+            ProjectDependencyCliArgs::builder()
+                .get_args(
+                    GetRemoteCliArgs::builder()
+                        .no_headers(options.no_headers)
+                        .format(format.into())
+                        .display_optional(options.more_output)
+                        .cache_args(options.cache_args.into())
+                        .backoff_max_retries(options.retry_args.max_retries)
+                        .backoff_retry_after(options.retry_args.retry_after)
+                        .build()
+                        .unwrap(),
+                )
+                .cyclonedx(options.format == DependencyFormatCli::Cyclonedx)
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl From<MirrorSubcommand> for ProjectOptions {
+    fn from(options: MirrorSubcommand) -> Self {
+        match options {
+            MirrorSubcommand::Status(options) => ProjectOptions::MirrorStatus(options.into()),
+            MirrorSubcommand::Sync(options) => ProjectOptions::MirrorSync(options.into()),
         }
     }
 }
 
+impl From<MirrorArgs> for ProjectMirrorCliArgs {
+    fn from(options: MirrorArgs) -> Self {
+        ProjectMirrorCliArgs::builder()
+            .get_args(options.get_args.into())
+            .build()
+            .unwrap()
+    }
+}
+
 impl From<ProjectInfo> for ProjectOptions {
     fn from(options: ProjectInfo) -> Self {
         ProjectOptions::Info(
@@ -86,6 +464,7 @@ impl From<ListMembers> for ProjectOptions {
             ProjectListCliArgs::builder()
                 .list_args(options.list_args.into())
                 .members(true)
+                .group(options.group)
                 .build()
                 .unwrap(),
         )
@@ -96,6 +475,13 @@ pub enum ProjectOptions {
     Info(ProjectMetadataGetCliArgs),
     Tags(ProjectListCliArgs),
     Members(ProjectListCliArgs),
+    Member(ProjectMemberCliArgs),
+    Invitations(ProjectInvitationCliArgs),
+    AccessCheck(ProjectAccessCheckCliArgs),
+    Blame(ProjectBlameCliArgs),
+    Dependencies(ProjectDependencyCliArgs),
+    MirrorStatus(ProjectMirrorCliArgs),
+    MirrorSync(ProjectMirrorCliArgs),
 }
 
 #[cfg(test)]
@@ -125,6 +511,103 @@ mod test {
         }
     }
 
+    fn member_options(args: Vec<&str>) -> ProjectMemberCliArgs {
+        let args = Args::parse_from(args);
+        match args.command {
+            Command::Project(options) => match options.into() {
+                ProjectOptions::Member(cli_args) => cli_args,
+                _ => panic!("Expected ProjectOptions::Member"),
+            },
+            _ => panic!("Expected ProjectCommand"),
+        }
+    }
+
+    #[test]
+    fn test_project_cli_member_access() {
+        let cli_args = member_options(vec![
+            "gr",
+            "pj",
+            "member",
+            "add",
+            "octocat",
+            "--access",
+            "developer",
+        ]);
+        assert_eq!("octocat", cli_args.username);
+        assert_eq!(
+            MemberAction::Add(MemberAccessLevel::Developer),
+            cli_args.action
+        );
+        let cli_args = member_options(vec![
+            "gr", "pj", "member", "update", "octocat", "--access", "owner",
+        ]);
+        assert_eq!(
+            MemberAction::Update(MemberAccessLevel::Owner),
+            cli_args.action
+        );
+        let cli_args = member_options(vec!["gr", "pj", "member", "remove", "octocat"]);
+        assert_eq!(MemberAction::Remove, cli_args.action);
+        let cli_args = member_options(vec!["gr", "pj", "member", "block", "octocat"]);
+        assert_eq!(MemberAction::Block, cli_args.action);
+        assert!(!cli_args.yes);
+        let cli_args = member_options(vec!["gr", "pj", "member", "block", "octocat", "-y"]);
+        assert!(cli_args.yes);
+    }
+
+    #[test]
+    fn test_project_cli_member_invite_by_email() {
+        let cli_args = member_options(vec![
+            "gr",
+            "pj",
+            "member",
+            "invite",
+            "tom@example.com",
+            "--access",
+            "reporter",
+        ]);
+        assert_eq!("tom@example.com", cli_args.username);
+        assert_eq!(
+            MemberAction::Invite(MemberAccessLevel::Reporter),
+            cli_args.action
+        );
+    }
+
+    #[test]
+    fn test_project_cli_invites_list_and_revoke() {
+        let invitation_action = |args: Vec<&str>| match Args::parse_from(args).command {
+            Command::Project(options) => match options.into() {
+                ProjectOptions::Invitations(cli_args) => cli_args.action,
+                _ => panic!("Expected ProjectOptions::Invitations"),
+            },
+            _ => panic!("Expected ProjectCommand"),
+        };
+        assert_eq!(
+            InvitationAction::List,
+            invitation_action(vec!["gr", "pj", "invites", "list"])
+        );
+        assert_eq!(
+            InvitationAction::Revoke("tom@example.com".to_string()),
+            invitation_action(vec!["gr", "pj", "invites", "revoke", "tom@example.com"])
+        );
+    }
+
+    #[test]
+    fn test_project_cli_access_check() {
+        let args = Args::parse_from(vec!["gr", "pj", "access-check", "octocat"]);
+        match args.command {
+            Command::Project(options) => match options.into() {
+                ProjectOptions::AccessCheck(cli_args) => assert_eq!("octocat", cli_args.username),
+                _ => panic!("Expected ProjectOptions::AccessCheck"),
+            },
+            _ => panic!("Expected ProjectCommand"),
+        }
+    }
+
+    #[test]
+    fn test_project_cli_member_add_requires_access() {
+        assert!(Args::try_parse_from(vec!["gr", "pj", "member", "add", "octocat"]).is_err());
+    }
+
     #[test]
     fn test_project_cli_list_tags() {
         let args = Args::parse_from(vec!["gr", "pj", "tags"]);
@@ -164,4 +647,124 @@ mod test {
             _ => panic!("Expected ProjectOptions::Members"),
         }
     }
+
+    #[test]
+    fn test_project_cli_mirror_status() {
+        let args = Args::parse_from(vec!["gr", "pj", "mirror", "status"]);
+        let mirror = match args.command {
+            Command::Project(ProjectCommand {
+                subcommand: ProjectSubcommand::Mirror(options),
+            }) => options,
+            _ => panic!("Expected ProjectCommand::Mirror"),
+        };
+        let options: ProjectOptions = mirror.into();
+        match options {
+            ProjectOptions::MirrorStatus(_) => {}
+            _ => panic!("Expected ProjectOptions::MirrorStatus"),
+        }
+    }
+
+    #[test]
+    fn test_project_cli_mirror_sync() {
+        let args = Args::parse_from(vec!["gr", "pj", "mirror", "sync"]);
+        let mirror = match args.command {
+            Command::Project(ProjectCommand {
+                subcommand: ProjectSubcommand::Mirror(options),
+            }) => options,
+            _ => panic!("Expected ProjectCommand::Mirror"),
+        };
+        let options: ProjectOptions = mirror.into();
+        match options {
+            ProjectOptions::MirrorSync(_) => {}
+            _ => panic!("Expected ProjectOptions::MirrorSync"),
+        }
+    }
+
+    #[test]
+    fn test_project_cli_deps_cyclonedx() {
+        let args = Args::parse_from(vec!["gr", "pj", "deps", "--format", "cyclonedx"]);
+        let deps = match args.command {
+            Command::Project(ProjectCommand {
+                subcommand: ProjectSubcommand::Deps(options),
+            }) => {
+                assert_eq!(options.format, DependencyFormatCli::Cyclonedx);
+                options
+            }
+            _ => panic!("Expected ProjectCommand::Deps"),
+        };
+        let options: ProjectOptions = deps.into();
+        match options {
+            ProjectOptions::Dependencies(cli_args) => assert!(cli_args.cyclonedx),
+            _ => panic!("Expected ProjectOptions::Dependencies"),
+        }
+    }
+
+    #[test]
+    fn test_project_cli_deps_default_listing() {
+        let args = Args::parse_from(vec!["gr", "pj", "deps"]);
+        let deps = match args.command {
+            Command::Project(ProjectCommand {
+                subcommand: ProjectSubcommand::Deps(options),
+            }) => options,
+            _ => panic!("Expected ProjectCommand::Deps"),
+        };
+        let options: ProjectOptions = deps.into();
+        match options {
+            ProjectOptions::Dependencies(cli_args) => assert!(!cli_args.cyclonedx),
+            _ => panic!("Expected ProjectOptions::Dependencies"),
+        }
+    }
+
+    #[test]
+    fn test_project_cli_blame() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "pj",
+            "blame",
+            "src/main.rs",
+            "--ref",
+            "main",
+            "--range",
+            "10,40",
+        ]);
+        let blame = match args.command {
+            Command::Project(ProjectCommand {
+                subcommand: ProjectSubcommand::Blame(options),
+            }) => options,
+            _ => panic!("Expected ProjectCommand::Blame"),
+        };
+        let options: ProjectOptions = blame.into();
+        match options {
+            ProjectOptions::Blame(cli_args) => {
+                assert_eq!("src/main.rs", cli_args.path);
+                assert_eq!("main", cli_args.git_ref);
+                assert_eq!(Some((10, 40)), cli_args.range);
+            }
+            _ => panic!("Expected ProjectOptions::Blame"),
+        }
+    }
+
+    #[test]
+    fn test_project_cli_blame_defaults_to_head() {
+        let args = Args::parse_from(vec!["gr", "pj", "blame", "README.md"]);
+        match args.command {
+            Command::Project(ProjectCommand {
+                subcommand: ProjectSubcommand::Blame(options),
+            }) => {
+                assert_eq!("HEAD", options.git_ref);
+                assert_eq!(None, options.range);
+            }
+            _ => panic!("Expected ProjectCommand::Blame"),
+        }
+    }
+
+    #[test]
+    fn test_parse_line_range() {
+        assert_eq!(Ok((10, 40)), parse_line_range("10,40"));
+        assert_eq!(Ok((5, 5)), parse_line_range("5, 5"));
+        assert!(parse_line_range("40,10").is_err());
+        assert!(parse_line_range("0,10").is_err());
+        assert!(parse_line_range("10").is_err());
+        assert!(parse_line_range("a,b").is_err());
+    }
 }
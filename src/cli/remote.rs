@@ -0,0 +1,54 @@
+use clap::Parser;
+
+use crate::remote::GetRemoteCliArgs;
+
+use super::common::GetArgs;
+
+#[derive(Parser)]
+pub struct RemoteCommand {
+    #[clap(subcommand)]
+    subcommand: RemoteSubcommand,
+}
+
+#[derive(Parser)]
+enum RemoteSubcommand {
+    #[clap(about = "Version of the remote and features it does not support")]
+    Info(RemoteInfo),
+}
+
+#[derive(Parser)]
+struct RemoteInfo {
+    #[clap(flatten)]
+    get_args: GetArgs,
+}
+
+impl From<RemoteCommand> for RemoteOptions {
+    fn from(options: RemoteCommand) -> Self {
+        match options.subcommand {
+            RemoteSubcommand::Info(options) => RemoteOptions::Info(options.get_args.into()),
+        }
+    }
+}
+
+pub enum RemoteOptions {
+    Info(GetRemoteCliArgs),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_remote_info_cli_args() {
+        let args = Args::parse_from(vec!["gr", "remote", "info", "--refresh"]);
+        let options: RemoteOptions = match args.command {
+            Command::Remote(options) => options.into(),
+            _ => panic!("Expected RemoteCommand"),
+        };
+        match options {
+            RemoteOptions::Info(args) => assert!(args.cache_args.refresh),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use clap::{Parser, ValueEnum};
+
+use crate::cmds::report::{ReportFormat, WeeklyReportCliArgs};
+
+#[derive(Parser)]
+pub struct ReportCommand {
+    #[clap(subcommand)]
+    subcommand: ReportSubcommand,
+}
+
+#[derive(Parser)]
+enum ReportSubcommand {
+    #[clap(
+        about = "Merge requests opened and merged, top reviewers and pipeline pass rate over the last 7 days",
+        name = "mr-weekly"
+    )]
+    MergeRequestWeekly(MergeRequestWeekly),
+}
+
+#[derive(Parser)]
+struct MergeRequestWeekly {
+    /// Output format. Markdown is suitable for pasting into team chat
+    #[clap(long, default_value = "md")]
+    output: ReportFormatCli,
+    /// Template file to render the report with instead of the default one.
+    /// Placeholders {since}, {until}, {opened_count}, {merged_count},
+    /// {opened}, {merged}, {top_reviewers} and {pass_rate} are replaced by
+    /// the report values
+    #[clap(long, value_name = "FILE")]
+    template: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, PartialEq, Debug)]
+enum ReportFormatCli {
+    Md,
+    Text,
+}
+
+impl From<ReportFormatCli> for ReportFormat {
+    fn from(format: ReportFormatCli) -> Self {
+        match format {
+            ReportFormatCli::Md => ReportFormat::Markdown,
+            ReportFormatCli::Text => ReportFormat::Text,
+        }
+    }
+}
+
+impl From<ReportCommand> for ReportOptions {
+    fn from(options: ReportCommand) -> Self {
+        match options.subcommand {
+            ReportSubcommand::MergeRequestWeekly(options) => ReportOptions::MergeRequestWeekly(
+                WeeklyReportCliArgs::builder()
+                    .format(options.output.into())
+                    .template(options.template)
+                    .build()
+                    .unwrap(),
+            ),
+        }
+    }
+}
+
+pub enum ReportOptions {
+    MergeRequestWeekly(WeeklyReportCliArgs),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    fn options(argv: Vec<&str>) -> ReportOptions {
+        let args = Args::parse_from(argv);
+        match args.command {
+            Command::Report(options) => options.into(),
+            _ => panic!("Expected ReportCommand"),
+        }
+    }
+
+    #[test]
+    fn test_mr_weekly_defaults_to_markdown() {
+        let ReportOptions::MergeRequestWeekly(args) = options(vec!["gr", "report", "mr-weekly"]);
+        assert_eq!(ReportFormat::Markdown, args.format);
+        assert_eq!(None, args.template);
+    }
+
+    #[test]
+    fn test_mr_weekly_text_with_template() {
+        let ReportOptions::MergeRequestWeekly(args) = options(vec![
+            "gr",
+            "report",
+            "mr-weekly",
+            "--output",
+            "text",
+            "--template",
+            "weekly.txt",
+        ]);
+        assert_eq!(ReportFormat::Text, args.format);
+        assert_eq!(Some("weekly.txt".to_string()), args.template);
+    }
+}
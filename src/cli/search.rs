@@ -0,0 +1,96 @@
+use clap::Parser;
+
+use crate::cmds::search::{SearchCliArgs, SearchScope};
+
+use super::common::ListArgs;
+
+#[derive(Parser)]
+pub struct SearchCommand {
+    #[clap(subcommand)]
+    subcommand: SearchSubcommand,
+}
+
+#[derive(Parser)]
+enum SearchSubcommand {
+    #[clap(about = "Search issues")]
+    Issue(SearchArgs),
+    #[clap(name = "mr", about = "Search merge requests")]
+    MergeRequest(SearchArgs),
+    #[clap(about = "Search commits")]
+    Commit(SearchArgs),
+}
+
+#[derive(Parser)]
+struct SearchArgs {
+    /// Search terms
+    #[clap()]
+    query: String,
+    #[command(flatten)]
+    list_args: ListArgs,
+}
+
+impl SearchArgs {
+    fn into_cli_args(self, scope: SearchScope) -> SearchCliArgs {
+        SearchCliArgs::builder()
+            .scope(scope)
+            .query(self.query)
+            .list_args(self.list_args.into())
+            .build()
+            .unwrap()
+    }
+}
+
+impl From<SearchCommand> for SearchOptions {
+    fn from(options: SearchCommand) -> Self {
+        let cli_args = match options.subcommand {
+            SearchSubcommand::Issue(args) => args.into_cli_args(SearchScope::Issues),
+            SearchSubcommand::MergeRequest(args) => args.into_cli_args(SearchScope::MergeRequests),
+            SearchSubcommand::Commit(args) => args.into_cli_args(SearchScope::Commits),
+        };
+        SearchOptions::Search(cli_args)
+    }
+}
+
+pub enum SearchOptions {
+    Search(SearchCliArgs),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_search_merge_requests_cli_args() {
+        let args = Args::parse_from(vec!["gr", "search", "mr", "rate limit"]);
+        let options = match args.command {
+            Command::Search(options) => options,
+            _ => panic!("Expected SearchCommand"),
+        };
+        let options: SearchOptions = options.into();
+        match options {
+            SearchOptions::Search(cli_args) => {
+                assert_eq!(SearchScope::MergeRequests, cli_args.scope);
+                assert_eq!("rate limit", cli_args.query);
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_commits_cli_args() {
+        let args = Args::parse_from(vec!["gr", "search", "commit", "etag", "--num-pages"]);
+        let options = match args.command {
+            Command::Search(options) => options,
+            _ => panic!("Expected SearchCommand"),
+        };
+        let options: SearchOptions = options.into();
+        match options {
+            SearchOptions::Search(cli_args) => {
+                assert_eq!(SearchScope::Commits, cli_args.scope);
+                assert_eq!("etag", cli_args.query);
+                assert!(cli_args.list_args.num_pages);
+            }
+        }
+    }
+}
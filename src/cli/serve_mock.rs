@@ -0,0 +1,61 @@
+use clap::Parser;
+
+#[derive(Parser)]
+pub struct ServeMockCommand {
+    /// Directory with the gitlab/ and github/ contracts to serve
+    #[clap(long, default_value = "contracts")]
+    pub contracts: String,
+    /// Port to listen on localhost
+    #[clap(long, default_value = "8080")]
+    pub port: u16,
+}
+
+pub struct ServeMockOptions {
+    pub contracts: String,
+    pub port: u16,
+}
+
+impl From<ServeMockCommand> for ServeMockOptions {
+    fn from(options: ServeMockCommand) -> Self {
+        ServeMockOptions {
+            contracts: options.contracts,
+            port: options.port,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Command};
+
+    use super::*;
+
+    #[test]
+    fn test_serve_mock_cli_args() {
+        let args = Args::parse_from(vec![
+            "gr",
+            "serve-mock",
+            "--contracts",
+            "dir/",
+            "--port",
+            "9090",
+        ]);
+        let options: ServeMockOptions = match args.command {
+            Command::ServeMock(options) => options.into(),
+            _ => panic!("Expected ServeMockCommand"),
+        };
+        assert_eq!("dir/", options.contracts);
+        assert_eq!(9090, options.port);
+    }
+
+    #[test]
+    fn test_serve_mock_cli_defaults() {
+        let args = Args::parse_from(vec!["gr", "serve-mock"]);
+        let options: ServeMockOptions = match args.command {
+            Command::ServeMock(options) => options.into(),
+            _ => panic!("Expected ServeMockCommand"),
+        };
+        assert_eq!("contracts", options.contracts);
+        assert_eq!(8080, options.port);
+    }
+}
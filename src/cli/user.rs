@@ -12,7 +12,7 @@ pub struct UserCommand {
 
 #[derive(Parser)]
 enum UserSubCommand {
-    #[clap(about = "Gets user information")]
+    #[clap(about = "Gets the profile of a user, its organizations and recent activity")]
     Get(GetUser),
 }
 
@@ -21,6 +21,10 @@ struct GetUser {
     /// Retrieve user information by username
     #[clap()]
     username: String,
+    /// Number of recent contribution events to show along with the profile.
+    /// Github and Gitlab.
+    #[clap(long, default_value = "5", value_name = "NUMBER")]
+    events: usize,
     #[clap(flatten)]
     get_args: GetArgs,
 }
@@ -38,6 +42,7 @@ impl From<GetUser> for UserOptions {
         UserOptions::Get(
             UserCliArgs::builder()
                 .username(options.username)
+                .events(options.events)
                 .get_args(options.get_args.into())
                 .build()
                 .unwrap(),
@@ -57,7 +62,7 @@ mod tests {
 
     #[test]
     fn test_user_command() {
-        let args = Args::parse_from(&["gr", "us", "get", "octocat"]);
+        let args = Args::parse_from(["gr", "us", "get", "octocat"]);
         let user_command = match args.command {
             Command::User(cmd) => cmd,
             _ => panic!("Expected user command"),
@@ -66,7 +71,20 @@ mod tests {
         match options {
             UserOptions::Get(args) => {
                 assert_eq!(args.username, "octocat");
+                assert_eq!(args.events, 5);
             }
         }
     }
+
+    #[test]
+    fn test_user_command_no_events() {
+        let args = Args::parse_from(["gr", "us", "get", "octocat", "--events", "0"]);
+        let user_command = match args.command {
+            Command::User(cmd) => cmd,
+            _ => panic!("Expected user command"),
+        };
+        match user_command.into() {
+            UserOptions::Get(args) => assert_eq!(args.events, 0),
+        }
+    }
 }
@@ -1,13 +1,23 @@
 pub mod amps;
+pub mod auth;
 pub mod browse;
 pub mod cache;
 pub mod cicd;
 pub mod common;
+pub mod contract;
+pub mod deployment;
 pub mod docker;
+pub mod flow;
 pub mod gist;
+pub mod issue;
+pub mod label;
 pub mod merge_request;
 pub mod my;
 pub mod project;
 pub mod release;
+pub mod remote;
+pub mod report;
+pub mod search;
+pub mod serve_mock;
 pub mod trending;
 pub mod user;
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::sync::Arc;
+
+use crate::api_traits::RemoteCredentials;
+use crate::auth::CredentialHelperAction;
+use crate::{log_debug, Result};
+
+/// Serves credentials to git following the git credential helper protocol.
+/// The request is read from `reader` as `key=value` lines and credentials
+/// for its host are resolved with `get_credentials`. Hosts gitar has no
+/// credentials for are left to other helpers configured in git.
+/// https://git-scm.com/docs/git-credential
+pub fn git_credential<R: BufRead, W: Write>(
+    action: CredentialHelperAction,
+    reader: R,
+    mut writer: W,
+    get_credentials: impl FnOnce(&str) -> Result<Arc<dyn RemoteCredentials + Send + Sync>>,
+) -> Result<()> {
+    let mut attributes = HashMap::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            attributes.insert(key.to_string(), value.to_string());
+        }
+    }
+    if action != CredentialHelperAction::Get {
+        // Tokens are managed in the gitar configuration.
+        log_debug!("Ignoring git credential helper action {:?}", action);
+        return Ok(());
+    }
+    if attributes.get("protocol").map(String::as_str) != Some("https") {
+        return Ok(());
+    }
+    let Some(host) = attributes.get("host") else {
+        return Ok(());
+    };
+    let domain = host.split(':').next().unwrap_or_default();
+    let credentials = match get_credentials(domain).and_then(|remote| remote.credentials()) {
+        Ok(credentials) => credentials,
+        Err(err) => {
+            log_debug!("No credentials for {}: {}", host, err);
+            return Ok(());
+        }
+    };
+    writeln!(writer, "protocol=https")?;
+    writeln!(writer, "host={}", host)?;
+    writeln!(writer, "username={}", credentials.username)?;
+    writeln!(writer, "password={}", credentials.secret)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::auth::Credentials;
+    use crate::error;
+
+    use super::*;
+
+    struct MockCredentials;
+
+    impl RemoteCredentials for MockCredentials {
+        fn credentials(&self) -> Result<Credentials> {
+            Ok(Credentials {
+                username: "x-access-token".to_string(),
+                secret: "1234".to_string(),
+            })
+        }
+    }
+
+    fn git_credential_output(
+        action: CredentialHelperAction,
+        input: &str,
+        get_credentials: impl FnOnce(&str) -> Result<Arc<dyn RemoteCredentials + Send + Sync>>,
+    ) -> String {
+        let mut buf = Vec::new();
+        git_credential(action, input.as_bytes(), &mut buf, get_credentials).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_git_credential_get() {
+        let mut requested_domain = String::new();
+        let output = git_credential_output(
+            CredentialHelperAction::Get,
+            "protocol=https\nhost=github.com\npath=jordilin/gitar.git\n\n",
+            |domain| {
+                requested_domain = domain.to_string();
+                Ok(Arc::new(MockCredentials))
+            },
+        );
+        assert_eq!("github.com", requested_domain);
+        assert_eq!(
+            "protocol=https\nhost=github.com\nusername=x-access-token\npassword=1234\n",
+            output
+        );
+    }
+
+    #[test]
+    fn test_git_credential_unconfigured_host_is_left_to_other_helpers() {
+        let output = git_credential_output(
+            CredentialHelperAction::Get,
+            "protocol=https\nhost=bitbucket.org\n",
+            |domain| Err(error::gen(format!("Unsupported domain: {}", domain))),
+        );
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_git_credential_ignores_non_https_and_store() {
+        let output = git_credential_output(
+            CredentialHelperAction::Get,
+            "protocol=http\nhost=gitlab.com\n",
+            |_| Ok(Arc::new(MockCredentials)),
+        );
+        assert!(output.is_empty());
+        let output = git_credential_output(
+            CredentialHelperAction::Store,
+            "protocol=https\nhost=gitlab.com\nusername=jdoe\npassword=abc\n",
+            |_| Ok(Arc::new(MockCredentials)),
+        );
+        assert!(output.is_empty());
+    }
+}
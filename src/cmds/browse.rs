@@ -42,5 +42,7 @@ pub fn execute(
             Ok(open::that(remote.get_url(BrowseOptions::Releases))?)
         }
         BrowseOptions::Manual => Ok(open::that(crate::USER_GUIDE_URL)?),
+        // Opened by `gr mr create --web` once the branch is pushed.
+        BrowseOptions::NewMergeRequest { .. } => unreachable!(),
     }
 }
@@ -1,22 +1,120 @@
+use crate::api_traits::UserInfo;
+use crate::cache::filesystem::{CacheEntry, FileCache};
 use crate::cli::cache::CacheOptions;
 use crate::config::ConfigProperties;
 use crate::error::GRError;
+use crate::remote::{self, CacheCliArgs, CacheType};
+use crate::time::Seconds;
 use crate::Result;
 use std::fmt;
+use std::io::Write;
 use std::sync::Arc;
 
-pub fn execute(options: CacheOptions, config: Arc<dyn ConfigProperties>) -> Result<()> {
+#[derive(Builder, Clone)]
+pub struct PruneOptions {
+    #[builder(default)]
+    pub older_than: Option<Seconds>,
+    #[builder(default)]
+    pub max_size: Option<u64>,
+    #[builder(default)]
+    pub dry_run: bool,
+}
+
+impl PruneOptions {
+    pub fn builder() -> PruneOptionsBuilder {
+        PruneOptionsBuilder::default()
+    }
+}
+
+pub fn execute(
+    options: CacheOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
     match options {
         CacheOptions::Info => {
             let size = get_cache_directory_size(&config)?;
             println!("Location: {}", config.cache_location().unwrap_or("Not set"));
             println!("Size: {}", BytesToHumanReadable::from(size));
         }
+        CacheOptions::Prune(options) => {
+            let file_cache = FileCache::new(config);
+            prune(&file_cache, &options, std::io::stdout())?;
+        }
+        CacheOptions::RefreshUser => {
+            let cache_args = CacheCliArgs::builder().refresh(true).build().unwrap();
+            let remote =
+                remote::get_auth_user(domain, path, config, Some(&cache_args), CacheType::File)?;
+            refresh_user(remote, std::io::stdout())?;
+        }
+    }
+    Ok(())
+}
+
+/// Fetches the authenticated user. Given a remote that refreshes the cache,
+/// the cached identity is replaced.
+fn refresh_user<W: Write>(remote: Arc<dyn UserInfo + Send + Sync>, mut writer: W) -> Result<()> {
+    let user = remote.get_auth_user()?;
+    writeln!(
+        writer,
+        "Cached user refreshed: {} ({})",
+        user.username, user.id
+    )?;
+    Ok(())
+}
+
+fn prune<W: Write>(file_cache: &FileCache, options: &PruneOptions, mut writer: W) -> Result<()> {
+    let entries = select_for_pruning(file_cache.entries()?, options);
+    let size = entries.iter().map(|entry| entry.size).sum::<u64>();
+    if options.dry_run {
+        writeln!(
+            writer,
+            "Would remove {} entries, {} reclaimed.",
+            entries.len(),
+            BytesToHumanReadable::from(size)
+        )?;
+        return Ok(());
     }
+    for entry in &entries {
+        file_cache.remove(entry)?;
+    }
+    writeln!(
+        writer,
+        "Removed {} entries, {} reclaimed.",
+        entries.len(),
+        BytesToHumanReadable::from(size)
+    )?;
     Ok(())
 }
 
-struct BytesToHumanReadable(u64);
+/// Entries older than the given age plus, if a maximum size is given, the
+/// oldest entries that do not fit in it once the most recent ones are kept.
+fn select_for_pruning(mut entries: Vec<CacheEntry>, options: &PruneOptions) -> Vec<CacheEntry> {
+    entries.sort_by_key(|entry| *entry.age);
+    let mut kept_size = 0;
+    let mut full = false;
+    entries
+        .into_iter()
+        .filter(|entry| {
+            if let Some(older_than) = options.older_than {
+                if entry.age >= older_than {
+                    return true;
+                }
+            }
+            if let Some(max_size) = options.max_size {
+                full = full || kept_size + entry.size > max_size;
+            }
+            if full {
+                return true;
+            }
+            kept_size += entry.size;
+            false
+        })
+        .collect()
+}
+
+pub struct BytesToHumanReadable(u64);
 
 impl From<u64> for BytesToHumanReadable {
     fn from(size: u64) -> Self {
@@ -45,7 +143,11 @@ fn get_cache_directory_size(config: &Arc<dyn ConfigProperties>) -> Result<u64> {
         for entry in std::fs::read_dir(path)? {
             let entry = entry?;
             let metadata = entry.metadata()?;
-            size += metadata.len();
+            // Cached responses are files, directories hold state such as the
+            // circuit breaker's.
+            if metadata.is_file() {
+                size += metadata.len();
+            }
         }
         return Ok(size);
     }
@@ -56,11 +158,46 @@ fn get_cache_directory_size(config: &Arc<dyn ConfigProperties>) -> Result<u64> {
 mod test {
     use super::*;
 
+    use crate::api_defaults::CIRCUIT_BREAKER_FAILURE_THRESHOLD;
+    use crate::cmds::project::Member;
+    use crate::cmds::user::{UserCliArgs, UserProfile};
     use crate::config::ConfigProperties;
+    use crate::http::circuit::CircuitBreaker;
     use std::fs::File;
-    use std::io::Write;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
     use tempfile::{tempdir, TempDir};
 
+    struct UserInfoMock;
+
+    impl UserInfo for UserInfoMock {
+        fn get_auth_user(&self) -> Result<Member> {
+            Ok(Member::builder()
+                .id(1234)
+                .username("jdoe".to_string())
+                .build()
+                .unwrap())
+        }
+
+        fn get(&self, _args: &UserCliArgs) -> Result<Member> {
+            todo!()
+        }
+
+        fn get_profile(&self, _args: &UserCliArgs) -> Result<UserProfile> {
+            todo!()
+        }
+    }
+
+    #[test]
+    fn test_refresh_user() {
+        let mut writer = Vec::new();
+        refresh_user(Arc::new(UserInfoMock), &mut writer).unwrap();
+        assert_eq!(
+            "Cached user refreshed: jdoe (1234)\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
     #[test]
     fn test_bytes_display() {
         let test_table = vec![
@@ -107,4 +244,128 @@ mod test {
         let size = get_cache_directory_size(&config).unwrap();
         assert_eq!(size, 10);
     }
+
+    fn open_circuit(config: &ConfigMock) {
+        let circuit = CircuitBreaker::new("gitlab.com", config);
+        for _ in 0..CIRCUIT_BREAKER_FAILURE_THRESHOLD {
+            circuit.record_failure();
+        }
+    }
+
+    #[test]
+    fn test_get_size_of_cached_data_excludes_circuit_state() {
+        let dir = tempdir().unwrap();
+        let mut file = File::create(dir.path().join("test_file")).unwrap();
+        file.write_all(&[0; 10]).unwrap();
+        let config = ConfigMock::new(&dir);
+        open_circuit(&config);
+        let config: Arc<dyn ConfigProperties> = Arc::new(config);
+        let size = get_cache_directory_size(&config).unwrap();
+        assert_eq!(size, 10);
+    }
+
+    #[test]
+    fn test_prune_keeps_circuit_state() {
+        let dir = tempdir().unwrap();
+        let config = ConfigMock::new(&dir);
+        open_circuit(&config);
+        let file_cache = FileCache::new(Arc::new(config));
+        let options = PruneOptions::builder().max_size(Some(0)).build().unwrap();
+        prune(&file_cache, &options, Vec::new()).unwrap();
+        let circuit = CircuitBreaker::new("gitlab.com", &ConfigMock::new(&dir));
+        assert!(circuit.check().is_err());
+    }
+
+    fn entry(name: &str, size: u64, age: u64) -> CacheEntry {
+        CacheEntry {
+            path: PathBuf::from(name),
+            size,
+            age: Seconds::new(age),
+        }
+    }
+
+    fn names(entries: Vec<CacheEntry>) -> Vec<String> {
+        entries
+            .iter()
+            .map(|entry| entry.path.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_select_for_pruning_older_than() {
+        let entries = vec![entry("a", 10, 100), entry("b", 10, 10), entry("c", 10, 50)];
+        let options = PruneOptions::builder()
+            .older_than(Some(Seconds::new(50)))
+            .build()
+            .unwrap();
+        assert_eq!(vec!["c", "a"], names(select_for_pruning(entries, &options)));
+    }
+
+    #[test]
+    fn test_select_for_pruning_max_size_removes_oldest_first() {
+        let entries = vec![
+            entry("a", 30, 300),
+            entry("b", 50, 10),
+            entry("c", 40, 100),
+            entry("d", 5, 400),
+        ];
+        let options = PruneOptions::builder().max_size(Some(100)).build().unwrap();
+        // b and c fit in 100 bytes. a does not, and neither does d even
+        // though it would fit, as it is older.
+        assert_eq!(vec!["a", "d"], names(select_for_pruning(entries, &options)));
+    }
+
+    #[test]
+    fn test_select_for_pruning_older_than_and_max_size() {
+        let entries = vec![entry("a", 10, 300), entry("b", 50, 10), entry("c", 60, 100)];
+        let options = PruneOptions::builder()
+            .older_than(Some(Seconds::new(200)))
+            .max_size(Some(100))
+            .build()
+            .unwrap();
+        assert_eq!(vec!["c", "a"], names(select_for_pruning(entries, &options)));
+    }
+
+    #[test]
+    fn test_prune_removes_old_entries_from_cache_location() {
+        let dir = tempdir().unwrap();
+        let old = File::create(dir.path().join("old")).unwrap();
+        old.set_modified(SystemTime::now() - Duration::from_secs(8 * 86400))
+            .unwrap();
+        let mut recent = File::create(dir.path().join("recent")).unwrap();
+        recent.write_all(&[0; 10]).unwrap();
+        let file_cache = FileCache::new(Arc::new(ConfigMock::new(&dir)));
+        let options = PruneOptions::builder()
+            .older_than(Some(Seconds::new(7 * 86400)))
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        prune(&file_cache, &options, &mut writer).unwrap();
+        assert_eq!(
+            "Removed 1 entries, 0.00 B reclaimed.\n",
+            String::from_utf8(writer).unwrap()
+        );
+        assert!(!dir.path().join("old").exists());
+        assert!(dir.path().join("recent").exists());
+    }
+
+    #[test]
+    fn test_prune_dry_run_keeps_entries() {
+        let dir = tempdir().unwrap();
+        let mut file = File::create(dir.path().join("entry")).unwrap();
+        file.write_all(&[0; 2048]).unwrap();
+        let file_cache = FileCache::new(Arc::new(ConfigMock::new(&dir)));
+        let options = PruneOptions::builder()
+            .max_size(Some(1024))
+            .dry_run(true)
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        prune(&file_cache, &options, &mut writer).unwrap();
+        assert_eq!(
+            "Would remove 1 entries, 2.00 KB reclaimed.\n",
+            String::from_utf8(writer).unwrap()
+        );
+        assert!(dir.path().join("entry").exists());
+    }
 }
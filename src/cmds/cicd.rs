@@ -1,19 +1,39 @@
-use mermaid::{generate_mermaid_stages_diagram, YamlParser};
+use mermaid::{
+    generate_json_model, generate_mermaid_stages_diagram, simulate_pipeline, ChartFormat,
+    YamlParser,
+};
+use rules::PipelineEvent;
 use yaml::load_yaml;
 
-use crate::api_traits::{Cicd, CicdJob, CicdRunner, Timestamp};
-use crate::cli::cicd::{JobOptions, PipelineOptions, RunnerOptions};
+use crate::api_traits::{
+    Cicd, CicdArtifact, CicdJob, CicdJobLog, CicdJobTiming, CicdRunner, CicdTestReport, CicdUsage,
+    Timestamp,
+};
+use crate::cli::cicd::{ArtifactOptions, JobOptions, PipelineOptions, RunnerOptions};
 use crate::config::ConfigProperties;
 use crate::display::{Column, DisplayBody};
+use crate::git;
+use crate::http::throttle::{DynamicFixed, ThrottleStrategy};
+use crate::io::CmdInfo;
 use crate::remote::{CacheType, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs};
-use crate::{display, error, remote, Result};
+use crate::shell::BlockingCommand;
+use crate::time::{self, Milliseconds, Seconds};
+use crate::{dialog, display, error, exec, remote, Cmd, Result};
+use chrono::{DateTime, Utc};
 use std::fmt::Display;
 use std::io::{Read, Write};
 use std::sync::Arc;
 
+pub mod flaky;
+pub mod lint;
 pub mod mermaid;
+pub mod rules;
+pub mod stats;
+pub mod status;
 pub mod yaml;
+pub mod zip;
 
+use super::cache::BytesToHumanReadable;
 use super::common::{
     self, num_cicd_pages, num_cicd_resources, num_job_pages, num_job_resources, num_runner_pages,
     num_runner_resources,
@@ -26,8 +46,8 @@ pub struct Pipeline {
     web_url: String,
     branch: String,
     sha: String,
-    created_at: String,
-    updated_at: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
     duration: u64,
 }
 
@@ -38,8 +58,12 @@ impl Pipeline {
 }
 
 impl Timestamp for Pipeline {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        Some(self.updated_at)
     }
 }
 
@@ -51,9 +75,9 @@ impl From<Pipeline> for DisplayBody {
                 Column::new("URL", p.web_url),
                 Column::new("Branch", p.branch),
                 Column::new("SHA", p.sha),
-                Column::new("Created at", p.created_at),
-                Column::new("Updated at", p.updated_at),
-                Column::new("Duration", p.duration.to_string()),
+                Column::new("Created at", time::format_timestamp(&p.created_at)),
+                Column::new("Updated at", time::format_timestamp(&p.updated_at)),
+                Column::duration("Duration", p.duration.to_string()),
                 Column::new("Status", p.status),
             ],
         }
@@ -88,6 +112,8 @@ pub struct LintResponse {
     #[builder(default)]
     pub merged_yaml: String,
     pub errors: Vec<String>,
+    #[builder(default)]
+    pub warnings: Vec<String>,
 }
 
 impl LintResponse {
@@ -99,7 +125,7 @@ impl LintResponse {
 pub struct YamlBytes<'a>(&'a [u8]);
 
 impl YamlBytes<'_> {
-    pub fn new(data: &[u8]) -> YamlBytes {
+    pub fn new(data: &[u8]) -> YamlBytes<'_> {
         YamlBytes(data)
     }
 }
@@ -151,9 +177,9 @@ impl From<Runner> for DisplayBody {
 }
 
 impl Timestamp for Runner {
-    fn created_at(&self) -> String {
+    fn created_at(&self) -> DateTime<Utc> {
         // There is no created_at field for runners, set it to UNIX epoch
-        "1970-01-01T00:00:00Z".to_string()
+        DateTime::UNIX_EPOCH
     }
 }
 
@@ -166,7 +192,7 @@ pub struct RunnerMetadata {
     pub version: String,
     pub architecture: String,
     pub platform: String,
-    pub contacted_at: String,
+    pub contacted_at: Option<DateTime<Utc>>,
     pub revision: String,
 }
 
@@ -185,7 +211,10 @@ impl From<RunnerMetadata> for DisplayBody {
                 Column::new("Tags", r.tag_list.join(", ")),
                 Column::new("Architecture", r.architecture),
                 Column::new("Platform", r.platform),
-                Column::new("Contacted at", r.contacted_at),
+                Column::new(
+                    "Contacted at",
+                    time::format_optional_timestamp(r.contacted_at.as_ref()),
+                ),
                 Column::new("Version", r.version),
                 Column::new("Revision", r.revision),
             ],
@@ -200,6 +229,8 @@ pub struct RunnerListCliArgs {
     pub tags: Option<String>,
     #[builder(default)]
     pub all: bool,
+    #[builder(default)]
+    pub group: Option<String>,
     pub list_args: ListRemoteCliArgs,
 }
 
@@ -217,6 +248,9 @@ pub struct RunnerListBodyArgs {
     pub tags: Option<String>,
     #[builder(default)]
     pub all: bool,
+    /// List the runners available to a group instead of the project.
+    #[builder(default)]
+    pub group: Option<String>,
 }
 
 impl RunnerListBodyArgs {
@@ -225,6 +259,195 @@ impl RunnerListBodyArgs {
     }
 }
 
+#[derive(Builder, Clone)]
+pub struct RunnerUsageCliArgs {
+    /// Gitlab group or Github organization
+    pub group: String,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl RunnerUsageCliArgs {
+    pub fn builder() -> RunnerUsageCliArgsBuilder {
+        RunnerUsageCliArgsBuilder::default()
+    }
+}
+
+/// CI/CD minutes consumed by a project in a month.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct RunnerUsage {
+    /// Month in YYYY-MM format
+    pub month: String,
+    pub project: String,
+    pub minutes: f64,
+}
+
+impl RunnerUsage {
+    pub fn builder() -> RunnerUsageBuilder {
+        RunnerUsageBuilder::default()
+    }
+}
+
+impl From<RunnerUsage> for DisplayBody {
+    fn from(usage: RunnerUsage) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Month", usage.month),
+                Column::new("Project", usage.project),
+                Column::new("Minutes", format!("{:.1}", usage.minutes)),
+            ],
+        }
+    }
+}
+
+/// Pipeline artifacts. In Gitlab artifacts belong to a job, so the ID is
+/// the job's ID and the size adds up all its artifact files but the job log.
+#[derive(Builder, Clone, Debug)]
+pub struct Artifact {
+    pub id: i64,
+    pub name: String,
+    pub size: u64,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl Artifact {
+    pub fn builder() -> ArtifactBuilder {
+        ArtifactBuilder::default()
+    }
+}
+
+impl From<Artifact> for DisplayBody {
+    fn from(artifact: Artifact) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("ID", artifact.id.to_string()),
+                Column::new("Name", artifact.name),
+                Column::new(
+                    "Size",
+                    BytesToHumanReadable::from(artifact.size).to_string(),
+                ),
+                Column::new(
+                    "Created at",
+                    time::format_optional_timestamp(artifact.created_at.as_ref()),
+                ),
+            ],
+        }
+    }
+}
+
+impl Timestamp for Artifact {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at.unwrap_or_default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct ArtifactListFilesCliArgs {
+    pub id: i64,
+    /// File inside the archive to write to STDOUT instead of listing.
+    #[builder(default)]
+    pub path: Option<String>,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl ArtifactListFilesCliArgs {
+    pub fn builder() -> ArtifactListFilesCliArgsBuilder {
+        ArtifactListFilesCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct ArtifactPruneCliArgs {
+    /// Artifacts created before now minus this amount of time are deleted.
+    pub older_than: Seconds,
+    #[builder(default)]
+    pub dry_run: bool,
+    #[builder(default)]
+    pub yes: bool,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl ArtifactPruneCliArgs {
+    pub fn builder() -> ArtifactPruneCliArgsBuilder {
+        ArtifactPruneCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct FailedJob {
+    pub pipeline_id: i64,
+    pub id: i64,
+    pub name: String,
+    pub web_url: String,
+}
+
+impl FailedJob {
+    pub fn builder() -> FailedJobBuilder {
+        FailedJobBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct JobLogGrepCliArgs {
+    /// Regular expression to search for in the job logs
+    pub pattern: String,
+    /// Number of most recent failed pipelines to search
+    pub last: u32,
+    /// Maximum number of logs downloaded in parallel
+    #[builder(default = "4")]
+    pub parallel: usize,
+    /// Wait time between batches of parallel downloads
+    #[builder(default)]
+    pub throttle: Option<Milliseconds>,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl JobLogGrepCliArgs {
+    pub fn builder() -> JobLogGrepCliArgsBuilder {
+        JobLogGrepCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct FlakyTestsCliArgs {
+    /// Number of most recent finished pipelines to analyze
+    pub last: u32,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl FlakyTestsCliArgs {
+    pub fn builder() -> FlakyTestsCliArgsBuilder {
+        FlakyTestsCliArgsBuilder::default()
+    }
+}
+
+/// A failed job whose log matched the searched pattern.
+#[derive(Clone, Debug)]
+pub struct JobLogMatch {
+    pub job: FailedJob,
+    pub matches: usize,
+    pub first_match: String,
+}
+
+impl From<JobLogMatch> for DisplayBody {
+    fn from(m: JobLogMatch) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Pipeline", m.job.pipeline_id.to_string()),
+                Column::new("Job ID", m.job.id.to_string()),
+                Column::new("Job", m.job.name),
+                Column::new("Matches", m.matches.to_string()),
+                Column::new("First match", m.first_match),
+                Column::builder()
+                    .name("URL".to_string())
+                    .value(m.job.web_url)
+                    .optional(true)
+                    .build()
+                    .unwrap(),
+            ],
+        }
+    }
+}
+
 #[derive(Builder, Clone)]
 pub struct RunnerMetadataGetCliArgs {
     pub id: i64,
@@ -339,9 +562,9 @@ pub struct Job {
     runner_tags: Vec<String>,
     stage: String,
     status: String,
-    created_at: String,
-    started_at: String,
-    finished_at: String,
+    created_at: Option<DateTime<Utc>>,
+    started_at: Option<DateTime<Utc>>,
+    finished_at: Option<DateTime<Utc>>,
     duration: String,
 }
 
@@ -349,6 +572,10 @@ impl Job {
     pub fn builder() -> JobBuilder {
         JobBuilder::default()
     }
+
+    fn is_active(&self) -> bool {
+        status::is_active(&self.status)
+    }
 }
 
 impl From<Job> for DisplayBody {
@@ -365,18 +592,27 @@ impl From<Job> for DisplayBody {
                 Column::new("Runner Tags", j.runner_tags.join(", ")),
                 Column::new("Stage", j.stage),
                 Column::new("Status", j.status),
-                Column::new("Created At", j.created_at),
-                Column::new("Started At", j.started_at),
-                Column::new("Finished At", j.finished_at),
-                Column::new("Duration", j.duration.to_string()),
+                Column::new(
+                    "Created At",
+                    time::format_optional_timestamp(j.created_at.as_ref()),
+                ),
+                Column::new(
+                    "Started At",
+                    time::format_optional_timestamp(j.started_at.as_ref()),
+                ),
+                Column::new(
+                    "Finished At",
+                    time::format_optional_timestamp(j.finished_at.as_ref()),
+                ),
+                Column::duration("Duration", j.duration),
             ],
         }
     }
 }
 
 impl Timestamp for Job {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at.unwrap_or_default()
     }
 }
 
@@ -393,6 +629,56 @@ impl JobListCliArgs {
     }
 }
 
+#[derive(Builder, Clone)]
+pub struct JobLogCliArgs {
+    pub id: i64,
+    /// Keep printing the log as it grows until the job finishes.
+    #[builder(default)]
+    pub follow: bool,
+    /// Wait time between polls when following the log.
+    #[builder(default = "Seconds::new(5)")]
+    pub interval: Seconds,
+}
+
+impl JobLogCliArgs {
+    pub fn builder() -> JobLogCliArgsBuilder {
+        JobLogCliArgsBuilder::default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JobAction {
+    Retry,
+    Cancel,
+    Play,
+}
+
+#[derive(Builder, Clone)]
+pub struct JobActionCliArgs {
+    pub action: JobAction,
+    pub id: i64,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl JobActionCliArgs {
+    pub fn builder() -> JobActionCliArgsBuilder {
+        JobActionCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct JobStatsCliArgs {
+    /// Number of most recent finished pipelines to analyze
+    pub last: u32,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl JobStatsCliArgs {
+    pub fn builder() -> JobStatsCliArgsBuilder {
+        JobStatsCliArgsBuilder::default()
+    }
+}
+
 #[derive(Builder, Clone)]
 pub struct JobListBodyArgs {
     pub list_args: Option<ListBodyArgs>,
@@ -426,13 +712,30 @@ pub fn execute(
             lint_ci_file(remote, &body, true, std::io::stdout())
         }
         PipelineOptions::Chart(args) => {
-            let file = std::fs::File::open(".gitlab-ci.yml")?;
-            let body = read_ci_file(file)?;
-            let parser = YamlParser::new(load_yaml(&String::from_utf8_lossy(&body)));
-            let chart = generate_mermaid_stages_diagram(parser, args)?;
-            println!("{}", chart);
+            let parser = ci_file_parser()?;
+            let event = args.git_ref.as_deref().map(PipelineEvent::push);
+            match args.format {
+                ChartFormat::Mermaid => {
+                    let chart =
+                        generate_mermaid_stages_diagram(parser, args.chart_type, event.as_ref())?;
+                    println!("{}", chart);
+                }
+                ChartFormat::Json => println!("{}", generate_json_model(parser, event.as_ref())?),
+            }
             Ok(())
         }
+        PipelineOptions::Simulate(event) => {
+            let jobs = simulate_pipeline(ci_file_parser()?, &event)?;
+            if jobs.is_empty() {
+                eprintln!("No jobs run in a pipeline for {}", event);
+                return Ok(());
+            }
+            display::print(
+                &mut std::io::stdout(),
+                jobs,
+                GetRemoteCliArgs::builder().build()?,
+            )
+        }
         PipelineOptions::List(cli_args) => {
             let remote = remote::get_cicd(
                 domain,
@@ -452,6 +755,19 @@ pub fn execute(
                 .build()?;
             list_pipelines(remote, body_args, cli_args, std::io::stdout())
         }
+        PipelineOptions::Status(cli_args) => {
+            // Statuses change while the pipeline runs, they are never cached.
+            let remote =
+                remote::get_cicd_pipeline_status(domain, path, config, None, CacheType::None)?;
+            let git_ref = match &cli_args.sha {
+                Some(sha) => status::PipelineRef::Sha(sha.clone()),
+                None => match git::current_branch(Arc::new(BlockingCommand))? {
+                    CmdInfo::Branch(branch) => status::PipelineRef::Branch(branch),
+                    _ => unreachable!(),
+                },
+            };
+            status::pipeline_status(remote, git_ref, cli_args, &DynamicFixed, std::io::stdout())
+        }
         PipelineOptions::Jobs(options) => match options {
             JobOptions::List(cli_args) => {
                 let remote = remote::get_cicd_job(
@@ -471,6 +787,73 @@ pub fn execute(
                 }
                 list_jobs(remote, body_args, cli_args, std::io::stdout())
             }
+            JobOptions::Log(cli_args) => {
+                // Logs grow while the job runs, they are never cached.
+                let log_remote = remote::get_cicd_job_log(
+                    domain.clone(),
+                    path.clone(),
+                    config.clone(),
+                    None,
+                    CacheType::None,
+                )?;
+                let job_remote = remote::get_cicd_job(domain, path, config, None, CacheType::None)?;
+                job_log(
+                    log_remote,
+                    job_remote,
+                    cli_args,
+                    &DynamicFixed,
+                    std::io::stdout(),
+                )
+            }
+            JobOptions::Action(cli_args) => {
+                let remote = remote::get_cicd_job(domain, path, config, None, CacheType::None)?;
+                job_action(remote, cli_args, std::io::stdout())
+            }
+            JobOptions::Stats(cli_args) => {
+                let remote = remote::get_cicd_job_timing(
+                    domain,
+                    path,
+                    config,
+                    Some(&cli_args.get_args.cache_args),
+                    CacheType::File,
+                )?;
+                job_stats(remote, cli_args, std::io::stdout())
+            }
+        },
+        PipelineOptions::Grep(cli_args) => {
+            let remote = remote::get_cicd_job_log(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            grep_job_logs(remote, cli_args, std::io::stdout())
+        }
+        PipelineOptions::Flaky(cli_args) => {
+            let remote = remote::get_cicd_test_report(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            flaky_tests(remote, cli_args, std::io::stdout())
+        }
+        PipelineOptions::Artifacts(options) => match options {
+            ArtifactOptions::Prune(cli_args) => {
+                // Listing needs to be fresh, artifacts are about to be
+                // deleted.
+                let remote =
+                    remote::get_cicd_artifact(domain, path, config, None, CacheType::None)?;
+                prune_artifacts(remote, cli_args, time::now_epoch_seconds, std::io::stdout())
+            }
+            ArtifactOptions::ListFiles(cli_args) => {
+                // Range requests over the same URL cannot be cached.
+                let remote =
+                    remote::get_cicd_artifact(domain, path, config, None, CacheType::None)?;
+                list_artifact_files(remote, cli_args, std::io::stdout())
+            }
         },
         PipelineOptions::Runners(options) => match options {
             RunnerOptions::List(cli_args) => {
@@ -488,6 +871,7 @@ pub fn execute(
                     .status(cli_args.status)
                     .tags(tags)
                     .all(cli_args.all)
+                    .group(cli_args.group.clone())
                     .build()?;
                 if cli_args.list_args.num_pages {
                     return num_runner_pages(remote, body_args, std::io::stdout());
@@ -511,6 +895,16 @@ pub fn execute(
                 let remote = remote::get_cicd_runner(domain, path, config, None, CacheType::None)?;
                 create_runner(remote, cli_args, std::io::stdout())
             }
+            RunnerOptions::Usage(cli_args) => {
+                let remote = remote::get_cicd_usage(
+                    domain,
+                    path,
+                    config,
+                    Some(&cli_args.get_args.cache_args),
+                    CacheType::File,
+                )?;
+                runner_usage(remote, cli_args, std::io::stdout())
+            }
         },
     }
 }
@@ -521,10 +915,204 @@ fn get_runner_details<W: Write>(
     mut writer: W,
 ) -> Result<()> {
     let runner = remote.get(cli_args.id)?;
-    display::print(&mut writer, vec![runner], cli_args.get_args)?;
+    display::print_one(&mut writer, runner, cli_args.get_args)?;
+    Ok(())
+}
+
+fn grep_job_logs<W: Write>(
+    remote: Arc<dyn CicdJobLog + Send + Sync>,
+    cli_args: JobLogGrepCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let pattern = regex::Regex::new(&cli_args.pattern).map_err(|e| {
+        error::GRError::PreconditionNotMet(format!("Invalid pattern {}: {}", cli_args.pattern, e))
+    })?;
+    let jobs = remote.list_failed_jobs(cli_args.last)?;
+    let num_jobs = jobs.len();
+    let mut matches = Vec::new();
+    let batches = jobs.chunks(cli_args.parallel.max(1)).collect::<Vec<_>>();
+    for (i, batch) in batches.iter().enumerate() {
+        if i > 0 {
+            if let Some(throttle) = cli_args.throttle {
+                std::thread::sleep(throttle.into());
+            }
+        }
+        let cmds = batch
+            .iter()
+            .map(|job| {
+                let remote = remote.clone();
+                let job = job.clone();
+                let cmd = move || -> Result<(FailedJob, String)> {
+                    let log = remote.get_log(job.id)?;
+                    Ok((job, log))
+                };
+                Box::new(cmd) as Cmd<(FailedJob, String)>
+            })
+            .collect::<Vec<_>>();
+        for result in exec::parallel_stream(cmds) {
+            let (job, log) = result?;
+            if let Some(job_match) = match_log(&pattern, job, &log) {
+                matches.push(job_match);
+            }
+        }
+    }
+    if matches.is_empty() {
+        writeln!(writer, "No matches found in {} failed jobs.", num_jobs)?;
+        return Ok(());
+    }
+    // Most recent pipelines first.
+    matches.sort_by(|a, b| {
+        b.job
+            .pipeline_id
+            .cmp(&a.job.pipeline_id)
+            .then(a.job.id.cmp(&b.job.id))
+    });
+    display::print(&mut writer, matches, cli_args.get_args)
+}
+
+fn flaky_tests<W: Write>(
+    remote: Arc<dyn CicdTestReport>,
+    cli_args: FlakyTestsCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let results = remote.list_test_results(cli_args.last)?;
+    let num_results = results.len();
+    let flaky = flaky::detect(results);
+    if flaky.is_empty() {
+        writeln!(
+            writer,
+            "No flaky tests found in {} test results.",
+            num_results
+        )?;
+        return Ok(());
+    }
+    display::print(&mut writer, flaky, cli_args.get_args)
+}
+
+fn job_stats<W: Write>(
+    remote: Arc<dyn CicdJobTiming>,
+    cli_args: JobStatsCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let timings = remote.list_job_timings(cli_args.last)?;
+    if timings.is_empty() {
+        writeln!(
+            writer,
+            "No finished jobs found in the last {} pipelines.",
+            cli_args.last
+        )?;
+        return Ok(());
+    }
+    display::print(&mut writer, stats::summarize(timings), cli_args.get_args)
+}
+
+fn match_log(pattern: &regex::Regex, job: FailedJob, log: &str) -> Option<JobLogMatch> {
+    // Job logs are colored. Strip ANSI escape sequences, so they do not
+    // interfere with the pattern and the reported line.
+    let ansi = regex::Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap();
+    let log = ansi.replace_all(log, "");
+    let mut matched_lines = log.lines().filter(|line| pattern.is_match(line));
+    let first_match = matched_lines.next()?.trim().to_string();
+    Some(JobLogMatch {
+        job,
+        matches: 1 + matched_lines.count(),
+        first_match,
+    })
+}
+
+fn list_artifact_files<W: Write>(
+    remote: Arc<dyn CicdArtifact>,
+    cli_args: ArtifactListFilesCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let read = |range| remote.read_archive(cli_args.id, range);
+    let entries = zip::list_entries(read)?;
+    match &cli_args.path {
+        Some(path) => {
+            let entry = entries
+                .iter()
+                .find(|entry| &entry.path == path)
+                .ok_or_else(|| {
+                    error::GRError::PreconditionNotMet(format!(
+                        "File {} not found in artifact {}",
+                        path, cli_args.id
+                    ))
+                })?;
+            writer.write_all(&zip::extract(read, entry)?)?;
+            Ok(())
+        }
+        None => display::print(&mut writer, entries, cli_args.get_args),
+    }
+}
+
+fn prune_artifacts<W: Write>(
+    remote: Arc<dyn CicdArtifact>,
+    cli_args: ArtifactPruneCliArgs,
+    now: fn() -> Seconds,
+    mut writer: W,
+) -> Result<()> {
+    let now = now();
+    let artifacts = remote
+        .list()?
+        .into_iter()
+        .filter(|artifact| {
+            // Artifacts with no creation date have an unknown age.
+            let Some(created_at) = artifact.created_at else {
+                return false;
+            };
+            let created_at = Seconds::new(created_at.timestamp().max(0) as u64);
+            created_at < now && now - created_at > cli_args.older_than
+        })
+        .collect::<Vec<Artifact>>();
+    if artifacts.is_empty() {
+        writeln!(writer, "No artifacts to prune.")?;
+        return Ok(());
+    }
+    let total_size = artifacts.iter().map(|artifact| artifact.size).sum::<u64>();
+    let summary = format!(
+        "{} artifacts, {}",
+        artifacts.len(),
+        BytesToHumanReadable::from(total_size)
+    );
+    if cli_args.dry_run {
+        display::print(&mut writer, artifacts, cli_args.get_args)?;
+        writeln!(writer, "Space to be reclaimed: {}", summary)?;
+        return Ok(());
+    }
+    dialog::confirm_action(&format!("Delete {}?", summary), cli_args.yes)?;
+    for artifact in artifacts {
+        remote.delete(artifact.id)?;
+        writeln!(
+            writer,
+            "Deleted artifact ID: [{}], Name: [{}]",
+            artifact.id, artifact.name
+        )?;
+    }
+    writeln!(writer, "Space reclaimed: {}", summary)?;
     Ok(())
 }
 
+fn runner_usage<W: Write>(
+    remote: Arc<dyn CicdUsage>,
+    cli_args: RunnerUsageCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let mut usage = remote.usage(&cli_args.group)?;
+    if usage.is_empty() {
+        writeln!(writer, "No CI/CD minutes usage found.")?;
+        return Ok(());
+    }
+    // Most recent month first and projects consuming more minutes on top, so
+    // the report reads as a cost ranking.
+    usage.sort_by(|a, b| {
+        b.month
+            .cmp(&a.month)
+            .then(b.minutes.total_cmp(&a.minutes))
+            .then(a.project.cmp(&b.project))
+    });
+    display::print(&mut writer, usage, cli_args.get_args)
+}
+
 fn list_runners<W: Write>(
     remote: Arc<dyn CicdRunner>,
     body_args: RunnerListBodyArgs,
@@ -543,6 +1131,48 @@ fn list_jobs<W: Write>(
     common::list_jobs(remote, body_args, cli_args, &mut writer)
 }
 
+/// Prints the log of a job. When following, the log is polled and only the
+/// part added since the last poll is downloaded and printed, until the job
+/// finishes.
+fn job_log<W: Write>(
+    log_remote: Arc<dyn CicdJobLog>,
+    job_remote: Arc<dyn CicdJob>,
+    cli_args: JobLogCliArgs,
+    throttler: &dyn ThrottleStrategy,
+    mut writer: W,
+) -> Result<()> {
+    let mut printed = 0;
+    loop {
+        // The status is read before the log, so the log of a finished job
+        // is complete.
+        let active = cli_args.follow && job_remote.get(cli_args.id)?.is_active();
+        // Bytes are written as they come, a character can be split between
+        // two polls.
+        let log = log_remote.get_log_from(cli_args.id, printed)?;
+        writer.write_all(&log)?;
+        writer.flush()?;
+        printed += log.len() as u64;
+        if !active {
+            return Ok(());
+        }
+        throttler.throttle_for(cli_args.interval.into());
+    }
+}
+
+fn job_action<W: Write>(
+    remote: Arc<dyn CicdJob>,
+    cli_args: JobActionCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let job = match cli_args.action {
+        JobAction::Retry => remote.retry(cli_args.id)?,
+        JobAction::Cancel => remote.cancel(cli_args.id)?,
+        JobAction::Play => remote.play(cli_args.id)?,
+    };
+    display::print_one(&mut writer, job, cli_args.get_args)?;
+    Ok(())
+}
+
 fn list_pipelines<W: Write>(
     remote: Arc<dyn Cicd>,
     body_args: PipelineBodyArgs,
@@ -558,6 +1188,23 @@ fn read_ci_file<R: Read>(mut reader: R) -> Result<Vec<u8>> {
     Ok(buf)
 }
 
+/// Parser of the .gitlab-ci.yml in the current directory, with its local
+/// includes and extends resolved.
+fn ci_file_parser() -> Result<YamlParser<yaml_rust2::Yaml>> {
+    let file = std::fs::File::open(".gitlab-ci.yml")?;
+    let body = read_ci_file(file)?;
+    let yaml = yaml::resolve(load_yaml(&String::from_utf8_lossy(&body)), &|path| {
+        std::fs::read_to_string(path).map_err(|err| {
+            error::GRError::MermaidParsingError(format!(
+                "Cannot read included file {}: {}",
+                path, err
+            ))
+            .into()
+        })
+    })?;
+    Ok(YamlParser::new(yaml))
+}
+
 fn lint_ci_file<W: Write>(
     remote: Arc<dyn Cicd>,
     body: &[u8],
@@ -577,20 +1224,40 @@ fn lint_ci_file<W: Write>(
             return Ok(());
         }
         writeln!(writer, "File is valid.")?;
+        write_lint_messages(&mut writer, &response.warnings, "Warning: ", body)?;
     } else {
-        for error in response.errors {
-            writeln!(writer, "{}", error)?;
-        }
+        write_lint_messages(&mut writer, &response.errors, "", body)?;
+        write_lint_messages(&mut writer, &response.warnings, "Warning: ", body)?;
         return Err(error::gen("Linting failed."));
     }
     Ok(())
 }
 
+fn write_lint_messages<W: Write>(
+    writer: &mut W,
+    messages: &[String],
+    prefix: &str,
+    body: &[u8],
+) -> Result<()> {
+    let body = String::from_utf8_lossy(body);
+    for message in messages {
+        writeln!(writer, "{}{}", prefix, message)?;
+        if let Some((number, line)) = lint::line_context(message, &body) {
+            writeln!(writer, "  {} | {}", number, line)?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
     use std::io::Cursor;
+    use std::rc::Rc;
 
     use super::*;
+    use crate::test::utils::MockThrottler;
     use crate::{api_traits::NumberDeltaErr, error};
 
     #[derive(Clone, Builder)]
@@ -603,6 +1270,10 @@ mod test {
         num_pages: Option<u32>,
         #[builder(default)]
         gitlab_ci_merged_yaml: String,
+        #[builder(default)]
+        lint_errors: Vec<String>,
+        #[builder(default)]
+        lint_warnings: Vec<String>,
     }
 
     impl PipelineMock {
@@ -629,7 +1300,7 @@ mod test {
             if self.error {
                 return Err(error::gen("Error"));
             }
-            return Ok(self.num_pages);
+            Ok(self.num_pages)
         }
 
         fn num_resources(&self) -> Result<Option<crate::api_traits::NumberDeltaErr>> {
@@ -645,8 +1316,9 @@ mod test {
                     .unwrap());
             }
             Ok(LintResponse::builder()
-                .valid(true)
-                .errors(vec![])
+                .valid(self.lint_errors.is_empty())
+                .errors(self.lint_errors.clone())
+                .warnings(self.lint_warnings.clone())
                 .merged_yaml(self.gitlab_ci_merged_yaml.clone())
                 .build()
                 .unwrap())
@@ -663,8 +1335,8 @@ mod test {
                     .web_url("https://gitlab.com/owner/repo/-/pipelines/123".to_string())
                     .branch("master".to_string())
                     .sha("1234567890abcdef".to_string())
-                    .created_at("2020-01-01T00:00:00Z".to_string())
-                    .updated_at("2020-01-01T00:01:00Z".to_string())
+                    .created_at("2020-01-01T00:00:00Z".parse().unwrap())
+                    .updated_at("2020-01-01T00:01:00Z".parse().unwrap())
                     .duration(60)
                     .build()
                     .unwrap(),
@@ -674,8 +1346,8 @@ mod test {
                     .web_url("https://gitlab.com/owner/repo/-/pipelines/456".to_string())
                     .branch("master".to_string())
                     .sha("1234567890abcdef".to_string())
-                    .created_at("2020-01-01T00:00:00Z".to_string())
-                    .updated_at("2020-01-01T00:01:01Z".to_string())
+                    .created_at("2020-01-01T00:00:00Z".parse().unwrap())
+                    .updated_at("2020-01-01T00:01:01Z".parse().unwrap())
                     .duration(61)
                     .build()
                     .unwrap(),
@@ -692,8 +1364,8 @@ mod test {
         assert_eq!(
             String::from_utf8(buf).unwrap(),
             "ID|URL|Branch|SHA|Created at|Updated at|Duration|Status\n\
-             123|https://gitlab.com/owner/repo/-/pipelines/123|master|1234567890abcdef|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|60|success\n\
-             456|https://gitlab.com/owner/repo/-/pipelines/456|master|1234567890abcdef|2020-01-01T00:00:00Z|2020-01-01T00:01:01Z|61|failed\n")
+             123|https://gitlab.com/owner/repo/-/pipelines/123|master|1234567890abcdef|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|1m|success\n\
+             456|https://gitlab.com/owner/repo/-/pipelines/456|master|1234567890abcdef|2020-01-01T00:00:00Z|2020-01-01T00:01:01Z|1m 1s|failed\n")
     }
 
     #[test]
@@ -737,7 +1409,7 @@ mod test {
 
     #[test]
     fn test_list_number_of_pipelines_pages() {
-        let pp_remote = PipelineMock::builder().num_pages(3 as u32).build().unwrap();
+        let pp_remote = PipelineMock::builder().num_pages(3_u32).build().unwrap();
         let mut buf = Vec::new();
         num_cicd_pages(Arc::new(pp_remote), &mut buf).unwrap();
         assert_eq!("3\n", String::from_utf8(buf).unwrap(),)
@@ -771,8 +1443,8 @@ mod test {
                     .web_url("https://gitlab.com/owner/repo/-/pipelines/123".to_string())
                     .branch("master".to_string())
                     .sha("1234567890abcdef".to_string())
-                    .created_at("2020-01-01T00:00:00Z".to_string())
-                    .updated_at("2020-01-01T00:01:00Z".to_string())
+                    .created_at("2020-01-01T00:00:00Z".parse().unwrap())
+                    .updated_at("2020-01-01T00:01:00Z".parse().unwrap())
                     .duration(60)
                     .build()
                     .unwrap(),
@@ -782,8 +1454,8 @@ mod test {
                     .web_url("https://gitlab.com/owner/repo/-/pipelines/456".to_string())
                     .branch("master".to_string())
                     .sha("1234567890abcdef".to_string())
-                    .created_at("2020-01-01T00:00:00Z".to_string())
-                    .updated_at("2020-01-01T00:01:00Z".to_string())
+                    .created_at("2020-01-01T00:00:00Z".parse().unwrap())
+                    .updated_at("2020-01-01T00:01:00Z".parse().unwrap())
                     .duration(60)
                     .build()
                     .unwrap(),
@@ -806,8 +1478,8 @@ mod test {
             .unwrap();
         list_pipelines(Arc::new(pp_remote), body_args, cli_args, &mut buf).unwrap();
         assert_eq!(
-            "123|https://gitlab.com/owner/repo/-/pipelines/123|master|1234567890abcdef|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|60|success\n\
-             456|https://gitlab.com/owner/repo/-/pipelines/456|master|1234567890abcdef|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|60|failed\n",
+            "123|https://gitlab.com/owner/repo/-/pipelines/123|master|1234567890abcdef|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|1m|success\n\
+             456|https://gitlab.com/owner/repo/-/pipelines/456|master|1234567890abcdef|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|1m|failed\n",
             String::from_utf8(buf).unwrap(),
         )
     }
@@ -962,7 +1634,7 @@ mod test {
             .version("13.0.0".to_string())
             .architecture("amd64".to_string())
             .platform("linux".to_string())
-            .contacted_at("2020-01-01T00:00:00Z".to_string())
+            .contacted_at(Some("2020-01-01T00:00:00Z".parse().unwrap()))
             .revision("1234567890abcdef".to_string())
             .build()
             .unwrap();
@@ -1027,29 +1699,72 @@ mod test {
     }
 
     #[test]
-    fn test_get_merged_yaml_from_lint_response() {
-        let response = LintResponse::builder()
-            .valid(true)
-            .merged_yaml("image: alpine\nstages:\n  - build\n  - test\nbuild:\n  stage: build\n  script:\n  - echo \"Building\"\ntest:\n  stage: test\n  script:\n  - echo \"Testing\"\n".to_string())
-            .errors(vec![])
-            .build()
-            .unwrap();
-        let mut writer = Vec::new();
+    fn test_lint_ci_file_errors_with_line_context() {
         let mock_cicd = Arc::new(
             PipelineMock::builder()
-                .gitlab_ci_merged_yaml(response.merged_yaml)
+                .lint_errors(vec![
+                    "jobs:test config contains unknown keys: scripts".to_string(),
+                    "(<unknown>): did not find expected key at line 3 column 1".to_string(),
+                ])
+                .lint_warnings(vec!["jobs:build uses a deprecated keyword".to_string()])
                 .build()
                 .unwrap(),
         );
+        let mut writer = Vec::new();
+        let result = lint_ci_file(mock_cicd, &gen_gitlab_ci_body(), false, &mut writer);
+        assert!(result.is_err());
+        assert_eq!(
+            "jobs:test config contains unknown keys: scripts\n\
+             \x20 9 | test:\n\
+             (<unknown>): did not find expected key at line 3 column 1\n\
+             \x20 3 | - build\n\
+             Warning: jobs:build uses a deprecated keyword\n\
+             \x20 5 | build:\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
 
-        let result = lint_ci_file(mock_cicd, &gen_gitlab_ci_body(), true, &mut writer);
-        assert!(result.is_ok());
-        let merged_gitlab_ci = r#"image: alpine
-stages:
-  - build
-  - test
-build:
-  stage: build
+    #[test]
+    fn test_lint_ci_file_valid_prints_warnings() {
+        let mock_cicd = Arc::new(
+            PipelineMock::builder()
+                .lint_warnings(vec!["Unknown key `stages`".to_string()])
+                .build()
+                .unwrap(),
+        );
+        let mut writer = Vec::new();
+        let result = lint_ci_file(mock_cicd, &gen_gitlab_ci_body(), false, &mut writer);
+        assert!(result.is_ok());
+        assert_eq!(
+            "File is valid.\nWarning: Unknown key `stages`\n  2 | stages:\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_merged_yaml_from_lint_response() {
+        let response = LintResponse::builder()
+            .valid(true)
+            .merged_yaml("image: alpine\nstages:\n  - build\n  - test\nbuild:\n  stage: build\n  script:\n  - echo \"Building\"\ntest:\n  stage: test\n  script:\n  - echo \"Testing\"\n".to_string())
+            .errors(vec![])
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        let mock_cicd = Arc::new(
+            PipelineMock::builder()
+                .gitlab_ci_merged_yaml(response.merged_yaml)
+                .build()
+                .unwrap(),
+        );
+
+        let result = lint_ci_file(mock_cicd, &gen_gitlab_ci_body(), true, &mut writer);
+        assert!(result.is_ok());
+        let merged_gitlab_ci = r#"image: alpine
+stages:
+  - build
+  - test
+build:
+  stage: build
   script:
   - echo "Building"
 test:
@@ -1068,6 +1783,9 @@ test:
         error: bool,
         #[builder(default)]
         num_pages: Option<u32>,
+        /// Successive calls to get return the next job, the last one repeats.
+        #[builder(default)]
+        get_calls: RefCell<usize>,
     }
 
     impl JobMock {
@@ -1095,6 +1813,170 @@ test:
         fn num_resources(&self, _args: JobListBodyArgs) -> Result<Option<NumberDeltaErr>> {
             todo!()
         }
+
+        fn get(&self, _job_id: i64) -> Result<Job> {
+            let mut calls = self.get_calls.borrow_mut();
+            let job = self.jobs[(*calls).min(self.jobs.len() - 1)].clone();
+            *calls += 1;
+            Ok(job)
+        }
+
+        fn retry(&self, job_id: i64) -> Result<Job> {
+            Ok(self.with_status(job_id, "pending"))
+        }
+
+        fn cancel(&self, job_id: i64) -> Result<Job> {
+            Ok(self.with_status(job_id, "canceled"))
+        }
+
+        fn play(&self, _job_id: i64) -> Result<Job> {
+            Err(error::gen("Play not supported"))
+        }
+    }
+
+    impl JobMock {
+        fn with_status(&self, job_id: i64, status: &str) -> Job {
+            let mut job = self.jobs[0].clone();
+            job.id = job_id;
+            job.status = status.to_string();
+            job
+        }
+    }
+
+    fn job_with_status(status: &str) -> Job {
+        Job::builder()
+            .id(7)
+            .name("build".to_string())
+            .branch("main".to_string())
+            .author_name("user1".to_string())
+            .commit_sha("1234567890abcdef".to_string())
+            .pipeline_id(1)
+            .url("https://gitlab.com/owner/repo/-/jobs/7".to_string())
+            .runner_tags(vec![])
+            .stage("build".to_string())
+            .status(status.to_string())
+            .created_at(Some("2020-01-01T00:00:00Z".parse().unwrap()))
+            .started_at(Some("2020-01-01T00:01:00Z".parse().unwrap()))
+            .finished_at(Some("2020-01-01T00:01:30Z".parse().unwrap()))
+            .duration("30".to_string())
+            .build()
+            .unwrap()
+    }
+
+    /// Returns a longer log on each call, as a running job does.
+    struct GrowingLogMock {
+        logs: Vec<&'static str>,
+        calls: RefCell<usize>,
+        offsets: RefCell<Vec<u64>>,
+    }
+
+    impl CicdJobLog for GrowingLogMock {
+        fn list_failed_jobs(&self, _last: u32) -> Result<Vec<FailedJob>> {
+            Ok(vec![])
+        }
+
+        fn get_log(&self, _job_id: i64) -> Result<String> {
+            let mut calls = self.calls.borrow_mut();
+            let log = self.logs[(*calls).min(self.logs.len() - 1)];
+            *calls += 1;
+            Ok(log.to_string())
+        }
+
+        fn get_log_from(&self, job_id: i64, offset: u64) -> Result<Vec<u8>> {
+            self.offsets.borrow_mut().push(offset);
+            let log = self.get_log(job_id)?.into_bytes();
+            Ok(log[offset as usize..].to_vec())
+        }
+    }
+
+    fn follow_args(follow: bool) -> JobLogCliArgs {
+        JobLogCliArgs::builder()
+            .id(7)
+            .follow(follow)
+            .interval(Seconds::new(2))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_job_log_follow_prints_new_lines_until_job_finishes() {
+        let log_remote = Arc::new(GrowingLogMock {
+            logs: vec!["step 1\n", "step 1\nstep 2\n", "step 1\nstep 2\ndone\n"],
+            calls: RefCell::new(0),
+            offsets: RefCell::new(Vec::new()),
+        });
+        let job_remote = Arc::new(
+            JobMock::builder()
+                .jobs(vec![
+                    job_with_status("running"),
+                    job_with_status("running"),
+                    job_with_status("success"),
+                ])
+                .build()
+                .unwrap(),
+        );
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        job_log(
+            log_remote.clone(),
+            job_remote,
+            follow_args(true),
+            &throttler,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!("step 1\nstep 2\ndone\n", String::from_utf8(buf).unwrap());
+        assert_eq!(vec![0, 7, 14], *log_remote.offsets.borrow());
+        assert_eq!(2, *throttler.throttled());
+        assert_eq!(Milliseconds::new(4000), *throttler.milliseconds_throttled());
+    }
+
+    #[test]
+    fn test_job_log_without_follow_prints_once() {
+        let log_remote = Arc::new(GrowingLogMock {
+            logs: vec!["step 1\n", "step 1\nstep 2\n"],
+            calls: RefCell::new(0),
+            offsets: RefCell::new(Vec::new()),
+        });
+        let job_remote = Arc::new(
+            JobMock::builder()
+                .jobs(vec![job_with_status("running")])
+                .build()
+                .unwrap(),
+        );
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        job_log(
+            log_remote,
+            job_remote,
+            follow_args(false),
+            &throttler,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!("step 1\n", String::from_utf8(buf).unwrap());
+        assert_eq!(0, *throttler.throttled());
+    }
+
+    #[test]
+    fn test_job_action_prints_job() {
+        let remote = Arc::new(
+            JobMock::builder()
+                .jobs(vec![job_with_status("failed")])
+                .build()
+                .unwrap(),
+        );
+        let args = JobActionCliArgs::builder()
+            .action(JobAction::Retry)
+            .id(8)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        job_action(remote, args, &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("8"));
+        assert!(output.contains("pending"));
     }
 
     #[test]
@@ -1111,9 +1993,9 @@ test:
                 .runner_tags(vec!["tag1".to_string(), "tag2".to_string()])
                 .stage("build".to_string())
                 .status("success".to_string())
-                .created_at("2020-01-01T00:00:00Z".to_string())
-                .started_at("2020-01-01T00:01:00Z".to_string())
-                .finished_at("2020-01-01T00:01:30Z".to_string())
+                .created_at(Some("2020-01-01T00:00:00Z".parse().unwrap()))
+                .started_at(Some("2020-01-01T00:01:00Z".parse().unwrap()))
+                .finished_at(Some("2020-01-01T00:01:30Z".parse().unwrap()))
                 .duration("25".to_string())
                 .build()
                 .unwrap(),
@@ -1128,9 +2010,9 @@ test:
                 .runner_tags(vec!["tag1".to_string(), "tag2".to_string()])
                 .stage("test".to_string())
                 .status("failed".to_string())
-                .created_at("2020-01-01T00:00:00Z".to_string())
-                .started_at("2020-01-01T00:01:00Z".to_string())
-                .finished_at("2020-01-01T00:01:30Z".to_string())
+                .created_at(Some("2020-01-01T00:00:00Z".parse().unwrap()))
+                .started_at(Some("2020-01-01T00:01:00Z".parse().unwrap()))
+                .finished_at(Some("2020-01-01T00:01:30Z".parse().unwrap()))
                 .duration("30".to_string())
                 .build()
                 .unwrap(),
@@ -1144,7 +2026,7 @@ test:
             .unwrap();
         list_jobs(Arc::new(remote), body_args, cli_args, &mut buf).unwrap();
         assert_eq!(
-"ID|Name|Author Name|Branch|Commit SHA|Pipeline ID|URL|Runner Tags|Stage|Status|Created At|Started At|Finished At|Duration\n1|job1|user1|main|1234567890abcdef|1|https://gitlab.com/owner/repo/-/jobs/1|tag1, tag2|build|success|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|2020-01-01T00:01:30Z|25\n2|job2|user2|main|1234567890abcdef|1|https://gitlab.com/owner/repo/-/jobs/2|tag1, tag2|test|failed|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|2020-01-01T00:01:30Z|30\n",
+"ID|Name|Author Name|Branch|Commit SHA|Pipeline ID|URL|Runner Tags|Stage|Status|Created At|Started At|Finished At|Duration\n1|job1|user1|main|1234567890abcdef|1|https://gitlab.com/owner/repo/-/jobs/1|tag1, tag2|build|success|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|2020-01-01T00:01:30Z|25s\n2|job2|user2|main|1234567890abcdef|1|https://gitlab.com/owner/repo/-/jobs/2|tag1, tag2|test|failed|2020-01-01T00:00:00Z|2020-01-01T00:01:00Z|2020-01-01T00:01:30Z|30s\n",
             String::from_utf8(buf).unwrap()
         );
     }
@@ -1165,4 +2047,487 @@ test:
             String::from_utf8(buf).unwrap()
         )
     }
+
+    struct UsageMock {
+        usage: Vec<RunnerUsage>,
+    }
+
+    impl CicdUsage for UsageMock {
+        fn usage(&self, _group: &str) -> Result<Vec<RunnerUsage>> {
+            Ok(self.usage.clone())
+        }
+    }
+
+    fn usage(month: &str, project: &str, minutes: f64) -> RunnerUsage {
+        RunnerUsage::builder()
+            .month(month.to_string())
+            .project(project.to_string())
+            .minutes(minutes)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_runner_usage_report_sorted_by_month_and_minutes() {
+        let remote = Arc::new(UsageMock {
+            usage: vec![
+                usage("2024-01", "jordilin/gitar", 12.0),
+                usage("2024-02", "jordilin/gitar", 35.0),
+                usage("2024-02", "jordilin/gitlapi", 420.0),
+            ],
+        });
+        let cli_args = RunnerUsageCliArgs::builder()
+            .group("jordilin".to_string())
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        runner_usage(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "Month|Project|Minutes\n\
+             2024-02|jordilin/gitlapi|420.0\n\
+             2024-02|jordilin/gitar|35.0\n\
+             2024-01|jordilin/gitar|12.0\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_runner_usage_report_empty() {
+        let remote = Arc::new(UsageMock { usage: vec![] });
+        let cli_args = RunnerUsageCliArgs::builder()
+            .group("jordilin".to_string())
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        runner_usage(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "No CI/CD minutes usage found.\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    struct ArtifactMock {
+        artifacts: Vec<Artifact>,
+        archive: Vec<u8>,
+        deleted: std::cell::RefCell<Vec<i64>>,
+    }
+
+    impl ArtifactMock {
+        fn new(artifacts: Vec<Artifact>) -> Self {
+            ArtifactMock {
+                artifacts,
+                archive: Vec::new(),
+                deleted: std::cell::RefCell::new(Vec::new()),
+            }
+        }
+
+        fn with_archive(archive: Vec<u8>) -> Self {
+            ArtifactMock {
+                archive,
+                ..ArtifactMock::new(vec![])
+            }
+        }
+    }
+
+    impl CicdArtifact for ArtifactMock {
+        fn list(&self) -> Result<Vec<Artifact>> {
+            Ok(self.artifacts.clone())
+        }
+
+        fn delete(&self, id: i64) -> Result<()> {
+            self.deleted.borrow_mut().push(id);
+            Ok(())
+        }
+
+        fn read_archive(&self, _id: i64, range: zip::ByteRange) -> Result<Vec<u8>> {
+            Ok(range.slice(self.archive.clone()))
+        }
+    }
+
+    fn archive_files_cli_args(path: Option<&str>) -> ArtifactListFilesCliArgs {
+        ArtifactListFilesCliArgs::builder()
+            .id(23)
+            .path(path.map(|p| p.to_string()))
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_list_artifact_files() {
+        let archive = zip::test::build_archive(&[
+            ("target/report.txt", b"test result: ok"),
+            ("target/gr", b"binary"),
+        ]);
+        let remote = Arc::new(ArtifactMock::with_archive(archive));
+        let mut buf = Vec::new();
+        list_artifact_files(remote, archive_files_cli_args(None), &mut buf).unwrap();
+        assert_eq!(
+            "Path|Size|Compressed\n\
+             target/report.txt|15.00 B|17.00 B\n\
+             target/gr|6.00 B|6.00 B\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_artifact_files_extracts_path_to_writer() {
+        let archive = zip::test::build_archive(&[("target/report.txt", b"test result: ok")]);
+        let remote = Arc::new(ArtifactMock::with_archive(archive));
+        let mut buf = Vec::new();
+        list_artifact_files(
+            remote,
+            archive_files_cli_args(Some("target/report.txt")),
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(b"test result: ok".to_vec(), buf);
+    }
+
+    #[test]
+    fn test_list_artifact_files_path_not_found_is_error() {
+        let archive = zip::test::build_archive(&[("target/gr", b"binary")]);
+        let remote = Arc::new(ArtifactMock::with_archive(archive));
+        let mut buf = Vec::new();
+        let result = list_artifact_files(remote, archive_files_cli_args(Some("missing")), &mut buf);
+        match result {
+            Err(err) => match err.downcast_ref::<error::GRError>() {
+                Some(error::GRError::PreconditionNotMet(msg)) => {
+                    assert!(msg.contains("missing"))
+                }
+                _ => panic!("Expected PreconditionNotMet"),
+            },
+            _ => panic!("Expected error"),
+        }
+    }
+
+    fn artifact(id: i64, size: u64, created_at: &str) -> Artifact {
+        Artifact::builder()
+            .id(id)
+            .name(format!("build-{}", id))
+            .size(size)
+            .created_at(Some(created_at.parse().unwrap()))
+            .build()
+            .unwrap()
+    }
+
+    fn artifacts() -> Vec<Artifact> {
+        vec![
+            artifact(1, 2048, "2024-01-01T00:00:00Z"),
+            artifact(2, 1024, "2024-01-10T00:00:00Z"),
+            artifact(3, 4096, "2024-02-25T00:00:00Z"),
+        ]
+    }
+
+    // 2024-03-01T00:00:00Z
+    fn now() -> Seconds {
+        Seconds::new(1709251200)
+    }
+
+    fn prune_args(dry_run: bool) -> ArtifactPruneCliArgs {
+        ArtifactPruneCliArgs::builder()
+            .older_than(Seconds::new(30 * 86400))
+            .dry_run(dry_run)
+            .yes(true)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prune_artifacts_dry_run_lists_space_to_reclaim() {
+        let remote = Arc::new(ArtifactMock::new(artifacts()));
+        let mut buf = Vec::new();
+        prune_artifacts(remote.clone(), prune_args(true), now, &mut buf).unwrap();
+        assert_eq!(
+            "ID|Name|Size|Created at\n\
+             1|build-1|2.00 KB|2024-01-01T00:00:00Z\n\
+             2|build-2|1.00 KB|2024-01-10T00:00:00Z\n\
+             Space to be reclaimed: 2 artifacts, 3.00 KB\n",
+            String::from_utf8(buf).unwrap()
+        );
+        assert!(remote.deleted.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_prune_artifacts_deletes_old_artifacts() {
+        let remote = Arc::new(ArtifactMock::new(artifacts()));
+        let mut buf = Vec::new();
+        prune_artifacts(remote.clone(), prune_args(false), now, &mut buf).unwrap();
+        assert_eq!(vec![1, 2], *remote.deleted.borrow());
+        assert_eq!(
+            "Deleted artifact ID: [1], Name: [build-1]\n\
+             Deleted artifact ID: [2], Name: [build-2]\n\
+             Space reclaimed: 2 artifacts, 3.00 KB\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_prune_artifacts_nothing_to_prune() {
+        let remote = Arc::new(ArtifactMock::new(vec![artifact(
+            3,
+            4096,
+            "2024-02-25T00:00:00Z",
+        )]));
+        let mut buf = Vec::new();
+        prune_artifacts(remote, prune_args(false), now, &mut buf).unwrap();
+        assert_eq!("No artifacts to prune.\n", String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn test_prune_artifacts_keeps_artifacts_without_creation_date() {
+        let mut artifacts = artifacts();
+        artifacts.push(
+            Artifact::builder()
+                .id(4)
+                .name("build-4".to_string())
+                .size(512)
+                .created_at(None)
+                .build()
+                .unwrap(),
+        );
+        let remote = Arc::new(ArtifactMock::new(artifacts));
+        let mut buf = Vec::new();
+        prune_artifacts(remote.clone(), prune_args(false), now, &mut buf).unwrap();
+        assert_eq!(vec![1, 2], *remote.deleted.borrow());
+    }
+
+    #[test]
+    fn test_artifact_without_creation_date_displays_empty() {
+        let artifact = Artifact::builder()
+            .id(4)
+            .name("build-4".to_string())
+            .size(1024)
+            .created_at(None)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        display::print(
+            &mut buf,
+            vec![artifact],
+            GetRemoteCliArgs::builder().build().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            "ID|Name|Size|Created at\n4|build-4|1.00 KB|\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    struct JobLogMock {
+        jobs: Vec<FailedJob>,
+        logs: HashMap<i64, String>,
+    }
+
+    impl CicdJobLog for JobLogMock {
+        fn list_failed_jobs(&self, _last: u32) -> Result<Vec<FailedJob>> {
+            Ok(self.jobs.clone())
+        }
+
+        fn get_log(&self, job_id: i64) -> Result<String> {
+            Ok(self.logs.get(&job_id).cloned().unwrap_or_default())
+        }
+    }
+
+    fn failed_job(pipeline_id: i64, id: i64, name: &str) -> FailedJob {
+        FailedJob::builder()
+            .pipeline_id(pipeline_id)
+            .id(id)
+            .name(name.to_string())
+            .web_url(format!("https://gitlab.com/jobs/{}", id))
+            .build()
+            .unwrap()
+    }
+
+    fn grep_args(pattern: &str) -> JobLogGrepCliArgs {
+        JobLogGrepCliArgs::builder()
+            .pattern(pattern.to_string())
+            .last(20)
+            .parallel(2)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_grep_job_logs_reports_matching_jobs() {
+        let remote = Arc::new(JobLogMock {
+            jobs: vec![
+                failed_job(10, 100, "build"),
+                failed_job(11, 110, "test"),
+                failed_job(11, 111, "lint"),
+            ],
+            logs: HashMap::from([
+                (100, "step 1\nprocess OOMKilled\n".to_string()),
+                (110, "OOMKilled once\nOOMKilled twice\n".to_string()),
+                (111, "clippy warning\n".to_string()),
+            ]),
+        });
+        let mut buf = Vec::new();
+        grep_job_logs(remote, grep_args("OOMKilled"), &mut buf).unwrap();
+        assert_eq!(
+            "Pipeline|Job ID|Job|Matches|First match\n\
+             11|110|test|2|OOMKilled once\n\
+             10|100|build|1|process OOMKilled\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_grep_job_logs_no_matches() {
+        let remote = Arc::new(JobLogMock {
+            jobs: vec![failed_job(10, 100, "build")],
+            logs: HashMap::from([(100, "all good\n".to_string())]),
+        });
+        let mut buf = Vec::new();
+        grep_job_logs(remote, grep_args("OOMKilled"), &mut buf).unwrap();
+        assert_eq!(
+            "No matches found in 1 failed jobs.\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_grep_job_logs_invalid_pattern_is_error() {
+        let remote = Arc::new(JobLogMock {
+            jobs: vec![],
+            logs: HashMap::new(),
+        });
+        let mut buf = Vec::new();
+        assert!(grep_job_logs(remote, grep_args("(OOM"), &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_match_log_strips_ansi_escape_sequences() {
+        let pattern = regex::Regex::new("exit code 137").unwrap();
+        let log = "\x1b[0;31mERROR: Job failed: exit code 137\x1b[0m\n";
+        let job_match = match_log(&pattern, failed_job(10, 100, "build"), log).unwrap();
+        assert_eq!("ERROR: Job failed: exit code 137", job_match.first_match);
+        assert_eq!(1, job_match.matches);
+    }
+
+    struct TestReportMock {
+        results: Vec<flaky::TestResult>,
+    }
+
+    impl CicdTestReport for TestReportMock {
+        fn list_test_results(&self, _last: u32) -> Result<Vec<flaky::TestResult>> {
+            Ok(self.results.clone())
+        }
+    }
+
+    fn test_result(pipeline_id: i64, name: &str, passed: bool) -> flaky::TestResult {
+        flaky::TestResult::builder()
+            .pipeline_id(pipeline_id)
+            .name(name.to_string())
+            .passed(passed)
+            .build()
+            .unwrap()
+    }
+
+    fn flaky_args() -> FlakyTestsCliArgs {
+        FlakyTestsCliArgs::builder()
+            .last(20)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_flaky_tests_ranked_table() {
+        let remote = Arc::new(TestReportMock {
+            results: vec![
+                test_result(1, "test_a", true),
+                test_result(2, "test_a", false),
+                test_result(1, "test_b", false),
+                test_result(2, "test_b", true),
+                test_result(3, "test_b", false),
+                test_result(1, "test_c", true),
+                test_result(2, "test_c", true),
+            ],
+        });
+        let mut buf = Vec::new();
+        flaky_tests(remote, flaky_args(), &mut buf).unwrap();
+        assert_eq!(
+            "Test|Runs|Passed|Failed|Flips|Last failed pipeline\n\
+             test_b|3|1|2|2|3\n\
+             test_a|2|1|1|1|2\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_flaky_tests_none_found() {
+        let remote = Arc::new(TestReportMock {
+            results: vec![test_result(1, "test_a", true)],
+        });
+        let mut buf = Vec::new();
+        flaky_tests(remote, flaky_args(), &mut buf).unwrap();
+        assert_eq!(
+            "No flaky tests found in 1 test results.\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    struct JobTimingMock {
+        timings: Vec<stats::JobTiming>,
+    }
+
+    impl CicdJobTiming for JobTimingMock {
+        fn list_job_timings(&self, _last: u32) -> Result<Vec<stats::JobTiming>> {
+            Ok(self.timings.clone())
+        }
+    }
+
+    fn job_stats_args() -> JobStatsCliArgs {
+        JobStatsCliArgs::builder()
+            .last(20)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn job_timing(pipeline_id: i64, name: &str, queued: f64, duration: f64) -> stats::JobTiming {
+        stats::JobTiming::builder()
+            .pipeline_id(pipeline_id)
+            .name(name.to_string())
+            .queued(queued)
+            .duration(duration)
+            .runner("runner-1".to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_job_stats_longest_queue_first() {
+        let remote = Arc::new(JobTimingMock {
+            timings: vec![
+                job_timing(1, "build", 2.0, 120.0),
+                job_timing(2, "build", 4.0, 60.0),
+                job_timing(1, "deploy", 600.0, 30.0),
+            ],
+        });
+        let mut buf = Vec::new();
+        job_stats(remote, job_stats_args(), &mut buf).unwrap();
+        assert_eq!(
+            "Job|Runs|Avg queued|Max queued|Avg duration|Max duration|Queued|Runners\n\
+             deploy|1|10m|10m|30s|30s|95%|1\n\
+             build|2|3s|4s|1m 30s|2m|3%|1\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_job_stats_no_jobs() {
+        let remote = Arc::new(JobTimingMock { timings: vec![] });
+        let mut buf = Vec::new();
+        job_stats(remote, job_stats_args(), &mut buf).unwrap();
+        assert_eq!(
+            "No finished jobs found in the last 20 pipelines.\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
 }
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::display::{Column, DisplayBody};
+
+/// Outcome of a single test case execution in a pipeline. Gathered either
+/// from JUnit test reports or from test runner output in job logs.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub pipeline_id: i64,
+    pub name: String,
+    pub passed: bool,
+}
+
+impl TestResult {
+    pub fn builder() -> TestResultBuilder {
+        TestResultBuilder::default()
+    }
+}
+
+/// A test that both passed and failed across the analyzed pipelines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlakyTest {
+    pub name: String,
+    pub runs: usize,
+    pub passed: usize,
+    pub failed: usize,
+    /// Number of times the outcome changed from pass to fail or vice versa,
+    /// following pipelines in chronological order.
+    pub flips: usize,
+    pub last_failed_pipeline: i64,
+}
+
+impl From<FlakyTest> for DisplayBody {
+    fn from(test: FlakyTest) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Test", test.name),
+                Column::count("Runs", test.runs as u64),
+                Column::new("Passed", test.passed.to_string()),
+                Column::new("Failed", test.failed.to_string()),
+                Column::new("Flips", test.flips.to_string()),
+                Column::new(
+                    "Last failed pipeline",
+                    test.last_failed_pipeline.to_string(),
+                ),
+            ],
+        }
+    }
+}
+
+/// Finds the tests that alternate between pass and fail. Results are ranked
+/// by number of flips, then by number of failures. Ties are broken by test
+/// name, so the report is stable across runs.
+pub fn detect(results: Vec<TestResult>) -> Vec<FlakyTest> {
+    let mut by_test: HashMap<String, Vec<TestResult>> = HashMap::new();
+    for result in results {
+        by_test.entry(result.name.clone()).or_default().push(result);
+    }
+    let mut flaky = by_test
+        .into_iter()
+        .filter_map(|(name, mut results)| {
+            // Pipeline IDs are monotonically increasing. Stable sort keeps
+            // the order of retries within the same pipeline.
+            results.sort_by_key(|result| result.pipeline_id);
+            let passed = results.iter().filter(|result| result.passed).count();
+            let failed = results.len() - passed;
+            if passed == 0 || failed == 0 {
+                return None;
+            }
+            let flips = results
+                .windows(2)
+                .filter(|pair| pair[0].passed != pair[1].passed)
+                .count();
+            let last_failed_pipeline = results
+                .iter()
+                .filter(|result| !result.passed)
+                .map(|result| result.pipeline_id)
+                .max()
+                .unwrap_or_default();
+            Some(FlakyTest {
+                name,
+                runs: results.len(),
+                passed,
+                failed,
+                flips,
+                last_failed_pipeline,
+            })
+        })
+        .collect::<Vec<_>>();
+    flaky.sort_by(|a, b| {
+        b.flips
+            .cmp(&a.flips)
+            .then(b.failed.cmp(&a.failed))
+            .then(a.name.cmp(&b.name))
+    });
+    flaky
+}
+
+lazy_static! {
+    static ref ANSI: Regex = Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap();
+    // cargo test: `test module::test_name ... ok`
+    static ref CARGO_TEST: Regex = Regex::new(r"\btest (\S+) \.\.\. (ok|FAILED)\b").unwrap();
+    // go test -v: `--- PASS: TestName (0.00s)`
+    static ref GO_TEST: Regex = Regex::new(r"--- (PASS|FAIL): (\S+)").unwrap();
+    // pytest -v: `tests/test_file.py::test_name PASSED`
+    static ref PYTEST: Regex = Regex::new(r"(\S+::\S+) (PASSED|FAILED)\b").unwrap();
+}
+
+/// Extracts test outcomes from a job log. Recognizes the verbose output of
+/// cargo test, go test and pytest. Lines not matching any of them are
+/// ignored.
+pub fn parse_log(pipeline_id: i64, log: &str) -> Vec<TestResult> {
+    let log = ANSI.replace_all(log, "");
+    log.lines()
+        .filter_map(|line| {
+            if let Some(caps) = CARGO_TEST.captures(line) {
+                return Some((caps[1].to_string(), &caps[2] == "ok"));
+            }
+            if let Some(caps) = GO_TEST.captures(line) {
+                return Some((caps[2].to_string(), &caps[1] == "PASS"));
+            }
+            if let Some(caps) = PYTEST.captures(line) {
+                return Some((caps[1].to_string(), &caps[2] == "PASSED"));
+            }
+            None
+        })
+        .map(|(name, passed)| TestResult {
+            pipeline_id,
+            name,
+            passed,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn result(pipeline_id: i64, name: &str, passed: bool) -> TestResult {
+        TestResult::builder()
+            .pipeline_id(pipeline_id)
+            .name(name.to_string())
+            .passed(passed)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_detect_ranks_by_flips_then_failures() {
+        let results = vec![
+            result(1, "test_stable", true),
+            result(2, "test_stable", true),
+            result(1, "test_broken", false),
+            result(2, "test_broken", false),
+            result(1, "test_flaky", true),
+            result(2, "test_flaky", false),
+            result(3, "test_flaky", true),
+            result(1, "test_once", true),
+            result(2, "test_once", true),
+            result(3, "test_once", false),
+        ];
+        let flaky = detect(results);
+        assert_eq!(2, flaky.len());
+        assert_eq!("test_flaky", flaky[0].name);
+        assert_eq!(2, flaky[0].flips);
+        assert_eq!(3, flaky[0].runs);
+        assert_eq!(2, flaky[0].last_failed_pipeline);
+        assert_eq!("test_once", flaky[1].name);
+        assert_eq!(1, flaky[1].flips);
+        assert_eq!(1, flaky[1].failed);
+    }
+
+    #[test]
+    fn test_detect_orders_results_by_pipeline() {
+        let results = vec![
+            result(3, "test_flaky", false),
+            result(1, "test_flaky", false),
+            result(2, "test_flaky", true),
+        ];
+        let flaky = detect(results);
+        assert_eq!(2, flaky[0].flips);
+        assert_eq!(3, flaky[0].last_failed_pipeline);
+    }
+
+    #[test]
+    fn test_parse_log_recognizes_test_runners() {
+        let log = "2024-02-13T04:58:47.1Z test cache::test_get ... ok\n\
+                   \x1b[31mtest cache::test_set ... FAILED\x1b[0m\n\
+                   --- FAIL: TestHandler (0.01s)\n\
+                   --- PASS: TestRouter (0.00s)\n\
+                   tests/test_api.py::test_login PASSED [ 50%]\n\
+                   tests/test_api.py::test_logout FAILED [100%]\n\
+                   running 6 tests\n";
+        let results = parse_log(7, log);
+        assert_eq!(
+            vec![
+                result(7, "cache::test_get", true),
+                result(7, "cache::test_set", false),
+                result(7, "TestHandler", false),
+                result(7, "TestRouter", true),
+                result(7, "tests/test_api.py::test_login", true),
+                result(7, "tests/test_api.py::test_logout", false),
+            ],
+            results
+        );
+    }
+}
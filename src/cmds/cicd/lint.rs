@@ -0,0 +1,261 @@
+//! Local validation of Github Actions workflows and line context for lint
+//! errors. Gitlab validates its CI configuration through its lint API, Github
+//! has no such API, so the most common mistakes are checked here.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use yaml_rust2::{Yaml, YamlLoader};
+
+use super::LintResponse;
+
+lazy_static! {
+    // Gitlab YAML errors and yaml_rust2 scan errors, ex. `at line 3 column 1`
+    static ref LINE: Regex = Regex::new(r"\bline (\d+)").unwrap();
+    // Gitlab job errors, ex. `jobs:build config contains unknown keys`
+    static ref JOB: Regex = Regex::new(r"\bjobs:([^:\s]+)").unwrap();
+    static ref KEY: Regex = Regex::new(r"`([^`]+)`").unwrap();
+}
+
+const WORKFLOW_KEYS: [&str; 8] = [
+    "name",
+    "run-name",
+    "on",
+    "permissions",
+    "env",
+    "defaults",
+    "concurrency",
+    "jobs",
+];
+
+/// Line number and content of the file the lint message refers to, if it can
+/// be told from the message.
+pub fn line_context(message: &str, body: &str) -> Option<(usize, String)> {
+    let lines = body.lines().collect::<Vec<_>>();
+    if let Some(caps) = LINE.captures(message) {
+        let number = caps[1].parse::<usize>().ok()?;
+        let line = lines.get(number.checked_sub(1)?)?;
+        return Some((number, line.to_string()));
+    }
+    let key = JOB
+        .captures(message)
+        .or_else(|| KEY.captures(message))
+        .map(|caps| caps[1].to_string())?;
+    lines
+        .iter()
+        .position(|line| {
+            let line = line.trim_start();
+            line.strip_prefix(key.as_str())
+                .is_some_and(|rest| rest.starts_with(':'))
+        })
+        .map(|index| (index + 1, lines[index].to_string()))
+}
+
+/// Validates the structure of a Github Actions workflow: triggers, jobs,
+/// runners and steps.
+pub fn validate_workflow(body: &str) -> LintResponse {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    match YamlLoader::load_from_str(body) {
+        Ok(docs) => match docs.first() {
+            Some(workflow @ Yaml::Hash(_)) => {
+                validate_keys(workflow, &mut errors);
+                validate_jobs(&workflow["jobs"], &mut errors, &mut warnings);
+            }
+            _ => errors.push("Workflow should be a mapping".to_string()),
+        },
+        Err(err) => errors.push(err.to_string()),
+    }
+    LintResponse::builder()
+        .valid(errors.is_empty())
+        .errors(errors)
+        .warnings(warnings)
+        .build()
+        .unwrap()
+}
+
+fn validate_keys(workflow: &Yaml, errors: &mut Vec<String>) {
+    if workflow["on"].is_badvalue() {
+        errors.push("Workflow is missing the `on` trigger".to_string());
+    }
+    if let Yaml::Hash(keys) = workflow {
+        for key in keys.keys().filter_map(|key| key.as_str()) {
+            if !WORKFLOW_KEYS.contains(&key) {
+                errors.push(format!("Unknown workflow key `{}`", key));
+            }
+        }
+    }
+}
+
+fn validate_jobs(jobs: &Yaml, errors: &mut Vec<String>, warnings: &mut Vec<String>) {
+    let jobs = match jobs {
+        Yaml::Hash(jobs) if !jobs.is_empty() => jobs,
+        Yaml::BadValue => {
+            errors.push("Workflow is missing `jobs`".to_string());
+            return;
+        }
+        _ => {
+            errors.push("`jobs` should be a mapping with at least one job".to_string());
+            return;
+        }
+    };
+    let names = jobs
+        .keys()
+        .filter_map(|name| name.as_str())
+        .collect::<Vec<_>>();
+    for (name, job) in jobs {
+        let name = name.as_str().unwrap_or_default();
+        if !matches!(job, Yaml::Hash(_)) {
+            errors.push(format!("jobs:{} should be a mapping", name));
+            continue;
+        }
+        let needs = match &job["needs"] {
+            Yaml::String(need) => vec![need.as_str()],
+            Yaml::Array(needs) => needs.iter().filter_map(|need| need.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        for need in needs.iter().filter(|need| !names.contains(need)) {
+            errors.push(format!("jobs:{} needs unknown job {}", name, need));
+        }
+        // Jobs calling a reusable workflow have no runner nor steps.
+        if !job["uses"].is_badvalue() {
+            if !job["steps"].is_badvalue() {
+                errors.push(format!("jobs:{} cannot have both uses and steps", name));
+            }
+            continue;
+        }
+        if job["runs-on"].is_badvalue() {
+            errors.push(format!("jobs:{} is missing runs-on", name));
+        }
+        match &job["steps"] {
+            Yaml::Array(steps) if !steps.is_empty() => {
+                validate_steps(name, steps, errors, warnings)
+            }
+            _ => errors.push(format!("jobs:{} should have a list of steps", name)),
+        }
+    }
+}
+
+fn validate_steps(job: &str, steps: &[Yaml], errors: &mut Vec<String>, warnings: &mut Vec<String>) {
+    for (index, step) in steps.iter().enumerate() {
+        let number = index + 1;
+        match (&step["run"], &step["uses"]) {
+            (Yaml::BadValue, Yaml::BadValue) | (Yaml::String(_), Yaml::String(_)) => {
+                errors.push(format!(
+                    "jobs:{} step {} should have either run or uses",
+                    job, number
+                ));
+            }
+            (_, Yaml::String(action)) if !action.contains('@') && !action.starts_with('.') => {
+                warnings.push(format!(
+                    "jobs:{} step {} uses {} without a version, ex. {}@v4",
+                    job, number, action, action
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const WORKFLOW: &str = r#"name: CI
+on: [push]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: cargo build
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo test
+"#;
+
+    #[test]
+    fn test_valid_workflow() {
+        let response = validate_workflow(WORKFLOW);
+        assert!(response.valid);
+        assert!(response.errors.is_empty());
+        assert!(response.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_workflow_errors_and_warnings() {
+        let workflow = r#"name: CI
+jobs:
+  build:
+    steps:
+      - uses: actions/checkout
+      - name: Nothing to do
+  test:
+    needs: [build, lint]
+    runs-on: ubuntu-latest
+    steps: []
+"#;
+        let response = validate_workflow(workflow);
+        assert!(!response.valid);
+        assert_eq!(
+            vec![
+                "Workflow is missing the `on` trigger",
+                "jobs:build is missing runs-on",
+                "jobs:build step 2 should have either run or uses",
+                "jobs:test needs unknown job lint",
+                "jobs:test should have a list of steps",
+            ],
+            response.errors
+        );
+        assert_eq!(
+            vec!["jobs:build step 1 uses actions/checkout without a version, ex. actions/checkout@v4"],
+            response.warnings
+        );
+    }
+
+    #[test]
+    fn test_reusable_workflow_jobs_need_no_steps() {
+        let workflow = r#"on: push
+jobs:
+  call:
+    uses: org/repo/.github/workflows/ci.yml@main
+"#;
+        assert!(validate_workflow(workflow).valid);
+    }
+
+    #[test]
+    fn test_workflow_yaml_syntax_error_has_line() {
+        let workflow = "on: push\njobs:\n  build: [\n";
+        let response = validate_workflow(workflow);
+        assert!(!response.valid);
+        assert!(LINE.is_match(&response.errors[0]));
+    }
+
+    #[test]
+    fn test_line_context() {
+        assert_eq!(
+            Some((9, "  test:".to_string())),
+            line_context("jobs:test needs unknown job lint", WORKFLOW)
+        );
+        assert_eq!(
+            Some((2, "on: [push]".to_string())),
+            line_context(
+                "(<unknown>): mapping values are not allowed at line 2 column 3",
+                WORKFLOW
+            )
+        );
+        assert_eq!(
+            Some((1, "name: CI".to_string())),
+            line_context("Unknown workflow key `name`", WORKFLOW)
+        );
+        assert_eq!(
+            None,
+            line_context(
+                "jobs config should contain at least one visible job",
+                WORKFLOW
+            )
+        );
+        assert_eq!(None, line_context("error at line 99", WORKFLOW));
+    }
+}
@@ -4,11 +4,19 @@ use std::{
     ops::Index,
 };
 
-use crate::{error::GRError, Result};
+use serde::Serialize;
+
+use crate::{
+    display::{Column, DisplayBody},
+    error::GRError,
+    Result,
+};
+
+use super::rules::{self, PipelineEvent, When};
 
 /// A .gitlab-ci.yml is a sequence of stages, where each stage is a collection
 /// of jobs. A stage name is unique, so we can uniquely identify them by name.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Stage {
     pub name: String,
     pub jobs: Vec<Job>,
@@ -57,10 +65,13 @@ impl StageMap {
 /// A job is a unique unit of work that is executed in a gitlab-ci pipeline. They
 /// belong to a stage. No job can be named the same, so we can uniquely identify
 /// them by name.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Job {
     pub name: String,
     pub rules: Vec<HashMap<String, CicdEntity>>,
+    /// The job has to be started by hand, `when: manual`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub manual: bool,
 }
 
 impl Job {
@@ -68,12 +79,31 @@ impl Job {
         Self {
             name: name.to_string(),
             rules,
+            manual: false,
+        }
+    }
+
+    /// Mermaid node of the job, labelled if it is manual.
+    fn node(&self) -> String {
+        if self.manual {
+            format!("{}[\"{} (manual)\"]", self.name, self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+
+    fn label(&self) -> String {
+        if self.manual {
+            format!("{} (manual)", self.name)
+        } else {
+            self.name.clone()
         }
     }
 }
 
 /// Defines a CicdEntity entity that can be a sequence, a mapping, a string or null.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
 pub enum CicdEntity {
     Vec(Vec<CicdEntity>),
     Hash(HashMap<String, CicdEntity>),
@@ -136,6 +166,7 @@ impl Index<usize> for CicdEntity {
 pub enum EntityName {
     Stage,
     Job,
+    Workflow,
 }
 
 impl AsRef<str> for EntityName {
@@ -143,6 +174,7 @@ impl AsRef<str> for EntityName {
         match self {
             EntityName::Stage => "stages",
             EntityName::Job => "jobs",
+            EntityName::Workflow => "workflow",
         }
     }
 }
@@ -155,6 +187,9 @@ pub trait CicdParser {
     fn get_stages(&self) -> Result<StageMap>;
     /// Gathers the jobs and populate the stages with their corresponding jobs
     fn get_jobs(&self, stages: &mut StageMap);
+    /// Rules of the top-level `workflow:` keyword, deciding whether a
+    /// pipeline is created at all.
+    fn get_workflow_rules(&self) -> Vec<HashMap<String, CicdEntity>>;
 }
 
 // Encapsulates the YAML parser library that we use to parse the YAML file.
@@ -206,64 +241,21 @@ impl<T: ToCicdEntity> CicdParser for YamlParser<T> {
                     // could be an anchor `.template` without an associated stage
                     continue;
                 }
-                let mut rules: Vec<HashMap<String, CicdEntity>> = job_details["rules"]
-                    .as_vec()
-                    .map(|rules| {
-                        rules
-                            .iter()
-                            .map(|rule| {
-                                if let Some(rule) = rule.as_hash() {
-                                    let mut rule_map = HashMap::new();
-                                    for (key, value) in rule.iter() {
-                                        rule_map.insert(key.clone(), value.clone());
-                                    }
-                                    rule_map
-                                } else if let Some(rule) = rule.as_vec() {
-                                    let mut rule_map = HashMap::new();
-                                    for rule in rule {
-                                        if let Some(rule) = rule.as_hash() {
-                                            for (key, value) in rule {
-                                                let value = value.clone();
-                                                rule_map.insert(key.clone(), value);
-                                            }
-                                        }
-                                    }
-                                    rule_map
-                                } else {
-                                    // empty rules
-                                    HashMap::new()
-                                }
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
+                let mut rules = rule_maps(&job_details["rules"]);
                 // if job_name has white spaces join them with a hyphen
                 let job_name = job_name.split_whitespace().collect::<Vec<&str>>().join("-");
-                // if rules is empty, check only rules
-                let only = job_details["only"].as_vec();
-                if only.is_some() {
-                    rules = vec![];
-                    for rule in only.unwrap() {
-                        let mut rule_map = HashMap::new();
-                        rule_map.insert("only".to_string(), rule.clone());
-                        rules.push(rule_map);
-                    }
-                } else {
-                    let refs = job_details["only"]["refs"].as_vec();
-                    if refs.is_some() {
-                        rules = vec![];
-                        for rule in refs.unwrap() {
-                            let mut rule_map = HashMap::new();
-                            rule_map.insert("only".to_string(), rule.clone());
-                            rules.push(rule_map);
-                        }
-                    }
+                // `only:` and `except:` cannot be used along with `rules:`
+                let only_except = only_except_rules(job_details);
+                if !only_except.is_empty() {
+                    rules = only_except;
                 }
                 // If job begins with dot, then it's a template.
                 if job_name.starts_with('.') {
                     continue;
                 }
-                let job = Job::new(&job_name, rules.clone());
+                let manual = job_details["when"].as_str() == Some("manual");
+                let mut job = Job::new(&job_name, rules.clone());
+                job.manual = manual;
                 let mut parallel_jobs = vec![];
                 // check if it's a parallel job
                 if let Some(parallel) = job_details["parallel"].as_hash() {
@@ -276,8 +268,10 @@ impl<T: ToCicdEntity> CicdParser for YamlParser<T> {
                     }
                     for val_matrix in all_values {
                         for val in val_matrix {
-                            parallel_jobs
-                                .push(Job::new(&format!("{}-{}", job_name, val), rules.clone()))
+                            let mut parallel_job =
+                                Job::new(&format!("{}-{}", job_name, val), rules.clone());
+                            parallel_job.manual = manual;
+                            parallel_jobs.push(parallel_job)
                         }
                     }
                 }
@@ -291,6 +285,71 @@ impl<T: ToCicdEntity> CicdParser for YamlParser<T> {
             }
         }
     }
+
+    fn get_workflow_rules(&self) -> Vec<HashMap<String, CicdEntity>> {
+        rule_maps(&self.parser.get(&Some(EntityName::Workflow))["rules"])
+    }
+}
+
+/// `only:` and `except:` as rules, one per ref or `variables:` expression. See
+/// `rules::evaluate` for the keys.
+fn only_except_rules(job_details: &CicdEntity) -> Vec<HashMap<String, CicdEntity>> {
+    let mut rules = vec![];
+    for keyword in ["only", "except"] {
+        let entity = &job_details[keyword];
+        let (refs, variables) = match entity {
+            CicdEntity::Vec(refs) => (refs.clone(), vec![]),
+            CicdEntity::String(_) => (vec![entity.clone()], vec![]),
+            _ => (
+                entity["refs"].as_vec().cloned().unwrap_or_default(),
+                entity["variables"].as_vec().cloned().unwrap_or_default(),
+            ),
+        };
+        for git_ref in refs {
+            rules.push(HashMap::from([(keyword.to_string(), git_ref)]));
+        }
+        for condition in variables {
+            rules.push(HashMap::from([(
+                format!("{}_variables", keyword),
+                condition,
+            )]));
+        }
+    }
+    rules
+}
+
+fn rule_maps(rules: &CicdEntity) -> Vec<HashMap<String, CicdEntity>> {
+    rules
+        .as_vec()
+        .map(|rules| {
+            rules
+                .iter()
+                .map(|rule| {
+                    if let Some(rule) = rule.as_hash() {
+                        let mut rule_map = HashMap::new();
+                        for (key, value) in rule.iter() {
+                            rule_map.insert(key.clone(), value.clone());
+                        }
+                        rule_map
+                    } else if let Some(rule) = rule.as_vec() {
+                        let mut rule_map = HashMap::new();
+                        for rule in rule {
+                            if let Some(rule) = rule.as_hash() {
+                                for (key, value) in rule {
+                                    let value = value.clone();
+                                    rule_map.insert(key.clone(), value);
+                                }
+                            }
+                        }
+                        rule_map
+                    } else {
+                        // empty rules
+                        HashMap::new()
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 fn combine_matrix_values(matrix: &CicdEntity) -> Vec<String> {
@@ -360,11 +419,121 @@ pub enum ChartType {
     Stages,
 }
 
+#[derive(Eq, PartialEq, Debug)]
+pub enum ChartFormat {
+    Mermaid,
+    /// The parsed stages, jobs and rules the chart is computed from.
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ChartCliArgs {
+    pub chart_type: ChartType,
+    pub format: ChartFormat,
+    /// Only chart the jobs a pipeline for this ref runs.
+    pub git_ref: Option<String>,
+}
+
+/// Stages with their jobs. Given an event, only the jobs its pipeline would
+/// run are kept, as decided by the `workflow:` rules and the rules of each
+/// job.
+fn parse_stages(parser: &impl CicdParser, event: Option<&PipelineEvent>) -> Result<StageMap> {
+    let mut stages = parser.get_stages()?;
+    parser.get_jobs(&mut stages);
+    let Some(event) = event else {
+        return Ok(stages);
+    };
+    // Without `workflow:` rules pipelines are created for all events.
+    let workflow_rules = parser.get_workflow_rules();
+    if !workflow_rules.is_empty() && rules::evaluate(&workflow_rules, event) == When::Never {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Workflow rules do not create a pipeline for {}",
+            event
+        ))
+        .into());
+    }
+    for stage in stages.stages.values_mut() {
+        stage
+            .jobs
+            .retain_mut(|job| match rules::evaluate(&job.rules, event) {
+                When::Never => false,
+                When::Manual => {
+                    job.manual = true;
+                    true
+                }
+                When::OnSuccess => true,
+            });
+    }
+    Ok(stages)
+}
+
+/// Job a simulated pipeline runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulatedJob {
+    pub stage: String,
+    pub name: String,
+    pub manual: bool,
+}
+
+impl From<SimulatedJob> for DisplayBody {
+    fn from(job: SimulatedJob) -> Self {
+        let when = if job.manual { "manual" } else { "on_success" };
+        DisplayBody::new(vec![
+            Column::new("Stage", job.stage),
+            Column::new("Job", job.name),
+            Column::new("When", when),
+        ])
+    }
+}
+
+/// Jobs a pipeline for the event would run, in stage order. Jobs in a stage
+/// are sorted by name.
+pub fn simulate_pipeline(
+    parser: impl CicdParser,
+    event: &PipelineEvent,
+) -> Result<Vec<SimulatedJob>> {
+    let mut stages = parse_stages(&parser, Some(event))?;
+    let mut simulated = vec![];
+    for name in stages.stage_names.iter() {
+        let Some(stage) = stages.stages.remove(name) else {
+            continue;
+        };
+        let mut jobs = stage.jobs;
+        jobs.sort_by(|a, b| a.name.cmp(&b.name));
+        simulated.extend(jobs.into_iter().map(|job| SimulatedJob {
+            stage: stage.name.clone(),
+            name: job.name,
+            manual: job.manual,
+        }));
+    }
+    Ok(simulated)
+}
+
+/// Generate the stages, in pipeline order, with their jobs and rules as a JSON
+/// document. Keys in rules are sorted, so the output is stable across runs.
+pub fn generate_json_model(
+    parser: impl CicdParser,
+    event: Option<&PipelineEvent>,
+) -> Result<String> {
+    let mut stages = parse_stages(&parser, event)?;
+    let stages = stages
+        .stage_names
+        .iter()
+        .filter_map(|name| stages.stages.remove(name))
+        // Same as in the diagrams, .pre and .post are only there with jobs
+        .filter(|stage| !((stage.name == ".pre" || stage.name == ".post") && stage.jobs.is_empty()))
+        .collect::<Vec<Stage>>();
+    let model = serde_json::json!({ "stages": stages });
+    Ok(serde_json::to_string_pretty(&model)?)
+}
+
 /// Generate a Mermaid state diagram with each stage encapsulating all its jobs
-/// and the links in between stages.
+/// and the links in between stages. Given an event, all the jobs left run in
+/// the same pipeline, so their rules no longer restrict the links.
 pub fn generate_mermaid_stages_diagram(
     parser: impl CicdParser,
     chart_type: ChartType,
+    event: Option<&PipelineEvent>,
 ) -> Result<Mermaid> {
     let mut mermaid = Mermaid::new();
 
@@ -378,9 +547,7 @@ pub fn generate_mermaid_stages_diagram(
         }
     }
 
-    let mut stages = parser.get_stages()?;
-
-    parser.get_jobs(&mut stages);
+    let stages = parse_stages(&parser, event)?;
 
     for (i, stage) in stages.stage_names.iter().enumerate() {
         let stage_obj = stages.stages.get(stage).unwrap();
@@ -400,7 +567,11 @@ pub fn generate_mermaid_stages_diagram(
             mermaid.push("        direction LR".to_string());
             mermaid.push(format!("        state \"jobs\" as {}", anchor_name));
             for job in jobs.iter() {
-                mermaid.push(format!("        state \"{}\" as {}", job.name, anchor_name));
+                mermaid.push(format!(
+                    "        state \"{}\" as {}",
+                    job.label(),
+                    anchor_name
+                ));
             }
             mermaid.push(format!("    {}", "}"));
         }
@@ -432,7 +603,7 @@ pub fn generate_mermaid_stages_diagram(
             let mut jobs_first_stage_compatible = false;
             for job in jobs.iter() {
                 for next_job in next_jobs.iter() {
-                    if rules_compatible(&job.rules, &next_job.rules) {
+                    if event.is_some() || rules_compatible(&job.rules, &next_job.rules) {
                         match chart_type {
                             ChartType::StagesWithJobs | ChartType::Stages => {
                                 mermaid.push(format!("    {} --> {}", stage_name, next_stage_name));
@@ -441,7 +612,7 @@ pub fn generate_mermaid_stages_diagram(
                             }
                             ChartType::Jobs => {
                                 jobs_first_stage_compatible = true;
-                                mermaid.push(format!("    {} --> {}", job.name, next_job.name));
+                                mermaid.push(format!("    {} --> {}", job.node(), next_job.node()));
                             }
                         }
                     }
@@ -579,7 +750,7 @@ mod tests {
                         .collect(),
                 ),
                 Some(EntityName::Job) => CicdEntity::Hash(self.jobs.clone()),
-                None => CicdEntity::Null,
+                Some(EntityName::Workflow) | None => CicdEntity::Null,
             }
         }
     }
@@ -983,6 +1154,7 @@ mod tests {
     struct MockParser {
         stages: Vec<String>,
         jobs: HashMap<String, Vec<MockJob>>,
+        workflow_rules: Vec<HashMap<String, CicdEntity>>,
     }
 
     struct MockJob {
@@ -1009,12 +1181,15 @@ mod tests {
                 }
             }
         }
+
+        fn get_workflow_rules(&self) -> Vec<HashMap<String, CicdEntity>> {
+            self.workflow_rules.clone()
+        }
     }
 
-    fn create_mock_parser(
-        stages: Vec<&str>,
-        jobs: Vec<(&str, Vec<(&str, Vec<HashMap<String, CicdEntity>>)>)>,
-    ) -> MockParser {
+    type MockJobSpecs<'a> = Vec<(&'a str, Vec<HashMap<String, CicdEntity>>)>;
+
+    fn create_mock_parser(stages: Vec<&str>, jobs: Vec<(&str, MockJobSpecs)>) -> MockParser {
         let stages = stages.into_iter().map(String::from).collect();
         let jobs = jobs
             .into_iter()
@@ -1031,7 +1206,11 @@ mod tests {
                 )
             })
             .collect();
-        MockParser { stages, jobs }
+        MockParser {
+            stages,
+            jobs,
+            workflow_rules: vec![],
+        }
     }
 
     #[test]
@@ -1048,7 +1227,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("stateDiagram-v2"));
@@ -1077,7 +1256,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("build --> deploy"));
@@ -1124,7 +1303,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("build --> test"));
@@ -1145,7 +1324,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("state .pre{"));
@@ -1172,7 +1351,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(!diagram.contains("state .pre{"));
@@ -1204,7 +1383,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::StagesWithJobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("state build_and_compile{"));
@@ -1230,7 +1409,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::Jobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::Jobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("graph LR"));
@@ -1283,7 +1462,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::Jobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::Jobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("graph LR"));
@@ -1308,7 +1487,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::Jobs)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::Jobs, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("graph LR"));
@@ -1338,7 +1517,7 @@ mod tests {
             ],
         );
 
-        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::Stages)?;
+        let mermaid = generate_mermaid_stages_diagram(parser, ChartType::Stages, None)?;
         let diagram = mermaid.to_string();
 
         assert!(diagram.contains("graph LR"));
@@ -1350,4 +1529,253 @@ mod tests {
 
         Ok(())
     }
+
+    fn if_rule(condition: &str, when: Option<&str>) -> HashMap<String, CicdEntity> {
+        let mut rule =
+            HashMap::from([("if".to_string(), CicdEntity::String(condition.to_string()))]);
+        if let Some(when) = when {
+            rule.insert("when".to_string(), CicdEntity::String(when.to_string()));
+        }
+        rule
+    }
+
+    #[test]
+    fn test_pipeline_simulated_for_ref_jobs_only() -> Result<()> {
+        let parser = create_mock_parser(
+            vec!["build", "test", "deploy"],
+            vec![
+                ("build", vec![("compile", vec![])]),
+                (
+                    "test",
+                    vec![("release-test", vec![if_rule("$CI_COMMIT_TAG", None)])],
+                ),
+                (
+                    "deploy",
+                    vec![(
+                        "production",
+                        vec![if_rule(
+                            "$CI_COMMIT_BRANCH == $CI_DEFAULT_BRANCH",
+                            Some("manual"),
+                        )],
+                    )],
+                ),
+            ],
+        );
+
+        let mermaid = generate_mermaid_stages_diagram(
+            parser,
+            ChartType::Jobs,
+            Some(&PipelineEvent::push("main")),
+        )?;
+        let diagram = mermaid.to_string();
+
+        assert!(diagram.contains("compile --> production[\"production (manual)\"]"));
+        assert!(!diagram.contains("release-test"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipeline_simulated_for_ref_annotates_manual_jobs() -> Result<()> {
+        let parser = create_mock_parser(
+            vec!["build", "deploy"],
+            vec![
+                ("build", vec![("compile", vec![])]),
+                (
+                    "deploy",
+                    vec![(
+                        "production",
+                        vec![
+                            if_rule("$CI_COMMIT_BRANCH =~ /^feature/", Some("never")),
+                            if_rule("$CI_COMMIT_BRANCH", Some("manual")),
+                        ],
+                    )],
+                ),
+            ],
+        );
+
+        let mermaid = generate_mermaid_stages_diagram(
+            parser,
+            ChartType::StagesWithJobs,
+            Some(&PipelineEvent::push("main")),
+        )?;
+        let diagram = mermaid.to_string();
+
+        assert!(diagram.contains("state \"production (manual)\" as anchorT1"));
+        assert!(diagram.contains("build --> deploy"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_workflow_rules_create_no_pipeline_for_ref() {
+        let mut parser =
+            create_mock_parser(vec!["build"], vec![("build", vec![("compile", vec![])])]);
+        parser.workflow_rules = vec![if_rule("$CI_COMMIT_BRANCH == \"main\"", None)];
+
+        let result = generate_mermaid_stages_diagram(
+            parser,
+            ChartType::Stages,
+            Some(&PipelineEvent::push("feature")),
+        );
+
+        match result {
+            Err(err) => match err.downcast_ref::<GRError>() {
+                Some(GRError::PreconditionNotMet(msg)) => assert!(msg.contains("feature")),
+                _ => panic!("Expected PreconditionNotMet"),
+            },
+            _ => panic!("Expected error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_manual_job() {
+        let mock = create_mock_cicd_entity(
+            vec!["deploy"],
+            vec![(
+                "production",
+                CicdEntity::Hash(HashMap::from([
+                    (
+                        "stage".to_string(),
+                        CicdEntity::String("deploy".to_string()),
+                    ),
+                    ("when".to_string(), CicdEntity::String("manual".to_string())),
+                ])),
+            )],
+        );
+
+        let parser = YamlParser::new(mock);
+        let mut stage_map = StageMap::new();
+        stage_map.insert("deploy".to_string(), Stage::new("deploy"));
+
+        parser.get_jobs(&mut stage_map);
+
+        assert!(stage_map.stages["deploy"].jobs[0].manual);
+    }
+
+    #[test]
+    fn test_json_model_with_rules() -> Result<()> {
+        let parser = create_mock_parser(
+            vec![".pre", "build", "deploy"],
+            vec![
+                ("build", vec![("compile", vec![])]),
+                (
+                    "deploy",
+                    vec![(
+                        "production",
+                        vec![HashMap::from([
+                            (
+                                "if".to_string(),
+                                CicdEntity::String("$CI_COMMIT_TAG".to_string()),
+                            ),
+                            ("when".to_string(), CicdEntity::String("manual".to_string())),
+                        ])],
+                    )],
+                ),
+            ],
+        );
+
+        let model: serde_json::Value = serde_json::from_str(&generate_json_model(parser, None)?)?;
+
+        assert_eq!(
+            serde_json::json!({
+                "stages": [
+                    {"name": "build", "jobs": [{"name": "compile", "rules": []}]},
+                    {"name": "deploy", "jobs": [{
+                        "name": "production",
+                        "rules": [{"if": "$CI_COMMIT_TAG", "when": "manual"}]
+                    }]}
+                ]
+            }),
+            model
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_job_with_except_and_only_variables() {
+        let mock = create_mock_cicd_entity(
+            vec!["test"],
+            vec![(
+                "unit",
+                CicdEntity::Hash(HashMap::from([
+                    ("stage".to_string(), CicdEntity::String("test".to_string())),
+                    (
+                        "only".to_string(),
+                        CicdEntity::Hash(HashMap::from([(
+                            "variables".to_string(),
+                            CicdEntity::Vec(vec![CicdEntity::String(
+                                "$CI_COMMIT_BRANCH".to_string(),
+                            )]),
+                        )])),
+                    ),
+                    (
+                        "except".to_string(),
+                        CicdEntity::Vec(vec![CicdEntity::String("main".to_string())]),
+                    ),
+                ])),
+            )],
+        );
+
+        let parser = YamlParser::new(mock);
+        let mut stage_map = StageMap::new();
+        stage_map.insert("test".to_string(), Stage::new("test"));
+
+        parser.get_jobs(&mut stage_map);
+
+        let rules = &stage_map.stages["test"].jobs[0].rules;
+        assert_eq!(2, rules.len());
+        assert_eq!(
+            Some("$CI_COMMIT_BRANCH"),
+            rules[0]["only_variables"].as_str()
+        );
+        assert_eq!(Some("main"), rules[1]["except"].as_str());
+    }
+
+    #[test]
+    fn test_simulate_merge_request_pipeline() -> Result<()> {
+        let only = |refs: &str| {
+            HashMap::from([("only".to_string(), CicdEntity::String(refs.to_string()))])
+        };
+        let parser = create_mock_parser(
+            vec!["build", "test", "deploy"],
+            vec![
+                ("build", vec![("compile", vec![])]),
+                (
+                    "test",
+                    vec![
+                        ("unit", vec![only("merge_requests")]),
+                        (
+                            "review",
+                            vec![if_rule(
+                                "$CI_PIPELINE_SOURCE == \"merge_request_event\"",
+                                Some("manual"),
+                            )],
+                        ),
+                    ],
+                ),
+                ("deploy", vec![("production", vec![only("main")])]),
+            ],
+        );
+
+        let jobs = simulate_pipeline(parser, &PipelineEvent::merge_request("feature/x"))?;
+
+        assert_eq!(
+            vec![
+                SimulatedJob {
+                    stage: "test".to_string(),
+                    name: "review".to_string(),
+                    manual: true,
+                },
+                SimulatedJob {
+                    stage: "test".to_string(),
+                    name: "unit".to_string(),
+                    manual: false,
+                },
+            ],
+            jobs
+        );
+        Ok(())
+    }
 }
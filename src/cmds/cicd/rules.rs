@@ -0,0 +1,509 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use regex::Regex;
+
+use super::mermaid::CicdEntity;
+
+/// Gitar cannot tell the default branch of the project from the CI file, the
+/// one of new Gitlab projects is assumed.
+const DEFAULT_BRANCH: &str = "main";
+
+/// Outcome of the rules of a job, or of the workflow, for a given ref.
+#[derive(Debug, PartialEq)]
+pub enum When {
+    OnSuccess,
+    Manual,
+    Never,
+}
+
+impl From<&CicdEntity> for When {
+    fn from(when: &CicdEntity) -> Self {
+        match when.as_str() {
+            Some("never") => When::Never,
+            Some("manual") => When::Manual,
+            _ => When::OnSuccess,
+        }
+    }
+}
+
+/// Keys of the rules converted from `only:` and `except:`. Refs are kept
+/// under `only` and `except`, `variables:` expressions under
+/// `only_variables` and `except_variables`.
+const ONLY_EXCEPT_KEYS: [&str; 4] = ["only", "except", "only_variables", "except_variables"];
+
+/// Pipeline the rules are evaluated for, either a push to a branch or a
+/// merge request from it targeting the default branch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PipelineEvent {
+    pub git_ref: String,
+    pub merge_request: bool,
+}
+
+impl PipelineEvent {
+    pub fn push(git_ref: &str) -> Self {
+        PipelineEvent {
+            git_ref: git_ref.to_string(),
+            merge_request: false,
+        }
+    }
+
+    pub fn merge_request(source_branch: &str) -> Self {
+        PipelineEvent {
+            git_ref: source_branch.to_string(),
+            merge_request: true,
+        }
+    }
+
+    /// Predefined CI/CD variables as set by Gitlab for the event. Merge
+    /// request pipelines have no `CI_COMMIT_BRANCH`.
+    fn variables(&self) -> HashMap<&'static str, String> {
+        let mut variables = HashMap::from([
+            ("CI_COMMIT_REF_NAME", self.git_ref.clone()),
+            ("CI_COMMIT_REF_SLUG", slug(&self.git_ref)),
+            ("CI_DEFAULT_BRANCH", DEFAULT_BRANCH.to_string()),
+        ]);
+        if self.merge_request {
+            variables.extend([
+                ("CI_PIPELINE_SOURCE", "merge_request_event".to_string()),
+                ("CI_MERGE_REQUEST_IID", "1".to_string()),
+                ("CI_MERGE_REQUEST_SOURCE_BRANCH_NAME", self.git_ref.clone()),
+                (
+                    "CI_MERGE_REQUEST_TARGET_BRANCH_NAME",
+                    DEFAULT_BRANCH.to_string(),
+                ),
+                ("CI_MERGE_REQUEST_EVENT_TYPE", "detached".to_string()),
+            ]);
+        } else {
+            variables.extend([
+                ("CI_PIPELINE_SOURCE", "push".to_string()),
+                ("CI_COMMIT_BRANCH", self.git_ref.clone()),
+            ]);
+        }
+        variables
+    }
+}
+
+impl Display for PipelineEvent {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.merge_request {
+            write!(f, "merge request from {}", self.git_ref)
+        } else {
+            write!(f, "ref {}", self.git_ref)
+        }
+    }
+}
+
+/// Evaluates `rules:` for a pipeline of the given event. The first rule whose
+/// `if:` holds decides, and no matching rule means the job is not added to
+/// the pipeline. Jobs without rules run as set by `only:` and `except:`.
+/// Conditions gitar cannot evaluate are considered true, as are `changes:`
+/// and `exists:`, so jobs are shown rather than dropped.
+pub fn evaluate(rules: &[HashMap<String, CicdEntity>], event: &PipelineEvent) -> When {
+    let variables = event.variables();
+    let only_except = rules
+        .iter()
+        .all(|rule| ONLY_EXCEPT_KEYS.iter().any(|key| rule.contains_key(*key)));
+    if only_except {
+        return evaluate_only_except(rules, event, &variables);
+    }
+    for rule in rules {
+        let holds = match rule.get("if").and_then(|condition| condition.as_str()) {
+            Some(condition) => eval_condition(condition, &variables).unwrap_or(true),
+            None => true,
+        };
+        if holds {
+            return rule.get("when").map(When::from).unwrap_or(When::OnSuccess);
+        }
+    }
+    When::Never
+}
+
+/// A job runs if any of its `only:` refs and any of its `only:variables`
+/// match, and none of its `except:` ones do. Without `only:`, jobs run for
+/// branches and tags, so not in merge request pipelines.
+fn evaluate_only_except(
+    rules: &[HashMap<String, CicdEntity>],
+    event: &PipelineEvent,
+    variables: &HashMap<&'static str, String>,
+) -> When {
+    let values = |key: &str| {
+        rules
+            .iter()
+            .filter_map(|rule| rule.get(key).and_then(|value| value.as_str()))
+            .collect::<Vec<&str>>()
+    };
+    let mut only_refs = values("only");
+    let only_variables = values("only_variables");
+    if only_refs.is_empty() && only_variables.is_empty() {
+        only_refs = vec!["branches", "tags"];
+    }
+    let runs = (only_refs.is_empty() || only_refs.iter().any(|only| only_matches(only, event)))
+        && (only_variables.is_empty()
+            || only_variables
+                .iter()
+                .any(|condition| eval_condition(condition, variables).unwrap_or(true)))
+        && !values("except")
+            .iter()
+            .any(|except| only_matches(except, event))
+        && !values("except_variables")
+            .iter()
+            .any(|condition| eval_condition(condition, variables).unwrap_or(false));
+    if runs {
+        When::OnSuccess
+    } else {
+        When::Never
+    }
+}
+
+fn slug(git_ref: &str) -> String {
+    git_ref
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
+
+/// `only:` and `except:` refs, ex. `main`, `branches`, `merge_requests` or
+/// `/^release-.*$/`. Branch names only match branch pipelines, merge request
+/// pipelines run for the `merge_requests` keyword alone.
+fn only_matches(only: &str, event: &PipelineEvent) -> bool {
+    match only {
+        "branches" | "pushes" => !event.merge_request,
+        "merge_requests" => event.merge_request,
+        "tags" | "schedules" | "api" | "web" | "triggers" | "external_pull_requests" => false,
+        _ if event.merge_request => false,
+        only if only.starts_with('/') => {
+            regex_literal(only).is_some_and(|regex| regex.is_match(&event.git_ref))
+        }
+        only => only == event.git_ref,
+    }
+}
+
+/// Builds the regex of a `/pattern/flags` literal.
+fn regex_literal(literal: &str) -> Option<Regex> {
+    let literal = literal.strip_prefix('/')?;
+    let end = literal.rfind('/')?;
+    let (pattern, flags) = (&literal[..end], &literal[end + 1..]);
+    let pattern = if flags.contains('i') {
+        format!("(?i){}", pattern)
+    } else {
+        pattern.to_string()
+    };
+    Regex::new(&pattern).ok()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Variable(String),
+    String(String),
+    Regex(String),
+    Null,
+    Equal,
+    NotEqual,
+    Matches,
+    NotMatches,
+    And,
+    Or,
+    OpenParen,
+    CloseParen,
+}
+
+fn tokenize(condition: &str) -> Option<Vec<Token>> {
+    let chars = condition.chars().collect::<Vec<char>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match (c, next) {
+            (c, _) if c.is_whitespace() => i += 1,
+            ('$', _) => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Variable(chars[start..i].iter().collect()));
+            }
+            ('"' | '\'', _) => {
+                let end = chars[i + 1..].iter().position(|&q| q == c)? + i + 1;
+                tokens.push(Token::String(chars[i + 1..end].iter().collect()));
+                i = end + 1;
+            }
+            ('/', _) => {
+                let mut end = i + 1;
+                while end < chars.len() && (chars[end] != '/' || chars[end - 1] == '\\') {
+                    end += 1;
+                }
+                if end == chars.len() {
+                    return None;
+                }
+                end += 1;
+                while end < chars.len() && chars[end].is_ascii_alphabetic() {
+                    end += 1;
+                }
+                tokens.push(Token::Regex(chars[i..end].iter().collect()));
+                i = end;
+            }
+            ('=', Some('=')) => push_operator(&mut tokens, Token::Equal, &mut i),
+            ('!', Some('=')) => push_operator(&mut tokens, Token::NotEqual, &mut i),
+            ('=', Some('~')) => push_operator(&mut tokens, Token::Matches, &mut i),
+            ('!', Some('~')) => push_operator(&mut tokens, Token::NotMatches, &mut i),
+            ('&', Some('&')) => push_operator(&mut tokens, Token::And, &mut i),
+            ('|', Some('|')) => push_operator(&mut tokens, Token::Or, &mut i),
+            ('(', _) => {
+                tokens.push(Token::OpenParen);
+                i += 1;
+            }
+            (')', _) => {
+                tokens.push(Token::CloseParen);
+                i += 1;
+            }
+            _ if chars[i..].starts_with(&['n', 'u', 'l', 'l']) => {
+                tokens.push(Token::Null);
+                i += 4;
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+fn push_operator(tokens: &mut Vec<Token>, token: Token, i: &mut usize) {
+    tokens.push(token);
+    *i += 2;
+}
+
+enum Operand {
+    /// Value of a string or a variable. Undefined variables and `null` have
+    /// none.
+    Value(Option<String>),
+    Regex(String),
+}
+
+/// Recursive descent evaluator of `if:` expressions. `&&` binds tighter than
+/// `||` as in Gitlab.
+struct Condition<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    variables: &'a HashMap<&'static str, String>,
+}
+
+/// Evaluates an `if:` expression. None if the expression is not supported.
+fn eval_condition(condition: &str, variables: &HashMap<&'static str, String>) -> Option<bool> {
+    let mut condition = Condition {
+        tokens: tokenize(condition)?,
+        position: 0,
+        variables,
+    };
+    let result = condition.or()?;
+    if condition.position != condition.tokens.len() {
+        return None;
+    }
+    Some(result)
+}
+
+impl Condition<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn or(&mut self) -> Option<bool> {
+        let mut result = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            // Evaluated regardless of the result to consume the tokens.
+            let right = self.and()?;
+            result = result || right;
+        }
+        Some(result)
+    }
+
+    fn and(&mut self) -> Option<bool> {
+        let mut result = self.primary()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            let right = self.primary()?;
+            result = result && right;
+        }
+        Some(result)
+    }
+
+    fn primary(&mut self) -> Option<bool> {
+        if self.peek() == Some(&Token::OpenParen) {
+            self.position += 1;
+            let result = self.or()?;
+            if self.peek() != Some(&Token::CloseParen) {
+                return None;
+            }
+            self.position += 1;
+            return Some(result);
+        }
+        let left = self.operand()?;
+        let operator = match self.peek() {
+            Some(Token::Equal | Token::NotEqual | Token::Matches | Token::NotMatches) => {
+                self.peek().cloned()
+            }
+            _ => None,
+        };
+        let Some(operator) = operator else {
+            return match left {
+                Operand::Value(value) => Some(value.is_some_and(|value| !value.is_empty())),
+                Operand::Regex(_) => None,
+            };
+        };
+        self.position += 1;
+        let right = self.operand()?;
+        match (operator, left, right) {
+            (Token::Equal, Operand::Value(left), Operand::Value(right)) => Some(left == right),
+            (Token::NotEqual, Operand::Value(left), Operand::Value(right)) => Some(left != right),
+            (Token::Matches, Operand::Value(left), right) => Some(regex_matches(left, right)?),
+            (Token::NotMatches, Operand::Value(left), right) => Some(!regex_matches(left, right)?),
+            _ => None,
+        }
+    }
+
+    fn operand(&mut self) -> Option<Operand> {
+        let operand = match self.peek()? {
+            Token::Variable(name) => Operand::Value(self.variables.get(name.as_str()).cloned()),
+            Token::String(value) => Operand::Value(Some(value.clone())),
+            Token::Regex(regex) => Operand::Regex(regex.clone()),
+            Token::Null => Operand::Value(None),
+            _ => return None,
+        };
+        self.position += 1;
+        Some(operand)
+    }
+}
+
+/// Right hand side of `=~` can be a regex literal or a variable holding one.
+fn regex_matches(value: Option<String>, regex: Operand) -> Option<bool> {
+    let regex = match regex {
+        Operand::Regex(regex) => regex,
+        Operand::Value(Some(regex)) => regex,
+        Operand::Value(None) => return None,
+    };
+    let regex = regex_literal(&regex)?;
+    Some(value.is_some_and(|value| regex.is_match(&value)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rule(condition: Option<&str>, when: Option<&str>) -> HashMap<String, CicdEntity> {
+        let mut rule = HashMap::new();
+        if let Some(condition) = condition {
+            rule.insert("if".to_string(), CicdEntity::String(condition.to_string()));
+        }
+        if let Some(when) = when {
+            rule.insert("when".to_string(), CicdEntity::String(when.to_string()));
+        }
+        rule
+    }
+
+    fn eval(condition: &str, git_ref: &str) -> Option<bool> {
+        eval_condition(condition, &PipelineEvent::push(git_ref).variables())
+    }
+
+    fn push(git_ref: &str) -> PipelineEvent {
+        PipelineEvent::push(git_ref)
+    }
+
+    fn keyword(key: &str, value: &str) -> HashMap<String, CicdEntity> {
+        HashMap::from([(key.to_string(), CicdEntity::String(value.to_string()))])
+    }
+
+    #[test]
+    fn test_eval_conditions() {
+        assert_eq!(Some(true), eval("$CI_COMMIT_BRANCH == \"main\"", "main"));
+        assert_eq!(Some(false), eval("$CI_COMMIT_BRANCH != 'main'", "main"));
+        assert_eq!(
+            Some(true),
+            eval("$CI_COMMIT_BRANCH == $CI_DEFAULT_BRANCH", "main")
+        );
+        assert_eq!(Some(false), eval("$CI_COMMIT_TAG", "main"));
+        assert_eq!(Some(true), eval("$CI_COMMIT_TAG == null", "main"));
+        assert_eq!(
+            Some(true),
+            eval("$CI_COMMIT_REF_NAME =~ /^Release-.*$/i", "release-1.0")
+        );
+        assert_eq!(
+            Some(true),
+            eval(
+                "$CI_COMMIT_TAG || ($CI_PIPELINE_SOURCE == \"push\" && $CI_COMMIT_BRANCH !~ /^wip/)",
+                "feature"
+            )
+        );
+        assert_eq!(None, eval("$CI_COMMIT_BRANCH == ", "main"));
+        assert_eq!(None, eval("$CI_COMMIT_BRANCH === \"main\"", "main"));
+    }
+
+    #[test]
+    fn test_first_matching_rule_decides() {
+        let rules = vec![
+            rule(Some("$CI_COMMIT_BRANCH == \"main\""), Some("manual")),
+            rule(Some("$CI_COMMIT_BRANCH =~ /^feature/"), Some("never")),
+            rule(None, None),
+        ];
+        assert_eq!(When::Manual, evaluate(&rules, &push("main")));
+        assert_eq!(When::Never, evaluate(&rules, &push("feature-x")));
+        assert_eq!(When::OnSuccess, evaluate(&rules, &push("fix")));
+    }
+
+    #[test]
+    fn test_no_matching_rule_never_runs() {
+        let rules = vec![rule(Some("$CI_COMMIT_TAG"), None)];
+        assert_eq!(When::Never, evaluate(&rules, &push("main")));
+        assert_eq!(When::OnSuccess, evaluate(&[], &push("main")));
+    }
+
+    #[test]
+    fn test_only_refs() {
+        let only = |refs: &str| keyword("only", refs);
+        let rules = vec![only("main"), only("/^release-/")];
+        assert_eq!(When::OnSuccess, evaluate(&rules, &push("main")));
+        assert_eq!(When::OnSuccess, evaluate(&rules, &push("release-2")));
+        assert_eq!(When::Never, evaluate(&rules, &push("feature")));
+        assert_eq!(When::Never, evaluate(&[only("tags")], &push("main")));
+    }
+
+    #[test]
+    fn test_merge_request_pipeline() {
+        let mr = PipelineEvent::merge_request("feature");
+        // Jobs without rules run for branches and tags only.
+        assert_eq!(When::Never, evaluate(&[], &mr));
+        assert_eq!(
+            When::OnSuccess,
+            evaluate(&[keyword("only", "merge_requests")], &mr)
+        );
+        assert_eq!(When::Never, evaluate(&[keyword("only", "feature")], &mr));
+        let rules = vec![rule(
+            Some("$CI_PIPELINE_SOURCE == \"merge_request_event\""),
+            None,
+        )];
+        assert_eq!(When::OnSuccess, evaluate(&rules, &mr));
+        assert_eq!(When::Never, evaluate(&rules, &push("feature")));
+        let rules = vec![rule(Some("$CI_COMMIT_BRANCH"), None)];
+        assert_eq!(When::Never, evaluate(&rules, &mr));
+    }
+
+    #[test]
+    fn test_except_refs_and_variables() {
+        let rules = vec![keyword("except", "main")];
+        assert_eq!(When::Never, evaluate(&rules, &push("main")));
+        assert_eq!(When::OnSuccess, evaluate(&rules, &push("feature")));
+        let rules = vec![
+            keyword("only", "branches"),
+            keyword("except_variables", "$CI_COMMIT_REF_NAME =~ /^docs-/"),
+        ];
+        assert_eq!(When::Never, evaluate(&rules, &push("docs-intro")));
+        assert_eq!(When::OnSuccess, evaluate(&rules, &push("feature")));
+        let rules = vec![keyword("only_variables", "$CI_COMMIT_BRANCH == \"main\"")];
+        assert_eq!(When::OnSuccess, evaluate(&rules, &push("main")));
+        assert_eq!(When::Never, evaluate(&rules, &push("feature")));
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::display::{Column, DisplayBody};
+
+/// Time a job waited for a runner and then ran in a pipeline.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct JobTiming {
+    pub pipeline_id: i64,
+    pub name: String,
+    /// Seconds since the job was ready to run until a runner picked it up.
+    pub queued: f64,
+    /// Seconds the job ran for.
+    pub duration: f64,
+    /// Runner that ran the job. Empty if the remote does not tell.
+    #[builder(default)]
+    pub runner: String,
+}
+
+impl JobTiming {
+    pub fn builder() -> JobTimingBuilder {
+        JobTimingBuilder::default()
+    }
+}
+
+/// Queue and run times of all the runs of a job.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobStats {
+    pub name: String,
+    pub runs: usize,
+    pub avg_queued: f64,
+    pub max_queued: f64,
+    pub avg_duration: f64,
+    pub max_duration: f64,
+    /// Share of the total time of the job spent waiting for a runner.
+    pub queued_ratio: f64,
+    pub runners: usize,
+}
+
+fn seconds(seconds: f64) -> String {
+    format!("{:.0}", seconds)
+}
+
+impl From<JobStats> for DisplayBody {
+    fn from(stats: JobStats) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Job", stats.name),
+                Column::count("Runs", stats.runs as u64),
+                Column::duration("Avg queued", seconds(stats.avg_queued)),
+                Column::duration("Max queued", seconds(stats.max_queued)),
+                Column::duration("Avg duration", seconds(stats.avg_duration)),
+                Column::duration("Max duration", seconds(stats.max_duration)),
+                Column::new("Queued", format!("{:.0}%", stats.queued_ratio * 100.0)),
+                Column::new("Runners", stats.runners.to_string()),
+            ],
+        }
+    }
+}
+
+/// Aggregates the timings per job name. Jobs waiting the longest on average
+/// come first, as they point to a lack of runners for them. Ties are broken
+/// by job name, so the report is stable across runs.
+pub fn summarize(timings: Vec<JobTiming>) -> Vec<JobStats> {
+    let mut by_job: HashMap<String, Vec<JobTiming>> = HashMap::new();
+    for timing in timings {
+        by_job.entry(timing.name.clone()).or_default().push(timing);
+    }
+    let mut stats = by_job
+        .into_iter()
+        .map(|(name, timings)| {
+            let runs = timings.len();
+            let total_queued = timings.iter().map(|timing| timing.queued).sum::<f64>();
+            let total_duration = timings.iter().map(|timing| timing.duration).sum::<f64>();
+            let total = total_queued + total_duration;
+            let runners = timings
+                .iter()
+                .filter(|timing| !timing.runner.is_empty())
+                .map(|timing| timing.runner.as_str())
+                .collect::<HashSet<_>>()
+                .len();
+            JobStats {
+                name,
+                runs,
+                avg_queued: total_queued / runs as f64,
+                max_queued: timings
+                    .iter()
+                    .map(|timing| timing.queued)
+                    .fold(0.0, f64::max),
+                avg_duration: total_duration / runs as f64,
+                max_duration: timings
+                    .iter()
+                    .map(|timing| timing.duration)
+                    .fold(0.0, f64::max),
+                queued_ratio: if total > 0.0 {
+                    total_queued / total
+                } else {
+                    0.0
+                },
+                runners,
+            }
+        })
+        .collect::<Vec<_>>();
+    stats.sort_by(|a, b| {
+        b.avg_queued
+            .total_cmp(&a.avg_queued)
+            .then(a.name.cmp(&b.name))
+    });
+    stats
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn timing(pipeline_id: i64, name: &str, queued: f64, duration: f64, runner: &str) -> JobTiming {
+        JobTiming::builder()
+            .pipeline_id(pipeline_id)
+            .name(name.to_string())
+            .queued(queued)
+            .duration(duration)
+            .runner(runner.to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_summarize_ranks_by_average_queue_time() {
+        let timings = vec![
+            timing(1, "build", 10.0, 100.0, "runner-1"),
+            timing(2, "build", 30.0, 140.0, "runner-2"),
+            timing(1, "deploy", 300.0, 60.0, "runner-1"),
+            timing(1, "lint", 1.0, 5.0, ""),
+        ];
+        let stats = summarize(timings);
+        assert_eq!(
+            vec!["deploy", "build", "lint"],
+            stats.iter().map(|s| s.name.as_str()).collect::<Vec<_>>()
+        );
+        let build = &stats[1];
+        assert_eq!(2, build.runs);
+        assert_eq!(20.0, build.avg_queued);
+        assert_eq!(30.0, build.max_queued);
+        assert_eq!(120.0, build.avg_duration);
+        assert_eq!(140.0, build.max_duration);
+        assert_eq!(2, build.runners);
+        assert_eq!(0, stats[2].runners);
+    }
+
+    #[test]
+    fn test_job_stats_display() {
+        let stats = summarize(vec![timing(1, "deploy", 300.0, 60.0, "runner-1")]);
+        let body: DisplayBody = stats[0].clone().into();
+        assert_eq!(
+            vec!["deploy", "1", "300", "300", "60", "60", "83%", "1"],
+            body.columns
+                .iter()
+                .map(|column| column.value.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Status of the latest pipeline of a branch or commit. When following, the
+//! pipeline is polled and every job status change is printed until the
+//! pipeline finishes.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::api_traits::CicdPipelineStatus;
+use crate::display;
+use crate::error::GRError;
+use crate::http::throttle::ThrottleStrategy;
+use crate::remote::GetRemoteCliArgs;
+use crate::time::Seconds;
+use crate::Result;
+
+use super::{Job, Pipeline};
+
+/// Git reference the pipeline ran for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PipelineRef {
+    Branch(String),
+    Sha(String),
+}
+
+impl Display for PipelineRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipelineRef::Branch(branch) => write!(f, "branch {}", branch),
+            PipelineRef::Sha(sha) => write!(f, "commit {}", sha),
+        }
+    }
+}
+
+/// A pipeline and its jobs. In Github, all the workflow runs of a commit
+/// make up the pipeline.
+#[derive(Builder, Clone)]
+pub struct PipelineStatus {
+    pub pipeline: Pipeline,
+    #[builder(default)]
+    pub jobs: Vec<Job>,
+}
+
+impl PipelineStatus {
+    pub fn builder() -> PipelineStatusBuilder {
+        PipelineStatusBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct PipelineStatusCliArgs {
+    /// Commit SHA. The current branch is used when not given.
+    #[builder(default)]
+    pub sha: Option<String>,
+    /// Keep polling and print job status changes until the pipeline
+    /// finishes.
+    #[builder(default)]
+    pub follow: bool,
+    #[builder(default = "Seconds::new(10)")]
+    pub interval: Seconds,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl PipelineStatusCliArgs {
+    pub fn builder() -> PipelineStatusCliArgsBuilder {
+        PipelineStatusCliArgsBuilder::default()
+    }
+}
+
+/// Whether a pipeline or job is still to run or running. Covers Gitlab
+/// statuses and Github statuses before they conclude.
+pub fn is_active(status: &str) -> bool {
+    matches!(
+        status,
+        "created"
+            | "pending"
+            | "preparing"
+            | "running"
+            | "scheduled"
+            | "waiting_for_resource"
+            | "queued"
+            | "in_progress"
+            | "waiting"
+            | "requested"
+    )
+}
+
+/// Whether a finished pipeline or job did not succeed. Gitlab and Github
+/// conclusions.
+pub fn is_failed(status: &str) -> bool {
+    matches!(
+        status,
+        "failed" | "failure" | "canceled" | "cancelled" | "timed_out" | "startup_failure"
+    )
+}
+
+/// Prints the status of the latest pipeline of `git_ref`. Returns an error
+/// if the pipeline failed, so scripts can wait on it.
+pub fn pipeline_status<W: Write>(
+    remote: Arc<dyn CicdPipelineStatus>,
+    git_ref: PipelineRef,
+    cli_args: PipelineStatusCliArgs,
+    throttler: &dyn ThrottleStrategy,
+    mut writer: W,
+) -> Result<()> {
+    if !cli_args.follow {
+        let status = remote.pipeline_status(&git_ref)?.ok_or_else(|| {
+            GRError::PreconditionNotMet(format!("No pipeline found for {}", git_ref))
+        })?;
+        let pipeline = status.pipeline.clone();
+        display::print_one(&mut writer, status.pipeline, cli_args.get_args.clone())?;
+        if !status.jobs.is_empty() {
+            writeln!(writer)?;
+            display::print(&mut writer, status.jobs, cli_args.get_args)?;
+        }
+        return check_finished(&pipeline);
+    }
+    let mut waiting = false;
+    let mut pipeline_status = String::new();
+    let mut job_status: HashMap<i64, String> = HashMap::new();
+    loop {
+        match remote.pipeline_status(&git_ref)? {
+            // The pipeline might not have been created yet, ex. right after
+            // pushing or opening a merge request.
+            None => {
+                if !waiting {
+                    writeln!(writer, "Waiting for a pipeline for {}", git_ref)?;
+                    waiting = true;
+                }
+            }
+            Some(status) => {
+                let pipeline = status.pipeline;
+                if pipeline.status != pipeline_status {
+                    writeln!(
+                        writer,
+                        "Pipeline {} {}: {}",
+                        pipeline.id, pipeline.status, pipeline.web_url
+                    )?;
+                    pipeline_status = pipeline.status.clone();
+                }
+                for job in status.jobs {
+                    if job_status.get(&job.id) != Some(&job.status) {
+                        writeln!(writer, "  [{}] {}: {}", job.stage, job.name, job.status)?;
+                        job_status.insert(job.id, job.status);
+                    }
+                }
+                writer.flush()?;
+                if !is_active(&pipeline.status) {
+                    return check_finished(&pipeline);
+                }
+            }
+        }
+        throttler.throttle_for(cli_args.interval.into());
+    }
+}
+
+fn check_finished(pipeline: &Pipeline) -> Result<()> {
+    if is_failed(&pipeline.status) {
+        return Err(GRError::PipelineFailed(format!(
+            "{} {}: {}",
+            pipeline.id, pipeline.status, pipeline.web_url
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::test::utils::MockThrottler;
+
+    struct PipelineStatusMock {
+        statuses: Vec<Option<PipelineStatus>>,
+        calls: RefCell<usize>,
+    }
+
+    impl PipelineStatusMock {
+        fn new(statuses: Vec<Option<PipelineStatus>>) -> Arc<Self> {
+            Arc::new(PipelineStatusMock {
+                statuses,
+                calls: RefCell::new(0),
+            })
+        }
+    }
+
+    impl CicdPipelineStatus for PipelineStatusMock {
+        fn pipeline_status(&self, git_ref: &PipelineRef) -> Result<Option<PipelineStatus>> {
+            assert_eq!(&PipelineRef::Branch("main".to_string()), git_ref);
+            let mut calls = self.calls.borrow_mut();
+            let status = self.statuses[(*calls).min(self.statuses.len() - 1)].clone();
+            *calls += 1;
+            Ok(status)
+        }
+    }
+
+    fn job(id: i64, name: &str, status: &str) -> Job {
+        Job::builder()
+            .id(id)
+            .name(name.to_string())
+            .branch("main".to_string())
+            .url(format!("https://gitlab.com/owner/repo/-/jobs/{}", id))
+            .author_name("user1".to_string())
+            .commit_sha("1234567890abcdef".to_string())
+            .pipeline_id(10)
+            .runner_tags(vec![])
+            .stage("test".to_string())
+            .status(status.to_string())
+            .created_at(Some("2024-01-01T00:00:00Z".parse().unwrap()))
+            .started_at(Some("2024-01-01T00:01:00Z".parse().unwrap()))
+            .finished_at(Some("2024-01-01T00:02:00Z".parse().unwrap()))
+            .duration("60".to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn status(pipeline_status: &str, jobs: Vec<Job>) -> Option<PipelineStatus> {
+        let pipeline = Pipeline::builder()
+            .id(10)
+            .status(pipeline_status.to_string())
+            .web_url("https://gitlab.com/owner/repo/-/pipelines/10".to_string())
+            .branch("main".to_string())
+            .sha("1234567890abcdef".to_string())
+            .created_at("2024-01-01T00:00:00Z".parse().unwrap())
+            .updated_at("2024-01-01T00:02:00Z".parse().unwrap())
+            .duration(120)
+            .build()
+            .unwrap();
+        Some(
+            PipelineStatus::builder()
+                .pipeline(pipeline)
+                .jobs(jobs)
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn status_args(follow: bool) -> PipelineStatusCliArgs {
+        PipelineStatusCliArgs::builder()
+            .follow(follow)
+            .interval(Seconds::new(3))
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn main_branch() -> PipelineRef {
+        PipelineRef::Branch("main".to_string())
+    }
+
+    #[test]
+    fn test_follow_prints_status_changes_until_pipeline_finishes() {
+        let remote = PipelineStatusMock::new(vec![
+            None,
+            status(
+                "running",
+                vec![job(1, "lint", "running"), job(2, "unit", "pending")],
+            ),
+            status(
+                "running",
+                vec![job(1, "lint", "success"), job(2, "unit", "pending")],
+            ),
+            status(
+                "success",
+                vec![job(1, "lint", "success"), job(2, "unit", "success")],
+            ),
+        ]);
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        pipeline_status(
+            remote,
+            main_branch(),
+            status_args(true),
+            &throttler,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            "Waiting for a pipeline for branch main\n\
+             Pipeline 10 running: https://gitlab.com/owner/repo/-/pipelines/10\n\
+             \x20 [test] lint: running\n\
+             \x20 [test] unit: pending\n\
+             \x20 [test] lint: success\n\
+             Pipeline 10 success: https://gitlab.com/owner/repo/-/pipelines/10\n\
+             \x20 [test] unit: success\n",
+            String::from_utf8(buf).unwrap()
+        );
+        assert_eq!(3, *throttler.throttled());
+    }
+
+    #[test]
+    fn test_follow_failed_pipeline_is_error() {
+        let remote = PipelineStatusMock::new(vec![
+            status("running", vec![job(1, "lint", "running")]),
+            status("failed", vec![job(1, "lint", "failed")]),
+        ]);
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        let Err(err) = pipeline_status(
+            remote,
+            main_branch(),
+            status_args(true),
+            &throttler,
+            &mut buf,
+        ) else {
+            panic!("Expected the pipeline to fail");
+        };
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PipelineFailed(msg)) => assert_eq!(
+                "10 failed: https://gitlab.com/owner/repo/-/pipelines/10",
+                msg
+            ),
+            _ => panic!("Expected PipelineFailed"),
+        }
+    }
+
+    #[test]
+    fn test_status_without_follow_prints_once() {
+        let remote =
+            PipelineStatusMock::new(vec![status("running", vec![job(1, "lint", "running")])]);
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        pipeline_status(
+            remote,
+            main_branch(),
+            status_args(false),
+            &throttler,
+            &mut buf,
+        )
+        .unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.starts_with("ID|URL|Branch|SHA|"));
+        assert!(output.contains("|running\n\n"));
+        assert!(output.contains("lint"));
+        assert_eq!(0, *throttler.throttled());
+    }
+
+    #[test]
+    fn test_status_without_pipeline_is_error() {
+        let remote = PipelineStatusMock::new(vec![None]);
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        assert!(pipeline_status(
+            remote,
+            main_branch(),
+            status_args(false),
+            &throttler,
+            &mut buf
+        )
+        .is_err());
+    }
+}
@@ -1,8 +1,13 @@
 use std::collections::HashMap;
 
-use yaml_rust2::Yaml;
+use yaml_rust2::{yaml::Hash, Yaml};
 
 use super::mermaid::{CicdEntity, EntityName, ToCicdEntity};
+use crate::{error::GRError, Result};
+
+/// Same limits Gitlab applies to nested includes and to extends chains.
+const MAX_INCLUDE_DEPTH: usize = 100;
+const MAX_EXTENDS_DEPTH: usize = 11;
 
 impl ToCicdEntity for Yaml {
     fn get(&self, entity_name: &Option<EntityName>) -> CicdEntity {
@@ -10,6 +15,7 @@ impl ToCicdEntity for Yaml {
             match entity_name {
                 EntityName::Stage => &self["stages"],
                 EntityName::Job => self,
+                EntityName::Workflow => &self["workflow"],
             }
         } else {
             self
@@ -46,6 +52,137 @@ pub fn load_yaml(yaml: &str) -> Yaml {
         .unwrap()
 }
 
+/// Resolves the `include:` and `extends:` keywords of a CI configuration, so
+/// jobs get the stage and rules they inherit. Local includes are read with
+/// `read_local`, given the path relative to the root of the repository.
+/// Remote, project and template includes are not fetched.
+pub fn resolve(yaml: Yaml, read_local: &impl Fn(&str) -> Result<String>) -> Result<Yaml> {
+    let yaml = resolve_includes(yaml, read_local, 0)?;
+    resolve_extends(yaml)
+}
+
+fn key(name: &str) -> Yaml {
+    Yaml::String(name.to_string())
+}
+
+fn resolve_includes(
+    yaml: Yaml,
+    read_local: &impl Fn(&str) -> Result<String>,
+    depth: usize,
+) -> Result<Yaml> {
+    let Yaml::Hash(mut config) = yaml else {
+        return Ok(yaml);
+    };
+    let Some(include) = config.remove(&key("include")) else {
+        return Ok(Yaml::Hash(config));
+    };
+    if depth >= MAX_INCLUDE_DEPTH {
+        return Err(GRError::MermaidParsingError(format!(
+            "More than {} nested includes",
+            MAX_INCLUDE_DEPTH
+        ))
+        .into());
+    }
+    let entries = match include {
+        Yaml::Array(entries) => entries,
+        entry => vec![entry],
+    };
+    let mut included = Yaml::Hash(Hash::new());
+    for entry in entries {
+        let path = match &entry {
+            Yaml::String(path) if !path.starts_with("http") => Some(path.as_str()),
+            Yaml::Hash(entry) => entry.get(&key("local")).and_then(|path| path.as_str()),
+            _ => None,
+        };
+        match path {
+            Some(path) if !path.contains('*') => {
+                let content = read_local(path.trim_start_matches('/'))?;
+                let yaml = yaml_rust2::YamlLoader::load_from_str(&content)
+                    .map_err(|err| {
+                        GRError::MermaidParsingError(format!("Included file {}: {}", path, err))
+                    })?
+                    .pop()
+                    .unwrap_or(Yaml::Hash(Hash::new()));
+                let yaml = resolve_includes(yaml, read_local, depth + 1)?;
+                included = deep_merge(included, yaml);
+            }
+            _ => eprintln!(
+                "Warning: only local includes are resolved. Skipping include {:?}",
+                entry
+            ),
+        }
+    }
+    // The including file takes precedence over the files it includes.
+    Ok(deep_merge(included, Yaml::Hash(config)))
+}
+
+fn resolve_extends(yaml: Yaml) -> Result<Yaml> {
+    let Yaml::Hash(config) = &yaml else {
+        return Ok(yaml);
+    };
+    let mut resolved = Hash::new();
+    for (name, job) in config {
+        resolved.insert(name.clone(), extend(config, name, job, 0)?);
+    }
+    Ok(Yaml::Hash(resolved))
+}
+
+/// Merges the jobs `job` extends, in order, and then the job itself on top.
+fn extend(config: &Hash, name: &Yaml, job: &Yaml, depth: usize) -> Result<Yaml> {
+    let Yaml::Hash(job_keys) = job else {
+        return Ok(job.clone());
+    };
+    let parents = match job_keys.get(&key("extends")) {
+        Some(Yaml::String(parent)) => vec![parent.as_str()],
+        Some(Yaml::Array(parents)) => parents.iter().filter_map(|p| p.as_str()).collect(),
+        _ => return Ok(job.clone()),
+    };
+    let name = name.as_str().unwrap_or_default();
+    if depth >= MAX_EXTENDS_DEPTH {
+        return Err(GRError::MermaidParsingError(format!(
+            "Job {} extends more than {} levels deep",
+            name, MAX_EXTENDS_DEPTH
+        ))
+        .into());
+    }
+    let mut extended = Yaml::Hash(Hash::new());
+    for parent in parents {
+        let parent_name = key(parent);
+        let parent_job = config.get(&parent_name).ok_or_else(|| {
+            GRError::MermaidParsingError(format!("Job {} extends unknown job {}", name, parent))
+        })?;
+        extended = deep_merge(
+            extended,
+            extend(config, &parent_name, parent_job, depth + 1)?,
+        );
+    }
+    let mut job_keys = job_keys.clone();
+    job_keys.remove(&key("extends"));
+    Ok(deep_merge(extended, Yaml::Hash(job_keys)))
+}
+
+/// Merges `over` into `base`. Mappings are merged key by key, any other value
+/// in `over`, arrays included, replaces the one in `base`.
+fn deep_merge(base: Yaml, over: Yaml) -> Yaml {
+    match (base, over) {
+        (Yaml::Hash(mut base), Yaml::Hash(over)) => {
+            for (name, value) in over {
+                match base.get_mut(&name) {
+                    Some(base_value) => {
+                        let merged = deep_merge(std::mem::replace(base_value, Yaml::Null), value);
+                        *base_value = merged;
+                    }
+                    None => {
+                        base.insert(name, value);
+                    }
+                }
+            }
+            Yaml::Hash(base)
+        }
+        (_, over) => over,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -179,4 +316,79 @@ mod tests {
             assert!(matches!(hash.get("empty_hash"), Some(CicdEntity::Hash(h)) if h.is_empty()));
         }
     }
+
+    fn read_from<'a>(files: &'a [(&str, &str)]) -> impl Fn(&str) -> Result<String> + 'a {
+        move |path| {
+            files
+                .iter()
+                .find(|(name, _)| *name == path)
+                .map(|(_, content)| content.to_string())
+                .ok_or_else(|| GRError::MermaidParsingError(path.to_string()).into())
+        }
+    }
+
+    #[test]
+    fn test_resolve_local_includes() {
+        let yaml = create_yaml(
+            "
+            include:
+              - local: /ci/build.yml
+              - remote: https://example.com/ci.yml
+            stages: [build, test]
+            compile:
+              script: make release
+            ",
+        );
+        let files = [
+            (
+                "ci/build.yml",
+                "include: ci/test.yml\ncompile:\n  stage: build\n  script: make",
+            ),
+            ("ci/test.yml", "unit:\n  stage: test"),
+        ];
+        let yaml = resolve(yaml, &read_from(&files)).unwrap();
+        assert!(yaml["include"].is_badvalue());
+        assert_eq!(Some("build"), yaml["compile"]["stage"].as_str());
+        assert_eq!(Some("make release"), yaml["compile"]["script"].as_str());
+        assert_eq!(Some("test"), yaml["unit"]["stage"].as_str());
+    }
+
+    #[test]
+    fn test_resolve_extends_chain() {
+        let yaml = create_yaml(
+            "
+            .rules:
+              rules:
+                - if: $CI_COMMIT_TAG
+            .deploy:
+              extends: .rules
+              stage: deploy
+              variables:
+                ENV: staging
+                REGION: eu
+            production:
+              extends: [.deploy, .manual]
+              variables:
+                ENV: production
+            .manual:
+              when: manual
+            ",
+        );
+        let yaml = resolve(yaml, &read_from(&[])).unwrap();
+        let job = &yaml["production"];
+        assert!(job["extends"].is_badvalue());
+        assert_eq!(Some("deploy"), job["stage"].as_str());
+        assert_eq!(Some("$CI_COMMIT_TAG"), job["rules"][0]["if"].as_str());
+        assert_eq!(Some("production"), job["variables"]["ENV"].as_str());
+        assert_eq!(Some("eu"), job["variables"]["REGION"].as_str());
+        assert_eq!(Some("manual"), job["when"].as_str());
+    }
+
+    #[test]
+    fn test_resolve_extends_unknown_or_cyclic_job_is_error() {
+        let yaml = create_yaml("job:\n  extends: .missing");
+        assert!(resolve(yaml, &read_from(&[])).is_err());
+        let yaml = create_yaml(".a:\n  extends: .b\n.b:\n  extends: .a");
+        assert!(resolve(yaml, &read_from(&[])).is_err());
+    }
 }
@@ -0,0 +1,362 @@
+//! Reads zip archives stored remotely through HTTP range requests. Only the
+//! central directory at the end of the archive is downloaded to list its
+//! contents, and a single file is extracted by downloading just its bytes.
+
+use std::io::Read;
+
+use flate2::read::DeflateDecoder;
+
+use crate::cmds::cache::BytesToHumanReadable;
+use crate::display::{Column, DisplayBody};
+use crate::error::GRError;
+use crate::Result;
+
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+const CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x02014b50;
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x04034b50;
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+const CENTRAL_DIRECTORY_HEADER_SIZE: usize = 46;
+const LOCAL_FILE_HEADER_SIZE: u64 = 30;
+/// End of central directory record plus the maximum comment length.
+const MAX_END_OF_CENTRAL_DIRECTORY_SIZE: u64 = END_OF_CENTRAL_DIRECTORY_SIZE as u64 + 0xffff;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+/// Range of bytes of a remote file as requested in the `Range` HTTP header.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ByteRange {
+    /// Last N bytes of the file.
+    Suffix(u64),
+    /// Number of bytes from a given offset.
+    Span { offset: u64, length: u64 },
+}
+
+impl ByteRange {
+    /// Value of the `Range` HTTP header. Empty spans cannot be expressed and
+    /// are rejected.
+    pub fn header_value(&self) -> Result<String> {
+        match *self {
+            ByteRange::Suffix(n) => Ok(format!("bytes=-{}", n)),
+            ByteRange::Span { length: 0, .. } => Err(GRError::PreconditionNotMet(
+                "Cannot request an empty byte range".to_string(),
+            )
+            .into()),
+            ByteRange::Span { offset, length } => {
+                Ok(format!("bytes={}-{}", offset, offset + length - 1))
+            }
+        }
+    }
+
+    /// Gets the requested range out of the response to a range request. A
+    /// 200 means the server did not honor the range and returned the whole
+    /// file instead.
+    pub fn response_data(&self, status: i32, data: Vec<u8>) -> Result<Vec<u8>> {
+        match status {
+            206 => Ok(data),
+            200 => Ok(self.slice(data)),
+            status => Err(GRError::RemoteServerError(format!(
+                "Unexpected status {} reading range {:?} of archive",
+                status, self
+            ))
+            .into()),
+        }
+    }
+
+    /// Slices the requested range from a full response, for servers that do
+    /// not honor range requests and return the whole file instead.
+    pub fn slice(&self, data: Vec<u8>) -> Vec<u8> {
+        let len = data.len();
+        match *self {
+            ByteRange::Suffix(n) => data[len.saturating_sub(n as usize)..].to_vec(),
+            ByteRange::Span { offset, length } => {
+                let start = (offset as usize).min(len);
+                let end = (offset.saturating_add(length) as usize).min(len);
+                data[start..end].to_vec()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub size: u64,
+    pub compressed_size: u64,
+    compression: u16,
+    local_header_offset: u64,
+}
+
+impl From<ArchiveEntry> for DisplayBody {
+    fn from(entry: ArchiveEntry) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Path", entry.path),
+                Column::new("Size", BytesToHumanReadable::from(entry.size).to_string()),
+                Column::new(
+                    "Compressed",
+                    BytesToHumanReadable::from(entry.compressed_size).to_string(),
+                ),
+            ],
+        }
+    }
+}
+
+/// Lists the files in a remote zip archive. `read` downloads the requested
+/// range of the archive.
+pub fn list_entries(read: impl Fn(ByteRange) -> Result<Vec<u8>>) -> Result<Vec<ArchiveEntry>> {
+    let tail = read(ByteRange::Suffix(MAX_END_OF_CENTRAL_DIRECTORY_SIZE))?;
+    let eocd = (0..=tail.len().saturating_sub(END_OF_CENTRAL_DIRECTORY_SIZE))
+        .rev()
+        .find(|&pos| u32_at(&tail, pos) == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+        .ok_or_else(|| invalid_archive("end of central directory not found"))?;
+    let num_entries = u16_at(&tail, eocd + 10).unwrap_or_default() as usize;
+    let directory_size = u32_at(&tail, eocd + 12).unwrap_or_default();
+    let directory_offset = u32_at(&tail, eocd + 16).unwrap_or_default();
+    if num_entries == 0xffff || directory_size == u32::MAX || directory_offset == u32::MAX {
+        return Err(
+            GRError::PreconditionNotMet("ZIP64 archives are not supported".to_string()).into(),
+        );
+    }
+    let directory = read_span(&read, directory_offset as u64, directory_size as u64)?;
+    parse_central_directory(&directory, num_entries)
+}
+
+/// Downloads and decompresses a single file of a remote zip archive.
+pub fn extract(
+    read: impl Fn(ByteRange) -> Result<Vec<u8>>,
+    entry: &ArchiveEntry,
+) -> Result<Vec<u8>> {
+    let header = read(ByteRange::Span {
+        offset: entry.local_header_offset,
+        length: LOCAL_FILE_HEADER_SIZE,
+    })?;
+    if u32_at(&header, 0) != Some(LOCAL_FILE_HEADER_SIGNATURE) {
+        return Err(invalid_archive("local file header not found"));
+    }
+    // Name and extra field lengths can differ from the central directory.
+    let name_length = u16_at(&header, 26).unwrap_or_default() as u64;
+    let extra_length = u16_at(&header, 28).unwrap_or_default() as u64;
+    let data = read_span(
+        &read,
+        entry.local_header_offset + LOCAL_FILE_HEADER_SIZE + name_length + extra_length,
+        entry.compressed_size,
+    )?;
+    match entry.compression {
+        STORED => Ok(data),
+        DEFLATED => {
+            // The size in the archive is not trusted to preallocate.
+            let mut contents = Vec::new();
+            DeflateDecoder::new(data.as_slice()).read_to_end(&mut contents)?;
+            Ok(contents)
+        }
+        method => Err(GRError::PreconditionNotMet(format!(
+            "Unsupported compression method {} for {}",
+            method, entry.path
+        ))
+        .into()),
+    }
+}
+
+fn parse_central_directory(directory: &[u8], num_entries: usize) -> Result<Vec<ArchiveEntry>> {
+    let mut entries = Vec::with_capacity(num_entries);
+    let mut pos = 0;
+    for _ in 0..num_entries {
+        if u32_at(directory, pos) != Some(CENTRAL_DIRECTORY_SIGNATURE) {
+            return Err(invalid_archive("corrupted central directory"));
+        }
+        let field = |offset: usize| u16_at(directory, pos + offset).unwrap_or_default();
+        let name_length = field(28) as usize;
+        let extra_length = field(30) as usize;
+        let comment_length = field(32) as usize;
+        let name_start = pos + CENTRAL_DIRECTORY_HEADER_SIZE;
+        let name = directory
+            .get(name_start..name_start + name_length)
+            .ok_or_else(|| invalid_archive("truncated central directory"))?;
+        entries.push(ArchiveEntry {
+            path: String::from_utf8_lossy(name).to_string(),
+            compression: field(10),
+            compressed_size: u32_at(directory, pos + 20).unwrap_or_default() as u64,
+            size: u32_at(directory, pos + 24).unwrap_or_default() as u64,
+            local_header_offset: u32_at(directory, pos + 42).unwrap_or_default() as u64,
+        });
+        pos = name_start + name_length + extra_length + comment_length;
+    }
+    Ok(entries)
+}
+
+/// Reads `length` bytes at `offset`. Empty files and directories need no
+/// request.
+fn read_span(
+    read: &impl Fn(ByteRange) -> Result<Vec<u8>>,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>> {
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+    read(ByteRange::Span { offset, length })
+}
+
+fn invalid_archive(reason: &str) -> anyhow::Error {
+    GRError::PreconditionNotMet(format!("Invalid zip archive: {}", reason)).into()
+}
+
+fn u16_at(data: &[u8], pos: usize) -> Option<u16> {
+    data.get(pos..pos + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(data: &[u8], pos: usize) -> Option<u32> {
+    data.get(pos..pos + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::cell::RefCell;
+    use std::io::Write;
+
+    use flate2::{write::DeflateEncoder, Compression};
+
+    use super::*;
+
+    /// Builds a zip archive with the given files. Files ending in `.txt` are
+    /// deflated, the rest are stored.
+    pub fn build_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        let mut directory = Vec::new();
+        for (name, contents) in files {
+            let (method, data) = if name.ends_with(".txt") {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(contents).unwrap();
+                (DEFLATED, encoder.finish().unwrap())
+            } else {
+                (STORED, contents.to_vec())
+            };
+            let offset = archive.len() as u32;
+            archive.extend(LOCAL_FILE_HEADER_SIGNATURE.to_le_bytes());
+            archive.extend([20, 0, 0, 0]);
+            archive.extend(method.to_le_bytes());
+            archive.extend([0; 8]);
+            archive.extend((data.len() as u32).to_le_bytes());
+            archive.extend((contents.len() as u32).to_le_bytes());
+            archive.extend((name.len() as u16).to_le_bytes());
+            archive.extend(0u16.to_le_bytes());
+            archive.extend(name.as_bytes());
+            archive.extend(&data);
+
+            directory.extend(CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+            directory.extend([20, 0, 20, 0, 0, 0]);
+            directory.extend(method.to_le_bytes());
+            directory.extend([0; 8]);
+            directory.extend((data.len() as u32).to_le_bytes());
+            directory.extend((contents.len() as u32).to_le_bytes());
+            directory.extend((name.len() as u16).to_le_bytes());
+            directory.extend([0; 12]);
+            directory.extend(offset.to_le_bytes());
+            directory.extend(name.as_bytes());
+        }
+        let directory_offset = archive.len() as u32;
+        archive.extend(&directory);
+        archive.extend(END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        archive.extend([0; 4]);
+        archive.extend((files.len() as u16).to_le_bytes());
+        archive.extend((files.len() as u16).to_le_bytes());
+        archive.extend((directory.len() as u32).to_le_bytes());
+        archive.extend(directory_offset.to_le_bytes());
+        archive.extend(0u16.to_le_bytes());
+        archive
+    }
+
+    #[test]
+    fn test_list_and_extract_entries() {
+        let archive = build_archive(&[
+            ("target/report.txt", b"test result: ok. 42 passed"),
+            ("target/gr", b"\x7fELF binary"),
+        ]);
+        let requests = RefCell::new(Vec::new());
+        let read = |range: ByteRange| {
+            requests.borrow_mut().push(range);
+            Ok(range.slice(archive.clone()))
+        };
+        let entries = list_entries(read).unwrap();
+        assert_eq!(2, entries.len());
+        assert_eq!("target/report.txt", entries[0].path);
+        assert_eq!(26, entries[0].size);
+        assert_eq!("target/gr", entries[1].path);
+        assert_eq!(11, entries[1].compressed_size);
+        assert_eq!(
+            b"test result: ok. 42 passed".to_vec(),
+            extract(read, &entries[0]).unwrap()
+        );
+        assert_eq!(
+            b"\x7fELF binary".to_vec(),
+            extract(read, &entries[1]).unwrap()
+        );
+        assert_eq!(
+            ByteRange::Suffix(MAX_END_OF_CENTRAL_DIRECTORY_SIZE),
+            requests.borrow()[0]
+        );
+    }
+
+    #[test]
+    fn test_extract_empty_file_sends_no_empty_range() {
+        let archive = build_archive(&[("target/empty.log", b"")]);
+        let read = |range: ByteRange| {
+            range.header_value()?;
+            Ok(range.slice(archive.clone()))
+        };
+        let entries = list_entries(read).unwrap();
+        assert_eq!(Vec::<u8>::new(), extract(read, &entries[0]).unwrap());
+    }
+
+    #[test]
+    fn test_not_a_zip_archive_is_error() {
+        let read = |_: ByteRange| Ok(b"<html>Not found</html>".to_vec());
+        assert!(list_entries(read).is_err());
+    }
+
+    #[test]
+    fn test_byte_range_header_value() {
+        assert_eq!(
+            "bytes=-65557",
+            ByteRange::Suffix(65557).header_value().unwrap()
+        );
+        assert_eq!(
+            "bytes=100-129",
+            ByteRange::Span {
+                offset: 100,
+                length: 30
+            }
+            .header_value()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_empty_byte_range_is_error() {
+        let range = ByteRange::Span {
+            offset: 100,
+            length: 0,
+        };
+        assert!(range.header_value().is_err());
+    }
+
+    #[test]
+    fn test_byte_range_response_data() {
+        let range = ByteRange::Span {
+            offset: 2,
+            length: 3,
+        };
+        assert_eq!(
+            b"234".to_vec(),
+            range.response_data(206, b"234".to_vec()).unwrap()
+        );
+        assert_eq!(
+            b"234".to_vec(),
+            range.response_data(200, b"0123456789".to_vec()).unwrap()
+        );
+        assert!(range.response_data(204, Vec::new()).is_err());
+    }
+}
@@ -9,17 +9,20 @@ use std::io::Write;
 use std::sync::Arc;
 
 use crate::api_traits::{
-    Cicd, CicdJob, CicdRunner, CodeGist, CommentMergeRequest, Deploy, DeployAsset, ProjectMember,
-    RemoteProject, RemoteTag, TrendingProjectURL,
+    Cicd, CicdJob, CicdRunner, CodeGist, CommentMergeRequest, Deploy, DeployAsset, Issue, Label,
+    ProjectMember, RemoteProject, RemoteTag, Search, TrendingProjectURL,
 };
 
 use super::cicd::{JobListBodyArgs, JobListCliArgs, RunnerListBodyArgs, RunnerListCliArgs};
 use super::gist::{GistListBodyArgs, GistListCliArgs};
+use super::issue::{IssueListBodyArgs, IssueListCliArgs};
+use super::label::LabelListBodyArgs;
 use super::merge_request::{
     CommentMergeRequestListBodyArgs, CommentMergeRequestListCliArgs, MergeRequestListBodyArgs,
 };
 use super::project::{Member, ProjectListBodyArgs, ProjectListCliArgs};
 use super::release::{ReleaseAssetListBodyArgs, ReleaseAssetListCliArgs, ReleaseBodyArgs};
+use super::search::{SearchBodyArgs, SearchCliArgs};
 use super::trending::TrendingCliArgs;
 use super::{cicd::PipelineBodyArgs, merge_request::MergeRequestListCliArgs};
 
@@ -155,6 +158,15 @@ query_pages!(
     CommentMergeRequestListBodyArgs
 );
 
+query_pages!(num_search_pages, Search, SearchBodyArgs);
+query_num_resources!(num_search_resources, Search, SearchBodyArgs);
+
+query_pages!(num_issue_pages, Issue, IssueListBodyArgs);
+query_num_resources!(num_issue_resources, Issue, IssueListBodyArgs);
+
+query_pages!(num_label_pages, Label);
+query_num_resources!(num_label_resources, Label);
+
 query_pages!(num_user_gists, CodeGist);
 query_num_resources!(num_user_gist_resources, CodeGist);
 
@@ -221,7 +233,16 @@ list_resource!(
 
 list_resource!(list_jobs, CicdJob, JobListBodyArgs, JobListCliArgs, true);
 
+list_resource!(
+    list_issues,
+    Issue,
+    IssueListBodyArgs,
+    IssueListCliArgs,
+    true
+);
+
 list_resource!(list_releases, Deploy, ReleaseBodyArgs, ListRemoteCliArgs);
+list_resource!(list_labels, Label, LabelListBodyArgs, ListRemoteCliArgs);
 list_resource!(
     list_release_assets,
     DeployAsset,
@@ -270,6 +291,14 @@ list_resource!(
     true
 );
 
+list_resource!(
+    list_search_results,
+    Search,
+    SearchBodyArgs,
+    SearchCliArgs,
+    true
+);
+
 list_resource!(list_trending, TrendingProjectURL, String, TrendingCliArgs);
 
 pub fn get_user(
@@ -0,0 +1,262 @@
+//! Records the JSON contracts used as test fixtures from the live API of the
+//! current repository, so mocked responses can be kept in sync with provider
+//! changes. Secrets and personal data are scrubbed before writing them.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::api_traits::ContractRecorder;
+use crate::cli::contract::ContractOptions;
+use crate::config::ConfigProperties;
+use crate::error::GRError;
+use crate::remote::{self, CacheType};
+use crate::Result;
+
+const REDACTED: &str = "REDACTED";
+/// Keys holding secrets or personal data are redacted if any of these is
+/// part of their name. Ex. runners_token, public_email
+const SENSITIVE_KEYS: &[&str] = &["token", "secret", "password", "email"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContractOperation {
+    Project,
+    MergeRequest,
+    Comment,
+    AuthUser,
+}
+
+#[derive(Builder)]
+pub struct RecordContractCliArgs {
+    pub operation: ContractOperation,
+    #[builder(default)]
+    pub id: Option<i64>,
+    pub dir: String,
+}
+
+impl RecordContractCliArgs {
+    pub fn builder() -> RecordContractCliArgsBuilder {
+        RecordContractCliArgsBuilder::default()
+    }
+}
+
+/// Payload of an API operation as returned by the remote, along with the
+/// fixture it is stored in, relative to the contracts directory. Ex.
+/// gitlab/merge_request.json
+pub struct ContractFixture {
+    pub path: String,
+    pub payload: Value,
+}
+
+impl ContractFixture {
+    pub fn new(path: impl Into<String>, payload: Value) -> Self {
+        ContractFixture {
+            path: path.into(),
+            payload,
+        }
+    }
+}
+
+pub fn execute(
+    options: ContractOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
+    match options {
+        ContractOptions::Record(cli_args) => {
+            let remote =
+                remote::get_contract_recorder(domain, path, config.clone(), None, CacheType::None)?;
+            record(remote, cli_args, config.api_token(), std::io::stdout())
+        }
+    }
+}
+
+fn record<W: Write>(
+    remote: Arc<dyn ContractRecorder>,
+    cli_args: RecordContractCliArgs,
+    api_token: &str,
+    mut writer: W,
+) -> Result<()> {
+    let id = match (cli_args.operation, cli_args.id) {
+        (ContractOperation::MergeRequest | ContractOperation::Comment, None) => {
+            return Err(GRError::PreconditionNotMet(
+                "A merge request --id is required to record this operation".to_string(),
+            )
+            .into())
+        }
+        (_, id) => id.unwrap_or_default(),
+    };
+    let mut fixture = remote.record(cli_args.operation, id)?;
+    if cli_args.operation == ContractOperation::Comment {
+        // Fixtures hold a single comment. Tests build the lists they need.
+        fixture.payload = match fixture.payload {
+            Value::Array(comments) if !comments.is_empty() => {
+                comments.into_iter().next().unwrap_or_default()
+            }
+            _ => {
+                return Err(GRError::PreconditionNotMet(format!(
+                    "Merge request {} has no comments to record",
+                    id
+                ))
+                .into())
+            }
+        };
+    }
+    let payload = scrub(fixture.payload, api_token);
+    let path = Path::new(&cli_args.dir).join(&fixture.path);
+    let mut contents = serde_json::to_string_pretty(&payload)?;
+    contents.push('\n');
+    fs::write(&path, contents)?;
+    writeln!(writer, "Recorded {}", path.display())?;
+    Ok(())
+}
+
+/// Redacts string values of sensitive keys and any occurrence of the API
+/// token in the payload.
+fn scrub(value: Value, api_token: &str) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let sensitive = SENSITIVE_KEYS.iter().any(|name| key.contains(name));
+                    match value {
+                        Value::String(_) if sensitive => (key, Value::String(REDACTED.to_string())),
+                        value => (key, scrub(value, api_token)),
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| scrub(item, api_token))
+                .collect(),
+        ),
+        Value::String(s) if !api_token.is_empty() && s.contains(api_token) => {
+            Value::String(s.replace(api_token, REDACTED))
+        }
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::json;
+
+    use super::*;
+
+    struct ContractRecorderMock {
+        payload: Value,
+        recorded: Mutex<Option<(ContractOperation, i64)>>,
+    }
+
+    impl ContractRecorderMock {
+        fn new(payload: Value) -> Self {
+            ContractRecorderMock {
+                payload,
+                recorded: Mutex::new(None),
+            }
+        }
+    }
+
+    impl ContractRecorder for ContractRecorderMock {
+        fn record(&self, operation: ContractOperation, id: i64) -> Result<ContractFixture> {
+            *self.recorded.lock().unwrap() = Some((operation, id));
+            Ok(ContractFixture::new(
+                "gitlab/merge_request.json",
+                self.payload.clone(),
+            ))
+        }
+    }
+
+    fn cli_args(
+        operation: ContractOperation,
+        id: Option<i64>,
+        dir: &Path,
+    ) -> RecordContractCliArgs {
+        RecordContractCliArgs::builder()
+            .operation(operation)
+            .id(id)
+            .dir(dir.to_string_lossy().to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scrub_redacts_secrets_and_api_token() {
+        let payload = json!({
+            "id": 1,
+            "runners_token": "GR1348941abc",
+            "owner": {"public_email": "jdoe@example.com", "email_verified": true},
+            "description": "Uses token glpat-1234 for CI",
+            "members": [{"commit_email": "jdoe@example.com"}],
+        });
+        let scrubbed = scrub(payload, "glpat-1234");
+        assert_eq!(
+            json!({
+                "id": 1,
+                "runners_token": "REDACTED",
+                "owner": {"public_email": "REDACTED", "email_verified": true},
+                "description": "Uses token REDACTED for CI",
+                "members": [{"commit_email": "REDACTED"}],
+            }),
+            scrubbed
+        );
+    }
+
+    #[test]
+    fn test_record_writes_scrubbed_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("gitlab")).unwrap();
+        let remote = Arc::new(ContractRecorderMock::new(
+            json!({"iid": 33, "title": "New feature", "author": {"email": "jdoe@example.com"}}),
+        ));
+        let mut buf = Vec::new();
+        let args = cli_args(ContractOperation::MergeRequest, Some(33), dir.path());
+        record(remote.clone(), args, "glpat-1234", &mut buf).unwrap();
+        let path = dir.path().join("gitlab/merge_request.json");
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            "{\n  \"author\": {\n    \"email\": \"REDACTED\"\n  },\n  \"iid\": 33,\n  \"title\": \"New feature\"\n}\n",
+            contents
+        );
+        assert_eq!(
+            Some((ContractOperation::MergeRequest, 33)),
+            *remote.recorded.lock().unwrap()
+        );
+        assert_eq!(
+            format!("Recorded {}\n", path.display()),
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_record_comment_keeps_first_comment() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("gitlab")).unwrap();
+        let remote = Arc::new(ContractRecorderMock::new(
+            json!([{"id": 1, "body": "LGTM"}, {"id": 2, "body": "Thanks"}]),
+        ));
+        let args = cli_args(ContractOperation::Comment, Some(33), dir.path());
+        record(remote, args, "glpat-1234", Vec::new()).unwrap();
+        let contents = fs::read_to_string(dir.path().join("gitlab/merge_request.json")).unwrap();
+        let payload: Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(json!({"id": 1, "body": "LGTM"}), payload);
+    }
+
+    #[test]
+    fn test_record_merge_request_requires_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = Arc::new(ContractRecorderMock::new(json!({})));
+        let args = cli_args(ContractOperation::MergeRequest, None, dir.path());
+        assert!(record(remote.clone(), args, "glpat-1234", Vec::new()).is_err());
+        assert!(remote.recorded.lock().unwrap().is_none());
+    }
+}
@@ -0,0 +1,346 @@
+use std::fmt::Display;
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::api_traits::Deployment;
+use crate::cli::deployment::DeploymentOptions;
+use crate::config::ConfigProperties;
+use crate::remote::{self, CacheType};
+use crate::{dialog, time, Result};
+use chrono::{DateTime, Utc};
+
+/// State of a deployment as reported by external deploy scripts. Follows the
+/// Github deployment status states. Each remote maps it to its own set of
+/// supported values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeploymentState {
+    Pending,
+    Queued,
+    InProgress,
+    Success,
+    Failure,
+    Error,
+    Inactive,
+}
+
+impl Display for DeploymentState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeploymentState::Pending => write!(f, "pending"),
+            DeploymentState::Queued => write!(f, "queued"),
+            DeploymentState::InProgress => write!(f, "in_progress"),
+            DeploymentState::Success => write!(f, "success"),
+            DeploymentState::Failure => write!(f, "failure"),
+            DeploymentState::Error => write!(f, "error"),
+            DeploymentState::Inactive => write!(f, "inactive"),
+        }
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct DeploymentStatusCliArgs {
+    pub id: i64,
+    pub state: DeploymentState,
+    #[builder(default)]
+    pub url: Option<String>,
+    #[builder(default)]
+    pub description: Option<String>,
+}
+
+impl DeploymentStatusCliArgs {
+    pub fn builder() -> DeploymentStatusCliArgsBuilder {
+        DeploymentStatusCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct DeploymentStatus {
+    pub id: i64,
+    pub state: String,
+    #[builder(default)]
+    pub url: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl DeploymentStatus {
+    pub fn builder() -> DeploymentStatusBuilder {
+        DeploymentStatusBuilder::default()
+    }
+}
+
+impl Display for DeploymentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Deployment status ID: [{}], State: [{}], URL: [{}], Created at: [{}]",
+            self.id,
+            self.state,
+            self.url,
+            time::format_optional_timestamp(self.created_at.as_ref())
+        )
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct EnvironmentStopCliArgs {
+    pub environment: String,
+    #[builder(default)]
+    pub yes: bool,
+}
+
+impl EnvironmentStopCliArgs {
+    pub fn builder() -> EnvironmentStopCliArgsBuilder {
+        EnvironmentStopCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct EnvironmentRollbackCliArgs {
+    pub environment: String,
+    /// Previous deployment to re-deploy
+    pub deployment_id: i64,
+    #[builder(default)]
+    pub yes: bool,
+}
+
+impl EnvironmentRollbackCliArgs {
+    pub fn builder() -> EnvironmentRollbackCliArgsBuilder {
+        EnvironmentRollbackCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct Environment {
+    pub id: i64,
+    pub name: String,
+    pub state: String,
+    #[builder(default)]
+    pub external_url: String,
+}
+
+impl Environment {
+    pub fn builder() -> EnvironmentBuilder {
+        EnvironmentBuilder::default()
+    }
+}
+
+impl Display for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Environment ID: [{}], Name: [{}], State: [{}], URL: [{}]",
+            self.id, self.name, self.state, self.external_url
+        )
+    }
+}
+
+/// Job that re-deploys a previous deployment on rollback.
+#[derive(Builder, Clone, Debug)]
+pub struct RollbackJob {
+    pub id: i64,
+    pub status: String,
+    pub web_url: String,
+}
+
+impl RollbackJob {
+    pub fn builder() -> RollbackJobBuilder {
+        RollbackJobBuilder::default()
+    }
+}
+
+impl Display for RollbackJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Rollback job ID: [{}], Status: [{}], URL: [{}]",
+            self.id, self.status, self.web_url
+        )
+    }
+}
+
+pub fn execute(
+    options: DeploymentOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
+    match options {
+        DeploymentOptions::SetStatus(cli_args) => {
+            let remote = remote::get_deployment(domain, path, config, None, CacheType::None)?;
+            set_status(remote, cli_args, std::io::stdout())
+        }
+        DeploymentOptions::Stop(cli_args) => {
+            let remote = remote::get_deployment(domain, path, config, None, CacheType::None)?;
+            stop_environment(remote, cli_args, std::io::stdout())
+        }
+        DeploymentOptions::Rollback(cli_args) => {
+            let remote = remote::get_deployment(domain, path, config, None, CacheType::None)?;
+            rollback_environment(remote, cli_args, std::io::stdout())
+        }
+    }
+}
+
+fn set_status<W: Write>(
+    remote: Arc<dyn Deployment>,
+    cli_args: DeploymentStatusCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let response = remote.set_status(cli_args)?;
+    writeln!(writer, "{}", response)?;
+    Ok(())
+}
+
+fn stop_environment<W: Write>(
+    remote: Arc<dyn Deployment>,
+    cli_args: EnvironmentStopCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    dialog::confirm_action(
+        &format!("Stop environment {}?", cli_args.environment),
+        cli_args.yes,
+    )?;
+    let response = remote.stop_environment(&cli_args.environment)?;
+    writeln!(writer, "{}", response)?;
+    Ok(())
+}
+
+fn rollback_environment<W: Write>(
+    remote: Arc<dyn Deployment>,
+    cli_args: EnvironmentRollbackCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    dialog::confirm_action(
+        &format!(
+            "Rollback environment {} to deployment {}?",
+            cli_args.environment, cli_args.deployment_id
+        ),
+        cli_args.yes,
+    )?;
+    let response = remote.rollback(&cli_args.environment, cli_args.deployment_id)?;
+    writeln!(writer, "{}", response)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error;
+
+    struct DeploymentMock {
+        error: bool,
+    }
+
+    impl Deployment for DeploymentMock {
+        fn set_status(&self, args: DeploymentStatusCliArgs) -> Result<DeploymentStatus> {
+            if self.error {
+                return Err(error::gen("Error"));
+            }
+            Ok(DeploymentStatus::builder()
+                .id(1)
+                .state(args.state.to_string())
+                .url(args.url.unwrap_or_default())
+                .created_at(Some("2024-01-01T00:00:00Z".parse().unwrap()))
+                .build()
+                .unwrap())
+        }
+
+        fn stop_environment(&self, environment: &str) -> Result<Environment> {
+            if self.error {
+                return Err(error::gen("Error"));
+            }
+            Ok(Environment::builder()
+                .id(9)
+                .name(environment.to_string())
+                .state("stopping".to_string())
+                .build()
+                .unwrap())
+        }
+
+        fn rollback(&self, _environment: &str, _deployment_id: i64) -> Result<RollbackJob> {
+            if self.error {
+                return Err(error::gen("Error"));
+            }
+            Ok(RollbackJob::builder()
+                .id(77)
+                .status("pending".to_string())
+                .web_url("https://gitlab.com/job/77".to_string())
+                .build()
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_set_deployment_status() {
+        let remote = Arc::new(DeploymentMock { error: false });
+        let cli_args = DeploymentStatusCliArgs::builder()
+            .id(123)
+            .state(DeploymentState::Success)
+            .url(Some("https://app".to_string()))
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        set_status(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "Deployment status ID: [1], State: [success], URL: [https://app], Created at: [2024-01-01T00:00:00Z]\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_deployment_status_error() {
+        let remote = Arc::new(DeploymentMock { error: true });
+        let cli_args = DeploymentStatusCliArgs::builder()
+            .id(123)
+            .state(DeploymentState::Failure)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        assert!(set_status(remote, cli_args, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_stop_environment() {
+        let remote = Arc::new(DeploymentMock { error: false });
+        let cli_args = EnvironmentStopCliArgs::builder()
+            .environment("production".to_string())
+            .yes(true)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        stop_environment(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "Environment ID: [9], Name: [production], State: [stopping], URL: []\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rollback_environment() {
+        let remote = Arc::new(DeploymentMock { error: false });
+        let cli_args = EnvironmentRollbackCliArgs::builder()
+            .environment("production".to_string())
+            .deployment_id(41)
+            .yes(true)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        rollback_environment(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "Rollback job ID: [77], Status: [pending], URL: [https://gitlab.com/job/77]\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rollback_environment_error() {
+        let remote = Arc::new(DeploymentMock { error: true });
+        let cli_args = EnvironmentRollbackCliArgs::builder()
+            .environment("production".to_string())
+            .deployment_id(41)
+            .yes(true)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        assert!(rollback_environment(remote, cli_args, &mut buf).is_err());
+    }
+}
@@ -1,13 +1,19 @@
-use std::{io::Write, sync::Arc};
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+};
 
 use crate::{
-    api_traits::{ContainerRegistry, Timestamp},
+    api_traits::{ContainerRegistry, RemoteCredentials, Timestamp},
+    auth::CredentialHelperAction,
     cli::docker::DockerOptions,
     config::ConfigProperties,
     display::{self, Column, DisplayBody},
+    log_debug,
     remote::{self, get_registry, CacheType, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs},
-    Result,
+    time, Result,
 };
+use chrono::{DateTime, Utc};
 
 use super::common::{process_num_metadata, MetadataName};
 
@@ -50,7 +56,7 @@ pub struct RegistryRepository {
     pub id: i64,
     pub location: String,
     pub tags_count: i64,
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
 }
 
 impl RegistryRepository {
@@ -65,14 +71,14 @@ impl From<RegistryRepository> for DisplayBody {
             Column::new("ID", repo.id.to_string()),
             Column::new("Location", repo.location),
             Column::new("Tags count", repo.tags_count.to_string()),
-            Column::new("Created at", repo.created_at),
+            Column::new("Created at", time::format_timestamp(&repo.created_at)),
         ])
     }
 }
 
 impl Timestamp for RegistryRepository {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
     }
 }
 
@@ -81,7 +87,7 @@ pub struct RepositoryTag {
     pub name: String,
     pub path: String,
     pub location: String,
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
 }
 
 impl RepositoryTag {
@@ -91,8 +97,8 @@ impl RepositoryTag {
 }
 
 impl Timestamp for RepositoryTag {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
     }
 }
 
@@ -125,7 +131,7 @@ pub struct ImageMetadata {
     pub location: String,
     pub short_sha: String,
     pub size: i64,
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
 }
 
 impl ImageMetadata {
@@ -141,7 +147,7 @@ impl From<ImageMetadata> for DisplayBody {
             Column::new("Location", metadata.location),
             Column::new("Short SHA", metadata.short_sha),
             Column::new("Size", metadata.size.to_string()),
-            Column::new("Created at", metadata.created_at),
+            Column::new("Created at", time::format_timestamp(&metadata.created_at)),
         ])
     }
 }
@@ -173,6 +179,51 @@ pub fn execute(
             )?;
             get_image_metadata(remote, cli_args, std::io::stdout())
         }
+        DockerOptions::LoginHelper(_) => {
+            unreachable!("Docker login helper does not depend on the current repository")
+        }
+    }
+}
+
+/// Serves credentials to docker for the registry read from `reader`. The
+/// domain the registry belongs to is resolved with `get_credentials`.
+pub fn login_helper<R: Read, W: Write>(
+    action: CredentialHelperAction,
+    mut reader: R,
+    mut writer: W,
+    get_credentials: impl FnOnce(&str) -> Result<Arc<dyn RemoteCredentials + Send + Sync>>,
+) -> Result<()> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    match action {
+        CredentialHelperAction::Get => {
+            let server_url = input.trim();
+            let credentials = get_credentials(&registry_domain(server_url))?.credentials()?;
+            let response = serde_json::json!({
+                "ServerURL": server_url,
+                "Username": credentials.username,
+                "Secret": credentials.secret,
+            });
+            writeln!(writer, "{}", response)?;
+        }
+        // Tokens are managed in the gitar configuration, docker logins and
+        // logouts do not change them.
+        CredentialHelperAction::Store | CredentialHelperAction::Erase => {
+            log_debug!("Ignoring docker credential helper action {:?}", action);
+        }
+    }
+    Ok(())
+}
+
+/// Domain of the remote serving a container registry, ex. registry.gitlab.com
+/// -> gitlab.com or ghcr.io -> github.com. Self-hosted Gitlab registries are
+/// served under the registry subdomain or on a dedicated port of the domain.
+fn registry_domain(server_url: &str) -> String {
+    let host = server_url.split("://").last().unwrap_or_default();
+    let host = host.split(['/', ':']).next().unwrap_or_default();
+    match host {
+        "ghcr.io" | "docker.pkg.github.com" => "github.com".to_string(),
+        host => host.strip_prefix("registry.").unwrap_or(host).to_string(),
     }
 }
 
@@ -182,7 +233,7 @@ fn get_image_metadata<W: Write>(
     mut writer: W,
 ) -> Result<()> {
     let metadata = remote.get_image_metadata(cli_args.repo_id, &cli_args.tag)?;
-    display::print(&mut writer, vec![metadata], cli_args.get_args)?;
+    display::print_one(&mut writer, metadata, cli_args.get_args)?;
     Ok(())
 }
 
@@ -270,7 +321,7 @@ mod tests {
                 .id(1)
                 .location("registry.gitlab.com/namespace/project".to_string())
                 .tags_count(10)
-                .created_at("2021-01-01T00:00:00Z".to_string())
+                .created_at("2021-01-01T00:00:00Z".parse().unwrap())
                 .build()
                 .unwrap();
             Ok(vec![repo])
@@ -281,7 +332,7 @@ mod tests {
                 .name("v0.0.1".to_string())
                 .path("namespace/project:v0.0.1".to_string())
                 .location("registry.gitlab.com/namespace/project:v0.0.1".to_string())
-                .created_at("2021-01-01T00:00:00Z".to_string())
+                .created_at("2021-01-01T00:00:00Z".parse().unwrap())
                 .build()
                 .unwrap();
             Ok(vec![tag])
@@ -307,7 +358,7 @@ mod tests {
                 .location(format!("registry.gitlab.com/namespace/project:{}", tag))
                 .short_sha("12345678".to_string())
                 .size(100)
-                .created_at("2021-01-01T00:00:00Z".to_string())
+                .created_at("2021-01-01T00:00:00Z".parse().unwrap())
                 .build()
                 .unwrap();
             Ok(metadata)
@@ -599,4 +650,64 @@ mod tests {
             String::from_utf8(buf).unwrap()
         );
     }
+
+    struct MockCredentials;
+
+    impl RemoteCredentials for MockCredentials {
+        fn credentials(&self) -> Result<crate::auth::Credentials> {
+            Ok(crate::auth::Credentials {
+                username: "oauth2".to_string(),
+                secret: "1234".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_registry_domain() {
+        assert_eq!("gitlab.com", registry_domain("registry.gitlab.com"));
+        assert_eq!(
+            "gitlab.com",
+            registry_domain("https://registry.gitlab.com/v2/")
+        );
+        assert_eq!(
+            "gitlab.company.com",
+            registry_domain("gitlab.company.com:5050")
+        );
+        assert_eq!("github.com", registry_domain("ghcr.io"));
+    }
+
+    #[test]
+    fn test_login_helper_get_credentials() {
+        let mut buf = Vec::new();
+        let mut requested_domain = String::new();
+        login_helper(
+            CredentialHelperAction::Get,
+            "registry.gitlab.com\n".as_bytes(),
+            &mut buf,
+            |domain| {
+                requested_domain = domain.to_string();
+                Ok(Arc::new(MockCredentials))
+            },
+        )
+        .unwrap();
+        assert_eq!("gitlab.com", requested_domain);
+        let response: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!("registry.gitlab.com", response["ServerURL"]);
+        assert_eq!("oauth2", response["Username"]);
+        assert_eq!("1234", response["Secret"]);
+    }
+
+    #[test]
+    fn test_login_helper_store_is_noop() {
+        let mut buf = Vec::new();
+        let input = r#"{"ServerURL": "registry.gitlab.com", "Username": "jdoe", "Secret": "abc"}"#;
+        login_helper(
+            CredentialHelperAction::Store,
+            input.as_bytes(),
+            &mut buf,
+            |_| Err(error::gen("Credentials are not requested")),
+        )
+        .unwrap();
+        assert!(buf.is_empty());
+    }
 }
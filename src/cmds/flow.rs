@@ -0,0 +1,313 @@
+//! Flows chain gitar commands declared in the `[flow]` table of the gitar
+//! config file or of a repository `.gitar.toml` file. Steps run in order and
+//! the flow stops at the first step that fails.
+//!
+//! ```toml
+//! [flow.ship]
+//! vars = { environment = "staging" }
+//! steps = [
+//!     "mr merge {id}",
+//!     "dp rollback {environment} --to {deployment} --yes",
+//! ]
+//! ```
+//!
+//! `{name}` is replaced by the variable given with `--var name=value` or by
+//! the default declared in `vars`. Global options given to `gr flow run`,
+//! such as `--repo`, apply to steps that do not set them.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+
+use crate::cli::alias::split_words;
+use crate::cli::flow::FlowRunCliArgs;
+use crate::cli::{try_parse_cli_from, CliArgs, CliOptions};
+use crate::error::{self, GRError};
+use crate::Result;
+
+lazy_static! {
+    static ref VARIABLE: Regex = Regex::new(r"\{(\w+)\}").unwrap();
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Flow {
+    pub steps: Vec<String>,
+    #[serde(default)]
+    pub vars: HashMap<String, String>,
+}
+
+struct Step {
+    command: String,
+    options: CliOptions,
+    cli_args: CliArgs,
+}
+
+/// Runs the steps of a flow through `dispatch`. All steps are interpolated
+/// and parsed before running the first one, so a typo in the last step does
+/// not leave the flow half done.
+pub fn run<W: Write>(
+    args: FlowRunCliArgs,
+    flows: &HashMap<String, Flow>,
+    parent: &CliArgs,
+    mut writer: W,
+    mut dispatch: impl FnMut(CliOptions, CliArgs) -> Result<()>,
+) -> Result<()> {
+    let Some(flow) = flows.get(&args.name) else {
+        let mut names = flows.keys().map(String::as_str).collect::<Vec<_>>();
+        names.sort();
+        return Err(GRError::ConfigurationError(format!(
+            "Flow '{}' not found. Available flows: {}",
+            args.name,
+            names.join(", ")
+        ))
+        .into());
+    };
+    let mut vars = flow.vars.clone();
+    vars.extend(args.vars);
+    let steps = flow
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| {
+            parse_step(step, &vars, parent).map_err(|err| {
+                GRError::ConfigurationError(format!(
+                    "Step {} of flow '{}' is invalid: {}",
+                    i + 1,
+                    args.name,
+                    err
+                ))
+                .into()
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let num_steps = steps.len();
+    for (i, step) in steps.into_iter().enumerate() {
+        writeln!(
+            writer,
+            "Running step {}/{}: gr {}",
+            i + 1,
+            num_steps,
+            step.command
+        )?;
+        writer.flush()?;
+        dispatch(step.options, step.cli_args).map_err(|err| {
+            err.context(format!(
+                "Flow '{}' stopped at step {}/{} (gr {})",
+                args.name,
+                i + 1,
+                num_steps,
+                step.command
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+fn parse_step(step: &str, vars: &HashMap<String, String>, parent: &CliArgs) -> Result<Step> {
+    let words = split_words(step)
+        .iter()
+        .map(|word| interpolate(word, vars))
+        .collect::<Result<Vec<_>>>()?;
+    let argv = std::iter::once("gr".to_string())
+        .chain(words.iter().cloned())
+        .collect();
+    let option_args = try_parse_cli_from(argv).map_err(|err| error::gen(err.to_string()))?;
+    let options = match option_args.cli_options {
+        Some(CliOptions::Flow(_)) => return Err(error::gen("flows cannot run other flows")),
+        Some(options) => options,
+        None => return Err(error::gen("missing subcommand")),
+    };
+    Ok(Step {
+        command: words.join(" "),
+        options,
+        cli_args: option_args.cli_args.inherit(parent),
+    })
+}
+
+/// Replaces `{name}` placeholders in a word. Done after splitting the step
+/// into words, so values with spaces are passed as a single argument.
+fn interpolate(word: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let mut missing = None;
+    let word = VARIABLE.replace_all(word, |caps: &Captures| match vars.get(&caps[1]) {
+        Some(value) => value.clone(),
+        None => {
+            missing.get_or_insert_with(|| caps[1].to_string());
+            caps[0].to_string()
+        }
+    });
+    match missing {
+        Some(name) => Err(error::gen(format!(
+            "variable '{}' is not set. Use --var {}=VALUE",
+            name, name
+        ))),
+        None => Ok(word.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::merge_request::MergeRequestOptions;
+
+    use super::*;
+
+    fn flows(steps: &[&str]) -> HashMap<String, Flow> {
+        HashMap::from([(
+            "release".to_string(),
+            Flow {
+                steps: steps.iter().map(|step| step.to_string()).collect(),
+                vars: HashMap::from([("state".to_string(), "opened".to_string())]),
+            },
+        )])
+    }
+
+    fn run_args(vars: &[(&str, &str)]) -> FlowRunCliArgs {
+        FlowRunCliArgs {
+            name: "release".to_string(),
+            vars: vars
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_run_flow_steps_in_order_with_variables() {
+        let flows = flows(&[
+            "mr list {state}",
+            "--repo github.com/jordilin/gitar mr merge {id}",
+        ]);
+        let parent = CliArgs::new(0, None, Some("gitlab.com".to_string()), None, None);
+        let mut writer = Vec::new();
+        let mut executed = Vec::new();
+        run(
+            run_args(&[("id", "23")]),
+            &flows,
+            &parent,
+            &mut writer,
+            |options, cli_args| {
+                executed.push((options, cli_args));
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(2, executed.len());
+        assert!(matches!(
+            executed[0].0,
+            CliOptions::MergeRequest(MergeRequestOptions::List(_))
+        ));
+        assert_eq!(Some("gitlab.com".to_string()), executed[0].1.domain);
+        match &executed[1] {
+            (CliOptions::MergeRequest(MergeRequestOptions::Merge { id, .. }), cli_args) => {
+                assert_eq!(23, *id);
+                assert_eq!(Some("github.com/jordilin/gitar".to_string()), cli_args.repo);
+            }
+            _ => panic!("Expected merge request merge"),
+        }
+        assert_eq!(
+            "Running step 1/2: gr mr list opened\n\
+             Running step 2/2: gr --repo github.com/jordilin/gitar mr merge 23\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_run_flow_stops_at_first_error() {
+        let flows = flows(&["mr list {state}", "mr merge 1", "mr list closed"]);
+        let mut writer = Vec::new();
+        let mut num_executed = 0;
+        let result = run(
+            run_args(&[]),
+            &flows,
+            &CliArgs::default(),
+            &mut writer,
+            |options, _| {
+                num_executed += 1;
+                match options {
+                    CliOptions::MergeRequest(MergeRequestOptions::Merge { .. }) => Err(
+                        GRError::PreconditionNotMet("Merge request not mergeable".to_string())
+                            .into(),
+                    ),
+                    _ => Ok(()),
+                }
+            },
+        );
+        assert_eq!(2, num_executed);
+        let err = result.unwrap_err();
+        assert_eq!(
+            "Flow 'release' stopped at step 2/3 (gr mr merge 1)",
+            err.to_string()
+        );
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert_eq!("Merge request not mergeable", msg);
+            }
+            _ => panic!("Expected GRError::PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_run_flow_validates_all_steps_before_running() {
+        let flows = flows(&["mr list {state}", "mr merge {id}"]);
+        let result = run(
+            run_args(&[]),
+            &flows,
+            &CliArgs::default(),
+            Vec::new(),
+            |_, _| panic!("No step should run"),
+        );
+        let err = result.unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::ConfigurationError(msg)) => {
+                assert!(msg.starts_with("Step 2 of flow 'release' is invalid"));
+                assert!(msg.contains("variable 'id' is not set"));
+            }
+            _ => panic!("Expected GRError::ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_run_flow_cannot_run_other_flows() {
+        let flows = flows(&["flow run release"]);
+        let result = run(
+            run_args(&[]),
+            &flows,
+            &CliArgs::default(),
+            Vec::new(),
+            |_, _| panic!("No step should run"),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_run_unknown_flow_is_error() {
+        let result = run(
+            FlowRunCliArgs {
+                name: "deploy".to_string(),
+                vars: vec![],
+            },
+            &flows(&["mr list"]),
+            &CliArgs::default(),
+            Vec::new(),
+            |_, _| Ok(()),
+        );
+        let err = result.unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::ConfigurationError(msg)) => {
+                assert_eq!("Flow 'deploy' not found. Available flows: release", msg)
+            }
+            _ => panic!("Expected GRError::ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_interpolate_keeps_values_with_spaces_in_one_word() {
+        let vars = HashMap::from([("title".to_string(), "Release 1.2.0".to_string())]);
+        assert_eq!(
+            "--title=Release 1.2.0",
+            interpolate("--title={title}", &vars).unwrap()
+        );
+    }
+}
@@ -4,8 +4,9 @@ use crate::{
     api_traits::{CodeGist, Timestamp},
     display::{Column, DisplayBody},
     remote::{ListBodyArgs, ListRemoteCliArgs},
-    Result,
+    time, Result,
 };
+use chrono::{DateTime, Utc};
 
 use super::common;
 
@@ -36,7 +37,7 @@ pub struct Gist {
     pub url: String,
     pub description: String,
     pub files: String,
-    pub created_at: String,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl Gist {
@@ -57,15 +58,18 @@ impl From<Gist> for DisplayBody {
                     .optional(true)
                     .build()
                     .unwrap(),
-                Column::new("Created at", gist.created_at),
+                Column::new(
+                    "Created at",
+                    time::format_optional_timestamp(gist.created_at.as_ref()),
+                ),
             ],
         }
     }
 }
 
 impl Timestamp for Gist {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at.unwrap_or_default()
     }
 }
 
@@ -90,7 +94,7 @@ mod tests {
                 .url("https://gist.github.com/aa5a315d61ae9438b18d".to_string())
                 .description("A gist".to_string())
                 .files("main.rs,hello_rust.rs".to_string())
-                .created_at("2021-08-01T00:00:00Z".to_string())
+                .created_at(Some("2021-08-01T00:00:00Z".parse().unwrap()))
                 .build()
                 .unwrap();
             Ok(vec![gist])
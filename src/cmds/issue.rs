@@ -0,0 +1,418 @@
+use std::fmt::{self, Display, Formatter};
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use crate::api_traits::{Issue, Timestamp};
+use crate::cli::issue::IssueOptions;
+use crate::config::ConfigProperties;
+use crate::display::{self, Column, DisplayBody};
+use crate::remote::{self, CacheType, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs};
+use crate::time;
+use crate::Result;
+use chrono::{DateTime, Utc};
+
+use super::common::{self, num_issue_pages, num_issue_resources};
+use super::merge_request::get_reader_file_cli;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IssueState {
+    Opened,
+    Closed,
+    All,
+}
+
+impl Display for IssueState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueState::Opened => write!(f, "opened"),
+            IssueState::Closed => write!(f, "closed"),
+            IssueState::All => write!(f, "all"),
+        }
+    }
+}
+
+#[derive(Builder, Clone, Debug, Default)]
+pub struct IssueResponse {
+    pub id: i64,
+    pub title: String,
+    #[builder(default)]
+    pub description: String,
+    #[builder(default)]
+    pub author: String,
+    #[builder(default)]
+    pub state: String,
+    pub web_url: String,
+    #[builder(default)]
+    pub created_at: DateTime<Utc>,
+    #[builder(default)]
+    pub updated_at: DateTime<Utc>,
+    /// Github lists pull requests as issues too.
+    #[builder(default)]
+    pub pull_request: bool,
+}
+
+impl IssueResponse {
+    pub fn builder() -> IssueResponseBuilder {
+        IssueResponseBuilder::default()
+    }
+}
+
+impl From<IssueResponse> for DisplayBody {
+    fn from(issue: IssueResponse) -> DisplayBody {
+        DisplayBody::new(vec![
+            Column::new("ID", issue.id.to_string()),
+            Column::new("Title", issue.title),
+            Column::builder()
+                .name("Description".to_string())
+                .value(issue.description)
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::new("Author", issue.author),
+            Column::new("State", issue.state),
+            Column::new("URL", issue.web_url),
+            Column::new("Created at", time::format_timestamp(&issue.created_at)),
+            Column::builder()
+                .name("Updated at".to_string())
+                .value(time::format_timestamp(&issue.updated_at))
+                .optional(true)
+                .build()
+                .unwrap(),
+        ])
+    }
+}
+
+impl Timestamp for IssueResponse {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct IssueBodyArgs {
+    pub title: String,
+    #[builder(default)]
+    pub description: String,
+    #[builder(default)]
+    pub labels: Vec<String>,
+}
+
+impl IssueBodyArgs {
+    pub fn builder() -> IssueBodyArgsBuilder {
+        IssueBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct IssueListBodyArgs {
+    pub state: IssueState,
+    pub list_args: Option<ListBodyArgs>,
+}
+
+impl IssueListBodyArgs {
+    pub fn builder() -> IssueListBodyArgsBuilder {
+        IssueListBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct IssueListCliArgs {
+    pub state: IssueState,
+    pub list_args: ListRemoteCliArgs,
+}
+
+impl IssueListCliArgs {
+    pub fn builder() -> IssueListCliArgsBuilder {
+        IssueListCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct IssueGetCliArgs {
+    pub id: i64,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl IssueGetCliArgs {
+    pub fn builder() -> IssueGetCliArgsBuilder {
+        IssueGetCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct IssueCommentBodyArgs {
+    pub id: i64,
+    pub comment: String,
+}
+
+impl IssueCommentBodyArgs {
+    pub fn builder() -> IssueCommentBodyArgsBuilder {
+        IssueCommentBodyArgsBuilder::default()
+    }
+}
+
+pub fn execute(
+    options: IssueOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
+    match options {
+        IssueOptions::List(cli_args) => {
+            let remote = remote::get_issue(
+                domain,
+                path,
+                config,
+                Some(&cli_args.list_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            let from_to_args = remote::validate_from_to_page(&cli_args.list_args)?;
+            let body_args = IssueListBodyArgs::builder()
+                .state(cli_args.state)
+                .list_args(from_to_args)
+                .build()?;
+            if cli_args.list_args.num_pages {
+                return num_issue_pages(remote, body_args, std::io::stdout());
+            }
+            if cli_args.list_args.num_resources {
+                return num_issue_resources(remote, body_args, std::io::stdout());
+            }
+            list_issues(remote, body_args, cli_args, std::io::stdout())
+        }
+        IssueOptions::Get(cli_args) => {
+            let remote = remote::get_issue(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            get_issue_details(remote, cli_args, std::io::stdout())
+        }
+        IssueOptions::Create(body_args) => {
+            let remote = remote::get_issue(domain, path, config, None, CacheType::None)?;
+            create(remote, body_args, std::io::stdout())
+        }
+        IssueOptions::Comment {
+            id,
+            comment,
+            comment_from_file,
+        } => {
+            let remote = remote::get_issue(domain, path, config, None, CacheType::None)?;
+            let comment = match comment_from_file {
+                Some(file) => {
+                    let mut comment = String::new();
+                    get_reader_file_cli(&file)?.read_to_string(&mut comment)?;
+                    comment
+                }
+                None => comment.unwrap_or_default(),
+            };
+            let body_args = IssueCommentBodyArgs::builder()
+                .id(id)
+                .comment(comment)
+                .build()?;
+            create_comment(remote, body_args, std::io::stdout())
+        }
+        IssueOptions::Close { id } => {
+            let remote = remote::get_issue(domain, path, config, None, CacheType::None)?;
+            close(remote, id, std::io::stdout())
+        }
+    }
+}
+
+fn list_issues<W: Write>(
+    remote: Arc<dyn Issue>,
+    body_args: IssueListBodyArgs,
+    cli_args: IssueListCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    common::list_issues(remote, body_args, cli_args, &mut writer)
+}
+
+fn get_issue_details<W: Write>(
+    remote: Arc<dyn Issue>,
+    cli_args: IssueGetCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let issue = remote.get(cli_args.id)?;
+    display::print_one(&mut writer, issue, cli_args.get_args)?;
+    Ok(())
+}
+
+fn create<W: Write>(remote: Arc<dyn Issue>, body_args: IssueBodyArgs, mut writer: W) -> Result<()> {
+    let issue = remote.create(body_args)?;
+    writeln!(writer, "Issue created: {}", issue.web_url)?;
+    Ok(())
+}
+
+fn create_comment<W: Write>(
+    remote: Arc<dyn Issue>,
+    body_args: IssueCommentBodyArgs,
+    mut writer: W,
+) -> Result<()> {
+    let id = body_args.id;
+    remote.comment(body_args)?;
+    writeln!(writer, "Comment added to issue {}", id)?;
+    Ok(())
+}
+
+fn close<W: Write>(remote: Arc<dyn Issue>, id: i64, mut writer: W) -> Result<()> {
+    let issue = remote.close(id)?;
+    writeln!(writer, "Issue closed: {}", issue.web_url)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use crate::api_traits::NumberDeltaErr;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockIssue {
+        issues: Vec<IssueResponse>,
+        comment: Mutex<Option<IssueCommentBodyArgs>>,
+    }
+
+    impl Issue for MockIssue {
+        fn create(&self, args: IssueBodyArgs) -> Result<IssueResponse> {
+            Ok(IssueResponse::builder()
+                .id(24)
+                .title(args.title)
+                .web_url("https://gitlab.com/jordilin/gitlapi/-/issues/24".to_string())
+                .build()
+                .unwrap())
+        }
+
+        fn get(&self, _id: i64) -> Result<IssueResponse> {
+            Ok(self.issues[0].clone())
+        }
+
+        fn list(&self, _args: IssueListBodyArgs) -> Result<Vec<IssueResponse>> {
+            Ok(self.issues.clone())
+        }
+
+        fn num_pages(&self, _args: IssueListBodyArgs) -> Result<Option<u32>> {
+            todo!()
+        }
+
+        fn num_resources(&self, _args: IssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+            todo!()
+        }
+
+        fn comment(&self, args: IssueCommentBodyArgs) -> Result<()> {
+            *self.comment.lock().unwrap() = Some(args);
+            Ok(())
+        }
+
+        fn close(&self, id: i64) -> Result<IssueResponse> {
+            Ok(IssueResponse::builder()
+                .id(id)
+                .title("Test issue".to_string())
+                .web_url(format!(
+                    "https://gitlab.com/jordilin/gitlapi/-/issues/{}",
+                    id
+                ))
+                .build()
+                .unwrap())
+        }
+    }
+
+    fn issue() -> IssueResponse {
+        IssueResponse::builder()
+            .id(24)
+            .title("Test issue".to_string())
+            .description("Steps to reproduce".to_string())
+            .author("jdoe".to_string())
+            .state("opened".to_string())
+            .web_url("https://gitlab.com/jordilin/gitlapi/-/issues/24".to_string())
+            .created_at("2024-03-19T22:38:38Z".parse().unwrap())
+            .updated_at("2024-03-20T08:00:00Z".parse().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_list_issues() {
+        let remote = Arc::new(MockIssue {
+            issues: vec![issue()],
+            ..Default::default()
+        });
+        let body_args = IssueListBodyArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let cli_args = IssueListCliArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(ListRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        list_issues(remote, body_args, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "ID|Title|Author|State|URL|Created at\n\
+             24|Test issue|jdoe|opened|https://gitlab.com/jordilin/gitlapi/-/issues/24|2024-03-19T22:38:38Z\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_issue_details_with_optional_fields() {
+        let remote = Arc::new(MockIssue {
+            issues: vec![issue()],
+            ..Default::default()
+        });
+        let cli_args = IssueGetCliArgs::builder()
+            .id(24)
+            .get_args(
+                GetRemoteCliArgs::builder()
+                    .display_optional(true)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        get_issue_details(remote, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "ID|Title|Description|Author|State|URL|Created at|Updated at\n\
+             24|Test issue|Steps to reproduce|jdoe|opened|https://gitlab.com/jordilin/gitlapi/-/issues/24|2024-03-19T22:38:38Z|2024-03-20T08:00:00Z\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_create_comment_and_close_issue() {
+        let remote = Arc::new(MockIssue::default());
+        let mut writer = Vec::new();
+        create(
+            remote.clone(),
+            IssueBodyArgs::builder()
+                .title("Test issue".to_string())
+                .build()
+                .unwrap(),
+            &mut writer,
+        )
+        .unwrap();
+        let body_args = IssueCommentBodyArgs::builder()
+            .id(24)
+            .comment("Cannot reproduce".to_string())
+            .build()
+            .unwrap();
+        create_comment(remote.clone(), body_args, &mut writer).unwrap();
+        close(remote.clone(), 24, &mut writer).unwrap();
+        assert_eq!(
+            "Issue created: https://gitlab.com/jordilin/gitlapi/-/issues/24\n\
+             Comment added to issue 24\n\
+             Issue closed: https://gitlab.com/jordilin/gitlapi/-/issues/24\n",
+            String::from_utf8(writer).unwrap()
+        );
+        assert_eq!(
+            "Cannot reproduce",
+            remote.comment.lock().unwrap().as_ref().unwrap().comment
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::api_traits::{Label, Timestamp};
+use crate::cli::label::LabelOptions;
+use crate::config::ConfigProperties;
+use crate::display::{Column, DisplayBody};
+use crate::remote::{self, CacheType, ListBodyArgs};
+use crate::Result;
+use chrono::{DateTime, Utc};
+
+use super::common::{self, num_label_pages, num_label_resources};
+
+#[derive(Builder, Clone, Debug, Default)]
+pub struct LabelResponse {
+    pub id: i64,
+    pub name: String,
+    /// Hex color, ex. #d9534f
+    #[builder(default)]
+    pub color: String,
+    #[builder(default)]
+    pub description: String,
+}
+
+impl LabelResponse {
+    pub fn builder() -> LabelResponseBuilder {
+        LabelResponseBuilder::default()
+    }
+}
+
+impl From<LabelResponse> for DisplayBody {
+    fn from(label: LabelResponse) -> Self {
+        DisplayBody::new(vec![
+            Column::new("ID", label.id.to_string()),
+            Column::new("Name", label.name),
+            Column::new("Color", label.color),
+            Column::new("Description", label.description),
+        ])
+    }
+}
+
+impl Timestamp for LabelResponse {
+    fn created_at(&self) -> DateTime<Utc> {
+        // Labels carry no creation date, listed in the order of the remote.
+        DateTime::UNIX_EPOCH
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct LabelListBodyArgs {
+    pub list_args: Option<ListBodyArgs>,
+}
+
+impl LabelListBodyArgs {
+    pub fn builder() -> LabelListBodyArgsBuilder {
+        LabelListBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct LabelBodyArgs {
+    pub name: String,
+    /// Hex color, with or without the leading #
+    pub color: String,
+    #[builder(default)]
+    pub description: String,
+}
+
+impl LabelBodyArgs {
+    pub fn builder() -> LabelBodyArgsBuilder {
+        LabelBodyArgsBuilder::default()
+    }
+}
+
+pub fn execute(
+    options: LabelOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
+    match options {
+        LabelOptions::List(cli_args) => {
+            let remote = remote::get_label(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            if cli_args.num_pages {
+                return num_label_pages(remote, std::io::stdout());
+            }
+            if cli_args.num_resources {
+                return num_label_resources(remote, std::io::stdout());
+            }
+            let from_to_args = remote::validate_from_to_page(&cli_args)?;
+            let body_args = LabelListBodyArgs::builder()
+                .list_args(from_to_args)
+                .build()?;
+            common::list_labels(remote, body_args, cli_args, std::io::stdout())
+        }
+        LabelOptions::Create(body_args) => {
+            let remote = remote::get_label(domain, path, config, None, CacheType::None)?;
+            create(remote, body_args, std::io::stdout())
+        }
+    }
+}
+
+fn create<W: Write>(remote: Arc<dyn Label>, body_args: LabelBodyArgs, mut writer: W) -> Result<()> {
+    let label = remote.create(body_args)?;
+    writeln!(writer, "Label created: {}", label.name)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::api_traits::NumberDeltaErr;
+    use crate::remote::ListRemoteCliArgs;
+
+    use super::*;
+
+    struct MockLabel;
+
+    impl Label for MockLabel {
+        fn list(&self, _args: LabelListBodyArgs) -> Result<Vec<LabelResponse>> {
+            Ok(vec![LabelResponse::builder()
+                .id(1)
+                .name("bug".to_string())
+                .color("#d9534f".to_string())
+                .description("Something is broken".to_string())
+                .build()
+                .unwrap()])
+        }
+
+        fn num_pages(&self) -> Result<Option<u32>> {
+            Ok(Some(1))
+        }
+
+        fn num_resources(&self) -> Result<Option<NumberDeltaErr>> {
+            todo!()
+        }
+
+        fn create(&self, args: LabelBodyArgs) -> Result<LabelResponse> {
+            Ok(LabelResponse::builder()
+                .id(2)
+                .name(args.name)
+                .color(args.color)
+                .build()
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_list_labels() {
+        let body_args = LabelListBodyArgs::builder()
+            .list_args(None)
+            .build()
+            .unwrap();
+        let cli_args = ListRemoteCliArgs::builder().build().unwrap();
+        let mut writer = Vec::new();
+        common::list_labels(Arc::new(MockLabel), body_args, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "ID|Name|Color|Description\n1|bug|#d9534f|Something is broken\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_create_label() {
+        let body_args = LabelBodyArgs::builder()
+            .name("ui".to_string())
+            .color("#428BCA".to_string())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        create(Arc::new(MockLabel), body_args, &mut writer).unwrap();
+        assert_eq!("Label created: ui\n", String::from_utf8(writer).unwrap());
+    }
+}
@@ -1,22 +1,44 @@
-use crate::api_traits::{CommentMergeRequest, MergeRequest, RemoteProject, Timestamp};
-use crate::cli::merge_request::MergeRequestOptions;
+use crate::api_defaults::{DEFAULT_OPERATION_RETRY_WAIT_SECONDS, MAX_PARALLEL_REMOTE_QUERIES};
+use crate::api_traits::{
+    CommentMergeRequest, MergeQueue, MergeRequest, MergeRequestDependency, MergeRequestDiff,
+    MergeRequestDiscussion, MergeRequestReaction, MergeRequestRebase, MergeRequestReview,
+    MergeRequestSuggestion, RemoteProject, ReviewMergeRequest, Timestamp, UserInfo,
+};
+use crate::backoff::{Exponential, RetryOperation};
+use crate::cli::browse::BrowseOptions;
+use crate::cli::merge_request::{MergeQueueOptions, MergeRequestOptions};
 use crate::config::ConfigProperties;
 use crate::display::{Column, DisplayBody};
 use crate::error::{AddContext, GRError};
 use crate::git::Repo;
+use crate::http::throttle::{DynamicFixed, ThrottleStrategy};
 use crate::io::{CmdInfo, ShellResponse, TaskRunner};
-use crate::remote::{CacheCliArgs, CacheType, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs};
+use crate::remote::{
+    CacheCliArgs, CacheType, ConfigFilePath, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs,
+    RemoteURL,
+};
 use crate::shell::BlockingCommand;
-use crate::{dialog, display, exec, git, remote, Cmd, Result};
+use crate::time::{self, Seconds};
+use crate::{dialog, display, exec, git, log_info, remote, Cmd, Result};
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
 use std::fmt::{self, Display, Formatter};
 use std::{
+    collections::HashMap,
     fs::File,
     io::{BufRead, BufReader, Cursor, Write},
     sync::Arc,
 };
 
-use super::common::{self, get_user};
+use super::common;
 use super::project::{Member, Project};
+use super::user::UserCliArgs;
+use review::ReviewFile;
+
+pub mod owners;
+pub mod review;
+
+use owners::Owners;
 
 /// GPT_PROMPT is a template for the GPT prompt to generate a merge request
 /// description given a list of commit messages.
@@ -43,24 +65,40 @@ The formatted output that you will provide is as follows:
 
 Below are the changes:"#;
 
+/// Default review reminder. Can be overridden per domain and project with
+/// the `review_reminder` merge request setting. Placeholders {id}, {title},
+/// {author} and {age} are replaced by the merge request values.
+const DEFAULT_REVIEW_REMINDER: &str =
+    "Friendly reminder: this merge request has been awaiting review for {age}.";
+
 #[derive(Builder, Clone, Debug, Default)]
 #[builder(default)]
 pub struct MergeRequestResponse {
     pub id: i64,
     pub web_url: String,
     pub author: String,
-    pub updated_at: String,
+    pub updated_at: DateTime<Utc>,
     pub source_branch: String,
     pub sha: String,
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
     pub title: String,
     // For Github to filter pull requests from issues.
     pub pull_request: String,
     // Optional fields to display for get and list operations
     pub description: String,
-    pub merged_at: String,
+    pub merged_at: Option<DateTime<Utc>>,
     pub pipeline_id: Option<i64>,
     pub pipeline_url: Option<String>,
+    /// Latest commit of the source branch.
+    pub head_sha: String,
+    /// Usernames of the reviewers. Github only lists the requested reviewers
+    /// that have not submitted a review yet.
+    pub reviewers: Vec<String>,
+    /// Whether the source branch lives in a fork of the target project.
+    pub from_fork: bool,
+    /// Ref of the target project pointing at the head of the merge request.
+    /// Available even if the source branch lives in a fork.
+    pub head_ref: String,
 }
 
 impl MergeRequestResponse {
@@ -90,10 +128,10 @@ impl From<MergeRequestResponse> for DisplayBody {
                     .unwrap(),
                 Column::new("Author", mr.author),
                 Column::new("URL", mr.web_url),
-                Column::new("Updated at", mr.updated_at),
+                Column::new("Updated at", time::format_timestamp(&mr.updated_at)),
                 Column::builder()
                     .name("Merged at".to_string())
-                    .value(mr.merged_at)
+                    .value(time::format_optional_timestamp(mr.merged_at.as_ref()))
                     .optional(true)
                     .build()
                     .unwrap(),
@@ -115,8 +153,12 @@ impl From<MergeRequestResponse> for DisplayBody {
 }
 
 impl Timestamp for MergeRequestResponse {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        Some(self.updated_at)
     }
 }
 
@@ -165,13 +207,15 @@ pub struct MergeRequestBodyArgs {
     #[builder(default)]
     pub assignee: Member,
     #[builder(default)]
-    pub reviewer: Member,
+    pub reviewers: Vec<Member>,
     #[builder(default = "String::from(\"true\")")]
     pub remove_source_branch: String,
     #[builder(default)]
     pub draft: bool,
     #[builder(default)]
     pub amend: bool,
+    #[builder(default)]
+    pub labels: Vec<String>,
 }
 
 impl MergeRequestBodyArgs {
@@ -180,6 +224,43 @@ impl MergeRequestBodyArgs {
     }
 }
 
+/// How the changes get into the target branch. Gitlab sets the merge method
+/// per project, only squashing can be chosen when merging.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct MergeRequestMergeBodyArgs {
+    pub id: i64,
+    #[builder(default)]
+    pub message: Option<String>,
+    /// Squash the commits into a single one.
+    #[builder(default)]
+    pub squash: bool,
+    #[builder(default)]
+    pub delete_source_branch: bool,
+    /// Provider default when not given.
+    #[builder(default)]
+    pub method: Option<MergeMethod>,
+    /// Full SHA the head of the source branch is expected to be at.
+    #[builder(default)]
+    pub sha: Option<String>,
+    /// Queue the merge to happen once the pipeline succeeds instead of merging
+    /// right away. Gitlab merge when pipeline succeeds, Github auto-merge.
+    #[builder(default)]
+    pub auto_merge: bool,
+}
+
+impl MergeRequestMergeBodyArgs {
+    pub fn builder() -> MergeRequestMergeBodyArgsBuilder {
+        MergeRequestMergeBodyArgsBuilder::default()
+    }
+}
+
 #[derive(Builder, Clone)]
 pub struct MergeRequestListBodyArgs {
     pub state: MergeRequestState,
@@ -190,6 +271,20 @@ pub struct MergeRequestListBodyArgs {
     pub author: Option<Member>,
     #[builder(default)]
     pub reviewer: Option<Member>,
+    /// Gitlab group, including its subgroups, or Github organization to list
+    /// merge requests from instead of the project.
+    #[builder(default)]
+    pub group: Option<String>,
+    /// Merge requests must carry all these labels.
+    #[builder(default)]
+    pub labels: Vec<String>,
+    /// Milestone title. Github also takes the milestone number.
+    #[builder(default)]
+    pub milestone: Option<String>,
+    /// Filter by author, assignee or reviewer within the project instead of
+    /// across all the projects of the remote.
+    #[builder(default)]
+    pub project_only: bool,
 }
 
 impl MergeRequestListBodyArgs {
@@ -209,9 +304,13 @@ pub struct MergeRequestCliArgs {
     #[builder(default)]
     pub assignee: Option<String>,
     #[builder(default)]
-    pub reviewer: Option<String>,
+    pub reviewers: Vec<String>,
     #[builder(default)]
     pub rand_reviewer: bool,
+    /// Labels given in the command line, added to the ones from the
+    /// ownership rules.
+    #[builder(default)]
+    pub labels: Vec<String>,
     pub target_branch: Option<String>,
     #[builder(default)]
     pub target_repo: Option<String>,
@@ -228,12 +327,18 @@ pub struct MergeRequestCliArgs {
     pub force: bool,
     pub draft: bool,
     pub dry_run: bool,
+    /// Open the new merge request form in the browser instead of creating it.
+    #[builder(default)]
+    pub web: bool,
     #[builder(default)]
     pub summary: SummaryOptions,
     #[builder(default)]
     pub patch: bool,
     #[builder(default)]
     pub gpt_prompt: bool,
+    /// Merge requests that need to be merged before this one.
+    #[builder(default)]
+    pub depends_on: Vec<i64>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -253,13 +358,13 @@ impl MergeRequestCliArgs {
 /// Enum for filtering merge requests by user
 /// Me: current authenticated user
 /// Other: another username, provided by cli flags.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum MergeRequestUser {
     Me,
     Other(String),
 }
 
-#[derive(Builder)]
+#[derive(Builder, Clone)]
 pub struct MergeRequestListCliArgs {
     pub state: MergeRequestState,
     pub list_args: ListRemoteCliArgs,
@@ -270,6 +375,14 @@ pub struct MergeRequestListCliArgs {
     pub author: Option<MergeRequestUser>,
     #[builder(default)]
     pub reviewer: Option<MergeRequestUser>,
+    #[builder(default)]
+    pub group: Option<String>,
+    #[builder(default)]
+    pub labels: Vec<String>,
+    #[builder(default)]
+    pub milestone: Option<String>,
+    #[builder(default)]
+    pub project_only: bool,
 }
 
 impl MergeRequestListCliArgs {
@@ -280,6 +393,10 @@ impl MergeRequestListCliArgs {
             assignee: None,
             author: None,
             reviewer: None,
+            group: None,
+            labels: Vec::new(),
+            milestone: None,
+            project_only: false,
         }
     }
     pub fn builder() -> MergeRequestListCliArgsBuilder {
@@ -299,11 +416,109 @@ impl MergeRequestGetCliArgs {
     }
 }
 
+#[derive(Builder, Clone)]
+pub struct MergeRequestNagCliArgs {
+    /// Open merge requests with no updates for longer than this
+    pub older_than: Seconds,
+    #[builder(default)]
+    pub report: bool,
+    #[builder(default)]
+    pub yes: bool,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl MergeRequestNagCliArgs {
+    pub fn builder() -> MergeRequestNagCliArgsBuilder {
+        MergeRequestNagCliArgsBuilder::default()
+    }
+}
+
+/// Entry of a merge request in a Gitlab merge train or a Github merge queue.
+#[derive(Builder, Clone, Debug)]
+pub struct MergeQueueEntry {
+    pub id: i64,
+    /// Position in the queue, starting at 1. None if the merge request has
+    /// already left the queue.
+    #[builder(default)]
+    pub position: Option<u32>,
+    pub status: String,
+    #[builder(default)]
+    pub target_branch: String,
+    /// Estimated time to merge
+    #[builder(default)]
+    pub eta: Option<Seconds>,
+}
+
+impl MergeQueueEntry {
+    pub fn builder() -> MergeQueueEntryBuilder {
+        MergeQueueEntryBuilder::default()
+    }
+}
+
+impl From<MergeQueueEntry> for DisplayBody {
+    fn from(entry: MergeQueueEntry) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("ID", entry.id.to_string()),
+                Column::new(
+                    "Position",
+                    entry.position.map(|p| p.to_string()).unwrap_or_default(),
+                ),
+                Column::new("Status", entry.status),
+                Column::new("Target branch", entry.target_branch),
+                Column::new(
+                    "ETA",
+                    entry.eta.map(time::human_duration).unwrap_or_default(),
+                ),
+            ],
+        }
+    }
+}
+
+/// Server-side rebase of a merge request in progress or finished.
+#[derive(Builder, Clone, Debug)]
+pub struct RebaseStatus {
+    pub id: i64,
+    #[builder(default)]
+    pub in_progress: bool,
+    /// Head commit of the source branch.
+    #[builder(default)]
+    pub sha: String,
+    /// Reason the rebase failed, ex. conflicts with the target branch.
+    #[builder(default)]
+    pub error: Option<String>,
+}
+
+impl RebaseStatus {
+    pub fn builder() -> RebaseStatusBuilder {
+        RebaseStatusBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct MergeRequestRebaseCliArgs {
+    pub id: i64,
+    /// Seconds to wait between polls of the rebase status.
+    #[builder(default = "Seconds::new(2)")]
+    pub interval: Seconds,
+    /// Give up waiting for the rebase to finish after this long.
+    #[builder(default = "Seconds::new(300)")]
+    pub timeout: Seconds,
+}
+
+impl MergeRequestRebaseCliArgs {
+    pub fn builder() -> MergeRequestRebaseCliArgsBuilder {
+        MergeRequestRebaseCliArgsBuilder::default()
+    }
+}
+
 #[derive(Builder)]
 pub struct CommentMergeRequestCliArgs {
     pub id: i64,
     pub comment: Option<String>,
     pub comment_from_file: Option<String>,
+    #[builder(default)]
+    pub retry: u32,
 }
 
 impl CommentMergeRequestCliArgs {
@@ -328,6 +543,10 @@ impl CommentMergeRequestListCliArgs {
 pub struct CommentMergeRequestListBodyArgs {
     pub id: i64,
     pub list_args: Option<ListBodyArgs>,
+    /// Gather the reactions of each comment. Costs a request per comment in
+    /// remotes that do not include them in the comment payload.
+    #[builder(default)]
+    pub reactions: bool,
 }
 
 impl CommentMergeRequestListBodyArgs {
@@ -336,7 +555,7 @@ impl CommentMergeRequestListBodyArgs {
     }
 }
 
-#[derive(Builder)]
+#[derive(Builder, Clone)]
 pub struct CommentMergeRequestBodyArgs {
     pub id: i64,
     pub comment: String,
@@ -353,7 +572,9 @@ pub struct Comment {
     pub id: i64,
     pub body: String,
     pub author: String,
-    pub created_at: String,
+    pub created_at: Option<DateTime<Utc>>,
+    #[builder(default)]
+    pub reactions: Vec<Reaction>,
 }
 
 impl Comment {
@@ -363,8 +584,8 @@ impl Comment {
 }
 
 impl Timestamp for Comment {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at.unwrap_or_default()
     }
 }
 
@@ -374,11 +595,360 @@ impl From<Comment> for DisplayBody {
             Column::new("ID", comment.id.to_string()),
             Column::new("Body", comment.body),
             Column::new("Author", comment.author),
-            Column::new("Created at", comment.created_at),
+            Column::new(
+                "Created at",
+                time::format_optional_timestamp(comment.created_at.as_ref()),
+            ),
+            Column::builder()
+                .name("Reactions".to_string())
+                .value(
+                    comment
+                        .reactions
+                        .iter()
+                        .map(|reaction| reaction.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+                .optional(true)
+                .build()
+                .unwrap(),
+        ])
+    }
+}
+
+/// Number of users that reacted with a given emoji. Emoji are named after
+/// Gitlab award emoji, ex. thumbsup, tada.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reaction {
+    pub emoji: String,
+    pub count: u32,
+}
+
+impl Reaction {
+    pub fn new(emoji: &str, count: u32) -> Self {
+        Reaction {
+            emoji: emoji.to_string(),
+            count,
+        }
+    }
+
+    /// Counts the emoji awarded by each user, most used first.
+    pub fn count<I: IntoIterator<Item = String>>(emojis: I) -> Vec<Reaction> {
+        let mut reactions: Vec<Reaction> = Vec::new();
+        for emoji in emojis {
+            match reactions
+                .iter_mut()
+                .find(|reaction| reaction.emoji == emoji)
+            {
+                Some(reaction) => reaction.count += 1,
+                None => reactions.push(Reaction::new(&emoji, 1)),
+            }
+        }
+        reactions.sort_by_key(|reaction| Reverse(reaction.count));
+        reactions
+    }
+}
+
+impl Display for Reaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, ":{}: {}", self.emoji, self.count)
+    }
+}
+
+/// Suggested replacement for a line of a file changed in a merge request.
+#[derive(Builder, Clone, Debug)]
+pub struct SuggestionBodyArgs {
+    pub id: i64,
+    /// Path of the file in the source branch
+    pub file: String,
+    /// Line in the new version of the file
+    pub line: u32,
+    pub replacement: String,
+    /// Text shown above the suggested change
+    #[builder(default)]
+    pub message: Option<String>,
+}
+
+impl SuggestionBodyArgs {
+    pub fn builder() -> SuggestionBodyArgsBuilder {
+        SuggestionBodyArgsBuilder::default()
+    }
+
+    /// Comment body with the replacement in a fenced suggestion block. The
+    /// info string differs between remotes, ex. `suggestion:-0+0` in Gitlab.
+    /// The fence is longer than any run of backticks in the replacement, so
+    /// code blocks can be suggested too.
+    pub fn body(&self, info: &str) -> String {
+        let longest_run = self
+            .replacement
+            .split(|c| c != '`')
+            .map(str::len)
+            .max()
+            .unwrap_or_default();
+        let fence = "`".repeat(longest_run.max(2) + 1);
+        let block = format!("{}{}\n{}\n{}", fence, info, self.replacement, fence);
+        match &self.message {
+            Some(message) => format!("{}\n\n{}", message, block),
+            None => block,
+        }
+    }
+}
+
+/// Suggested change not yet applied to the merge request.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub id: i64,
+    pub file: String,
+    pub line: i64,
+    pub author: String,
+    pub replacement: String,
+}
+
+impl Suggestion {
+    pub fn builder() -> SuggestionBuilder {
+        SuggestionBuilder::default()
+    }
+}
+
+impl From<Suggestion> for DisplayBody {
+    fn from(suggestion: Suggestion) -> Self {
+        DisplayBody::new(vec![
+            Column::new("ID", suggestion.id.to_string()),
+            Column::new("File", suggestion.file),
+            Column::new("Line", suggestion.line.to_string()),
+            Column::new("Author", suggestion.author),
+            Column::new("Replacement", suggestion.replacement),
+        ])
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct SuggestionApplyCliArgs {
+    pub id: i64,
+    /// Suggestions to apply. The user is prompted to select them if empty
+    /// and `all` is not set.
+    #[builder(default)]
+    pub suggestion_ids: Vec<i64>,
+    #[builder(default)]
+    pub all: bool,
+    #[builder(default)]
+    pub message: Option<String>,
+}
+
+impl SuggestionApplyCliArgs {
+    pub fn builder() -> SuggestionApplyCliArgsBuilder {
+        SuggestionApplyCliArgsBuilder::default()
+    }
+}
+
+/// Thread of comments on a merge request, either on the merge request itself
+/// or on a line of its diff.
+#[derive(Builder, Clone)]
+pub struct Discussion {
+    /// Id replies are posted to. In Github, the id of the review comment
+    /// that started the thread.
+    pub id: String,
+    /// File the thread is on. Empty for threads on the merge request itself.
+    #[builder(default)]
+    pub file: String,
+    #[builder(default)]
+    pub line: Option<i64>,
+    #[builder(default)]
+    pub resolved: bool,
+    /// Comments of the thread, oldest first.
+    pub notes: Vec<Comment>,
+}
+
+impl Discussion {
+    pub fn builder() -> DiscussionBuilder {
+        DiscussionBuilder::default()
+    }
+}
+
+/// A comment of a discussion along with the thread it belongs to, so threads
+/// can be listed one comment per row.
+#[derive(Clone)]
+struct DiscussionNote {
+    discussion_id: String,
+    file: String,
+    line: Option<i64>,
+    resolved: bool,
+    note: Comment,
+}
+
+impl From<DiscussionNote> for DisplayBody {
+    fn from(note: DiscussionNote) -> Self {
+        DisplayBody::new(vec![
+            Column::new("Discussion", note.discussion_id),
+            Column::new("ID", note.note.id.to_string()),
+            Column::new("Author", note.note.author),
+            Column::new("Body", note.note.body),
+            Column::new(
+                "Location",
+                match note.line {
+                    Some(line) => format!("{}:{}", note.file, line),
+                    None => note.file,
+                },
+            ),
+            Column::new("Resolved", note.resolved.to_string()),
+            Column::builder()
+                .name("Created at".to_string())
+                .value(time::format_optional_timestamp(
+                    note.note.created_at.as_ref(),
+                ))
+                .optional(true)
+                .build()
+                .unwrap(),
+        ])
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct DiscussionReplyBodyArgs {
+    /// Id of the merge request
+    pub id: i64,
+    pub discussion_id: String,
+    pub body: String,
+}
+
+impl DiscussionReplyBodyArgs {
+    pub fn builder() -> DiscussionReplyBodyArgsBuilder {
+        DiscussionReplyBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FileChangeStatus {
+    Added,
+    Deleted,
+    Renamed,
+    #[default]
+    Modified,
+}
+
+impl Display for FileChangeStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FileChangeStatus::Added => write!(f, "added"),
+            FileChangeStatus::Deleted => write!(f, "deleted"),
+            FileChangeStatus::Renamed => write!(f, "renamed"),
+            FileChangeStatus::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// File changed in a merge request along with its patch.
+#[derive(Builder, Clone, Debug, Default, PartialEq)]
+pub struct FileChange {
+    /// Path of the file in the source branch
+    pub path: String,
+    /// Path of the file in the target branch. Differs from `path` when the
+    /// file has been renamed.
+    pub old_path: String,
+    #[builder(default)]
+    pub status: FileChangeStatus,
+    #[builder(default)]
+    pub additions: u32,
+    #[builder(default)]
+    pub deletions: u32,
+    /// Hunks of the change. Empty for binary files and for changes too large
+    /// for the remote to render.
+    #[builder(default)]
+    pub patch: String,
+}
+
+impl FileChange {
+    pub fn builder() -> FileChangeBuilder {
+        FileChangeBuilder::default()
+    }
+
+    /// Change in unified diff format, as given by `git diff`.
+    pub fn unified_diff(&self) -> String {
+        let mut diff = format!("diff --git a/{} b/{}\n", self.old_path, self.path);
+        if self.status == FileChangeStatus::Renamed {
+            diff.push_str(&format!(
+                "rename from {}\nrename to {}\n",
+                self.old_path, self.path
+            ));
+        }
+        if self.patch.is_empty() {
+            return diff;
+        }
+        let old_path = match self.status {
+            FileChangeStatus::Added => "/dev/null".to_string(),
+            _ => format!("a/{}", self.old_path),
+        };
+        let path = match self.status {
+            FileChangeStatus::Deleted => "/dev/null".to_string(),
+            _ => format!("b/{}", self.path),
+        };
+        diff.push_str(&format!("--- {}\n+++ {}\n{}", old_path, path, self.patch));
+        if !self.patch.ends_with('\n') {
+            diff.push('\n');
+        }
+        diff
+    }
+}
+
+impl From<FileChange> for DisplayBody {
+    fn from(change: FileChange) -> Self {
+        DisplayBody::new(vec![
+            Column::new("File", change.path),
+            Column::new("Status", change.status.to_string()),
+            Column::count("Additions", change.additions as u64),
+            Column::count("Deletions", change.deletions as u64),
         ])
     }
 }
 
+impl Timestamp for FileChange {
+    fn created_at(&self) -> DateTime<Utc> {
+        // Changes are listed in the order of the diff, no creation date.
+        DateTime::UNIX_EPOCH
+    }
+}
+
+/// Outcome of a review. Github submits it as the review event, Gitlab has no
+/// review object and maps it to approvals and notes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReviewEvent {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct ReviewBodyArgs {
+    pub id: i64,
+    pub event: ReviewEvent,
+    /// Review comment. Optional when approving.
+    #[builder(default)]
+    pub body: String,
+}
+
+impl ReviewBodyArgs {
+    pub fn builder() -> ReviewBodyArgsBuilder {
+        ReviewBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct ReactionBodyArgs {
+    pub id: i64,
+    /// React to a comment of the merge request instead of the merge request
+    /// itself.
+    #[builder(default)]
+    pub comment_id: Option<i64>,
+    /// Emoji name without surrounding colons, ex. thumbsup
+    pub emoji: String,
+}
+
+impl ReactionBodyArgs {
+    pub fn builder() -> ReactionBodyArgsBuilder {
+        ReactionBodyArgsBuilder::default()
+    }
+}
+
 pub fn execute(
     options: MergeRequestOptions,
     config: Arc<dyn ConfigProperties>,
@@ -386,7 +956,10 @@ pub fn execute(
     path: String,
 ) -> Result<()> {
     match options {
-        MergeRequestOptions::Create(cli_args) => {
+        MergeRequestOptions::Create(mut cli_args) => {
+            if cli_args.target_branch.is_none() {
+                cli_args.target_branch = config.merge_request_target_branch().map(String::from);
+            }
             let mr_remote = remote::get_mr(
                 domain.clone(),
                 path.clone(),
@@ -394,6 +967,13 @@ pub fn execute(
                 Some(&cli_args.cache_args),
                 CacheType::File,
             )?;
+            let dependency_remote = remote::get_merge_request_dependency(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                None,
+                CacheType::None,
+            )?;
             let project_remote = remote::get_project(
                 domain,
                 path,
@@ -401,7 +981,8 @@ pub fn execute(
                 Some(&cli_args.cache_args),
                 CacheType::File,
             )?;
-            if let Some(commit_message) = &cli_args.commit {
+            let browse_remote = project_remote.clone();
+            if let Some(commit_message) = cli_args.commit.as_ref().filter(|_| !cli_args.dry_run) {
                 git::add(&BlockingCommand)?;
                 git::commit(&BlockingCommand, commit_message)?;
             }
@@ -436,29 +1017,97 @@ pub fn execute(
             if cli_args.patch {
                 return patch(mr_body, &cli_args);
             }
-            open(mr_remote, config, mr_body, &cli_args)
+            open(
+                mr_remote,
+                dependency_remote,
+                browse_remote,
+                config,
+                mr_body,
+                &cli_args,
+            )
         }
         MergeRequestOptions::List(cli_args) => list_merge_requests(domain, path, config, cli_args),
-        MergeRequestOptions::Merge { id } => {
+        MergeRequestOptions::ListRepoSet { .. } => {
+            unreachable!("Not bound to a project, see list_merge_requests_repo_set")
+        }
+        MergeRequestOptions::Merge {
+            id,
+            retry,
+            message,
+            sha,
+            auto_merge,
+            squash,
+            delete_source_branch,
+            method,
+        } => {
+            let dependency_remote = remote::get_merge_request_dependency(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                None,
+                CacheType::None,
+            )?;
+            check_dependencies(dependency_remote, id)?;
             let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
-            merge(remote, id)
+            let retry = RetryOperation::new(
+                retry,
+                Seconds::new(DEFAULT_OPERATION_RETRY_WAIT_SECONDS),
+                Box::new(Exponential),
+                Box::new(DynamicFixed),
+            );
+            let args = MergeRequestMergeBodyArgs::builder()
+                .id(id)
+                .message(message)
+                .sha(sha)
+                .auto_merge(auto_merge)
+                .squash(squash)
+                .delete_source_branch(delete_source_branch)
+                .method(method)
+                .build()?;
+            merge(remote, args, retry, std::io::stdout())
         }
         MergeRequestOptions::Checkout { id } => {
             // TODO: It should propagate the cache cli args.
             let remote = remote::get_mr(domain, path, config, None, CacheType::File)?;
-            checkout(remote, id)
+            checkout(remote, id, Arc::new(BlockingCommand), std::io::stdout())
         }
         MergeRequestOptions::Close { id } => {
             let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
             close(remote, id)
         }
+        MergeRequestOptions::Diff { cli_args, stat } => {
+            let remote = remote::get_mr_diff(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            diff(remote, cli_args, stat, std::io::stdout())
+        }
+        MergeRequestOptions::ToggleDraft { id, draft } => {
+            let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
+            toggle_draft(remote, id, draft, std::io::stdout())
+        }
         MergeRequestOptions::CreateComment(cli_args) => {
             let remote = remote::get_comment_mr(domain, path, config, None, CacheType::None)?;
+            let retry = RetryOperation::new(
+                cli_args.retry,
+                Seconds::new(DEFAULT_OPERATION_RETRY_WAIT_SECONDS),
+                Box::new(Exponential),
+                Box::new(DynamicFixed),
+            );
             if let Some(comment_file) = &cli_args.comment_from_file {
                 let reader = get_reader_file_cli(comment_file)?;
-                create_comment(remote, cli_args, Some(reader))
+                create_comment(remote, cli_args, Some(reader), retry, std::io::stdout())
             } else {
-                create_comment(remote, cli_args, None::<Cursor<&str>>)
+                create_comment(
+                    remote,
+                    cli_args,
+                    None::<Cursor<&str>>,
+                    retry,
+                    std::io::stdout(),
+                )
             }
         }
         MergeRequestOptions::ListComment(cli_args) => {
@@ -473,6 +1122,7 @@ pub fn execute(
             let body_args = CommentMergeRequestListBodyArgs::builder()
                 .id(cli_args.id)
                 .list_args(from_to_args)
+                .reactions(cli_args.list_args.get_args.display_optional)
                 .build()?;
             if cli_args.list_args.num_pages {
                 return common::num_comment_merge_request_pages(
@@ -504,36 +1154,190 @@ pub fn execute(
             let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
             approve(remote, id, std::io::stdout())
         }
-    }
-}
-
-pub fn get_reader_file_cli(file_path: &str) -> Result<Box<dyn BufRead + Send + Sync>> {
-    if file_path == "-" {
-        Ok(Box::new(BufReader::new(std::io::stdin())))
-    } else {
-        let file = File::open(file_path).err_context(GRError::PreconditionNotMet(format!(
-            "Cannot open file {}",
-            file_path
-        )))?;
-        Ok(Box::new(BufReader::new(file)))
-    }
-}
-
-fn get_filter_user(
-    user: &Option<MergeRequestUser>,
-    domain: &str,
-    path: &str,
-    config: &Arc<dyn ConfigProperties>,
-    list_args: &ListRemoteCliArgs,
-) -> Result<Option<Member>> {
-    let member = match user {
-        Some(MergeRequestUser::Me) => Some(get_user(domain, path, config, list_args)?),
-        // TODO filter by specific username, not necessarily the
-        // authenticated user.
-        _ => None,
-    };
-    Ok(member)
-}
+        MergeRequestOptions::EditComment {
+            id,
+            comment_id,
+            body,
+        } => {
+            let remote = remote::get_comment_mr(domain, path, config, None, CacheType::None)?;
+            edit_comment(remote, id, comment_id, body, std::io::stdout())
+        }
+        MergeRequestOptions::DeleteComment {
+            id,
+            comment_id,
+            yes,
+        } => {
+            let remote = remote::get_comment_mr(domain, path, config, None, CacheType::None)?;
+            dialog::confirm_action(
+                &format!("Delete comment {} of merge request {}?", comment_id, id),
+                yes,
+            )?;
+            delete_comment(remote, id, comment_id, std::io::stdout())
+        }
+        MergeRequestOptions::ListDiscussions(cli_args) => {
+            let remote = remote::get_mr_discussion(domain, path, config, None, CacheType::None)?;
+            list_discussions(remote, cli_args, std::io::stdout())
+        }
+        MergeRequestOptions::ReplyDiscussion(body_args) => {
+            let remote = remote::get_mr_discussion(domain, path, config, None, CacheType::None)?;
+            reply_discussion(remote, body_args, std::io::stdout())
+        }
+        MergeRequestOptions::Suggest(body_args) => {
+            let remote = remote::get_mr_suggestion(domain, path, config, None, CacheType::None)?;
+            suggest(remote, body_args, std::io::stdout())
+        }
+        MergeRequestOptions::ListSuggestions(cli_args) => {
+            let remote = remote::get_mr_suggestion(domain, path, config, None, CacheType::None)?;
+            list_suggestions(remote, cli_args, std::io::stdout())
+        }
+        MergeRequestOptions::ApplySuggestions(cli_args) => {
+            let remote = remote::get_mr_suggestion(domain, path, config, None, CacheType::None)?;
+            apply_suggestions(
+                remote,
+                cli_args,
+                |suggestions| {
+                    let items = suggestions
+                        .iter()
+                        .map(|suggestion| {
+                            format!(
+                                "{}:{} by {}: {}",
+                                suggestion.file,
+                                suggestion.line,
+                                suggestion.author,
+                                suggestion.replacement.lines().next().unwrap_or_default()
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    Ok(dialog::multi_select("Suggestions to apply", &items)?
+                        .into_iter()
+                        .map(|index| suggestions[index].id)
+                        .collect())
+                },
+                std::io::stdout(),
+            )
+        }
+        MergeRequestOptions::ExportReview { id } => {
+            let remote = remote::get_mr(domain, path, config, None, CacheType::None)?;
+            export_review(remote, id, std::io::stdout())
+        }
+        MergeRequestOptions::SubmitReview { file, force } => {
+            let mut data = String::new();
+            get_reader_file_cli(&file)?.read_to_string(&mut data)?;
+            let review = ReviewFile::parse(&data)?;
+            let mr_remote = remote::get_mr(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                None,
+                CacheType::None,
+            )?;
+            let review_remote = remote::get_mr_review(domain, path, config, None, CacheType::None)?;
+            submit_review(mr_remote, review_remote, review, force, std::io::stdout())
+        }
+        MergeRequestOptions::Review(body_args) => {
+            let remote = remote::get_review_mr(domain, path, config, None, CacheType::None)?;
+            review(remote, body_args, std::io::stdout())
+        }
+        MergeRequestOptions::React(body_args) => {
+            let remote = remote::get_mr_reaction(domain, path, config, None, CacheType::None)?;
+            react(remote, body_args, std::io::stdout())
+        }
+        MergeRequestOptions::Nag(cli_args) => {
+            let mr_remote = remote::get_mr(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            let comment_remote =
+                remote::get_comment_mr(domain, path, config.clone(), None, CacheType::None)?;
+            nag(
+                mr_remote,
+                comment_remote,
+                config.merge_request_review_reminder(),
+                cli_args,
+                time::now_epoch_seconds,
+                std::io::stdout(),
+            )
+        }
+        MergeRequestOptions::Rebase(cli_args) => {
+            let remote =
+                remote::get_merge_request_rebase(domain, path, config, None, CacheType::None)?;
+            rebase(remote, cli_args, &DynamicFixed, std::io::stdout())
+        }
+        MergeRequestOptions::Queue(options) => match options {
+            MergeQueueOptions::Add { id } => {
+                let remote = remote::get_merge_queue(domain, path, config, None, CacheType::None)?;
+                queue_add(remote, id, std::io::stdout())
+            }
+            MergeQueueOptions::Remove { id } => {
+                let remote = remote::get_merge_queue(domain, path, config, None, CacheType::None)?;
+                queue_remove(remote, id, std::io::stdout())
+            }
+            MergeQueueOptions::Status(cli_args) => {
+                // Queue moves fast, always get a fresh status.
+                let remote = remote::get_merge_queue(domain, path, config, None, CacheType::None)?;
+                queue_status(remote, cli_args, std::io::stdout())
+            }
+        },
+    }
+}
+
+pub fn get_reader_file_cli(file_path: &str) -> Result<Box<dyn BufRead + Send + Sync>> {
+    if file_path == "-" {
+        Ok(Box::new(BufReader::new(std::io::stdin())))
+    } else {
+        let file = File::open(file_path).err_context(GRError::PreconditionNotMet(format!(
+            "Cannot open file {}",
+            file_path
+        )))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Resolves the author, assignee and reviewer filters to members. Distinct
+/// users are looked up in parallel and each of them only once, ex. the
+/// authenticated user being both the author and the assignee. Lookups are
+/// user operations, cached per domain and username across runs.
+fn resolve_filter_users(
+    remote: Arc<dyn UserInfo + Send + Sync>,
+    users: &[&Option<MergeRequestUser>],
+    get_args: &GetRemoteCliArgs,
+) -> Result<Vec<Option<Member>>> {
+    let mut unique_users: Vec<MergeRequestUser> = Vec::new();
+    for user in users.iter().filter_map(|user| user.as_ref()) {
+        if !unique_users.contains(user) {
+            unique_users.push(user.clone());
+        }
+    }
+    let cmds = unique_users.into_iter().map(|user| {
+        let remote = remote.clone();
+        let get_args = get_args.clone();
+        let cmd: Cmd<(MergeRequestUser, Member)> = Box::new(move || {
+            let member = match &user {
+                MergeRequestUser::Me => remote.get_auth_user()?,
+                MergeRequestUser::Other(username) => remote.get(
+                    &UserCliArgs::builder()
+                        .username(username.clone())
+                        .get_args(get_args)
+                        .build()?,
+                )?,
+            };
+            Ok((user, member))
+        });
+        cmd
+    });
+    let mut members = HashMap::new();
+    for result in exec::parallel_stream(cmds) {
+        let (user, member) = result?;
+        members.insert(user, member);
+    }
+    Ok(users
+        .iter()
+        .map(|user| user.as_ref().and_then(|user| members.get(user).cloned()))
+        .collect())
+}
 
 pub fn list_merge_requests(
     domain: String,
@@ -541,56 +1345,219 @@ pub fn list_merge_requests(
     config: Arc<dyn ConfigProperties>,
     cli_args: MergeRequestListCliArgs,
 ) -> Result<()> {
-    // Author, assignee and reviewer are mutually exclusive filters checked on
-    // cli's flags. While we do sequential calls to retrieve them it is a very
-    // fast operation. Only one ends up calling the remote to retrieve it's id.
-    let author = get_filter_user(
-        &cli_args.author,
-        &domain,
-        &path,
-        &config,
-        &cli_args.list_args,
+    let user_remote = remote::get_auth_user(
+        domain.clone(),
+        path.clone(),
+        config.clone(),
+        Some(&cli_args.list_args.get_args.cache_args),
+        CacheType::File,
     )?;
-
-    let assignee = get_filter_user(
-        &cli_args.assignee,
-        &domain,
-        &path,
-        &config,
-        &cli_args.list_args,
+    let body_args = list_body_args(user_remote, &cli_args)?;
+    let remote = remote::get_mr(
+        domain,
+        path,
+        config,
+        Some(&cli_args.list_args.get_args.cache_args),
+        CacheType::File,
     )?;
+    if cli_args.list_args.num_pages {
+        return common::num_merge_request_pages(remote, body_args, std::io::stdout());
+    }
+    if cli_args.list_args.num_resources {
+        return common::num_merge_request_resources(remote, body_args, std::io::stdout());
+    }
+    list(remote, body_args, cli_args, std::io::stdout())
+}
 
-    let reviewer = get_filter_user(
-        &cli_args.reviewer,
-        &domain,
-        &path,
-        &config,
-        &cli_args.list_args,
-    )?;
+/// Where merge requests listed across several remotes come from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MergeRequestSource {
+    Domain,
+    Repo,
+}
+
+impl Display for MergeRequestSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeRequestSource::Domain => write!(f, "Domain"),
+            MergeRequestSource::Repo => write!(f, "Repo"),
+        }
+    }
+}
 
+/// Merge request listed along with the merge requests of other domains or
+/// projects. Displayed with an additional column naming its source.
+#[derive(Clone)]
+pub struct SourcedMergeRequest {
+    pub source: MergeRequestSource,
+    pub name: String,
+    pub merge_request: MergeRequestResponse,
+}
+
+impl From<SourcedMergeRequest> for DisplayBody {
+    fn from(mr: SourcedMergeRequest) -> DisplayBody {
+        let mut body: DisplayBody = mr.merge_request.into();
+        body.columns
+            .insert(0, Column::new(mr.source.to_string(), mr.name));
+        body
+    }
+}
+
+/// Lists the merge requests of a set of projects declared in the `[repos]`
+/// table of the config, ex.
+///
+/// ```toml
+/// [repos.watchlist]
+/// repos = ["github.com/jordilin/gitar", "gitlab.com/jordilin/gitlapi"]
+/// ```
+pub fn list_merge_requests_repo_set(
+    repos: Vec<String>,
+    cli_args: MergeRequestListCliArgs,
+    config_file_path: ConfigFilePath,
+) -> Result<()> {
+    if repos.is_empty() {
+        return Err(GRError::ConfigurationError("No repos in the repo set".to_string()).into());
+    }
+    let urls = repos
+        .iter()
+        .map(|repo| match repo.split_once('/') {
+            Some((domain, path)) if !domain.is_empty() && path.contains('/') => Ok((
+                repo.clone(),
+                RemoteURL::new(domain.to_string(), path.to_string()),
+            )),
+            _ => Err(GRError::ConfigurationError(format!(
+                "Invalid repo {}. Expected DOMAIN/OWNER/PROJECT_NAME",
+                repo
+            ))
+            .into()),
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+    list_merge_requests_from(
+        MergeRequestSource::Repo,
+        repos,
+        cli_args,
+        move |repo, cli_args| {
+            remote_merge_requests(config_file_path.clone(), &urls[repo], cli_args)
+        },
+        std::io::stdout(),
+    )
+}
+
+/// Merge requests of a remote as filtered by the list cli args.
+pub fn remote_merge_requests(
+    config_file_path: ConfigFilePath,
+    url: &RemoteURL,
+    cli_args: MergeRequestListCliArgs,
+) -> Result<Vec<MergeRequestResponse>> {
+    let config = remote::read_config(config_file_path, url)?;
+    let user_remote = remote::get_auth_user(
+        url.domain().to_string(),
+        url.path().to_string(),
+        config.clone(),
+        Some(&cli_args.list_args.get_args.cache_args),
+        CacheType::File,
+    )?;
+    let body_args = list_body_args(user_remote, &cli_args)?;
     let remote = remote::get_mr(
-        domain,
-        path,
+        url.domain().to_string(),
+        url.path().to_string(),
         config,
         Some(&cli_args.list_args.get_args.cache_args),
         CacheType::File,
     )?;
+    remote.list(body_args)
+}
+
+/// Lists merge requests from several domains or projects, querying at most
+/// `MAX_PARALLEL_REMOTE_QUERIES` of them at the same time. A source that
+/// fails is reported and skipped, so an unreachable remote does not hide the
+/// merge requests in the rest. Results are grouped by source in the given
+/// order.
+pub fn list_merge_requests_from<W: Write>(
+    source: MergeRequestSource,
+    names: Vec<String>,
+    cli_args: MergeRequestListCliArgs,
+    list_source: impl Fn(&str, MergeRequestListCliArgs) -> Result<Vec<MergeRequestResponse>>
+        + Send
+        + Sync
+        + 'static,
+    mut writer: W,
+) -> Result<()> {
+    let list_source = Arc::new(list_source);
+    let cmds = names.iter().map(|name| {
+        let name = name.clone();
+        let cli_args = cli_args.clone();
+        let list_source = list_source.clone();
+        let cmd: Cmd<(String, Result<Vec<MergeRequestResponse>>)> = Box::new(move || {
+            let merge_requests = list_source(&name, cli_args);
+            Ok((name, merge_requests))
+        });
+        cmd
+    });
+    let mut results = HashMap::new();
+    for result in exec::bounded_parallel_stream(cmds, MAX_PARALLEL_REMOTE_QUERIES) {
+        let (name, merge_requests) = result?;
+        results.insert(name, merge_requests);
+    }
+    let mut rows = Vec::new();
+    let mut num_failed = 0;
+    for name in names.iter() {
+        match results.remove(name) {
+            Some(Ok(merge_requests)) => rows.extend(merge_requests.into_iter().map(
+                |merge_request| SourcedMergeRequest {
+                    source,
+                    name: name.clone(),
+                    merge_request,
+                },
+            )),
+            Some(Err(err)) => {
+                num_failed += 1;
+                eprintln!("Skipping {}: {:#}", name, err);
+            }
+            None => {}
+        }
+    }
+    if num_failed == names.len() {
+        return Err(GRError::ApplicationError(format!(
+            "Could not list merge requests in any {}",
+            source.to_string().to_lowercase()
+        ))
+        .into());
+    }
+    if rows.is_empty() {
+        writer.write_all(b"No resources found.\n")?;
+        return Ok(());
+    }
+    display::print(&mut writer, rows, cli_args.list_args.get_args)
+}
 
+/// Body args of a merge request listing, with the user filters resolved to
+/// members.
+pub fn list_body_args(
+    user_remote: Arc<dyn UserInfo + Send + Sync>,
+    cli_args: &MergeRequestListCliArgs,
+) -> Result<MergeRequestListBodyArgs> {
+    let mut members = resolve_filter_users(
+        user_remote,
+        &[&cli_args.author, &cli_args.assignee, &cli_args.reviewer],
+        &cli_args.list_args.get_args,
+    )?
+    .into_iter();
+    let author = members.next().flatten();
+    let assignee = members.next().flatten();
+    let reviewer = members.next().flatten();
     let from_to_args = remote::validate_from_to_page(&cli_args.list_args)?;
-    let body_args = MergeRequestListBodyArgs::builder()
+    Ok(MergeRequestListBodyArgs::builder()
         .list_args(from_to_args)
         .state(cli_args.state)
         .assignee(assignee)
         .author(author)
         .reviewer(reviewer)
-        .build()?;
-    if cli_args.list_args.num_pages {
-        return common::num_merge_request_pages(remote, body_args, std::io::stdout());
-    }
-    if cli_args.list_args.num_resources {
-        return common::num_merge_request_resources(remote, body_args, std::io::stdout());
-    }
-    list(remote, body_args, cli_args, std::io::stdout())
+        .group(cli_args.group.clone())
+        .labels(cli_args.labels.clone())
+        .milestone(cli_args.milestone.clone())
+        .project_only(cli_args.project_only)
+        .build()?)
 }
 
 fn get_member(members: &[Member], username: &str) -> Option<Member> {
@@ -600,11 +1567,31 @@ fn get_member(members: &[Member], username: &str) -> Option<Member> {
         .cloned()
 }
 
+/// First reviewer from the ownership rules that is a known member. Members
+/// carry the user ID required by Gitlab.
+fn owner_reviewer(members: &[Member], owners: &Owners) -> Option<Member> {
+    owners
+        .reviewers
+        .iter()
+        .find_map(|username| get_member(members, username))
+}
+
+fn merge_labels(owner_labels: &[String], cli_labels: &[String]) -> Vec<String> {
+    let mut labels = owner_labels.to_vec();
+    for label in cli_labels {
+        if !labels.contains(label) {
+            labels.push(label.clone());
+        }
+    }
+    labels
+}
+
 fn user_prompt_confirmation(
     mr_body: &MergeRequestBody,
     config: Arc<dyn ConfigProperties>,
     description: String,
     target_branch: &String,
+    owners: &Owners,
     cli_args: &MergeRequestCliArgs,
 ) -> Result<MergeRequestBodyArgs> {
     let mut title = mr_body.repo.title().to_string();
@@ -620,20 +1607,24 @@ fn user_prompt_confirmation(
         None
     };
 
-    let reviewer = if cli_args.reviewer.is_some() {
-        get_member(&members, &cli_args.reviewer.clone().unwrap())
+    let reviewers: Vec<Member> = if !cli_args.reviewers.is_empty() {
+        cli_args
+            .reviewers
+            .iter()
+            .filter_map(|username| get_member(&members, username))
+            .collect()
     } else if cli_args.rand_reviewer {
         let members = config.merge_request_members();
         let num_members = members.len();
         if num_members == 0 {
-            None
+            vec![]
         } else {
             let rand_index = rand::random::<usize>();
             let rand_user = members[rand_index % num_members].clone();
-            Some(rand_user)
+            vec![rand_user]
         }
     } else {
-        None
+        owner_reviewer(&members, owners).into_iter().collect()
     };
 
     let user_input = if cli_args.auto {
@@ -643,7 +1634,7 @@ fn user_prompt_confirmation(
             .title(title)
             .description(description)
             .assignee(preferred_assignee_members[0].clone())
-            .reviewer(reviewer.unwrap_or_default())
+            .reviewers(reviewers)
             .build()
             .unwrap()
     } else {
@@ -651,7 +1642,8 @@ fn user_prompt_confirmation(
             &title,
             &description,
             assignee.as_ref(),
-            reviewer.as_ref(),
+            &reviewers,
+            &owners.reviewers,
             &config,
         )?
     };
@@ -662,17 +1654,20 @@ fn user_prompt_confirmation(
         .source_branch(mr_body.repo.current_branch().to_string())
         .target_branch(target_branch.to_string())
         .assignee(user_input.assignee)
-        .reviewer(user_input.reviewer)
+        .reviewers(user_input.reviewers)
         // TODO make this configurable
         .remove_source_branch("true".to_string())
         .draft(cli_args.draft)
         .amend(cli_args.amend)
+        .labels(merge_labels(&owners.labels, &cli_args.labels))
         .build()?)
 }
 
 /// Open a merge request.
 fn open(
     remote: Arc<dyn MergeRequest>,
+    dependency_remote: Arc<dyn MergeRequestDependency>,
+    project_remote: Arc<dyn RemoteProject>,
     config: Arc<dyn ConfigProperties>,
     mr_body: MergeRequestBody,
     cli_args: &MergeRequestCliArgs,
@@ -689,11 +1684,33 @@ fn open(
     // make sure we are in a feature branch or bail
     in_feature_branch(source_branch, &target_branch)?;
 
+    // pre-fill reviewers and labels from the configured ownership rules and
+    // the repository's CODEOWNERS file
+    let rules = config.merge_request_rules();
+    let codeowners = git::codeowners(&BlockingCommand)
+        .map(|content| owners::parse_codeowners(&content))
+        .unwrap_or_default();
+    let owners = if rules.is_empty() && codeowners.is_empty() {
+        Owners::default()
+    } else {
+        let changed_files = git::changed_files(&BlockingCommand, "origin", &target_branch)?;
+        let mut owners = owners::compute(&rules, &changed_files);
+        owners.add_reviewers(owners::code_owners(&codeowners, &changed_files));
+        owners
+    };
+
     // confirm title, description and assignee
-    let args = user_prompt_confirmation(&mr_body, config, description, &target_branch, cli_args)?;
+    let args = user_prompt_confirmation(
+        &mr_body,
+        config,
+        description,
+        &target_branch,
+        &owners,
+        cli_args,
+    )?;
 
-    if cli_args.rebase.is_some() {
-        git::rebase(&BlockingCommand, cli_args.rebase.as_ref().unwrap())?;
+    if let Some(rebase) = cli_args.rebase.as_ref().filter(|_| !cli_args.dry_run) {
+        git::rebase(&BlockingCommand, rebase)?;
     }
 
     let outgoing_commits = git::outgoing_commits(
@@ -703,7 +1720,9 @@ fn open(
         &SummaryOptions::Short,
     )?;
 
-    if outgoing_commits.is_empty() {
+    // In a dry run the changes to commit are not committed yet.
+    let pending_commit = cli_args.dry_run && cli_args.commit.is_some();
+    if outgoing_commits.is_empty() && !pending_commit {
         return Err(GRError::PreconditionNotMet(
             "No outgoing commits found. Please commit your changes.".to_string(),
         )
@@ -714,13 +1733,26 @@ fn open(
     if let Ok(()) =
         dialog::show_summary_merge_request(&outgoing_commits, &args, cli_args.accept_summary)
     {
-        println!("\nTaking off... 🚀\n");
         if cli_args.dry_run {
-            println!("Dry run completed. No changes were made.");
-            return Ok(());
+            return dry_run_summary(&args, cli_args, std::io::stdout());
         }
+        println!("\nTaking off... 🚀\n");
         git::push(&BlockingCommand, "origin", &mr_body.repo, cli_args.force)?;
+        if cli_args.web {
+            let url = project_remote.get_url(BrowseOptions::NewMergeRequest {
+                source_branch: args.source_branch,
+                target_branch: args.target_branch,
+                title: args.title,
+                description: args.description,
+            });
+            println!("Opening merge request form: {}", url);
+            open::that(url)?;
+            return Ok(());
+        }
         let merge_request_response = remote.open(args)?;
+        for depends_on in &cli_args.depends_on {
+            dependency_remote.add_dependency(merge_request_response.id, *depends_on)?;
+        }
         println!("Merge request opened: {}", merge_request_response.web_url);
         if cli_args.open_browser {
             open::that(merge_request_response.web_url)?;
@@ -729,6 +1761,47 @@ fn open(
     Ok(())
 }
 
+/// Prints the write operations a merge request creation would have done.
+fn dry_run_summary<W: Write>(
+    args: &MergeRequestBodyArgs,
+    cli_args: &MergeRequestCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    writeln!(writer, "Dry run. The following operations were skipped:")?;
+    if let Some(commit_message) = &cli_args.commit {
+        writeln!(writer, "- Commit local changes: {}", commit_message)?;
+    }
+    if let Some(rebase) = &cli_args.rebase {
+        writeln!(writer, "- Rebase {} onto {}", args.source_branch, rebase)?;
+    }
+    let force = if cli_args.force { " (force)" } else { "" };
+    writeln!(
+        writer,
+        "- Push branch {} to origin{}",
+        args.source_branch, force
+    )?;
+    let target = match &cli_args.target_repo {
+        Some(target_repo) => format!("{}:{}", target_repo, args.target_branch),
+        None => args.target_branch.clone(),
+    };
+    if cli_args.web {
+        writeln!(writer, "- Open the new merge request form in the browser")?;
+        return Ok(());
+    }
+    writeln!(
+        writer,
+        "- Open merge request \"{}\" from {} into {}",
+        args.title, args.source_branch, target
+    )?;
+    for depends_on in &cli_args.depends_on {
+        writeln!(writer, "- Depend on merge request {}", depends_on)?;
+    }
+    if cli_args.open_browser {
+        writeln!(writer, "- Open the merge request in the browser")?;
+    }
+    Ok(())
+}
+
 /// Summary - list of outgoing commits
 fn summary(mr_body: MergeRequestBody, cli_args: &MergeRequestCliArgs) -> Result<()> {
     let source_branch = mr_body.repo.current_branch();
@@ -737,8 +1810,8 @@ fn summary(mr_body: MergeRequestBody, cli_args: &MergeRequestCliArgs) -> Result<
 
     in_feature_branch(source_branch, &target_branch)?;
 
-    if cli_args.rebase.is_some() {
-        git::rebase(&BlockingCommand, cli_args.rebase.as_ref().unwrap())?;
+    if let Some(rebase) = &cli_args.rebase {
+        git::rebase(&BlockingCommand, rebase)?;
     }
 
     let outgoing_commits = git::outgoing_commits(
@@ -769,8 +1842,8 @@ fn patch(mr_body: MergeRequestBody, cli_args: &MergeRequestCliArgs) -> Result<()
 
     in_feature_branch(source_branch, &target_branch)?;
 
-    if cli_args.rebase.is_some() {
-        git::rebase(&BlockingCommand, cli_args.rebase.as_ref().unwrap())?;
+    if let Some(rebase) = &cli_args.rebase {
+        git::rebase(&BlockingCommand, rebase)?;
     }
     println!(
         "{}",
@@ -787,7 +1860,13 @@ fn cmds<R: BufRead + Send + Sync + 'static>(
     reader: Option<R>,
 ) -> Vec<Cmd<CmdInfo>> {
     let remote_cl = remote.clone();
-    let remote_project_cmd = move || -> Result<CmdInfo> { remote_cl.get_project_data(None, None) };
+    let target_branch = cli_args.target_branch.clone();
+    let default_branch_runner = task_runner.clone();
+    let remote_project_cmd = move || -> Result<CmdInfo> {
+        remote_cl
+            .get_project_data(None, None)
+            .or_else(|err| fallback_project(err, target_branch, &*default_branch_runner))
+    };
     let status_runner = task_runner.clone();
     let git_status_cmd = || -> Result<CmdInfo> { git::status(status_runner) };
     let current_branch_runner = task_runner.clone();
@@ -854,15 +1933,35 @@ fn cmds<R: BufRead + Send + Sync + 'static>(
         cmds.push(Box::new(git_title_cmd));
         cmds.push(Box::new(git_last_commit_message));
     }
-    if cli_args.fetch.is_some() {
+    if let Some(remote_alias) = &cli_args.fetch {
         let fetch_runner = task_runner.clone();
-        let remote_alias = cli_args.fetch.as_ref().unwrap().clone();
+        let remote_alias = remote_alias.clone();
         let git_fetch_cmd = || -> Result<CmdInfo> { git::fetch(fetch_runner, remote_alias) };
         cmds.push(Box::new(git_fetch_cmd));
     }
     cmds
 }
 
+/// The project is only needed for its default branch, so when it cannot be
+/// retrieved, ex. missing permissions, resolve the target branch locally: the
+/// one given on the command line or in the config file, or else the default
+/// branch recorded by git when cloning.
+fn fallback_project(
+    err: anyhow::Error,
+    target_branch: Option<String>,
+    runner: &impl TaskRunner<Response = ShellResponse>,
+) -> Result<CmdInfo> {
+    let target_branch = match target_branch {
+        Some(target_branch) => target_branch,
+        None => git::default_branch(runner).map_err(|_| err)?,
+    };
+    log_info!(
+        "Could not retrieve project data, targeting branch {}",
+        target_branch
+    );
+    Ok(CmdInfo::Project(Project::new(0, &target_branch)))
+}
+
 // append description signature from the configuration
 fn build_description(description: &str, signature: &str) -> String {
     if description.is_empty() && signature.is_empty() {
@@ -951,17 +2050,107 @@ fn list<W: Write>(
     common::list_merge_requests(remote, body_args, cli_args, &mut writer)
 }
 
-fn merge(remote: Arc<dyn MergeRequest>, merge_request_id: i64) -> Result<()> {
-    let merge_request = remote.merge(merge_request_id)?;
-    println!("Merge request merged: {}", merge_request.web_url);
+fn merge<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    mut args: MergeRequestMergeBodyArgs,
+    retry: RetryOperation,
+    mut writer: W,
+) -> Result<()> {
+    let merge_request_id = args.id;
+    if let Some(sha) = &args.sha {
+        // Check upfront, so a short SHA can be given. The provider verifies
+        // the full SHA again at merge time.
+        let head_sha = remote.get(merge_request_id)?.head_sha;
+        if sha.is_empty() || !head_sha.starts_with(sha.as_str()) {
+            return Err(GRError::PreconditionNotMet(format!(
+                "Merge request {} head is at {}, expected {}. Review the latest changes before merging.",
+                merge_request_id, head_sha, sha
+            ))
+            .into());
+        }
+        args.sha = Some(head_sha);
+    }
+    // Providers answer 405/409 when the merge request is not mergeable yet,
+    // ex. right after approval or while its pipeline is finishing.
+    let is_not_mergeable_yet = |err: &anyhow::Error| {
+        matches!(
+            err.downcast_ref::<GRError>(),
+            Some(GRError::RemoteConflict(_))
+        )
+    };
+    let merge_request = retry.run(
+        is_not_mergeable_yet,
+        |num_retry, wait_time| {
+            writeln!(
+                writer,
+                "Merge request {} is not mergeable yet. Retrying in {}s ({}/{})",
+                merge_request_id,
+                wait_time,
+                num_retry,
+                retry.max_retries()
+            )?;
+            Ok(())
+        },
+        || remote.merge(args.clone()),
+    )?;
+    // Gitlab merges right away if the pipeline has already succeeded.
+    if args.auto_merge && merge_request.merged_at.is_none() {
+        writeln!(
+            writer,
+            "Merge request {} will be merged when its pipeline succeeds: {}",
+            merge_request_id, merge_request.web_url
+        )?;
+        return Ok(());
+    }
+    writeln!(writer, "Merge request merged: {}", merge_request.web_url)?;
+    Ok(())
+}
+
+/// Refuses to merge while any of the merge requests it depends on has not been
+/// merged yet.
+fn check_dependencies(remote: Arc<dyn MergeRequestDependency>, id: i64) -> Result<()> {
+    let pending = remote
+        .list_dependencies(id)?
+        .into_iter()
+        .filter(|dependency| dependency.merged_at.is_none())
+        .map(|dependency| format!("{} ({})", dependency.id, dependency.web_url))
+        .collect::<Vec<_>>();
+    if !pending.is_empty() {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Merge request {} depends on merge requests not merged yet: {}",
+            id,
+            pending.join(", ")
+        ))
+        .into());
+    }
     Ok(())
 }
 
-fn checkout(remote: Arc<dyn MergeRequest>, id: i64) -> Result<()> {
+fn checkout<R: TaskRunner<Response = ShellResponse>, W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    id: i64,
+    runner: Arc<R>,
+    mut writer: W,
+) -> Result<()> {
     let merge_request = remote.get(id)?;
     // assume origin for now
-    git::fetch(Arc::new(BlockingCommand), "origin".to_string())?;
-    git::checkout(&BlockingCommand, &merge_request.source_branch)
+    if !merge_request.from_fork {
+        git::fetch(runner.clone(), "origin".to_string())?;
+        return git::checkout(runner.as_ref(), &merge_request.source_branch);
+    }
+    // The source branch lives in a fork. Its head is fetched from the ref the
+    // target project keeps for the merge request instead. The local branch is
+    // named after the merge request, as forks are often opened from their
+    // default branch.
+    let branch = format!("mr/{}/{}", id, merge_request.source_branch);
+    git::fetch_ref(runner.as_ref(), "origin", &merge_request.head_ref)?;
+    git::checkout_tracking(runner.as_ref(), &branch, "origin", &merge_request.head_ref)?;
+    writeln!(
+        writer,
+        "Merge request {} comes from a fork. Checked out into branch {} tracking {}",
+        id, branch, merge_request.head_ref
+    )?;
+    Ok(())
 }
 
 fn close(remote: Arc<dyn MergeRequest>, id: i64) -> Result<()> {
@@ -970,64 +2159,529 @@ fn close(remote: Arc<dyn MergeRequest>, id: i64) -> Result<()> {
     Ok(())
 }
 
+/// Prints the changes of a merge request as a unified diff, or a summary of
+/// the lines added and deleted per file if `stat` is set.
+fn diff<W: Write>(
+    remote: Arc<dyn MergeRequestDiff>,
+    cli_args: MergeRequestGetCliArgs,
+    stat: bool,
+    mut writer: W,
+) -> Result<()> {
+    let changes = remote.list_changes(cli_args.id)?;
+    if stat {
+        return display::print(&mut writer, changes, cli_args.get_args);
+    }
+    for change in changes {
+        writer.write_all(change.unified_diff().as_bytes())?;
+    }
+    Ok(())
+}
+
+fn toggle_draft<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    id: i64,
+    draft: bool,
+    mut writer: W,
+) -> Result<()> {
+    let merge_request = remote.toggle_draft(id, draft)?;
+    if draft {
+        writeln!(
+            writer,
+            "Merge request marked as draft: {}",
+            merge_request.web_url
+        )?;
+    } else {
+        writeln!(
+            writer,
+            "Merge request ready for review: {}",
+            merge_request.web_url
+        )?;
+    }
+    Ok(())
+}
+
 fn approve<W: Write>(remote: Arc<dyn MergeRequest>, id: i64, mut writer: W) -> Result<()> {
     let merge_request = remote.approve(id)?;
     writer.write_all(format!("Merge request approved: {}\n", merge_request.web_url).as_bytes())?;
     Ok(())
 }
 
-fn create_comment<R: BufRead>(
-    remote: Arc<dyn CommentMergeRequest>,
-    args: CommentMergeRequestCliArgs,
-    reader: Option<R>,
+fn suggest<W: Write>(
+    remote: Arc<dyn MergeRequestSuggestion>,
+    body_args: SuggestionBodyArgs,
+    mut writer: W,
 ) -> Result<()> {
-    let comment = if let Some(comment) = args.comment {
-        comment
-    } else {
-        let mut comment = String::new();
-        // The unwrap is Ok here. This is enforced at the CLI interface. The
-        // user is required to provide a file or a comment.
-        reader.unwrap().read_to_string(&mut comment)?;
-        comment
-    };
-    remote.create(
-        CommentMergeRequestBodyArgs::builder()
-            .id(args.id)
-            .comment(comment)
-            .build()
-            .unwrap(),
-    )
+    let location = format!("{}:{}", body_args.file, body_args.line);
+    let id = body_args.id;
+    remote.suggest(body_args)?;
+    writeln!(
+        writer,
+        "Suggested change at {} on merge request {}",
+        location, id
+    )?;
+    Ok(())
 }
 
-pub fn get_merge_request_details<W: Write>(
-    remote: Arc<dyn MergeRequest>,
-    args: MergeRequestGetCliArgs,
+fn list_discussions<W: Write>(
+    remote: Arc<dyn MergeRequestDiscussion>,
+    cli_args: MergeRequestGetCliArgs,
     mut writer: W,
 ) -> Result<()> {
-    let response = remote.get(args.id)?;
-    display::print(&mut writer, vec![response], args.get_args)?;
-    Ok(())
+    let discussions = remote.list_discussions(cli_args.id)?;
+    if discussions.is_empty() {
+        writeln!(writer, "No discussions found.")?;
+        return Ok(());
+    }
+    let notes = discussions
+        .into_iter()
+        .flat_map(|discussion| {
+            let Discussion {
+                id,
+                file,
+                line,
+                resolved,
+                notes,
+            } = discussion;
+            notes.into_iter().map(move |note| DiscussionNote {
+                discussion_id: id.clone(),
+                file: file.clone(),
+                line,
+                resolved,
+                note,
+            })
+        })
+        .collect::<Vec<_>>();
+    display::print(&mut writer, notes, cli_args.get_args)
 }
 
-fn list_comments<W: Write>(
-    remote: Arc<dyn CommentMergeRequest>,
-    body_args: CommentMergeRequestListBodyArgs,
-    cli_args: CommentMergeRequestListCliArgs,
-    writer: W,
+fn reply_discussion<W: Write>(
+    remote: Arc<dyn MergeRequestDiscussion>,
+    body_args: DiscussionReplyBodyArgs,
+    mut writer: W,
 ) -> Result<()> {
-    common::list_merge_request_comments(remote, body_args, cli_args, writer)
+    let discussion_id = body_args.discussion_id.clone();
+    let id = body_args.id;
+    remote.reply(body_args)?;
+    writeln!(
+        writer,
+        "Replied to discussion {} on merge request {}",
+        discussion_id, id
+    )?;
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use std::{
-        io::{Cursor, Read},
-        sync::Mutex,
-    };
+fn list_suggestions<W: Write>(
+    remote: Arc<dyn MergeRequestSuggestion>,
+    cli_args: MergeRequestGetCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let suggestions = remote.list_suggestions(cli_args.id)?;
+    if suggestions.is_empty() {
+        writeln!(writer, "No pending suggestions found.")?;
+        return Ok(());
+    }
+    display::print(&mut writer, suggestions, cli_args.get_args)
+}
 
-    use crate::{
-        api_traits::CommentMergeRequest, cli::browse::BrowseOptions,
-        cmds::project::ProjectListBodyArgs, error,
+/// Applies pending suggestions given by id, all of them, or the ones picked
+/// with `select` otherwise.
+fn apply_suggestions<W: Write>(
+    remote: Arc<dyn MergeRequestSuggestion>,
+    cli_args: SuggestionApplyCliArgs,
+    select: impl FnOnce(&[Suggestion]) -> Result<Vec<i64>>,
+    mut writer: W,
+) -> Result<()> {
+    let pending = remote.list_suggestions(cli_args.id)?;
+    if pending.is_empty() {
+        writeln!(writer, "No pending suggestions found.")?;
+        return Ok(());
+    }
+    let ids = if cli_args.all {
+        pending.iter().map(|suggestion| suggestion.id).collect()
+    } else if !cli_args.suggestion_ids.is_empty() {
+        if let Some(id) = cli_args
+            .suggestion_ids
+            .iter()
+            .find(|id| !pending.iter().any(|suggestion| suggestion.id == **id))
+        {
+            return Err(GRError::PreconditionNotMet(format!(
+                "Suggestion {} is not pending on merge request {}",
+                id, cli_args.id
+            ))
+            .into());
+        }
+        cli_args.suggestion_ids
+    } else {
+        select(&pending)?
+    };
+    if ids.is_empty() {
+        writeln!(writer, "No suggestions selected.")?;
+        return Ok(());
+    }
+    remote.apply_suggestions(&ids, cli_args.message)?;
+    writeln!(
+        writer,
+        "Applied {} suggestions to merge request {}",
+        ids.len(),
+        cli_args.id
+    )?;
+    Ok(())
+}
+
+fn export_review<W: Write>(remote: Arc<dyn MergeRequest>, id: i64, mut writer: W) -> Result<()> {
+    let merge_request = remote.get(id)?;
+    let review = ReviewFile::new(id, &merge_request.title, &merge_request.head_sha);
+    writer.write_all(review.to_toml()?.as_bytes())?;
+    Ok(())
+}
+
+/// Submits a review drafted offline. Lines in the review refer to the head
+/// of the merge request when exported, so submitting after new commits have
+/// been pushed requires `force`.
+fn submit_review<W: Write>(
+    mr_remote: Arc<dyn MergeRequest>,
+    review_remote: Arc<dyn MergeRequestReview>,
+    review: ReviewFile,
+    force: bool,
+    mut writer: W,
+) -> Result<()> {
+    let id = review.merge_request;
+    let head_sha = mr_remote.get(id)?.head_sha;
+    if head_sha != review.head_sha && !force {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Merge request {} has new commits since the review was exported ({} != {}). \
+             Commented lines might have moved. Use --force to submit anyway",
+            id, head_sha, review.head_sha
+        ))
+        .into());
+    }
+    let num_comments = review.comments.len();
+    review_remote.submit_review(review)?;
+    writeln!(
+        writer,
+        "Submitted review with {} inline comments on merge request {}",
+        num_comments, id
+    )?;
+    Ok(())
+}
+
+fn review<W: Write>(
+    remote: Arc<dyn ReviewMergeRequest>,
+    body_args: ReviewBodyArgs,
+    mut writer: W,
+) -> Result<()> {
+    let id = body_args.id;
+    let event = body_args.event;
+    remote.review(body_args)?;
+    match event {
+        ReviewEvent::Approve => writeln!(writer, "Approved merge request {}", id)?,
+        ReviewEvent::RequestChanges => {
+            writeln!(writer, "Requested changes on merge request {}", id)?
+        }
+        ReviewEvent::Comment => writeln!(writer, "Reviewed merge request {}", id)?,
+    }
+    Ok(())
+}
+
+fn react<W: Write>(
+    remote: Arc<dyn MergeRequestReaction>,
+    body_args: ReactionBodyArgs,
+    mut writer: W,
+) -> Result<()> {
+    let target = match body_args.comment_id {
+        Some(comment_id) => format!("comment {} of merge request {}", comment_id, body_args.id),
+        None => format!("merge request {}", body_args.id),
+    };
+    let emoji = body_args.emoji.clone();
+    remote.react(body_args)?;
+    writeln!(writer, "Reacted with :{}: to {}", emoji, target)?;
+    Ok(())
+}
+
+fn nag<W: Write>(
+    mr_remote: Arc<dyn MergeRequest>,
+    comment_remote: Arc<dyn CommentMergeRequest>,
+    template: &str,
+    cli_args: MergeRequestNagCliArgs,
+    now: fn() -> Seconds,
+    mut writer: W,
+) -> Result<()> {
+    let now = now();
+    let body_args = MergeRequestListBodyArgs::builder()
+        .state(MergeRequestState::Opened)
+        .list_args(None)
+        .build()?;
+    let awaiting_review = mr_remote
+        .list(body_args)?
+        .into_iter()
+        .filter(|mr| {
+            let updated_at = Seconds::new(mr.updated_at.timestamp().max(0) as u64);
+            updated_at < now && now - updated_at > cli_args.older_than
+        })
+        .collect::<Vec<MergeRequestResponse>>();
+    if awaiting_review.is_empty() {
+        writeln!(writer, "No merge requests awaiting review.")?;
+        return Ok(());
+    }
+    if cli_args.report {
+        return display::print(&mut writer, awaiting_review, cli_args.get_args);
+    }
+    dialog::confirm_action(
+        &format!(
+            "Post a review reminder on {} merge requests?",
+            awaiting_review.len()
+        ),
+        cli_args.yes,
+    )?;
+    let template = if template.is_empty() {
+        DEFAULT_REVIEW_REMINDER
+    } else {
+        template
+    };
+    for mr in awaiting_review {
+        let comment = template
+            .replace("{id}", &mr.id.to_string())
+            .replace("{title}", &mr.title)
+            .replace("{author}", &mr.author)
+            .replace("{age}", &time::age(&mr.updated_at, now));
+        comment_remote.create(
+            CommentMergeRequestBodyArgs::builder()
+                .id(mr.id)
+                .comment(comment)
+                .build()
+                .unwrap(),
+        )?;
+        writeln!(writer, "Reminder posted: {}", mr.web_url)?;
+    }
+    Ok(())
+}
+
+fn queue_add<W: Write>(remote: Arc<dyn MergeQueue>, id: i64, mut writer: W) -> Result<()> {
+    let entry = remote.add(id)?;
+    match entry.position {
+        Some(position) => writeln!(
+            writer,
+            "Merge request {} added to the merge queue at position {}",
+            id, position
+        )?,
+        None => writeln!(writer, "Merge request {} added to the merge queue", id)?,
+    }
+    Ok(())
+}
+
+fn queue_remove<W: Write>(remote: Arc<dyn MergeQueue>, id: i64, mut writer: W) -> Result<()> {
+    remote.remove(id)?;
+    writeln!(writer, "Merge request {} removed from the merge queue", id)?;
+    Ok(())
+}
+
+fn queue_status<W: Write>(
+    remote: Arc<dyn MergeQueue>,
+    cli_args: MergeRequestGetCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let entry = remote.status(cli_args.id)?;
+    display::print_one(&mut writer, entry, cli_args.get_args)
+}
+
+/// Triggers a server-side rebase of the merge request and polls its status
+/// until it finishes, so stale merge requests can be refreshed without
+/// fetching them locally.
+fn rebase<W: Write>(
+    remote: Arc<dyn MergeRequestRebase>,
+    cli_args: MergeRequestRebaseCliArgs,
+    throttler: &dyn ThrottleStrategy,
+    mut writer: W,
+) -> Result<()> {
+    let id = cli_args.id;
+    let started = remote.rebase(id)?;
+    writeln!(writer, "Rebasing merge request {}", id)?;
+    writer.flush()?;
+    let mut status = started.clone();
+    let mut waited = Seconds::new(0);
+    while status.in_progress {
+        if waited >= cli_args.timeout {
+            return Err(GRError::PreconditionNotMet(format!(
+                "Rebase of merge request {} still in progress after {}",
+                id,
+                time::human_duration(waited)
+            ))
+            .into());
+        }
+        throttler.throttle_for(cli_args.interval.into());
+        waited = waited + cli_args.interval;
+        status = remote.rebase_status(&started)?;
+    }
+    if let Some(error) = status.error {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Rebase of merge request {} failed: {}",
+            id, error
+        ))
+        .into());
+    }
+    writeln!(
+        writer,
+        "Merge request {} rebased onto its target branch",
+        id
+    )?;
+    if !status.sha.is_empty() {
+        writeln!(writer, "Head commit: {}", status.sha)?;
+    }
+    Ok(())
+}
+
+fn create_comment<R: BufRead, W: Write>(
+    remote: Arc<dyn CommentMergeRequest>,
+    args: CommentMergeRequestCliArgs,
+    reader: Option<R>,
+    retry: RetryOperation,
+    mut writer: W,
+) -> Result<()> {
+    let comment = if let Some(comment) = args.comment {
+        comment
+    } else {
+        let mut comment = String::new();
+        // The unwrap is Ok here. This is enforced at the CLI interface. The
+        // user is required to provide a file or a comment.
+        reader.unwrap().read_to_string(&mut comment)?;
+        comment
+    };
+    let body_args = CommentMergeRequestBodyArgs::builder()
+        .id(args.id)
+        .comment(comment)
+        .build()
+        .unwrap();
+    if retry.max_retries() == 0 {
+        return remote.create(body_args);
+    }
+    // Comments are not idempotent and neither Github nor Gitlab support
+    // idempotency keys. A request that failed on our side might have been
+    // processed by the remote, so before posting again, check whether a
+    // comment with the same body showed up after our first attempt.
+    let existing = identical_comments(&remote, &body_args)?;
+    let is_transient = |err: &anyhow::Error| {
+        matches!(
+            err.downcast_ref::<GRError>(),
+            Some(GRError::HttpTransportError(_)) | Some(GRError::RemoteServerError(_))
+        )
+    };
+    let mut attempted = false;
+    retry.run(
+        is_transient,
+        |num_retry, wait_time| {
+            writeln!(
+                writer,
+                "Failed to comment on merge request {}. Retrying in {}s ({}/{})",
+                body_args.id,
+                wait_time,
+                num_retry,
+                retry.max_retries()
+            )?;
+            Ok(())
+        },
+        || {
+            if attempted
+                && identical_comments(&remote, &body_args)?
+                    .iter()
+                    .any(|id| !existing.contains(id))
+            {
+                return Ok(());
+            }
+            attempted = true;
+            remote.create(body_args.clone())
+        },
+    )
+}
+
+/// IDs of the merge request comments whose body matches the one being posted.
+fn identical_comments(
+    remote: &Arc<dyn CommentMergeRequest>,
+    body_args: &CommentMergeRequestBodyArgs,
+) -> Result<Vec<i64>> {
+    let comments = remote.list(
+        CommentMergeRequestListBodyArgs::builder()
+            .id(body_args.id)
+            .list_args(None)
+            .build()
+            .unwrap(),
+    )?;
+    Ok(comments
+        .into_iter()
+        .filter(|comment| comment.body == body_args.comment)
+        .map(|comment| comment.id)
+        .collect())
+}
+
+pub fn get_merge_request_details<W: Write>(
+    remote: Arc<dyn MergeRequest>,
+    args: MergeRequestGetCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let response = remote.get(args.id)?;
+    display::print_one(&mut writer, response, args.get_args)?;
+    Ok(())
+}
+
+fn edit_comment<W: Write>(
+    remote: Arc<dyn CommentMergeRequest>,
+    id: i64,
+    comment_id: i64,
+    body: String,
+    mut writer: W,
+) -> Result<()> {
+    let body_args = CommentMergeRequestBodyArgs::builder()
+        .id(id)
+        .comment(body)
+        .build()
+        .unwrap();
+    remote.update(comment_id, body_args)?;
+    writeln!(
+        writer,
+        "Comment {} of merge request {} updated",
+        comment_id, id
+    )?;
+    Ok(())
+}
+
+fn delete_comment<W: Write>(
+    remote: Arc<dyn CommentMergeRequest>,
+    id: i64,
+    comment_id: i64,
+    mut writer: W,
+) -> Result<()> {
+    remote.delete(id, comment_id)?;
+    writeln!(
+        writer,
+        "Comment {} of merge request {} deleted",
+        comment_id, id
+    )?;
+    Ok(())
+}
+
+fn list_comments<W: Write>(
+    remote: Arc<dyn CommentMergeRequest>,
+    body_args: CommentMergeRequestListBodyArgs,
+    cli_args: CommentMergeRequestListCliArgs,
+    writer: W,
+) -> Result<()> {
+    common::list_merge_request_comments(remote, body_args, cli_args, writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        io::{Cursor, Read},
+        rc::Rc,
+        sync::Mutex,
+    };
+
+    use crate::{
+        api_traits::CommentMergeRequest,
+        cli::browse::BrowseOptions,
+        cmds::project::ProjectListBodyArgs,
+        error,
+        test::utils::{MockRunner, MockThrottler},
     };
 
     use super::*;
@@ -1218,7 +2872,7 @@ mod tests {
                     .title("New feature".to_string())
                     .web_url("https://gitlab.com/owner/repo/-/merge_requests/1".to_string())
                     .author("author".to_string())
-                    .updated_at("2021-01-01".to_string())
+                    .updated_at("2021-01-01T00:00:00Z".parse().unwrap())
                     .build()
                     .unwrap()])
                 .build()
@@ -1238,7 +2892,7 @@ mod tests {
         list(remote, body_args, cli_args, &mut buf).unwrap();
         assert_eq!(
             "ID|Title|Source Branch|Author|URL|Updated at\n\
-             1|New feature||author|https://gitlab.com/owner/repo/-/merge_requests/1|2021-01-01\n",
+             1|New feature||author|https://gitlab.com/owner/repo/-/merge_requests/1|2021-01-01T00:00:00Z\n",
             String::from_utf8(buf).unwrap(),
         )
     }
@@ -1288,7 +2942,7 @@ mod tests {
                     .title("New feature".to_string())
                     .web_url("https://gitlab.com/owner/repo/-/merge_requests/1".to_string())
                     .author("author".to_string())
-                    .updated_at("2021-01-01".to_string())
+                    .updated_at("2021-01-01T00:00:00Z".parse().unwrap())
                     .build()
                     .unwrap()])
                 .build()
@@ -1315,7 +2969,7 @@ mod tests {
         );
         list(remote, body_args, cli_args, &mut buf).unwrap();
         assert_eq!(
-            "1|New feature||author|https://gitlab.com/owner/repo/-/merge_requests/1|2021-01-01\n",
+            "1|New feature||author|https://gitlab.com/owner/repo/-/merge_requests/1|2021-01-01T00:00:00Z\n",
             String::from_utf8(buf).unwrap(),
         )
     }
@@ -1324,6 +2978,13 @@ mod tests {
     struct MergeRequestRemoteMock {
         #[builder(default = "Vec::new()")]
         merge_requests: Vec<MergeRequestResponse>,
+        /// Number of merge attempts that fail as not mergeable yet
+        #[builder(default)]
+        not_mergeable: u32,
+        #[builder(setter(skip))]
+        merge_attempts: Arc<Mutex<u32>>,
+        #[builder(setter(skip))]
+        merge_args: Arc<Mutex<Option<MergeRequestMergeBodyArgs>>>,
     }
 
     impl MergeRequestRemoteMock {
@@ -1339,8 +3000,22 @@ mod tests {
         fn list(&self, _args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
             Ok(self.merge_requests.clone())
         }
-        fn merge(&self, _id: i64) -> Result<MergeRequestResponse> {
-            Ok(MergeRequestResponse::builder().build().unwrap())
+        fn merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
+            let id = args.id;
+            *self.merge_args.lock().unwrap() = Some(args);
+            let mut attempts = self.merge_attempts.lock().unwrap();
+            *attempts += 1;
+            if *attempts <= self.not_mergeable {
+                return Err(GRError::RemoteConflict("405 Method Not Allowed".to_string()).into());
+            }
+            Ok(MergeRequestResponse::builder()
+                .id(id)
+                .web_url(format!(
+                    "https://gitlab.com/jordilin/gitlapi/-/merge_requests/{}",
+                    id
+                ))
+                .build()
+                .unwrap())
         }
         fn get(&self, _id: i64) -> Result<MergeRequestResponse> {
             Ok(self.merge_requests[0].clone())
@@ -1354,6 +3029,9 @@ mod tests {
         fn approve(&self, _id: i64) -> Result<MergeRequestResponse> {
             Ok(self.merge_requests[0].clone())
         }
+        fn toggle_draft(&self, _id: i64, _draft: bool) -> Result<MergeRequestResponse> {
+            Ok(self.merge_requests[0].clone())
+        }
 
         fn num_resources(
             &self,
@@ -1368,6 +3046,17 @@ mod tests {
         comment_called: Mutex<bool>,
         comment_argument: Mutex<String>,
         list_comments: Vec<Comment>,
+        comment_failures: u32,
+        comment_attempts: Mutex<u32>,
+        reaction: Mutex<Option<ReactionBodyArgs>>,
+        discussions: Vec<Discussion>,
+        discussion_reply: Mutex<Option<DiscussionReplyBodyArgs>>,
+        suggestion: Mutex<Option<SuggestionBodyArgs>>,
+        suggestions: Vec<Suggestion>,
+        applied_suggestions: Mutex<Vec<i64>>,
+        review: Mutex<Option<ReviewFile>>,
+        review_args: Mutex<Option<ReviewBodyArgs>>,
+        changes: Vec<FileChange>,
     }
 
     impl MockRemoteProject {
@@ -1376,6 +3065,7 @@ mod tests {
                 comment_called: Mutex::new(false),
                 comment_argument: Mutex::new("".to_string()),
                 list_comments: comments,
+                ..Default::default()
             }
         }
     }
@@ -1424,12 +3114,81 @@ mod tests {
         }
     }
 
+    impl MergeRequestReaction for MockRemoteProject {
+        fn react(&self, args: ReactionBodyArgs) -> Result<()> {
+            *self.reaction.lock().unwrap() = Some(args);
+            Ok(())
+        }
+    }
+
+    impl MergeRequestDiscussion for MockRemoteProject {
+        fn list_discussions(&self, _id: i64) -> Result<Vec<Discussion>> {
+            Ok(self.discussions.clone())
+        }
+
+        fn reply(&self, args: DiscussionReplyBodyArgs) -> Result<()> {
+            *self.discussion_reply.lock().unwrap() = Some(args);
+            Ok(())
+        }
+    }
+
+    impl MergeRequestSuggestion for MockRemoteProject {
+        fn suggest(&self, args: SuggestionBodyArgs) -> Result<()> {
+            *self.suggestion.lock().unwrap() = Some(args);
+            Ok(())
+        }
+
+        fn list_suggestions(&self, _id: i64) -> Result<Vec<Suggestion>> {
+            Ok(self.suggestions.clone())
+        }
+
+        fn apply_suggestions(&self, ids: &[i64], _commit_message: Option<String>) -> Result<()> {
+            self.applied_suggestions.lock().unwrap().extend(ids);
+            Ok(())
+        }
+    }
+
+    impl MergeRequestReview for MockRemoteProject {
+        fn submit_review(&self, review: ReviewFile) -> Result<()> {
+            *self.review.lock().unwrap() = Some(review);
+            Ok(())
+        }
+    }
+
+    impl MergeRequestDiff for MockRemoteProject {
+        fn list_changes(&self, _id: i64) -> Result<Vec<FileChange>> {
+            Ok(self.changes.clone())
+        }
+    }
+
+    impl ReviewMergeRequest for MockRemoteProject {
+        fn review(&self, args: ReviewBodyArgs) -> Result<()> {
+            *self.review_args.lock().unwrap() = Some(args);
+            Ok(())
+        }
+    }
+
     impl CommentMergeRequest for MockRemoteProject {
         fn create(&self, args: CommentMergeRequestBodyArgs) -> Result<()> {
             let mut called = self.comment_called.lock().unwrap();
             *called = true;
             let mut argument = self.comment_argument.lock().unwrap();
             *argument = args.comment;
+            let mut attempts = self.comment_attempts.lock().unwrap();
+            *attempts += 1;
+            if *attempts <= self.comment_failures {
+                return Err(GRError::HttpTransportError("connection reset".to_string()).into());
+            }
+            Ok(())
+        }
+
+        fn update(&self, comment_id: i64, args: CommentMergeRequestBodyArgs) -> Result<()> {
+            *self.comment_argument.lock().unwrap() = format!("{}: {}", comment_id, args.comment);
+            Ok(())
+        }
+
+        fn delete(&self, id: i64, comment_id: i64) -> Result<()> {
+            *self.comment_argument.lock().unwrap() = format!("{}: {}", id, comment_id);
             Ok(())
         }
 
@@ -1477,6 +3236,44 @@ mod tests {
         }
     }
 
+    fn forbidden() -> anyhow::Error {
+        GRError::RemoteServerError("403 Forbidden".to_string()).into()
+    }
+
+    fn default_branch_of(cmd_info: CmdInfo) -> String {
+        match cmd_info {
+            CmdInfo::Project(project) => project.default_branch().to_string(),
+            _ => panic!("Expected CmdInfo::Project"),
+        }
+    }
+
+    #[test]
+    fn test_fallback_project_targets_configured_branch() {
+        let runner = MockShellRunner::new(vec![]);
+        let cmd_info = fallback_project(forbidden(), Some("develop".to_string()), &runner).unwrap();
+        assert_eq!("develop", default_branch_of(cmd_info));
+    }
+
+    #[test]
+    fn test_fallback_project_targets_origin_head() {
+        let runner = MockShellRunner::new(vec![ShellResponse::builder()
+            .body("refs/remotes/origin/trunk".to_string())
+            .build()
+            .unwrap()]);
+        let cmd_info = fallback_project(forbidden(), None, &runner).unwrap();
+        assert_eq!("trunk", default_branch_of(cmd_info));
+    }
+
+    #[test]
+    fn test_fallback_project_without_default_branch_is_remote_error() {
+        let runner = MockShellRunner::new(vec![ShellResponse::builder().build().unwrap()]);
+        let err = fallback_project(forbidden(), None, &runner).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::RemoteServerError(msg)) => assert_eq!("403 Forbidden", msg),
+            _ => panic!("Expected GRError::RemoteServerError"),
+        }
+    }
+
     fn gen_cmd_responses() -> Vec<ShellResponse> {
         let responses = vec![
             ShellResponse::builder()
@@ -1503,6 +3300,46 @@ mod tests {
         responses
     }
 
+    #[test]
+    fn test_dry_run_summary_lists_skipped_operations() {
+        let cli_args = MergeRequestCliArgs::builder()
+            .title(None)
+            .body_from_commit(None)
+            .description(None)
+            .description_from_file(None)
+            .target_branch(None)
+            .rebase(Some("origin/main".to_string()))
+            .auto(false)
+            .cache_args(CacheCliArgs::default())
+            .open_browser(false)
+            .accept_summary(true)
+            .commit(Some("Add issue list".to_string()))
+            .draft(false)
+            .force(true)
+            .amend(false)
+            .dry_run(true)
+            .depends_on(vec![21])
+            .build()
+            .unwrap();
+        let args = MergeRequestBodyArgs::builder()
+            .title("Add issue list".to_string())
+            .source_branch("feature".to_string())
+            .target_branch("main".to_string())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        dry_run_summary(&args, &cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "Dry run. The following operations were skipped:\n\
+             - Commit local changes: Add issue list\n\
+             - Rebase feature onto origin/main\n\
+             - Push branch feature to origin (force)\n\
+             - Open merge request \"Add issue list\" from feature into main\n\
+             - Depend on merge request 21\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
     #[test]
     fn test_cmds_gather_title_from_cli_arg() {
         let remote = Arc::new(MockRemoteProject::default());
@@ -1630,8 +3467,15 @@ mod tests {
             .build()
             .unwrap();
         let reader = Cursor::new("comment");
-        assert!(create_comment(remote.clone(), cli_args, Some(reader)).is_ok());
-        assert!(remote.comment_called.lock().unwrap().clone());
+        assert!(create_comment(
+            remote.clone(),
+            cli_args,
+            Some(reader),
+            merge_retry(0, Rc::new(MockThrottler::new(None))),
+            Vec::new(),
+        )
+        .is_ok());
+        assert!(*remote.comment_called.lock().unwrap());
         assert_eq!(
             "All features complete, ship it",
             remote.comment_argument.lock().unwrap().clone(),
@@ -1648,8 +3492,15 @@ mod tests {
             .build()
             .unwrap();
         let reader = Cursor::new("Just a long, long comment from a file");
-        assert!(create_comment(remote.clone(), cli_args, Some(reader)).is_ok());
-        assert!(remote.comment_called.lock().unwrap().clone());
+        assert!(create_comment(
+            remote.clone(),
+            cli_args,
+            Some(reader),
+            merge_retry(0, Rc::new(MockThrottler::new(None))),
+            Vec::new(),
+        )
+        .is_ok());
+        assert!(*remote.comment_called.lock().unwrap());
         assert_eq!(
             "Just a long, long comment from a file",
             remote.comment_argument.lock().unwrap().clone(),
@@ -1660,19 +3511,13 @@ mod tests {
 
     impl Read for ErrorReader {
         fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Error reading from reader",
-            ))
+            Err(std::io::Error::other("Error reading from reader"))
         }
     }
 
     impl BufRead for ErrorReader {
         fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Error reading from reader",
-            ))
+            Err(std::io::Error::other("Error reading from reader"))
         }
         fn consume(&mut self, _amt: usize) {}
     }
@@ -1687,7 +3532,131 @@ mod tests {
             .build()
             .unwrap();
         let reader = ErrorReader {};
-        assert!(create_comment(remote.clone(), cli_args, Some(reader)).is_err());
+        assert!(create_comment(
+            remote.clone(),
+            cli_args,
+            Some(reader),
+            merge_retry(0, Rc::new(MockThrottler::new(None))),
+            Vec::new(),
+        )
+        .is_err());
+    }
+
+    fn comment(id: i64, body: &str) -> Comment {
+        Comment::builder()
+            .id(id)
+            .body(body.to_string())
+            .author("jordilin".to_string())
+            .created_at(Some("2024-03-03T09:00:00Z".parse().unwrap()))
+            .build()
+            .unwrap()
+    }
+
+    fn comment_with_retries(id: i64, comment: &str, retries: u32) -> CommentMergeRequestCliArgs {
+        CommentMergeRequestCliArgs::builder()
+            .id(id)
+            .comment(Some(comment.to_string()))
+            .comment_from_file(None)
+            .retry(retries)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_create_comment_retries_on_transport_error() {
+        let remote = Arc::new(MockRemoteProject {
+            list_comments: vec![comment(1, "LGTM")],
+            comment_failures: 1,
+            ..Default::default()
+        });
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        create_comment(
+            remote.clone(),
+            comment_with_retries(1, "LGTM", 2),
+            None::<Cursor<&str>>,
+            merge_retry(2, throttler.clone()),
+            &mut buf,
+        )
+        .unwrap();
+        // The only identical comment was there before the first attempt.
+        assert_eq!(2, *remote.comment_attempts.lock().unwrap());
+        assert_eq!(1, *throttler.throttled());
+        assert!(String::from_utf8(buf)
+            .unwrap()
+            .starts_with("Failed to comment on merge request 1. Retrying in"));
+    }
+
+    #[test]
+    fn test_create_comment_does_not_post_twice_if_failed_attempt_went_through() {
+        // Comment 2 shows up after the first attempt, as if the request
+        // reached the remote despite the transport error.
+        struct LandedComment(MockRemoteProject, Mutex<u32>);
+        impl CommentMergeRequest for LandedComment {
+            fn create(&self, args: CommentMergeRequestBodyArgs) -> Result<()> {
+                self.0.create(args)
+            }
+            fn update(&self, comment_id: i64, args: CommentMergeRequestBodyArgs) -> Result<()> {
+                self.0.update(comment_id, args)
+            }
+            fn delete(&self, id: i64, comment_id: i64) -> Result<()> {
+                self.0.delete(id, comment_id)
+            }
+            fn list(&self, args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>> {
+                let mut num_lists = self.1.lock().unwrap();
+                *num_lists += 1;
+                let mut comments = CommentMergeRequest::list(&self.0, args)?;
+                if *num_lists > 1 {
+                    comments.push(comment(2, "LGTM"));
+                }
+                Ok(comments)
+            }
+            fn num_pages(&self, args: CommentMergeRequestListBodyArgs) -> Result<Option<u32>> {
+                CommentMergeRequest::num_pages(&self.0, args)
+            }
+            fn num_resources(
+                &self,
+                args: CommentMergeRequestListBodyArgs,
+            ) -> Result<Option<crate::api_traits::NumberDeltaErr>> {
+                CommentMergeRequest::num_resources(&self.0, args)
+            }
+        }
+        let remote = Arc::new(LandedComment(
+            MockRemoteProject {
+                list_comments: vec![comment(1, "LGTM")],
+                comment_failures: 1,
+                ..Default::default()
+            },
+            Mutex::new(0),
+        ));
+        let throttler = Rc::new(MockThrottler::new(None));
+        create_comment(
+            remote.clone(),
+            comment_with_retries(1, "LGTM", 2),
+            None::<Cursor<&str>>,
+            merge_retry(2, throttler),
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(1, *remote.0.comment_attempts.lock().unwrap());
+    }
+
+    #[test]
+    fn test_create_comment_without_retries_fails_on_transport_error() {
+        let remote = Arc::new(MockRemoteProject {
+            comment_failures: 1,
+            ..Default::default()
+        });
+        let throttler = Rc::new(MockThrottler::new(None));
+        assert!(create_comment(
+            remote.clone(),
+            comment_with_retries(1, "LGTM", 0),
+            None::<Cursor<&str>>,
+            merge_retry(0, throttler),
+            Vec::new(),
+        )
+        .is_err());
+        assert_eq!(1, *remote.comment_attempts.lock().unwrap());
     }
 
     #[test]
@@ -1707,7 +3676,8 @@ mod tests {
             .title("New feature".to_string())
             .web_url("https://gitlab.com/owner/repo/-/merge_requests/1".to_string())
             .description("Implement get merge request".to_string())
-            .merged_at("2024-03-03T00:00:00Z".to_string())
+            .updated_at("2024-03-03T00:00:00Z".parse().unwrap())
+            .merged_at(Some("2024-03-03T00:00:00Z".parse().unwrap()))
             .pipeline_id(Some(1))
             .pipeline_url(Some(
                 "https://gitlab.com/owner/repo/-/pipelines/1".to_string(),
@@ -1724,7 +3694,7 @@ mod tests {
         get_merge_request_details(remote, cli_args, &mut writer).unwrap();
         assert_eq!(
             "ID|Title|Source Branch|SHA|Description|Author|URL|Updated at|Merged at|Pipeline ID|Pipeline URL\n\
-             1|New feature|||Implement get merge request||https://gitlab.com/owner/repo/-/merge_requests/1||2024-03-03T00:00:00Z|1|https://gitlab.com/owner/repo/-/pipelines/1\n",
+             1|New feature|||Implement get merge request||https://gitlab.com/owner/repo/-/merge_requests/1|2024-03-03T00:00:00Z|2024-03-03T00:00:00Z|1|https://gitlab.com/owner/repo/-/pipelines/1\n",
             String::from_utf8(writer).unwrap(),
         )
     }
@@ -1750,11 +3720,100 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_cmds_fetch_cli_arg() {
-        let remote = Arc::new(MockRemoteProject::default());
-        let cli_args = MergeRequestCliArgs::builder()
-            .title(Some("title cli".to_string()))
+    fn file_changes() -> Vec<FileChange> {
+        vec![
+            FileChange::builder()
+                .path("src/main.rs".to_string())
+                .old_path("src/main.rs".to_string())
+                .additions(1)
+                .deletions(1)
+                .patch("@@ -1 +1 @@\n-old\n+new".to_string())
+                .build()
+                .unwrap(),
+            FileChange::builder()
+                .path("docs/intro.md".to_string())
+                .old_path("docs/intro.md".to_string())
+                .status(FileChangeStatus::Added)
+                .additions(1)
+                .patch("@@ -0,0 +1 @@\n+# Introduction\n".to_string())
+                .build()
+                .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_diff_unified() {
+        let remote = Arc::new(MockRemoteProject {
+            changes: file_changes(),
+            ..Default::default()
+        });
+        let cli_args = MergeRequestGetCliArgs::builder()
+            .id(1)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        diff(remote, cli_args, false, &mut writer).unwrap();
+        assert_eq!(
+            "diff --git a/src/main.rs b/src/main.rs\n\
+             --- a/src/main.rs\n\
+             +++ b/src/main.rs\n\
+             @@ -1 +1 @@\n-old\n+new\n\
+             diff --git a/docs/intro.md b/docs/intro.md\n\
+             --- /dev/null\n\
+             +++ b/docs/intro.md\n\
+             @@ -0,0 +1 @@\n+# Introduction\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_diff_stat() {
+        let remote = Arc::new(MockRemoteProject {
+            changes: file_changes(),
+            ..Default::default()
+        });
+        let cli_args = MergeRequestGetCliArgs::builder()
+            .id(1)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        diff(remote, cli_args, true, &mut writer).unwrap();
+        assert_eq!(
+            "File|Status|Additions|Deletions\n\
+             src/main.rs|modified|1|1\n\
+             docs/intro.md|added|1|0\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_toggle_draft_ready_for_review() {
+        let response = MergeRequestResponse::builder()
+            .id(1)
+            .web_url("https://gitlab.com/owner/repo/-/merge_requests/1".to_string())
+            .build()
+            .unwrap();
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![response])
+                .build()
+                .unwrap(),
+        );
+        let mut writer = Vec::new();
+        toggle_draft(remote, 1, false, &mut writer).unwrap();
+        assert_eq!(
+            "Merge request ready for review: https://gitlab.com/owner/repo/-/merge_requests/1\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_cmds_fetch_cli_arg() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let cli_args = MergeRequestCliArgs::builder()
+            .title(Some("title cli".to_string()))
             .body_from_commit(None)
             .description(None)
             .description_from_file(None)
@@ -1796,14 +3855,14 @@ mod tests {
                 .id(1)
                 .body("Great work!".to_string())
                 .author("user1".to_string())
-                .created_at("2021-01-01".to_string())
+                .created_at(Some("2021-01-01T00:00:00Z".parse().unwrap()))
                 .build()
                 .unwrap(),
             Comment::builder()
                 .id(2)
                 .body("Keep it up!".to_string())
                 .author("user2".to_string())
-                .created_at("2021-01-02".to_string())
+                .created_at(Some("2021-01-02T00:00:00Z".parse().unwrap()))
                 .build()
                 .unwrap(),
         ];
@@ -1822,12 +3881,329 @@ mod tests {
         list_comments(remote, body_args, cli_args, &mut buf).unwrap();
         assert_eq!(
             "ID|Body|Author|Created at\n\
-             1|Great work!|user1|2021-01-01\n\
-             2|Keep it up!|user2|2021-01-02\n",
+             1|Great work!|user1|2021-01-01T00:00:00Z\n\
+             2|Keep it up!|user2|2021-01-02T00:00:00Z\n",
+            String::from_utf8(buf).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_list_merge_request_comments_with_reactions() {
+        let comments = vec![Comment::builder()
+            .id(1)
+            .body("Great work!".to_string())
+            .author("user1".to_string())
+            .created_at(Some("2021-01-01T00:00:00Z".parse().unwrap()))
+            .reactions(vec![Reaction::new("tada", 2), Reaction::new("eyes", 1)])
+            .build()
+            .unwrap()];
+        let remote = Arc::new(MockRemoteProject::new(comments));
+        let body_args = CommentMergeRequestListBodyArgs::builder()
+            .id(1)
+            .list_args(None)
+            .reactions(true)
+            .build()
+            .unwrap();
+        let cli_args = CommentMergeRequestListCliArgs::builder()
+            .id(1)
+            .list_args(
+                ListRemoteCliArgs::builder()
+                    .get_args(
+                        GetRemoteCliArgs::builder()
+                            .display_optional(true)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_comments(remote, body_args, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "ID|Body|Author|Created at|Reactions\n\
+             1|Great work!|user1|2021-01-01T00:00:00Z|:tada: 2, :eyes: 1\n",
             String::from_utf8(buf).unwrap(),
         );
     }
 
+    #[test]
+    fn test_edit_merge_request_comment() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let mut writer = Vec::new();
+        edit_comment(remote.clone(), 23, 301, "LGTM!".to_string(), &mut writer).unwrap();
+        assert_eq!("301: LGTM!", *remote.comment_argument.lock().unwrap());
+        assert_eq!(
+            "Comment 301 of merge request 23 updated\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_delete_merge_request_comment() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let mut writer = Vec::new();
+        delete_comment(remote.clone(), 23, 301, &mut writer).unwrap();
+        assert_eq!("23: 301", *remote.comment_argument.lock().unwrap());
+        assert_eq!(
+            "Comment 301 of merge request 23 deleted\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_react_to_merge_request_comment() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let body_args = ReactionBodyArgs::builder()
+            .id(23)
+            .comment_id(Some(301))
+            .emoji("thumbsup".to_string())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        react(remote.clone(), body_args, &mut writer).unwrap();
+        assert_eq!(
+            "Reacted with :thumbsup: to comment 301 of merge request 23\n",
+            String::from_utf8(writer).unwrap(),
+        );
+        let reaction = remote.reaction.lock().unwrap();
+        assert_eq!("thumbsup", reaction.as_ref().unwrap().emoji);
+    }
+
+    fn suggestion_args(replacement: &str, message: Option<&str>) -> SuggestionBodyArgs {
+        SuggestionBodyArgs::builder()
+            .id(23)
+            .file("src/main.rs".to_string())
+            .line(10)
+            .replacement(replacement.to_string())
+            .message(message.map(String::from))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_suggest_change_on_merge_request() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let mut writer = Vec::new();
+        suggest(
+            remote.clone(),
+            suggestion_args("let x = 1;", None),
+            &mut writer,
+        )
+        .unwrap();
+        assert_eq!(
+            "Suggested change at src/main.rs:10 on merge request 23\n",
+            String::from_utf8(writer).unwrap(),
+        );
+        assert!(remote.suggestion.lock().unwrap().is_some());
+    }
+
+    fn pending_suggestions_remote() -> Arc<MockRemoteProject> {
+        let suggestion = |id: i64, line: i64| {
+            Suggestion::builder()
+                .id(id)
+                .file("src/main.rs".to_string())
+                .line(line)
+                .author("tom".to_string())
+                .replacement("let x = 1;".to_string())
+                .build()
+                .unwrap()
+        };
+        Arc::new(MockRemoteProject {
+            suggestions: vec![suggestion(5, 10), suggestion(6, 20)],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_apply_selected_suggestions() {
+        let remote = pending_suggestions_remote();
+        let cli_args = SuggestionApplyCliArgs::builder().id(23).build().unwrap();
+        let mut writer = Vec::new();
+        apply_suggestions(
+            remote.clone(),
+            cli_args,
+            |suggestions| {
+                assert_eq!(2, suggestions.len());
+                Ok(vec![suggestions[1].id])
+            },
+            &mut writer,
+        )
+        .unwrap();
+        assert_eq!(vec![6], *remote.applied_suggestions.lock().unwrap());
+        assert_eq!(
+            "Applied 1 suggestions to merge request 23\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_apply_all_suggestions_does_not_prompt() {
+        let remote = pending_suggestions_remote();
+        let cli_args = SuggestionApplyCliArgs::builder()
+            .id(23)
+            .all(true)
+            .build()
+            .unwrap();
+        apply_suggestions(
+            remote.clone(),
+            cli_args,
+            |_| panic!("No prompt expected"),
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(vec![5, 6], *remote.applied_suggestions.lock().unwrap());
+    }
+
+    #[test]
+    fn test_apply_suggestion_not_pending_is_error() {
+        let remote = pending_suggestions_remote();
+        let cli_args = SuggestionApplyCliArgs::builder()
+            .id(23)
+            .suggestion_ids(vec![5, 7])
+            .build()
+            .unwrap();
+        let err =
+            apply_suggestions(remote.clone(), cli_args, |_| Ok(vec![]), Vec::new()).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert_eq!("Suggestion 7 is not pending on merge request 23", msg)
+            }
+            _ => panic!("Expected GRError::PreconditionNotMet"),
+        }
+        assert!(remote.applied_suggestions.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_suggestions() {
+        let remote = pending_suggestions_remote();
+        let cli_args = MergeRequestGetCliArgs::builder()
+            .id(23)
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        list_suggestions(remote, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "ID|File|Line|Author|Replacement\n\
+             5|src/main.rs|10|tom|let x = 1;\n\
+             6|src/main.rs|20|tom|let x = 1;\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    fn note(id: i64, author: &str, body: &str) -> Comment {
+        Comment::builder()
+            .id(id)
+            .author(author.to_string())
+            .body(body.to_string())
+            .created_at(Some("2024-01-15T10:30:00Z".parse().unwrap()))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_list_discussions_one_comment_per_row() {
+        let remote = Arc::new(MockRemoteProject {
+            discussions: vec![
+                Discussion::builder()
+                    .id("6a9c17".to_string())
+                    .file("src/main.rs".to_string())
+                    .line(Some(10))
+                    .notes(vec![
+                        note(301, "tom", "Should this be a constant?"),
+                        note(302, "ana", "Done"),
+                    ])
+                    .build()
+                    .unwrap(),
+                Discussion::builder()
+                    .id("8b0d22".to_string())
+                    .resolved(true)
+                    .notes(vec![note(303, "tom", "LGTM")])
+                    .build()
+                    .unwrap(),
+            ],
+            ..Default::default()
+        });
+        let cli_args = MergeRequestGetCliArgs::builder()
+            .id(23)
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        list_discussions(remote, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "Discussion|ID|Author|Body|Location|Resolved\n\
+             6a9c17|301|tom|Should this be a constant?|src/main.rs:10|false\n\
+             6a9c17|302|ana|Done|src/main.rs:10|false\n\
+             8b0d22|303|tom|LGTM||true\n",
+            String::from_utf8(writer).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_list_discussions_none_found() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let cli_args = MergeRequestGetCliArgs::builder()
+            .id(23)
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        list_discussions(remote, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "No discussions found.\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reply_discussion() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let body_args = DiscussionReplyBodyArgs::builder()
+            .id(23)
+            .discussion_id("6a9c17".to_string())
+            .body("Done".to_string())
+            .build()
+            .unwrap();
+        let mut writer = Vec::new();
+        reply_discussion(remote.clone(), body_args, &mut writer).unwrap();
+        assert_eq!(
+            "Replied to discussion 6a9c17 on merge request 23\n",
+            String::from_utf8(writer).unwrap(),
+        );
+        let reply = remote.discussion_reply.lock().unwrap();
+        assert_eq!("Done", reply.as_ref().unwrap().body);
+    }
+
+    #[test]
+    fn test_suggestion_body_with_message() {
+        let args = suggestion_args("let x = 1;", Some("Use a binding"));
+        assert_eq!(
+            "Use a binding\n\n```suggestion:-0+0\nlet x = 1;\n```",
+            args.body("suggestion:-0+0")
+        );
+    }
+
+    #[test]
+    fn test_suggestion_body_fence_longer_than_replacement_backticks() {
+        let args = suggestion_args("/// ```\n/// gr mr list\n/// ```", None);
+        assert_eq!(
+            "````suggestion\n/// ```\n/// gr mr list\n/// ```\n````",
+            args.body("suggestion")
+        );
+    }
+
+    #[test]
+    fn test_count_reactions_most_used_first() {
+        let emojis = ["eyes", "tada", "tada"].map(String::from);
+        assert_eq!(
+            vec![Reaction::new("tada", 2), Reaction::new("eyes", 1)],
+            Reaction::count(emojis)
+        );
+    }
+
     #[test]
     fn test_gather_member_from_members_list() {
         let members = vec![
@@ -1849,4 +4225,791 @@ mod tests {
         assert_eq!(member.username, assignee_username);
         assert_eq!(member.id, 2);
     }
+
+    #[test]
+    fn test_owner_reviewer_is_first_known_member() {
+        let members = vec![Member::builder()
+            .id(2)
+            .username("user2".to_string())
+            .build()
+            .unwrap()];
+        let owners = Owners {
+            reviewers: vec!["outsider".to_string(), "user2".to_string()],
+            labels: vec![],
+        };
+        let member = owner_reviewer(&members, &owners).unwrap();
+        assert_eq!(2, member.id);
+        assert!(owner_reviewer(&members, &Owners::default()).is_none());
+    }
+
+    struct RebaseMock {
+        statuses: RefCell<Vec<RebaseStatus>>,
+    }
+
+    impl RebaseMock {
+        // Statuses are returned in order on every poll.
+        fn new(mut statuses: Vec<RebaseStatus>) -> Arc<Self> {
+            statuses.reverse();
+            Arc::new(RebaseMock {
+                statuses: RefCell::new(statuses),
+            })
+        }
+    }
+
+    impl MergeRequestRebase for RebaseMock {
+        fn rebase(&self, id: i64) -> Result<RebaseStatus> {
+            Ok(rebase_status(id, true, None))
+        }
+
+        fn rebase_status(&self, started: &RebaseStatus) -> Result<RebaseStatus> {
+            assert_eq!(23, started.id);
+            Ok(self.statuses.borrow_mut().pop().unwrap())
+        }
+    }
+
+    fn rebase_status(id: i64, in_progress: bool, error: Option<&str>) -> RebaseStatus {
+        RebaseStatus::builder()
+            .id(id)
+            .in_progress(in_progress)
+            .sha("abc123".to_string())
+            .error(error.map(|error| error.to_string()))
+            .build()
+            .unwrap()
+    }
+
+    fn rebase_args(timeout: u64) -> MergeRequestRebaseCliArgs {
+        MergeRequestRebaseCliArgs::builder()
+            .id(23)
+            .interval(Seconds::new(2))
+            .timeout(Seconds::new(timeout))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rebase_polls_until_finished() {
+        let remote = RebaseMock::new(vec![
+            rebase_status(23, true, None),
+            rebase_status(23, false, None),
+        ]);
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        rebase(remote, rebase_args(300), &throttler, &mut buf).unwrap();
+        assert_eq!(
+            "Rebasing merge request 23\n\
+             Merge request 23 rebased onto its target branch\n\
+             Head commit: abc123\n",
+            String::from_utf8(buf).unwrap()
+        );
+        assert_eq!(2, *throttler.throttled());
+    }
+
+    #[test]
+    fn test_rebase_failed_is_error() {
+        let remote = RebaseMock::new(vec![rebase_status(23, false, Some("Rebase failed"))]);
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        let err = rebase(remote, rebase_args(300), &throttler, &mut buf).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert_eq!("Rebase of merge request 23 failed: Rebase failed", msg)
+            }
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_rebase_gives_up_after_timeout() {
+        let remote = RebaseMock::new(vec![
+            rebase_status(23, true, None),
+            rebase_status(23, true, None),
+        ]);
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        assert!(rebase(remote, rebase_args(4), &throttler, &mut buf).is_err());
+        assert_eq!(2, *throttler.throttled());
+    }
+
+    struct MergeQueueMock {
+        position: Option<u32>,
+    }
+
+    impl MergeQueue for MergeQueueMock {
+        fn add(&self, id: i64) -> Result<MergeQueueEntry> {
+            self.status(id)
+        }
+
+        fn remove(&self, _id: i64) -> Result<()> {
+            Ok(())
+        }
+
+        fn status(&self, id: i64) -> Result<MergeQueueEntry> {
+            Ok(MergeQueueEntry::builder()
+                .id(id)
+                .position(self.position)
+                .status("fresh".to_string())
+                .target_branch("main".to_string())
+                .eta(self.position.map(|_| Seconds::new(1260)))
+                .build()
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_queue_add_reports_position() {
+        let remote = Arc::new(MergeQueueMock { position: Some(2) });
+        let mut buf = Vec::new();
+        queue_add(remote, 23, &mut buf).unwrap();
+        assert_eq!(
+            "Merge request 23 added to the merge queue at position 2\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_queue_status_displays_position_and_eta() {
+        let remote = Arc::new(MergeQueueMock { position: Some(2) });
+        let cli_args = MergeRequestGetCliArgs::builder()
+            .id(23)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        queue_status(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "ID|Position|Status|Target branch|ETA\n23|2|fresh|main|21m\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    fn merge_retry(max_retries: u32, throttler: Rc<MockThrottler>) -> RetryOperation {
+        RetryOperation::new(
+            max_retries,
+            Seconds::new(DEFAULT_OPERATION_RETRY_WAIT_SECONDS),
+            Box::new(Exponential),
+            Box::new(throttler),
+        )
+    }
+
+    fn merge_args(id: i64) -> MergeRequestMergeBodyArgs {
+        MergeRequestMergeBodyArgs::builder().id(id).build().unwrap()
+    }
+
+    fn head_at(sha: &str) -> MergeRequestResponse {
+        MergeRequestResponse::builder()
+            .id(23)
+            .head_sha(sha.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn review_at(sha: &str) -> ReviewFile {
+        let mut review = ReviewFile::new(23, "Add offline reviews", sha);
+        review.comments.push(review::ReviewComment {
+            file: "src/main.rs".to_string(),
+            line: 10,
+            body: "Handle the error".to_string(),
+        });
+        review
+    }
+
+    #[test]
+    fn test_export_review_of_merge_request_head() {
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![head_at("9c5bf00")])
+                .build()
+                .unwrap(),
+        );
+        let mut buf = Vec::new();
+        export_review(remote, 23, &mut buf).unwrap();
+        let review = String::from_utf8(buf).unwrap();
+        assert!(review.contains("head_sha = \"9c5bf00\"\n"));
+        assert!(review.contains("# [[comments]]\n"));
+    }
+
+    #[test]
+    fn test_submit_review() {
+        let mr_remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![head_at("9c5bf00")])
+                .build()
+                .unwrap(),
+        );
+        let review_remote = Arc::new(MockRemoteProject::default());
+        let mut buf = Vec::new();
+        submit_review(
+            mr_remote,
+            review_remote.clone(),
+            review_at("9c5bf00"),
+            false,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            Some(review_at("9c5bf00")),
+            *review_remote.review.lock().unwrap()
+        );
+        assert_eq!(
+            "Submitted review with 1 inline comments on merge request 23\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_review_request_changes() {
+        let remote = Arc::new(MockRemoteProject::default());
+        let body_args = ReviewBodyArgs::builder()
+            .id(23)
+            .event(ReviewEvent::RequestChanges)
+            .body("Handle the error instead of unwrapping".to_string())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        review(remote.clone(), body_args, &mut buf).unwrap();
+        let args = remote.review_args.lock().unwrap().clone().unwrap();
+        assert_eq!(ReviewEvent::RequestChanges, args.event);
+        assert_eq!("Handle the error instead of unwrapping", args.body);
+        assert_eq!(
+            "Requested changes on merge request 23\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_submit_review_after_new_commits_is_error() {
+        let mr_remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![head_at("e4a1d2c")])
+                .build()
+                .unwrap(),
+        );
+        let review_remote = Arc::new(MockRemoteProject::default());
+        let err = submit_review(
+            mr_remote.clone(),
+            review_remote.clone(),
+            review_at("9c5bf00"),
+            false,
+            Vec::new(),
+        )
+        .unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert!(msg.contains("(e4a1d2c != 9c5bf00)"))
+            }
+            _ => panic!("Expected GRError::PreconditionNotMet"),
+        }
+        assert!(review_remote.review.lock().unwrap().is_none());
+        submit_review(
+            mr_remote,
+            review_remote.clone(),
+            review_at("9c5bf00"),
+            true,
+            Vec::new(),
+        )
+        .unwrap();
+        assert!(review_remote.review.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_checkout_merge_request_from_same_project() {
+        let merge_request = MergeRequestResponse::builder()
+            .id(23)
+            .source_branch("feature".to_string())
+            .head_ref("refs/pull/23/head".to_string())
+            .build()
+            .unwrap();
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![merge_request])
+                .build()
+                .unwrap(),
+        );
+        let responses = (0..2)
+            .map(|_| ShellResponse::builder().build().unwrap())
+            .collect();
+        let runner = Arc::new(MockRunner::new(responses));
+        let mut buf = Vec::new();
+        checkout(remote, 23, runner.clone(), &mut buf).unwrap();
+        assert_eq!(
+            "/bin/sh -c git checkout origin/feature -b feature",
+            *runner.cmd()
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_checkout_merge_request_from_fork_uses_head_ref() {
+        let merge_request = MergeRequestResponse::builder()
+            .id(23)
+            .source_branch("main".to_string())
+            .from_fork(true)
+            .head_ref("refs/pull/23/head".to_string())
+            .build()
+            .unwrap();
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![merge_request])
+                .build()
+                .unwrap(),
+        );
+        let responses = (0..4)
+            .map(|_| ShellResponse::builder().build().unwrap())
+            .collect();
+        let runner = Arc::new(MockRunner::new(responses));
+        let mut buf = Vec::new();
+        checkout(remote, 23, runner.clone(), &mut buf).unwrap();
+        assert_eq!(4, *runner.run_count.borrow());
+        assert_eq!(
+            "git config branch.mr/23/main.merge refs/pull/23/head",
+            *runner.cmd()
+        );
+        assert_eq!(
+            "Merge request 23 comes from a fork. Checked out into branch mr/23/main tracking refs/pull/23/head\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_merge_with_short_sha_sends_full_head_sha() {
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![head_at("9c5bf00e5e1d6e9e96cda3fa3986f680fcdbcd7f")])
+                .build()
+                .unwrap(),
+        );
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .message(Some("Release 1.2".to_string()))
+            .sha(Some("9c5bf00e".to_string()))
+            .build()
+            .unwrap();
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        merge(remote.clone(), args, merge_retry(0, throttler), &mut buf).unwrap();
+        let merged_with = remote.merge_args.lock().unwrap().clone().unwrap();
+        assert_eq!(
+            Some("9c5bf00e5e1d6e9e96cda3fa3986f680fcdbcd7f".to_string()),
+            merged_with.sha
+        );
+        assert_eq!(Some("Release 1.2".to_string()), merged_with.message);
+    }
+
+    #[test]
+    fn test_merge_refuses_when_head_has_changed() {
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![head_at("fcbb7490d4a216c2d162fa5466184e30dae1f087")])
+                .build()
+                .unwrap(),
+        );
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .sha(Some("9c5bf00e".to_string()))
+            .build()
+            .unwrap();
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        let err = merge(remote.clone(), args, merge_retry(3, throttler), &mut buf).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(_)) => (),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+        assert!(remote.merge_args.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_merge_retries_while_not_mergeable() {
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .not_mergeable(2)
+                .build()
+                .unwrap(),
+        );
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        merge(
+            remote,
+            merge_args(23),
+            merge_retry(3, throttler.clone()),
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            "Merge request 23 is not mergeable yet. Retrying in 7s (1/3)\n\
+             Merge request 23 is not mergeable yet. Retrying in 9s (2/3)\n\
+             Merge request merged: https://gitlab.com/jordilin/gitlapi/-/merge_requests/23\n",
+            String::from_utf8(buf).unwrap()
+        );
+        assert_eq!(2, *throttler.throttled());
+    }
+
+    #[test]
+    fn test_merge_when_pipeline_succeeds_reports_queued_merge() {
+        let remote = Arc::new(MergeRequestRemoteMock::builder().build().unwrap());
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        merge(remote.clone(), args, merge_retry(0, throttler), &mut buf).unwrap();
+        assert!(
+            remote
+                .merge_args
+                .lock()
+                .unwrap()
+                .clone()
+                .unwrap()
+                .auto_merge
+        );
+        assert_eq!(
+            "Merge request 23 will be merged when its pipeline succeeds: https://gitlab.com/jordilin/gitlapi/-/merge_requests/23\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_merge_not_mergeable_without_retries_is_error() {
+        let remote = Arc::new(
+            MergeRequestRemoteMock::builder()
+                .not_mergeable(1)
+                .build()
+                .unwrap(),
+        );
+        let throttler = Rc::new(MockThrottler::new(None));
+        let mut buf = Vec::new();
+        assert!(merge(
+            remote,
+            merge_args(23),
+            merge_retry(0, throttler.clone()),
+            &mut buf
+        )
+        .is_err());
+        assert_eq!(0, *throttler.throttled());
+        assert!(buf.is_empty());
+    }
+
+    struct DependencyMock {
+        dependencies: Vec<MergeRequestResponse>,
+    }
+
+    impl MergeRequestDependency for DependencyMock {
+        fn add_dependency(&self, _id: i64, _depends_on: i64) -> Result<()> {
+            Ok(())
+        }
+
+        fn list_dependencies(&self, _id: i64) -> Result<Vec<MergeRequestResponse>> {
+            Ok(self.dependencies.clone())
+        }
+    }
+
+    fn dependency(id: i64, merged_at: &str) -> MergeRequestResponse {
+        MergeRequestResponse::builder()
+            .id(id)
+            .web_url(format!(
+                "https://gitlab.com/jordilin/gitlapi/-/merge_requests/{}",
+                id
+            ))
+            .merged_at(time::parse_timestamp(merged_at))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_check_dependencies_refuses_open_dependencies() {
+        let remote = Arc::new(DependencyMock {
+            dependencies: vec![
+                dependency(31, "2024-02-09T16:02:11.063Z"),
+                dependency(32, ""),
+            ],
+        });
+        let err = check_dependencies(remote, 33).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => assert_eq!(
+                "Merge request 33 depends on merge requests not merged yet: \
+                 32 (https://gitlab.com/jordilin/gitlapi/-/merge_requests/32)",
+                msg
+            ),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_check_dependencies_all_merged_is_ok() {
+        let remote = Arc::new(DependencyMock {
+            dependencies: vec![dependency(31, "2024-02-09T16:02:11.063Z")],
+        });
+        assert!(check_dependencies(remote, 33).is_ok());
+    }
+
+    #[derive(Default)]
+    struct UserInfoMock {
+        auth_user_calls: Mutex<u32>,
+        lookups: Mutex<Vec<String>>,
+    }
+
+    impl UserInfo for UserInfoMock {
+        fn get_auth_user(&self) -> Result<Member> {
+            *self.auth_user_calls.lock().unwrap() += 1;
+            Ok(Member::builder().id(1).username("me".to_string()).build()?)
+        }
+
+        fn get(&self, args: &UserCliArgs) -> Result<Member> {
+            self.lookups.lock().unwrap().push(args.username.clone());
+            Ok(Member::builder()
+                .id(2)
+                .username(args.username.clone())
+                .build()?)
+        }
+
+        fn get_profile(&self, _args: &UserCliArgs) -> Result<crate::cmds::user::UserProfile> {
+            todo!()
+        }
+    }
+
+    #[test]
+    fn test_resolve_filter_users_looks_up_each_user_once() {
+        let remote = Arc::new(UserInfoMock::default());
+        let author = Some(MergeRequestUser::Me);
+        let assignee = Some(MergeRequestUser::Me);
+        let reviewer = Some(MergeRequestUser::Other("jdoe".to_string()));
+        let members = resolve_filter_users(
+            remote.clone(),
+            &[&author, &assignee, &reviewer],
+            &GetRemoteCliArgs::default(),
+        )
+        .unwrap();
+        assert_eq!(3, members.len());
+        assert_eq!("me", members[0].as_ref().unwrap().username);
+        assert_eq!("me", members[1].as_ref().unwrap().username);
+        assert_eq!("jdoe", members[2].as_ref().unwrap().username);
+        assert_eq!(1, *remote.auth_user_calls.lock().unwrap());
+        assert_eq!(vec!["jdoe"], *remote.lookups.lock().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_filter_users_no_filters() {
+        let remote = Arc::new(UserInfoMock::default());
+        let members = resolve_filter_users(
+            remote.clone(),
+            &[&None, &None],
+            &GetRemoteCliArgs::default(),
+        )
+        .unwrap();
+        assert_eq!(vec![None, None], members);
+        assert_eq!(0, *remote.auth_user_calls.lock().unwrap());
+    }
+
+    fn open_mr(id: i64, updated_at: &str) -> MergeRequestResponse {
+        MergeRequestResponse::builder()
+            .id(id)
+            .title(format!("Merge request {}", id))
+            .author("jdoe".to_string())
+            .web_url(format!(
+                "https://gitlab.com/jordilin/gitlapi/-/merge_requests/{}",
+                id
+            ))
+            .updated_at(updated_at.parse().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    // 2024-02-12T10:00:00Z
+    fn nag_now() -> Seconds {
+        Seconds::new(1707732000)
+    }
+
+    fn nag_args(report: bool) -> MergeRequestNagCliArgs {
+        MergeRequestNagCliArgs::builder()
+            .older_than(Seconds::new(3 * 86400))
+            .report(report)
+            .yes(true)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn nag_mr_remote() -> Arc<MergeRequestRemoteMock> {
+        Arc::new(
+            MergeRequestRemoteMock::builder()
+                .merge_requests(vec![
+                    open_mr(1, "2024-02-11T10:00:00Z"),
+                    open_mr(2, "2024-02-07T10:00:00Z"),
+                ])
+                .build()
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_nag_posts_templated_reminder_on_stale_merge_requests() {
+        let comment_remote = Arc::new(MockRemoteProject::default());
+        let mut buf = Vec::new();
+        nag(
+            nag_mr_remote(),
+            comment_remote.clone(),
+            "@{author} {title} has been waiting for {age}",
+            nag_args(false),
+            nag_now,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            "Reminder posted: https://gitlab.com/jordilin/gitlapi/-/merge_requests/2\n",
+            String::from_utf8(buf).unwrap()
+        );
+        assert_eq!(
+            "@jdoe Merge request 2 has been waiting for 5d",
+            *comment_remote.comment_argument.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_nag_default_reminder() {
+        let comment_remote = Arc::new(MockRemoteProject::default());
+        let mut buf = Vec::new();
+        nag(
+            nag_mr_remote(),
+            comment_remote.clone(),
+            "",
+            nag_args(false),
+            nag_now,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            "Friendly reminder: this merge request has been awaiting review for 5d.",
+            *comment_remote.comment_argument.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_nag_report_does_not_post_comments() {
+        let comment_remote = Arc::new(MockRemoteProject::default());
+        let mut buf = Vec::new();
+        nag(
+            nag_mr_remote(),
+            comment_remote.clone(),
+            "",
+            nag_args(true),
+            nag_now,
+            &mut buf,
+        )
+        .unwrap();
+        let report = String::from_utf8(buf).unwrap();
+        assert!(report.starts_with("ID|Title|Source Branch|Author|URL|Updated at\n"));
+        assert!(report.contains("2|Merge request 2|"));
+        assert!(!report.contains("1|Merge request 1|"));
+        assert!(!*comment_remote.comment_called.lock().unwrap());
+    }
+
+    fn repo_merge_requests(
+        repo: &str,
+        _cli_args: MergeRequestListCliArgs,
+    ) -> Result<Vec<MergeRequestResponse>> {
+        match repo {
+            "github.com/jordilin/gitar" => Ok(vec![MergeRequestResponse::builder()
+                .id(1)
+                .title("Add cache prune".to_string())
+                .build()
+                .unwrap()]),
+            "gitlab.com/jordilin/gitlapi" => Ok(vec![
+                MergeRequestResponse::builder()
+                    .id(23)
+                    .title("Fix pipeline".to_string())
+                    .build()
+                    .unwrap(),
+                MergeRequestResponse::builder()
+                    .id(24)
+                    .title("Bump version".to_string())
+                    .build()
+                    .unwrap(),
+            ]),
+            _ => Err(GRError::RemoteServerError("502 Bad Gateway".to_string()).into()),
+        }
+    }
+
+    fn list_from_cli_args() -> MergeRequestListCliArgs {
+        MergeRequestListCliArgs::new(
+            MergeRequestState::Opened,
+            ListRemoteCliArgs::builder().build().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_list_merge_requests_from_several_repos_skips_failing_ones() {
+        let repos = vec![
+            "gitlab.com/jordilin/gitlapi".to_string(),
+            "gitlab.company.com/team/api".to_string(),
+            "github.com/jordilin/gitar".to_string(),
+        ];
+        let mut buf = Vec::new();
+        list_merge_requests_from(
+            MergeRequestSource::Repo,
+            repos,
+            list_from_cli_args(),
+            repo_merge_requests,
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            "Repo|ID|Title|Source Branch|Author|URL|Updated at\n\
+             gitlab.com/jordilin/gitlapi|23|Fix pipeline||||1970-01-01T00:00:00Z\n\
+             gitlab.com/jordilin/gitlapi|24|Bump version||||1970-01-01T00:00:00Z\n\
+             github.com/jordilin/gitar|1|Add cache prune||||1970-01-01T00:00:00Z\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_merge_requests_from_domains_adds_domain_column() {
+        let mut buf = Vec::new();
+        list_merge_requests_from(
+            MergeRequestSource::Domain,
+            vec!["github.com".to_string()],
+            list_from_cli_args(),
+            |_, _| {
+                Ok(vec![MergeRequestResponse::builder()
+                    .id(1)
+                    .title("Add cache prune".to_string())
+                    .build()
+                    .unwrap()])
+            },
+            &mut buf,
+        )
+        .unwrap();
+        assert!(String::from_utf8(buf)
+            .unwrap()
+            .starts_with("Domain|ID|Title|"));
+    }
+
+    #[test]
+    fn test_list_merge_requests_from_all_failing_is_error() {
+        let result = list_merge_requests_from(
+            MergeRequestSource::Repo,
+            vec!["gitlab.company.com/team/api".to_string()],
+            list_from_cli_args(),
+            repo_merge_requests,
+            Vec::new(),
+        );
+        let err = result.unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::ApplicationError(msg)) => {
+                assert_eq!("Could not list merge requests in any repo", msg)
+            }
+            _ => panic!("Expected GRError::ApplicationError"),
+        }
+    }
+
+    #[test]
+    fn test_merge_labels_keeps_owner_labels_first_without_duplicates() {
+        let owner_labels = vec!["cache".to_string(), "bug".to_string()];
+        let cli_labels = vec!["bug".to_string(), "ui".to_string()];
+        assert_eq!(
+            vec!["cache", "bug", "ui"],
+            merge_labels(&owner_labels, &cli_labels)
+        );
+    }
 }
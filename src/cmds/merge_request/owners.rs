@@ -0,0 +1,218 @@
+use regex::Regex;
+use serde::Deserialize;
+
+/// Maps changed paths to the reviewers and labels that should be added to a
+/// merge request. Configured per domain or project in the gitar config file:
+///
+/// ```toml
+/// [gitlab_com.merge_requests]
+/// rules = [
+///     { paths = ["src/cache/", "*.sql"], reviewers = ["jdoe"], labels = ["cache"] },
+/// ]
+/// ```
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct OwnershipRule {
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// Reviewers and labels gathered from the rules matching a set of changed
+/// files. Preserves the order in which rules are declared, without
+/// duplicates.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Owners {
+    pub reviewers: Vec<String>,
+    pub labels: Vec<String>,
+}
+
+impl Owners {
+    pub fn add_reviewers(&mut self, reviewers: Vec<String>) {
+        for reviewer in reviewers {
+            if !self.reviewers.contains(&reviewer) {
+                self.reviewers.push(reviewer);
+            }
+        }
+    }
+}
+
+pub fn compute<S: AsRef<str>>(rules: &[OwnershipRule], changed_files: &[S]) -> Owners {
+    let mut owners = Owners::default();
+    for rule in rules {
+        let matches = rule.paths.iter().any(|pattern| {
+            changed_files
+                .iter()
+                .any(|file| path_matches(pattern, file.as_ref()))
+        });
+        if !matches {
+            continue;
+        }
+        owners.add_reviewers(rule.reviewers.clone());
+        for label in &rule.labels {
+            if !owners.labels.contains(label) {
+                owners.labels.push(label.clone());
+            }
+        }
+    }
+    owners
+}
+
+/// Parses a CODEOWNERS file as found in Github and Gitlab repositories. Each
+/// entry becomes a rule with a single path and its owners as reviewers.
+/// Comments, Gitlab section headers and entries without owners are skipped.
+pub fn parse_codeowners(content: &str) -> Vec<OwnershipRule> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('[') && !line.starts_with('^'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pattern = fields.next()?;
+            let owners = fields
+                .map(|owner| owner.trim_start_matches('@').to_string())
+                .collect::<Vec<_>>();
+            if owners.is_empty() {
+                return None;
+            }
+            Some(OwnershipRule {
+                paths: vec![pattern.to_string()],
+                reviewers: owners,
+                labels: vec![],
+            })
+        })
+        .collect()
+}
+
+/// Owners of the changed files according to CODEOWNERS rules. As in Github
+/// and Gitlab, the last matching entry for a file takes precedence.
+pub fn code_owners<S: AsRef<str>>(rules: &[OwnershipRule], changed_files: &[S]) -> Vec<String> {
+    let mut owners: Vec<String> = Vec::new();
+    for file in changed_files {
+        let rule = rules.iter().rev().find(|rule| {
+            rule.paths
+                .iter()
+                .any(|pattern| path_matches(pattern, file.as_ref()))
+        });
+        if let Some(rule) = rule {
+            for owner in &rule.reviewers {
+                if !owners.contains(owner) {
+                    owners.push(owner.clone());
+                }
+            }
+        }
+    }
+    owners
+}
+
+/// Matches a path against a gitignore-like pattern. `*` and `?` do not cross
+/// directory boundaries, `**` does. A pattern matching a directory matches
+/// everything under it and patterns without a `/` match at any depth.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let anywhere = !pattern.trim_end_matches('/').contains('/');
+    let pattern = pattern.trim_start_matches('/');
+    let mut regex = String::from("^");
+    if anywhere {
+        regex.push_str("(.*/)?");
+    }
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    if pattern.ends_with('/') {
+        regex.push_str(".*");
+    } else {
+        regex.push_str("(/.*)?");
+    }
+    regex.push('$');
+    Regex::new(&regex)
+        .map(|regex| regex.is_match(path))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rule(paths: &[&str], reviewers: &[&str], labels: &[&str]) -> OwnershipRule {
+        OwnershipRule {
+            paths: paths.iter().map(|s| s.to_string()).collect(),
+            reviewers: reviewers.iter().map(|s| s.to_string()).collect(),
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_path_matches_patterns() {
+        assert!(path_matches("src/cache/", "src/cache/inmemory.rs"));
+        assert!(path_matches("src/cache/", "src/cache/nested/file.rs"));
+        assert!(!path_matches("src/cache/", "src/cache.rs"));
+        assert!(path_matches("src/*.rs", "src/main.rs"));
+        assert!(!path_matches("src/*.rs", "src/cmds/project.rs"));
+        assert!(path_matches("src/**/*.rs", "src/cmds/project.rs"));
+        assert!(path_matches("*.md", "doc/src/intro.md"));
+        assert!(path_matches("/Cargo.toml", "Cargo.toml"));
+        assert!(path_matches("docs/", "docs/index.md"));
+        assert!(path_matches("docs/", "src/docs/index.md"));
+        assert!(!path_matches("/docs/", "src/docs/index.md"));
+        assert!(path_matches("/src/cache", "src/cache/inmemory.rs"));
+    }
+
+    #[test]
+    fn test_compute_owners_from_matching_rules() {
+        let rules = vec![
+            rule(&["src/cache/"], &["jdoe", "jane"], &["cache"]),
+            rule(&["*.md"], &["writer"], &["docs"]),
+            rule(&["src/**/*.rs"], &["jane"], &["rust", "cache"]),
+        ];
+        let owners = compute(&rules, &["src/cache/inmemory.rs", "Cargo.lock"]);
+        assert_eq!(vec!["jdoe", "jane"], owners.reviewers);
+        assert_eq!(vec!["cache", "rust"], owners.labels);
+    }
+
+    #[test]
+    fn test_parse_codeowners() {
+        let content = "# Default owners\n\
+                       *       @jordilin\n\
+                       \n\
+                       [Documentation]\n\
+                       docs/   @jdoe @org/writers # docs team\n\
+                       /build/\n";
+        let rules = parse_codeowners(content);
+        assert_eq!(
+            vec![
+                rule(&["*"], &["jordilin"], &[]),
+                rule(&["docs/"], &["jdoe", "org/writers"], &[]),
+            ],
+            rules
+        );
+    }
+
+    #[test]
+    fn test_code_owners_last_match_wins() {
+        let rules = vec![
+            rule(&["*"], &["jordilin"], &[]),
+            rule(&["docs/"], &["jdoe", "org/writers"], &[]),
+        ];
+        let owners = code_owners(&rules, &["docs/intro.md", "src/main.rs"]);
+        assert_eq!(vec!["jdoe", "org/writers", "jordilin"], owners);
+        let owners = code_owners(&rules, &["docs/intro.md"]);
+        assert_eq!(vec!["jdoe", "org/writers"], owners);
+    }
+
+    #[test]
+    fn test_compute_owners_no_match_is_empty() {
+        let rules = vec![rule(&["src/cache/"], &["jdoe"], &["cache"])];
+        let owners = compute(&rules, &["README.md"]);
+        assert_eq!(Owners::default(), owners);
+    }
+}
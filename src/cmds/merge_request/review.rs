@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::GRError;
+use crate::Result;
+
+/// Review drafted offline. Exported from a merge request with `gr mr review
+/// export`, edited to add inline comments and submitted back as a single
+/// review with `gr mr review submit`:
+///
+/// ```toml
+/// merge_request = 23
+/// title = "Add offline reviews"
+/// head_sha = "9c5bf00e5e1d6e9e96cda3fa3986f680fcdbcd7f"
+/// body = "Looks good overall"
+///
+/// [[comments]]
+/// file = "src/main.rs"
+/// line = 10
+/// body = "Handle the error instead of unwrapping"
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ReviewFile {
+    pub merge_request: i64,
+    #[serde(default)]
+    pub title: String,
+    /// Head commit of the merge request the comment lines refer to.
+    pub head_sha: String,
+    /// Summary of the review, posted along with the inline comments.
+    #[serde(default)]
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<ReviewComment>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReviewComment {
+    /// Path of the file in the source branch
+    pub file: String,
+    /// Line in the new version of the file
+    pub line: u32,
+    pub body: String,
+}
+
+const COMMENTS_TEMPLATE: &str = "\
+# Add inline comments on lines of the new version of the changed files:
+#
+# [[comments]]
+# file = \"src/main.rs\"
+# line = 10
+# body = \"Handle the error instead of unwrapping\"
+";
+
+impl ReviewFile {
+    pub fn new(merge_request: i64, title: &str, head_sha: &str) -> Self {
+        ReviewFile {
+            merge_request,
+            title: title.to_string(),
+            head_sha: head_sha.to_string(),
+            ..ReviewFile::default()
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<Self> {
+        let review: ReviewFile = toml::from_str(data).map_err(|err| invalid_review(&err))?;
+        if let Some(comment) = review
+            .comments
+            .iter()
+            .find(|comment| comment.line == 0 || comment.body.trim().is_empty())
+        {
+            return Err(invalid_review(&format!(
+                "comment at {}:{} needs a line starting at 1 and a body",
+                comment.file, comment.line
+            )));
+        }
+        if review.body.trim().is_empty() && review.comments.is_empty() {
+            return Err(invalid_review(&"nothing to submit, add a body or comments"));
+        }
+        Ok(review)
+    }
+
+    /// Review file contents with a commented out example of inline comment
+    /// to get the reviewer started.
+    pub fn to_toml(&self) -> Result<String> {
+        let data = toml::to_string(self)?;
+        if self.comments.is_empty() {
+            return Ok(format!("{}\n{}", data, COMMENTS_TEMPLATE));
+        }
+        Ok(data)
+    }
+}
+
+fn invalid_review(reason: &dyn std::fmt::Display) -> anyhow::Error {
+    GRError::PreconditionNotMet(format!("Invalid review file: {}", reason)).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_exported_review_file_parses_back_once_commented() {
+        let review = ReviewFile::new(23, "Add offline reviews", "9c5bf00");
+        let data = review.to_toml().unwrap();
+        assert!(data.starts_with("merge_request = 23\n"));
+        // The template is commented out, so there is nothing to submit yet.
+        assert!(ReviewFile::parse(&data).is_err());
+        let data = data.replace("# [[comments]]", "[[comments]]").replace(
+            "# file = \"src/main.rs\"\n# line = 10\n# body",
+            "file = \"src/main.rs\"\nline = 10\nbody",
+        );
+        let review = ReviewFile::parse(&data).unwrap();
+        assert_eq!("9c5bf00", review.head_sha);
+        assert_eq!(
+            vec![ReviewComment {
+                file: "src/main.rs".to_string(),
+                line: 10,
+                body: "Handle the error instead of unwrapping".to_string(),
+            }],
+            review.comments
+        );
+    }
+
+    #[test]
+    fn test_review_comment_without_body_is_error() {
+        let data = "merge_request = 23\nhead_sha = \"9c5bf00\"\n\n\
+                    [[comments]]\nfile = \"src/main.rs\"\nline = 10\nbody = \"\"\n";
+        let err = ReviewFile::parse(data).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert!(msg.contains("comment at src/main.rs:10"))
+            }
+            _ => panic!("Expected GRError::PreconditionNotMet"),
+        }
+    }
+}
@@ -1,17 +1,21 @@
-use std::{io::Write, sync::Arc};
+use std::{collections::BTreeMap, io::Write, sync::Arc};
 
 use crate::{
-    api_traits::RemoteProject,
+    api_traits::{RemoteProject, UserActivity, UserInfo},
     cli::my::MyOptions,
-    config::ConfigProperties,
-    remote::{self, CacheType},
+    config::{self, ConfigProperties},
+    display,
+    error::GRError,
+    remote::{self, CacheType, ConfigFilePath, RemoteURL},
     Result,
 };
 
 use super::{
     common::{self, get_user},
-    gist, merge_request,
+    gist,
+    merge_request::{self, MergeRequestListCliArgs, MergeRequestSource},
     project::{ProjectListBodyArgs, ProjectListCliArgs},
+    user::{ActivityBodyArgs, ContributionsCliArgs, DailyContributions},
 };
 
 pub fn execute(
@@ -24,8 +28,15 @@ pub fn execute(
         MyOptions::MergeRequest(cli_args) => {
             merge_request::list_merge_requests(domain, path, config, cli_args)
         }
+        MyOptions::MergeRequestAllDomains(_) => {
+            unreachable!("Not bound to a domain, see list_merge_requests_all_domains")
+        }
         MyOptions::Project(cli_args) => {
-            let user = get_user(&domain, &path, &config, &cli_args.list_args)?;
+            // Projects of a group are listed regardless of the user.
+            let user = match cli_args.group {
+                Some(_) => None,
+                None => Some(get_user(&domain, &path, &config, &cli_args.list_args)?),
+            };
             let remote = remote::get_project(
                 domain,
                 path,
@@ -36,8 +47,9 @@ pub fn execute(
             let from_to_args = remote::validate_from_to_page(&cli_args.list_args)?;
             let body_args = ProjectListBodyArgs::builder()
                 .from_to_page(from_to_args)
-                .user(Some(user))
+                .user(user)
                 .stars(cli_args.stars)
+                .group(cli_args.group.clone())
                 .build()?;
             if cli_args.list_args.num_pages {
                 return common::num_project_pages(remote, body_args, std::io::stdout());
@@ -67,7 +79,91 @@ pub fn execute(
                 .build()?;
             gist::list_user_gists(remote, body_args, cli_args, std::io::stdout())
         }
+        MyOptions::Contributions(cli_args) => {
+            let user_remote = remote::get_auth_user(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            let remote = remote::get_user_activity(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            contributions(user_remote, remote, cli_args, std::io::stdout())
+        }
+    }
+}
+
+/// Lists your merge requests in every domain configured in the config
+/// directory.
+pub fn list_merge_requests_all_domains(
+    cli_args: MergeRequestListCliArgs,
+    config_file_path: ConfigFilePath,
+) -> Result<()> {
+    let domains = config::configured_domains(config_file_path.directory())?;
+    if domains.is_empty() {
+        return Err(GRError::ConfigurationError(
+            "No domains found in the config directory".to_string(),
+        )
+        .into());
+    }
+    merge_request::list_merge_requests_from(
+        MergeRequestSource::Domain,
+        domains,
+        cli_args,
+        move |domain, cli_args| {
+            let url = RemoteURL::new(domain.to_string(), "".to_string());
+            merge_request::remote_merge_requests(config_file_path.clone(), &url, cli_args)
+        },
+        std::io::stdout(),
+    )
+}
+
+fn contributions<W: Write>(
+    user_remote: Arc<dyn UserInfo>,
+    remote: Arc<dyn UserActivity>,
+    cli_args: ContributionsCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let user = user_remote.get_auth_user()?;
+    let body_args = ActivityBodyArgs::builder()
+        .user(user)
+        .after(cli_args.after.clone())
+        .before(cli_args.before.clone())
+        .build()?;
+    let events = remote.list_events(body_args)?;
+    let mut days: BTreeMap<String, DailyContributions> = BTreeMap::new();
+    let mut total = DailyContributions::new("Total");
+    for event in events {
+        // YYYY-MM-DD days compare lexicographically.
+        let day = event.created_at.format("%Y-%m-%d").to_string();
+        if let Some(after) = &cli_args.after {
+            if day.as_str() <= after.as_str() {
+                continue;
+            }
+        }
+        if let Some(before) = &cli_args.before {
+            if day.as_str() >= before.as_str() {
+                continue;
+            }
+        }
+        days.entry(day.clone())
+            .or_insert_with(|| DailyContributions::new(&day))
+            .add(event.kind);
+        total.add(event.kind);
+    }
+    if days.is_empty() {
+        writeln!(writer, "No contributions found.")?;
+        return Ok(());
     }
+    let mut rows: Vec<DailyContributions> = days.into_values().collect();
+    rows.push(total);
+    display::print(&mut writer, rows, cli_args.get_args)
 }
 
 fn list_user_projects<W: Write>(
@@ -82,9 +178,12 @@ fn list_user_projects<W: Write>(
 #[cfg(test)]
 mod tests {
 
-    use crate::cmds::project::{Member, Project, ProjectListCliArgs};
+    use crate::cmds::{
+        project::{Member, Project, ProjectListCliArgs},
+        user::{ActivityEvent, ActivityKind},
+    };
 
-    use self::remote::ListRemoteCliArgs;
+    use self::remote::{GetRemoteCliArgs, ListRemoteCliArgs};
 
     use super::*;
 
@@ -160,7 +259,93 @@ mod tests {
         )
         .is_ok());
         assert_eq!(
-            "ID|Default Branch|URL|Created at\n1|main||\n2|dev||\n",
+            "ID|Default Branch|URL|Created at\n1|main||1970-01-01T00:00:00Z\n2|dev||1970-01-01T00:00:00Z\n",
+            String::from_utf8(buffer).unwrap()
+        );
+    }
+
+    struct MockActivity {
+        events: Vec<ActivityEvent>,
+    }
+
+    impl UserInfo for MockActivity {
+        fn get_auth_user(&self) -> Result<Member> {
+            Ok(Member::builder()
+                .id(1)
+                .username("jordilin".to_string())
+                .build()
+                .unwrap())
+        }
+
+        fn get(&self, _args: &crate::cmds::user::UserCliArgs) -> Result<Member> {
+            todo!()
+        }
+
+        fn get_profile(
+            &self,
+            _args: &crate::cmds::user::UserCliArgs,
+        ) -> Result<crate::cmds::user::UserProfile> {
+            todo!()
+        }
+    }
+
+    impl UserActivity for MockActivity {
+        fn list_events(&self, _args: ActivityBodyArgs) -> Result<Vec<ActivityEvent>> {
+            Ok(self.events.clone())
+        }
+    }
+
+    fn event(kind: ActivityKind, created_at: &str) -> ActivityEvent {
+        ActivityEvent::builder()
+            .kind(kind)
+            .created_at(created_at.parse().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_contributions_aggregated_per_day() {
+        let remote = Arc::new(MockActivity {
+            events: vec![
+                event(ActivityKind::Push, "2024-02-14T17:00:00Z"),
+                event(ActivityKind::IssueOpened, "2024-02-14T16:45:00Z"),
+                event(ActivityKind::Review, "2024-02-13T08:10:00Z"),
+                event(ActivityKind::MergeRequestMerged, "2024-02-12T12:30:00Z"),
+                event(ActivityKind::MergeRequestOpened, "2024-02-12T10:00:00Z"),
+                event(ActivityKind::Push, "2024-02-01T10:00:00Z"),
+            ],
+        });
+        let cli_args = ContributionsCliArgs::builder()
+            .after(Some("2024-02-01".to_string()))
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buffer = Vec::new();
+        contributions(remote.clone(), remote, cli_args, &mut buffer).unwrap();
+        assert_eq!(
+            "Date|MRs opened|MRs merged|Reviews|Issues opened|Pushes\n\
+             2024-02-12|1|1|0|0|0\n\
+             2024-02-13|0|0|1|0|0\n\
+             2024-02-14|0|0|0|1|1\n\
+             Total|1|1|1|1|1\n",
+            String::from_utf8(buffer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_contributions_none_found() {
+        let remote = Arc::new(MockActivity {
+            events: vec![event(ActivityKind::Push, "2024-03-01T10:00:00Z")],
+        });
+        let cli_args = ContributionsCliArgs::builder()
+            .before(Some("2024-03-01".to_string()))
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buffer = Vec::new();
+        contributions(remote.clone(), remote, cli_args, &mut buffer).unwrap();
+        assert_eq!(
+            "No contributions found.\n",
             String::from_utf8(buffer).unwrap()
         );
     }
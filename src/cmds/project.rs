@@ -1,11 +1,18 @@
-use crate::api_traits::{ProjectMember, RemoteProject, RemoteTag, Timestamp};
+use crate::api_traits::{
+    ProjectAccessCheck, ProjectBlame, ProjectDependency, ProjectInvitation, ProjectLanguages,
+    ProjectMember, ProjectMemberAccess, ProjectMirror, RemoteProject, RemoteTag, Timestamp,
+};
 use crate::cli::project::ProjectOptions;
 use crate::config::ConfigProperties;
+use crate::dialog;
 use crate::display::{self, Column, DisplayBody};
 use crate::error;
 use crate::io::CmdInfo;
 use crate::remote::{self, CacheType, GetRemoteCliArgs, ListBodyArgs, ListRemoteCliArgs};
+use crate::time::{self, Seconds};
 use crate::Result;
+use chrono::{DateTime, Utc};
+use std::fmt::Display;
 use std::io::Write;
 use std::sync::Arc;
 
@@ -18,11 +25,42 @@ pub struct Project {
     #[builder(default)]
     members: Vec<Member>,
     html_url: String,
-    created_at: String,
+    created_at: DateTime<Utc>,
     description: String,
     // Field not available in Gitlab. Set to empty string.
     #[builder(default)]
     language: String,
+    #[builder(default)]
+    license: String,
+    #[builder(default)]
+    topics: Vec<String>,
+    #[builder(default)]
+    visibility: String,
+    #[builder(default)]
+    stars: u64,
+    #[builder(default)]
+    forks: u64,
+    #[builder(default)]
+    avatar_url: String,
+    #[builder(default)]
+    languages: Vec<ProjectLanguage>,
+}
+
+/// Share of a programming language in the repository, as a percentage of
+/// its code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectLanguage {
+    pub name: String,
+    pub percentage: f64,
+}
+
+impl ProjectLanguage {
+    pub fn new(name: &str, percentage: f64) -> Self {
+        ProjectLanguage {
+            name: name.to_string(),
+            percentage,
+        }
+    }
 }
 
 impl Project {
@@ -36,9 +74,9 @@ impl Project {
             default_branch: default_branch.to_string(),
             members: Vec::new(),
             html_url: String::new(),
-            created_at: String::new(),
+            created_at: DateTime::default(),
             description: String::new(),
-            language: String::new(),
+            ..Project::default()
         }
     }
 
@@ -48,8 +86,14 @@ impl Project {
     }
 
     // TODO - builder pattern
-    pub fn with_created_at(mut self, created_at: &str) -> Self {
-        self.created_at = created_at.to_string();
+    pub fn with_created_at(mut self, created_at: DateTime<Utc>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    pub fn with_languages(mut self, mut languages: Vec<ProjectLanguage>) -> Self {
+        languages.sort_by(|a, b| b.percentage.total_cmp(&a.percentage));
+        self.languages = languages;
         self
     }
 
@@ -58,6 +102,23 @@ impl Project {
     }
 }
 
+fn format_languages(languages: &[ProjectLanguage]) -> String {
+    languages
+        .iter()
+        .map(|language| format!("{} {:.1}%", language.name, language.percentage))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn optional_column(name: &str, value: String) -> Column {
+    Column::builder()
+        .name(name.to_string())
+        .value(value)
+        .optional(true)
+        .build()
+        .unwrap()
+}
+
 impl From<Project> for DisplayBody {
     fn from(p: Project) -> DisplayBody {
         DisplayBody {
@@ -65,7 +126,7 @@ impl From<Project> for DisplayBody {
                 Column::new("ID", p.id.to_string()),
                 Column::new("Default Branch", p.default_branch),
                 Column::new("URL", p.html_url),
-                Column::new("Created at", p.created_at),
+                Column::new("Created at", time::format_timestamp(&p.created_at)),
                 Column::builder()
                     .name("Description".to_string())
                     .value(p.description)
@@ -78,14 +139,21 @@ impl From<Project> for DisplayBody {
                     .optional(true)
                     .build()
                     .unwrap(),
+                optional_column("Languages", format_languages(&p.languages)),
+                optional_column("Visibility", p.visibility),
+                optional_column("License", p.license),
+                optional_column("Topics", p.topics.join(", ")),
+                optional_column("Stars", p.stars.to_string()),
+                optional_column("Forks", p.forks.to_string()),
+                optional_column("Avatar", p.avatar_url),
             ],
         }
     }
 }
 
 impl Timestamp for Project {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
     }
 }
 
@@ -106,8 +174,8 @@ pub struct Member {
     pub name: String,
     #[builder(default)]
     pub username: String,
-    #[builder(default = "String::from(\"1970-01-01T00:00:00Z\")")]
-    pub created_at: String,
+    #[builder(default = "DateTime::UNIX_EPOCH")]
+    pub created_at: DateTime<Utc>,
     #[builder(default)]
     pub mr_member_type: MrMemberType,
 }
@@ -119,8 +187,8 @@ impl Member {
 }
 
 impl Timestamp for Member {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
     }
 }
 
@@ -150,6 +218,8 @@ pub struct ProjectListCliArgs {
     pub tags: bool,
     #[builder(default)]
     pub members: bool,
+    #[builder(default)]
+    pub group: Option<String>,
 }
 
 impl ProjectListCliArgs {
@@ -168,6 +238,10 @@ pub struct ProjectListBodyArgs {
     pub tags: bool,
     #[builder(default)]
     pub members: bool,
+    /// Gitlab group, including its subgroups, or Github organization to list
+    /// the members or projects of instead of the project or user.
+    #[builder(default)]
+    pub group: Option<String>,
 }
 
 impl ProjectListBodyArgs {
@@ -194,7 +268,7 @@ impl ProjectMetadataGetCliArgs {
 pub struct Tag {
     pub name: String,
     pub sha: String,
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
 }
 
 impl Tag {
@@ -204,8 +278,8 @@ impl Tag {
 }
 
 impl Timestamp for Tag {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
     }
 }
 
@@ -217,7 +291,7 @@ impl From<Tag> for DisplayBody {
                 Column::new("SHA", t.sha),
                 Column::builder()
                     .name("Created at".to_string())
-                    .value(t.created_at)
+                    .value(time::format_timestamp(&t.created_at))
                     .optional(true)
                     .build()
                     .unwrap(),
@@ -226,6 +300,431 @@ impl From<Tag> for DisplayBody {
     }
 }
 
+/// Kind of project mirror. Pull mirrors get updated from an upstream
+/// repository, push mirrors send the project to a downstream repository.
+/// Workflow mirrors are Github Actions workflows that mirror the repository.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MirrorKind {
+    Pull,
+    Push,
+    Workflow,
+}
+
+impl Display for MirrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MirrorKind::Pull => write!(f, "pull"),
+            MirrorKind::Push => write!(f, "push"),
+            MirrorKind::Workflow => write!(f, "workflow"),
+        }
+    }
+}
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct Mirror {
+    pub id: i64,
+    pub kind: MirrorKind,
+    pub url: String,
+    #[builder(default)]
+    pub enabled: bool,
+    #[builder(default)]
+    pub status: String,
+    #[builder(default)]
+    pub last_update_at: Option<DateTime<Utc>>,
+    #[builder(default)]
+    pub last_successful_update_at: Option<DateTime<Utc>>,
+    #[builder(default)]
+    pub last_error: String,
+}
+
+impl Mirror {
+    pub fn builder() -> MirrorBuilder {
+        MirrorBuilder::default()
+    }
+}
+
+impl From<Mirror> for DisplayBody {
+    fn from(m: Mirror) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("ID", m.id.to_string()),
+                Column::new("Kind", m.kind.to_string()),
+                Column::new("URL", m.url),
+                Column::new("Enabled", m.enabled.to_string()),
+                Column::new("Status", m.status),
+                Column::new(
+                    "Last update",
+                    time::format_optional_timestamp(m.last_update_at.as_ref()),
+                ),
+                Column::new(
+                    "Last successful update",
+                    time::format_optional_timestamp(m.last_successful_update_at.as_ref()),
+                ),
+                Column::builder()
+                    .name("Last error".to_string())
+                    .value(m.last_error)
+                    .optional(true)
+                    .build()
+                    .unwrap(),
+            ],
+        }
+    }
+}
+
+#[derive(Builder)]
+pub struct ProjectMirrorCliArgs {
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl ProjectMirrorCliArgs {
+    pub fn builder() -> ProjectMirrorCliArgsBuilder {
+        ProjectMirrorCliArgsBuilder::default()
+    }
+}
+
+/// Access level of a project member, from the least to the most privileged.
+/// Github repository permissions map to them in the same order: pull,
+/// triage, push, maintain and admin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MemberAccessLevel {
+    Guest,
+    Reporter,
+    Developer,
+    Maintainer,
+    Owner,
+}
+
+impl Display for MemberAccessLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MemberAccessLevel::Guest => write!(f, "guest"),
+            MemberAccessLevel::Reporter => write!(f, "reporter"),
+            MemberAccessLevel::Developer => write!(f, "developer"),
+            MemberAccessLevel::Maintainer => write!(f, "maintainer"),
+            MemberAccessLevel::Owner => write!(f, "owner"),
+        }
+    }
+}
+
+/// Membership of a user in a project after adding or updating it. The state
+/// tells whether the user is a member already or has yet to accept an
+/// invitation.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct Membership {
+    pub username: String,
+    pub access: MemberAccessLevel,
+    pub state: String,
+}
+
+impl Membership {
+    pub fn builder() -> MembershipBuilder {
+        MembershipBuilder::default()
+    }
+}
+
+impl From<Membership> for DisplayBody {
+    fn from(m: Membership) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Username", m.username),
+                Column::new("Access", m.access.to_string()),
+                Column::new("State", m.state),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MemberAction {
+    Add(MemberAccessLevel),
+    Update(MemberAccessLevel),
+    Remove,
+    Block,
+    /// Invite by email someone who might not have an account yet.
+    Invite(MemberAccessLevel),
+}
+
+#[derive(Builder)]
+pub struct ProjectMemberCliArgs {
+    /// Username of the member, or email of the invitee when inviting.
+    pub username: String,
+    pub action: MemberAction,
+    /// Block without prompting for confirmation.
+    #[builder(default)]
+    pub yes: bool,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl ProjectMemberCliArgs {
+    pub fn builder() -> ProjectMemberCliArgsBuilder {
+        ProjectMemberCliArgsBuilder::default()
+    }
+}
+
+/// Access to a project granted to a user and where it comes from, ex. a
+/// direct membership or a group the user belongs to.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct AccessGrant {
+    pub source: String,
+    pub access: MemberAccessLevel,
+}
+
+impl AccessGrant {
+    pub fn builder() -> AccessGrantBuilder {
+        AccessGrantBuilder::default()
+    }
+}
+
+impl From<AccessGrant> for DisplayBody {
+    fn from(g: AccessGrant) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Source", g.source),
+                Column::new("Access", g.access.to_string()),
+            ],
+        }
+    }
+}
+
+/// All the grants a user has on a project.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct ProjectAccess {
+    pub username: String,
+    #[builder(default)]
+    pub grants: Vec<AccessGrant>,
+}
+
+impl ProjectAccess {
+    pub fn builder() -> ProjectAccessBuilder {
+        ProjectAccessBuilder::default()
+    }
+
+    /// The most privileged of the grants, if any.
+    pub fn effective(&self) -> Option<MemberAccessLevel> {
+        self.grants.iter().map(|grant| grant.access).max()
+    }
+}
+
+#[derive(Builder)]
+pub struct ProjectAccessCheckCliArgs {
+    pub username: String,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl ProjectAccessCheckCliArgs {
+    pub fn builder() -> ProjectAccessCheckCliArgsBuilder {
+        ProjectAccessCheckCliArgsBuilder::default()
+    }
+}
+
+/// Pending invitation to join a project. Gitlab invitations are addressed to
+/// an email, Github invitations to a user.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct Invitation {
+    pub invitee: String,
+    pub access: MemberAccessLevel,
+    #[builder(default)]
+    pub invited_by: String,
+    #[builder(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Gitlab invitations expire at a given date.
+    #[builder(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Github invitations do not expire at a given date, they are either
+    /// expired or not.
+    #[builder(default)]
+    pub expired: bool,
+}
+
+impl Invitation {
+    pub fn builder() -> InvitationBuilder {
+        InvitationBuilder::default()
+    }
+}
+
+impl From<Invitation> for DisplayBody {
+    fn from(i: Invitation) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Invitee", i.invitee),
+                Column::new("Access", i.access.to_string()),
+                Column::new("Invited by", i.invited_by),
+                Column::new(
+                    "Created at",
+                    time::format_optional_timestamp(i.created_at.as_ref()),
+                ),
+                Column::builder()
+                    .name("Expires at".to_string())
+                    .value(if i.expired {
+                        "expired".to_string()
+                    } else {
+                        time::format_optional_timestamp(i.expires_at.as_ref())
+                    })
+                    .optional(true)
+                    .build()
+                    .unwrap(),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InvitationAction {
+    List,
+    /// Revoke the invitation of the given email or username.
+    Revoke(String),
+}
+
+#[derive(Builder)]
+pub struct ProjectInvitationCliArgs {
+    pub action: InvitationAction,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl ProjectInvitationCliArgs {
+    pub fn builder() -> ProjectInvitationCliArgsBuilder {
+        ProjectInvitationCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct ProjectBlameCliArgs {
+    pub path: String,
+    pub git_ref: String,
+    /// Inclusive range of lines to blame
+    #[builder(default)]
+    pub range: Option<(usize, usize)>,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl ProjectBlameCliArgs {
+    pub fn builder() -> ProjectBlameCliArgsBuilder {
+        ProjectBlameCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct ProjectBlameBodyArgs {
+    pub path: String,
+    pub git_ref: String,
+    #[builder(default)]
+    pub range: Option<(usize, usize)>,
+}
+
+impl ProjectBlameBodyArgs {
+    pub fn builder() -> ProjectBlameBodyArgsBuilder {
+        ProjectBlameBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct BlameLine {
+    pub line: usize,
+    pub commit: String,
+    pub author: String,
+    /// Authored date of the commit
+    pub date: String,
+    /// Age of the commit relative to now. Filled in before displaying.
+    #[builder(default)]
+    pub age: String,
+    #[builder(default)]
+    pub content: String,
+}
+
+impl BlameLine {
+    pub fn builder() -> BlameLineBuilder {
+        BlameLineBuilder::default()
+    }
+}
+
+impl From<BlameLine> for DisplayBody {
+    fn from(b: BlameLine) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Line", b.line.to_string()),
+                Column::new("Commit", b.commit),
+                Column::new("Author", b.author),
+                Column::new("Age", b.age),
+                Column::builder()
+                    .name("Date".to_string())
+                    .value(b.date)
+                    .optional(true)
+                    .build()
+                    .unwrap(),
+                Column::new("Content", b.content),
+            ],
+        }
+    }
+}
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct Dependency {
+    pub name: String,
+    pub version: String,
+    pub package_manager: String,
+    /// Package URL. See https://github.com/package-url/purl-spec
+    #[builder(default)]
+    pub purl: String,
+    #[builder(default)]
+    pub licenses: Vec<String>,
+    /// File in the repository where the dependency is declared. Only
+    /// available in Gitlab.
+    #[builder(default)]
+    pub source: String,
+}
+
+impl Dependency {
+    pub fn builder() -> DependencyBuilder {
+        DependencyBuilder::default()
+    }
+}
+
+impl Timestamp for Dependency {
+    // Dependencies have no creation date. Set it to UNIX epoch.
+    fn created_at(&self) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH
+    }
+}
+
+impl From<Dependency> for DisplayBody {
+    fn from(d: Dependency) -> DisplayBody {
+        DisplayBody {
+            columns: vec![
+                Column::new("Name", d.name),
+                Column::new("Version", d.version),
+                Column::new("Package manager", d.package_manager),
+                Column::new("Licenses", d.licenses.join(", ")),
+                Column::builder()
+                    .name("PURL".to_string())
+                    .value(d.purl)
+                    .optional(true)
+                    .build()
+                    .unwrap(),
+                Column::builder()
+                    .name("Source".to_string())
+                    .value(d.source)
+                    .optional(true)
+                    .build()
+                    .unwrap(),
+            ],
+        }
+    }
+}
+
+#[derive(Builder)]
+pub struct ProjectDependencyCliArgs {
+    pub get_args: GetRemoteCliArgs,
+    /// Output a CycloneDX SBOM document instead of a listing
+    #[builder(default)]
+    pub cyclonedx: bool,
+}
+
+impl ProjectDependencyCliArgs {
+    pub fn builder() -> ProjectDependencyCliArgsBuilder {
+        ProjectDependencyCliArgsBuilder::default()
+    }
+}
+
 pub fn execute(
     options: ProjectOptions,
     config: Arc<dyn ConfigProperties>,
@@ -235,13 +734,20 @@ pub fn execute(
     match options {
         ProjectOptions::Info(cli_args) => {
             let remote = remote::get_project(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            let remote_languages = remote::get_project_languages(
                 domain,
                 path,
                 config,
                 Some(&cli_args.get_args.cache_args),
                 CacheType::File,
             )?;
-            project_info(remote, std::io::stdout(), cli_args)
+            project_info(remote, remote_languages, std::io::stdout(), cli_args)
         }
         ProjectOptions::Members(cli_args) => {
             let remote = remote::get_project_member(
@@ -256,6 +762,7 @@ pub fn execute(
                 .members(true)
                 .from_to_page(from_to_args)
                 .user(None)
+                .group(cli_args.group.clone())
                 .build()?;
             if cli_args.list_args.num_pages {
                 return common::num_project_member_pages(remote, body_args, std::io::stdout());
@@ -287,11 +794,58 @@ pub fn execute(
             }
             list_project_tags(remote, body_args, cli_args, std::io::stdout())
         }
+        ProjectOptions::Blame(cli_args) => {
+            let remote = remote::get_project_blame(
+                domain,
+                path,
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            blame(remote, cli_args, time::now_epoch_seconds, std::io::stdout())
+        }
+        ProjectOptions::Dependencies(cli_args) => {
+            let remote = remote::get_project_dependency(
+                domain,
+                path.clone(),
+                config,
+                Some(&cli_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            list_dependencies(remote, cli_args, &path, std::io::stdout())
+        }
+        ProjectOptions::Member(cli_args) => {
+            let remote =
+                remote::get_project_member_access(domain, path, config, None, CacheType::None)?;
+            member_access(remote, cli_args, std::io::stdout())
+        }
+        ProjectOptions::AccessCheck(cli_args) => {
+            let remote =
+                remote::get_project_access_check(domain, path, config, None, CacheType::None)?;
+            access_check(remote, cli_args, std::io::stdout())
+        }
+        ProjectOptions::Invitations(cli_args) => {
+            // Invitations are always retrieved live, as they get accepted
+            // or revoked at any time.
+            let remote =
+                remote::get_project_invitation(domain, path, config, None, CacheType::None)?;
+            invitations(remote, cli_args, std::io::stdout())
+        }
+        ProjectOptions::MirrorStatus(cli_args) => {
+            // Mirror status is always retrieved live, so lag can be inspected
+            let remote = remote::get_project_mirror(domain, path, config, None, CacheType::None)?;
+            mirror_status(remote, cli_args, std::io::stdout())
+        }
+        ProjectOptions::MirrorSync(cli_args) => {
+            let remote = remote::get_project_mirror(domain, path, config, None, CacheType::None)?;
+            mirror_sync(remote, cli_args, std::io::stdout())
+        }
     }
 }
 
 fn project_info<W: Write>(
     remote: Arc<dyn RemoteProject>,
+    remote_languages: Arc<dyn ProjectLanguages>,
     mut writer: W,
     cli_args: ProjectMetadataGetCliArgs,
 ) -> Result<()> {
@@ -303,14 +857,20 @@ fn project_info<W: Write>(
     } else {
         None
     };
-    let CmdInfo::Project(project_data) = remote.get_project_data(cli_args.id, path.as_deref())?
+    let CmdInfo::Project(mut project_data) =
+        remote.get_project_data(cli_args.id, path.as_deref())?
     else {
         return Err(error::GRError::ApplicationError(
             "remote.get_project_data expects CmdInfo::Project invariant".to_string(),
         )
         .into());
     };
-    display::print(&mut writer, vec![project_data], cli_args.get_args)?;
+    // Languages are an optional column. Only query them when displayed.
+    if cli_args.get_args.display_optional {
+        let languages = remote_languages.languages(cli_args.id, path.as_deref())?;
+        project_data = project_data.with_languages(languages);
+    }
+    display::print_one(&mut writer, project_data, cli_args.get_args)?;
     Ok(())
 }
 
@@ -332,6 +892,183 @@ fn list_project_members<W: Write>(
     common::list_project_members(remote, body_args, cli_args, &mut writer)
 }
 
+fn blame<W: Write>(
+    remote: Arc<dyn ProjectBlame>,
+    cli_args: ProjectBlameCliArgs,
+    now: fn() -> Seconds,
+    mut writer: W,
+) -> Result<()> {
+    let body_args = ProjectBlameBodyArgs::builder()
+        .path(cli_args.path)
+        .git_ref(cli_args.git_ref)
+        .range(cli_args.range)
+        .build()?;
+    let now = now();
+    let lines = remote
+        .blame(body_args)?
+        .into_iter()
+        .map(|mut line| {
+            // Blame dates are not parsed on deserialization, a line without
+            // a date has no age.
+            line.age = time::parse_timestamp(&line.date)
+                .map(|date| time::age(&date, now))
+                .unwrap_or_default();
+            line
+        })
+        .collect::<Vec<_>>();
+    display::print(&mut writer, lines, cli_args.get_args)
+}
+
+fn list_dependencies<W: Write>(
+    remote: Arc<dyn ProjectDependency>,
+    cli_args: ProjectDependencyCliArgs,
+    project_path: &str,
+    mut writer: W,
+) -> Result<()> {
+    let dependencies = remote.list_dependencies()?;
+    if cli_args.cyclonedx {
+        let bom = cyclonedx_bom(project_path, dependencies);
+        writeln!(writer, "{}", serde_json::to_string_pretty(&bom)?)?;
+        return Ok(());
+    }
+    display::print(&mut writer, dependencies, cli_args.get_args)
+}
+
+/// Builds a CycloneDX JSON document with the project as the main component
+/// and its dependencies as library components.
+/// See https://cyclonedx.org/docs/1.5/json/
+fn cyclonedx_bom(project_path: &str, dependencies: Vec<Dependency>) -> serde_json::Value {
+    let components = dependencies
+        .into_iter()
+        .map(|dependency| {
+            let mut component = serde_json::json!({
+                "type": "library",
+                "bom-ref": format!("{}@{}", dependency.name, dependency.version),
+                "name": dependency.name,
+                "version": dependency.version,
+            });
+            if !dependency.purl.is_empty() {
+                component["purl"] = serde_json::Value::String(dependency.purl);
+            }
+            if !dependency.licenses.is_empty() {
+                component["licenses"] = dependency
+                    .licenses
+                    .into_iter()
+                    .map(|license| serde_json::json!({ "license": { "name": license } }))
+                    .collect();
+            }
+            component
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "component": {
+                "type": "application",
+                "bom-ref": project_path,
+                "name": project_path,
+            }
+        },
+        "components": components,
+    })
+}
+
+fn member_access<W: Write>(
+    remote: Arc<dyn ProjectMemberAccess>,
+    cli_args: ProjectMemberCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let username = &cli_args.username;
+    let membership = match cli_args.action {
+        MemberAction::Add(access) => remote.add_member(username, access)?,
+        MemberAction::Update(access) => remote.update_member(username, access)?,
+        MemberAction::Remove => {
+            remote.remove_member(username)?;
+            writeln!(writer, "Removed {} from the project", username)?;
+            return Ok(());
+        }
+        MemberAction::Block => {
+            // Gitlab blocks the user in the whole instance.
+            dialog::confirm_action(&format!("Block user {}?", username), cli_args.yes)?;
+            remote.block_member(username)?;
+            writeln!(writer, "Blocked {}", username)?;
+            return Ok(());
+        }
+        MemberAction::Invite(access) => remote.invite_member(username, access)?,
+    };
+    display::print_one(&mut writer, membership, cli_args.get_args)
+}
+
+fn access_check<W: Write>(
+    remote: Arc<dyn ProjectAccessCheck>,
+    cli_args: ProjectAccessCheckCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let access = remote.access_check(&cli_args.username)?;
+    let Some(effective) = access.effective() else {
+        writeln!(writer, "{} has no access to the project", access.username)?;
+        return Ok(());
+    };
+    writeln!(
+        writer,
+        "Effective access of {}: {}\n",
+        access.username, effective
+    )?;
+    display::print(&mut writer, access.grants, cli_args.get_args)
+}
+
+fn invitations<W: Write>(
+    remote: Arc<dyn ProjectInvitation>,
+    cli_args: ProjectInvitationCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    match cli_args.action {
+        InvitationAction::List => {
+            let invitations = remote.list_invitations()?;
+            if invitations.is_empty() {
+                writeln!(writer, "No pending invitations.")?;
+                return Ok(());
+            }
+            display::print(&mut writer, invitations, cli_args.get_args)
+        }
+        InvitationAction::Revoke(invitee) => {
+            remote.revoke_invitation(&invitee)?;
+            writeln!(writer, "Revoked the invitation of {}", invitee)?;
+            Ok(())
+        }
+    }
+}
+
+fn mirror_status<W: Write>(
+    remote: Arc<dyn ProjectMirror>,
+    cli_args: ProjectMirrorCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    let mirrors = remote.list_mirrors()?;
+    if mirrors.is_empty() {
+        writeln!(writer, "No mirrors found.")?;
+        return Ok(());
+    }
+    display::print(&mut writer, mirrors, cli_args.get_args)
+}
+
+fn mirror_sync<W: Write>(
+    remote: Arc<dyn ProjectMirror>,
+    _cli_args: ProjectMirrorCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    for mirror in remote.sync_mirrors()? {
+        writeln!(
+            writer,
+            "Sync triggered for {} mirror ID: [{}], URL: [{}]",
+            mirror.kind, mirror.id, mirror.url
+        )?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
 
@@ -360,10 +1097,10 @@ mod test {
 
     impl RemoteProject for ProjectDataProvider {
         fn get_project_data(&self, id: Option<i64>, path: Option<&str>) -> crate::Result<CmdInfo> {
-            if let Some(_) = id {
+            if id.is_some() {
                 *self.project_data_with_id_called.borrow_mut() = true;
             }
-            if let Some(_) = path {
+            if path.is_some() {
                 *self.project_data_with_path_called.borrow_mut() = true;
             }
             if self.error {
@@ -399,12 +1136,21 @@ mod test {
         }
     }
 
+    impl ProjectLanguages for ProjectDataProvider {
+        fn languages(&self, _id: Option<i64>, _path: Option<&str>) -> Result<Vec<ProjectLanguage>> {
+            Ok(vec![
+                ProjectLanguage::new("Shell", 7.85),
+                ProjectLanguage::new("Rust", 92.15),
+            ])
+        }
+    }
+
     impl RemoteTag for ProjectDataProvider {
         fn list(&self, _args: ProjectListBodyArgs) -> Result<Vec<Tag>> {
             let tag = Tag::builder()
                 .name("v1.0.0".to_string())
                 .sha("123456".to_string())
-                .created_at("2021-01-01".to_string())
+                .created_at("2021-01-01T00:00:00Z".parse().unwrap())
                 .build()
                 .unwrap();
             Ok(vec![tag])
@@ -422,6 +1168,249 @@ mod test {
         }
     }
 
+    #[derive(Default)]
+    struct MemberAccessMock {
+        removed: RefCell<Vec<String>>,
+        blocked: RefCell<Vec<String>>,
+    }
+
+    impl ProjectMemberAccess for MemberAccessMock {
+        fn add_member(&self, username: &str, access: MemberAccessLevel) -> Result<Membership> {
+            Ok(Membership::builder()
+                .username(username.to_string())
+                .access(access)
+                .state("invited".to_string())
+                .build()
+                .unwrap())
+        }
+
+        fn update_member(&self, _username: &str, _access: MemberAccessLevel) -> Result<Membership> {
+            todo!()
+        }
+
+        fn remove_member(&self, username: &str) -> Result<()> {
+            self.removed.borrow_mut().push(username.to_string());
+            Ok(())
+        }
+
+        fn block_member(&self, username: &str) -> Result<()> {
+            self.blocked.borrow_mut().push(username.to_string());
+            Ok(())
+        }
+
+        fn invite_member(&self, email: &str, access: MemberAccessLevel) -> Result<Membership> {
+            self.add_member(email, access)
+        }
+    }
+
+    #[derive(Default)]
+    struct InvitationMock {
+        invitations: Vec<Invitation>,
+        revoked: RefCell<Vec<String>>,
+    }
+
+    impl ProjectInvitation for InvitationMock {
+        fn list_invitations(&self) -> Result<Vec<Invitation>> {
+            Ok(self.invitations.clone())
+        }
+
+        fn revoke_invitation(&self, invitee: &str) -> Result<()> {
+            self.revoked.borrow_mut().push(invitee.to_string());
+            Ok(())
+        }
+    }
+
+    fn invitation_args(action: InvitationAction) -> ProjectInvitationCliArgs {
+        ProjectInvitationCliArgs::builder()
+            .action(action)
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap()
+    }
+
+    fn member_args(action: MemberAction) -> ProjectMemberCliArgs {
+        ProjectMemberCliArgs::builder()
+            .username("octocat".to_string())
+            .action(action)
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_add_member_shows_invitation_state() {
+        let remote = Arc::new(MemberAccessMock::default());
+        let mut writer = Vec::new();
+        member_access(
+            remote,
+            member_args(MemberAction::Add(MemberAccessLevel::Reporter)),
+            &mut writer,
+        )
+        .unwrap();
+        assert_eq!(
+            "Username|Access|State\noctocat|reporter|invited\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invite_member_by_email() {
+        let remote = Arc::new(MemberAccessMock::default());
+        let mut writer = Vec::new();
+        let cli_args = ProjectMemberCliArgs::builder()
+            .username("tom@example.com".to_string())
+            .action(MemberAction::Invite(MemberAccessLevel::Developer))
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap();
+        member_access(remote, cli_args, &mut writer).unwrap();
+        assert_eq!(
+            "Username|Access|State\ntom@example.com|developer|invited\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    struct AccessCheckMock {
+        grants: Vec<AccessGrant>,
+    }
+
+    impl ProjectAccessCheck for AccessCheckMock {
+        fn access_check(&self, username: &str) -> Result<ProjectAccess> {
+            Ok(ProjectAccess::builder()
+                .username(username.to_string())
+                .grants(self.grants.clone())
+                .build()
+                .unwrap())
+        }
+    }
+
+    fn access_check_args() -> ProjectAccessCheckCliArgs {
+        ProjectAccessCheckCliArgs::builder()
+            .username("octocat".to_string())
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_access_check_shows_effective_access_and_sources() {
+        let grant = |source: &str, access| {
+            AccessGrant::builder()
+                .source(source.to_string())
+                .access(access)
+                .build()
+                .unwrap()
+        };
+        let remote = Arc::new(AccessCheckMock {
+            grants: vec![
+                grant("direct member", MemberAccessLevel::Reporter),
+                grant("group team", MemberAccessLevel::Maintainer),
+            ],
+        });
+        let mut writer = Vec::new();
+        access_check(remote, access_check_args(), &mut writer).unwrap();
+        assert_eq!(
+            "Effective access of octocat: maintainer\n\n\
+             Source|Access\n\
+             direct member|reporter\n\
+             group team|maintainer\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_access_check_without_access() {
+        let remote = Arc::new(AccessCheckMock { grants: vec![] });
+        let mut writer = Vec::new();
+        access_check(remote, access_check_args(), &mut writer).unwrap();
+        assert_eq!(
+            "octocat has no access to the project\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_invitations() {
+        let remote = Arc::new(InvitationMock {
+            invitations: vec![Invitation::builder()
+                .invitee("tom@example.com".to_string())
+                .access(MemberAccessLevel::Reporter)
+                .invited_by("jordilin".to_string())
+                .created_at(Some("2024-01-01T00:00:00Z".parse().unwrap()))
+                .build()
+                .unwrap()],
+            ..Default::default()
+        });
+        let mut writer = Vec::new();
+        invitations(remote, invitation_args(InvitationAction::List), &mut writer).unwrap();
+        assert_eq!(
+            "Invitee|Access|Invited by|Created at\n\
+             tom@example.com|reporter|jordilin|2024-01-01T00:00:00Z\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_no_invitations() {
+        let remote = Arc::new(InvitationMock::default());
+        let mut writer = Vec::new();
+        invitations(remote, invitation_args(InvitationAction::List), &mut writer).unwrap();
+        assert_eq!(
+            "No pending invitations.\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_revoke_invitation() {
+        let remote = Arc::new(InvitationMock::default());
+        let mut writer = Vec::new();
+        invitations(
+            remote.clone(),
+            invitation_args(InvitationAction::Revoke("tom@example.com".to_string())),
+            &mut writer,
+        )
+        .unwrap();
+        assert_eq!(vec!["tom@example.com"], *remote.revoked.borrow());
+        assert_eq!(
+            "Revoked the invitation of tom@example.com\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_remove_member() {
+        let remote = Arc::new(MemberAccessMock::default());
+        let mut writer = Vec::new();
+        member_access(
+            remote.clone(),
+            member_args(MemberAction::Remove),
+            &mut writer,
+        )
+        .unwrap();
+        assert_eq!(vec!["octocat"], *remote.removed.borrow());
+        assert_eq!(
+            "Removed octocat from the project\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_block_member_confirmed_with_yes() {
+        let remote = Arc::new(MemberAccessMock::default());
+        let mut writer = Vec::new();
+        let cli_args = ProjectMemberCliArgs::builder()
+            .username("octocat".to_string())
+            .action(MemberAction::Block)
+            .yes(true)
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap();
+        member_access(remote.clone(), cli_args, &mut writer).unwrap();
+        assert_eq!(vec!["octocat"], *remote.blocked.borrow());
+        assert_eq!("Blocked octocat\n", String::from_utf8(writer).unwrap());
+    }
+
     #[test]
     fn test_project_data_gets_persisted() {
         let remote = ProjectDataProviderBuilder::default()
@@ -436,8 +1425,8 @@ mod test {
             .get_args(get_args)
             .build()
             .unwrap();
-        project_info(remote.clone(), &mut writer, cli_args).unwrap();
-        assert!(writer.len() > 0);
+        project_info(remote.clone(), remote.clone(), &mut writer, cli_args).unwrap();
+        assert!(!writer.is_empty());
         assert!(*remote.project_data_with_id_called.borrow());
     }
 
@@ -456,11 +1445,49 @@ mod test {
             .get_args(get_args)
             .build()
             .unwrap();
-        project_info(remote.clone(), &mut writer, cli_args).unwrap();
-        assert!(writer.len() > 0);
+        project_info(remote.clone(), remote.clone(), &mut writer, cli_args).unwrap();
+        assert!(!writer.is_empty());
         assert!(*remote.project_data_with_path_called.borrow());
     }
 
+    #[test]
+    fn test_project_info_displays_metadata_and_languages() {
+        let project = Project::builder()
+            .id(1)
+            .default_branch("main".to_string())
+            .html_url("https://github.com/jordilin/gitar".to_string())
+            .created_at("2021-01-01T00:00:00Z".parse().unwrap())
+            .description("Git all remote".to_string())
+            .license("MIT License".to_string())
+            .topics(vec!["git".to_string(), "cli".to_string()])
+            .visibility("public".to_string())
+            .stars(10)
+            .forks(2)
+            .build()
+            .unwrap();
+        let remote = ProjectDataProviderBuilder::default()
+            .cmd_info(CmdInfo::Project(project))
+            .build()
+            .unwrap();
+        let remote = Arc::new(remote);
+        let mut writer = Vec::new();
+        let get_args = GetRemoteCliArgs::builder()
+            .display_optional(true)
+            .build()
+            .unwrap();
+        let cli_args = ProjectMetadataGetCliArgs::builder()
+            .id(Some(1))
+            .get_args(get_args)
+            .build()
+            .unwrap();
+        project_info(remote.clone(), remote, &mut writer, cli_args).unwrap();
+        assert_eq!(
+            "ID|Default Branch|URL|Created at|Description|Language|Languages|Visibility|License|Topics|Stars|Forks|Avatar\n\
+             1|main|https://github.com/jordilin/gitar|2021-01-01T00:00:00Z|Git all remote||Rust 92.2%, Shell 7.8%|public|MIT License|git, cli|10|2|\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
     #[test]
     fn test_project_data_error() {
         let remote = ProjectDataProviderBuilder::default()
@@ -476,8 +1503,8 @@ mod test {
             .get_args(get_args)
             .build()
             .unwrap();
-        project_info(remote, &mut writer, cli_args).unwrap_err();
-        assert!(writer.len() == 0);
+        project_info(remote.clone(), remote, &mut writer, cli_args).unwrap_err();
+        assert!(writer.is_empty());
     }
 
     #[test]
@@ -494,7 +1521,7 @@ mod test {
             .get_args(get_args)
             .build()
             .unwrap();
-        let result = project_info(remote, &mut writer, cli_args);
+        let result = project_info(remote.clone(), remote, &mut writer, cli_args);
         match result {
             Ok(_) => panic!("Expected error"),
             Err(err) => match err.downcast_ref::<error::GRError>() {
@@ -555,7 +1582,7 @@ mod test {
             .unwrap();
         list_project_tags(remote, body_args, cli_args, &mut writer).unwrap();
         assert_eq!(
-            "Name|SHA|Created at\nv1.0.0|123456|2021-01-01\n",
+            "Name|SHA|Created at\nv1.0.0|123456|2021-01-01T00:00:00Z\n",
             String::from_utf8(writer).unwrap()
         );
     }
@@ -587,4 +1614,196 @@ mod test {
             String::from_utf8(writer).unwrap()
         );
     }
+
+    struct MirrorMock {
+        mirrors: Vec<Mirror>,
+    }
+
+    impl ProjectMirror for MirrorMock {
+        fn list_mirrors(&self) -> Result<Vec<Mirror>> {
+            Ok(self.mirrors.clone())
+        }
+
+        fn sync_mirrors(&self) -> Result<Vec<Mirror>> {
+            Ok(self.mirrors.iter().filter(|m| m.enabled).cloned().collect())
+        }
+    }
+
+    fn mirrors() -> Vec<Mirror> {
+        vec![
+            Mirror::builder()
+                .id(1)
+                .kind(MirrorKind::Pull)
+                .url("https://github.com/jordilin/gitar.git".to_string())
+                .enabled(true)
+                .status("finished".to_string())
+                .last_update_at(Some("2024-02-01T10:00:00Z".parse().unwrap()))
+                .last_successful_update_at(Some("2024-02-01T10:00:00Z".parse().unwrap()))
+                .build()
+                .unwrap(),
+            Mirror::builder()
+                .id(2)
+                .kind(MirrorKind::Push)
+                .url("https://example.com/gitar.git".to_string())
+                .status("failed".to_string())
+                .build()
+                .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_mirror_status() {
+        let remote = Arc::new(MirrorMock { mirrors: mirrors() });
+        let cli_args = ProjectMirrorCliArgs::builder()
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        mirror_status(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "ID|Kind|URL|Enabled|Status|Last update|Last successful update\n\
+             1|pull|https://github.com/jordilin/gitar.git|true|finished|2024-02-01T10:00:00Z|2024-02-01T10:00:00Z\n\
+             2|push|https://example.com/gitar.git|false|failed||\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_mirror_status_no_mirrors() {
+        let remote = Arc::new(MirrorMock { mirrors: vec![] });
+        let cli_args = ProjectMirrorCliArgs::builder()
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        mirror_status(remote, cli_args, &mut buf).unwrap();
+        assert_eq!("No mirrors found.\n", String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn test_mirror_sync_only_enabled_mirrors() {
+        let remote = Arc::new(MirrorMock { mirrors: mirrors() });
+        let cli_args = ProjectMirrorCliArgs::builder()
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        mirror_sync(remote, cli_args, &mut buf).unwrap();
+        assert_eq!(
+            "Sync triggered for pull mirror ID: [1], URL: [https://github.com/jordilin/gitar.git]\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    struct DependencyMock;
+
+    impl ProjectDependency for DependencyMock {
+        fn list_dependencies(&self) -> Result<Vec<Dependency>> {
+            Ok(vec![
+                Dependency::builder()
+                    .name("serde".to_string())
+                    .version("1.0.210".to_string())
+                    .package_manager("cargo".to_string())
+                    .purl("pkg:cargo/serde@1.0.210".to_string())
+                    .licenses(vec!["MIT OR Apache-2.0".to_string()])
+                    .build()
+                    .unwrap(),
+                Dependency::builder()
+                    .name("left-pad".to_string())
+                    .version("1.3.0".to_string())
+                    .package_manager("npm".to_string())
+                    .build()
+                    .unwrap(),
+            ])
+        }
+    }
+
+    #[test]
+    fn test_list_dependencies() {
+        let remote = Arc::new(DependencyMock);
+        let cli_args = ProjectDependencyCliArgs::builder()
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_dependencies(remote, cli_args, "jordilin/gitar", &mut buf).unwrap();
+        assert_eq!(
+            "Name|Version|Package manager|Licenses\n\
+             serde|1.0.210|cargo|MIT OR Apache-2.0\n\
+             left-pad|1.3.0|npm|\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_dependencies_cyclonedx() {
+        let remote = Arc::new(DependencyMock);
+        let cli_args = ProjectDependencyCliArgs::builder()
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .cyclonedx(true)
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        list_dependencies(remote, cli_args, "jordilin/gitar", &mut buf).unwrap();
+        let bom: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!("CycloneDX", bom["bomFormat"]);
+        assert_eq!("jordilin/gitar", bom["metadata"]["component"]["name"]);
+        let components = bom["components"].as_array().unwrap();
+        assert_eq!(2, components.len());
+        assert_eq!("pkg:cargo/serde@1.0.210", components[0]["purl"]);
+        assert_eq!(
+            "MIT OR Apache-2.0",
+            components[0]["licenses"][0]["license"]["name"]
+        );
+        assert!(components[1].get("purl").is_none());
+        assert!(components[1].get("licenses").is_none());
+    }
+
+    struct BlameMock;
+
+    impl ProjectBlame for BlameMock {
+        fn blame(&self, args: ProjectBlameBodyArgs) -> Result<Vec<BlameLine>> {
+            assert_eq!("src/main.rs", args.path);
+            assert_eq!(Some((1, 2)), args.range);
+            Ok(vec![
+                BlameLine::builder()
+                    .line(1)
+                    .commit("6104942438c1".to_string())
+                    .author("Jordi Carrillo".to_string())
+                    .date("2024-02-02T10:00:00Z".to_string())
+                    .content("fn main() {".to_string())
+                    .build()
+                    .unwrap(),
+                BlameLine::builder()
+                    .line(2)
+                    .commit("ae1d9fb46aa2".to_string())
+                    .author("Jordi Carrillo".to_string())
+                    .date("2023-11-12T10:00:00Z".to_string())
+                    .content("}".to_string())
+                    .build()
+                    .unwrap(),
+            ])
+        }
+    }
+
+    #[test]
+    fn test_blame_shows_age_of_each_line() {
+        let remote = Arc::new(BlameMock);
+        let cli_args = ProjectBlameCliArgs::builder()
+            .path("src/main.rs".to_string())
+            .git_ref("main".to_string())
+            .range(Some((1, 2)))
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let mut buf = Vec::new();
+        // 2024-02-12T10:00:00Z
+        blame(remote, cli_args, || Seconds::new(1707732000), &mut buf).unwrap();
+        assert_eq!(
+            "Line|Commit|Author|Age|Content\n\
+             1|6104942438c1|Jordi Carrillo|10d|fn main() {\n\
+             2|ae1d9fb46aa2|Jordi Carrillo|3mo|}\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
 }
@@ -7,7 +7,9 @@ use crate::cmds::common::num_release_pages;
 use crate::config::ConfigProperties;
 use crate::display::{Column, DisplayBody};
 use crate::remote::{self, CacheType, ListBodyArgs, ListRemoteCliArgs};
+use crate::time;
 use crate::Result;
+use chrono::{DateTime, Utc};
 
 use super::common::{
     self, num_release_asset_pages, num_release_asset_resources, num_release_resources,
@@ -33,8 +35,8 @@ pub struct Release {
     description: String,
     #[builder(default)]
     prerelease: bool,
-    created_at: String,
-    updated_at: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
 }
 
 impl Release {
@@ -52,15 +54,15 @@ impl From<Release> for DisplayBody {
             Column::new("Description", release.description),
             Column::new("URL", release.url),
             Column::new("Prerelease", release.prerelease.to_string()),
-            Column::new("Created At", release.created_at),
-            Column::new("Updated At", release.updated_at),
+            Column::new("Created At", time::format_timestamp(&release.created_at)),
+            Column::new("Updated At", time::format_timestamp(&release.updated_at)),
         ])
     }
 }
 
 impl Timestamp for Release {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
     }
 }
 
@@ -95,8 +97,8 @@ pub struct ReleaseAssetMetadata {
     name: String,
     url: String,
     size: String,
-    created_at: String,
-    updated_at: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
 }
 
 impl ReleaseAssetMetadata {
@@ -112,15 +114,15 @@ impl From<ReleaseAssetMetadata> for DisplayBody {
             Column::new("Name", asset.name),
             Column::new("URL", asset.url),
             Column::new("Size", asset.size),
-            Column::new("Created At", asset.created_at),
-            Column::new("Updated At", asset.updated_at),
+            Column::new("Created At", time::format_timestamp(&asset.created_at)),
+            Column::new("Updated At", time::format_timestamp(&asset.updated_at)),
         ])
     }
 }
 
 impl Timestamp for ReleaseAssetMetadata {
-    fn created_at(&self) -> String {
-        self.created_at.clone()
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
     }
 }
 
@@ -225,8 +227,8 @@ mod test {
                 .tag(String::from("v1.0.0"))
                 .title(String::from("First release"))
                 .description(String::from("Initial release"))
-                .created_at(String::from("2021-01-01T00:00:00Z"))
-                .updated_at(String::from("2021-01-01T00:00:01Z"))
+                .created_at("2021-01-01T00:00:00Z".parse().unwrap())
+                .updated_at("2021-01-01T00:00:01Z".parse().unwrap())
                 .build()
                 .unwrap()])
         }
@@ -289,8 +291,8 @@ mod test {
                 name("gr-x86_64-unknown-linux-musl.tar.gz".to_string()).
                 url("https://github.com/jordilin/gitar/releases/download/v0.1.28/gr-x86_64-unknown-linux-musl.tar.gz".to_string()).
                 size("2871690".to_string())
-                .created_at("2024-03-08T08:29:47Z".to_string())
-                .updated_at("2024-03-08T08:29:47Z".to_string()).build().unwrap();
+                .created_at("2024-03-08T08:29:47Z".parse().unwrap())
+                .updated_at("2024-03-08T08:29:47Z".parse().unwrap()).build().unwrap();
             Ok(vec![asset])
         }
 
@@ -0,0 +1,150 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::api_traits::RemoteInstance;
+use crate::cli::remote::RemoteOptions;
+use crate::config::ConfigProperties;
+use crate::display::{self, Column, DisplayBody};
+use crate::remote::{self, CacheType, GetRemoteCliArgs};
+use crate::Result;
+
+/// Version of the remote instance along with the features gitar relies on
+/// that the instance does not provide.
+#[derive(Builder, Clone, Debug, Default, PartialEq)]
+pub struct RemoteInfo {
+    pub domain: String,
+    /// Version of the instance. Empty for hosted instances that do not
+    /// disclose it, ex. github.com.
+    #[builder(default)]
+    pub version: String,
+    #[builder(default)]
+    pub api_version: String,
+    /// Feature name and the reason it is not available.
+    #[builder(default)]
+    pub unavailable_features: Vec<(String, String)>,
+}
+
+impl RemoteInfo {
+    pub fn builder() -> RemoteInfoBuilder {
+        RemoteInfoBuilder::default()
+    }
+}
+
+impl From<RemoteInfo> for DisplayBody {
+    fn from(info: RemoteInfo) -> Self {
+        DisplayBody::new(vec![
+            Column::new("Domain", info.domain),
+            Column::new("Version", info.version),
+            Column::new("API version", info.api_version),
+            Column::new(
+                "Unavailable features",
+                info.unavailable_features
+                    .into_iter()
+                    .map(|(feature, _)| feature)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        ])
+    }
+}
+
+/// Whether a version such as `16.9.1-ee` is at least `major.minor`. Versions
+/// that cannot be parsed are assumed recent enough, so no feature is reported
+/// missing by mistake.
+pub fn version_at_least(version: &str, major: u32, minor: u32) -> bool {
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|part| part.parse::<u32>());
+    match (parts.next(), parts.next()) {
+        (Some(Ok(actual_major)), Some(Ok(actual_minor))) => {
+            (actual_major, actual_minor) >= (major, minor)
+        }
+        _ => true,
+    }
+}
+
+pub fn execute(
+    options: RemoteOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
+    match options {
+        RemoteOptions::Info(cli_args) => {
+            let remote = remote::get_remote_instance(
+                domain,
+                path,
+                config,
+                Some(&cli_args.cache_args),
+                CacheType::File,
+            )?;
+            info(remote, cli_args, std::io::stdout(), std::io::stderr())
+        }
+    }
+}
+
+fn info<W: Write, E: Write>(
+    remote: Arc<dyn RemoteInstance>,
+    cli_args: GetRemoteCliArgs,
+    mut writer: W,
+    mut warnings: E,
+) -> Result<()> {
+    let info = remote.info()?;
+    for (feature, reason) in &info.unavailable_features {
+        writeln!(warnings, "Warning: {} not available. {}", feature, reason)?;
+    }
+    display::print_one(&mut writer, info, cli_args)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct MockRemoteInstance;
+
+    impl RemoteInstance for MockRemoteInstance {
+        fn info(&self) -> Result<RemoteInfo> {
+            Ok(RemoteInfo::builder()
+                .domain("gitlab.company.com".to_string())
+                .version("15.4.2".to_string())
+                .api_version("v4".to_string())
+                .unavailable_features(vec![(
+                    "merge trains".to_string(),
+                    "Requires Gitlab Premium".to_string(),
+                )])
+                .build()
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_remote_info_warns_about_unavailable_features() {
+        let mut writer = Vec::new();
+        let mut warnings = Vec::new();
+        info(
+            Arc::new(MockRemoteInstance),
+            GetRemoteCliArgs::builder().build().unwrap(),
+            &mut writer,
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(
+            "Domain|Version|API version|Unavailable features\n\
+             gitlab.company.com|15.4.2|v4|merge trains\n",
+            String::from_utf8(writer).unwrap()
+        );
+        assert_eq!(
+            "Warning: merge trains not available. Requires Gitlab Premium\n",
+            String::from_utf8(warnings).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_version_at_least() {
+        assert!(version_at_least("16.9.1-ee", 15, 11));
+        assert!(version_at_least("15.11.0", 15, 11));
+        assert!(!version_at_least("15.9.3-ee", 15, 11));
+        assert!(!version_at_least("3.11.2", 3, 12));
+        assert!(version_at_least("", 3, 12));
+    }
+}
@@ -0,0 +1,561 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use crate::api_traits::{Cicd, MergeRequest, Timestamp};
+use crate::cli::report::ReportOptions;
+use crate::config::ConfigProperties;
+use crate::error::GRError;
+use crate::remote::{self, CacheType, ListBodyArgs};
+use crate::time::{self, Seconds};
+use crate::Result;
+
+use super::cicd::PipelineBodyArgs;
+use super::merge_request::{MergeRequestListBodyArgs, MergeRequestResponse, MergeRequestState};
+
+lazy_static! {
+    static ref PLACEHOLDER: Regex = Regex::new(r"\{(\w+)\}").unwrap();
+}
+
+const WEEK_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Number of reviewers listed in the report.
+const TOP_REVIEWERS: usize = 5;
+
+const MARKDOWN_TEMPLATE: &str = "\
+## Merge requests report: {since} to {until}
+
+- Opened: {opened_count}
+- Merged: {merged_count}
+- Pipeline pass rate: {pass_rate}
+
+### Opened
+
+{opened}
+
+### Merged
+
+{merged}
+
+### Top reviewers
+
+{top_reviewers}
+";
+
+const TEXT_TEMPLATE: &str = "\
+Merge requests report: {since} to {until}
+
+Opened: {opened_count}
+Merged: {merged_count}
+Pipeline pass rate: {pass_rate}
+
+Opened:
+{opened}
+
+Merged:
+{merged}
+
+Top reviewers:
+{top_reviewers}
+";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Text,
+}
+
+impl ReportFormat {
+    fn template(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => MARKDOWN_TEMPLATE,
+            ReportFormat::Text => TEXT_TEMPLATE,
+        }
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct WeeklyReportCliArgs {
+    pub format: ReportFormat,
+    /// Template file overriding the default template of the format.
+    #[builder(default)]
+    pub template: Option<String>,
+}
+
+impl WeeklyReportCliArgs {
+    pub fn builder() -> WeeklyReportCliArgsBuilder {
+        WeeklyReportCliArgsBuilder::default()
+    }
+}
+
+/// Merge request activity over the last 7 days. Built on the merge request
+/// and pipeline list APIs.
+#[derive(Debug)]
+pub struct WeeklyReport {
+    since: Seconds,
+    until: Seconds,
+    /// Merge requests created during the week that are still open or have
+    /// been merged.
+    opened: Vec<MergeRequestResponse>,
+    merged: Vec<MergeRequestResponse>,
+    /// Reviewers along with the number of merge requests they review, most
+    /// active first.
+    top_reviewers: Vec<(String, usize)>,
+    pipelines_passed: usize,
+    pipelines_failed: usize,
+}
+
+impl WeeklyReport {
+    /// Replaces the placeholders of the template by the report values in a
+    /// single pass, so placeholders in the values, ex. in a title, are kept
+    /// as is. Lists are rendered in the given format.
+    pub fn render(&self, template: &str, format: ReportFormat) -> String {
+        let merge_requests = |merge_requests: &[MergeRequestResponse]| {
+            render_list(
+                merge_requests
+                    .iter()
+                    .map(|mr| match format {
+                        ReportFormat::Markdown => {
+                            format!(
+                                "[{}]({}) by {}",
+                                escape_markdown(&mr.title),
+                                mr.web_url,
+                                mr.author
+                            )
+                        }
+                        ReportFormat::Text => {
+                            format!("{} ({}) {}", mr.title, mr.author, mr.web_url)
+                        }
+                    })
+                    .collect(),
+                format,
+            )
+        };
+        let top_reviewers = render_list(
+            self.top_reviewers
+                .iter()
+                .map(|(reviewer, count)| format!("{}: {}", reviewer, count))
+                .collect(),
+            format,
+        );
+        PLACEHOLDER
+            .replace_all(template, |caps: &Captures| match &caps[1] {
+                "since" => date(self.since),
+                "until" => date(self.until),
+                "opened_count" => self.opened.len().to_string(),
+                "merged_count" => self.merged.len().to_string(),
+                "pass_rate" => self.pass_rate(),
+                "opened" => merge_requests(&self.opened),
+                "merged" => merge_requests(&self.merged),
+                "top_reviewers" => top_reviewers.clone(),
+                _ => caps[0].to_string(),
+            })
+            .to_string()
+    }
+
+    /// Share of successful pipelines among the finished ones. Canceled and
+    /// skipped pipelines are not counted.
+    fn pass_rate(&self) -> String {
+        let finished = self.pipelines_passed + self.pipelines_failed;
+        if finished == 0 {
+            return "n/a, no finished pipelines".to_string();
+        }
+        format!(
+            "{}% ({}/{} pipelines)",
+            self.pipelines_passed * 100 / finished,
+            self.pipelines_passed,
+            finished
+        )
+    }
+}
+
+/// Escapes the characters that would change the markdown of a title, ex. a
+/// `|` breaking a table or a `]` closing the link early.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '|' | '*' | '_' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn render_list(items: Vec<String>, format: ReportFormat) -> String {
+    if items.is_empty() {
+        return match format {
+            ReportFormat::Markdown => "_None_".to_string(),
+            ReportFormat::Text => "None".to_string(),
+        };
+    }
+    items
+        .iter()
+        .map(|item| format!("- {}", item))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn date(seconds: Seconds) -> String {
+    chrono::DateTime::from_timestamp(*seconds as i64, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn rfc3339(seconds: Seconds) -> String {
+    chrono::DateTime::from_timestamp(*seconds as i64, 0)
+        .map(|date| date.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+/// Whether a date is at or after `since`.
+fn within(date: &DateTime<Utc>, since: Seconds) -> bool {
+    date.timestamp() >= *since as i64
+}
+
+pub fn execute(
+    options: ReportOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
+    match options {
+        ReportOptions::MergeRequestWeekly(cli_args) => {
+            let template = match &cli_args.template {
+                Some(template_path) => std::fs::read_to_string(template_path).map_err(|err| {
+                    GRError::PreconditionNotMet(format!(
+                        "Cannot read report template {}: {}",
+                        template_path, err
+                    ))
+                })?,
+                None => cli_args.format.template().to_string(),
+            };
+            // Reports are scheduled, so they are always built from fresh
+            // data.
+            let mr_remote = remote::get_mr(
+                domain.clone(),
+                path.clone(),
+                config.clone(),
+                None,
+                CacheType::None,
+            )?;
+            let cicd_remote = remote::get_cicd(domain, path, config, None, CacheType::None)?;
+            let report = weekly_report(mr_remote, cicd_remote, time::now_epoch_seconds())?;
+            write!(
+                std::io::stdout(),
+                "{}",
+                report.render(&template, cli_args.format)
+            )?;
+            Ok(())
+        }
+    }
+}
+
+fn weekly_report(
+    mr_remote: Arc<dyn MergeRequest>,
+    cicd_remote: Arc<dyn Cicd>,
+    now: Seconds,
+) -> Result<WeeklyReport> {
+    let since = now - Seconds::new(WEEK_SECONDS);
+    // Gitlab filters by update date on the server. Anything created, merged
+    // or run during the week was updated during the week too.
+    let list_args = || {
+        Some(
+            ListBodyArgs::builder()
+                .updated_after(Some(rfc3339(since)))
+                .build()
+                .unwrap(),
+        )
+    };
+    let list = |state| {
+        mr_remote.list(
+            MergeRequestListBodyArgs::builder()
+                .state(state)
+                .list_args(list_args())
+                .build()
+                .unwrap(),
+        )
+    };
+    let mut merged = list(MergeRequestState::Merged)?
+        .into_iter()
+        // Open merge requests have an empty merge date and are never
+        // within.
+        .filter(|mr| mr.merged_at.is_some_and(|date| within(&date, since)))
+        .collect::<Vec<_>>();
+    merged.sort_by_key(|mr| mr.id);
+    let mut opened = list(MergeRequestState::Opened)?
+        .into_iter()
+        .chain(merged.iter().cloned())
+        .filter(|mr| within(&mr.created_at, since))
+        .collect::<Vec<_>>();
+    opened.sort_by_key(|mr| mr.id);
+
+    let mut reviewed = HashSet::new();
+    let mut reviews: HashMap<String, usize> = HashMap::new();
+    for mr in merged.iter().chain(opened.iter()) {
+        if !reviewed.insert(mr.id) {
+            continue;
+        }
+        for reviewer in &mr.reviewers {
+            *reviews.entry(reviewer.clone()).or_default() += 1;
+        }
+    }
+    let mut top_reviewers = reviews.into_iter().collect::<Vec<_>>();
+    top_reviewers.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    top_reviewers.truncate(TOP_REVIEWERS);
+
+    let pipelines = cicd_remote.list(
+        PipelineBodyArgs::builder()
+            .from_to_page(list_args())
+            .build()
+            .unwrap(),
+    )?;
+    let statuses = pipelines
+        .iter()
+        .filter(|pipeline| within(&pipeline.created_at(), since))
+        .map(|pipeline| pipeline.status.as_str())
+        .collect::<Vec<_>>();
+    Ok(WeeklyReport {
+        since,
+        until: now,
+        opened,
+        merged,
+        top_reviewers,
+        pipelines_passed: statuses.iter().filter(|s| **s == "success").count(),
+        pipelines_failed: statuses.iter().filter(|s| **s == "failed").count(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::api_traits::NumberDeltaErr;
+    use crate::cmds::cicd::{LintResponse, Pipeline, YamlBytes};
+    use crate::cmds::merge_request::MergeRequestBodyArgs;
+    use crate::cmds::merge_request::MergeRequestMergeBodyArgs;
+
+    use super::*;
+
+    // 2024-06-15T00:00:00Z
+    const NOW: u64 = 1718409600;
+
+    struct MergeRequestMock {
+        opened: Vec<MergeRequestResponse>,
+        merged: Vec<MergeRequestResponse>,
+    }
+
+    impl MergeRequest for MergeRequestMock {
+        fn open(&self, _args: MergeRequestBodyArgs) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
+            assert_eq!(
+                Some("2024-06-08T00:00:00Z".to_string()),
+                args.list_args.unwrap().updated_after
+            );
+            match args.state {
+                MergeRequestState::Opened => Ok(self.opened.clone()),
+                MergeRequestState::Merged => Ok(self.merged.clone()),
+                MergeRequestState::Closed => Ok(vec![]),
+            }
+        }
+        fn merge(&self, _args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn get(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn close(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn approve(&self, _id: i64) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn toggle_draft(&self, _id: i64, _draft: bool) -> Result<MergeRequestResponse> {
+            unimplemented!()
+        }
+        fn num_pages(&self, _args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
+            unimplemented!()
+        }
+        fn num_resources(&self, _args: MergeRequestListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+            unimplemented!()
+        }
+    }
+
+    struct PipelineMock {
+        pipelines: Vec<Pipeline>,
+    }
+
+    impl Cicd for PipelineMock {
+        fn list(&self, _args: PipelineBodyArgs) -> Result<Vec<Pipeline>> {
+            Ok(self.pipelines.clone())
+        }
+        fn get_pipeline(&self, _id: i64) -> Result<Pipeline> {
+            unimplemented!()
+        }
+        fn num_pages(&self) -> Result<Option<u32>> {
+            unimplemented!()
+        }
+        fn num_resources(&self) -> Result<Option<NumberDeltaErr>> {
+            unimplemented!()
+        }
+        fn lint(&self, _body: YamlBytes) -> Result<LintResponse> {
+            unimplemented!()
+        }
+    }
+
+    fn merge_request(
+        id: i64,
+        created_at: &str,
+        merged_at: &str,
+        reviewers: &[&str],
+    ) -> MergeRequestResponse {
+        MergeRequestResponse::builder()
+            .id(id)
+            .title(format!("Merge request {}", id))
+            .web_url(format!(
+                "https://gitlab.com/jordilin/gitar/-/merge_requests/{}",
+                id
+            ))
+            .author("jordilin".to_string())
+            .created_at(created_at.parse().unwrap())
+            .merged_at(time::parse_timestamp(merged_at))
+            .reviewers(reviewers.iter().map(|r| r.to_string()).collect())
+            .build()
+            .unwrap()
+    }
+
+    fn pipeline(status: &str, created_at: &str) -> Pipeline {
+        Pipeline::builder()
+            .id(1)
+            .status(status.to_string())
+            .web_url("".to_string())
+            .branch("main".to_string())
+            .sha("".to_string())
+            .created_at(created_at.parse().unwrap())
+            .updated_at(created_at.parse().unwrap())
+            .duration(60)
+            .build()
+            .unwrap()
+    }
+
+    fn report() -> WeeklyReport {
+        let mr_remote = Arc::new(MergeRequestMock {
+            opened: vec![
+                merge_request(12, "2024-06-10T09:00:00Z", "", &["alice", "bob"]),
+                // Opened before the week.
+                merge_request(3, "2024-05-01T09:00:00Z", "", &["carol"]),
+            ],
+            merged: vec![
+                merge_request(
+                    10,
+                    "2024-06-09T09:00:00Z",
+                    "2024-06-11T09:00:00Z",
+                    &["alice"],
+                ),
+                merge_request(
+                    8,
+                    "2024-06-01T09:00:00Z",
+                    "2024-06-12T09:00:00Z",
+                    &["bob", "alice"],
+                ),
+                // Merged the week before, updated this week.
+                merge_request(5, "2024-05-20T09:00:00Z", "2024-06-01T09:00:00Z", &["dave"]),
+            ],
+        });
+        let cicd_remote = Arc::new(PipelineMock {
+            pipelines: vec![
+                pipeline("success", "2024-06-10T09:00:00Z"),
+                pipeline("success", "2024-06-11T09:00:00Z"),
+                pipeline("failed", "2024-06-12T09:00:00Z"),
+                pipeline("canceled", "2024-06-12T10:00:00Z"),
+                pipeline("failed", "2024-06-01T09:00:00Z"),
+            ],
+        });
+        weekly_report(mr_remote, cicd_remote, Seconds::new(NOW)).unwrap()
+    }
+
+    #[test]
+    fn test_weekly_report_markdown() {
+        let report = report().render(MARKDOWN_TEMPLATE, ReportFormat::Markdown);
+        assert_eq!(
+            "\
+## Merge requests report: 2024-06-08 to 2024-06-15
+
+- Opened: 2
+- Merged: 2
+- Pipeline pass rate: 66% (2/3 pipelines)
+
+### Opened
+
+- [Merge request 10](https://gitlab.com/jordilin/gitar/-/merge_requests/10) by jordilin
+- [Merge request 12](https://gitlab.com/jordilin/gitar/-/merge_requests/12) by jordilin
+
+### Merged
+
+- [Merge request 8](https://gitlab.com/jordilin/gitar/-/merge_requests/8) by jordilin
+- [Merge request 10](https://gitlab.com/jordilin/gitar/-/merge_requests/10) by jordilin
+
+### Top reviewers
+
+- alice: 3
+- bob: 2
+",
+            report
+        );
+    }
+
+    #[test]
+    fn test_weekly_report_custom_template() {
+        let report = report().render(
+            "{merged_count} merged, pipelines {pass_rate}\n{top_reviewers}",
+            ReportFormat::Text,
+        );
+        assert_eq!(
+            "2 merged, pipelines 66% (2/3 pipelines)\n- alice: 3\n- bob: 2",
+            report
+        );
+    }
+
+    #[test]
+    fn test_weekly_report_keeps_placeholders_in_titles() {
+        let mut report = report();
+        report.opened.truncate(1);
+        report.opened[0].title = "Render {merged} and {unknown}".to_string();
+        assert_eq!(
+            "- Render {merged} and {unknown} (jordilin) https://gitlab.com/jordilin/gitar/-/merge_requests/10 | {unknown}",
+            report.render("{opened} | {unknown}", ReportFormat::Text)
+        );
+    }
+
+    #[test]
+    fn test_weekly_report_escapes_markdown_in_titles() {
+        let mut report = report();
+        report.opened.truncate(1);
+        report.opened[0].title = "Fix *all* [the] a|b snake_case".to_string();
+        assert_eq!(
+            "- [Fix \\*all\\* \\[the\\] a\\|b snake\\_case](https://gitlab.com/jordilin/gitar/-/merge_requests/10) by jordilin",
+            report.render("{opened}", ReportFormat::Markdown)
+        );
+    }
+
+    #[test]
+    fn test_weekly_report_without_activity() {
+        let report = weekly_report(
+            Arc::new(MergeRequestMock {
+                opened: vec![],
+                merged: vec![],
+            }),
+            Arc::new(PipelineMock { pipelines: vec![] }),
+            Seconds::new(NOW),
+        )
+        .unwrap();
+        assert_eq!(
+            "None | n/a, no finished pipelines",
+            report.render("{opened} | {pass_rate}", ReportFormat::Text)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt::Display;
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::api_traits::{Search, Timestamp};
+use crate::cli::search::SearchOptions;
+use crate::config::ConfigProperties;
+use crate::display::{Column, DisplayBody};
+use crate::remote::{self, CacheType, ListBodyArgs, ListRemoteCliArgs};
+use crate::time;
+use crate::Result;
+use chrono::{DateTime, Utc};
+
+use super::common::{self, num_search_pages, num_search_resources};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchScope {
+    Issues,
+    MergeRequests,
+    Commits,
+}
+
+impl Display for SearchScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchScope::Issues => write!(f, "issues"),
+            SearchScope::MergeRequests => write!(f, "merge_requests"),
+            SearchScope::Commits => write!(f, "commits"),
+        }
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct SearchCliArgs {
+    pub scope: SearchScope,
+    pub query: String,
+    pub list_args: ListRemoteCliArgs,
+}
+
+impl SearchCliArgs {
+    pub fn builder() -> SearchCliArgsBuilder {
+        SearchCliArgsBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone)]
+pub struct SearchBodyArgs {
+    pub scope: SearchScope,
+    pub query: String,
+    #[builder(default)]
+    pub from_to_page: Option<ListBodyArgs>,
+}
+
+impl SearchBodyArgs {
+    pub fn builder() -> SearchBodyArgsBuilder {
+        SearchBodyArgsBuilder::default()
+    }
+}
+
+/// A search hit. Issues and merge requests are identified by their number in
+/// the project, commits by their short SHA.
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    pub id: String,
+    pub title: String,
+    /// Commits have no state
+    #[builder(default)]
+    pub state: String,
+    pub author: String,
+    pub web_url: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl SearchResult {
+    pub fn builder() -> SearchResultBuilder {
+        SearchResultBuilder::default()
+    }
+}
+
+impl Timestamp for SearchResult {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at.unwrap_or_default()
+    }
+}
+
+impl From<SearchResult> for DisplayBody {
+    fn from(result: SearchResult) -> Self {
+        DisplayBody::new(vec![
+            Column::new("ID", result.id),
+            Column::new("Title", result.title),
+            Column::builder()
+                .name("State".to_string())
+                .value(result.state)
+                .optional(true)
+                .build()
+                .unwrap(),
+            Column::new("Author", result.author),
+            Column::new("URL", result.web_url),
+            Column::new(
+                "Created at",
+                time::format_optional_timestamp(result.created_at.as_ref()),
+            ),
+        ])
+    }
+}
+
+pub fn execute(
+    options: SearchOptions,
+    config: Arc<dyn ConfigProperties>,
+    domain: String,
+    path: String,
+) -> Result<()> {
+    match options {
+        SearchOptions::Search(cli_args) => {
+            let remote = remote::get_search(
+                domain,
+                path,
+                config,
+                Some(&cli_args.list_args.get_args.cache_args),
+                CacheType::File,
+            )?;
+            let from_to_page = remote::validate_from_to_page(&cli_args.list_args)?;
+            let body_args = SearchBodyArgs::builder()
+                .scope(cli_args.scope)
+                .query(cli_args.query.clone())
+                .from_to_page(from_to_page)
+                .build()?;
+            if cli_args.list_args.num_pages {
+                return num_search_pages(remote, body_args, std::io::stdout());
+            }
+            if cli_args.list_args.num_resources {
+                return num_search_resources(remote, body_args, std::io::stdout());
+            }
+            search(remote, body_args, cli_args, std::io::stdout())
+        }
+    }
+}
+
+fn search<W: Write>(
+    remote: Arc<dyn Search>,
+    body_args: SearchBodyArgs,
+    cli_args: SearchCliArgs,
+    mut writer: W,
+) -> Result<()> {
+    common::list_search_results(remote, body_args, cli_args, &mut writer)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::api_traits::NumberDeltaErr;
+
+    use super::*;
+
+    struct SearchMock {
+        results: Vec<SearchResult>,
+    }
+
+    impl Search for SearchMock {
+        fn list(&self, _args: SearchBodyArgs) -> Result<Vec<SearchResult>> {
+            Ok(self.results.clone())
+        }
+
+        fn num_pages(&self, _args: SearchBodyArgs) -> Result<Option<u32>> {
+            Ok(Some(1))
+        }
+
+        fn num_resources(&self, _args: SearchBodyArgs) -> Result<Option<NumberDeltaErr>> {
+            todo!()
+        }
+    }
+
+    fn body_args() -> SearchBodyArgs {
+        SearchBodyArgs::builder()
+            .scope(SearchScope::MergeRequests)
+            .query("rate limit".to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn cli_args() -> SearchCliArgs {
+        SearchCliArgs::builder()
+            .scope(SearchScope::MergeRequests)
+            .query("rate limit".to_string())
+            .list_args(ListRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_search_results() {
+        let remote = Arc::new(SearchMock {
+            results: vec![SearchResult::builder()
+                .id("12".to_string())
+                .title("Handle rate limit headers".to_string())
+                .state("merged".to_string())
+                .author("jordilin".to_string())
+                .web_url("https://gitlab.com/jordilin/gitar/-/merge_requests/12".to_string())
+                .created_at(Some("2024-02-01T10:00:00Z".parse().unwrap()))
+                .build()
+                .unwrap()],
+        });
+        let mut buf = Vec::new();
+        search(remote, body_args(), cli_args(), &mut buf).unwrap();
+        assert_eq!(
+            "ID|Title|Author|URL|Created at\n\
+             12|Handle rate limit headers|jordilin|https://gitlab.com/jordilin/gitar/-/merge_requests/12|2024-02-01T10:00:00Z\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_search_no_results() {
+        let remote = Arc::new(SearchMock { results: vec![] });
+        let mut buf = Vec::new();
+        search(remote, body_args(), cli_args(), &mut buf).unwrap();
+        assert_eq!("No resources found.\n", String::from_utf8(buf).unwrap());
+    }
+}
@@ -0,0 +1,332 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+
+use crate::cli::serve_mock::ServeMockOptions;
+use crate::error::GRError;
+use crate::Result;
+
+const GITLAB_API_PREFIX: &str = "/api/v4";
+/// Github Enterprise serves its REST API under /api/v3. Also accepted so
+/// both `http://localhost:PORT` and `http://localhost:PORT/api/v3` work as
+/// `api_base_url`.
+const GITHUB_ENTERPRISE_API_PREFIX: &str = "/api/v3";
+
+/// Endpoint served from a contract. `*` in the pattern matches any path
+/// segment, such as the encoded project path or the id of a resource.
+struct Route {
+    method: &'static str,
+    pattern: &'static str,
+    contract: &'static str,
+    /// The contract holds a single object but the endpoint lists them.
+    list: bool,
+}
+
+const fn route(method: &'static str, pattern: &'static str, contract: &'static str) -> Route {
+    Route {
+        method,
+        pattern,
+        contract,
+        list: false,
+    }
+}
+
+const fn list_route(method: &'static str, pattern: &'static str, contract: &'static str) -> Route {
+    Route {
+        method,
+        pattern,
+        contract,
+        list: true,
+    }
+}
+
+const GITLAB_ROUTES: &[Route] = &[
+    route("GET", "/user", "get_user_info.json"),
+    route("GET", "/users", "get_user_by_username.json"),
+    route("GET", "/projects/*", "project.json"),
+    list_route("GET", "/projects/*/merge_requests", "merge_request.json"),
+    route("POST", "/projects/*/merge_requests", "merge_request.json"),
+    route("GET", "/projects/*/merge_requests/*", "merge_request.json"),
+    route("PUT", "/projects/*/merge_requests/*", "merge_request.json"),
+    route(
+        "PUT",
+        "/projects/*/merge_requests/*/merge",
+        "merge_request.json",
+    ),
+    route(
+        "POST",
+        "/projects/*/merge_requests/*/approve",
+        "approve_merge_request.json",
+    ),
+    route(
+        "GET",
+        "/projects/*/merge_requests/*/changes",
+        "merge_request_changes.json",
+    ),
+    list_route("GET", "/projects/*/merge_requests/*/notes", "comment.json"),
+    route("POST", "/projects/*/merge_requests/*/notes", "comment.json"),
+    list_route("GET", "/projects/*/issues", "issue.json"),
+    route("POST", "/projects/*/issues", "issue.json"),
+    route("GET", "/projects/*/issues/*", "issue.json"),
+    route("PUT", "/projects/*/issues/*", "issue.json"),
+    route("POST", "/projects/*/issues/*/notes", "comment.json"),
+    route("GET", "/projects/*/pipelines", "list_pipelines.json"),
+    route("GET", "/projects/*/jobs", "list_project_jobs.json"),
+    route("GET", "/projects/*/members/all", "project_members.json"),
+    route("GET", "/projects/*/releases", "list_releases.json"),
+    route("GET", "/projects/*/repository/tags", "list_tags.json"),
+    route("GET", "/projects/*/languages", "project_languages.json"),
+    list_route("GET", "/projects/*/labels", "label.json"),
+    route("POST", "/projects/*/labels", "label.json"),
+];
+
+const GITHUB_ROUTES: &[Route] = &[
+    route("GET", "/user", "get_auth_user.json"),
+    route("GET", "/users/*", "get_user_by_username.json"),
+    route("GET", "/repos/*/*", "project.json"),
+    list_route("GET", "/repos/*/*/pulls", "merge_request.json"),
+    route("POST", "/repos/*/*/pulls", "merge_request.json"),
+    route("GET", "/repos/*/*/pulls/*", "merge_request.json"),
+    route("PATCH", "/repos/*/*/pulls/*", "merge_request.json"),
+    route("GET", "/repos/*/*/pulls/*/files", "pull_request_files.json"),
+    route("GET", "/repos/*/*/issues", "list_issues_user.json"),
+    route("POST", "/repos/*/*/issues", "issue.json"),
+    route("GET", "/repos/*/*/issues/*", "issue.json"),
+    route("PATCH", "/repos/*/*/issues/*", "issue.json"),
+    list_route("GET", "/repos/*/*/issues/*/comments", "comment.json"),
+    route("POST", "/repos/*/*/issues/*/comments", "comment.json"),
+    route("GET", "/repos/*/*/actions/runs", "list_workflow_runs.json"),
+    route("GET", "/repos/*/*/actions/workflows", "list_workflows.json"),
+    route("GET", "/repos/*/*/contributors", "project_members.json"),
+    route("GET", "/repos/*/*/releases", "list_releases.json"),
+    route("GET", "/repos/*/*/tags", "list_tags.json"),
+    route("GET", "/repos/*/*/languages", "project_languages.json"),
+    list_route("GET", "/repos/*/*/labels", "label.json"),
+    route("POST", "/repos/*/*/labels", "label.json"),
+];
+
+/// Contract file answering a request, relative to the contracts directory,
+/// and whether it has to be wrapped in a list.
+#[derive(Debug, PartialEq)]
+struct Resolved {
+    contract: PathBuf,
+    list: bool,
+}
+
+fn resolve(method: &str, path: &str) -> Option<Resolved> {
+    let path = path.split('?').next().unwrap_or_default();
+    let path = path.trim_end_matches('/');
+    let (provider, routes, path) = match path.strip_prefix(GITLAB_API_PREFIX) {
+        Some(path) => ("gitlab", GITLAB_ROUTES, path),
+        None => (
+            "github",
+            GITHUB_ROUTES,
+            path.strip_prefix(GITHUB_ENTERPRISE_API_PREFIX)
+                .unwrap_or(path),
+        ),
+    };
+    routes
+        .iter()
+        .find(|route| route.method == method && matches(route.pattern, path))
+        .map(|route| Resolved {
+            contract: Path::new(provider).join(route.contract),
+            list: route.list,
+        })
+}
+
+fn matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.split('/').collect::<Vec<_>>();
+    let path = path.split('/').collect::<Vec<_>>();
+    pattern.len() == path.len()
+        && pattern
+            .iter()
+            .zip(path.iter())
+            .all(|(expected, actual)| *expected == "*" || expected == actual)
+}
+
+pub fn execute(options: ServeMockOptions) -> Result<()> {
+    let contracts = PathBuf::from(&options.contracts);
+    if !contracts.is_dir() {
+        return Err(GRError::PreconditionNotMet(format!(
+            "Contracts directory {} does not exist",
+            options.contracts
+        ))
+        .into());
+    }
+    let listener = TcpListener::bind(("127.0.0.1", options.port))?;
+    let mut writer = std::io::stdout();
+    writeln!(
+        writer,
+        "Serving contracts from {} at http://127.0.0.1:{}",
+        options.contracts, options.port
+    )?;
+    writeln!(
+        writer,
+        "Set api_base_url to http://127.0.0.1:{port}/api/v4 for Gitlab or \
+         http://127.0.0.1:{port}/api/v3 for Github",
+        port = options.port
+    )?;
+    serve(listener, &contracts, writer)
+}
+
+fn serve<W: Write>(listener: TcpListener, contracts: &Path, mut writer: W) -> Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        // A client going away mid request does not stop the server.
+        match handle(&stream, contracts) {
+            Ok(log_line) => writeln!(writer, "{}", log_line)?,
+            Err(err) => writeln!(writer, "Error: {}", err)?,
+        }
+    }
+    Ok(())
+}
+
+/// Answers a single request and returns the line logging it.
+fn handle(stream: &TcpStream, contracts: &Path) -> Result<String> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    // Request bodies are not used but have to be read for the client to get
+    // the response.
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let (status, response) = match resolve(&method, &path) {
+        Some(resolved) => {
+            let contract = fs::read_to_string(contracts.join(&resolved.contract))?;
+            let status = if method == "POST" {
+                "201 Created"
+            } else {
+                "200 OK"
+            };
+            let response = if resolved.list {
+                format!("[{}]", contract)
+            } else {
+                contract
+            };
+            (status, response)
+        }
+        None => ("404 Not Found", r#"{"message":"Not Found"}"#.to_string()),
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        response.len(),
+        response
+    )?;
+    stream.flush()?;
+    Ok(format!("{} {} {}", method, path, status))
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_gitlab_routes() {
+        assert_eq!(
+            Some(Resolved {
+                contract: PathBuf::from("gitlab/merge_request.json"),
+                list: false,
+            }),
+            resolve(
+                "GET",
+                "/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33"
+            )
+        );
+        assert_eq!(
+            Some(Resolved {
+                contract: PathBuf::from("gitlab/merge_request.json"),
+                list: true,
+            }),
+            resolve(
+                "GET",
+                "/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=opened&page=1"
+            )
+        );
+        assert_eq!(
+            None,
+            resolve("DELETE", "/api/v4/projects/jordilin%2Fgitlapi")
+        );
+    }
+
+    #[test]
+    fn test_resolve_github_routes() {
+        assert_eq!(
+            Some(Resolved {
+                contract: PathBuf::from("github/pull_request_files.json"),
+                list: false,
+            }),
+            resolve("GET", "/repos/jordilin/githapi/pulls/23/files")
+        );
+        assert_eq!(
+            Some(Resolved {
+                contract: PathBuf::from("github/project.json"),
+                list: false,
+            }),
+            resolve("GET", "/api/v3/repos/jordilin/githapi")
+        );
+        assert_eq!(None, resolve("GET", "/repos/jordilin/githapi/unknown"));
+    }
+
+    fn request(port: u16, request: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_serve_contracts() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let contracts = Path::new("contracts");
+            let mut log = Vec::new();
+            for stream in listener.incoming().take(2) {
+                log.push(handle(&stream.unwrap(), contracts).unwrap());
+            }
+            log
+        });
+        let response = request(
+            port,
+            "POST /api/v4/projects/jordilin%2Fgitlapi/issues HTTP/1.1\r\n\
+             Host: localhost\r\nContent-Length: 17\r\n\r\n{\"title\":\"Test\"}\n",
+        );
+        assert!(response.starts_with("HTTP/1.1 201 Created\r\n"));
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        let issue: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(24, issue["iid"]);
+        let response = request(port, "GET /unknown HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(response.ends_with(r#"{"message":"Not Found"}"#));
+        assert_eq!(
+            vec![
+                "POST /api/v4/projects/jordilin%2Fgitlapi/issues 201 Created",
+                "GET /unknown 404 Not Found",
+            ],
+            server.join().unwrap()
+        );
+    }
+}
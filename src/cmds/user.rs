@@ -1,17 +1,23 @@
-use std::{io::Write, sync::Arc};
+use std::{fmt::Display, io::Write, sync::Arc};
 
 use crate::{
-    api_traits::UserInfo,
+    api_traits::{Timestamp, UserActivity, UserInfo},
     cli::user::UserOptions,
     config::ConfigProperties,
-    display,
+    display::{self, Column, DisplayBody, Format},
+    error::GRError,
     remote::{self, CacheType, GetRemoteCliArgs},
-    Result,
+    time, Result,
 };
+use chrono::{DateTime, Utc};
 
 #[derive(Builder)]
 pub struct UserCliArgs {
     pub username: String,
+    /// Number of recent contribution events shown with the profile. Zero
+    /// shows none.
+    #[builder(default)]
+    pub events: usize,
     pub get_args: GetRemoteCliArgs,
 }
 
@@ -21,6 +27,203 @@ impl UserCliArgs {
     }
 }
 
+use super::project::Member;
+
+/// Public profile of a user. Organizations are the Github organizations the
+/// user is a public member of, Gitlab only exposes the organization set in
+/// the profile.
+#[derive(Builder, Clone, Debug)]
+pub struct UserProfile {
+    #[builder(default)]
+    pub id: i64,
+    pub username: String,
+    #[builder(default)]
+    pub name: String,
+    #[builder(default)]
+    pub bio: String,
+    #[builder(default)]
+    pub location: String,
+    #[builder(default)]
+    pub organizations: Vec<String>,
+    #[builder(default)]
+    pub web_url: String,
+    #[builder(default)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl UserProfile {
+    pub fn builder() -> UserProfileBuilder {
+        UserProfileBuilder::default()
+    }
+}
+
+impl From<Member> for UserProfile {
+    fn from(member: Member) -> Self {
+        UserProfile::builder()
+            .id(member.id)
+            .username(member.username)
+            .name(member.name)
+            .build()
+            .unwrap()
+    }
+}
+
+fn optional_column(name: &str, value: String) -> Column {
+    Column::builder()
+        .name(name.to_string())
+        .value(value)
+        .optional(true)
+        .build()
+        .unwrap()
+}
+
+impl From<UserProfile> for DisplayBody {
+    fn from(profile: UserProfile) -> Self {
+        DisplayBody::new(vec![
+            Column::new("ID", profile.id.to_string()),
+            Column::new("Username", profile.username),
+            Column::new("Name", profile.name),
+            Column::new("Organizations", profile.organizations.join(", ")),
+            optional_column("Bio", profile.bio),
+            optional_column("Location", profile.location),
+            optional_column("URL", profile.web_url),
+            optional_column(
+                "Created at",
+                time::format_optional_timestamp(profile.created_at.as_ref()),
+            ),
+        ])
+    }
+}
+
+/// Kind of user contribution. Events that do not count as contributions are
+/// `Other`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ActivityKind {
+    MergeRequestOpened,
+    MergeRequestMerged,
+    Review,
+    IssueOpened,
+    Push,
+    Other,
+}
+
+impl Display for ActivityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActivityKind::MergeRequestOpened => write!(f, "merge_request_opened"),
+            ActivityKind::MergeRequestMerged => write!(f, "merge_request_merged"),
+            ActivityKind::Review => write!(f, "review"),
+            ActivityKind::IssueOpened => write!(f, "issue_opened"),
+            ActivityKind::Push => write!(f, "push"),
+            ActivityKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct ActivityEvent {
+    pub kind: ActivityKind,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ActivityEvent {
+    pub fn builder() -> ActivityEventBuilder {
+        ActivityEventBuilder::default()
+    }
+}
+
+impl Timestamp for ActivityEvent {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+impl From<ActivityEvent> for DisplayBody {
+    fn from(event: ActivityEvent) -> Self {
+        DisplayBody::new(vec![
+            Column::new("Kind", event.kind.to_string()),
+            Column::new("Created at", time::format_timestamp(&event.created_at)),
+        ])
+    }
+}
+
+#[derive(Builder)]
+pub struct ActivityBodyArgs {
+    pub user: Member,
+    /// Exclusive lower bound date in YYYY-MM-DD format
+    #[builder(default)]
+    pub after: Option<String>,
+    /// Exclusive upper bound date in YYYY-MM-DD format
+    #[builder(default)]
+    pub before: Option<String>,
+}
+
+impl ActivityBodyArgs {
+    pub fn builder() -> ActivityBodyArgsBuilder {
+        ActivityBodyArgsBuilder::default()
+    }
+}
+
+#[derive(Builder)]
+pub struct ContributionsCliArgs {
+    #[builder(default)]
+    pub after: Option<String>,
+    #[builder(default)]
+    pub before: Option<String>,
+    pub get_args: GetRemoteCliArgs,
+}
+
+impl ContributionsCliArgs {
+    pub fn builder() -> ContributionsCliArgsBuilder {
+        ContributionsCliArgsBuilder::default()
+    }
+}
+
+/// Number of contributions per kind aggregated over a day. The date is
+/// `Total` for the summary row.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DailyContributions {
+    pub date: String,
+    pub mrs_opened: u32,
+    pub mrs_merged: u32,
+    pub reviews: u32,
+    pub issues_opened: u32,
+    pub pushes: u32,
+}
+
+impl DailyContributions {
+    pub fn new(date: &str) -> Self {
+        DailyContributions {
+            date: date.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn add(&mut self, kind: ActivityKind) {
+        match kind {
+            ActivityKind::MergeRequestOpened => self.mrs_opened += 1,
+            ActivityKind::MergeRequestMerged => self.mrs_merged += 1,
+            ActivityKind::Review => self.reviews += 1,
+            ActivityKind::IssueOpened => self.issues_opened += 1,
+            ActivityKind::Push => self.pushes += 1,
+            ActivityKind::Other => {}
+        }
+    }
+}
+
+impl From<DailyContributions> for DisplayBody {
+    fn from(day: DailyContributions) -> Self {
+        DisplayBody::new(vec![
+            Column::new("Date", day.date),
+            Column::new("MRs opened", day.mrs_opened.to_string()),
+            Column::new("MRs merged", day.mrs_merged.to_string()),
+            Column::new("Reviews", day.reviews.to_string()),
+            Column::new("Issues opened", day.issues_opened.to_string()),
+            Column::new("Pushes", day.pushes.to_string()),
+        ])
+    }
+}
+
 pub fn execute(
     options: UserOptions,
     config: Arc<dyn ConfigProperties>,
@@ -30,24 +233,77 @@ pub fn execute(
     match options {
         UserOptions::Get(args) => {
             let remote = remote::get_user(
-                domain,
-                path,
-                config,
+                domain.clone(),
+                path.clone(),
+                config.clone(),
                 Some(&args.get_args.cache_args),
                 CacheType::File,
             )?;
-            get_user_details(remote, &args, std::io::stdout())
+            let activity_remote = if args.events > 0 {
+                match remote::get_user_activity(
+                    domain,
+                    path,
+                    config,
+                    Some(&args.get_args.cache_args),
+                    CacheType::File,
+                ) {
+                    Ok(activity_remote) => Some(activity_remote as Arc<dyn UserActivity>),
+                    // Bitbucket has no user events, the profile is shown on
+                    // its own.
+                    Err(err)
+                        if matches!(
+                            err.downcast_ref::<GRError>(),
+                            Some(GRError::OperationNotSupported(_))
+                        ) =>
+                    {
+                        None
+                    }
+                    Err(err) => return Err(err),
+                }
+            } else {
+                None
+            };
+            get_user_details(remote, activity_remote, &args, std::io::stdout())
         }
     }
 }
 
 pub fn get_user_details<W: Write>(
     remote: Arc<dyn UserInfo>,
+    activity_remote: Option<Arc<dyn UserActivity>>,
     args: &UserCliArgs,
     mut writer: W,
 ) -> Result<()> {
-    let response = remote.get(args)?;
-    display::print(&mut writer, vec![response], args.get_args.clone())?;
+    let profile = remote.get_profile(args)?;
+    let user = Member::builder()
+        .id(profile.id)
+        .username(profile.username.clone())
+        .build()
+        .unwrap();
+    display::print_one(&mut writer, profile, args.get_args.clone())?;
+    let Some(activity_remote) = activity_remote else {
+        return Ok(());
+    };
+    // Github only keeps the events of the last 90 days, Gitlab is bound to
+    // the same window.
+    let after = (chrono::Local::now() - chrono::Duration::days(90))
+        .format("%Y-%m-%d")
+        .to_string();
+    let body_args = ActivityBodyArgs::builder()
+        .user(user)
+        .after(Some(after))
+        .build()?;
+    let mut events = activity_remote.list_events(body_args)?;
+    if events.is_empty() {
+        return Ok(());
+    }
+    // Newest first.
+    events.sort_by_key(|event| std::cmp::Reverse(event.created_at));
+    events.truncate(args.events);
+    if !matches!(args.get_args.format, Format::JSON | Format::NDJSON) {
+        writeln!(writer, "\nRecent activity:")?;
+    }
+    display::print(&mut writer, events, args.get_args.clone())?;
     Ok(())
 }
 
@@ -55,7 +311,6 @@ pub fn get_user_details<W: Write>(
 mod tests {
 
     use super::*;
-    use crate::cmds::project::Member;
 
     struct MockUserInfo;
 
@@ -77,20 +332,85 @@ mod tests {
                 .build()
                 .unwrap())
         }
+
+        fn get_profile(&self, _args: &UserCliArgs) -> Result<UserProfile> {
+            Ok(UserProfile::builder()
+                .id(1)
+                .username("tomsawyer".to_string())
+                .name("Tom Sawyer".to_string())
+                .bio("Adventurer".to_string())
+                .organizations(vec!["github".to_string(), "gitlab".to_string()])
+                .build()
+                .unwrap())
+        }
+    }
+
+    struct MockActivity {
+        events: Vec<ActivityEvent>,
+    }
+
+    impl UserActivity for MockActivity {
+        fn list_events(&self, args: ActivityBodyArgs) -> Result<Vec<ActivityEvent>> {
+            assert_eq!("tomsawyer", args.user.username);
+            assert!(args.after.is_some());
+            Ok(self.events.clone())
+        }
+    }
+
+    fn event(kind: ActivityKind, created_at: &str) -> ActivityEvent {
+        ActivityEvent::builder()
+            .kind(kind)
+            .created_at(created_at.parse().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn user_args(events: usize) -> UserCliArgs {
+        UserCliArgs::builder()
+            .username("tomsawyer".to_string())
+            .events(events)
+            .get_args(GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap()
     }
 
     #[test]
     fn test_get_user_details() {
         let remote = MockUserInfo::new();
-        let args = UserCliArgs::builder()
-            .username("test".to_string())
-            .get_args(GetRemoteCliArgs::builder().build().unwrap())
-            .build()
-            .unwrap();
         let mut writer = Vec::new();
-        get_user_details(Arc::new(remote), &args, &mut writer).unwrap();
+        get_user_details(Arc::new(remote), None, &user_args(0), &mut writer).unwrap();
+        assert_eq!(
+            "ID|Username|Name|Organizations\n1|tomsawyer|Tom Sawyer|github, gitlab\n",
+            String::from_utf8(writer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_user_details_with_recent_activity() {
+        let remote = MockUserInfo::new();
+        let activity = MockActivity {
+            events: vec![
+                event(ActivityKind::Push, "2024-02-10T09:00:00Z"),
+                event(ActivityKind::Review, "2024-02-12T09:00:00Z"),
+                event(ActivityKind::IssueOpened, "2024-02-11T09:00:00Z"),
+            ],
+        };
+        let mut writer = Vec::new();
+        get_user_details(
+            Arc::new(remote),
+            Some(Arc::new(activity)),
+            &user_args(2),
+            &mut writer,
+        )
+        .unwrap();
         assert_eq!(
-            "ID|Username\n1|tomsawyer\n",
+            "ID|Username|Name|Organizations\n\
+             1|tomsawyer|Tom Sawyer|github, gitlab\n\
+             \n\
+             Recent activity:\n\
+             Kind|Created at\n\
+             review|2024-02-12T09:00:00Z\n\
+             issue_opened|2024-02-11T09:00:00Z\n",
             String::from_utf8(writer).unwrap()
         );
     }
@@ -1,15 +1,41 @@
 //! Config file parsing and validation.
 
-use crate::api_defaults::{EXPIRE_IMMEDIATELY, RATE_LIMIT_REMAINING_THRESHOLD, REST_API_MAX_PAGES};
+use crate::api_defaults::{
+    EXPIRE_IMMEDIATELY, MAX_PER_PAGE, MAX_RESPONSE_BODY_SIZE, RATE_LIMIT_REMAINING_THRESHOLD,
+    REST_API_MAX_PAGES, USER_CACHE_EXPIRATION,
+};
 use crate::api_traits::ApiOperation;
+use crate::auth::github_app::GithubAppConfig;
+use crate::auth::AuthMethod;
+use crate::cmds::merge_request::owners::OwnershipRule;
 use crate::cmds::project::{Member, MrMemberType};
 use crate::error::{self, GRError};
 use crate::remote::RemoteURL;
 use crate::Result;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
 use std::sync::Arc;
 use std::{collections::HashMap, io::Read};
 
+/// Command aliases, read by the command line parser.
+pub const ALIAS_TABLE: &str = "alias";
+/// Workflows of gitar commands, read by `gr flow`.
+pub const FLOW_TABLE: &str = "flow";
+/// Named sets of projects, read by `gr mr list --repos`.
+pub const REPOS_TABLE: &str = "repos";
+/// Top level tables that are not domain configurations.
+const COMMAND_TABLES: [&str; 3] = [ALIAS_TABLE, FLOW_TABLE, REPOS_TABLE];
+
+/// Set of projects declared in the `[repos]` table. Projects are given as
+/// DOMAIN/OWNER/PROJECT_NAME.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct RepoSet {
+    pub repos: Vec<String>,
+}
+
 pub trait ConfigProperties: Send + Sync {
     fn api_token(&self) -> &str;
     fn cache_location(&self) -> Option<&str>;
@@ -25,6 +51,24 @@ pub trait ConfigProperties: Send + Sync {
         ""
     }
 
+    /// Template of the comment posted by `mr nag` on merge requests awaiting
+    /// review. Empty if not configured.
+    fn merge_request_review_reminder(&self) -> &str {
+        ""
+    }
+
+    /// Rules mapping changed paths to reviewers and labels, used to pre-fill
+    /// `mr create`.
+    fn merge_request_rules(&self) -> Vec<OwnershipRule> {
+        vec![]
+    }
+
+    /// Branch `mr create` targets when not given on the command line,
+    /// instead of the default branch of the project.
+    fn merge_request_target_branch(&self) -> Option<&str> {
+        None
+    }
+
     fn get_cache_expiration(&self, _api_operation: &ApiOperation) -> &str {
         // Defaults to regular HTTP cache expiration mechanisms.
         "0s"
@@ -33,9 +77,119 @@ pub trait ConfigProperties: Send + Sync {
         REST_API_MAX_PAGES
     }
 
+    /// Retries allowed for failed requests of an API operation when no
+    /// retries have been requested on the command line.
+    fn get_retry_budget(&self, _api_operation: &ApiOperation) -> Option<RetryBudget> {
+        None
+    }
+
     fn rate_limit_remaining_threshold(&self) -> u32 {
         RATE_LIMIT_REMAINING_THRESHOLD
     }
+
+    /// Maximum size in bytes of an HTTP response body. Bodies are read in full
+    /// into memory before being parsed, larger ones are an error.
+    fn max_response_body_size(&self) -> u64 {
+        MAX_RESPONSE_BODY_SIZE
+    }
+
+    /// Number of results per page requested when listing.
+    fn per_page(&self) -> u32 {
+        MAX_PER_PAGE
+    }
+
+    /// Username to impersonate using the Gitlab Sudo header.
+    fn sudo_username(&self) -> Option<&str> {
+        None
+    }
+
+    /// Send requests even if the circuit breaker for the domain is open.
+    fn force_circuit(&self) -> bool {
+        false
+    }
+
+    /// Fail on remote payloads that do not match the expected contract
+    /// instead of defaulting missing or unexpected fields.
+    fn strict_contract(&self) -> bool {
+        false
+    }
+
+    /// Full REST API base URL of the remote, for instances served behind a
+    /// path prefix. Ex. https://tools.corp/gitlab/api/v4
+    fn api_base_url(&self) -> Option<&str> {
+        None
+    }
+
+    /// How the API token authenticates requests.
+    fn auth_method(&self) -> AuthMethod {
+        AuthMethod::default()
+    }
+
+    /// Github App used to mint installation tokens when the auth method is
+    /// `github_app`.
+    fn github_app(&self) -> Option<GithubAppConfig> {
+        None
+    }
+
+    /// Restrict connections to IPv4 or IPv6 addresses only.
+    fn ip_family(&self) -> Option<IpFamily> {
+        None
+    }
+
+    /// Provider of the domain when it cannot be told from the domain name.
+    fn provider(&self) -> Option<Provider> {
+        None
+    }
+
+    /// Compression applied to responses persisted in the file cache.
+    fn cache_compression(&self) -> CacheCompression {
+        CacheCompression::default()
+    }
+
+    /// IP address to connect to for a given host instead of resolving it
+    /// through DNS.
+    fn dns_override(&self, _host: &str) -> Option<IpAddr> {
+        None
+    }
+}
+
+/// Remote software serving a domain. Detected from the domain name, ex.
+/// gitlab.com, unless configured for self-hosted instances under arbitrary
+/// domains:
+///
+/// ```toml
+/// [git_mycompany_com]
+/// provider = "gitlab"
+/// ```
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    Github,
+    Gitlab,
+    Bitbucket,
+}
+
+/// Compression of the responses stored in the file cache. Entries are read
+/// back regardless of the compression they were written with, so the setting
+/// can be switched without clearing the cache.
+///
+/// ```toml
+/// [gitlab_com]
+/// cache_compression = "none"
+/// ```
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheCompression {
+    #[default]
+    Gzip,
+    None,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IpFamily {
+    Ipv4,
+    Ipv6,
 }
 
 /// The NoConfig struct is used when no configuration is found and it can be
@@ -43,6 +197,10 @@ pub trait ConfigProperties: Send + Sync {
 /// one-off scenarios.
 pub struct NoConfig {
     api_token: String,
+    sudo: Option<String>,
+    force_circuit: bool,
+    strict_contract: bool,
+    ci_job_token: bool,
 }
 
 impl NoConfig {
@@ -54,7 +212,34 @@ impl NoConfig {
                 env_var(domain)
             ))
         })?;
-        Ok(NoConfig { api_token })
+        Ok(NoConfig {
+            api_token,
+            sudo: None,
+            force_circuit: false,
+            strict_contract: false,
+            ci_job_token: false,
+        })
+    }
+
+    pub fn with_sudo(mut self, username: Option<String>) -> Self {
+        self.sudo = username;
+        self
+    }
+
+    pub fn with_force_circuit(mut self, force: bool) -> Self {
+        self.force_circuit = force;
+        self
+    }
+
+    pub fn with_strict_contract(mut self, strict: bool) -> Self {
+        self.strict_contract = strict;
+        self
+    }
+
+    /// Authenticates with the job token if it is the token in use.
+    pub fn with_ci_job_token(mut self, job_token: Option<&str>) -> Self {
+        self.ci_job_token = job_token == Some(self.api_token.as_str());
+        self
     }
 }
 
@@ -66,6 +251,25 @@ impl ConfigProperties for NoConfig {
     fn cache_location(&self) -> Option<&str> {
         None
     }
+
+    fn sudo_username(&self) -> Option<&str> {
+        self.sudo.as_deref()
+    }
+
+    fn force_circuit(&self) -> bool {
+        self.force_circuit
+    }
+
+    fn strict_contract(&self) -> bool {
+        self.strict_contract
+    }
+
+    fn auth_method(&self) -> AuthMethod {
+        if self.ci_job_token {
+            return AuthMethod::JobToken;
+        }
+        AuthMethod::default()
+    }
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -80,6 +284,28 @@ struct MaxPagesApi {
     settings: HashMap<ApiOperation, u32>,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+struct RetryBudgetApi {
+    #[serde(flatten)]
+    settings: HashMap<ApiOperation, RetryBudget>,
+}
+
+/// Backoff settings of an API operation, ex. retrying pipeline requests more
+/// aggressively than write operations:
+///
+/// ```toml
+/// [gitlab_com.backoff.pipeline]
+/// max_retries = 5
+/// retry_after = 30
+/// ```
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RetryBudget {
+    pub max_retries: u32,
+    /// Base wait in seconds before retrying. Defaults to --retry-after.
+    #[serde(default)]
+    pub retry_after: Option<u64>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(untagged)]
 enum UserInfo {
@@ -104,6 +330,9 @@ struct MergeRequestConfig {
     preferred_assignee_username: Option<UserInfo>,
     members: Option<Vec<UserInfo>>,
     description_signature: Option<String>,
+    review_reminder: Option<String>,
+    rules: Option<Vec<OwnershipRule>>,
+    target_branch: Option<String>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -113,12 +342,22 @@ struct ProjectConfig {
 
 #[derive(Deserialize, Clone, Debug, Default)]
 pub struct DomainConfig {
+    provider: Option<Provider>,
     api_token: Option<String>,
+    auth: Option<AuthMethod>,
+    github_app: Option<GithubAppConfig>,
+    api_base_url: Option<String>,
+    ip_family: Option<IpFamily>,
+    dns_overrides: Option<HashMap<String, IpAddr>>,
     cache_location: Option<String>,
+    cache_compression: Option<CacheCompression>,
     merge_requests: Option<MergeRequestConfig>,
     rate_limit_remaining_threshold: Option<u32>,
+    max_response_body_mb: Option<u64>,
+    per_page: Option<u32>,
     cache_expirations: Option<ApiSettings>,
     max_pages_api: Option<MaxPagesApi>,
+    backoff: Option<RetryBudgetApi>,
     #[serde(flatten)]
     projects: HashMap<String, ProjectConfig>,
 }
@@ -134,6 +373,22 @@ pub struct ConfigFile {
     inner: ConfigFileInner,
     domain_key: String,
     project_path_key: String,
+    sudo: Option<String>,
+    force_circuit: bool,
+    strict_contract: bool,
+    ci_job_token: bool,
+}
+
+/// Key of the project specific configuration. Projects configured under the
+/// legacy key, ex. `[gitlab_com.jordilin_gitar]`, keep working, but a/b_c and
+/// a_b/c share it. The config encoded project path, ex.
+/// `[gitlab_com."jordilin%2Fgitar"]`, is unambiguous and takes precedence.
+fn project_path_key<'a>(domain_config: &DomainConfig, url: &'a RemoteURL) -> &'a str {
+    let project_path_key = url.config_encoded_project_path();
+    if domain_config.projects.contains_key(project_path_key) {
+        return project_path_key;
+    }
+    url.legacy_config_encoded_project_path()
 }
 
 pub fn env_token(domain: &str) -> Result<String> {
@@ -141,6 +396,74 @@ pub fn env_token(domain: &str) -> Result<String> {
     Ok(std::env::var(format!("{}_API_TOKEN", env_domain))?)
 }
 
+/// Entries of a top level table, ex. `[alias]`, gathered from several config
+/// files. Entries in later files override earlier ones. Missing files are
+/// skipped.
+pub fn read_table<T: DeserializeOwned, P: AsRef<Path>>(
+    files: &[P],
+    table: &str,
+) -> Result<HashMap<String, T>> {
+    let mut entries = HashMap::new();
+    for file in files {
+        let Ok(data) = fs::read_to_string(file) else {
+            continue;
+        };
+        let mut config: toml::Table = toml::from_str(&data).map_err(|err| {
+            GRError::ConfigurationError(format!("{}: {}", file.as_ref().display(), err))
+        })?;
+        if let Some(value) = config.remove(table) {
+            let file_entries: HashMap<String, T> = value.try_into().map_err(|err| {
+                GRError::ConfigurationError(format!(
+                    "Invalid [{}] table in {}: {}",
+                    table,
+                    file.as_ref().display(),
+                    err
+                ))
+            })?;
+            entries.extend(file_entries);
+        }
+    }
+    Ok(entries)
+}
+
+/// Domains configured in the config files of a config directory, ex.
+/// `gitlab.com` for a `[gitlab_com]` table. Sorted alphabetically.
+pub fn configured_domains<P: AsRef<Path>>(config_dir: P) -> Result<Vec<String>> {
+    let mut domains = Vec::new();
+    let entries = fs::read_dir(config_dir.as_ref()).map_err(|err| {
+        GRError::ConfigurationError(format!("{}: {}", config_dir.as_ref().display(), err))
+    })?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+            continue;
+        }
+        let data = fs::read_to_string(&path)?;
+        let config: toml::Table = toml::from_str(&data)
+            .map_err(|err| GRError::ConfigurationError(format!("{}: {}", path.display(), err)))?;
+        for (key, value) in config {
+            if COMMAND_TABLES.contains(&key.as_str()) || !value.is_table() {
+                continue;
+            }
+            let domain = key.replace('_', ".");
+            if !domains.contains(&domain) {
+                domains.push(domain);
+            }
+        }
+    }
+    domains.sort();
+    Ok(domains)
+}
+
+/// CI/CD job token when running inside Gitlab CI. It is only returned for
+/// the Gitlab instance running the job, so it is never sent to other remotes.
+pub fn ci_job_token<FV: Fn(&str) -> Option<String>>(domain: &str, var: FV) -> Option<String> {
+    if var("CI_SERVER_HOST")? != domain {
+        return None;
+    }
+    var("CI_JOB_TOKEN").filter(|token| !token.is_empty())
+}
+
 fn env_var(domain: &str) -> String {
     let domain_fields = domain.split('.').collect::<Vec<&str>>();
     let env_domain = if domain_fields.len() == 1 {
@@ -153,6 +476,28 @@ fn env_var(domain: &str) -> String {
 }
 
 impl ConfigFile {
+    pub fn with_sudo(mut self, username: Option<String>) -> Self {
+        self.sudo = username;
+        self
+    }
+
+    pub fn with_force_circuit(mut self, force: bool) -> Self {
+        self.force_circuit = force;
+        self
+    }
+
+    pub fn with_strict_contract(mut self, strict: bool) -> Self {
+        self.strict_contract = strict;
+        self
+    }
+
+    /// Authenticates with the job token if it is the token in use, that is,
+    /// no API token is configured for the domain.
+    pub fn with_ci_job_token(mut self, job_token: Option<&str>) -> Self {
+        self.ci_job_token = job_token.is_some_and(|token| token == self.api_token());
+        self
+    }
+
     // TODO: make use of a BufReader instead
     /// Reads the configuration file and returns a ConfigFile struct that holds
     /// the configuration data for a given domain and project path.
@@ -171,8 +516,11 @@ impl ConfigFile {
         for mut reader in readers.into_iter() {
             reader.read_to_string(&mut config_data)?;
         }
-        let mut config: ConfigFileInner = toml::from_str(&config_data)?;
-        let project_path_key = url.config_encoded_project_path();
+        let mut table: toml::Table = toml::from_str(&config_data)?;
+        for command_table in COMMAND_TABLES {
+            table.remove(command_table);
+        }
+        let mut config: ConfigFileInner = table.try_into()?;
         let domain = url.domain();
         // ENV VAR API token takes preference. For a given domain, we try to fetch
         // <DOMAIN>_API_TOKEN env var first, then we fallback to the config
@@ -182,7 +530,16 @@ impl ConfigFile {
 
         let domain_key = url.config_encoded_domain();
         if let Some(domain_config) = config.domains.get_mut(domain_key) {
-            if domain_config.api_token.is_none() {
+            let project_path_key = project_path_key(domain_config, url);
+            if domain_config.auth == Some(AuthMethod::GithubApp) {
+                if domain_config.github_app.is_none() {
+                    return Err(GRError::PreconditionNotMet(format!(
+                        "Github App auth requires a github_app section for domain {}",
+                        domain
+                    ))
+                    .into());
+                }
+            } else if domain_config.api_token.is_none() {
                 domain_config.api_token = Some(env(domain).map_err(|_| {
                     GRError::PreconditionNotMet(format!(
                         "No api_token found for domain {} in config or environment variable",
@@ -194,6 +551,10 @@ impl ConfigFile {
                 inner: config,
                 domain_key: domain_key.to_string(),
                 project_path_key: project_path_key.to_string(),
+                sudo: None,
+                force_circuit: false,
+                strict_contract: false,
+                ci_job_token: false,
             })
         } else {
             Err(error::gen(format!(
@@ -380,6 +741,71 @@ impl ConfigProperties for ConfigFile {
         }
     }
 
+    fn merge_request_review_reminder(&self) -> &str {
+        if let Some(domain_config) = &self.inner.domains.get(&self.domain_key) {
+            domain_config
+                .projects
+                .get(&self.project_path_key)
+                .and_then(|project_config| {
+                    project_config
+                        .merge_requests
+                        .as_ref()
+                        .and_then(|merge_request_config| {
+                            merge_request_config.review_reminder.as_deref()
+                        })
+                })
+                .unwrap_or_else(|| {
+                    domain_config
+                        .merge_requests
+                        .as_ref()
+                        .and_then(|merge_request_config| {
+                            merge_request_config.review_reminder.as_deref()
+                        })
+                        .unwrap_or_default()
+                })
+        } else {
+            ""
+        }
+    }
+
+    fn merge_request_rules(&self) -> Vec<OwnershipRule> {
+        if let Some(domain_config) = &self.inner.domains.get(&self.domain_key) {
+            domain_config
+                .projects
+                .get(&self.project_path_key)
+                .and_then(|project_config| {
+                    project_config
+                        .merge_requests
+                        .as_ref()
+                        .and_then(|merge_request_config| merge_request_config.rules.clone())
+                })
+                .or_else(|| {
+                    domain_config
+                        .merge_requests
+                        .as_ref()
+                        .and_then(|merge_request_config| merge_request_config.rules.clone())
+                })
+                .unwrap_or_default()
+        } else {
+            vec![]
+        }
+    }
+
+    fn merge_request_target_branch(&self) -> Option<&str> {
+        let domain_config = self.inner.domains.get(&self.domain_key)?;
+        domain_config
+            .projects
+            .get(&self.project_path_key)
+            .and_then(|project_config| project_config.merge_requests.as_ref())
+            .and_then(|merge_request_config| merge_request_config.target_branch.as_deref())
+            .or_else(|| {
+                domain_config
+                    .merge_requests
+                    .as_ref()
+                    .and_then(|merge_request_config| merge_request_config.target_branch.as_deref())
+            })
+    }
+
     fn get_cache_expiration(&self, api_operation: &ApiOperation) -> &str {
         self.inner
             .domains
@@ -391,7 +817,10 @@ impl ConfigProperties for ConfigFile {
                     .and_then(|cache_expirations| cache_expirations.settings.get(api_operation))
             })
             .map(|s| s.as_str())
-            .unwrap_or_else(|| EXPIRE_IMMEDIATELY)
+            .unwrap_or_else(|| match api_operation {
+                ApiOperation::User => USER_CACHE_EXPIRATION,
+                _ => EXPIRE_IMMEDIATELY,
+            })
     }
 
     fn get_max_pages(&self, api_operation: &ApiOperation) -> u32 {
@@ -408,6 +837,15 @@ impl ConfigProperties for ConfigFile {
             .unwrap_or(REST_API_MAX_PAGES)
     }
 
+    fn get_retry_budget(&self, api_operation: &ApiOperation) -> Option<RetryBudget> {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.backoff.as_ref())
+            .and_then(|backoff| backoff.settings.get(api_operation))
+            .copied()
+    }
+
     fn rate_limit_remaining_threshold(&self) -> u32 {
         self.inner
             .domains
@@ -415,6 +853,93 @@ impl ConfigProperties for ConfigFile {
             .and_then(|domain_config| domain_config.rate_limit_remaining_threshold)
             .unwrap_or(RATE_LIMIT_REMAINING_THRESHOLD)
     }
+
+    fn max_response_body_size(&self) -> u64 {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.max_response_body_mb)
+            .map(|megabytes| megabytes.saturating_mul(1024 * 1024))
+            .unwrap_or(MAX_RESPONSE_BODY_SIZE)
+    }
+
+    fn per_page(&self) -> u32 {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.per_page)
+            .map(|per_page| per_page.clamp(1, MAX_PER_PAGE))
+            .unwrap_or(MAX_PER_PAGE)
+    }
+
+    fn sudo_username(&self) -> Option<&str> {
+        self.sudo.as_deref()
+    }
+
+    fn force_circuit(&self) -> bool {
+        self.force_circuit
+    }
+
+    fn strict_contract(&self) -> bool {
+        self.strict_contract
+    }
+
+    fn api_base_url(&self) -> Option<&str> {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.api_base_url.as_deref())
+            .map(|url| url.trim_end_matches('/'))
+    }
+
+    fn auth_method(&self) -> AuthMethod {
+        if self.ci_job_token {
+            return AuthMethod::JobToken;
+        }
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.auth)
+            .unwrap_or_default()
+    }
+
+    fn github_app(&self) -> Option<GithubAppConfig> {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.github_app.clone())
+    }
+
+    fn ip_family(&self) -> Option<IpFamily> {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.ip_family)
+    }
+
+    fn provider(&self) -> Option<Provider> {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.provider)
+    }
+
+    fn cache_compression(&self) -> CacheCompression {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.cache_compression)
+            .unwrap_or_default()
+    }
+
+    fn dns_override(&self, host: &str) -> Option<IpAddr> {
+        self.inner
+            .domains
+            .get(&self.domain_key)
+            .and_then(|domain_config| domain_config.dns_overrides.as_ref())
+            .and_then(|overrides| overrides.get(host))
+            .copied()
+    }
 }
 
 impl ConfigProperties for Arc<ConfigFile> {
@@ -434,6 +959,18 @@ impl ConfigProperties for Arc<ConfigFile> {
         self.as_ref().merge_request_description_signature()
     }
 
+    fn merge_request_review_reminder(&self) -> &str {
+        self.as_ref().merge_request_review_reminder()
+    }
+
+    fn merge_request_rules(&self) -> Vec<OwnershipRule> {
+        self.as_ref().merge_request_rules()
+    }
+
+    fn merge_request_target_branch(&self) -> Option<&str> {
+        self.as_ref().merge_request_target_branch()
+    }
+
     fn get_cache_expiration(&self, api_operation: &ApiOperation) -> &str {
         self.as_ref().get_cache_expiration(api_operation)
     }
@@ -442,13 +979,65 @@ impl ConfigProperties for Arc<ConfigFile> {
         self.as_ref().get_max_pages(api_operation)
     }
 
+    fn get_retry_budget(&self, api_operation: &ApiOperation) -> Option<RetryBudget> {
+        self.as_ref().get_retry_budget(api_operation)
+    }
+
     fn rate_limit_remaining_threshold(&self) -> u32 {
         self.as_ref().rate_limit_remaining_threshold()
     }
 
+    fn max_response_body_size(&self) -> u64 {
+        self.as_ref().max_response_body_size()
+    }
+
+    fn per_page(&self) -> u32 {
+        self.as_ref().per_page()
+    }
+
     fn merge_request_members(&self) -> Vec<Member> {
         self.as_ref().merge_request_members()
     }
+
+    fn sudo_username(&self) -> Option<&str> {
+        self.as_ref().sudo_username()
+    }
+
+    fn force_circuit(&self) -> bool {
+        self.as_ref().force_circuit()
+    }
+
+    fn strict_contract(&self) -> bool {
+        self.as_ref().strict_contract()
+    }
+
+    fn api_base_url(&self) -> Option<&str> {
+        self.as_ref().api_base_url()
+    }
+
+    fn auth_method(&self) -> AuthMethod {
+        self.as_ref().auth_method()
+    }
+
+    fn github_app(&self) -> Option<GithubAppConfig> {
+        self.as_ref().github_app()
+    }
+
+    fn ip_family(&self) -> Option<IpFamily> {
+        self.as_ref().ip_family()
+    }
+
+    fn provider(&self) -> Option<Provider> {
+        self.as_ref().provider()
+    }
+
+    fn cache_compression(&self) -> CacheCompression {
+        self.as_ref().cache_compression()
+    }
+
+    fn dns_override(&self, host: &str) -> Option<IpAddr> {
+        self.as_ref().dns_override(host)
+    }
 }
 
 #[cfg(test)]
@@ -563,10 +1152,11 @@ mod test {
         let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
         for api_operation in ApiOperation::iter() {
             assert_eq!(REST_API_MAX_PAGES, config.get_max_pages(&api_operation));
-            assert_eq!(
-                EXPIRE_IMMEDIATELY,
-                config.get_cache_expiration(&api_operation)
-            );
+            let expiration = match api_operation {
+                ApiOperation::User => USER_CACHE_EXPIRATION,
+                _ => EXPIRE_IMMEDIATELY,
+            };
+            assert_eq!(expiration, config.get_cache_expiration(&api_operation));
         }
         assert_eq!(
             RATE_LIMIT_REMAINING_THRESHOLD,
@@ -615,6 +1205,274 @@ mod test {
         assert_eq!(1234, members[0].id);
     }
 
+    #[test]
+    fn test_config_target_branch_project_overrides_domain() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+
+        [gitlab_com.merge_requests]
+        target_branch = "develop"
+
+        [gitlab_com.datateam_projecta.merge_requests]
+        target_branch = "release""#;
+
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "datateam/projecta".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(Some("release"), config.merge_request_target_branch());
+
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "datateam/projectb".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(Some("develop"), config.merge_request_target_branch());
+
+        let reader = vec![std::io::Cursor::new("[github_com]\napi_token = '1234'")];
+        let url = RemoteURL::new("github.com".to_string(), "jordilin/gitar".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(None, config.merge_request_target_branch());
+    }
+
+    #[test]
+    fn test_config_review_reminder_project_overrides_domain() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+
+        [gitlab_com.merge_requests]
+        review_reminder = "Awaiting review for {age}"
+
+        [gitlab_com.datateam_projecta.merge_requests]
+        review_reminder = "@{author} ping, {age} old""#;
+
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "datateam/projecta".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(
+            "@{author} ping, {age} old",
+            config.merge_request_review_reminder()
+        );
+
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "datateam/projectb".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(
+            "Awaiting review for {age}",
+            config.merge_request_review_reminder()
+        );
+    }
+
+    #[test]
+    fn test_config_merge_request_rules_project_overrides_domain() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+
+        [gitlab_com.merge_requests]
+        rules = [
+            { paths = ["src/cache/"], reviewers = ["jdoe"], labels = ["cache"] },
+            { paths = ["*.md"], labels = ["docs"] },
+        ]
+
+        [gitlab_com.datateam_projecta.merge_requests]
+        rules = [ { paths = ["models/"], reviewers = ["jane"] } ]"#;
+
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "datateam/projecta".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        let rules = config.merge_request_rules();
+        assert_eq!(1, rules.len());
+        assert_eq!(vec!["jane"], rules[0].reviewers);
+        assert!(rules[0].labels.is_empty());
+
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "datateam/projectb".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        let rules = config.merge_request_rules();
+        assert_eq!(2, rules.len());
+        assert_eq!(vec!["docs"], rules[1].labels);
+    }
+
+    #[test]
+    fn test_config_api_base_url() {
+        let config_data = r#"
+        [gitlab_tools_corp]
+        api_token = '1234'
+        api_base_url = "https://tools.corp/gitlab/api/v4/"
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new(
+            "gitlab.tools.corp".to_string(),
+            "jordilin/gitar".to_string(),
+        );
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(
+            Some("https://tools.corp/gitlab/api/v4"),
+            config.api_base_url()
+        );
+    }
+
+    #[test]
+    fn test_config_ip_family_and_dns_overrides() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+        ip_family = "ipv4"
+
+        [gitlab_com.dns_overrides]
+        "gitlab.com" = "10.0.0.5"
+        "registry.gitlab.com" = "fd00::5"
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "jordilin/gitar".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(Some(IpFamily::Ipv4), config.ip_family());
+        assert_eq!(
+            Some("10.0.0.5".parse::<IpAddr>().unwrap()),
+            config.dns_override("gitlab.com")
+        );
+        assert_eq!(
+            Some("fd00::5".parse::<IpAddr>().unwrap()),
+            config.dns_override("registry.gitlab.com")
+        );
+        assert_eq!(None, config.dns_override("github.com"));
+    }
+
+    #[test]
+    fn test_config_provider_of_custom_domain() {
+        let config_data = r#"
+        [git_mycompany_com]
+        api_token = '1234'
+        provider = "gitlab"
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new(
+            "git.mycompany.com".to_string(),
+            "jordilin/gitar".to_string(),
+        );
+        let config = ConfigFile::new(reader, &url, no_env).unwrap();
+        assert_eq!(Some(Provider::Gitlab), config.provider());
+    }
+
+    #[test]
+    fn test_config_cache_compression() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+        cache_compression = "none"
+
+        [github_com]
+        api_token = '1234'
+        "#;
+        let url = RemoteURL::new("gitlab.com".to_string(), "jordilin/gitar".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = ConfigFile::new(reader, &url, no_env).unwrap();
+        assert_eq!(CacheCompression::None, config.cache_compression());
+        let url = RemoteURL::new("github.com".to_string(), "jordilin/gitar".to_string());
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let config = ConfigFile::new(reader, &url, no_env).unwrap();
+        assert_eq!(CacheCompression::Gzip, config.cache_compression());
+    }
+
+    #[test]
+    fn test_config_retry_budget_per_api_operation() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+
+        [gitlab_com.backoff.pipeline]
+        max_retries = 5
+        retry_after = 30
+
+        [gitlab_com.backoff.merge_request]
+        max_retries = 1
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "jordilin/gitar".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(
+            Some(RetryBudget {
+                max_retries: 5,
+                retry_after: Some(30)
+            }),
+            config.get_retry_budget(&ApiOperation::Pipeline)
+        );
+        assert_eq!(
+            Some(RetryBudget {
+                max_retries: 1,
+                retry_after: None
+            }),
+            config.get_retry_budget(&ApiOperation::MergeRequest)
+        );
+        assert_eq!(None, config.get_retry_budget(&ApiOperation::Project));
+    }
+
+    #[test]
+    fn test_config_max_response_body_size() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+        max_response_body_mb = 50
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "jordilin/gitar".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(50 * 1024 * 1024, config.max_response_body_size());
+    }
+
+    #[test]
+    fn test_config_max_response_body_size_does_not_overflow() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+        max_response_body_mb = 9223372036854775807
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "jordilin/gitar".to_string());
+        let config = Arc::new(ConfigFile::new(reader, &url, no_env).unwrap());
+        assert_eq!(u64::MAX, config.max_response_body_size());
+    }
+
+    #[test]
+    fn test_config_per_page() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+        per_page = 50
+
+        [github_com]
+        api_token = '1234'
+        per_page = 500
+
+        [gitlab_local]
+        api_token = '1234'
+        "#;
+        let url = RemoteURL::new("gitlab.com".to_string(), "jordilin/gitar".to_string());
+        let config =
+            ConfigFile::new(vec![std::io::Cursor::new(config_data)], &url, no_env).unwrap();
+        assert_eq!(50, config.per_page());
+        let url = RemoteURL::new("github.com".to_string(), "jordilin/gitar".to_string());
+        let config =
+            ConfigFile::new(vec![std::io::Cursor::new(config_data)], &url, no_env).unwrap();
+        assert_eq!(MAX_PER_PAGE, config.per_page());
+        let url = RemoteURL::new("gitlab.local".to_string(), "jordilin/gitar".to_string());
+        let config =
+            ConfigFile::new(vec![std::io::Cursor::new(config_data)], &url, no_env).unwrap();
+        assert_eq!(MAX_PER_PAGE, config.per_page());
+    }
+
+    #[test]
+    fn test_config_api_base_url_defaults_to_none() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "jordilin/gitar".to_string());
+        let config = ConfigFile::new(reader, &url, no_env).unwrap();
+        assert_eq!(None, config.api_base_url());
+    }
+
     #[test]
     fn test_config_with_overridden_project_specific_settings_multiple_readers() {
         let config_data = r#"
@@ -657,6 +1515,35 @@ mod test {
         assert_eq!(1234, members[0].id);
     }
 
+    #[test]
+    fn test_config_encoded_project_path_takes_precedence_over_legacy() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+
+        [gitlab_com.a_b_c.merge_requests]
+        preferred_assignee_username = 'legacy'
+
+        [gitlab_com."a%2Fb_c".merge_requests]
+        preferred_assignee_username = 'jdoe'
+        "#;
+        let url = RemoteURL::new("gitlab.com".to_string(), "a/b_c".to_string());
+        let config =
+            ConfigFile::new(vec![std::io::Cursor::new(config_data)], &url, no_env).unwrap();
+        assert_eq!(
+            "jdoe",
+            config.preferred_assignee_username().unwrap().username
+        );
+        // Projects without an unambiguous key fall back to the legacy one.
+        let url = RemoteURL::new("gitlab.com".to_string(), "a_b/c".to_string());
+        let config =
+            ConfigFile::new(vec![std::io::Cursor::new(config_data)], &url, no_env).unwrap();
+        assert_eq!(
+            "legacy",
+            config.preferred_assignee_username().unwrap().username
+        );
+    }
+
     #[test]
     fn test_config_multiple_readers_same_headers_is_error() {
         let config_data = r#"
@@ -725,6 +1612,124 @@ mod test {
         assert!(ConfigFile::new(reader, &url, no_env).is_err());
     }
 
+    #[test]
+    fn test_github_app_auth_does_not_require_api_token() {
+        let config_data = r#"
+        [github_com]
+        auth = "github_app"
+
+        [github_com.github_app]
+        app_id = 1234
+        private_key_path = "/home/user/gitar-app.pem"
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("github.com".to_string(), "/jordilin/gitar".to_string());
+        let config = ConfigFile::new(reader, &url, no_env).unwrap();
+        assert_eq!(AuthMethod::GithubApp, config.auth_method());
+        let app = config.github_app().unwrap();
+        assert_eq!(1234, app.app_id);
+        assert_eq!("/home/user/gitar-app.pem", app.private_key_path);
+        assert_eq!(None, app.installation_id);
+    }
+
+    #[test]
+    fn test_github_app_auth_without_app_is_err() {
+        let config_data = r#"
+        [github_com]
+        auth = "github_app"
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("github.com".to_string(), "/jordilin/gitar".to_string());
+        assert!(ConfigFile::new(reader, &url, env).is_err());
+    }
+
+    #[test]
+    fn test_config_with_aliases() {
+        let config_data = r#"
+        [alias]
+        prs = "mr list --state opened"
+
+        [gitlab_com]
+        api_token = '1234'
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "/jordilin/gitar".to_string());
+        let config = ConfigFile::new(reader, &url, no_env).unwrap();
+        assert_eq!("1234", config.api_token());
+    }
+
+    #[test]
+    fn test_config_with_repo_sets() {
+        let config_data = r#"
+        [repos.watchlist]
+        repos = ["github.com/jordilin/gitar", "gitlab.com/jordilin/gitlapi"]
+
+        [gitlab_com]
+        api_token = '1234'
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "/jordilin/gitar".to_string());
+        let config = ConfigFile::new(reader, &url, no_env).unwrap();
+        assert_eq!("1234", config.api_token());
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("gitar.toml");
+        fs::write(&file, config_data).unwrap();
+        let repo_sets: HashMap<String, RepoSet> = read_table(&[file], REPOS_TABLE).unwrap();
+        assert_eq!(
+            vec!["github.com/jordilin/gitar", "gitlab.com/jordilin/gitlapi"],
+            repo_sets["watchlist"].repos
+        );
+    }
+
+    #[test]
+    fn test_read_table_later_files_take_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let global = dir.path().join("gitar.toml");
+        fs::write(
+            &global,
+            "[alias]\nprs = \"mr list\"\nmine = \"my mr\"\n\n[gitlab_com]\napi_token = '1234'\n",
+        )
+        .unwrap();
+        let repo = dir.path().join(".gitar.toml");
+        fs::write(&repo, "[alias]\nprs = \"mr list opened\"\n").unwrap();
+        let aliases: HashMap<String, String> = read_table(
+            &[global, repo, dir.path().join("missing.toml")],
+            ALIAS_TABLE,
+        )
+        .unwrap();
+        assert_eq!("mr list opened", aliases["prs"]);
+        assert_eq!("my mr", aliases["mine"]);
+    }
+
+    #[test]
+    fn test_read_table_invalid_entries_is_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("gitar.toml");
+        fs::write(&file, "[alias]\nprs = 1\n").unwrap();
+        assert!(read_table::<String, _>(&[file], ALIAS_TABLE).is_err());
+    }
+
+    #[test]
+    fn test_configured_domains() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("gitar.toml"),
+            "[alias]\nprs = \"mr list\"\n\n[repos.team]\nrepos = []\n\n\
+             [github_com]\napi_token = '1234'\n\n[gitlab_company_com]\napi_token = '5678'\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("gitlab_com.toml"),
+            "[gitlab_com]\napi_token = '1234'\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "[bitbucket_org]\n").unwrap();
+        assert_eq!(
+            vec!["github.com", "gitlab.com", "gitlab.company.com"],
+            configured_domains(dir.path()).unwrap()
+        );
+    }
+
     #[test]
     fn test_config_no_data() {
         let config_data = "";
@@ -801,6 +1806,50 @@ mod test {
         }
     }
 
+    fn gitlab_ci_var(name: &str) -> Option<String> {
+        match name {
+            "CI_SERVER_HOST" => Some("gitlab.com".to_string()),
+            "CI_JOB_TOKEN" => Some("job-1234".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_ci_job_token_only_for_the_ci_server() {
+        assert_eq!(
+            Some("job-1234".to_string()),
+            ci_job_token("gitlab.com", gitlab_ci_var)
+        );
+        assert_eq!(None, ci_job_token("github.com", gitlab_ci_var));
+        assert_eq!(None, ci_job_token("gitlab.com", |_| None));
+    }
+
+    #[test]
+    fn test_no_config_uses_ci_job_token() {
+        let job_token = ci_job_token("gitlab.com", gitlab_ci_var);
+        let env = |_: &str| job_token.clone().ok_or(error::gen("No env var"));
+        let config = NoConfig::new("gitlab.com", env)
+            .unwrap()
+            .with_ci_job_token(job_token.as_deref());
+        assert_eq!("job-1234", config.api_token());
+        assert_eq!(AuthMethod::JobToken, config.auth_method());
+    }
+
+    #[test]
+    fn test_configured_api_token_takes_precedence_over_ci_job_token() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new("gitlab.com".to_string(), "/jordilin/gitar".to_string());
+        let config = ConfigFile::new(reader, &url, no_env)
+            .unwrap()
+            .with_ci_job_token(Some("job-1234"));
+        assert_eq!("1234", config.api_token());
+        assert_eq!(AuthMethod::Token, config.auth_method());
+    }
+
     #[test]
     fn test_default_config_file() {
         // This is the case when browsing and no configuration is needed.
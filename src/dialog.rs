@@ -7,9 +7,10 @@ use dialoguer::Confirm;
 use dialoguer::Editor;
 use dialoguer::FuzzySelect;
 use dialoguer::Input;
+use dialoguer::MultiSelect;
 
 use crate::cmds::merge_request::MergeRequestBodyArgs;
-use crate::cmds::project::Member;
+use crate::cmds::project::{Member, MrMemberType};
 use crate::config::ConfigProperties;
 use crate::error;
 use crate::Result;
@@ -20,7 +21,7 @@ pub struct MergeRequestUserInput {
     pub description: String,
     pub assignee: Member,
     #[builder(default)]
-    pub reviewer: Member,
+    pub reviewers: Vec<Member>,
 }
 
 impl MergeRequestUserInput {
@@ -37,7 +38,7 @@ impl MergeRequestUserInput {
                 .username(username.to_string())
                 .build()
                 .unwrap(),
-            reviewer: Member::default(),
+            reviewers: vec![],
         }
     }
 }
@@ -51,6 +52,18 @@ impl MemberSelector {
         Self { members }
     }
 
+    /// Moves the suggested usernames to the front of the members list, so
+    /// they show up first when selecting a reviewer.
+    pub fn prioritize(mut self, usernames: &[String]) -> Self {
+        self.members.sort_by_key(|member| {
+            usernames
+                .iter()
+                .position(|username| username == &member.username)
+                .unwrap_or(usernames.len())
+        });
+        self
+    }
+
     /// Determines the assignee based on priority:
     /// 1. CLI provided assignee (if present)
     /// 2. Config preferred assignee (if present)
@@ -89,8 +102,8 @@ impl MemberSelector {
         default_cli_reviewer: Option<&Member>,
         assigned_member: &Member,
     ) -> Vec<Member> {
-        let mut selection_list = if default_cli_reviewer.is_some() {
-            vec![default_cli_reviewer.unwrap().clone(), Member::default()]
+        let mut selection_list = if let Some(default_cli_reviewer) = default_cli_reviewer {
+            vec![default_cli_reviewer.clone(), Member::default()]
         } else {
             vec![Member::default()]
         };
@@ -109,13 +122,15 @@ pub fn prompt_user_merge_request_info(
     default_title: &str,
     default_description: &str,
     default_cli_assignee: Option<&Member>,
-    default_cli_reviewer: Option<&Member>,
+    default_cli_reviewers: &[Member],
+    suggested_reviewers: &[String],
     config: &Arc<dyn ConfigProperties>,
 ) -> Result<MergeRequestUserInput> {
     let (title, description) = prompt_user_title_description(default_title, default_description);
 
-    // Initialize member selector with available members
-    let selector = MemberSelector::new(config.merge_request_members());
+    // Initialize member selector with available members, code owners first
+    let selector =
+        MemberSelector::new(config.merge_request_members()).prioritize(suggested_reviewers);
 
     // Prepare assignee selection list with priorities
     let assignee_list =
@@ -125,15 +140,38 @@ pub fn prompt_user_merge_request_info(
     let assignee_index = gather_member(&assignee_list, "Assignee:");
     let assigned_member = assignee_list[assignee_index].clone();
 
+    if !suggested_reviewers.is_empty() {
+        show_input(
+            "Suggested reviewers",
+            &suggested_reviewers.join(", "),
+            false,
+            Style::Bold,
+        );
+    }
+
     // Prepare reviewer list excluding the selected assignee
-    let reviewer_list = selector.prepare_reviewer_list(default_cli_reviewer, &assigned_member);
+    let reviewer_list =
+        selector.prepare_reviewer_list(default_cli_reviewers.first(), &assigned_member);
     let reviewer_index = gather_member(&reviewer_list, "Reviewer:");
+    // The selection replaces the first reviewer given in the command line.
+    // The rest of them are kept.
+    let selected_reviewer = reviewer_list[reviewer_index].clone();
+    let reviewers = std::iter::once(selected_reviewer.clone())
+        .filter(|reviewer| reviewer.mr_member_type == MrMemberType::Filled)
+        .chain(
+            default_cli_reviewers
+                .iter()
+                .skip(1)
+                .filter(|reviewer| **reviewer != selected_reviewer)
+                .cloned(),
+        )
+        .collect::<Vec<_>>();
 
     Ok(MergeRequestUserInput::builder()
         .title(title)
         .description(description)
         .assignee(assigned_member)
-        .reviewer(reviewer_list[reviewer_index].clone())
+        .reviewers(reviewers)
         .build()
         .unwrap())
 }
@@ -219,6 +257,22 @@ fn confirm(prompt: &str, default_answer: bool) -> bool {
     !default_answer
 }
 
+/// Asks the user to confirm an operation that changes remote state, such as
+/// stopping an environment. Defaults to no. Skipped if `accept` is set.
+pub fn confirm_action(prompt: &str, accept: bool) -> Result<()> {
+    if accept
+        || Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .default(false)
+            .interact()
+            .unwrap()
+    {
+        Ok(())
+    } else {
+        Err(error::gen("User cancelled"))
+    }
+}
+
 pub fn show_summary_merge_request(
     commit_str: &str,
     args: &MergeRequestBodyArgs,
@@ -227,7 +281,15 @@ pub fn show_summary_merge_request(
     show_outgoing_changes_summary(commit_str);
     show_input("Target branch", &args.target_branch, false, Style::Bold);
     show_input("Assignee", &args.assignee.username, false, Style::Bold);
-    show_input("Reviewer", &args.reviewer.username, false, Style::Bold);
+    let reviewers = args
+        .reviewers
+        .iter()
+        .map(|reviewer| reviewer.username.as_str())
+        .collect::<Vec<_>>();
+    show_input("Reviewers", &reviewers.join(", "), false, Style::Bold);
+    if !args.labels.is_empty() {
+        show_input("Labels", &args.labels.join(", "), false, Style::Bold);
+    }
     show_input("Title", &args.title, false, Style::Bold);
     if !args.description.is_empty() {
         show_input("Description:", &args.description, true, Style::Bold);
@@ -259,6 +321,14 @@ pub fn prompt_args() -> String {
         .unwrap()
 }
 
+/// Lets the user pick any number of items. Returns their indexes.
+pub fn multi_select(prompt: &str, items: &[String]) -> Result<Vec<usize>> {
+    Ok(MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(items)
+        .interact()?)
+}
+
 pub fn fuzzy_select(amps: Vec<String>) -> Result<String> {
     let selection = dialoguer::FuzzySelect::with_theme(&ColorfulTheme::default())
         .with_prompt("amp:")
@@ -372,6 +442,19 @@ mod tests {
         assert_eq!(result.len(), 4);
     }
 
+    #[test]
+    fn test_prioritize_suggested_members() {
+        let selector = MemberSelector::new(create_test_members())
+            .prioritize(&["charlie".to_string(), "org/team".to_string()]);
+        let assignee = create_test_member(1, "alice");
+
+        let result = selector.prepare_reviewer_list(None::<&Member>, &assignee);
+
+        assert_eq!(result[0], Member::default());
+        assert_eq!(result[1].username, "charlie");
+        assert_eq!(result[2].username, "bob");
+    }
+
     #[test]
     fn test_prepare_reviewer_list_no_cli_reviewer_provided() {
         let members = create_test_members();
@@ -1,11 +1,24 @@
+use crate::error::{self, GRError};
 use crate::remote::GetRemoteCliArgs;
+use crate::time::{self, Seconds};
 use crate::Result;
-use std::{collections::HashMap, io::Write};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::io::{BufWriter, Write};
 
 #[derive(Clone, Debug, Default)]
 pub enum Format {
+    /// RFC 4180 CSV, ready to be imported in spreadsheets. Fields containing
+    /// commas, double quotes or line breaks are enclosed in double quotes,
+    /// double quotes are escaped by doubling them and records end with CRLF.
     CSV,
+    /// Array of objects, one per line. Keys are the lowercase column names
+    /// with spaces replaced by underscores.
     JSON,
+    /// One object per line without an enclosing array, keys are the
+    /// lowercase column names. Output of the json format before it became an
+    /// array.
+    NDJSON,
     #[default]
     PIPE,
     TOML,
@@ -17,6 +30,7 @@ impl From<Format> for u8 {
             Format::CSV => b',',
             Format::PIPE => b'|',
             Format::JSON => 0,
+            Format::NDJSON => 0,
             Format::TOML => 0,
         }
     }
@@ -32,12 +46,60 @@ impl DisplayBody {
     }
 }
 
+/// How a numeric column value is displayed in the pipe format unless raw
+/// numbers are requested. Other formats are read by programs and always get
+/// the raw value.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Unit {
+    #[default]
+    None,
+    /// Seconds, displayed as 4m 32s
+    Seconds,
+    /// Large counts, displayed as 1.2k
+    Count,
+}
+
+impl Unit {
+    fn humanize(&self, value: &str) -> Option<String> {
+        match self {
+            Unit::None => None,
+            Unit::Seconds => {
+                let seconds = value.parse::<f64>().ok()?;
+                Some(time::human_duration(Seconds::new(seconds.round() as u64)))
+            }
+            Unit::Count => Some(human_count(value.parse::<u64>().ok()?)),
+        }
+    }
+}
+
+/// Human readable count with one decimal. Ex. 950, 1.2k, 3M
+pub fn human_count(count: u64) -> String {
+    if count < 1000 {
+        return count.to_string();
+    }
+    let mut value = count as f64 / 1000.0;
+    let mut suffix = "k";
+    for next in ["M", "B"] {
+        // Check the rounded value so 999_999 becomes 1M instead of 1000k.
+        if (value * 10.0).round() < 10000.0 {
+            break;
+        }
+        value /= 1000.0;
+        suffix = next;
+    }
+    let value = format!("{:.1}", value);
+    let value = value.strip_suffix(".0").unwrap_or(&value);
+    format!("{}{}", value, suffix)
+}
+
 #[derive(Builder)]
 pub struct Column {
     pub name: String,
     pub value: String,
     #[builder(default)]
     pub optional: bool,
+    #[builder(default)]
+    pub unit: Unit,
 }
 
 impl Column {
@@ -49,86 +111,340 @@ impl Column {
             name: name.into(),
             value: value.into(),
             optional: false,
+            unit: Unit::None,
+        }
+    }
+
+    /// Column with a duration in seconds.
+    pub fn duration(name: impl Into<String>, seconds: impl Into<String>) -> Self {
+        Self {
+            unit: Unit::Seconds,
+            ..Self::new(name, seconds)
+        }
+    }
+
+    /// Column with a count that can grow large, ex. number of runs.
+    pub fn count(name: impl Into<String>, count: u64) -> Self {
+        Self {
+            unit: Unit::Count,
+            ..Self::new(name, count.to_string())
+        }
+    }
+}
+
+lazy_static! {
+    // Links and images, ex. [text](url) or ![alt](url), keep their text.
+    static ref MARKDOWN_LINK: Regex = Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").unwrap();
+    // Headings, quotes and list items at the start of a line.
+    static ref MARKDOWN_LINE_MARKER: Regex =
+        Regex::new(r"(?m)^\s*(#{1,6}\s+|>\s?|[-*+]\s+|\d+\.\s+)").unwrap();
+    static ref MARKDOWN_EMPHASIS: Regex = Regex::new(r"\*\*|__|~~|\*|`").unwrap();
+    static ref URL: Regex = Regex::new(r"https?://([^/\s]+)([^\s]*)").unwrap();
+}
+
+/// Transformation applied to the value of a column before it is printed, so
+/// listings stay readable. Ex. `--transform description=strip-markdown`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnTransform {
+    /// Cut values longer than the given number of characters, ending them
+    /// with `...`
+    Truncate(usize),
+    /// Remove markdown syntax and join lines into one
+    StripMarkdown,
+    /// Shorten URLs to their host and last path segment, usually the id of
+    /// the resource. Ex. gitlab.com/.../42
+    UrlShorten,
+}
+
+impl TryFrom<&str> for ColumnTransform {
+    type Error = GRError;
+
+    fn try_from(transform: &str) -> std::result::Result<Self, Self::Error> {
+        match transform.split_once(':') {
+            Some(("truncate", width)) => match width.parse::<usize>() {
+                Ok(width) if width > 0 => Ok(ColumnTransform::Truncate(width)),
+                _ => Err(GRError::PreconditionNotMet(format!(
+                    "Invalid truncate width {}. Ex. truncate:40",
+                    width
+                ))),
+            },
+            None if transform == "strip-markdown" => Ok(ColumnTransform::StripMarkdown),
+            None if transform == "url-shorten" => Ok(ColumnTransform::UrlShorten),
+            _ => Err(GRError::PreconditionNotMet(format!(
+                "Unknown transform {}. Valid transforms are truncate:N, \
+                 strip-markdown and url-shorten",
+                transform
+            ))),
+        }
+    }
+}
+
+impl ColumnTransform {
+    pub fn apply(&self, value: &str) -> String {
+        match self {
+            ColumnTransform::Truncate(width) => {
+                if value.chars().count() <= *width {
+                    return value.to_string();
+                }
+                if *width <= 3 {
+                    return value.chars().take(*width).collect();
+                }
+                let mut truncated: String = value.chars().take(width - 3).collect();
+                truncated.push_str("...");
+                truncated
+            }
+            ColumnTransform::StripMarkdown => {
+                let value = MARKDOWN_LINK.replace_all(value, "$1");
+                let value = MARKDOWN_LINE_MARKER.replace_all(&value, "");
+                let value = MARKDOWN_EMPHASIS.replace_all(&value, "");
+                value.split_whitespace().collect::<Vec<_>>().join(" ")
+            }
+            ColumnTransform::UrlShorten => URL
+                .replace_all(value, |caps: &Captures| {
+                    let host = &caps[1];
+                    let segments = caps[2]
+                        .split(['/', '?', '#'])
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>();
+                    match segments.as_slice() {
+                        [] => host.to_string(),
+                        [segment] => format!("{}/{}", host, segment),
+                        [.., last] => format!("{}/.../{}", host, last),
+                    }
+                })
+                .to_string(),
         }
     }
 }
 
+/// Applies the transforms whose column name matches, ignoring case and
+/// treating spaces and underscores the same, in the order they were given.
+fn apply_transforms(
+    mut body: DisplayBody,
+    transforms: &[(String, ColumnTransform)],
+) -> DisplayBody {
+    if transforms.is_empty() {
+        return body;
+    }
+    let key = |name: &str| name.to_lowercase().replace(' ', "_");
+    for column in body.columns.iter_mut() {
+        let name = key(&column.name);
+        for (_, transform) in transforms.iter().filter(|(c, _)| key(c) == name) {
+            column.value = transform.apply(&column.value);
+        }
+    }
+    body
+}
+
+/// Parses a `COLUMN=TRANSFORM` pair. Ex. title=truncate:40
+pub fn parse_column_transform(arg: &str) -> Result<(String, ColumnTransform)> {
+    match arg.split_once('=') {
+        Some((column, transform)) if !column.is_empty() => {
+            Ok((column.to_string(), ColumnTransform::try_from(transform)?))
+        }
+        _ => Err(error::gen(format!(
+            "Invalid transform {}. Ex. title=truncate:40",
+            arg
+        ))),
+    }
+}
+
 // TODO: Change args to borrow GetRemoteCliArgs
+/// Writes the data in the requested format. Output is buffered and flushed
+/// once at the end, so listing thousands of rows does not issue a write per
+/// column.
 pub fn print<W: Write, D: Into<DisplayBody> + Clone>(
     w: &mut W,
     data: Vec<D>,
     args: GetRemoteCliArgs,
 ) -> Result<()> {
-    if data.is_empty() {
-        return Ok(());
+    let mut w = BufWriter::new(w);
+    let mut stream = PageStream::new(args);
+    stream.write_rows(&mut w, data)?;
+    stream.write_end(&mut w)?;
+    w.flush()?;
+    Ok(())
+}
+
+/// Prints the pages of a listing as they are received, so the output is the
+/// same as printing all the pages at once. Headers are printed once and JSON
+/// and TOML rows of all pages go in a single array, closed by `finish`.
+pub struct PageStream {
+    args: GetRemoteCliArgs,
+    num_rows: usize,
+}
+
+impl PageStream {
+    pub fn new(args: GetRemoteCliArgs) -> Self {
+        Self { args, num_rows: 0 }
     }
-    match args.format {
-        Format::JSON => {
-            for d in data {
-                let d = d.into();
-                let kvs: HashMap<String, String> = d
-                    .columns
-                    .into_iter()
-                    .filter(|c| !c.optional || args.display_optional)
-                    .map(|item| (item.name.to_lowercase(), item.value))
-                    .collect();
-                writeln!(w, "{}", serde_json::to_string(&kvs)?)?;
-            }
-        }
-        Format::TOML => {
-            writeln!(w, "[")?;
-            let data_len = data.len();
-            for (index, d) in data.into_iter().enumerate() {
-                let d = d.into();
-                write!(w, "    {{")?;
-                let mut first = true;
-                for column in d.columns {
-                    if !column.optional || args.display_optional {
-                        if !first {
-                            write!(w, ",")?;
-                        }
-                        write!(w, " {} = {:?}", column.name.to_lowercase(), column.value)?;
-                        first = false;
+
+    pub fn print_page<W: Write, D: Into<DisplayBody> + Clone>(
+        &mut self,
+        w: &mut W,
+        data: Vec<D>,
+    ) -> Result<()> {
+        let mut w = BufWriter::new(w);
+        self.write_rows(&mut w, data)?;
+        w.flush()?;
+        Ok(())
+    }
+
+    pub fn finish<W: Write>(self, w: &mut W) -> Result<()> {
+        self.write_end(w)?;
+        w.flush()?;
+        Ok(())
+    }
+
+    fn write_rows<W: Write, D: Into<DisplayBody> + Clone>(
+        &mut self,
+        w: &mut W,
+        data: Vec<D>,
+    ) -> Result<()> {
+        let args = &self.args;
+        let visible = |c: &Column| !c.optional || args.display_optional;
+        match args.format {
+            Format::JSON | Format::TOML => {
+                for d in data {
+                    // One object per line, so the output is also easy to grep.
+                    if self.num_rows == 0 {
+                        writeln!(w, "[")?;
+                    } else {
+                        writeln!(w, ",")?;
                     }
+                    let d = apply_transforms(d.into(), &args.transforms);
+                    if let Format::JSON = args.format {
+                        write_json_object(w, d, &visible, true)?;
+                    } else {
+                        write_toml_object(w, d, &visible)?;
+                    }
+                    self.num_rows += 1;
                 }
-                write!(w, " }}")?;
-                if index < data_len - 1 {
-                    writeln!(w, ",")?;
-                } else {
+            }
+            Format::NDJSON => {
+                for d in data {
+                    let d = apply_transforms(d.into(), &args.transforms);
+                    write_json_object(w, d, &visible, false)?;
                     writeln!(w)?;
+                    self.num_rows += 1;
                 }
             }
-            writeln!(w, "]")?;
-        }
-        _ => {
-            let mut wtr = csv::WriterBuilder::new()
-                .delimiter(args.format.into())
-                .from_writer(w);
-            if !args.no_headers {
-                // Get the headers from the first row of columns
-                let headers = data[0]
-                    .clone()
-                    .into()
-                    .columns
-                    .iter()
-                    .filter(|c| !c.optional || args.display_optional)
-                    .map(|c| c.name.clone())
-                    .collect::<Vec<_>>();
-                wtr.write_record(&headers)?;
+            _ => {
+                let humanize = matches!(args.format, Format::PIPE) && !args.raw_numbers;
+                let terminator = match args.format {
+                    Format::CSV => csv::Terminator::CRLF,
+                    _ => csv::Terminator::Any(b'\n'),
+                };
+                let mut wtr = csv::WriterBuilder::new()
+                    .delimiter(args.format.clone().into())
+                    .terminator(terminator)
+                    .quote_style(csv::QuoteStyle::Necessary)
+                    .from_writer(&mut *w);
+                for d in data {
+                    let d = apply_transforms(d.into(), &args.transforms);
+                    // Headers are taken from the first row of columns. Fields
+                    // are written one by one to avoid collecting each row.
+                    if self.num_rows == 0 && !args.no_headers {
+                        for column in d.columns.iter().filter(|c| visible(c)) {
+                            wtr.write_field(&column.name)?;
+                        }
+                        wtr.write_record(None::<&[u8]>)?;
+                    }
+                    for column in d.columns.iter().filter(|c| visible(c)) {
+                        let humanized = humanize
+                            .then(|| column.unit.humanize(&column.value))
+                            .flatten();
+                        wtr.write_field(humanized.as_ref().unwrap_or(&column.value))?;
+                    }
+                    wtr.write_record(None::<&[u8]>)?;
+                    self.num_rows += 1;
+                }
+                wtr.flush()?;
             }
-            for d in data {
-                let d = d.into();
-                let row = d
-                    .columns
-                    .into_iter()
-                    .filter(|c| !c.optional || args.display_optional)
-                    .map(|c| c.value)
-                    .collect::<Vec<_>>();
-                wtr.write_record(&row)?;
+        }
+        Ok(())
+    }
+
+    fn write_end<W: Write>(&self, w: &mut W) -> Result<()> {
+        match self.args.format {
+            Format::JSON | Format::TOML if self.num_rows > 0 => {
+                writeln!(w)?;
+                writeln!(w, "]")?;
             }
-            wtr.flush()?;
+            // An empty array keeps scripts parsing the output working.
+            Format::JSON => writeln!(w, "[]")?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Same as `print` for a single resource, ex. get commands. JSON output is an
+/// object instead of an array of one object.
+pub fn print_one<W: Write, D: Into<DisplayBody> + Clone>(
+    w: &mut W,
+    data: D,
+    args: GetRemoteCliArgs,
+) -> Result<()> {
+    if let Format::JSON = args.format {
+        let visible = |c: &Column| !c.optional || args.display_optional;
+        let mut w = BufWriter::new(w);
+        write_json_object(
+            &mut w,
+            apply_transforms(data.into(), &args.transforms),
+            &visible,
+            true,
+        )?;
+        writeln!(w)?;
+        w.flush()?;
+        return Ok(());
+    }
+    print(w, vec![data], args)
+}
+
+/// Writes the columns as a JSON object keeping the order of the columns.
+/// Keys are the lowercase column names, with spaces replaced by underscores
+/// if `underscore_keys` is set.
+fn write_json_object<W: Write>(
+    w: &mut W,
+    body: DisplayBody,
+    visible: &impl Fn(&Column) -> bool,
+    underscore_keys: bool,
+) -> Result<()> {
+    write!(w, "{{")?;
+    let mut first = true;
+    for column in body.columns.iter().filter(|c| visible(c)) {
+        if !first {
+            write!(w, ",")?;
+        }
+        let mut key = column.name.to_lowercase();
+        if underscore_keys {
+            key = key.replace(' ', "_");
         }
+        serde_json::to_writer(&mut *w, &key)?;
+        write!(w, ":")?;
+        serde_json::to_writer(&mut *w, &column.value)?;
+        first = false;
     }
+    write!(w, "}}")?;
+    Ok(())
+}
+
+fn write_toml_object<W: Write>(
+    w: &mut W,
+    body: DisplayBody,
+    visible: &impl Fn(&Column) -> bool,
+) -> Result<()> {
+    write!(w, "    {{")?;
+    let mut first = true;
+    for column in body.columns.iter().filter(|c| visible(c)) {
+        if !first {
+            write!(w, ",")?;
+        }
+        write!(w, " {} = {:?}", column.name.to_lowercase(), column.value)?;
+        first = false;
+    }
+    write!(w, " }}")?;
     Ok(())
 }
 
@@ -174,15 +490,148 @@ mod test {
             .unwrap();
         print(&mut w, books, args).unwrap();
         let s = String::from_utf8(w).unwrap();
-        assert_eq!(2, s.lines().count());
-        for line in s.lines() {
-            let v: serde_json::Value = serde_json::from_str(line).unwrap();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        let array = v.as_array().unwrap();
+        assert_eq!(2, array.len());
+        for v in array {
             assert!(v.is_object());
             let obj = v.as_object().unwrap();
             assert_eq!(obj.len(), 2);
             assert!(obj.contains_key("title"));
             assert!(obj.contains_key("author"));
         }
+        assert_eq!(
+            "[\n\
+             {\"title\":\"The Catcher in the Rye\",\"author\":\"J.D. Salinger\"},\n\
+             {\"title\":\"The Adventures of Huckleberry Finn\",\"author\":\"Mark Twain\"}\n\
+             ]\n",
+            s
+        );
+    }
+
+    #[test]
+    fn test_json_empty_list_is_empty_array() {
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .format(Format::JSON)
+            .build()
+            .unwrap();
+        print(&mut w, Vec::<Book>::new(), args).unwrap();
+        assert_eq!("[]\n", String::from_utf8(w).unwrap());
+    }
+
+    #[test]
+    fn test_ndjson_is_one_object_per_line_with_column_names_as_keys() {
+        #[derive(Clone)]
+        struct Event(&'static str);
+        impl From<Event> for DisplayBody {
+            fn from(e: Event) -> Self {
+                DisplayBody::new(vec![Column::new("Created at", e.0)])
+            }
+        }
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .format(Format::NDJSON)
+            .build()
+            .unwrap();
+        print(&mut w, vec![Event("2024-01-01"), Event("2024-01-02")], args).unwrap();
+        assert_eq!(
+            "{\"created at\":\"2024-01-01\"}\n{\"created at\":\"2024-01-02\"}\n",
+            String::from_utf8(w).unwrap()
+        );
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .format(Format::NDJSON)
+            .build()
+            .unwrap();
+        print(&mut w, Vec::<Book>::new(), args).unwrap();
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_streamed_json_pages_are_a_single_array() {
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .format(Format::JSON)
+            .build()
+            .unwrap();
+        let mut stream = PageStream::new(args);
+        stream
+            .print_page(&mut w, vec![Book::new("Faust", "Goethe")])
+            .unwrap();
+        stream.print_page(&mut w, Vec::<Book>::new()).unwrap();
+        stream
+            .print_page(&mut w, vec![Book::new("Ulysses", "James Joyce")])
+            .unwrap();
+        stream.finish(&mut w).unwrap();
+        let s = String::from_utf8(w).unwrap();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(2, v.as_array().unwrap().len());
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .format(Format::JSON)
+            .build()
+            .unwrap();
+        let books = vec![
+            Book::new("Faust", "Goethe"),
+            Book::new("Ulysses", "James Joyce"),
+        ];
+        print(&mut w, books, args).unwrap();
+        assert_eq!(String::from_utf8(w).unwrap(), s);
+    }
+
+    #[test]
+    fn test_streamed_json_without_rows_is_empty_array() {
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .format(Format::JSON)
+            .build()
+            .unwrap();
+        let mut stream = PageStream::new(args);
+        stream.print_page(&mut w, Vec::<Book>::new()).unwrap();
+        stream.finish(&mut w).unwrap();
+        assert_eq!("[]\n", String::from_utf8(w).unwrap());
+    }
+
+    #[test]
+    fn test_streamed_pipe_pages_print_headers_once() {
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder().build().unwrap();
+        let mut stream = PageStream::new(args);
+        stream.print_page(&mut w, Vec::<Book>::new()).unwrap();
+        stream
+            .print_page(&mut w, vec![Book::new("Faust", "Goethe")])
+            .unwrap();
+        stream
+            .print_page(&mut w, vec![Book::new("Ulysses", "James Joyce")])
+            .unwrap();
+        stream.finish(&mut w).unwrap();
+        assert_eq!(
+            "title|author\nFaust|Goethe\nUlysses|James Joyce\n",
+            String::from_utf8(w).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_single_resource_is_object() {
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .format(Format::JSON)
+            .build()
+            .unwrap();
+        let book = Book::new("Faust, \"Part One\"", "Goethe");
+        print_one(&mut w, book, args).unwrap();
+        assert_eq!(
+            "{\"title\":\"Faust, \\\"Part One\\\"\",\"author\":\"Goethe\"}\n",
+            String::from_utf8(w).unwrap()
+        );
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .no_headers(true)
+            .build()
+            .unwrap();
+        print_one(&mut w, Book::new("Faust", "Goethe"), args).unwrap();
+        assert_eq!("Faust|Goethe\n", String::from_utf8(w).unwrap());
     }
 
     #[test]
@@ -260,7 +709,7 @@ mod test {
             .unwrap();
         print(&mut w, books, args).unwrap();
         assert_eq!(
-            "title,author\nThe Catcher in the Rye,J.D. Salinger\nThe Adventures of Huckleberry Finn,Mark Twain\n",
+            "title,author\r\nThe Catcher in the Rye,J.D. Salinger\r\nThe Adventures of Huckleberry Finn,Mark Twain\r\n",
             String::from_utf8(w).unwrap()
         );
     }
@@ -283,7 +732,26 @@ mod test {
             .unwrap();
         print(&mut w, books, args).unwrap();
         assert_eq!(
-            "title,author,isbn\nThe Catcher in the Rye,J.D. Salinger,0316769487\nThe Adventures of Huckleberry Finn,Mark Twain,9780199536559\n",
+            "title,author,isbn\r\nThe Catcher in the Rye,J.D. Salinger,0316769487\r\nThe Adventures of Huckleberry Finn,Mark Twain,9780199536559\r\n",
+            String::from_utf8(w).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_csv_quotes_fields_as_rfc4180() {
+        let mut w = Vec::new();
+        let books = vec![
+            Book::new("Faust, \"Part One\"", "Goethe"),
+            Book::new("Line one\nLine two", "A | B"),
+        ];
+        let args = GetRemoteCliArgs::builder()
+            .no_headers(true)
+            .format(Format::CSV)
+            .build()
+            .unwrap();
+        print(&mut w, books, args).unwrap();
+        assert_eq!(
+            "\"Faust, \"\"Part One\"\"\",Goethe\r\n\"Line one\nLine two\",A | B\r\n",
             String::from_utf8(w).unwrap()
         );
     }
@@ -339,4 +807,185 @@ mod test {
         let s = String::from_utf8(w).unwrap();
         assert_eq!(s, "[\n    { title = \"The Catcher in the Rye\", author = \"J.D. Salinger\" },\n    { title = \"The Adventures of Huckleberry Finn\", author = \"Mark Twain\" }\n]\n");
     }
+
+    #[derive(Default)]
+    struct CountingWriter {
+        data: Vec<u8>,
+        writes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.writes += 1;
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_print_writes_rows_in_a_single_write() {
+        let books = (0..1000)
+            .map(|i| Book::new(format!("Title {}", i), "Author"))
+            .collect::<Vec<_>>();
+        for format in [Format::PIPE, Format::JSON, Format::TOML] {
+            let mut w = CountingWriter::default();
+            let args = GetRemoteCliArgs::builder().format(format).build().unwrap();
+            print(&mut w, books.clone(), args).unwrap();
+            assert!(w.writes < 10);
+            assert!(String::from_utf8(w.data).unwrap().contains("Title 999"));
+        }
+    }
+
+    #[test]
+    fn test_parse_column_transform() {
+        assert_eq!(
+            ("title".to_string(), ColumnTransform::Truncate(40)),
+            parse_column_transform("title=truncate:40").unwrap()
+        );
+        assert_eq!(
+            ("description".to_string(), ColumnTransform::StripMarkdown),
+            parse_column_transform("description=strip-markdown").unwrap()
+        );
+        assert_eq!(
+            ("url".to_string(), ColumnTransform::UrlShorten),
+            parse_column_transform("url=url-shorten").unwrap()
+        );
+        assert!(parse_column_transform("title").is_err());
+        assert!(parse_column_transform("=truncate:4").is_err());
+        assert!(parse_column_transform("title=truncate:0").is_err());
+        assert!(parse_column_transform("title=uppercase").is_err());
+    }
+
+    #[test]
+    fn test_truncate_transform() {
+        let transform = ColumnTransform::Truncate(10);
+        assert_eq!("short", transform.apply("short"));
+        assert_eq!("Faust, ...", transform.apply("Faust, Part One"));
+        assert_eq!("Fau", ColumnTransform::Truncate(3).apply("Faust"));
+    }
+
+    #[test]
+    fn test_strip_markdown_transform() {
+        let description = "## Summary\n\n- Fix **login** with `sso`\n\
+                           > See [the docs](https://docs.gitlab.com) and ![img](a.png)";
+        assert_eq!(
+            "Summary Fix login with sso See the docs and img",
+            ColumnTransform::StripMarkdown.apply(description)
+        );
+    }
+
+    #[test]
+    fn test_url_shorten_transform() {
+        let transform = ColumnTransform::UrlShorten;
+        assert_eq!(
+            "gitlab.com/.../42",
+            transform.apply("https://gitlab.com/jordilin/gitar/-/merge_requests/42")
+        );
+        assert_eq!(
+            "github.com/jordilin",
+            transform.apply("https://github.com/jordilin/")
+        );
+        assert_eq!("github.com", transform.apply("https://github.com"));
+        assert_eq!("not a url", transform.apply("not a url"));
+    }
+
+    #[test]
+    fn test_print_applies_transforms_to_matching_columns() {
+        let mut w = Vec::new();
+        let books = vec![Book::new(
+            "The Adventures of Huckleberry Finn",
+            "Mark Twain",
+        )];
+        let args = GetRemoteCliArgs::builder()
+            .transforms(vec![("Title".to_string(), ColumnTransform::Truncate(14))])
+            .build()
+            .unwrap();
+        print(&mut w, books, args).unwrap();
+        assert_eq!(
+            "title|author\nThe Adventu...|Mark Twain\n",
+            String::from_utf8(w).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_human_count() {
+        let test_table = vec![
+            (0, "0"),
+            (950, "950"),
+            (1000, "1k"),
+            (1234, "1.2k"),
+            (12_345, "12.3k"),
+            (999_999, "1M"),
+            (3_400_000, "3.4M"),
+            (2_000_000_000, "2B"),
+        ];
+        for (count, expected) in test_table {
+            assert_eq!(expected, human_count(count));
+        }
+    }
+
+    #[derive(Clone)]
+    struct Run {
+        duration: String,
+        runs: u64,
+    }
+
+    impl From<Run> for DisplayBody {
+        fn from(r: Run) -> Self {
+            DisplayBody::new(vec![
+                Column::duration("duration", r.duration),
+                Column::count("runs", r.runs),
+            ])
+        }
+    }
+
+    fn runs() -> Vec<Run> {
+        vec![
+            Run {
+                duration: "272".to_string(),
+                runs: 1234,
+            },
+            Run {
+                duration: "".to_string(),
+                runs: 12,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_pipe_humanizes_durations_and_counts() {
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder().build().unwrap();
+        print(&mut w, runs(), args).unwrap();
+        assert_eq!(
+            "duration|runs\n4m 32s|1.2k\n|12\n",
+            String::from_utf8(w).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_raw_numbers_and_machine_formats_are_not_humanized() {
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .raw_numbers(true)
+            .build()
+            .unwrap();
+        print(&mut w, runs(), args).unwrap();
+        assert_eq!(
+            "duration|runs\n272|1234\n|12\n",
+            String::from_utf8(w).unwrap()
+        );
+        let mut w = Vec::new();
+        let args = GetRemoteCliArgs::builder()
+            .no_headers(true)
+            .format(Format::CSV)
+            .build()
+            .unwrap();
+        print(&mut w, runs(), args).unwrap();
+        assert_eq!("272,1234\r\n,12\r\n", String::from_utf8(w).unwrap());
+    }
 }
@@ -35,8 +35,17 @@ pub enum GRError {
     RemoteUnexpectedResponseContract(String),
     #[error("Remote server status error: {0}")]
     RemoteServerError(String),
+    // The remote refused the operation given the current state of the
+    // resource (HTTP 405 or 409). Ex. a merge request that is not mergeable
+    // yet because its pipeline is still running.
+    #[error("Remote conflict error: {0}")]
+    RemoteConflict(String),
     #[error("HTTP Transport error/network outage: {0}")]
     HttpTransportError(String),
+    #[error("Response body too large: {0}")]
+    ResponseBodyTooLarge(String),
+    #[error("Circuit open: {0}")]
+    CircuitOpen(String),
     #[error("Mermaid parsing error: {0}")]
     MermaidParsingError(String),
     #[error("Configuration not found")]
@@ -51,6 +60,8 @@ pub enum GRError {
     CacheLocationWriteTestFailed(String),
     #[error("User not found: {0}")]
     UserNotFound(String),
+    #[error("Pipeline failed: {0}")]
+    PipelineFailed(String),
 }
 
 pub trait AddContext<T, E>: Context<T, E> {
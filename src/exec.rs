@@ -2,6 +2,7 @@ use crate::Cmd;
 use crate::Result;
 use std::sync::mpsc::channel;
 use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
 
 /// Executes a sequence of commands in parallel
 pub fn parallel_stream<T>(cmds: impl IntoIterator<Item = Cmd<T>>) -> Receiver<Result<T>>
@@ -22,6 +23,31 @@ where
     receiver
 }
 
+/// Executes a sequence of commands in parallel, running at most `max_parallel`
+/// of them at the same time.
+pub fn bounded_parallel_stream<T>(
+    cmds: impl IntoIterator<Item = Cmd<T>>,
+    max_parallel: usize,
+) -> Receiver<Result<T>>
+where
+    T: Send + 'static,
+{
+    let (sender, receiver) = channel();
+    let cmds = Arc::new(Mutex::new(cmds.into_iter().collect::<Vec<_>>().into_iter()));
+    for _ in 0..max_parallel.max(1) {
+        let sender = sender.clone();
+        let cmds = cmds.clone();
+        std::thread::spawn(move || loop {
+            let Some(cmd) = cmds.lock().unwrap().next() else {
+                break;
+            };
+            sender.send(cmd()).unwrap_or_default();
+        });
+    }
+    drop(sender);
+    receiver
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -45,4 +71,30 @@ mod test {
         let results = repo_data_stream.iter().collect::<Vec<_>>();
         assert_eq!(2, results.len());
     }
+
+    #[test]
+    fn test_exec_bounded_runs_at_most_max_parallel_cmds() {
+        let running = Arc::new(Mutex::new((0, 0)));
+        let cmds = (0..6).map(|i| {
+            let running = running.clone();
+            let cmd: Cmd<i32> = Box::new(move || {
+                {
+                    let mut running = running.lock().unwrap();
+                    running.0 += 1;
+                    running.1 = running.1.max(running.0);
+                }
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                running.lock().unwrap().0 -= 1;
+                Ok(i)
+            });
+            cmd
+        });
+        let mut results = bounded_parallel_stream(cmds, 2)
+            .iter()
+            .map(|result| result.unwrap())
+            .collect::<Vec<_>>();
+        results.sort();
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], results);
+        assert!(running.lock().unwrap().1 <= 2);
+    }
 }
@@ -210,6 +210,66 @@ pub fn patch<S: Into<String>, T: Into<String>>(
     Ok(response.body)
 }
 
+/// Files changed in the current branch since it diverged from the remote
+/// target branch.
+pub fn changed_files(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    remote: &str,
+    target_branch: &str,
+) -> Result<Vec<String>> {
+    let cmd = vec![
+        "git".to_string(),
+        "diff".to_string(),
+        "--name-only".to_string(),
+        format!("{}/{}...", remote, target_branch),
+    ];
+    let response = runner.run(cmd)?;
+    Ok(response
+        .body
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect())
+}
+
+/// Locations where Github and Gitlab look up the CODEOWNERS file.
+const CODEOWNERS_LOCATIONS: [&str; 4] = [
+    "CODEOWNERS",
+    ".github/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+    "docs/CODEOWNERS",
+];
+
+/// Contents of the CODEOWNERS file committed in the current branch, if any.
+pub fn codeowners(runner: &impl TaskRunner<Response = ShellResponse>) -> Option<String> {
+    CODEOWNERS_LOCATIONS.iter().find_map(|location| {
+        let cmd = vec![
+            "git".to_string(),
+            "show".to_string(),
+            format!("HEAD:{}", location),
+        ];
+        runner.run(cmd).ok().map(|response| response.body)
+    })
+}
+
+/// Default branch of the origin remote as recorded locally when cloning, ex.
+/// `refs/remotes/origin/HEAD` pointing to `refs/remotes/origin/main`.
+pub fn default_branch(runner: &impl TaskRunner<Response = ShellResponse>) -> Result<String> {
+    let cmd_params = ["git", "symbolic-ref", "refs/remotes/origin/HEAD"];
+    let response = runner.run(cmd_params).err_context(format!(
+        "Failed to get the default branch. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    match response.body.trim().strip_prefix("refs/remotes/origin/") {
+        Some(branch) if !branch.is_empty() => Ok(branch.to_string()),
+        _ => Err(GRError::PreconditionNotMet(format!(
+            "Unexpected default branch reference: {}",
+            response.body
+        ))
+        .into()),
+    }
+}
+
 pub fn push(runner: &impl TaskRunner, remote: &str, repo: &Repo, force: bool) -> Result<CmdInfo> {
     let force_str = if force { "+" } else { "" };
     let cmd = format!("git push {} {}{}", remote, force_str, repo.current_branch);
@@ -247,6 +307,45 @@ pub fn checkout(runner: &impl TaskRunner<Response = ShellResponse>, branch: &str
     Ok(())
 }
 
+/// Fetch a single ref from the remote, ex. the head of a pull request. The
+/// fetched commit is left in FETCH_HEAD.
+pub fn fetch_ref(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    remote_alias: &str,
+    git_ref: &str,
+) -> Result<()> {
+    let cmd_params = ["git", "fetch", remote_alias, git_ref];
+    runner.run(cmd_params).err_context(format!(
+        "Failed to git fetch ref. Command: {}",
+        cmd_params.join(" ")
+    ))?;
+    Ok(())
+}
+
+/// Creates a local branch at FETCH_HEAD and sets it to track `git_ref` in the
+/// remote, so `git pull` brings in new commits of that ref.
+pub fn checkout_tracking(
+    runner: &impl TaskRunner<Response = ShellResponse>,
+    branch: &str,
+    remote_alias: &str,
+    git_ref: &str,
+) -> Result<()> {
+    let remote_key = format!("branch.{}.remote", branch);
+    let merge_key = format!("branch.{}.merge", branch);
+    let cmds = [
+        vec!["git", "checkout", "-b", branch, "FETCH_HEAD"],
+        vec!["git", "config", &remote_key, remote_alias],
+        vec!["git", "config", &merge_key, git_ref],
+    ];
+    for cmd_params in cmds {
+        runner.run(&cmd_params).err_context(format!(
+            "Failed to checkout tracking branch. Command: {}",
+            cmd_params.join(" ")
+        ))?;
+    }
+    Ok(())
+}
+
 /// Repo represents a local git repository
 #[derive(Clone, Debug, Default)]
 pub struct Repo {
@@ -316,7 +415,7 @@ mod tests {
         let runner = Arc::new(MockRunner::new(vec![response]));
         let cmd_info = status(runner).unwrap();
         if let CmdInfo::StatusModified(dirty) = cmd_info {
-            assert_eq!(true, dirty);
+            assert!(dirty);
         } else {
             panic!("Expected CmdInfo::StatusModified");
         }
@@ -334,7 +433,7 @@ mod tests {
         let runner = Arc::new(MockRunner::new(vec![response]));
         let cmd_info = status(runner).unwrap();
         if let CmdInfo::StatusModified(dirty) = cmd_info {
-            assert_eq!(true, dirty);
+            assert!(dirty);
         } else {
             panic!("Expected CmdInfo::StatusModified");
         }
@@ -358,7 +457,7 @@ mod tests {
         let runner = Arc::new(MockRunner::new(vec![response]));
         let cmd_info = status(runner).unwrap();
         if let CmdInfo::StatusModified(dirty) = cmd_info {
-            assert_eq!(false, dirty);
+            assert!(!dirty);
         } else {
             panic!("Expected CmdInfo::StatusModified");
         }
@@ -376,7 +475,7 @@ mod tests {
         let runner = Arc::new(MockRunner::new(vec![response]));
         let cmd_info = status(runner).unwrap();
         if let CmdInfo::StatusModified(dirty) = cmd_info {
-            assert_eq!(false, dirty);
+            assert!(!dirty);
         } else {
             panic!("Expected CmdInfo::StatusModified");
         }
@@ -405,7 +504,7 @@ mod tests {
             CmdInfo::RemoteUrl(url) => {
                 assert_eq!("github.com", url.domain());
                 assert_eq!("jordilin/mr", url.path());
-                assert_eq!("jordilin_mr", url.config_encoded_project_path());
+                assert_eq!("jordilin%2Fmr", url.config_encoded_project_path());
             }
             _ => panic!("Failed to parse remote url"),
         }
@@ -423,7 +522,7 @@ mod tests {
             CmdInfo::RemoteUrl(url) => {
                 assert_eq!("github.com", url.domain());
                 assert_eq!("jordilin/gitar", url.path());
-                assert_eq!("jordilin_gitar", url.config_encoded_project_path());
+                assert_eq!("jordilin%2Fgitar", url.config_encoded_project_path());
             }
             _ => panic!("Failed to parse remote url"),
         }
@@ -442,7 +541,7 @@ mod tests {
                 assert_eq!("gitlab-web", url.domain());
                 assert_eq!("testgroup/testsubproject", url.path());
                 assert_eq!(
-                    "testgroup_testsubproject",
+                    "testgroup%2Ftestsubproject",
                     url.config_encoded_project_path()
                 );
             }
@@ -610,6 +709,42 @@ mod tests {
         assert_eq!("git rebase origin/main", *runner.cmd());
     }
 
+    #[test]
+    fn test_git_fetch_ref_cmd_is_correct() {
+        let response = ShellResponse::builder().build().unwrap();
+        let runner = MockRunner::new(vec![response]);
+        fetch_ref(&runner, "origin", "refs/pull/23/head").unwrap();
+        assert_eq!("git fetch origin refs/pull/23/head", *runner.cmd());
+    }
+
+    #[test]
+    fn test_checkout_tracking_sets_upstream_ref() {
+        let responses = (0..3)
+            .map(|_| ShellResponse::builder().build().unwrap())
+            .collect();
+        let runner = MockRunner::new(responses);
+        checkout_tracking(&runner, "mr/23/feature", "origin", "refs/pull/23/head").unwrap();
+        assert_eq!(3, *runner.run_count.borrow());
+        assert_eq!(
+            "git config branch.mr/23/feature.merge refs/pull/23/head",
+            *runner.cmd()
+        );
+    }
+
+    #[test]
+    fn test_checkout_tracking_stops_if_branch_exists() {
+        let response = ShellResponse::builder()
+            .status(128)
+            .body("fatal: a branch named 'mr/23/feature' already exists".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert!(
+            checkout_tracking(&runner, "mr/23/feature", "origin", "refs/pull/23/head").is_err()
+        );
+        assert_eq!(1, *runner.run_count.borrow());
+    }
+
     #[test]
     fn test_git_rebase_fails_throws_error() {
         let response = ShellResponse::builder()
@@ -666,6 +801,62 @@ mod tests {
         assert_eq!(expected_cmd, *runner.cmd());
     }
 
+    #[test]
+    fn test_changed_files_cmd_is_ok() {
+        let response = ShellResponse::builder()
+            .body("src/cache.rs\nsrc/cmds/project.rs\n".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        let files = changed_files(&runner, "origin", "main").unwrap();
+        let expected_cmd = "git diff --name-only origin/main...".to_string();
+        assert_eq!(expected_cmd, *runner.cmd());
+        assert_eq!(vec!["src/cache.rs", "src/cmds/project.rs"], files);
+    }
+
+    #[test]
+    fn test_codeowners_looks_up_known_locations() {
+        let not_found = || {
+            ShellResponse::builder()
+                .status(128)
+                .body("fatal: path does not exist".to_string())
+                .build()
+                .unwrap()
+        };
+        let found = ShellResponse::builder()
+            .body("* @jordilin".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![found, not_found()]);
+        assert_eq!(Some("* @jordilin".to_string()), codeowners(&runner));
+        assert_eq!("git show HEAD:.github/CODEOWNERS", *runner.cmd());
+
+        let runner = MockRunner::new(vec![not_found(), not_found(), not_found(), not_found()]);
+        assert_eq!(None, codeowners(&runner));
+    }
+
+    #[test]
+    fn test_default_branch_from_origin_head() {
+        let response = ShellResponse::builder()
+            .body("refs/remotes/origin/develop\n".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert_eq!("develop", default_branch(&runner).unwrap());
+        assert_eq!("git symbolic-ref refs/remotes/origin/HEAD", *runner.cmd());
+    }
+
+    #[test]
+    fn test_default_branch_origin_head_not_set_is_error() {
+        let response = ShellResponse::builder()
+            .status(128)
+            .body("fatal: ref refs/remotes/origin/HEAD is not a symbolic ref".to_string())
+            .build()
+            .unwrap();
+        let runner = MockRunner::new(vec![response]);
+        assert!(default_branch(&runner).is_err());
+    }
+
     #[test]
     fn test_last_commit_message_cmd_is_ok() {
         let response = ShellResponse::builder().build().unwrap();
@@ -1,22 +1,34 @@
+use crate::api_traits::RemoteCredentials;
+use crate::auth::{self, AuthStrategy, Credentials};
 use crate::config::ConfigProperties;
+use crate::error::GRError;
 use crate::http::Headers;
+use crate::remote::{ListBodyArgs, ListOrderBy, ListSortMode};
+use crate::Result;
 use std::sync::Arc;
 
 pub mod cicd;
 pub mod container_registry;
+pub mod contract;
+pub mod deployment;
 pub mod gist;
+pub mod instance;
+pub mod issue;
+pub mod label;
 pub mod merge_request;
 pub mod project;
 pub mod release;
+pub mod search;
 pub mod trending;
 pub mod user;
 
 #[derive(Clone)]
 pub struct Github<R> {
-    api_token: String,
+    auth: Arc<dyn AuthStrategy>,
     domain: String,
     path: String,
     rest_api_basepath: String,
+    graphql_url: String,
     runner: Arc<R>,
 }
 
@@ -27,23 +39,32 @@ impl<R> Github<R> {
         path: &str,
         runner: Arc<R>,
     ) -> Self {
-        let api_token = config.api_token().to_string();
         let domain = domain.to_string();
-        let rest_api_basepath = format!("https://api.{}", domain);
+        // Github Enterprise serves the REST API under /api/v3 and GraphQL
+        // under /api/graphql.
+        let rest_api_basepath = match config.api_base_url() {
+            Some(url) => url.to_string(),
+            None => format!("https://api.{}", domain),
+        };
+        let graphql_url = match rest_api_basepath.strip_suffix("/v3") {
+            Some(api_path) => format!("{}/graphql", api_path),
+            None => format!("{}/graphql", rest_api_basepath),
+        };
 
+        let auth = auth::github_strategy(config, &rest_api_basepath, path);
         Github {
-            api_token,
+            auth,
             domain,
             path: path.to_string(),
             rest_api_basepath,
+            graphql_url,
             runner,
         }
     }
 
     fn request_headers(&self) -> Headers {
         let mut headers = Headers::new();
-        let auth_token_value = format!("bearer {}", self.api_token);
-        headers.set("Authorization".to_string(), auth_token_value);
+        self.auth.apply(&mut headers);
         headers.set(
             "Accept".to_string(),
             "application/vnd.github.v3+json".to_string(),
@@ -53,3 +74,39 @@ impl<R> Github<R> {
         headers
     }
 }
+
+impl<R> RemoteCredentials for Github<R> {
+    fn credentials(&self) -> Result<Credentials> {
+        self.auth.credentials()
+    }
+}
+
+/// Appends the order requested for the listing as Github query parameters.
+/// Pull requests and issues are sorted by creation or update date only.
+fn with_order_by(url: String, list_args: &Option<ListBodyArgs>) -> Result<String> {
+    let Some((order_by, sort_mode)) = list_args
+        .as_ref()
+        .and_then(|args| Some((args.order_by.as_ref()?, &args.sort_mode)))
+    else {
+        return Ok(url);
+    };
+    let sort = match order_by {
+        ListOrderBy::Created => "created",
+        ListOrderBy::Updated => "updated",
+        ListOrderBy::Title => {
+            return Err(GRError::OperationNotSupported(
+                "Github does not order pull requests nor issues by title".to_string(),
+            )
+            .into())
+        }
+    };
+    let direction = match sort_mode {
+        ListSortMode::Asc => "asc",
+        ListSortMode::Desc => "desc",
+    };
+    let separator = if url.contains('?') { '&' } else { '?' };
+    Ok(format!(
+        "{}{}sort={}&direction={}",
+        url, separator, sort, direction
+    ))
+}
@@ -1,15 +1,27 @@
+use std::collections::BTreeMap;
+
 use super::Github;
-use crate::api_traits::{ApiOperation, CicdJob, CicdRunner, NumberDeltaErr};
+use crate::api_traits::{
+    ApiOperation, CicdArtifact, CicdJob, CicdJobLog, CicdJobTiming, CicdPipelineStatus, CicdRunner,
+    CicdTestReport, CicdUsage, NumberDeltaErr,
+};
 use crate::cmds::cicd::{
-    Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs, RunnerListBodyArgs,
-    RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse, YamlBytes,
+    flaky::{self, TestResult},
+    lint,
+    stats::JobTiming,
+    status::{self, PipelineRef, PipelineStatus},
+    zip::ByteRange,
+    Artifact, FailedJob, Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs,
+    RunnerListBodyArgs, RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse,
+    RunnerUsage, YamlBytes,
 };
+use crate::error::GRError;
 use crate::remote::query;
 use crate::{
     api_traits::Cicd,
     io::{HttpResponse, HttpRunner},
 };
-use crate::{http, time, Result};
+use crate::{http, time, Error, Result};
 
 impl<R: HttpRunner<Response = HttpResponse>> Cicd for Github<R> {
     fn list(&self, args: PipelineBodyArgs) -> Result<Vec<Pipeline>> {
@@ -26,7 +38,7 @@ impl<R: HttpRunner<Response = HttpResponse>> Cicd for Github<R> {
             self.request_headers(),
             Some("workflow_runs"),
             ApiOperation::Pipeline,
-            |value| GithubPipelineFields::from(value).into(),
+            |value| Ok(GithubPipelineFields::try_from(value)?.into()),
         )
     }
 
@@ -44,8 +56,9 @@ impl<R: HttpRunner<Response = HttpResponse>> Cicd for Github<R> {
         query::num_resources(&self.runner, &url, headers, ApiOperation::Pipeline)
     }
 
-    fn lint(&self, _body: YamlBytes) -> Result<LintResponse> {
-        todo!()
+    // Github has no API to validate workflows, check them locally instead.
+    fn lint(&self, body: YamlBytes) -> Result<LintResponse> {
+        Ok(lint::validate_workflow(&body.to_string()))
     }
 }
 
@@ -82,6 +95,402 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdRunner for Github<R> {
     }
 }
 
+impl<R: HttpRunner<Response = HttpResponse>> CicdJobLog for Github<R> {
+    fn list_failed_jobs(&self, last: u32) -> Result<Vec<FailedJob>> {
+        // https://docs.github.com/en/rest/actions/workflow-runs?apiVersion=2022-11-28#list-workflow-runs-for-a-repository
+        let url = format!(
+            "{}/repos/{}/actions/runs?status=failure&per_page={}",
+            self.rest_api_basepath,
+            self.path,
+            last.min(100)
+        );
+        let runs = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let mut jobs = Vec::new();
+        for run in runs["workflow_runs"].as_array().unwrap_or(&vec![]) {
+            // https://docs.github.com/en/rest/actions/workflow-jobs?apiVersion=2022-11-28#list-jobs-for-a-workflow-run
+            let url = format!(
+                "{}/repos/{}/actions/runs/{}/jobs?filter=latest&per_page=100",
+                self.rest_api_basepath,
+                self.path,
+                run["id"].as_i64().unwrap_or_default()
+            );
+            let run_jobs = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.request_headers(),
+                ApiOperation::Pipeline,
+            )?;
+            for job in run_jobs["jobs"].as_array().unwrap_or(&vec![]) {
+                if job["conclusion"].as_str() != Some("failure") {
+                    continue;
+                }
+                jobs.push(
+                    FailedJob::builder()
+                        .pipeline_id(job["run_id"].as_i64().unwrap_or_default())
+                        .id(job["id"].as_i64().unwrap_or_default())
+                        .name(job["name"].as_str().unwrap_or_default().to_string())
+                        .web_url(job["html_url"].as_str().unwrap_or_default().to_string())
+                        .build()
+                        .unwrap(),
+                );
+            }
+        }
+        Ok(jobs)
+    }
+
+    // https://docs.github.com/en/rest/actions/workflow-jobs?apiVersion=2022-11-28#download-job-logs-for-a-workflow-run
+    fn get_log(&self, job_id: i64) -> Result<String> {
+        let url = format!(
+            "{}/repos/{}/actions/jobs/{}/logs",
+            self.rest_api_basepath, self.path, job_id
+        );
+        let response = query::get_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        Ok(String::from_utf8_lossy(response.body_bytes()).into_owned())
+    }
+
+    // The logs are served from a storage that honors range requests.
+    fn get_log_from(&self, job_id: i64, offset: u64) -> Result<Vec<u8>> {
+        let url = format!(
+            "{}/repos/{}/actions/jobs/{}/logs",
+            self.rest_api_basepath, self.path, job_id
+        );
+        query::get_bytes_from(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            offset,
+        )
+    }
+}
+
+/// Github does not expose test reports. Test results are parsed from the test
+/// runner output in the logs of each job.
+impl<R: HttpRunner<Response = HttpResponse>> CicdTestReport for Github<R> {
+    fn list_test_results(&self, last: u32) -> Result<Vec<TestResult>> {
+        let url = format!(
+            "{}/repos/{}/actions/runs?status=completed&per_page={}",
+            self.rest_api_basepath,
+            self.path,
+            last.min(100)
+        );
+        let runs = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let mut results = Vec::new();
+        for run in runs["workflow_runs"].as_array().unwrap_or(&vec![]) {
+            let run_id = run["id"].as_i64().unwrap_or_default();
+            let url = format!(
+                "{}/repos/{}/actions/runs/{}/jobs?filter=all&per_page=100",
+                self.rest_api_basepath, self.path, run_id
+            );
+            let run_jobs = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.request_headers(),
+                ApiOperation::Pipeline,
+            )?;
+            for job in run_jobs["jobs"].as_array().unwrap_or(&vec![]) {
+                let log = self.get_log(job["id"].as_i64().unwrap_or_default())?;
+                results.extend(flaky::parse_log(run_id, &log));
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Jobs have no queue duration. A job is queued since it is created until a
+/// runner starts it.
+impl<R: HttpRunner<Response = HttpResponse>> CicdJobTiming for Github<R> {
+    fn list_job_timings(&self, last: u32) -> Result<Vec<JobTiming>> {
+        let url = format!(
+            "{}/repos/{}/actions/runs?status=completed&per_page={}",
+            self.rest_api_basepath,
+            self.path,
+            last.min(100)
+        );
+        let runs = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let mut timings = Vec::new();
+        for run in runs["workflow_runs"].as_array().unwrap_or(&vec![]) {
+            let run_id = run["id"].as_i64().unwrap_or_default();
+            // https://docs.github.com/en/rest/actions/workflow-jobs?apiVersion=2022-11-28#list-jobs-for-a-workflow-run
+            let url = format!(
+                "{}/repos/{}/actions/runs/{}/jobs?filter=all&per_page=100",
+                self.rest_api_basepath, self.path, run_id
+            );
+            let run_jobs = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.request_headers(),
+                ApiOperation::Pipeline,
+            )?;
+            for job in run_jobs["jobs"].as_array().unwrap_or(&vec![]) {
+                // Skipped jobs never get a runner.
+                if job["conclusion"].as_str() == Some("skipped") {
+                    continue;
+                }
+                let (Some(created_at), Some(started_at), Some(completed_at)) = (
+                    time::json_optional_timestamp(job, "created_at")?,
+                    time::json_optional_timestamp(job, "started_at")?,
+                    time::json_optional_timestamp(job, "completed_at")?,
+                ) else {
+                    continue;
+                };
+                timings.push(
+                    JobTiming::builder()
+                        .pipeline_id(run_id)
+                        .name(job["name"].as_str().unwrap_or_default().to_string())
+                        .queued(time::duration_between(&created_at, &started_at) as f64)
+                        .duration(time::duration_between(&started_at, &completed_at) as f64)
+                        .runner(job["runner_name"].as_str().unwrap_or_default().to_string())
+                        .build()
+                        .unwrap(),
+                );
+            }
+        }
+        Ok(timings)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdPipelineStatus for Github<R> {
+    fn pipeline_status(&self, git_ref: &PipelineRef) -> Result<Option<PipelineStatus>> {
+        // https://docs.github.com/en/rest/actions/workflow-runs?apiVersion=2022-11-28#list-workflow-runs-for-a-repository
+        // Every workflow triggered by a commit has its own run. The runs of
+        // the most recent commit make up the pipeline.
+        let filter = match git_ref {
+            PipelineRef::Branch(branch) => format!("branch={}", branch),
+            PipelineRef::Sha(sha) => format!("head_sha={}", sha),
+        };
+        let url = format!(
+            "{}/repos/{}/actions/runs?{}&per_page=100",
+            self.rest_api_basepath, self.path, filter
+        );
+        let runs = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let runs = runs["workflow_runs"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let Some(head_sha) = runs.first().map(|run| run["head_sha"].clone()) else {
+            return Ok(None);
+        };
+        let runs = runs
+            .iter()
+            .filter(|run| run["head_sha"] == head_sha)
+            .collect::<Vec<_>>();
+        let mut jobs = Vec::new();
+        for run in runs.iter().rev() {
+            // https://docs.github.com/en/rest/actions/workflow-jobs?apiVersion=2022-11-28#list-jobs-for-a-workflow-run
+            let url = format!(
+                "{}/repos/{}/actions/runs/{}/jobs?filter=latest&per_page=100",
+                self.rest_api_basepath,
+                self.path,
+                run["id"].as_i64().unwrap_or_default()
+            );
+            let run_jobs = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.request_headers(),
+                ApiOperation::Pipeline,
+            )?;
+            for job in run_jobs["jobs"].as_array().unwrap_or(&vec![]) {
+                jobs.push(GithubJobFields::try_from(job)?.into());
+            }
+        }
+        let statuses = runs
+            .iter()
+            .map(|run| Ok(Pipeline::from(GithubPipelineFields::try_from(*run)?).status))
+            .collect::<Result<Vec<_>>>()?;
+        // The pipeline runs while any of its runs does, and fails if any of
+        // them failed.
+        let status = statuses
+            .iter()
+            .find(|status| status::is_active(status))
+            .or_else(|| statuses.iter().find(|status| status::is_failed(status)))
+            .unwrap_or(&statuses[0])
+            .clone();
+        let mut pipeline: Pipeline = GithubPipelineFields::try_from(runs[0])?.into();
+        pipeline.status = status;
+        Ok(Some(
+            PipelineStatus::builder()
+                .pipeline(pipeline)
+                .jobs(jobs)
+                .build()
+                .unwrap(),
+        ))
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdArtifact for Github<R> {
+    // https://docs.github.com/en/rest/actions/artifacts?apiVersion=2022-11-28#list-artifacts-for-a-repository
+    fn list(&self) -> Result<Vec<Artifact>> {
+        let url = format!(
+            "{}/repos/{}/actions/artifacts",
+            self.rest_api_basepath, self.path
+        );
+        let artifacts: Vec<Artifact> = query::paged(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            Some("artifacts"),
+            ApiOperation::Pipeline,
+            |value| Ok(GithubArtifactFields::try_from(value)?.into()),
+        )?;
+        Ok(artifacts
+            .into_iter()
+            .filter(|artifact| artifact.size > 0)
+            .collect())
+    }
+
+    // https://docs.github.com/en/rest/actions/artifacts?apiVersion=2022-11-28#delete-an-artifact
+    fn delete(&self, id: i64) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/actions/artifacts/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.github.com/en/rest/actions/artifacts?apiVersion=2022-11-28#download-an-artifact
+    fn read_archive(&self, id: i64, range: ByteRange) -> Result<Vec<u8>> {
+        let url = format!(
+            "{}/repos/{}/actions/artifacts/{}/zip",
+            self.rest_api_basepath, self.path, id
+        );
+        let mut headers = self.request_headers();
+        headers.set("Range", range.header_value()?);
+        let response =
+            query::get_raw::<_, ()>(&self.runner, &url, None, headers, ApiOperation::Pipeline)?;
+        range.response_data(response.status, response.body_bytes().to_vec())
+    }
+}
+
+pub struct GithubArtifactFields {
+    artifact: Artifact,
+}
+
+impl TryFrom<&serde_json::Value> for GithubArtifactFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        // Expired artifacts are already gone and take no storage.
+        let size = if data["expired"].as_bool().unwrap_or_default() {
+            0
+        } else {
+            data["size_in_bytes"].as_u64().unwrap_or_default()
+        };
+        Ok(GithubArtifactFields {
+            artifact: Artifact::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .name(data["name"].as_str().unwrap_or_default().to_string())
+                .size(size)
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GithubArtifactFields> for Artifact {
+    fn from(fields: GithubArtifactFields) -> Self {
+        fields.artifact
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdUsage for Github<R> {
+    fn usage(&self, group: &str) -> Result<Vec<RunnerUsage>> {
+        // https://docs.github.com/en/billing/using-the-new-billing-platform/automating-usage-reporting#get-billing-usage-report-for-an-organization
+        // Usage items are reported per day and SKU (runner OS). Minutes are
+        // added up per month and repository.
+        let url = format!(
+            "{}/organizations/{}/settings/billing/usage",
+            self.rest_api_basepath, group
+        );
+        let response = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let items = response["usageItems"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let mut minutes: BTreeMap<(String, String), f64> = BTreeMap::new();
+        for item in items {
+            let product = item["product"].as_str().unwrap_or_default();
+            let unit = item["unitType"].as_str().unwrap_or_default();
+            if !product.eq_ignore_ascii_case("actions") || !unit.eq_ignore_ascii_case("minutes") {
+                continue;
+            }
+            let month = item["date"]
+                .as_str()
+                .unwrap_or_default()
+                .chars()
+                .take(7)
+                .collect::<String>();
+            let repository = item["repositoryName"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            *minutes.entry((month, repository)).or_default() +=
+                item["quantity"].as_f64().unwrap_or_default();
+        }
+        Ok(minutes
+            .into_iter()
+            .map(|((month, project), minutes)| {
+                RunnerUsage::builder()
+                    .month(month)
+                    .project(project)
+                    .minutes(minutes)
+                    .build()
+                    .unwrap()
+            })
+            .collect())
+    }
+}
+
 impl<R: HttpRunner<Response = HttpResponse>> CicdJob for Github<R> {
     fn list(&self, _args: JobListBodyArgs) -> Result<Vec<Job>> {
         todo!();
@@ -97,15 +506,155 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdJob for Github<R> {
     ) -> Result<Option<crate::api_traits::NumberDeltaErr>> {
         todo!();
     }
+
+    // https://docs.github.com/en/rest/actions/workflow-jobs?apiVersion=2022-11-28#get-a-job-for-a-workflow-run
+    fn get(&self, job_id: i64) -> Result<Job> {
+        let job = self.get_job_json(job_id)?;
+        Ok(GithubJobFields::try_from(&job)?.into())
+    }
+
+    // https://docs.github.com/en/rest/actions/workflow-runs?apiVersion=2022-11-28#re-run-a-job-from-a-workflow-run
+    fn retry(&self, job_id: i64) -> Result<Job> {
+        let url = format!(
+            "{}/repos/{}/actions/jobs/{}/rerun",
+            self.rest_api_basepath, self.path, job_id
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            http::Method::POST,
+        )?;
+        CicdJob::get(self, job_id)
+    }
+
+    /// Github cancels whole workflow runs, so the rest of the jobs of the
+    /// run are cancelled too.
+    // https://docs.github.com/en/rest/actions/workflow-runs?apiVersion=2022-11-28#cancel-a-workflow-run
+    fn cancel(&self, job_id: i64) -> Result<Job> {
+        let job = self.get_job_json(job_id)?;
+        let url = format!(
+            "{}/repos/{}/actions/runs/{}/cancel",
+            self.rest_api_basepath,
+            self.path,
+            job["run_id"].as_i64().unwrap_or_default()
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+            http::Method::POST,
+        )?;
+        CicdJob::get(self, job_id)
+    }
+
+    fn play(&self, _job_id: i64) -> Result<Job> {
+        Err(GRError::OperationNotSupported(
+            "Github has no manual jobs. Use workflow_dispatch triggers instead".to_string(),
+        )
+        .into())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    fn get_job_json(&self, job_id: i64) -> Result<serde_json::Value> {
+        let url = format!(
+            "{}/repos/{}/actions/jobs/{}",
+            self.rest_api_basepath, self.path, job_id
+        );
+        query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Pipeline,
+        )
+    }
+}
+
+pub struct GithubJobFields {
+    job: Job,
+}
+
+impl TryFrom<&serde_json::Value> for GithubJobFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        let started_at = time::json_optional_timestamp(data, "started_at")?;
+        let completed_at = time::json_optional_timestamp(data, "completed_at")?;
+        Ok(GithubJobFields {
+            job: Job::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .name(data["name"].as_str().unwrap_or_default().to_string())
+                .branch(data["head_branch"].as_str().unwrap_or_default().to_string())
+                .url(data["html_url"].as_str().unwrap_or_default().to_string())
+                // Jobs do not carry the actor that triggered the run.
+                .author_name(String::new())
+                .commit_sha(data["head_sha"].as_str().unwrap_or_default().to_string())
+                .pipeline_id(data["run_id"].as_i64().unwrap_or_default())
+                .runner_tags(
+                    data["labels"]
+                        .as_array()
+                        .map(|labels| {
+                            labels
+                                .iter()
+                                .filter_map(|v| v.as_str())
+                                .map(|v| v.to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
+                .stage(
+                    data["workflow_name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                // Same as pipelines, the conclusion is the final state and
+                // the status the current one while running.
+                .status(
+                    data["conclusion"]
+                        .as_str()
+                        .or_else(|| data["status"].as_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                )
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .started_at(started_at)
+                .finished_at(completed_at)
+                .duration(match (started_at, completed_at) {
+                    (Some(started_at), Some(completed_at)) => {
+                        time::duration_between(&started_at, &completed_at).to_string()
+                    }
+                    _ => String::new(),
+                })
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GithubJobFields> for Job {
+    fn from(fields: GithubJobFields) -> Self {
+        fields.job
+    }
 }
 
 pub struct GithubPipelineFields {
     pipeline: Pipeline,
 }
 
-impl From<&serde_json::Value> for GithubPipelineFields {
-    fn from(pipeline_data: &serde_json::Value) -> Self {
-        GithubPipelineFields {
+impl TryFrom<&serde_json::Value> for GithubPipelineFields {
+    type Error = Error;
+
+    fn try_from(pipeline_data: &serde_json::Value) -> Result<Self> {
+        let created_at = time::json_timestamp(pipeline_data, "created_at")?;
+        let updated_at = time::json_timestamp(pipeline_data, "updated_at")?;
+        Ok(GithubPipelineFields {
             pipeline: Pipeline::builder()
                 .id(pipeline_data["id"].as_i64().unwrap_or_default())
                 // Github has `conclusion` as the final
@@ -128,15 +677,12 @@ impl From<&serde_json::Value> for GithubPipelineFields {
                 .web_url(pipeline_data["html_url"].as_str().unwrap().to_string())
                 .branch(pipeline_data["head_branch"].as_str().unwrap().to_string())
                 .sha(pipeline_data["head_sha"].as_str().unwrap().to_string())
-                .created_at(pipeline_data["created_at"].as_str().unwrap().to_string())
-                .updated_at(pipeline_data["updated_at"].as_str().unwrap().to_string())
-                .duration(time::compute_duration(
-                    pipeline_data["created_at"].as_str().unwrap(),
-                    pipeline_data["updated_at"].as_str().unwrap(),
-                ))
+                .created_at(created_at)
+                .updated_at(updated_at)
+                .duration(time::duration_between(&created_at, &updated_at))
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -159,6 +705,16 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn test_lint_validates_workflow_locally() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn Cicd);
+        let body = b"on: push\njobs:\n  build:\n    steps:\n      - run: make\n";
+        let response = github.lint(YamlBytes::new(body)).unwrap();
+        assert!(!response.valid);
+        assert_eq!(vec!["jobs:build is missing runs-on"], response.errors);
+    }
+
     #[test]
     fn test_list_actions() {
         let contracts = ResponseContracts::new(ContractType::Github).add_contract(
@@ -342,4 +898,233 @@ mod test {
         let runs = github.list(args).unwrap();
         assert_eq!("unknown", runs[0].status);
     }
+
+    #[test]
+    fn test_actions_minutes_usage_per_repository() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "billing_usage.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdUsage);
+        let usage = github.usage("jordilin").unwrap();
+        assert_eq!(
+            "https://api.github.com/organizations/jordilin/settings/billing/usage",
+            *client.url()
+        );
+        assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
+        assert_eq!(2, usage.len());
+        assert_eq!("2024-01", usage[0].month);
+        assert_eq!("jordilin/gitar", usage[0].project);
+        assert_eq!(12.0, usage[0].minutes);
+        assert_eq!("2024-02", usage[1].month);
+        assert_eq!("jordilin/githapi", usage[1].project);
+        assert_eq!(120.5, usage[1].minutes);
+    }
+
+    #[test]
+    fn test_list_artifacts_skips_expired() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_artifacts.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdArtifact);
+        let artifacts = github.list().unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/artifacts",
+            *client.url()
+        );
+        assert_eq!(1, artifacts.len());
+        assert_eq!(11, artifacts[0].id);
+        assert_eq!(556, artifacts[0].size);
+    }
+
+    #[test]
+    fn test_delete_artifact() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(204, None, None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdArtifact);
+        github.delete(11).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/artifacts/11",
+            *client.url()
+        );
+        assert_eq!(http::Method::DELETE, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_read_artifact_archive_range() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(206, Some("PK"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdArtifact);
+        let range = ByteRange::Span {
+            offset: 0,
+            length: 2,
+        };
+        assert_eq!(b"PK".to_vec(), github.read_archive(11, range).unwrap());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/artifacts/11/zip",
+            *client.url()
+        );
+        assert_eq!("bytes=0-1", client.headers().get("Range").unwrap());
+    }
+
+    #[test]
+    fn test_list_failed_jobs_of_failed_runs() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "list_run_jobs.json", None)
+            .add_contract(200, "list_pipelines.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJobLog);
+        let jobs = github.list_failed_jobs(20).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/7881917826/jobs?filter=latest&per_page=100",
+            *client.url()
+        );
+        // Successful jobs in a failed run are skipped.
+        assert_eq!(1, jobs.len());
+        assert_eq!(21611843123, jobs[0].id);
+        assert_eq!(7881917826, jobs[0].pipeline_id);
+        assert_eq!("build", jobs[0].name);
+    }
+
+    const JOB: &str = r#"{"id": 21611843123, "run_id": 7881917826, "name": "build", "status": "in_progress", "conclusion": null, "labels": ["ubuntu-latest"]}"#;
+
+    #[test]
+    fn test_pipeline_status_aggregates_runs_of_the_latest_commit() {
+        let runs = r#"{"workflow_runs": [
+            {"id": 2, "head_sha": "bbb", "head_branch": "main", "status": "completed", "conclusion": "failure", "html_url": "https://github.com/jordilin/githapi/actions/runs/2", "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:01:00Z"},
+            {"id": 3, "head_sha": "bbb", "head_branch": "main", "status": "completed", "conclusion": "success", "html_url": "https://github.com/jordilin/githapi/actions/runs/3", "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:01:00Z"},
+            {"id": 1, "head_sha": "aaa", "head_branch": "main", "status": "completed", "conclusion": "success", "html_url": "https://github.com/jordilin/githapi/actions/runs/1", "created_at": "2023-12-31T00:00:00Z", "updated_at": "2023-12-31T00:01:00Z"}
+        ]}"#;
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(200, Some(r#"{"jobs": [{"id": 20, "run_id": 2, "name": "test", "status": "completed", "conclusion": "failure"}]}"#), None)
+            .add_body(200, Some(r#"{"jobs": [{"id": 30, "run_id": 3, "name": "lint", "status": "completed", "conclusion": "success"}]}"#), None)
+            .add_body(200, Some(runs), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdPipelineStatus);
+        let status = github
+            .pipeline_status(&PipelineRef::Branch("main".to_string()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/2/jobs?filter=latest&per_page=100",
+            *client.url()
+        );
+        assert_eq!("failure", status.pipeline.status);
+        assert_eq!(2, status.jobs.len());
+    }
+
+    #[test]
+    fn test_cancel_job_cancels_its_run() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(200, Some(JOB), None)
+            .add_body::<String>(204, None, None)
+            .add_body(200, Some(JOB), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        github.cancel(21611843123).unwrap();
+        assert_eq!(
+            vec![http::Method::GET, http::Method::POST, http::Method::GET],
+            *client.http_method.borrow()
+        );
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/jobs/21611843123",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_retry_job() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(200, Some(JOB), None)
+            .add_body::<String>(201, None, None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        assert!(github.retry(21611843123).is_ok());
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_play_job_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn CicdJob);
+        let Err(err) = github.play(21611843123) else {
+            panic!("Expected an error");
+        };
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::OperationNotSupported(_)) => {}
+            _ => panic!("Expected OperationNotSupported"),
+        }
+    }
+
+    #[test]
+    fn test_get_job_log() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some("Error: OOMKilled"),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJobLog);
+        let log = github.get_log(21611843123).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/jobs/21611843123/logs",
+            *client.url()
+        );
+        assert_eq!("Error: OOMKilled", log);
+    }
+
+    #[test]
+    fn test_get_job_log_from_offset() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(206, Some("step 2\n"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJobLog);
+        let log = github.get_log_from(21611843123, 7).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/jobs/21611843123/logs",
+            *client.url()
+        );
+        assert_eq!(b"step 2\n".to_vec(), log);
+        assert_eq!("bytes=7-", client.headers().get("Range").unwrap());
+    }
+
+    #[test]
+    fn test_list_test_results_parses_job_logs() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(200, Some("test lint::test_fmt ... ok"), None)
+            .add_body(
+                200,
+                Some("test api::test_get ... FAILED\ntest api::test_post ... ok"),
+                None,
+            )
+            .add_contract(200, "list_run_jobs.json", None)
+            .add_contract(200, "list_pipelines.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdTestReport);
+        let results = github.list_test_results(20).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/jobs/21611843124/logs",
+            *client.url()
+        );
+        assert_eq!(3, results.len());
+        assert_eq!(7881917826, results[0].pipeline_id);
+        assert_eq!("api::test_get", results[0].name);
+        assert!(!results[0].passed);
+        assert_eq!("lint::test_fmt", results[2].name);
+    }
+
+    #[test]
+    fn test_list_job_timings_from_run_jobs() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "list_run_jobs.json", None)
+            .add_contract(200, "list_pipelines.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CicdJobTiming);
+        let timings = github.list_job_timings(20).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/runs/7881917826/jobs?filter=all&per_page=100",
+            *client.url()
+        );
+        assert_eq!(2, timings.len());
+        assert_eq!(7881917826, timings[0].pipeline_id);
+        assert_eq!("build", timings[0].name);
+        assert_eq!(1.0, timings[0].queued);
+        assert_eq!(6.0, timings[0].duration);
+        assert_eq!("GitHub Actions 2", timings[0].runner);
+    }
 }
@@ -0,0 +1,73 @@
+use crate::api_traits::{ApiOperation, ContractRecorder};
+use crate::cmds::contract::{ContractFixture, ContractOperation};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::query;
+use crate::Result;
+
+use super::Github;
+
+impl<R: HttpRunner<Response = HttpResponse>> ContractRecorder for Github<R> {
+    fn record(&self, operation: ContractOperation, id: i64) -> Result<ContractFixture> {
+        let (url, file_name, api_operation) = match operation {
+            ContractOperation::Project => (
+                format!("{}/repos/{}", self.rest_api_basepath, self.path),
+                "project.json",
+                ApiOperation::Project,
+            ),
+            ContractOperation::MergeRequest => (
+                format!(
+                    "{}/repos/{}/pulls/{}",
+                    self.rest_api_basepath, self.path, id
+                ),
+                "merge_request.json",
+                ApiOperation::MergeRequest,
+            ),
+            ContractOperation::Comment => (
+                format!(
+                    "{}/repos/{}/issues/{}/comments",
+                    self.rest_api_basepath, self.path, id
+                ),
+                "comment.json",
+                ApiOperation::MergeRequest,
+            ),
+            ContractOperation::AuthUser => (
+                format!("{}/user", self.rest_api_basepath),
+                "get_auth_user.json",
+                ApiOperation::Project,
+            ),
+        };
+        let payload = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            api_operation,
+        )?;
+        Ok(ContractFixture::new(
+            format!("github/{}", file_name),
+            payload,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::setup_client;
+    use crate::test::utils::{default_github, ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_record_auth_user_contract() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "get_auth_user.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn ContractRecorder);
+        let fixture = github.record(ContractOperation::AuthUser, 0).unwrap();
+        assert_eq!("github/get_auth_user.json", fixture.path);
+        assert_eq!("https://api.github.com/user", *client.url());
+        assert!(fixture.payload["login"].is_string());
+    }
+}
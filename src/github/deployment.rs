@@ -0,0 +1,151 @@
+use super::Github;
+use crate::api_traits::{ApiOperation, Deployment};
+use crate::cmds::deployment::{
+    DeploymentStatus, DeploymentStatusCliArgs, Environment, RollbackJob,
+};
+use crate::error::GRError;
+use crate::http::{self, Body};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::query;
+use crate::{time, Error, Result};
+
+impl<R: HttpRunner<Response = HttpResponse>> Deployment for Github<R> {
+    // https://docs.github.com/en/rest/deployments/statuses?apiVersion=2022-11-28#create-a-deployment-status
+    fn set_status(&self, args: DeploymentStatusCliArgs) -> Result<DeploymentStatus> {
+        let url = format!(
+            "{}/repos/{}/deployments/{}/statuses",
+            self.rest_api_basepath, self.path, args.id
+        );
+        let mut body = Body::new();
+        body.add("state", args.state.to_string());
+        if let Some(environment_url) = args.url {
+            body.add("environment_url", environment_url);
+        }
+        if let Some(description) = args.description {
+            body.add("description", description);
+        }
+        query::send(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Deployment,
+            |value| Ok(GithubDeploymentStatusFields::try_from(value)?.into()),
+            http::Method::POST,
+        )
+    }
+
+    fn stop_environment(&self, environment: &str) -> Result<Environment> {
+        Err(GRError::OperationNotSupported(format!(
+            "Stopping environment {} is not supported in Github",
+            environment
+        ))
+        .into())
+    }
+
+    fn rollback(&self, environment: &str, _deployment_id: i64) -> Result<RollbackJob> {
+        Err(GRError::OperationNotSupported(format!(
+            "Rolling back environment {} is not supported in Github",
+            environment
+        ))
+        .into())
+    }
+}
+
+pub struct GithubDeploymentStatusFields {
+    status: DeploymentStatus,
+}
+
+impl TryFrom<&serde_json::Value> for GithubDeploymentStatusFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        Ok(GithubDeploymentStatusFields {
+            status: DeploymentStatus::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .state(data["state"].as_str().unwrap_or_default().to_string())
+                .url(
+                    data["environment_url"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GithubDeploymentStatusFields> for DeploymentStatus {
+    fn from(fields: GithubDeploymentStatusFields) -> Self {
+        fields.status
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{
+        cmds::deployment::DeploymentState,
+        setup_client,
+        test::utils::{default_github, ContractType, ResponseContracts},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_set_deployment_status() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            201,
+            "create_deployment_status.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Deployment);
+        let args = DeploymentStatusCliArgs::builder()
+            .id(42)
+            .state(DeploymentState::Success)
+            .url(Some("https://app".to_string()))
+            .build()
+            .unwrap();
+        let status = github.set_status(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/deployments/42/statuses",
+            *client.url(),
+        );
+        assert_eq!(
+            Some(ApiOperation::Deployment),
+            *client.api_operation.borrow()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+        let body = client.request_body();
+        assert!(body.contains("\"state\":\"success\""));
+        assert!(body.contains("\"environment_url\":\"https://app\""));
+        assert_eq!("success", status.state);
+        assert_eq!("https://app", status.url);
+    }
+
+    #[test]
+    fn test_set_deployment_status_error() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(404, None, None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn Deployment);
+        let args = DeploymentStatusCliArgs::builder()
+            .id(42)
+            .state(DeploymentState::Failure)
+            .build()
+            .unwrap();
+        assert!(github.set_status(args).is_err());
+    }
+
+    #[test]
+    fn test_stop_environment_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn Deployment);
+        let err = github.stop_environment("production").unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::OperationNotSupported(_)) => {}
+            _ => panic!("Expected OperationNotSupported"),
+        }
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     cmds::gist::{Gist, GistListBodyArgs},
     io::{HttpResponse, HttpRunner},
     remote::{query, URLQueryParamBuilder},
-    Result,
+    time, Error, Result,
 };
 
 use super::Github;
@@ -20,7 +20,7 @@ impl<R: HttpRunner<Response = HttpResponse>> CodeGist for Github<R> {
             self.request_headers(),
             None,
             ApiOperation::Gist,
-            |value| GithubGistFields::from(value).into(),
+            |value| Ok(GithubGistFields::try_from(value)?.into()),
         )
     }
 
@@ -60,8 +60,10 @@ pub struct GithubGistFields {
     pub gist: Gist,
 }
 
-impl From<&serde_json::Value> for GithubGistFields {
-    fn from(value: &serde_json::Value) -> Self {
+impl TryFrom<&serde_json::Value> for GithubGistFields {
+    type Error = Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self> {
         let gist = Gist::builder()
             .url(value["html_url"].as_str().unwrap().to_string())
             .description(value["description"].as_str().unwrap().to_string())
@@ -74,10 +76,10 @@ impl From<&serde_json::Value> for GithubGistFields {
                     .collect::<Vec<String>>()
                     .join(","),
             )
-            .created_at(value["created_at"].as_str().unwrap_or("").to_string())
+            .created_at(time::json_optional_timestamp(value, "created_at")?)
             .build()
             .unwrap();
-        Self { gist }
+        Ok(Self { gist })
     }
 }
 
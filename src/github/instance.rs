@@ -0,0 +1,99 @@
+use crate::api_traits::{ApiOperation, RemoteInstance};
+use crate::cmds::remote::{version_at_least, RemoteInfo};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::json_loads;
+use crate::remote::query;
+use crate::Result;
+
+use super::Github;
+
+impl<R: HttpRunner<Response = HttpResponse>> RemoteInstance for Github<R> {
+    // Github Enterprise Server discloses its version in the meta endpoint.
+    // https://docs.github.com/en/enterprise-server@latest/rest/meta/meta#get-github-enterprise-server-meta-information
+    fn info(&self) -> Result<RemoteInfo> {
+        let url = format!("{}/meta", self.rest_api_basepath);
+        let response = query::get_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        let api_version = response
+            .header("x-github-api-version-selected")
+            .unwrap_or_default()
+            .to_string();
+        let meta = json_loads(&response.body)?;
+        let version = meta["installed_version"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let mut unavailable_features = Vec::new();
+        if api_version.is_empty() {
+            unavailable_features.push((
+                "API versioning".to_string(),
+                "Requests target API version 2022-11-28, available from Github Enterprise \
+                 Server 3.9"
+                    .to_string(),
+            ));
+        }
+        if !version_at_least(&version, 3, 12) {
+            unavailable_features.push((
+                "merge queue".to_string(),
+                "Requires Github Enterprise Server 3.12".to_string(),
+            ));
+        }
+        Ok(RemoteInfo::builder()
+            .domain(self.domain.clone())
+            .version(version)
+            .api_version(api_version)
+            .unavailable_features(unavailable_features)
+            .build()
+            .unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http::Headers;
+    use crate::setup_client;
+    use crate::test::utils::{default_github, ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_info_of_github_com() {
+        let mut headers = Headers::new();
+        headers.set("x-github-api-version-selected", "2022-11-28");
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(r#"{"verifiable_password_authentication":false}"#),
+            Some(headers),
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn RemoteInstance);
+        let info = github.info().unwrap();
+        assert_eq!("https://api.github.com/meta", *client.url());
+        assert_eq!("2022-11-28", info.api_version);
+        assert_eq!("", info.version);
+        assert!(info.unavailable_features.is_empty());
+    }
+
+    #[test]
+    fn test_info_of_outdated_enterprise_server() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(r#"{"installed_version":"3.8.4"}"#),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn RemoteInstance);
+        let info = github.info().unwrap();
+        assert_eq!("3.8.4", info.version);
+        assert_eq!(
+            vec!["API versioning", "merge queue"],
+            info.unavailable_features
+                .iter()
+                .map(|(feature, _)| feature.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::api_traits::{ApiOperation, Issue, NumberDeltaErr};
+use crate::cmds::issue::{
+    IssueBodyArgs, IssueCommentBodyArgs, IssueListBodyArgs, IssueResponse, IssueState,
+};
+use crate::http::{self, Body};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::contract::Contract;
+use crate::remote::query;
+use crate::time;
+use crate::Result;
+
+use super::{with_order_by, Github};
+
+impl<R: HttpRunner<Response = HttpResponse>> Issue for Github<R> {
+    fn create(&self, args: IssueBodyArgs) -> Result<IssueResponse> {
+        let url = format!("{}/repos/{}/issues", self.rest_api_basepath, self.path);
+        let mut body = Body::new();
+        body.add("title", serde_json::Value::from(args.title));
+        body.add("body", serde_json::Value::from(args.description));
+        if !args.labels.is_empty() {
+            body.add("labels", serde_json::Value::from(args.labels));
+        }
+        query::send_as::<_, serde_json::Value, GithubIssue, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Issue,
+            http::Method::POST,
+        )
+    }
+
+    fn get(&self, id: i64) -> Result<IssueResponse> {
+        let url = format!(
+            "{}/repos/{}/issues/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        query::get_as::<_, (), GithubIssue, _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Issue,
+        )
+    }
+
+    fn list(&self, args: IssueListBodyArgs) -> Result<Vec<IssueResponse>> {
+        let url = self.list_issues_url(&args, false)?;
+        let mut issues: Vec<IssueResponse> = query::paged_as::<_, GithubIssue, _>(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.request_headers(),
+            None,
+            ApiOperation::Issue,
+        )?;
+        // Github lists pull requests as issues too.
+        issues.retain(|issue| !issue.pull_request);
+        Ok(issues)
+    }
+
+    fn num_pages(&self, args: IssueListBodyArgs) -> Result<Option<u32>> {
+        let url = self.list_issues_url(&args, true)?;
+        query::num_pages(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Issue,
+        )
+    }
+
+    fn num_resources(&self, args: IssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.list_issues_url(&args, true)?;
+        query::num_resources(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Issue,
+        )
+    }
+
+    fn comment(&self, args: IssueCommentBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/issues/{}/comments",
+            self.rest_api_basepath, self.path, args.id
+        );
+        let mut body = Body::new();
+        body.add("body", args.comment);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Issue,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    fn close(&self, id: i64) -> Result<IssueResponse> {
+        let url = format!(
+            "{}/repos/{}/issues/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        let mut body = Body::new();
+        body.add("state", "closed");
+        query::send_as::<_, &str, GithubIssue, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Issue,
+            http::Method::PATCH,
+        )
+    }
+}
+
+impl<R> Github<R> {
+    fn list_issues_url(&self, args: &IssueListBodyArgs, num_pages: bool) -> Result<String> {
+        let state = match args.state {
+            IssueState::Opened => "open",
+            IssueState::Closed => "closed",
+            IssueState::All => "all",
+        };
+        let url = format!(
+            "{}/repos/{}/issues?state={}",
+            self.rest_api_basepath, self.path, state
+        );
+        let url = with_order_by(url, &args.list_args)?;
+        if num_pages {
+            return Ok(format!("{}&page=1", url));
+        }
+        Ok(url)
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubIssue {
+    number: i64,
+    title: String,
+    body: Option<String>,
+    user: GithubIssueUser,
+    state: String,
+    html_url: String,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_at: DateTime<Utc>,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    updated_at: DateTime<Utc>,
+    pull_request: Option<serde_json::Value>,
+}
+
+impl Contract for GithubIssue {
+    const OPTIONAL_FIELDS: &'static [&'static str] = &["pull_request"];
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubIssueUser {
+    login: String,
+}
+
+impl From<GithubIssue> for IssueResponse {
+    fn from(data: GithubIssue) -> Self {
+        IssueResponse::builder()
+            .id(data.number)
+            .title(data.title)
+            .description(data.body.unwrap_or_default())
+            .author(data.user.login)
+            .state(data.state)
+            .web_url(data.html_url)
+            .created_at(data.created_at)
+            .updated_at(data.updated_at)
+            .pull_request(data.pull_request.is_some())
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::setup_client;
+    use crate::test::utils::{default_github, ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_list_issues_skips_pull_requests() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_issues_user.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Issue);
+        let args = IssueListBodyArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let issues = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues?state=open",
+            *client.url()
+        );
+        assert_eq!(1, issues.len());
+        assert_eq!(24, issues[0].id);
+        assert_eq!("jdoe", issues[0].author);
+        assert_eq!(Some(ApiOperation::Issue), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_create_issue_with_labels() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(201, "issue.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn Issue);
+        let args = IssueBodyArgs::builder()
+            .title("Test issue".to_string())
+            .labels(vec!["bug".to_string(), "ui".to_string()])
+            .build()
+            .unwrap();
+        let issue = github.create(args).unwrap();
+        assert_eq!("https://github.com/jdoe/githapi/issues/24", issue.web_url);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!(serde_json::json!(["bug", "ui"]), body["labels"]);
+    }
+
+    #[test]
+    fn test_close_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(200, "issue.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn Issue);
+        github.close(24).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/24",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("closed", body["state"]);
+        assert_eq!(http::Method::PATCH, client.http_method.borrow()[0]);
+    }
+}
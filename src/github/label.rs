@@ -0,0 +1,145 @@
+use serde::Deserialize;
+
+use crate::api_traits::{ApiOperation, Label, NumberDeltaErr};
+use crate::cmds::label::{LabelBodyArgs, LabelListBodyArgs, LabelResponse};
+use crate::http::{self, Body};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::contract::Contract;
+use crate::remote::query;
+use crate::Result;
+
+use super::Github;
+
+impl<R: HttpRunner<Response = HttpResponse>> Label for Github<R> {
+    fn list(&self, args: LabelListBodyArgs) -> Result<Vec<LabelResponse>> {
+        let url = format!("{}/repos/{}/labels", self.rest_api_basepath, self.path);
+        query::paged_as::<_, GithubLabel, _>(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.request_headers(),
+            None,
+            ApiOperation::Project,
+        )
+    }
+
+    fn num_pages(&self) -> Result<Option<u32>> {
+        let url = format!(
+            "{}/repos/{}/labels?page=1",
+            self.rest_api_basepath, self.path
+        );
+        query::num_pages(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Project,
+        )
+    }
+
+    fn num_resources(&self) -> Result<Option<NumberDeltaErr>> {
+        let url = format!(
+            "{}/repos/{}/labels?page=1",
+            self.rest_api_basepath, self.path
+        );
+        query::num_resources(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Project,
+        )
+    }
+
+    fn create(&self, args: LabelBodyArgs) -> Result<LabelResponse> {
+        let url = format!("{}/repos/{}/labels", self.rest_api_basepath, self.path);
+        let mut body = Body::new();
+        body.add("name", args.name);
+        // Github colors go without the leading #
+        body.add("color", args.color.trim_start_matches('#').to_string());
+        if !args.description.is_empty() {
+            body.add("description", args.description);
+        }
+        query::send_as::<_, String, GithubLabel, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Project,
+            http::Method::POST,
+        )
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubLabel {
+    id: i64,
+    name: String,
+    color: String,
+    description: Option<String>,
+}
+
+impl Contract for GithubLabel {}
+
+impl From<GithubLabel> for LabelResponse {
+    fn from(data: GithubLabel) -> Self {
+        LabelResponse::builder()
+            .id(data.id)
+            .name(data.name)
+            // Same format as Gitlab
+            .color(format!("#{}", data.color))
+            .description(data.description.unwrap_or_default())
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::setup_client;
+    use crate::test::utils::{default_github, get_contract, ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_list_labels() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(format!(
+                "[{}]",
+                get_contract(ContractType::Github, "label.json")
+            )),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Label);
+        let args = LabelListBodyArgs::builder()
+            .list_args(None)
+            .build()
+            .unwrap();
+        let labels = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/labels",
+            *client.url()
+        );
+        assert_eq!(1, labels.len());
+        assert_eq!("bug", labels[0].name);
+        assert_eq!("#d73a4a", labels[0].color);
+    }
+
+    #[test]
+    fn test_create_label_strips_leading_hash_from_color() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_contract(201, "label.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn Label);
+        let args = LabelBodyArgs::builder()
+            .name("bug".to_string())
+            .color("#d73a4a".to_string())
+            .description("Something isn't working".to_string())
+            .build()
+            .unwrap();
+        github.create(args).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("d73a4a", body["color"]);
+        assert_eq!("Something isn't working", body["description"]);
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+}
@@ -1,46 +1,134 @@
-use super::Github;
+use super::{with_order_by, Github};
 use crate::{
-    api_traits::{ApiOperation, CommentMergeRequest, MergeRequest, NumberDeltaErr, RemoteProject},
+    api_defaults::MAX_PER_PAGE,
+    api_traits::{
+        ApiOperation, CommentMergeRequest, MergeQueue, MergeRequest, MergeRequestDependency,
+        MergeRequestDiff, MergeRequestDiscussion, MergeRequestReaction, MergeRequestRebase,
+        MergeRequestReview, MergeRequestSuggestion, NumberDeltaErr, RemoteProject,
+        ReviewMergeRequest,
+    },
     cli::browse::BrowseOptions,
     cmds::{
         merge_request::{
-            Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs,
-            MergeRequestBodyArgs, MergeRequestListBodyArgs, MergeRequestResponse,
-            MergeRequestState,
+            review::ReviewFile, Comment, CommentMergeRequestBodyArgs,
+            CommentMergeRequestListBodyArgs, Discussion, DiscussionReplyBodyArgs, FileChange,
+            FileChangeStatus, MergeMethod, MergeQueueEntry, MergeRequestBodyArgs,
+            MergeRequestListBodyArgs, MergeRequestMergeBodyArgs, MergeRequestResponse,
+            MergeRequestState, Reaction, ReactionBodyArgs, RebaseStatus, ReviewBodyArgs,
+            ReviewEvent, Suggestion, SuggestionBodyArgs,
         },
         project::MrMemberType,
     },
     http::{self, Body},
     io::{HttpResponse, HttpRunner},
     json_loads,
-    remote::query,
+    remote::{self, contract::Contract, query},
+    time::{self, Seconds},
 };
 
 use crate::{error, Result};
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use std::{cmp::Reverse, collections::HashMap};
 
-impl<R> Github<R> {
-    fn url_list_merge_requests(&self, args: &MergeRequestListBodyArgs) -> String {
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    fn url_list_merge_requests(&self, args: &MergeRequestListBodyArgs) -> Result<String> {
         let state = match args.state {
             MergeRequestState::Opened => "open".to_string(),
             // Github has no distinction between closed and merged. A merged
             // pull request is considered closed.
             MergeRequestState::Closed | MergeRequestState::Merged => "closed".to_string(),
         };
-        if args.assignee.is_some() {
-            return format!(
+        let user_filter = args.assignee.is_some() || args.author.is_some();
+        let across_repos = args.group.is_some() || (user_filter && !args.project_only);
+        if across_repos && args.milestone.is_some() {
+            return Err(error::GRError::OperationNotSupported(
+                "Github filters by milestone the pull requests of a repository only".to_string(),
+            )
+            .into());
+        }
+        let mut url = if let Some(org) = &args.group {
+            format!(
+                "{}/orgs/{}/issues?state={}&filter=all",
+                self.rest_api_basepath, org, state
+            )
+        } else if across_repos && args.assignee.is_some() {
+            format!(
                 "{}/issues?state={}&filter=assigned",
                 self.rest_api_basepath, state
-            );
-        } else if args.author.is_some() {
-            return format!(
+            )
+        } else if across_repos {
+            format!(
                 "{}/issues?state={}&filter=created",
                 self.rest_api_basepath, state
-            );
+            )
+        } else if args.labels.is_empty() && args.milestone.is_none() && !user_filter {
+            format!(
+                "{}/repos/{}/pulls?state={}",
+                self.rest_api_basepath, self.path, state
+            )
+        } else {
+            // The pulls endpoint does not filter by labels, milestone nor
+            // user, the issues one does.
+            format!(
+                "{}/repos/{}/issues?state={}",
+                self.rest_api_basepath, self.path, state
+            )
+        };
+        if !across_repos {
+            if let Some(author) = &args.author {
+                url.push_str("&creator=");
+                url.push_str(&remote::encode_query_param(&author.username));
+            }
+            if let Some(assignee) = &args.assignee {
+                url.push_str("&assignee=");
+                url.push_str(&remote::encode_query_param(&assignee.username));
+            }
         }
-        format!(
-            "{}/repos/{}/pulls?state={}",
-            self.rest_api_basepath, self.path, state
-        )
+        if !args.labels.is_empty() {
+            url.push_str("&labels=");
+            url.push_str(&remote::encode_query_param(&args.labels.join(",")));
+        }
+        if let Some(milestone) = &args.milestone {
+            url.push_str("&milestone=");
+            url.push_str(&self.milestone_number(milestone)?);
+        }
+        with_order_by(url, &args.list_args)
+    }
+
+    /// Number of the milestone with the given title, the only milestone
+    /// reference the issues endpoint takes.
+    fn milestone_number(&self, milestone: &str) -> Result<String> {
+        if milestone.parse::<u64>().is_ok() {
+            return Ok(milestone.to_string());
+        }
+        let url = format!(
+            "{}/repos/{}/milestones?state=all&per_page=100",
+            self.rest_api_basepath, self.path
+        );
+        let milestones = query::send_json::<_, String>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+            http::Method::GET,
+        )?;
+        milestones
+            .as_array()
+            .and_then(|milestones| {
+                milestones
+                    .iter()
+                    .find(|candidate| candidate["title"] == milestone)
+            })
+            .and_then(|milestone| milestone["number"].as_i64())
+            .map(|number| number.to_string())
+            .ok_or_else(|| {
+                error::GRError::PreconditionNotMet(format!("Milestone {} not found", milestone))
+                    .into()
+            })
     }
 
     fn resource_comments_metadata_url(&self, args: CommentMergeRequestListBodyArgs) -> String {
@@ -126,7 +214,7 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
                                 let mut body = Body::new();
                                 let assignees = vec![args.assignee.username.as_str()];
                                 body.add("assignees", &assignees);
-                                query::send_raw(
+                                let response = query::send_raw_unchecked(
                                     &self.runner,
                                     &issues_url,
                                     Some(&body),
@@ -134,38 +222,79 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
                                     ApiOperation::MergeRequest,
                                     http::Method::PATCH,
                                 )?;
+                                // Users that are not members of the repository
+                                // cannot assign. The pull request is already
+                                // open, so keep it unassigned.
+                                match response.status {
+                                    403 | 404 => eprintln!(
+                                        "Warning: not allowed to assign pull requests in {}. \
+                                         Opened without assignee",
+                                        self.path
+                                    ),
+                                    _ if !response.is_ok(&http::Method::PATCH) => {
+                                        return Err(query::request_error(
+                                            &http::Method::PATCH,
+                                            &issues_url,
+                                            &ApiOperation::MergeRequest,
+                                            &response,
+                                        ));
+                                    }
+                                    _ => (),
+                                }
                             }
                         }
+                        // Labels API
+                        // https://docs.github.com/en/rest/issues/labels#add-labels-to-an-issue
+                        if !args.labels.is_empty() {
+                            let labels_url = format!(
+                                "{}/repos/{}/issues/{}/labels",
+                                self.rest_api_basepath, self.path, id
+                            );
+                            let mut body = Body::new();
+                            let labels = args.labels.iter().map(|l| l.as_str()).collect::<Vec<_>>();
+                            body.add("labels", &labels);
+                            query::send_raw(
+                                &self.runner,
+                                &labels_url,
+                                Some(&body),
+                                self.request_headers(),
+                                ApiOperation::MergeRequest,
+                                http::Method::POST,
+                            )?;
+                        }
                         // Requested reviewers API
                         // https://docs.github.com/en/rest/pulls/review-requests?apiVersion=2022-11-28#request-reviewers-for-a-pull-request
-                        match args.reviewer.mr_member_type {
-                            MrMemberType::Empty => (),
-                            MrMemberType::Filled => {
-                                let mut body = Body::new();
-                                let reviewers = vec![args.reviewer.username.as_str()];
-                                body.add("reviewers", &reviewers);
-                                let requested_reviewers_url =
-                                    format!("{}/{}/requested_reviewers", mr_url, id);
+                        let reviewers = args
+                            .reviewers
+                            .iter()
+                            .filter(|reviewer| reviewer.mr_member_type == MrMemberType::Filled)
+                            .map(|reviewer| reviewer.username.as_str())
+                            .collect::<Vec<_>>();
+                        if !reviewers.is_empty() {
+                            let mut body = Body::new();
+                            body.add("reviewers", &reviewers);
+                            let requested_reviewers_url =
+                                format!("{}/{}/requested_reviewers", mr_url, id);
 
-                                let response = query::send_raw(
-                                    &self.runner,
+                            let response = query::send_raw(
+                                &self.runner,
+                                &requested_reviewers_url,
+                                Some(&body),
+                                self.request_headers(),
+                                ApiOperation::MergeRequest,
+                                http::Method::POST,
+                            )?;
+                            // Consider 422 failure - Reviewer not a collaborator
+                            if response.status != 201 {
+                                return Err(query::request_error(
+                                    &http::Method::POST,
                                     &requested_reviewers_url,
-                                    Some(&body),
-                                    self.request_headers(),
-                                    ApiOperation::MergeRequest,
-                                    http::Method::POST,
-                                )?;
-                                // Consider 422 failure - Reviewer not a collaborator
-                                if response.status != 201 {
-                                    return Err(query::query_error(
-                                        &requested_reviewers_url,
-                                        &response,
-                                    )
-                                    .into());
-                                }
+                                    &ApiOperation::MergeRequest,
+                                    &response,
+                                ));
                             }
                         }
-                        Ok(GithubMergeRequestFields::from(&merge_request_json).into())
+                        query::decode::<_, GithubMergeRequest, _>(&self.runner, &merge_request_json)
                     }
                     422 => {
                         // There is an existing pull request already.
@@ -242,18 +371,22 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
     }
 
     fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
-        let url = self.url_list_merge_requests(&args);
-        let response = query::paged::<_, MergeRequestResponse>(
+        let url = self.url_list_merge_requests(&args)?;
+        let response = query::paged_as::<_, GithubMergeRequest, MergeRequestResponse>(
             &self.runner,
             &url,
             args.list_args,
             self.request_headers(),
             None,
             ApiOperation::MergeRequest,
-            |value| GithubMergeRequestFields::from(value).into(),
         );
-        if args.assignee.is_some() || args.author.is_some() {
-            // Pull requests for the current authenticated user.
+        if args.assignee.is_some()
+            || args.author.is_some()
+            || args.group.is_some()
+            || !args.labels.is_empty()
+            || args.milestone.is_some()
+        {
+            // Pull requests for the current authenticated user or organization.
             // Filter those responses that have pull_request not empty See ref:
             // https://docs.github.com/en/rest/issues/issues?apiVersion=2022-11-28#list-issues-assigned-to-the-authenticated-user
             // Quoting Github's docs: Note: GitHub's REST API considers every
@@ -274,17 +407,40 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
         response
     }
 
-    fn merge(&self, id: i64) -> Result<MergeRequestResponse> {
+    // Merge commits created through the API are signed with the Github key,
+    // the API has no signing options.
+    fn merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
         // https://docs.github.com/en/rest/pulls/pulls?apiVersion=2022-11-28#merge-a-pull-request
         //  /repos/{owner}/{repo}/pulls/{pull_number}/merge
+        let id = args.id;
+        if args.auto_merge {
+            return self.enable_auto_merge(args);
+        }
+        let method = merge_method(&args);
         let url = format!(
             "{}/repos/{}/pulls/{}/merge",
             self.rest_api_basepath, self.path, id
         );
-        query::send_json::<_, ()>(
+        let mut body = Body::new();
+        // Github takes the first line as the title of the merge commit.
+        if let Some(message) = args.message {
+            let (title, message) = message.split_once('\n').unwrap_or((&message, ""));
+            body.add("commit_title", title.to_string());
+            if !message.trim().is_empty() {
+                body.add("commit_message", message.trim().to_string());
+            }
+        }
+        // Github refuses to merge if the head of the pull request does not match.
+        if let Some(sha) = args.sha {
+            body.add("sha", sha);
+        }
+        if let Some(method) = method {
+            body.add("merge_method", method.to_lowercase());
+        }
+        query::send_json::<_, String>(
             &self.runner,
             &url,
-            None,
+            Some(&body),
             self.request_headers(),
             ApiOperation::MergeRequest,
             http::Method::PUT,
@@ -295,6 +451,16 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
         //     "merged": true,
         //     "message": "Pull Request successfully merged"
         // }
+        // The pull request is merged already. Failing to delete its head
+        // branch does not fail the merge.
+        if args.delete_source_branch {
+            if let Err(err) = self.delete_head_branch(id) {
+                eprintln!(
+                    "Warning: head branch of pull request {} not deleted: {}",
+                    id, err
+                );
+            }
+        }
 
         // We do not have the id nor the url available in the response. Compute
         // it and return it to the client so we can open the url if needed.
@@ -310,13 +476,12 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
             "{}/repos/{}/pulls/{}",
             self.rest_api_basepath, self.path, id
         );
-        query::get::<_, (), _>(
+        query::get_as::<_, (), GithubMergeRequest, _>(
             &self.runner,
             &url,
             None,
             self.request_headers(),
             ApiOperation::MergeRequest,
-            |value| GithubMergeRequestFields::from(value).into(),
         )
     }
 
@@ -327,31 +492,57 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Github<R> {
         );
         let mut body = Body::new();
         body.add("state", "closed");
-        query::send::<_, &str, _>(
+        query::send_as::<_, &str, GithubMergeRequest, _>(
             &self.runner,
             &url,
             Some(&body),
             self.request_headers(),
             ApiOperation::MergeRequest,
-            |value| GithubMergeRequestFields::from(value).into(),
             http::Method::PATCH,
         )
     }
 
     fn num_pages(&self, args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
-        let url = self.url_list_merge_requests(&args) + "&page=1";
+        let url = self.url_list_merge_requests(&args)? + "&page=1";
         let headers = self.request_headers();
         query::num_pages(&self.runner, &url, headers, ApiOperation::MergeRequest)
     }
 
     fn num_resources(&self, args: MergeRequestListBodyArgs) -> Result<Option<NumberDeltaErr>> {
-        let url = self.url_list_merge_requests(&args) + "&page=1";
+        let url = self.url_list_merge_requests(&args)? + "&page=1";
         let headers = self.request_headers();
         query::num_resources(&self.runner, &url, headers, ApiOperation::MergeRequest)
     }
 
-    fn approve(&self, _id: i64) -> Result<MergeRequestResponse> {
-        todo!()
+    // Approvals are reviews with the APPROVE event. The review response does
+    // not carry the pull request, so the URL is built from the id.
+    fn approve(&self, id: i64) -> Result<MergeRequestResponse> {
+        let args = ReviewBodyArgs::builder()
+            .id(id)
+            .event(ReviewEvent::Approve)
+            .build()
+            .unwrap();
+        self.review(args)?;
+        Ok(MergeRequestResponse::builder()
+            .id(id)
+            .web_url(self.get_url(BrowseOptions::MergeRequestId(id)))
+            .build()
+            .unwrap())
+    }
+
+    // The draft field of the REST API can only be set when opening the pull
+    // request, updates go through GraphQL.
+    // https://docs.github.com/en/graphql/reference/mutations#convertpullrequesttodraft
+    // https://docs.github.com/en/graphql/reference/mutations#markpullrequestreadyforreview
+    fn toggle_draft(&self, id: i64, draft: bool) -> Result<MergeRequestResponse> {
+        let query = if draft {
+            "mutation($id: ID!) { convertPullRequestToDraft(input: {pullRequestId: $id}) { pullRequest { number } } }"
+        } else {
+            "mutation($id: ID!) { markPullRequestReadyForReview(input: {pullRequestId: $id}) { pullRequest { number } } }"
+        };
+        let node_id = self.pull_request_node_id(id)?;
+        self.pull_request_graphql(id, query, serde_json::json!({ "id": node_id }))?;
+        self.get(id)
     }
 }
 
@@ -374,19 +565,53 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Github<R> {
         Ok(())
     }
 
+    // Comments are addressed by id regardless of the pull request.
+    fn update(&self, comment_id: i64, args: CommentMergeRequestBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/issues/comments/{}",
+            self.rest_api_basepath, self.path, comment_id
+        );
+        let mut body = Body::new();
+        body.add("body", args.comment);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PATCH,
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, _id: i64, comment_id: i64) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/issues/comments/{}",
+            self.rest_api_basepath, self.path, comment_id
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
     fn list(&self, args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>> {
         let url = format!(
             "{}/repos/{}/issues/{}/comments",
             self.rest_api_basepath, self.path, args.id
         );
-        query::paged(
+        query::paged_as::<_, GithubMergeRequestComment, _>(
             &self.runner,
             &url,
             args.list_args,
             self.request_headers(),
             None,
             ApiOperation::MergeRequest,
-            |value| GithubMergeRequestCommentFields::from(value).into(),
         )
     }
 
@@ -414,245 +639,1527 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Github<R> {
     }
 }
 
-pub struct GithubMergeRequestFields {
-    fields: MergeRequestResponse,
-}
+const MERGE_QUEUE_ENTRY_FIELDS: &str = r#"
+    position
+    state
+    estimatedTimeToMerge
+    pullRequest { number baseRefName }
+"#;
+
+/// Merge queues are only available in the Github GraphQL API.
+impl<R: HttpRunner<Response = HttpResponse>> MergeQueue for Github<R> {
+    // https://docs.github.com/en/graphql/reference/mutations#enqueuepullrequest
+    fn add(&self, id: i64) -> Result<MergeQueueEntry> {
+        let query = format!(
+            "mutation($id: ID!) {{ enqueuePullRequest(input: {{pullRequestId: $id}}) {{ mergeQueueEntry {{ {} }} }} }}",
+            MERGE_QUEUE_ENTRY_FIELDS
+        );
+        let node_id = self.pull_request_node_id(id)?;
+        let response =
+            self.pull_request_graphql(id, &query, serde_json::json!({ "id": node_id }))?;
+        Ok(merge_queue_entry(
+            id,
+            &response["data"]["enqueuePullRequest"]["mergeQueueEntry"],
+        ))
+    }
+
+    // https://docs.github.com/en/graphql/reference/mutations#dequeuepullrequest
+    fn remove(&self, id: i64) -> Result<()> {
+        let query = "mutation($id: ID!) { dequeuePullRequest(input: {id: $id}) { mergeQueueEntry { id } } }";
+        let node_id = self.pull_request_node_id(id)?;
+        self.pull_request_graphql(id, query, serde_json::json!({ "id": node_id }))?;
+        Ok(())
+    }
 
-impl From<&serde_json::Value> for GithubMergeRequestFields {
-    fn from(merge_request_data: &serde_json::Value) -> Self {
-        GithubMergeRequestFields {
-            fields: MergeRequestResponse::builder()
-                .id(merge_request_data["number"].as_i64().unwrap())
-                .web_url(merge_request_data["html_url"].as_str().unwrap().to_string())
-                .source_branch(
-                    merge_request_data["head"]["ref"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .sha(
-                    merge_request_data["merge_commit_sha"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .author(
-                    merge_request_data["user"]["login"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .updated_at(
-                    merge_request_data["updated_at"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .created_at(
-                    merge_request_data["created_at"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .title(
-                    merge_request_data["title"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .pull_request(
-                    merge_request_data["pull_request"]["html_url"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .description(
-                    merge_request_data["body"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .merged_at(
-                    merge_request_data["merged_at"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                // Not available in the response. Set it to the same ID as the pull request
-                .pipeline_id(Some(merge_request_data["number"].as_i64().unwrap()))
-                .pipeline_url(
-                    merge_request_data["html_url"]
-                        .as_str()
-                        .map(|url| format!("{}/checks", url)),
-                )
-                .build()
-                .unwrap(),
+    // https://docs.github.com/en/graphql/reference/objects#mergequeueentry
+    fn status(&self, id: i64) -> Result<MergeQueueEntry> {
+        let query = format!(
+            "query($owner: String!, $name: String!, $number: Int!) {{ repository(owner: $owner, name: $name) {{ pullRequest(number: $number) {{ mergeQueueEntry {{ {} }} }} }} }}",
+            MERGE_QUEUE_ENTRY_FIELDS
+        );
+        let (owner, name) = self.path.split_once('/').unwrap_or_default();
+        let response = self.pull_request_graphql(
+            id,
+            &query,
+            serde_json::json!({ "owner": owner, "name": name, "number": id }),
+        )?;
+        let entry = &response["data"]["repository"]["pullRequest"]["mergeQueueEntry"];
+        if entry.is_null() {
+            return Err(error::GRError::PreconditionNotMet(format!(
+                "Pull request {} is not in the merge queue",
+                id
+            ))
+            .into());
         }
+        Ok(merge_queue_entry(id, entry))
     }
 }
 
-impl From<GithubMergeRequestFields> for MergeRequestResponse {
-    fn from(fields: GithubMergeRequestFields) -> Self {
-        fields.fields
+/// Github updates the pull request branch by merging the base branch into it.
+/// The update is done once the head of the pull request moves.
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestRebase for Github<R> {
+    // https://docs.github.com/en/rest/pulls/pulls#update-a-pull-request-branch
+    fn rebase(&self, id: i64) -> Result<RebaseStatus> {
+        let head_sha = self.get(id)?.head_sha;
+        let url = format!(
+            "{}/repos/{}/pulls/{}/update-branch",
+            self.rest_api_basepath, self.path, id
+        );
+        let mut body = Body::new();
+        body.add("expected_head_sha", head_sha.clone());
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(RebaseStatus::builder()
+            .id(id)
+            .in_progress(true)
+            .sha(head_sha)
+            .build()
+            .unwrap())
+    }
+
+    fn rebase_status(&self, started: &RebaseStatus) -> Result<RebaseStatus> {
+        let head_sha = self.get(started.id)?.head_sha;
+        Ok(RebaseStatus::builder()
+            .id(started.id)
+            .in_progress(head_sha == started.sha)
+            .sha(head_sha)
+            .build()
+            .unwrap())
     }
 }
 
-pub struct GithubMergeRequestCommentFields {
-    comment: Comment,
+lazy_static! {
+    static ref DEPENDS_ON: Regex = Regex::new(r"(?mi)^depends on #(\d+)\s*$").unwrap();
 }
 
-impl From<&serde_json::Value> for GithubMergeRequestCommentFields {
-    fn from(comment_data: &serde_json::Value) -> Self {
-        GithubMergeRequestCommentFields {
-            comment: Comment::builder()
-                .id(comment_data["id"].as_i64().unwrap())
-                .author(comment_data["user"]["login"].as_str().unwrap().to_string())
-                .created_at(comment_data["created_at"].as_str().unwrap().to_string())
-                .body(comment_data["body"].as_str().unwrap().to_string())
-                .build()
-                .unwrap(),
+/// Github has no notion of pull request dependencies. They are recorded as
+/// `Depends on #<id>` lines in the pull request description.
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestDependency for Github<R> {
+    fn add_dependency(&self, id: i64, depends_on: i64) -> Result<()> {
+        let pull_request = self.get(id)?;
+        if depends_on_ids(&pull_request.description).contains(&depends_on) {
+            return Ok(());
         }
+        let marker = format!("Depends on #{}", depends_on);
+        let description = if pull_request.description.is_empty() {
+            marker
+        } else {
+            format!("{}\n\n{}", pull_request.description.trim_end(), marker)
+        };
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        let mut body = Body::new();
+        body.add("body", description);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PATCH,
+        )?;
+        Ok(())
     }
-}
 
-impl From<GithubMergeRequestCommentFields> for Comment {
-    fn from(fields: GithubMergeRequestCommentFields) -> Self {
-        fields.comment
+    fn list_dependencies(&self, id: i64) -> Result<Vec<MergeRequestResponse>> {
+        let pull_request = self.get(id)?;
+        depends_on_ids(&pull_request.description)
+            .into_iter()
+            .map(|depends_on| self.get(depends_on))
+            .collect()
     }
 }
 
-#[cfg(test)]
-mod test {
-
-    use crate::{
-        cmds::project::{Member, MrMemberType},
-        http::{self, Headers},
-        remote::ListBodyArgs,
-        setup_client,
-        test::utils::{
-            default_github, get_contract, BasePath, ClientType, ContractType, Domain,
-            ResponseContracts,
-        },
-    };
-
-    use super::*;
+fn depends_on_ids(description: &str) -> Vec<i64> {
+    DEPENDS_ON
+        .captures_iter(description)
+        .filter_map(|caps| caps[1].parse().ok())
+        .collect()
+}
 
-    #[test]
-    fn test_open_merge_request() {
-        let responses = ResponseContracts::new(ContractType::Github)
-            .add_contract(201, "merge_request.json", None)
-            .add_contract(200, "merge_request.json", None)
-            .add_contract(201, "merge_request.json", None);
-        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
-        let assignee = Member::builder()
-            .name("tom".to_string())
-            .username("tsawyer".to_string())
-            .mr_member_type(MrMemberType::Filled)
-            .id(1234)
-            .build()
-            .unwrap();
-        let reviewer = Member::builder()
-            .name("huck".to_string())
-            .username("hfinn".to_string())
-            .mr_member_type(MrMemberType::Filled)
-            .id(45678)
-            .build()
-            .unwrap();
-        let mr_args = MergeRequestBodyArgs::builder()
-            .assignee(assignee)
-            .reviewer(reviewer)
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    /// Auto-merge is only available in the Github GraphQL API. The pull request
+    /// gets merged once its required checks pass.
+    // https://docs.github.com/en/graphql/reference/mutations#enablepullrequestautomerge
+    fn enable_auto_merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
+        if args.delete_source_branch {
+            return Err(error::GRError::PreconditionNotMet(
+                "Github deletes the branch after an auto-merge only if the repository is set to automatically delete head branches".to_string(),
+            )
+            .into());
+        }
+        let query = "mutation($id: ID!, $headline: String, $body: String, $sha: GitObjectID, $method: PullRequestMergeMethod) { enablePullRequestAutoMerge(input: {pullRequestId: $id, commitHeadline: $headline, commitBody: $body, expectedHeadOid: $sha, mergeMethod: $method}) { pullRequest { number } } }";
+        let (headline, body) = match &args.message {
+            Some(message) => {
+                let (title, message) = message.split_once('\n').unwrap_or((message, ""));
+                let message = message.trim();
+                (Some(title), (!message.is_empty()).then_some(message))
+            }
+            None => (None, None),
+        };
+        let node_id = self.pull_request_node_id(args.id)?;
+        self.pull_request_graphql(
+            args.id,
+            query,
+            serde_json::json!({
+                "id": node_id,
+                "headline": headline,
+                "body": body,
+                "sha": args.sha,
+                "method": merge_method(&args),
+            }),
+        )?;
+        Ok(MergeRequestResponse::builder()
+            .id(args.id)
+            .web_url(self.get_url(BrowseOptions::MergeRequestId(args.id)))
             .build()
-            .unwrap();
-        let response = github.open(mr_args).unwrap();
-        assert_eq!(23, response.id);
-        assert_eq!(
-            "https://github.com/jordilin/githapi/pull/23",
-            response.web_url
-        );
-        assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls/23/requested_reviewers",
-            *client.url(),
-        );
-        let actual_method = client.http_method.borrow();
-        assert_eq!(http::Method::POST, actual_method[0]);
-        // Assignee call
-        assert_eq!(http::Method::PATCH, actual_method[1]);
-        // Reviewer call
-        assert_eq!(http::Method::POST, actual_method[2]);
-        assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
-        );
+            .unwrap())
     }
 
-    #[test]
-    fn test_open_merge_request_with_assignee_no_reviewer() {
-        let responses = ResponseContracts::new(ContractType::Github)
-            .add_contract(200, "merge_request.json", None)
-            .add_contract(201, "merge_request.json", None);
-        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
-        let assignee = Member::builder()
-            .name("tom".to_string())
-            .username("tsawyer".to_string())
-            .mr_member_type(MrMemberType::Filled)
-            .id(1234)
-            .build()
-            .unwrap();
-        let reviewer = Member::default(); // Default member is empty/placeholder
-        let mr_args = MergeRequestBodyArgs::builder()
-            .assignee(assignee)
-            .reviewer(reviewer)
-            .build()
-            .unwrap();
-        let response = github.open(mr_args).unwrap();
-        assert_eq!(23, response.id);
-        assert_eq!(
-            "https://github.com/jordilin/githapi/pull/23",
-            response.web_url
-        );
-        assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/issues/23",
-            *client.url(),
+    /// The head branch lives in the fork the pull request comes from, if any.
+    // https://docs.github.com/en/rest/git/refs?apiVersion=2022-11-28#delete-a-reference
+    fn delete_head_branch(&self, id: i64) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, id
         );
-        let actual_method = client.http_method.borrow();
-        assert_eq!(http::Method::PATCH, actual_method[1]);
-        // The open create merge request was a POST.
-        assert_eq!(http::Method::POST, actual_method[0]);
-        assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+        let pull_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let head = &pull_request["head"];
+        // The head repository is null once the fork it lives in is deleted.
+        let (Some(repo), Some(branch)) = (
+            head["repo"]["full_name"].as_str(),
+            head["ref"].as_str().filter(|branch| !branch.is_empty()),
+        ) else {
+            return Err(error::GRError::PreconditionNotMet(
+                "its repository or branch no longer exists".to_string(),
+            )
+            .into());
+        };
+        let url = format!(
+            "{}/repos/{}/git/refs/heads/{}",
+            self.rest_api_basepath, repo, branch
         );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::DELETE,
+        )?;
+        Ok(())
     }
 
-    #[test]
-    fn test_open_merge_request_with_reviewer_no_assignee() {
-        let responses = ResponseContracts::new(ContractType::Github)
-            .add_contract(201, "merge_request.json", None)
-            .add_contract(201, "merge_request.json", None);
+    fn pull_request_node_id(&self, id: i64) -> Result<String> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        let pull_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        Ok(pull_request["node_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    fn pull_request_graphql(
+        &self,
+        id: i64,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let mut body = Body::new();
+        body.add("query", serde_json::json!(query));
+        body.add("variables", variables);
+        let url = format!("{}/graphql", self.rest_api_basepath);
+        let response = query::send_json(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        if let Some(errors) = response.get("errors") {
+            return Err(error::GRError::RemoteServerError(format!(
+                "Operation failed for pull request {}: {}",
+                id, errors
+            ))
+            .into());
+        }
+        Ok(response)
+    }
+}
+
+/// Merge method in GraphQL form, ex. SQUASH. The REST API takes it lowercase.
+fn merge_method(args: &MergeRequestMergeBodyArgs) -> Option<&'static str> {
+    match args.method {
+        Some(MergeMethod::Merge) => Some("MERGE"),
+        Some(MergeMethod::Squash) => Some("SQUASH"),
+        Some(MergeMethod::Rebase) => Some("REBASE"),
+        None if args.squash => Some("SQUASH"),
+        None => None,
+    }
+}
+
+fn merge_queue_entry(id: i64, entry: &serde_json::Value) -> MergeQueueEntry {
+    MergeQueueEntry::builder()
+        .id(id)
+        .position(entry["position"].as_u64().map(|p| p as u32))
+        .status(entry["state"].as_str().unwrap_or_default().to_lowercase())
+        .target_branch(
+            entry["pullRequest"]["baseRefName"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        )
+        .eta(entry["estimatedTimeToMerge"].as_u64().map(Seconds::new))
+        .build()
+        .unwrap()
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubMergeRequest {
+    number: i64,
+    html_url: String,
+    head: GithubHead,
+    base: GithubHead,
+    merge_commit_sha: Option<String>,
+    user: GithubUser,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    updated_at: DateTime<Utc>,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_at: DateTime<Utc>,
+    title: String,
+    body: Option<String>,
+    #[serde(deserialize_with = "time::deserialize_optional_timestamp")]
+    merged_at: Option<DateTime<Utc>>,
+    // Only in pull requests listed through the issues API. See list.
+    pull_request: Option<GithubIssuePullRequest>,
+    requested_reviewers: Vec<GithubUser>,
+}
+
+impl Contract for GithubMergeRequest {
+    // Pull requests listed through the issues API are in issue form.
+    const OPTIONAL_FIELDS: &'static [&'static str] = &[
+        "head",
+        "base",
+        "merge_commit_sha",
+        "merged_at",
+        "pull_request",
+        "requested_reviewers",
+    ];
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubHead {
+    #[serde(rename = "ref")]
+    branch: String,
+    sha: String,
+    // Null if the fork has been deleted.
+    repo: Option<GithubRepository>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubRepository {
+    full_name: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubUser {
+    login: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubIssuePullRequest {
+    html_url: String,
+}
+
+impl From<GithubMergeRequest> for MergeRequestResponse {
+    fn from(data: GithubMergeRequest) -> Self {
+        let checks_url = (!data.html_url.is_empty()).then(|| format!("{}/checks", data.html_url));
+        let from_fork = match (&data.head.repo, &data.base.repo) {
+            (Some(head), Some(base)) => head.full_name != base.full_name,
+            (None, Some(_)) => true,
+            _ => false,
+        };
+        MergeRequestResponse::builder()
+            .id(data.number)
+            .web_url(data.html_url)
+            .source_branch(data.head.branch)
+            .sha(data.merge_commit_sha.unwrap_or_default())
+            .author(data.user.login)
+            .updated_at(data.updated_at)
+            .created_at(data.created_at)
+            .title(data.title)
+            .pull_request(
+                data.pull_request
+                    .map(|pull_request| pull_request.html_url)
+                    .unwrap_or_default(),
+            )
+            .description(data.body.unwrap_or_default())
+            .merged_at(data.merged_at)
+            .head_sha(data.head.sha)
+            // Not available in the response. Set it to the same ID as the pull request
+            .pipeline_id(Some(data.number))
+            .pipeline_url(checks_url)
+            .reviewers(
+                data.requested_reviewers
+                    .into_iter()
+                    .map(|reviewer| reviewer.login)
+                    .collect(),
+            )
+            .from_fork(from_fork)
+            .head_ref(format!("refs/pull/{}/head", data.number))
+            .build()
+            .unwrap()
+    }
+}
+
+/// Github reaction contents along with the Gitlab award emoji names used to
+/// display them.
+const REACTIONS: [(&str, &str); 8] = [
+    ("+1", "thumbsup"),
+    ("-1", "thumbsdown"),
+    ("laugh", "laughing"),
+    ("confused", "confused"),
+    ("heart", "heart"),
+    ("hooray", "tada"),
+    ("rocket", "rocket"),
+    ("eyes", "eyes"),
+];
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestReaction for Github<R> {
+    // https://docs.github.com/en/rest/reactions/reactions?apiVersion=2022-11-28
+    fn react(&self, args: ReactionBodyArgs) -> Result<()> {
+        let content = REACTIONS
+            .iter()
+            .find(|(content, emoji)| *content == args.emoji || *emoji == args.emoji)
+            .map(|(content, _)| *content)
+            .ok_or_else(|| {
+                error::GRError::OperationNotSupported(format!(
+                    "Github does not support the :{}: reaction. Supported reactions are: {}",
+                    args.emoji,
+                    REACTIONS
+                        .iter()
+                        .map(|(_, emoji)| *emoji)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            })?;
+        // Pull requests are issues when it comes to reactions and comments.
+        let url = match args.comment_id {
+            Some(comment_id) => format!(
+                "{}/repos/{}/issues/comments/{}/reactions",
+                self.rest_api_basepath, self.path, comment_id
+            ),
+            None => format!(
+                "{}/repos/{}/issues/{}/reactions",
+                self.rest_api_basepath, self.path, args.id
+            ),
+        };
+        let mut body = Body::new();
+        body.add("content", content.to_string());
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+}
+
+/// Threads are review comments on the diff grouped by the comment that started
+/// them. Comments on the conversation tab are not threaded in Github.
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestDiscussion for Github<R> {
+    // https://docs.github.com/en/rest/pulls/comments?apiVersion=2022-11-28#list-review-comments-on-a-pull-request
+    fn list_discussions(&self, id: i64) -> Result<Vec<Discussion>> {
+        let mut threads: Vec<Discussion> = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/repos/{}/pulls/{}/comments?per_page={}&page={}",
+                self.rest_api_basepath, self.path, id, MAX_PER_PAGE, page
+            );
+            let comments = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.request_headers(),
+                ApiOperation::MergeRequest,
+            )?;
+            let comments = comments.as_array().cloned().unwrap_or_default();
+            for comment in comments.iter() {
+                let note = Comment::builder()
+                    .id(comment["id"].as_i64().unwrap_or_default())
+                    .author(
+                        comment["user"]["login"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string(),
+                    )
+                    .body(comment["body"].as_str().unwrap_or_default().to_string())
+                    .created_at(time::json_optional_timestamp(comment, "created_at")?)
+                    .build()
+                    .unwrap();
+                // Replies point to the first comment of the thread.
+                let thread = comment["in_reply_to_id"].as_i64().and_then(|root| {
+                    threads
+                        .iter_mut()
+                        .find(|thread| thread.id == root.to_string())
+                });
+                match thread {
+                    Some(thread) => thread.notes.push(note),
+                    None => threads.push(
+                        Discussion::builder()
+                            .id(note.id.to_string())
+                            .file(comment["path"].as_str().unwrap_or_default().to_string())
+                            .line(
+                                comment["line"]
+                                    .as_i64()
+                                    .or(comment["original_line"].as_i64()),
+                            )
+                            .notes(vec![note])
+                            .build()
+                            .unwrap(),
+                    ),
+                }
+            }
+            if comments.len() < MAX_PER_PAGE as usize {
+                break;
+            }
+        }
+        Ok(threads)
+    }
+
+    // https://docs.github.com/en/rest/pulls/comments?apiVersion=2022-11-28#create-a-reply-for-a-review-comment
+    fn reply(&self, args: DiscussionReplyBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}/comments/{}/replies",
+            self.rest_api_basepath, self.path, args.id, args.discussion_id
+        );
+        let mut body = Body::new();
+        body.add("body", args.body);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestSuggestion for Github<R> {
+    // Review comments are placed on a line of the pull request head commit.
+    // https://docs.github.com/en/rest/pulls/comments?apiVersion=2022-11-28#create-a-review-comment-for-a-pull-request
+    fn suggest(&self, args: SuggestionBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}",
+            self.rest_api_basepath, self.path, args.id
+        );
+        let pull_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let commit_id = pull_request["head"]["sha"].as_str().ok_or_else(|| {
+            error::GRError::RemoteUnexpectedResponseContract(format!(
+                "Pull request {} has no head sha",
+                args.id
+            ))
+        })?;
+        let mut body = Body::new();
+        body.add("body", serde_json::Value::from(args.body("suggestion")));
+        body.add("commit_id", serde_json::Value::from(commit_id));
+        body.add("path", serde_json::Value::from(args.file));
+        body.add("line", serde_json::Value::from(args.line));
+        body.add("side", serde_json::Value::from("RIGHT"));
+        query::send_raw(
+            &self.runner,
+            &format!("{}/comments", url),
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    // Github only applies suggested changes from its UI.
+    fn list_suggestions(&self, _id: i64) -> Result<Vec<Suggestion>> {
+        Err(suggestions_not_supported())
+    }
+
+    fn apply_suggestions(&self, _ids: &[i64], _commit_message: Option<String>) -> Result<()> {
+        Err(suggestions_not_supported())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestReview for Github<R> {
+    // https://docs.github.com/en/rest/pulls/reviews?apiVersion=2022-11-28#create-a-review-for-a-pull-request
+    fn submit_review(&self, review: ReviewFile) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}/reviews",
+            self.rest_api_basepath, self.path, review.merge_request
+        );
+        let comments = review
+            .comments
+            .iter()
+            .map(|comment| {
+                serde_json::json!({
+                    "path": comment.file,
+                    "line": comment.line,
+                    "side": "RIGHT",
+                    "body": comment.body,
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut body = Body::new();
+        body.add("commit_id", serde_json::Value::from(review.head_sha));
+        body.add("body", serde_json::Value::from(review.body));
+        body.add("event", serde_json::Value::from("COMMENT"));
+        body.add("comments", serde_json::Value::from(comments));
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestDiff for Github<R> {
+    // https://docs.github.com/en/rest/pulls/pulls?apiVersion=2022-11-28#list-pull-requests-files
+    fn list_changes(&self, id: i64) -> Result<Vec<FileChange>> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}/files",
+            self.rest_api_basepath, self.path, id
+        );
+        query::paged_as::<_, GithubFileChange, _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            None,
+            ApiOperation::MergeRequest,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ReviewMergeRequest for Github<R> {
+    // https://docs.github.com/en/rest/pulls/reviews?apiVersion=2022-11-28#create-a-review-for-a-pull-request
+    fn review(&self, args: ReviewBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}/reviews",
+            self.rest_api_basepath, self.path, args.id
+        );
+        let event = match args.event {
+            ReviewEvent::Approve => "APPROVE",
+            ReviewEvent::RequestChanges => "REQUEST_CHANGES",
+            ReviewEvent::Comment => "COMMENT",
+        };
+        let mut body = Body::new();
+        body.add("event", event.to_string());
+        if !args.body.trim().is_empty() {
+            body.add("body", args.body);
+        }
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubFileChange {
+    filename: String,
+    previous_filename: Option<String>,
+    status: String,
+    additions: u32,
+    deletions: u32,
+    patch: Option<String>,
+}
+
+impl Contract for GithubFileChange {
+    const OPTIONAL_FIELDS: &'static [&'static str] = &["previous_filename", "patch"];
+}
+
+impl From<GithubFileChange> for FileChange {
+    fn from(data: GithubFileChange) -> Self {
+        let status = match data.status.as_str() {
+            "added" => FileChangeStatus::Added,
+            "removed" => FileChangeStatus::Deleted,
+            "renamed" => FileChangeStatus::Renamed,
+            _ => FileChangeStatus::Modified,
+        };
+        FileChange::builder()
+            .old_path(data.previous_filename.unwrap_or(data.filename.clone()))
+            .path(data.filename)
+            .status(status)
+            .additions(data.additions)
+            .deletions(data.deletions)
+            .patch(data.patch.unwrap_or_default())
+            .build()
+            .unwrap()
+    }
+}
+
+fn suggestions_not_supported() -> anyhow::Error {
+    error::GRError::OperationNotSupported(
+        "Applying suggested changes is not supported in the Github REST API".to_string(),
+    )
+    .into()
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GithubMergeRequestComment {
+    id: i64,
+    body: String,
+    user: GithubUser,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_at: DateTime<Utc>,
+    /// Count of each reaction content along with the total and the URL.
+    reactions: HashMap<String, serde_json::Value>,
+}
+
+impl Contract for GithubMergeRequestComment {}
+
+impl From<GithubMergeRequestComment> for Comment {
+    fn from(data: GithubMergeRequestComment) -> Self {
+        let mut reactions = REACTIONS
+            .iter()
+            .filter_map(|(content, emoji)| {
+                let count = data.reactions.get(*content)?.as_u64()?;
+                (count > 0).then(|| Reaction::new(emoji, count as u32))
+            })
+            .collect::<Vec<_>>();
+        reactions.sort_by_key(|reaction| Reverse(reaction.count));
+        Comment::builder()
+            .id(data.id)
+            .author(data.user.login)
+            .created_at(Some(data.created_at))
+            .body(data.body)
+            .reactions(reactions)
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{
+        cmds::merge_request::review::ReviewComment,
+        cmds::project::{Member, MrMemberType},
+        http::{self, Headers},
+        remote::{ListBodyArgs, ListOrderBy, ListSortMode},
+        setup_client,
+        test::utils::{
+            default_github, get_contract, BasePath, ClientType, ContractType, Domain,
+            ResponseContracts,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_open_merge_request() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(201, "merge_request.json", None)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(201, "merge_request.json", None);
         let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
-        let assignee = Member::default();
+        let assignee = Member::builder()
+            .name("tom".to_string())
+            .username("tsawyer".to_string())
+            .mr_member_type(MrMemberType::Filled)
+            .id(1234)
+            .build()
+            .unwrap();
         let reviewer = Member::builder()
             .name("huck".to_string())
             .username("hfinn".to_string())
             .mr_member_type(MrMemberType::Filled)
-            .id(1234)
+            .id(45678)
             .build()
             .unwrap();
         let mr_args = MergeRequestBodyArgs::builder()
             .assignee(assignee)
-            .reviewer(reviewer)
+            .reviewers(vec![reviewer])
             .build()
             .unwrap();
-        assert!(github.open(mr_args).is_ok());
+        let response = github.open(mr_args).unwrap();
+        assert_eq!(23, response.id);
+        assert_eq!(
+            "https://github.com/jordilin/githapi/pull/23",
+            response.web_url
+        );
         assert_eq!(
             "https://api.github.com/repos/jordilin/githapi/pulls/23/requested_reviewers",
             *client.url(),
         );
         let actual_method = client.http_method.borrow();
-        // POST on requesting a reviewer
-        assert_eq!(http::Method::POST, actual_method[1]);
-        // The open create merge request was a POST.
         assert_eq!(http::Method::POST, actual_method[0]);
+        // Assignee call
+        assert_eq!(http::Method::PATCH, actual_method[1]);
+        // Reviewer call
+        assert_eq!(http::Method::POST, actual_method[2]);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_open_merge_request_with_assignee_no_reviewer() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let assignee = Member::builder()
+            .name("tom".to_string())
+            .username("tsawyer".to_string())
+            .mr_member_type(MrMemberType::Filled)
+            .id(1234)
+            .build()
+            .unwrap();
+        let reviewer = Member::default(); // Default member is empty/placeholder
+        let mr_args = MergeRequestBodyArgs::builder()
+            .assignee(assignee)
+            .reviewers(vec![reviewer])
+            .build()
+            .unwrap();
+        let response = github.open(mr_args).unwrap();
+        assert_eq!(23, response.id);
+        assert_eq!(
+            "https://github.com/jordilin/githapi/pull/23",
+            response.web_url
+        );
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/23",
+            *client.url(),
+        );
+        let actual_method = client.http_method.borrow();
+        assert_eq!(http::Method::PATCH, actual_method[1]);
+        // The open create merge request was a POST.
+        assert_eq!(http::Method::POST, actual_method[0]);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_open_merge_request_not_allowed_to_assign_opens_without_assignee() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(404, None, None)
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let assignee = Member::builder()
+            .name("tom".to_string())
+            .username("tsawyer".to_string())
+            .mr_member_type(MrMemberType::Filled)
+            .id(1234)
+            .build()
+            .unwrap();
+        let mr_args = MergeRequestBodyArgs::builder()
+            .assignee(assignee)
+            .reviewers(vec![Member::default()])
+            .build()
+            .unwrap();
+        let response = github.open(mr_args).unwrap();
+        assert_eq!(
+            "https://github.com/jordilin/githapi/pull/23",
+            response.web_url
+        );
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/23",
+            *client.url(),
+        );
+        assert_eq!(2, client.http_method.borrow().len());
+    }
+
+    #[test]
+    fn test_open_merge_request_with_multiple_reviewers() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(201, "merge_request.json", None)
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let reviewers = ["hfinn", "tsawyer"]
+            .into_iter()
+            .map(|username| {
+                Member::builder()
+                    .username(username.to_string())
+                    .mr_member_type(MrMemberType::Filled)
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        let mr_args = MergeRequestBodyArgs::builder()
+            .reviewers(reviewers)
+            .build()
+            .unwrap();
+        github.open(mr_args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/requested_reviewers",
+            *client.url(),
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!(serde_json::json!(["hfinn", "tsawyer"]), body["reviewers"]);
+    }
+
+    #[test]
+    fn test_open_merge_request_with_reviewer_no_assignee() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(201, "merge_request.json", None)
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let assignee = Member::default();
+        let reviewer = Member::builder()
+            .name("huck".to_string())
+            .username("hfinn".to_string())
+            .mr_member_type(MrMemberType::Filled)
+            .id(1234)
+            .build()
+            .unwrap();
+        let mr_args = MergeRequestBodyArgs::builder()
+            .assignee(assignee)
+            .reviewers(vec![reviewer])
+            .build()
+            .unwrap();
+        assert!(github.open(mr_args).is_ok());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/requested_reviewers",
+            *client.url(),
+        );
+        let actual_method = client.http_method.borrow();
+        // POST on requesting a reviewer
+        assert_eq!(http::Method::POST, actual_method[1]);
+        // The open create merge request was a POST.
+        assert_eq!(http::Method::POST, actual_method[0]);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_open_merge_request_with_labels() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(200, None, None)
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let mr_args = MergeRequestBodyArgs::builder()
+            .labels(vec!["cache".to_string()])
+            .build()
+            .unwrap();
+        assert!(github.open(mr_args).is_ok());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/23/labels",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[1]);
+        assert!(client.request_body().contains("cache"));
+    }
+
+    #[test]
+    fn test_open_merge_request_with_no_assignee_no_reviewer() {
+        let responses = ResponseContracts::new(ContractType::Github).add_contract(
+            201,
+            "merge_request.json",
+            None,
+        );
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let assignee = Member::default();
+        let reviewer = Member::default();
+        let mr_args = MergeRequestBodyArgs::builder()
+            .assignee(assignee)
+            .reviewers(vec![reviewer])
+            .build()
+            .unwrap();
+        assert!(github.open(mr_args).is_ok());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls",
+            *client.url(),
+        );
+        let actual_method = client.http_method.borrow();
+        // The open create merge request was a POST.
+        assert_eq!(http::Method::POST, actual_method[0]);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_open_merge_request_on_target_repository() {
+        let mr_args = MergeRequestBodyArgs::builder()
+            .target_repo("jordilin/gitar".to_string())
+            .build()
+            .unwrap();
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(201, "merge_request.json", None);
+        // current repo, targeting jordilin/gitar
+        let client_type = ClientType::Github(
+            Domain("github.com".to_string()),
+            BasePath("jdoe/gitar".to_string()),
+        );
+        let (client, github) = setup_client!(responses, client_type, dyn MergeRequest);
+
+        assert!(github.open(mr_args).is_ok());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/gitar/pulls",
+            *client.url(),
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_open_merge_request_with_no_assignee() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(201, "merge_request.json", None);
+        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        let assignee = Member::default();
+        let mr_args = MergeRequestBodyArgs::builder()
+            .assignee(assignee)
+            .build()
+            .unwrap();
+        assert!(github.open(mr_args).is_ok());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls",
+            *client.url(),
+        );
+        let actual_method = client.http_method.borrow();
+        assert_eq!(http::Method::POST, actual_method[0]);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+        let actual_body = client.request_body.borrow();
+        assert!(!actual_body.contains("assignees"));
+    }
+
+    #[test]
+    fn test_open_merge_request_error_status_code() {
+        let mr_args = MergeRequestBodyArgs::builder().build().unwrap();
+        let responses = ResponseContracts::new(ContractType::Github).add_body(
+            401,
+            Some(r#"{"message":"Bad credentials","documentation_url":"https://docs.github.com/rest"}"#),
+            None,
+        );
+        let (_, github) = setup_client!(responses, default_github(), dyn MergeRequest);
+        assert!(github.open(mr_args).is_err());
+    }
+
+    #[test]
+    fn test_open_merge_request_existing_one() {
+        let mr_args = MergeRequestBodyArgs::builder()
+            .source_branch("feature".to_string())
+            .build()
+            .unwrap();
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(format!(
+                    "[{}]",
+                    get_contract(ContractType::Github, "merge_request.json")
+                )),
+                None,
+            )
+            // Github returns a 422 (already exists), so the code grabs existing URL
+            // filtering by namespace and branch. The response is a list of merge
+            // requests.
+            .add_contract(422, "merge_request_conflict.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+
+        github.open(mr_args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls?head=jordilin:feature",
+            *client.url(),
+        );
+        let actual_method = client.http_method.borrow();
+        assert_eq!(http::Method::GET, actual_method[1]);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_amend_existing_pull_request() {
+        let mr_args = MergeRequestBodyArgs::builder()
+            .source_branch("feature".to_string())
+            .amend(true)
+            .build()
+            .unwrap();
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(
+                200,
+                Some(format!(
+                    "[{}]",
+                    get_contract(ContractType::Github, "merge_request.json")
+                )),
+                None,
+            )
+            .add_contract(422, "merge_request_conflict.json", None);
+
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+
+        github.open(mr_args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23",
+            *client.url(),
+        );
+        let actual_method = client.http_method.borrow();
+        assert_eq!(http::Method::PATCH, actual_method[2]);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_open_merge_request_cannot_retrieve_url_existing_one_is_error() {
+        let mr_args = MergeRequestBodyArgs::builder()
+            .source_branch("feature".to_string())
+            .build()
+            .unwrap();
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(200, Some("[]"), None)
+            .add_contract(422, "merge_request_conflict.json", None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let result = github.open(mr_args);
+        match result {
+            Ok(_) => panic!("Expected error"),
+            Err(err) => match err.downcast_ref::<error::GRError>() {
+                Some(error::GRError::RemoteUnexpectedResponseContract(_)) => (),
+                _ => panic!("Expected error::GRError::RemoteUnexpectedResponseContract"),
+            },
+        }
+    }
+
+    #[test]
+    fn test_open_merge_request_cannot_get_owner_org_namespace_in_existing_pull_request() {
+        let mr_args = MergeRequestBodyArgs::builder()
+            .source_branch("feature".to_string())
+            .build()
+            .unwrap();
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(200, Some("[]"), None)
+            .add_contract(422, "merge_request_conflict.json", None);
+        // missing the repo name on path
+        let client_type = ClientType::Github(
+            Domain("github.com".to_string()),
+            BasePath("jordilin".to_string()),
+        );
+        let (_, github) = setup_client!(contracts, client_type, dyn MergeRequest);
+
+        let result = github.open(mr_args);
+        match result {
+            Ok(_) => panic!("Expected error"),
+            Err(err) => match err.downcast_ref::<error::GRError>() {
+                Some(error::GRError::ApplicationError(_)) => (),
+                _ => panic!("Expected error::GRError::ApplicationError"),
+            },
+        }
+    }
+
+    #[test]
+    fn test_add_pull_request_dependency_appends_marker() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) =
+            setup_client!(responses, default_github(), dyn MergeRequestDependency);
+        github.add_dependency(23, 21).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23",
+            *client.url(),
+        );
+        assert_eq!(http::Method::PATCH, client.http_method.borrow()[1]);
+        assert!(client
+            .request_body()
+            .contains("This is a new feature\\n\\nDepends on #21"));
+    }
+
+    #[test]
+    fn test_list_pull_request_dependencies_from_description() {
+        let responses = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(
+                200,
+                Some(
+                    r#"{"number": 24, "html_url": "https://github.com/jordilin/githapi/pull/24",
+                    "title": "Second part", "user": {"login": "jordilin"},
+                    "created_at": "2024-03-03T09:00:00Z", "updated_at": "2024-03-03T09:00:00Z",
+                    "body": "Second part\r\n\r\nDepends on #23\r\ndepends on #22"}"#,
+                ),
+                None,
+            );
+        let (client, github) =
+            setup_client!(responses, default_github(), dyn MergeRequestDependency);
+        let dependencies = github.list_dependencies(24).unwrap();
+        assert_eq!(2, dependencies.len());
+        assert_eq!(23, dependencies[0].id);
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/22",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_merge_request_num_pages() {
+        let link_header = r#"<https://api.github.com/repos/jordilin/githapi/pulls?state=open&page=2>; rel="next", <https://api.github.com/repos/jordilin/githapi/pulls?state=open&page=2>; rel="last""#;
+        let mut headers = Headers::new();
+        headers.set("link".to_string(), link_header.to_string());
+        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+            200,
+            None,
+            Some(headers),
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .assignee(None)
+            .build()
+            .unwrap();
+        assert_eq!(Some(2), github.num_pages(args).unwrap());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls?state=open&page=1",
+            *client.url(),
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_list_merge_requests_from_to_page_set_in_url() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some("[]"), None);
+
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(Some(
+                ListBodyArgs::builder()
+                    .page(2)
+                    .max_pages(3)
+                    .build()
+                    .unwrap(),
+            ))
+            .assignee(None)
+            .build()
+            .unwrap();
+        github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls?state=open&page=2",
+            *client.url(),
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_list_pull_requests_of_organization_skips_issues() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_issues_user.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .group(Some("jordilin".to_string()))
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(1, merge_requests.len());
+        assert_eq!(
+            "https://api.github.com/orgs/jordilin/issues?state=open&filter=all",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_pull_requests_with_labels_and_milestone_number() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_issues_user.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .labels(vec!["bug".to_string(), "needs review".to_string()])
+            .milestone(Some("3".to_string()))
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(1, merge_requests.len());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues?state=open\
+             &labels=bug%2Cneeds%20review&milestone=3",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_pull_requests_ordered_by_remote() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some("[]"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(Some(
+                ListBodyArgs::builder()
+                    .order_by(Some(ListOrderBy::Created))
+                    .sort_mode(ListSortMode::Asc)
+                    .build()
+                    .unwrap(),
+            ))
+            .build()
+            .unwrap();
+        github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls?state=open\
+             &sort=created&direction=asc",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_pull_requests_ordered_by_title_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(Some(
+                ListBodyArgs::builder()
+                    .order_by(Some(ListOrderBy::Title))
+                    .build()
+                    .unwrap(),
+            ))
+            .build()
+            .unwrap();
+        match github.list(args) {
+            Err(err) => match err.downcast_ref::<error::GRError>() {
+                Some(error::GRError::OperationNotSupported(_)) => {}
+                _ => panic!("Expected OperationNotSupported"),
+            },
+            _ => panic!("Expected error"),
+        }
+    }
+
+    #[test]
+    fn test_list_pull_requests_resolves_milestone_title() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "list_issues_user.json", None)
+            .add_body(
+                200,
+                Some(r#"[{"number":1,"title":"v0.9"},{"number":2,"title":"v1.0"}]"#),
+                None,
+            );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .milestone(Some("v1.0".to_string()))
+            .build()
+            .unwrap();
+        github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues?state=open&milestone=2",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_pull_requests_of_organization_by_milestone_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .group(Some("jordilin".to_string()))
+            .milestone(Some("v1.0".to_string()))
+            .build()
+            .unwrap();
+        match github.list(args) {
+            Err(err) => match err.downcast_ref::<error::GRError>() {
+                Some(error::GRError::OperationNotSupported(_)) => {}
+                _ => panic!("Expected OperationNotSupported"),
+            },
+            _ => panic!("Expected error"),
+        }
+    }
+
+    #[test]
+    fn test_get_pull_requests_for_auth_user_is_assignee() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_issues_user.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .assignee(Some(
+                Member::builder()
+                    .name("tom".to_string())
+                    .username("tsawyer".to_string())
+                    .id(123456)
+                    .build()
+                    .unwrap(),
+            ))
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/issues?state=open&filter=assigned",
+            *client.url()
+        );
+        assert!(merge_requests.len() == 1);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_list_repo_pull_requests_by_author() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_issues_user.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .author(Some(
+                Member::builder()
+                    .name("tom".to_string())
+                    .username("tsawyer".to_string())
+                    .id(12345)
+                    .build()
+                    .unwrap(),
+            ))
+            .project_only(true)
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues?state=open&creator=tsawyer",
+            *client.url()
+        );
+        assert_eq!(1, merge_requests.len());
+    }
+
+    #[test]
+    fn test_get_pull_requests_for_auth_user_is_author() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_issues_user.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .author(Some(
+                Member::builder()
+                    .name("tom".to_string())
+                    .username("tsawyer".to_string())
+                    .id(12345)
+                    .build()
+                    .unwrap(),
+            ))
+            .build()
+            .unwrap();
+        let merge_requests = github.list(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/issues?state=open&filter=created",
+            *client.url()
+        );
+        assert!(merge_requests.len() == 1);
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_create_merge_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(201, None, None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestBodyArgs::builder()
+            .id(23)
+            .comment("Looks good to me".to_string())
+            .build()
+            .unwrap();
+        github.create(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/23/comments",
+            *client.url(),
+        );
         assert_eq!(
             Some(ApiOperation::MergeRequest),
             *client.api_operation.borrow()
@@ -660,28 +2167,33 @@ mod test {
     }
 
     #[test]
-    fn test_open_merge_request_with_no_assignee_no_reviewer() {
-        let responses = ResponseContracts::new(ContractType::Github).add_contract(
-            201,
+    fn test_create_merge_request_comment_error_status_code() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(500, None, None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestBodyArgs::builder()
+            .id(23)
+            .comment("Looks good to me".to_string())
+            .build()
+            .unwrap();
+        assert!(github.create(args).is_err());
+    }
+
+    #[test]
+    fn test_close_pull_request_ok() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
             "merge_request.json",
             None,
         );
-        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
-        let assignee = Member::default();
-        let reviewer = Member::default();
-        let mr_args = MergeRequestBodyArgs::builder()
-            .assignee(assignee)
-            .reviewer(reviewer)
-            .build()
-            .unwrap();
-        assert!(github.open(mr_args).is_ok());
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        github.close(23).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls",
+            "https://api.github.com/repos/jordilin/githapi/pulls/23",
             *client.url(),
         );
         let actual_method = client.http_method.borrow();
-        // The open create merge request was a POST.
-        assert_eq!(http::Method::POST, actual_method[0]);
+        assert_eq!(http::Method::PATCH, actual_method[0]);
         assert_eq!(
             Some(ApiOperation::MergeRequest),
             *client.api_operation.borrow()
@@ -689,26 +2201,23 @@ mod test {
     }
 
     #[test]
-    fn test_open_merge_request_on_target_repository() {
-        let mr_args = MergeRequestBodyArgs::builder()
-            .target_repo("jordilin/gitar".to_string())
-            .build()
-            .unwrap();
-        let responses = ResponseContracts::new(ContractType::Github)
-            .add_contract(200, "merge_request.json", None)
-            .add_contract(201, "merge_request.json", None);
-        // current repo, targeting jordilin/gitar
-        let client_type = ClientType::Github(
-            Domain("github.com".to_string()),
-            BasePath("jdoe/gitar".to_string()),
+    fn test_get_pull_request_details() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "merge_request.json",
+            None,
         );
-        let (client, github) = setup_client!(responses, client_type, dyn MergeRequest);
-
-        assert!(github.open(mr_args).is_ok());
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let pull_request = github.get(23).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/gitar/pulls",
+            "https://api.github.com/repos/jordilin/githapi/pulls/23",
             *client.url(),
         );
+        assert!(!pull_request.from_fork);
+        assert_eq!(
+            format!("refs/pull/{}/head", pull_request.id),
+            pull_request.head_ref
+        );
         assert_eq!(
             Some(ApiOperation::MergeRequest),
             *client.api_operation.borrow()
@@ -716,179 +2225,250 @@ mod test {
     }
 
     #[test]
-    fn test_open_merge_request_with_no_assignee() {
-        let responses = ResponseContracts::new(ContractType::Github)
-            .add_contract(200, "merge_request.json", None)
-            .add_contract(201, "merge_request.json", None);
-        let (client, github) = setup_client!(responses, default_github(), dyn MergeRequest);
-        let assignee = Member::default();
-        let mr_args = MergeRequestBodyArgs::builder()
-            .assignee(assignee)
+    fn test_get_pull_request_from_fork() {
+        let mut pull_request: serde_json::Value =
+            serde_json::from_str(&get_contract(ContractType::Github, "merge_request.json"))
+                .unwrap();
+        pull_request["head"]["repo"]["full_name"] = serde_json::json!("contributor/githapi");
+        let mut deleted_fork = pull_request.clone();
+        deleted_fork["head"]["repo"] = serde_json::Value::Null;
+        for pull_request in [pull_request, deleted_fork] {
+            let contracts = ResponseContracts::new(ContractType::Github).add_body(
+                200,
+                Some(pull_request.to_string()),
+                None,
+            );
+            let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+            assert!(github.get(23).unwrap().from_fork);
+        }
+    }
+
+    #[test]
+    fn test_github_merge_pull_request() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .message(Some("Release 1.2\n\nShips the new cache".to_string()))
+            .sha(Some("fcbb7490d4a216c2d162fa5466184e30dae1f087".to_string()))
             .build()
             .unwrap();
-        assert!(github.open(mr_args).is_ok());
+        github.merge(args).unwrap();
+        let body = client.request_body();
+        assert!(body.contains("\"commit_title\":\"Release 1.2\""));
+        assert!(body.contains("\"commit_message\":\"Ships the new cache\""));
+        assert!(body.contains("\"sha\":\"fcbb7490d4a216c2d162fa5466184e30dae1f087\""));
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls",
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/merge",
             *client.url(),
         );
         let actual_method = client.http_method.borrow();
-        assert_eq!(http::Method::POST, actual_method[0]);
+        assert_eq!(http::Method::PUT, actual_method[0]);
         assert_eq!(
             Some(ApiOperation::MergeRequest),
             *client.api_operation.borrow()
         );
-        let actual_body = client.request_body.borrow();
-        assert!(!actual_body.contains("assignees"));
     }
 
     #[test]
-    fn test_open_merge_request_error_status_code() {
-        let mr_args = MergeRequestBodyArgs::builder().build().unwrap();
-        let responses = ResponseContracts::new(ContractType::Github).add_body(
-            401,
-            Some(r#"{"message":"Bad credentials","documentation_url":"https://docs.github.com/rest"}"#),
-            None,
+    fn test_github_auto_merge_pull_request() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(r#"{"data":{"enablePullRequestAutoMerge":{"pullRequest":{"number":23}}}}"#),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .message(Some("Release 1.2\n\nShips the new cache".to_string()))
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        let response = github.merge(args).unwrap();
+        assert_eq!("https://api.github.com/graphql", *client.url());
+        assert_eq!(http::Method::POST, client.http_method.borrow()[1]);
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert!(body["query"]
+            .as_str()
+            .unwrap()
+            .contains("enablePullRequestAutoMerge"));
+        assert_eq!("abcdefg", body["variables"]["id"]);
+        assert_eq!("Release 1.2", body["variables"]["headline"]);
+        assert_eq!("Ships the new cache", body["variables"]["body"]);
+        assert!(body["variables"]["sha"].is_null());
+        assert!(body["variables"]["method"].is_null());
+        assert!(response.merged_at.is_none());
+        assert_eq!(
+            "https://github.com/jordilin/githapi/pull/23",
+            response.web_url
         );
-        let (_, github) = setup_client!(responses, default_github(), dyn MergeRequest);
-        assert!(github.open(mr_args).is_err());
     }
 
     #[test]
-    fn test_open_merge_request_existing_one() {
-        let mr_args = MergeRequestBodyArgs::builder()
-            .source_branch("feature".to_string())
-            .build()
-            .unwrap();
+    fn test_github_merge_deletes_head_branch() {
         let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(204, None, None)
+            .add_contract(200, "merge_request.json", None)
             .add_body(
                 200,
-                Some(format!(
-                    "[{}]",
-                    get_contract(ContractType::Github, "merge_request.json")
-                )),
+                Some(r#"{"sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e", "merged": true, "message": "Pull Request successfully merged"}"#),
                 None,
-            )
-            // Github returns a 422 (already exists), so the code grabs existing URL
-            // filtering by namespace and branch. The response is a list of merge
-            // requests.
-            .add_contract(422, "merge_request_conflict.json", None);
+            );
         let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-
-        github.open(mr_args).unwrap();
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .delete_source_branch(true)
+            .build()
+            .unwrap();
+        github.merge(args).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls?head=jordilin:feature",
+            "https://api.github.com/repos/jordilin/githapi/git/refs/heads/feature",
             *client.url(),
         );
-        let actual_method = client.http_method.borrow();
-        assert_eq!(http::Method::GET, actual_method[1]);
-        assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
-        );
+        let methods = client.http_method.borrow();
+        assert_eq!(http::Method::PUT, methods[0]);
+        assert_eq!(http::Method::DELETE, methods[2]);
     }
 
     #[test]
-    fn test_amend_existing_pull_request() {
-        let mr_args = MergeRequestBodyArgs::builder()
-            .source_branch("feature".to_string())
-            .amend(true)
+    fn test_github_merge_skips_deleted_head_repository() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(r#"{"number": 23, "head": {"ref": "feature", "repo": null}}"#),
+                None,
+            )
+            .add_body(
+                200,
+                Some(r#"{"sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e", "merged": true, "message": "Pull Request successfully merged"}"#),
+                None,
+            );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .delete_source_branch(true)
             .build()
             .unwrap();
+        github.merge(args).unwrap();
+        let methods = client.http_method.borrow();
+        assert_eq!(2, methods.len());
+        assert_eq!(http::Method::GET, methods[1]);
+    }
+
+    #[test]
+    fn test_github_merge_head_branch_deletion_failure_is_not_an_error() {
         let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(422, Some(r#"{"message": "Reference does not exist"}"#), None)
             .add_contract(200, "merge_request.json", None)
             .add_body(
                 200,
-                Some(format!(
-                    "[{}]",
-                    get_contract(ContractType::Github, "merge_request.json")
-                )),
+                Some(r#"{"sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e", "merged": true, "message": "Pull Request successfully merged"}"#),
                 None,
-            )
-            .add_contract(422, "merge_request_conflict.json", None);
-
+            );
         let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .delete_source_branch(true)
+            .build()
+            .unwrap();
+        let response = github.merge(args).unwrap();
+        assert_eq!(23, response.id);
+        assert_eq!(http::Method::DELETE, client.http_method.borrow()[2]);
+    }
 
-        github.open(mr_args).unwrap();
-        assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls/23",
-            *client.url(),
-        );
-        let actual_method = client.http_method.borrow();
-        assert_eq!(http::Method::PATCH, actual_method[2]);
-        assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+    #[test]
+    fn test_github_squash_merge() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "merge_request.json",
+            None,
         );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .squash(true)
+            .build()
+            .unwrap();
+        github.merge(args).unwrap();
+        assert!(client
+            .request_body()
+            .contains("\"merge_method\":\"squash\""));
     }
 
     #[test]
-    fn test_open_merge_request_cannot_retrieve_url_existing_one_is_error() {
-        let mr_args = MergeRequestBodyArgs::builder()
-            .source_branch("feature".to_string())
+    fn test_github_rebase_merge_method() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .method(Some(MergeMethod::Rebase))
             .build()
             .unwrap();
-        let contracts = ResponseContracts::new(ContractType::Github)
-            .add_body(200, Some("[]"), None)
-            .add_contract(422, "merge_request_conflict.json", None);
-        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        let result = github.open(mr_args);
-        match result {
-            Ok(_) => panic!("Expected error"),
-            Err(err) => match err.downcast_ref::<error::GRError>() {
-                Some(error::GRError::RemoteUnexpectedResponseContract(_)) => (),
-                _ => panic!("Expected error::GRError::RemoteUnexpectedResponseContract"),
-            },
-        }
+        github.merge(args).unwrap();
+        assert!(client
+            .request_body()
+            .contains("\"merge_method\":\"rebase\""));
     }
 
     #[test]
-    fn test_open_merge_request_cannot_get_owner_org_namespace_in_existing_pull_request() {
-        let mr_args = MergeRequestBodyArgs::builder()
-            .source_branch("feature".to_string())
+    fn test_github_auto_merge_not_allowed_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(r#"{"data":null,"errors":[{"message":"Auto merge is not allowed for this repository"}]}"#),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(23)
+            .auto_merge(true)
             .build()
             .unwrap();
-        let contracts = ResponseContracts::new(ContractType::Github)
-            .add_body(200, Some("[]"), None)
-            .add_contract(422, "merge_request_conflict.json", None);
-        // missing the repo name on path
-        let client_type = ClientType::Github(
-            Domain("github.com".to_string()),
-            BasePath("jordilin".to_string()),
-        );
-        let (_, github) = setup_client!(contracts, client_type, dyn MergeRequest);
-
-        let result = github.open(mr_args);
-        match result {
-            Ok(_) => panic!("Expected error"),
-            Err(err) => match err.downcast_ref::<error::GRError>() {
-                Some(error::GRError::ApplicationError(_)) => (),
-                _ => panic!("Expected error::GRError::ApplicationError"),
-            },
+        let err = github.merge(args).unwrap_err();
+        match err.downcast_ref::<error::GRError>() {
+            Some(error::GRError::RemoteServerError(msg)) => {
+                assert!(msg.contains("Auto merge is not allowed"))
+            }
+            _ => panic!("Expected RemoteServerError"),
         }
     }
 
     #[test]
-    fn test_merge_request_num_pages() {
-        let link_header = r#"<https://api.github.com/repos/jordilin/githapi/pulls?state=open&page=2>; rel="next", <https://api.github.com/repos/jordilin/githapi/pulls?state=open&page=2>; rel="last""#;
-        let mut headers = Headers::new();
-        headers.set("link".to_string(), link_header.to_string());
-        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+    fn test_list_pull_request_comments() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
             200,
+            Some(format!(
+                "[{}]",
+                get_contract(ContractType::Github, "comment.json")
+            )),
             None,
-            Some(headers),
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        let args = MergeRequestListBodyArgs::builder()
-            .state(MergeRequestState::Opened)
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestListBodyArgs::builder()
+            .id(23)
             .list_args(None)
-            .assignee(None)
             .build()
             .unwrap();
-        assert_eq!(Some(2), github.num_pages(args).unwrap());
+        let comments = github.list(args).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls?state=open&page=1",
+            "https://api.github.com/repos/jordilin/githapi/issues/23/comments",
             *client.url(),
         );
+        assert_eq!(
+            vec![Reaction::new("tada", 2), Reaction::new("thumbsup", 1)],
+            comments[0].reactions
+        );
         assert_eq!(
             Some(ApiOperation::MergeRequest),
             *client.api_operation.borrow()
@@ -896,149 +2476,240 @@ mod test {
     }
 
     #[test]
-    fn test_list_merge_requests_from_to_page_set_in_url() {
-        let contracts =
-            ResponseContracts::new(ContractType::Github).add_body(200, Some("[]"), None);
-
-        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        let args = MergeRequestListBodyArgs::builder()
-            .state(MergeRequestState::Opened)
-            .list_args(Some(
-                ListBodyArgs::builder()
-                    .page(2)
-                    .max_pages(3)
-                    .build()
-                    .unwrap(),
-            ))
-            .assignee(None)
+    fn test_suggest_change_creates_review_comment() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(201, Some("{}"), None)
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) =
+            setup_client!(contracts, default_github(), dyn MergeRequestSuggestion);
+        let args = SuggestionBodyArgs::builder()
+            .id(23)
+            .file("src/main.rs".to_string())
+            .line(10)
+            .replacement("let x = 1;".to_string())
             .build()
             .unwrap();
-        github.list(args).unwrap();
+        github.suggest(args).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls?state=open&page=2",
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/comments",
             *client.url(),
         );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("```suggestion\nlet x = 1;\n```", body["body"]);
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            "fcbb7490d4a216c2d162fa5466184e30dae1f087",
+            body["commit_id"]
         );
+        assert_eq!(10, body["line"]);
+        assert_eq!("RIGHT", body["side"]);
     }
 
     #[test]
-    fn test_get_pull_requests_for_auth_user_is_assignee() {
-        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
-            200,
-            "list_issues_user.json",
-            None,
-        );
-        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        let args = MergeRequestListBodyArgs::builder()
-            .state(MergeRequestState::Opened)
-            .list_args(None)
-            .assignee(Some(
-                Member::builder()
-                    .name("tom".to_string())
-                    .username("tsawyer".to_string())
-                    .id(123456)
-                    .build()
-                    .unwrap(),
-            ))
-            .build()
-            .unwrap();
-        let merge_requests = github.list(args).unwrap();
+    fn test_list_discussions_groups_replies_by_thread() {
+        let comments = r#"[
+            {"id": 10, "path": "src/main.rs", "line": 12, "user": {"login": "tom"},
+             "body": "Should this be a constant?", "created_at": "2024-01-15T10:30:00Z"},
+            {"id": 11, "path": "src/lib.rs", "line": null, "original_line": 3,
+             "user": {"login": "tom"}, "body": "Typo", "created_at": "2024-01-15T10:31:00Z"},
+            {"id": 12, "in_reply_to_id": 10, "path": "src/main.rs", "line": 12,
+             "user": {"login": "ana"}, "body": "Done", "created_at": "2024-01-15T11:30:00Z"}
+        ]"#;
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some(comments), None);
+        let (client, github) =
+            setup_client!(contracts, default_github(), dyn MergeRequestDiscussion);
+        let discussions = github.list_discussions(23).unwrap();
         assert_eq!(
-            "https://api.github.com/issues?state=open&filter=assigned",
-            *client.url()
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/comments?per_page=100&page=1",
+            *client.url(),
         );
-        assert!(merge_requests.len() == 1);
+        assert_eq!(2, discussions.len());
+        assert_eq!("10", discussions[0].id);
+        assert_eq!(Some(12), discussions[0].line);
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            vec![10, 12],
+            discussions[0]
+                .notes
+                .iter()
+                .map(|note| note.id)
+                .collect::<Vec<_>>()
         );
+        assert_eq!("11", discussions[1].id);
+        assert_eq!("src/lib.rs", discussions[1].file);
+        assert_eq!(Some(3), discussions[1].line);
     }
 
     #[test]
-    fn test_get_pull_requests_for_auth_user_is_author() {
-        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
-            200,
-            "list_issues_user.json",
-            None,
+    fn test_reply_to_review_comment_thread() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(201, Some("{}"), None);
+        let (client, github) =
+            setup_client!(contracts, default_github(), dyn MergeRequestDiscussion);
+        let args = DiscussionReplyBodyArgs::builder()
+            .id(23)
+            .discussion_id("10".to_string())
+            .body("Done".to_string())
+            .build()
+            .unwrap();
+        github.reply(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/comments/10/replies",
+            *client.url(),
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        let args = MergeRequestListBodyArgs::builder()
-            .state(MergeRequestState::Opened)
-            .list_args(None)
-            .author(Some(
-                Member::builder()
-                    .name("tom".to_string())
-                    .username("tsawyer".to_string())
-                    .id(12345)
-                    .build()
-                    .unwrap(),
-            ))
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("Done", body["body"]);
+    }
+
+    #[test]
+    fn test_review_request_changes() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some("{}"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ReviewMergeRequest);
+        let args = ReviewBodyArgs::builder()
+            .id(23)
+            .event(ReviewEvent::RequestChanges)
+            .body("Needs tests".to_string())
             .build()
             .unwrap();
-        let merge_requests = github.list(args).unwrap();
+        github.review(args).unwrap();
         assert_eq!(
-            "https://api.github.com/issues?state=open&filter=created",
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/reviews",
             *client.url()
         );
-        assert!(merge_requests.len() == 1);
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("REQUEST_CHANGES", body["event"]);
+        assert_eq!("Needs tests", body["body"]);
+    }
+
+    #[test]
+    fn test_submit_review_in_one_request() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some("{}"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestReview);
+        let mut review = ReviewFile::new(23, "Add offline reviews", "9c5bf00");
+        review.body = "Looks good overall".to_string();
+        review.comments.push(ReviewComment {
+            file: "src/main.rs".to_string(),
+            line: 10,
+            body: "Handle the error".to_string(),
+        });
+        github.submit_review(review).unwrap();
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/reviews",
+            *client.url(),
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("9c5bf00", body["commit_id"]);
+        assert_eq!("Looks good overall", body["body"]);
+        assert_eq!("COMMENT", body["event"]);
+        assert_eq!(
+            serde_json::json!([{
+                "path": "src/main.rs",
+                "line": 10,
+                "side": "RIGHT",
+                "body": "Handle the error"
+            }]),
+            body["comments"]
         );
     }
 
     #[test]
-    fn test_create_merge_request_comment() {
+    fn test_apply_suggestions_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequestSuggestion);
+        assert!(github.list_suggestions(23).is_err());
+        assert!(github.apply_suggestions(&[1], None).is_err());
+    }
+
+    #[test]
+    fn test_update_pull_request_comment() {
         let contracts =
-            ResponseContracts::new(ContractType::Github).add_body::<String>(201, None, None);
+            ResponseContracts::new(ContractType::Github).add_contract(200, "comment.json", None);
         let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
         let args = CommentMergeRequestBodyArgs::builder()
             .id(23)
-            .comment("Looks good to me".to_string())
+            .comment("LGTM!".to_string())
             .build()
             .unwrap();
-        github.create(args).unwrap();
+        github.update(1, args).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/issues/23/comments",
+            "https://api.github.com/repos/jordilin/githapi/issues/comments/1",
             *client.url(),
         );
+        assert_eq!(http::Method::PATCH, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_delete_pull_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(204, None, None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        github.delete(23, 1).unwrap();
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            "https://api.github.com/repos/jordilin/githapi/issues/comments/1",
+            *client.url(),
         );
+        assert_eq!(http::Method::DELETE, client.http_method.borrow()[0]);
     }
 
     #[test]
-    fn test_create_merge_request_comment_error_status_code() {
+    fn test_react_to_pull_request_with_gitlab_emoji_name() {
         let contracts =
-            ResponseContracts::new(ContractType::Github).add_body::<String>(500, None, None);
-        let (_, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
-        let args = CommentMergeRequestBodyArgs::builder()
+            ResponseContracts::new(ContractType::Github).add_body(201, Some("{}"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestReaction);
+        let args = ReactionBodyArgs::builder()
             .id(23)
-            .comment("Looks good to me".to_string())
+            .emoji("thumbsup".to_string())
             .build()
             .unwrap();
-        assert!(github.create(args).is_err());
+        github.react(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/issues/23/reactions",
+            *client.url(),
+        );
+        assert!(client.request_body().contains("\"+1\""));
     }
 
     #[test]
-    fn test_close_pull_request_ok() {
-        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+    fn test_react_with_unsupported_emoji_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequestReaction);
+        let args = ReactionBodyArgs::builder()
+            .id(23)
+            .comment_id(Some(1))
+            .emoji("coffee".to_string())
+            .build()
+            .unwrap();
+        let err = github.react(args).unwrap_err();
+        match err.downcast_ref::<error::GRError>() {
+            Some(error::GRError::OperationNotSupported(msg)) => {
+                assert!(msg.starts_with("Github does not support the :coffee: reaction"))
+            }
+            _ => panic!("Expected GRError::OperationNotSupported"),
+        }
+    }
+
+    #[test]
+    fn test_pull_request_comment_num_pages() {
+        let link_header = r#"<https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=2>; rel="next", <https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=2>; rel="last""#;
+        let mut headers = Headers::new();
+        headers.set("link".to_string(), link_header.to_string());
+        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
             200,
-            "merge_request.json",
             None,
+            Some(headers),
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        github.close(23).unwrap();
+        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestListBodyArgs::builder()
+            .id(23)
+            .list_args(None)
+            .build()
+            .unwrap();
+        assert_eq!(Some(2), github.num_pages(args).unwrap());
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls/23",
+            "https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=1",
             *client.url(),
         );
-        let actual_method = client.http_method.borrow();
-        assert_eq!(http::Method::PATCH, actual_method[0]);
         assert_eq!(
             Some(ApiOperation::MergeRequest),
             *client.api_operation.borrow()
@@ -1046,96 +2717,163 @@ mod test {
     }
 
     #[test]
-    fn test_get_pull_request_details() {
-        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+    fn test_merge_queue_status() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
             200,
-            "merge_request.json",
+            Some(
+                r#"{"data":{"repository":{"pullRequest":{"mergeQueueEntry":{"position":3,"state":"QUEUED","estimatedTimeToMerge":1260,"pullRequest":{"number":23,"baseRefName":"main"}}}}}}"#,
+            ),
             None,
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        github.get(23).unwrap();
-        assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls/23",
-            *client.url(),
-        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeQueue);
+        let entry = github.status(23).unwrap();
+        assert_eq!("https://api.github.com/graphql", *client.url());
+        assert!(client.request_body().contains("\"number\":23"));
+        assert_eq!(Some(3), entry.position);
+        assert_eq!("queued", entry.status);
+        assert_eq!("main", entry.target_branch);
+        assert_eq!(Some(Seconds::new(1260)), entry.eta);
+    }
+
+    #[test]
+    fn test_rebase_updates_pull_request_branch() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                202,
+                Some(r#"{"message": "Updating pull request branch.", "url": "https://github.com/jordilin/githapi/pull/23"}"#),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestRebase);
+        let status = github.rebase(23).unwrap();
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/update-branch",
+            *client.url()
         );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[1]);
+        assert!(client
+            .request_body()
+            .contains("\"expected_head_sha\":\"fcbb7490d4a216c2d162fa5466184e30dae1f087\""));
+        assert!(status.in_progress);
+        assert_eq!("fcbb7490d4a216c2d162fa5466184e30dae1f087", status.sha);
     }
 
     #[test]
-    fn test_github_merge_pull_request() {
-        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+    fn test_rebase_status_in_progress_until_head_moves() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(200, "merge_request.json", None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeRequestRebase);
+        let started = RebaseStatus::builder()
+            .id(23)
+            .in_progress(true)
+            .sha("fcbb7490d4a216c2d162fa5466184e30dae1f087".to_string())
+            .build()
+            .unwrap();
+        assert!(github.rebase_status(&started).unwrap().in_progress);
+        let started = RebaseStatus::builder()
+            .id(23)
+            .in_progress(true)
+            .sha("0000000".to_string())
+            .build()
+            .unwrap();
+        assert!(!github.rebase_status(&started).unwrap().in_progress);
+    }
+
+    #[test]
+    fn test_merge_queue_status_not_queued_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
             200,
-            "merge_request.json",
+            Some(r#"{"data":{"repository":{"pullRequest":{"mergeQueueEntry":null}}}}"#),
             None,
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
-        github.merge(23).unwrap();
-        assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/pulls/23/merge",
-            *client.url(),
-        );
-        let actual_method = client.http_method.borrow();
-        assert_eq!(http::Method::PUT, actual_method[0]);
-        assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
-        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn MergeQueue);
+        assert!(github.status(23).is_err());
     }
 
     #[test]
-    fn test_list_pull_request_comments() {
+    fn test_merge_queue_add_enqueues_pull_request_node() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(
+                    r#"{"data":{"enqueuePullRequest":{"mergeQueueEntry":{"position":1,"state":"AWAITING_CHECKS","estimatedTimeToMerge":null,"pullRequest":{"number":23,"baseRefName":"main"}}}}}"#,
+                ),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeQueue);
+        let entry = github.add(23).unwrap();
+        let body = client.request_body();
+        assert!(body.contains("enqueuePullRequest"));
+        assert!(body.contains("\"id\":\"abcdefg\""));
+        assert_eq!(Some(1), entry.position);
+        assert_eq!("awaiting_checks", entry.status);
+        assert_eq!(None, entry.eta);
+    }
+
+    #[test]
+    fn test_approve_pull_request_submits_approve_review() {
         let contracts = ResponseContracts::new(ContractType::Github).add_body(
             200,
-            Some(format!(
-                "[{}]",
-                get_contract(ContractType::Github, "comment.json")
-            )),
+            Some(r#"{"id":80,"state":"APPROVED"}"#),
             None,
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
-        let args = CommentMergeRequestListBodyArgs::builder()
-            .id(23)
-            .list_args(None)
-            .build()
-            .unwrap();
-        github.list(args).unwrap();
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let merge_request = github.approve(23).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/issues/23/comments",
-            *client.url(),
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/reviews",
+            *client.url()
         );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("APPROVE", body["event"]);
+        assert!(body.get("body").is_none());
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            "https://github.com/jordilin/githapi/pull/23",
+            merge_request.web_url
         );
     }
 
     #[test]
-    fn test_pull_request_comment_num_pages() {
-        let link_header = r#"<https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=2>; rel="next", <https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=2>; rel="last""#;
-        let mut headers = Headers::new();
-        headers.set("link".to_string(), link_header.to_string());
-        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+    fn test_list_changes_of_renamed_file() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
             200,
+            "pull_request_files.json",
             None,
-            Some(headers),
         );
-        let (client, github) = setup_client!(contracts, default_github(), dyn CommentMergeRequest);
-        let args = CommentMergeRequestListBodyArgs::builder()
-            .id(23)
-            .list_args(None)
-            .build()
-            .unwrap();
-        assert_eq!(Some(2), github.num_pages(args).unwrap());
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequestDiff);
+        let changes = github.list_changes(23).unwrap();
         assert_eq!(
-            "https://api.github.com/repos/jordilin/githapi/issues/23/comments?page=1",
-            *client.url(),
+            "https://api.github.com/repos/jordilin/githapi/pulls/23/files",
+            *client.url()
         );
+        assert_eq!((2, 1), (changes[0].additions, changes[0].deletions));
+        assert_eq!(FileChangeStatus::Renamed, changes[1].status);
+        assert_eq!("docs/intro.md", changes[1].old_path);
+        assert_eq!("docs/guide.md", changes[1].path);
+        assert!(changes[1].patch.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_draft_marks_ready_for_review() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "merge_request.json", None)
+            .add_body(
+                200,
+                Some(r#"{"data":{"markPullRequestReadyForReview":{"pullRequest":{"number":23}}}}"#),
+                None,
+            )
+            .add_contract(200, "merge_request.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn MergeRequest);
+        let merge_request = github.toggle_draft(23, false).unwrap();
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            "https://api.github.com/repos/jordilin/githapi/pulls/23",
+            *client.url()
+        );
+        assert_eq!(
+            vec![http::Method::GET, http::Method::POST, http::Method::GET],
+            *client.http_method.borrow()
         );
+        assert_eq!("New Feature", merge_request.title);
     }
 }
@@ -1,14 +1,25 @@
 use crate::{
-    api_traits::{ApiOperation, ProjectMember, RemoteProject, RemoteTag},
+    api_traits::{
+        ApiOperation, ProjectAccessCheck, ProjectBlame, ProjectDependency, ProjectInvitation,
+        ProjectLanguages, ProjectMember, ProjectMemberAccess, ProjectMirror, RemoteProject,
+        RemoteTag,
+    },
     cli::browse::BrowseOptions,
-    cmds::project::{Member, Project, ProjectListBodyArgs, Tag},
+    cmds::project::{
+        AccessGrant, BlameLine, Dependency, Invitation, Member, MemberAccessLevel, Membership,
+        Mirror, MirrorKind, Project, ProjectAccess, ProjectBlameBodyArgs, ProjectLanguage,
+        ProjectListBodyArgs, Tag,
+    },
     error::GRError,
+    http::{self, Body, Headers},
     io::{CmdInfo, HttpResponse, HttpRunner},
-    remote::{query, URLQueryParamBuilder},
+    json_loads,
+    remote::{encode_query_param, query, URLQueryParamBuilder},
 };
 
 use super::Github;
-use crate::Result;
+use crate::{time, Error, Result};
+use chrono::DateTime;
 
 impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Github<R> {
     fn get_project_data(&self, id: Option<i64>, path: Option<&str>) -> Result<CmdInfo> {
@@ -17,24 +28,16 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Github<R> {
         // projects in a given repository. Getting a repository by ID is not
         // supported in their REST API.
         if let Some(id) = id {
-            return Err(GRError::OperationNotSupported(format!(
-                "Getting project data by id is not supported in Github: {}",
-                id
-            ))
-            .into());
-        };
-        let url = if let Some(path) = path {
-            format!("{}/repos/{}", self.rest_api_basepath, path)
-        } else {
-            format!("{}/repos/{}", self.rest_api_basepath, self.path)
+            return Err(project_by_id_not_supported(id));
         };
+        let url = self.repo_url(path);
         let project = query::get::<_, (), Project>(
             &self.runner,
             &url,
             None,
             self.request_headers(),
             ApiOperation::Project,
-            |value| GithubProjectFields::from(value).into(),
+            |value| Ok(GithubProjectFields::try_from(value)?.into()),
         )?;
         Ok(CmdInfo::Project(project))
     }
@@ -51,7 +54,7 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Github<R> {
             self.request_headers(),
             None,
             ApiOperation::Project,
-            |value| GithubMemberFields::from(value).into(),
+            |value| Ok(GithubMemberFields::try_from(value)?.into()),
         )?;
         Ok(CmdInfo::Members(members))
     }
@@ -65,6 +68,19 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Github<R> {
             BrowseOptions::Pipelines => format!("{}/actions", base_url),
             BrowseOptions::PipelineId(id) => format!("{}/actions/runs/{}", base_url, id),
             BrowseOptions::Releases => format!("{}/releases", base_url),
+            BrowseOptions::NewMergeRequest {
+                source_branch,
+                target_branch,
+                title,
+                description,
+            } => format!(
+                "{}/compare/{}...{}?expand=1&title={}&body={}",
+                base_url,
+                target_branch,
+                source_branch,
+                encode_query_param(&title),
+                encode_query_param(&description)
+            ),
             // Manual is only one URL and it's the user guide. Handled in the
             // browser command.
             BrowseOptions::Manual => unreachable!(),
@@ -80,7 +96,7 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Github<R> {
             self.request_headers(),
             None,
             ApiOperation::Project,
-            |value| GithubProjectFields::from(value).into(),
+            |value| Ok(GithubProjectFields::try_from(value)?.into()),
         )?;
         Ok(projects)
     }
@@ -120,7 +136,7 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteTag for Github<R> {
             self.request_headers(),
             None,
             ApiOperation::RepositoryTag,
-            |value| GithubRepositoryTagFields::from(value).into(),
+            |value| Ok(GithubRepositoryTagFields::try_from(value)?.into()),
         )?;
         Ok(tags)
     }
@@ -128,39 +144,384 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteTag for Github<R> {
 
 impl<R: HttpRunner<Response = HttpResponse>> ProjectMember for Github<R> {
     fn list(&self, args: ProjectListBodyArgs) -> Result<Vec<Member>> {
-        let url = &format!(
-            "{}/repos/{}/contributors",
-            self.rest_api_basepath, self.path
-        );
+        let url = self.members_url(&args);
         let members = query::paged(
             &self.runner,
-            url,
+            &url,
             args.from_to_page,
             self.request_headers(),
             None,
             ApiOperation::Project,
-            |value| GithubMemberFields::from(value).into(),
+            |value| Ok(GithubMemberFields::try_from(value)?.into()),
         )?;
         Ok(members)
     }
 }
 
+impl<R: HttpRunner<Response = HttpResponse>> ProjectMemberAccess for Github<R> {
+    // https://docs.github.com/en/rest/collaborators/collaborators?apiVersion=2022-11-28#add-a-repository-collaborator
+    fn add_member(&self, username: &str, access: MemberAccessLevel) -> Result<Membership> {
+        let url = format!(
+            "{}/repos/{}/collaborators/{}",
+            self.rest_api_basepath, self.path, username
+        );
+        let mut body = Body::new();
+        body.add("permission", github_permission(access));
+        let response = query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Project,
+            http::Method::PUT,
+        )?;
+        // 201 when an invitation is sent, 204 when the user already was a
+        // collaborator or is a member of the organization.
+        let state = if response.status == 201 {
+            "invited"
+        } else {
+            "active"
+        };
+        Ok(Membership::builder()
+            .username(username.to_string())
+            .access(access)
+            .state(state.to_string())
+            .build()
+            .unwrap())
+    }
+
+    // Adding an existing collaborator updates its permission.
+    fn update_member(&self, username: &str, access: MemberAccessLevel) -> Result<Membership> {
+        self.add_member(username, access)
+    }
+
+    // https://docs.github.com/en/rest/collaborators/collaborators?apiVersion=2022-11-28#remove-a-repository-collaborator
+    fn remove_member(&self, username: &str) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/collaborators/{}",
+            self.rest_api_basepath, self.path, username
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.github.com/en/rest/orgs/blocking?apiVersion=2022-11-28#block-a-user-from-an-organization
+    fn block_member(&self, username: &str) -> Result<()> {
+        let org = self.path.split('/').next().unwrap_or_default();
+        let url = format!(
+            "{}/orgs/{}/blocks/{}",
+            self.rest_api_basepath, org, username
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+            http::Method::PUT,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.github.com/en/rest/search/search?apiVersion=2022-11-28#search-users
+    fn invite_member(&self, email: &str, access: MemberAccessLevel) -> Result<Membership> {
+        let url = format!(
+            "{}/search/users?q={}+in:email",
+            self.rest_api_basepath,
+            encode_query_param(email)
+        );
+        let users = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        let Some(username) = users["items"][0]["login"].as_str() else {
+            return Err(GRError::PreconditionNotMet(format!(
+                "No Github user has {} as public email. Add them by username instead",
+                email
+            ))
+            .into());
+        };
+        self.add_member(username, access)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ProjectInvitation for Github<R> {
+    // https://docs.github.com/en/rest/collaborators/invitations?apiVersion=2022-11-28#list-repository-invitations
+    fn list_invitations(&self) -> Result<Vec<Invitation>> {
+        self.repository_invitations()?
+            .iter()
+            .map(|invitation| {
+                Ok(Invitation::builder()
+                    .invitee(
+                        invitation["invitee"]["login"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string(),
+                    )
+                    .access(github_access(
+                        invitation["permissions"].as_str().unwrap_or_default(),
+                    ))
+                    .invited_by(
+                        invitation["inviter"]["login"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string(),
+                    )
+                    .created_at(time::json_optional_timestamp(invitation, "created_at")?)
+                    .expired(invitation["expired"].as_bool().unwrap_or_default())
+                    .build()
+                    .unwrap())
+            })
+            .collect()
+    }
+
+    // Invitations are deleted by ID, look it up by the invitee username.
+    // https://docs.github.com/en/rest/collaborators/invitations?apiVersion=2022-11-28#delete-a-repository-invitation
+    fn revoke_invitation(&self, invitee: &str) -> Result<()> {
+        let invitations = self.repository_invitations()?;
+        let Some(id) = invitations
+            .iter()
+            .find(|invitation| {
+                invitation["invitee"]["login"]
+                    .as_str()
+                    .is_some_and(|login| login.eq_ignore_ascii_case(invitee))
+            })
+            .and_then(|invitation| invitation["id"].as_i64())
+        else {
+            return Err(GRError::PreconditionNotMet(format!(
+                "No pending invitation found for {}",
+                invitee
+            ))
+            .into());
+        };
+        let url = format!(
+            "{}/repos/{}/invitations/{}",
+            self.rest_api_basepath, self.path, id
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+}
+
+/// Access comes from being a direct collaborator, from the teams the
+/// repository is shared with and from the role in the organization that owns
+/// the repository.
+impl<R: HttpRunner<Response = HttpResponse>> ProjectAccessCheck for Github<R> {
+    fn access_check(&self, username: &str) -> Result<ProjectAccess> {
+        let (owner, _) = self.path.split_once('/').unwrap_or_default();
+        let mut grants = Vec::new();
+        if owner.eq_ignore_ascii_case(username) {
+            grants.push(access_grant("repository owner".to_string(), "admin"));
+        }
+        // https://docs.github.com/en/rest/collaborators/collaborators?apiVersion=2022-11-28#list-repository-collaborators
+        let url = format!(
+            "{}/repos/{}/collaborators?affiliation=direct&per_page=100",
+            self.rest_api_basepath, self.path
+        );
+        let collaborators = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        if let Some(collaborator) = collaborators.as_array().and_then(|collaborators| {
+            collaborators.iter().find(|collaborator| {
+                collaborator["login"]
+                    .as_str()
+                    .is_some_and(|login| login.eq_ignore_ascii_case(username))
+            })
+        }) {
+            grants.push(access_grant(
+                "direct collaborator".to_string(),
+                collaborator["role_name"].as_str().unwrap_or_default(),
+            ));
+        }
+        // https://docs.github.com/en/rest/orgs/members?apiVersion=2022-11-28#get-organization-membership-for-a-user
+        // Not found if the owner is a user or the user is not a member.
+        let url = format!(
+            "{}/orgs/{}/memberships/{}",
+            self.rest_api_basepath, owner, username
+        );
+        let membership = self
+            .get_if_found(&url)?
+            .filter(|membership| membership["state"] == "active");
+        if let Some(membership) = membership {
+            grants.extend(self.organization_grants(owner, username, &membership)?);
+        }
+        Ok(ProjectAccess::builder()
+            .username(username.to_string())
+            .grants(grants)
+            .build()
+            .unwrap())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    fn organization_grants(
+        &self,
+        org: &str,
+        username: &str,
+        membership: &serde_json::Value,
+    ) -> Result<Vec<AccessGrant>> {
+        let mut grants = Vec::new();
+        if membership["role"] == "admin" {
+            grants.push(access_grant("organization owner".to_string(), "admin"));
+        }
+        // https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#list-repository-teams
+        let url = format!(
+            "{}/repos/{}/teams?per_page=100",
+            self.rest_api_basepath, self.path
+        );
+        let teams = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        for team in teams.as_array().cloned().unwrap_or_default() {
+            let slug = team["slug"].as_str().unwrap_or_default();
+            // Includes the members of child teams.
+            // https://docs.github.com/en/rest/teams/members?apiVersion=2022-11-28#get-team-membership-for-a-user
+            let url = format!(
+                "{}/orgs/{}/teams/{}/memberships/{}",
+                self.rest_api_basepath, org, slug, username
+            );
+            if let Some(team_membership) = self.get_if_found(&url)? {
+                if team_membership["state"] == "active" {
+                    grants.push(access_grant(
+                        format!("team {}", slug),
+                        team["permission"].as_str().unwrap_or_default(),
+                    ));
+                }
+            }
+        }
+        // Only visible to organization members.
+        // https://docs.github.com/en/rest/orgs/orgs?apiVersion=2022-11-28#get-an-organization
+        let url = format!("{}/orgs/{}", self.rest_api_basepath, org);
+        let organization = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        match organization["default_repository_permission"].as_str() {
+            Some("none") | None => {}
+            Some(permission) => grants.push(access_grant(
+                "organization base permission".to_string(),
+                permission,
+            )),
+        }
+        Ok(grants)
+    }
+
+    /// Gets a resource that might not exist, ex. the membership of a user.
+    fn get_if_found(&self, url: &str) -> Result<Option<serde_json::Value>> {
+        let response = query::send_raw_unchecked::<_, ()>(
+            &self.runner,
+            url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+            http::Method::GET,
+        )?;
+        if response.status == 404 {
+            return Ok(None);
+        }
+        if !response.is_ok(&http::Method::GET) {
+            return Err(query::request_error(
+                &http::Method::GET,
+                url,
+                &ApiOperation::Project,
+                &response,
+            ));
+        }
+        Ok(Some(json_loads(&response.body)?))
+    }
+
+    fn repository_invitations(&self) -> Result<Vec<serde_json::Value>> {
+        let url = format!(
+            "{}/repos/{}/invitations?per_page=100",
+            self.rest_api_basepath, self.path
+        );
+        let invitations = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        Ok(invitations.as_array().cloned().unwrap_or_default())
+    }
+}
+
+fn access_grant(source: String, permission: &str) -> AccessGrant {
+    AccessGrant::builder()
+        .source(source)
+        .access(github_access(permission))
+        .build()
+        .unwrap()
+}
+
+/// Roles, teams and invitations report `read` and `write` or `pull` and
+/// `push` depending on the endpoint.
+fn github_access(permission: &str) -> MemberAccessLevel {
+    match permission {
+        "triage" => MemberAccessLevel::Reporter,
+        "write" | "push" => MemberAccessLevel::Developer,
+        "maintain" => MemberAccessLevel::Maintainer,
+        "admin" => MemberAccessLevel::Owner,
+        _ => MemberAccessLevel::Guest,
+    }
+}
+
+fn github_permission(access: MemberAccessLevel) -> &'static str {
+    match access {
+        MemberAccessLevel::Guest => "pull",
+        MemberAccessLevel::Reporter => "triage",
+        MemberAccessLevel::Developer => "push",
+        MemberAccessLevel::Maintainer => "maintain",
+        MemberAccessLevel::Owner => "admin",
+    }
+}
+
 pub struct GithubRepositoryTagFields {
     tags: Tag,
 }
 
-impl From<&serde_json::Value> for GithubRepositoryTagFields {
-    fn from(tag_data: &serde_json::Value) -> Self {
-        GithubRepositoryTagFields {
+impl TryFrom<&serde_json::Value> for GithubRepositoryTagFields {
+    type Error = Error;
+
+    fn try_from(tag_data: &serde_json::Value) -> Result<Self> {
+        Ok(GithubRepositoryTagFields {
             tags: Tag::builder()
                 .name(tag_data["name"].as_str().unwrap().to_string())
                 .sha(tag_data["commit"]["sha"].as_str().unwrap().to_string())
                 // Github response does not provide a created_at field, so set
                 // it up to UNIX epoch.
-                .created_at("1970-01-01T00:00:00Z".to_string())
+                .created_at(DateTime::UNIX_EPOCH)
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -170,6 +531,375 @@ impl From<GithubRepositoryTagFields> for Tag {
     }
 }
 
+const BLAME_QUERY: &str = r#"query($owner: String!, $name: String!, $ref: String!, $path: String!) {
+  repository(owner: $owner, name: $name) {
+    object(expression: $ref) {
+      ... on Commit {
+        blame(path: $path) {
+          ranges {
+            startingLine
+            endingLine
+            commit {
+              oid
+              author {
+                name
+                date
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+/// Blame is only available in the Github GraphQL API. It returns ranges of
+/// lines per commit but not the lines themselves, which are gathered from the
+/// file contents at the same reference.
+impl<R: HttpRunner<Response = HttpResponse>> ProjectBlame for Github<R> {
+    // https://docs.github.com/en/graphql/reference/objects#blame
+    fn blame(&self, args: ProjectBlameBodyArgs) -> Result<Vec<BlameLine>> {
+        let (owner, name) = self.path.split_once('/').unwrap_or_default();
+        let mut body = Body::new();
+        body.add("query", serde_json::json!(BLAME_QUERY));
+        body.add(
+            "variables",
+            serde_json::json!({
+                "owner": owner,
+                "name": name,
+                "ref": args.git_ref,
+                "path": args.path,
+            }),
+        );
+        let response = query::send_json(
+            &self.runner,
+            &self.graphql_url,
+            Some(&body),
+            self.request_headers(),
+            ApiOperation::Project,
+            http::Method::POST,
+        )?;
+        if let Some(errors) = response.get("errors") {
+            return Err(GRError::RemoteServerError(format!(
+                "Failed to blame {} at {}: {}",
+                args.path, args.git_ref, errors
+            ))
+            .into());
+        }
+        let ranges = response["data"]["repository"]["object"]["blame"]["ranges"]
+            .as_array()
+            .ok_or_else(|| {
+                GRError::RemoteUnexpectedResponseContract(format!(
+                    "Expected blame ranges but got: {}",
+                    response
+                ))
+            })?;
+        let contents = self.get_file_contents(&args.path, &args.git_ref)?;
+        let contents = contents.lines().collect::<Vec<&str>>();
+        let (start, end) = args.range.unwrap_or((1, usize::MAX));
+        let mut lines = Vec::new();
+        for range in ranges {
+            let commit = &range["commit"];
+            let starting_line = range["startingLine"].as_u64().unwrap_or_default() as usize;
+            let ending_line = range["endingLine"].as_u64().unwrap_or_default() as usize;
+            for line in starting_line.max(start)..=ending_line.min(end) {
+                lines.push(
+                    BlameLine::builder()
+                        .line(line)
+                        .commit(
+                            commit["oid"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .chars()
+                                .take(12)
+                                .collect(),
+                        )
+                        .author(
+                            commit["author"]["name"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                        )
+                        .date(
+                            commit["author"]["date"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                        )
+                        .content(contents.get(line - 1).unwrap_or(&"").to_string())
+                        .build()
+                        .unwrap(),
+                );
+            }
+        }
+        Ok(lines)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    // https://docs.github.com/en/rest/repos/contents?apiVersion=2022-11-28#get-repository-content
+    fn get_file_contents(&self, path: &str, git_ref: &str) -> Result<String> {
+        let path = path
+            .split('/')
+            .map(encode_query_param)
+            .collect::<Vec<_>>()
+            .join("/");
+        let url = format!(
+            "{}/repos/{}/contents/{}?ref={}",
+            self.rest_api_basepath,
+            self.path,
+            path,
+            encode_query_param(git_ref)
+        );
+        let mut headers = self.request_headers();
+        let mut raw = Headers::new();
+        raw.set("Accept", "application/vnd.github.raw+json");
+        headers.extend(raw);
+        let response =
+            query::get_raw::<_, ()>(&self.runner, &url, None, headers, ApiOperation::Project)?;
+        Ok(response.body)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ProjectLanguages for Github<R> {
+    // Github returns the number of bytes of code written in each language.
+    // https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#list-repository-languages
+    fn languages(&self, id: Option<i64>, path: Option<&str>) -> Result<Vec<ProjectLanguage>> {
+        if let Some(id) = id {
+            return Err(project_by_id_not_supported(id));
+        };
+        let url = format!("{}/languages", self.repo_url(path));
+        let bytes = query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+            |value| {
+                Ok(value
+                    .as_object()
+                    .map(|languages| {
+                        languages
+                            .iter()
+                            .map(|(name, bytes)| (name.clone(), bytes.as_u64().unwrap_or_default()))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default())
+            },
+        )?;
+        let total = bytes.iter().map(|(_, bytes)| bytes).sum::<u64>();
+        if total == 0 {
+            return Ok(Vec::new());
+        }
+        Ok(bytes
+            .iter()
+            .map(|(name, bytes)| ProjectLanguage::new(name, *bytes as f64 * 100.0 / total as f64))
+            .collect())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    fn repo_url(&self, path: Option<&str>) -> String {
+        format!(
+            "{}/repos/{}",
+            self.rest_api_basepath,
+            path.unwrap_or(&self.path)
+        )
+    }
+}
+
+fn project_by_id_not_supported(id: i64) -> anyhow::Error {
+    GRError::OperationNotSupported(format!(
+        "Getting project data by id is not supported in Github: {}",
+        id
+    ))
+    .into()
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ProjectDependency for Github<R> {
+    // The SBOM is an SPDX document with all the packages in the dependency
+    // graph, including the repository itself.
+    // https://docs.github.com/en/rest/dependency-graph/sboms?apiVersion=2022-11-28
+    fn list_dependencies(&self) -> Result<Vec<Dependency>> {
+        let url = format!(
+            "{}/repos/{}/dependency-graph/sbom",
+            self.rest_api_basepath, self.path
+        );
+        let sbom = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        let sbom = &sbom["sbom"];
+        let packages = sbom["packages"].as_array().ok_or_else(|| {
+            GRError::RemoteUnexpectedResponseContract(format!(
+                "Expected an array of packages in the SBOM but got: {}",
+                sbom
+            ))
+        })?;
+        let described = sbom["documentDescribes"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        Ok(packages
+            .iter()
+            .filter(|package| !described.contains(&package["SPDXID"]))
+            .map(|package| GithubDependencyFields::from(package).into())
+            .collect())
+    }
+}
+
+/// Github has no native mirroring. Repositories that are mirrors of another
+/// repository expose a `mirror_url` and push mirrors are usually implemented
+/// with Github Actions workflows. Workflows with `mirror` in their name or
+/// file path are considered mirrors.
+impl<R: HttpRunner<Response = HttpResponse>> ProjectMirror for Github<R> {
+    fn list_mirrors(&self) -> Result<Vec<Mirror>> {
+        let repo = self.get_repo_json()?;
+        let mut mirrors = Vec::new();
+        if let Some(mirror_url) = repo["mirror_url"].as_str() {
+            mirrors.push(
+                Mirror::builder()
+                    .id(repo["id"].as_i64().unwrap_or_default())
+                    .kind(MirrorKind::Pull)
+                    .url(mirror_url.to_string())
+                    .enabled(true)
+                    .last_update_at(time::json_optional_timestamp(&repo, "pushed_at")?)
+                    .build()
+                    .unwrap(),
+            );
+        }
+        for workflow in self.list_mirror_workflows()? {
+            mirrors.push(self.workflow_mirror_status(workflow)?);
+        }
+        Ok(mirrors)
+    }
+
+    fn sync_mirrors(&self) -> Result<Vec<Mirror>> {
+        let repo = self.get_repo_json()?;
+        let default_branch = repo["default_branch"].as_str().unwrap_or("main");
+        let workflows = self
+            .list_mirror_workflows()?
+            .into_iter()
+            .filter(|workflow| workflow.enabled)
+            .collect::<Vec<Mirror>>();
+        if workflows.is_empty() {
+            return Err(GRError::PreconditionNotMet(
+                "No enabled mirror workflows found. Github mirrors can only be synced \
+                 through Github Actions workflows"
+                    .to_string(),
+            )
+            .into());
+        }
+        for workflow in workflows.iter() {
+            // https://docs.github.com/en/rest/actions/workflows?apiVersion=2022-11-28#create-a-workflow-dispatch-event
+            let url = format!(
+                "{}/repos/{}/actions/workflows/{}/dispatches",
+                self.rest_api_basepath, self.path, workflow.id
+            );
+            let mut body = Body::new();
+            body.add("ref", default_branch);
+            query::send_raw(
+                &self.runner,
+                &url,
+                Some(&body),
+                self.request_headers(),
+                ApiOperation::Project,
+                http::Method::POST,
+            )?;
+        }
+        Ok(workflows)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Github<R> {
+    fn get_repo_json(&self) -> Result<serde_json::Value> {
+        let url = format!("{}/repos/{}", self.rest_api_basepath, self.path);
+        query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )
+    }
+
+    // https://docs.github.com/en/rest/actions/workflows?apiVersion=2022-11-28#list-repository-workflows
+    fn list_mirror_workflows(&self) -> Result<Vec<Mirror>> {
+        let url = format!(
+            "{}/repos/{}/actions/workflows",
+            self.rest_api_basepath, self.path
+        );
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+            |value| {
+                Ok(value["workflows"]
+                    .as_array()
+                    .unwrap_or(&Vec::new())
+                    .iter()
+                    .filter(|workflow| is_mirror_workflow(workflow))
+                    .map(|workflow| GithubWorkflowMirrorFields::from(workflow).into())
+                    .collect())
+            },
+        )
+    }
+
+    /// Fills in the status of a mirror workflow using its latest run and its
+    /// latest successful run.
+    fn workflow_mirror_status(&self, mut mirror: Mirror) -> Result<Mirror> {
+        let runs_url = format!(
+            "{}/repos/{}/actions/workflows/{}/runs",
+            self.rest_api_basepath, self.path, mirror.id
+        );
+        let url = URLQueryParamBuilder::new(&runs_url)
+            .add_param("per_page", "1")
+            .build();
+        let latest = self.latest_workflow_run(&url)?;
+        if let Some(run) = latest {
+            mirror.status = if run["status"] == "completed" {
+                run["conclusion"].as_str().unwrap_or_default().to_string()
+            } else {
+                run["status"].as_str().unwrap_or_default().to_string()
+            };
+            mirror.last_update_at = time::json_optional_timestamp(&run, "updated_at")?;
+        }
+        let url = URLQueryParamBuilder::new(&runs_url)
+            .add_param("status", "success")
+            .add_param("per_page", "1")
+            .build();
+        if let Some(run) = self.latest_workflow_run(&url)? {
+            mirror.last_successful_update_at = time::json_optional_timestamp(&run, "updated_at")?;
+        }
+        Ok(mirror)
+    }
+
+    fn latest_workflow_run(&self, url: &str) -> Result<Option<serde_json::Value>> {
+        let runs = query::get_json::<_, ()>(
+            &self.runner,
+            url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        Ok(runs["workflow_runs"]
+            .as_array()
+            .and_then(|runs| runs.first())
+            .cloned())
+    }
+}
+
+fn is_mirror_workflow(workflow: &serde_json::Value) -> bool {
+    let name = workflow["name"].as_str().unwrap_or_default();
+    let path = workflow["path"].as_str().unwrap_or_default();
+    name.to_lowercase().contains("mirror") || path.to_lowercase().contains("mirror")
+}
+
 impl<R> Github<R> {
     fn list_project_url(&self, args: &ProjectListBodyArgs, num_pages: bool) -> String {
         let mut url = if args.tags {
@@ -178,12 +908,11 @@ impl<R> Github<R> {
                 self.rest_api_basepath, self.path
             ))
         } else if args.members {
-            URLQueryParamBuilder::new(&format!(
-                "{}/repos/{}/contributors",
-                self.rest_api_basepath, self.path
-            ))
+            URLQueryParamBuilder::new(&self.members_url(args))
         } else if args.stars {
             URLQueryParamBuilder::new(&format!("{}/user/starred", self.rest_api_basepath))
+        } else if let Some(org) = &args.group {
+            URLQueryParamBuilder::new(&format!("{}/orgs/{}/repos", self.rest_api_basepath, org))
         } else {
             let username = args.user.as_ref().unwrap().clone().username;
             // TODO - not needed - just /user/repos would do
@@ -198,20 +927,32 @@ impl<R> Github<R> {
         }
         url.build()
     }
+
+    fn members_url(&self, args: &ProjectListBodyArgs) -> String {
+        match &args.group {
+            Some(org) => format!("{}/orgs/{}/members", self.rest_api_basepath, org),
+            None => format!(
+                "{}/repos/{}/contributors",
+                self.rest_api_basepath, self.path
+            ),
+        }
+    }
 }
 
 pub struct GithubProjectFields {
     project: Project,
 }
 
-impl From<&serde_json::Value> for GithubProjectFields {
-    fn from(project_data: &serde_json::Value) -> Self {
-        GithubProjectFields {
+impl TryFrom<&serde_json::Value> for GithubProjectFields {
+    type Error = Error;
+
+    fn try_from(project_data: &serde_json::Value) -> Result<Self> {
+        Ok(GithubProjectFields {
             project: Project::builder()
                 .id(project_data["id"].as_i64().unwrap())
                 .default_branch(project_data["default_branch"].as_str().unwrap().to_string())
                 .html_url(project_data["html_url"].as_str().unwrap().to_string())
-                .created_at(project_data["created_at"].as_str().unwrap().to_string())
+                .created_at(time::json_timestamp(project_data, "created_at")?)
                 .description(
                     project_data["description"]
                         .as_str()
@@ -224,9 +965,44 @@ impl From<&serde_json::Value> for GithubProjectFields {
                         .unwrap_or_default()
                         .to_string(),
                 )
+                .license(
+                    project_data["license"]["name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .topics(
+                    project_data["topics"]
+                        .as_array()
+                        .map(|topics| {
+                            topics
+                                .iter()
+                                .filter_map(|topic| topic.as_str().map(String::from))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
+                .visibility(
+                    project_data["visibility"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .stars(
+                    project_data["stargazers_count"]
+                        .as_u64()
+                        .unwrap_or_default(),
+                )
+                .forks(project_data["forks_count"].as_u64().unwrap_or_default())
+                .avatar_url(
+                    project_data["owner"]["avatar_url"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -236,23 +1012,102 @@ impl From<GithubProjectFields> for Project {
     }
 }
 
+pub struct GithubDependencyFields {
+    dependency: Dependency,
+}
+
+impl From<&serde_json::Value> for GithubDependencyFields {
+    fn from(package: &serde_json::Value) -> Self {
+        let purl = package["externalRefs"]
+            .as_array()
+            .unwrap_or(&Vec::new())
+            .iter()
+            .find(|reference| reference["referenceType"] == "purl")
+            .and_then(|reference| reference["referenceLocator"].as_str())
+            .unwrap_or_default()
+            .to_string();
+        // purl format is pkg:type/namespace/name@version
+        let package_manager = purl
+            .strip_prefix("pkg:")
+            .and_then(|purl| purl.split('/').next())
+            .unwrap_or_default()
+            .to_string();
+        let licenses = match package["licenseConcluded"].as_str() {
+            Some("NOASSERTION") | None => Vec::new(),
+            Some(license) => vec![license.to_string()],
+        };
+        GithubDependencyFields {
+            dependency: Dependency::builder()
+                .name(package["name"].as_str().unwrap_or_default().to_string())
+                .version(
+                    package["versionInfo"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .package_manager(package_manager)
+                .purl(purl)
+                .licenses(licenses)
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubDependencyFields> for Dependency {
+    fn from(fields: GithubDependencyFields) -> Self {
+        fields.dependency
+    }
+}
+
+pub struct GithubWorkflowMirrorFields {
+    mirror: Mirror,
+}
+
+impl From<&serde_json::Value> for GithubWorkflowMirrorFields {
+    fn from(workflow: &serde_json::Value) -> Self {
+        GithubWorkflowMirrorFields {
+            mirror: Mirror::builder()
+                .id(workflow["id"].as_i64().unwrap_or_default())
+                .kind(MirrorKind::Workflow)
+                .url(
+                    workflow["html_url"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .enabled(workflow["state"] == "active")
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GithubWorkflowMirrorFields> for Mirror {
+    fn from(fields: GithubWorkflowMirrorFields) -> Self {
+        fields.mirror
+    }
+}
+
 pub struct GithubMemberFields {
     member: Member,
 }
 
-impl From<&serde_json::Value> for GithubMemberFields {
-    fn from(member_data: &serde_json::Value) -> Self {
-        GithubMemberFields {
+impl TryFrom<&serde_json::Value> for GithubMemberFields {
+    type Error = Error;
+
+    fn try_from(member_data: &serde_json::Value) -> Result<Self> {
+        Ok(GithubMemberFields {
             member: Member::builder()
                 .id(member_data["id"].as_i64().unwrap())
                 .username(member_data["login"].as_str().unwrap().to_string())
                 .name("".to_string())
                 // Github does not provide created_at field in the response for
                 // Members (aka contributors). Set it to UNIX epoch.
-                .created_at("1970-01-01T00:00:00Z".to_string())
+                .created_at(DateTime::UNIX_EPOCH)
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -267,6 +1122,7 @@ mod test {
 
     use crate::{
         cmds::project::ProjectListBodyArgs,
+        display::DisplayBody,
         http::Headers,
         setup_client,
         test::utils::{default_github, get_contract, ContractType, ResponseContracts},
@@ -297,11 +1153,33 @@ mod test {
         match result {
             Ok(CmdInfo::Project(project)) => {
                 assert_eq!(123456, project.id);
+                let columns = DisplayBody::from(project).columns;
+                let license = columns.iter().find(|c| c.name == "License").unwrap();
+                assert_eq!("MIT License", license.value);
             }
             _ => panic!("Expected project data"),
         }
     }
 
+    #[test]
+    fn test_get_project_languages_as_percentages() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "project_languages.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectLanguages);
+        let languages = github.languages(None, None).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/languages",
+            *client.url(),
+        );
+        assert_eq!(2, languages.len());
+        assert_eq!("Rust", languages[0].name);
+        assert!((languages[0].percentage - 92.15).abs() < 1e-9);
+        assert!((languages[1].percentage - 7.85).abs() < 1e-9);
+    }
+
     #[test]
     fn test_get_project_data_with_id_not_supported() {
         let contracts = ResponseContracts::new(ContractType::Github);
@@ -338,6 +1216,31 @@ mod test {
         assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
     }
 
+    #[test]
+    fn test_list_organization_repositories() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(format!(
+                "[{}]",
+                get_contract(ContractType::Github, "project.json")
+            )),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn RemoteProject);
+        let body_args = ProjectListBodyArgs::builder()
+            .from_to_page(None)
+            .user(None)
+            .group(Some("jordilin-org".to_string()))
+            .build()
+            .unwrap();
+        let projects = github.list(body_args).unwrap();
+        assert_eq!(1, projects.len());
+        assert_eq!(
+            "https://api.github.com/orgs/jordilin-org/repos",
+            *client.url()
+        );
+    }
+
     #[test]
     fn test_get_my_starred_projects() {
         let contracts =
@@ -422,6 +1325,23 @@ mod test {
         assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
     }
 
+    #[test]
+    fn test_get_url_new_merge_request_is_prefilled() {
+        let contracts = ResponseContracts::new(ContractType::Github);
+        let (_, github) = setup_client!(contracts, default_github(), dyn RemoteProject);
+        let url = github.get_url(BrowseOptions::NewMergeRequest {
+            source_branch: "feature/issues".to_string(),
+            target_branch: "main".to_string(),
+            title: "Add issues".to_string(),
+            description: "".to_string(),
+        });
+        assert_eq!(
+            "https://github.com/jordilin/githapi/compare/main...feature/issues?expand=1\
+             &title=Add%20issues&body=",
+            url
+        );
+    }
+
     #[test]
     fn test_get_url_pipeline_id() {
         let contracts = ResponseContracts::new(ContractType::Github);
@@ -480,6 +1400,197 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_add_member_sends_invitation() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            201,
+            Some(r#"{"id": 1, "permissions": "write"}"#),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectMemberAccess);
+        let membership = github
+            .add_member("octocat", MemberAccessLevel::Developer)
+            .unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/collaborators/octocat",
+            *client.url()
+        );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+        assert_eq!(r#"{"permission":"push"}"#, *client.request_body());
+        assert_eq!("invited", membership.state);
+    }
+
+    #[test]
+    fn test_update_existing_collaborator_is_active() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(204, None, None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn ProjectMemberAccess);
+        let membership = github
+            .update_member("octocat", MemberAccessLevel::Maintainer)
+            .unwrap();
+        assert_eq!("active", membership.state);
+        assert_eq!(MemberAccessLevel::Maintainer, membership.access);
+    }
+
+    #[test]
+    fn test_invite_member_by_public_email() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(201, Some(r#"{"id": 1, "permissions": "read"}"#), None)
+            .add_body(
+                200,
+                Some(r#"{"total_count": 1, "items": [{"login": "octocat", "id": 1}]}"#),
+                None,
+            );
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectMemberAccess);
+        let membership = github
+            .invite_member("octocat@github.com", MemberAccessLevel::Guest)
+            .unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/collaborators/octocat",
+            *client.url()
+        );
+        assert_eq!("octocat", membership.username);
+        assert_eq!("invited", membership.state);
+    }
+
+    #[test]
+    fn test_invite_member_without_public_email_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body(
+            200,
+            Some(r#"{"total_count": 0, "items": []}"#),
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectMemberAccess);
+        let err = github
+            .invite_member("tom@example.com", MemberAccessLevel::Guest)
+            .unwrap_err();
+        assert_eq!(
+            "https://api.github.com/search/users?q=tom%40example.com+in:email",
+            *client.url()
+        );
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(_)) => (),
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_access_check_collaborator_team_and_organization() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(r#"{"login": "jordilin", "default_repository_permission": "read"}"#),
+                None,
+            )
+            .add_body(200, Some(r#"{"state": "active", "role": "member"}"#), None)
+            .add_body::<String>(404, None, None)
+            .add_body(
+                200,
+                Some(r#"[{"slug": "docs", "permission": "triage"}, {"slug": "core", "permission": "maintain"}]"#),
+                None,
+            )
+            .add_body(200, Some(r#"{"state": "active", "role": "member"}"#), None)
+            .add_body(
+                200,
+                Some(r#"[{"login": "OctoCat", "role_name": "write"}, {"login": "other", "role_name": "admin"}]"#),
+                None,
+            );
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectAccessCheck);
+        let access = github.access_check("octocat").unwrap();
+        assert_eq!("https://api.github.com/orgs/jordilin", *client.url());
+        let grants = access
+            .grants
+            .iter()
+            .map(|grant| (grant.source.as_str(), grant.access))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("direct collaborator", MemberAccessLevel::Developer),
+                ("team core", MemberAccessLevel::Maintainer),
+                ("organization base permission", MemberAccessLevel::Guest),
+            ],
+            grants
+        );
+        assert_eq!(Some(MemberAccessLevel::Maintainer), access.effective());
+    }
+
+    #[test]
+    fn test_access_check_outside_organization() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(404, None, None)
+            .add_body(200, Some("[]"), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectAccessCheck);
+        let access = github.access_check("octocat").unwrap();
+        assert_eq!(
+            "https://api.github.com/orgs/jordilin/memberships/octocat",
+            *client.url()
+        );
+        assert_eq!(None, access.effective());
+    }
+
+    #[test]
+    fn test_access_check_repository_owner() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(404, None, None)
+            .add_body(200, Some("[]"), None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn ProjectAccessCheck);
+        let access = github.access_check("jordilin").unwrap();
+        assert_eq!(Some(MemberAccessLevel::Owner), access.effective());
+        assert_eq!("repository owner", access.grants[0].source);
+    }
+
+    const INVITATIONS: &str = r#"[{"id": 7, "invitee": {"login": "octocat"}, "inviter": {"login": "jordilin"}, "permissions": "write", "created_at": "2024-01-01T00:00:00Z", "expired": false}]"#;
+
+    #[test]
+    fn test_list_invitations() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some(INVITATIONS), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectInvitation);
+        let invitations = github.list_invitations().unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/invitations?per_page=100",
+            *client.url()
+        );
+        assert_eq!("octocat", invitations[0].invitee);
+        assert_eq!(MemberAccessLevel::Developer, invitations[0].access);
+        assert_eq!("jordilin", invitations[0].invited_by);
+        assert!(!invitations[0].expired);
+    }
+
+    #[test]
+    fn test_revoke_invitation_by_username() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(204, None, None)
+            .add_body(200, Some(INVITATIONS), None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectInvitation);
+        github.revoke_invitation("OctoCat").unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/invitations/7",
+            *client.url()
+        );
+        assert_eq!(http::Method::DELETE, client.http_method.borrow()[1]);
+    }
+
+    #[test]
+    fn test_revoke_invitation_not_found_is_error() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body(200, Some(INVITATIONS), None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn ProjectInvitation);
+        assert!(github.revoke_invitation("tom").is_err());
+    }
+
+    #[test]
+    fn test_block_member_in_organization() {
+        let contracts =
+            ResponseContracts::new(ContractType::Github).add_body::<String>(204, None, None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectMemberAccess);
+        github.block_member("spammer").unwrap();
+        assert_eq!(
+            "https://api.github.com/orgs/jordilin/blocks/spammer",
+            *client.url()
+        );
+    }
+
     #[test]
     fn test_list_project_members() {
         let contracts = ResponseContracts::new(ContractType::Github).add_contract(
@@ -508,6 +1619,28 @@ mod test {
         assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
     }
 
+    #[test]
+    fn test_list_organization_members() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "project_members.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectMember);
+        let args = ProjectListBodyArgs::builder()
+            .members(true)
+            .user(None)
+            .from_to_page(None)
+            .group(Some("jordilin".to_string()))
+            .build()
+            .unwrap();
+        ProjectMember::list(&*github, args).unwrap();
+        assert_eq!(
+            "https://api.github.com/orgs/jordilin/members",
+            *client.url()
+        );
+    }
+
     #[test]
     fn test_project_members_num_pages() {
         let link_header = "<https://api.github.com/repos/jordilin/githapi/contributors?page=2>; rel=\"next\", <https://api.github.com/repos/jordilin/githapi/contributors?page=2>; rel=\"last\"";
@@ -552,4 +1685,144 @@ mod test {
             *client.url()
         );
     }
+
+    #[test]
+    fn test_list_mirrors_detects_mirror_workflows() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_contract(200, "list_workflow_runs.json", None)
+            .add_contract(200, "list_workflow_runs.json", None)
+            .add_contract(200, "list_workflows.json", None)
+            .add_contract(200, "project.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectMirror);
+        let mirrors = github.list_mirrors().unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/workflows/269289/runs?status=success&per_page=1",
+            *client.url(),
+        );
+        assert_eq!(1, mirrors.len());
+        assert_eq!(MirrorKind::Workflow, mirrors[0].kind);
+        assert_eq!(269289, mirrors[0].id);
+        assert_eq!("success", mirrors[0].status);
+        assert_eq!(
+            time::parse_timestamp("2024-02-12T09:31:12Z"),
+            mirrors[0].last_successful_update_at
+        );
+    }
+
+    #[test]
+    fn test_sync_mirrors_dispatches_mirror_workflows() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(204, None, None)
+            .add_contract(200, "list_workflows.json", None)
+            .add_contract(200, "project.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectMirror);
+        let mirrors = github.sync_mirrors().unwrap();
+        assert_eq!(1, mirrors.len());
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/actions/workflows/269289/dispatches",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[2]);
+        assert_eq!(r#"{"ref":"main"}"#, *client.request_body());
+    }
+
+    #[test]
+    fn test_sync_mirrors_no_mirror_workflows_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(
+                200,
+                Some(r#"{"total_count": 0, "workflows": []}"#.to_string()),
+                None,
+            )
+            .add_contract(200, "project.json", None);
+        let (_, github) = setup_client!(contracts, default_github(), dyn ProjectMirror);
+        let err = github.sync_mirrors().unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(_)) => {}
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_list_dependencies_skips_repository_package() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "dependency_graph_sbom.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectDependency);
+        let dependencies = github.list_dependencies().unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/dependency-graph/sbom",
+            *client.url(),
+        );
+        assert_eq!(2, dependencies.len());
+        assert_eq!("serde", dependencies[0].name);
+        assert_eq!("cargo", dependencies[0].package_manager);
+        assert_eq!("pkg:cargo/serde@1.0.210", dependencies[0].purl);
+        assert_eq!(
+            vec!["MIT OR Apache-2.0".to_string()],
+            dependencies[0].licenses
+        );
+        assert!(dependencies[1].licenses.is_empty());
+    }
+
+    #[test]
+    fn test_blame_file_range() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body::<String>(
+                200,
+                Some("fn main() {\n    let args = parse_cli();\n}\n".to_string()),
+                None,
+            )
+            .add_contract(200, "blame_file.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn ProjectBlame);
+        let args = ProjectBlameBodyArgs::builder()
+            .path("src/main.rs".to_string())
+            .git_ref("main".to_string())
+            .range(Some((2, 3)))
+            .build()
+            .unwrap();
+        let lines = github.blame(args).unwrap();
+        assert_eq!(
+            "https://api.github.com/repos/jordilin/githapi/contents/src/main.rs?ref=main",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+        assert_eq!(
+            Some(&"application/vnd.github.raw+json".to_string()),
+            client.headers().get("Accept")
+        );
+        assert_eq!(2, lines.len());
+        assert_eq!(2, lines[0].line);
+        assert_eq!("6104942438c1", lines[0].commit);
+        assert_eq!("    let args = parse_cli();", lines[0].content);
+        assert_eq!(3, lines[1].line);
+        assert_eq!("ae1d9fb46aa2", lines[1].commit);
+        assert_eq!("}", lines[1].content);
+    }
+
+    #[test]
+    fn test_blame_graphql_errors() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_body::<String>(
+            200,
+            Some(
+                r#"{"data": null, "errors": [{"message": "Could not resolve file"}]}"#.to_string(),
+            ),
+            None,
+        );
+        let (_, github) = setup_client!(contracts, default_github(), dyn ProjectBlame);
+        let args = ProjectBlameBodyArgs::builder()
+            .path("missing.rs".to_string())
+            .git_ref("main".to_string())
+            .build()
+            .unwrap();
+        let err = github.blame(args).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::RemoteServerError(msg)) => {
+                assert!(msg.contains("Could not resolve file"));
+            }
+            _ => panic!("Expected RemoteServerError"),
+        }
+    }
 }
@@ -3,7 +3,7 @@ use crate::{
     cmds::release::{Release, ReleaseAssetListBodyArgs, ReleaseAssetMetadata, ReleaseBodyArgs},
     io::{HttpResponse, HttpRunner},
     remote::query,
-    Result,
+    time, Error, Result,
 };
 
 use super::Github;
@@ -18,7 +18,7 @@ impl<R: HttpRunner<Response = HttpResponse>> Deploy for Github<R> {
             self.request_headers(),
             None,
             ApiOperation::Release,
-            |value| GithubReleaseFields::from(value).into(),
+            |value| Ok(GithubReleaseFields::try_from(value)?.into()),
         )
     }
 
@@ -65,7 +65,7 @@ impl<R: HttpRunner<Response = HttpResponse>> DeployAsset for Github<R> {
             self.request_headers(),
             None,
             ApiOperation::Release,
-            |value| GithubReleaseAssetFields::from(value).into(),
+            |value| Ok(GithubReleaseAssetFields::try_from(value)?.into()),
         )
     }
 
@@ -94,9 +94,11 @@ pub struct GithubReleaseFields {
     release: Release,
 }
 
-impl From<&serde_json::Value> for GithubReleaseFields {
-    fn from(value: &serde_json::Value) -> Self {
-        Self {
+impl TryFrom<&serde_json::Value> for GithubReleaseFields {
+    type Error = Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self> {
+        Ok(Self {
             release: Release::builder()
                 .id(value["id"].as_i64().unwrap().to_string())
                 .url(value["html_url"].as_str().unwrap().to_string())
@@ -104,11 +106,11 @@ impl From<&serde_json::Value> for GithubReleaseFields {
                 .title(value["name"].as_str().unwrap_or_default().to_string())
                 .description(value["body"].as_str().unwrap_or_default().to_string())
                 .prerelease(value["prerelease"].as_bool().unwrap_or(false))
-                .created_at(value["created_at"].as_str().unwrap().to_string())
-                .updated_at(value["published_at"].as_str().unwrap().to_string())
+                .created_at(time::json_timestamp(value, "created_at")?)
+                .updated_at(time::json_timestamp(value, "published_at")?)
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -122,19 +124,21 @@ pub struct GithubReleaseAssetFields {
     release_asset: ReleaseAssetMetadata,
 }
 
-impl From<&serde_json::Value> for GithubReleaseAssetFields {
-    fn from(value: &serde_json::Value) -> Self {
-        Self {
+impl TryFrom<&serde_json::Value> for GithubReleaseAssetFields {
+    type Error = Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self> {
+        Ok(Self {
             release_asset: ReleaseAssetMetadata::builder()
                 .id(value["id"].as_i64().unwrap().to_string())
                 .name(value["name"].as_str().unwrap().to_string())
                 .url(value["browser_download_url"].as_str().unwrap().to_string())
                 .size(value["size"].as_i64().unwrap().to_string())
-                .created_at(value["created_at"].as_str().unwrap().to_string())
-                .updated_at(value["updated_at"].as_str().unwrap().to_string())
+                .created_at(time::json_timestamp(value, "created_at")?)
+                .updated_at(time::json_timestamp(value, "updated_at")?)
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
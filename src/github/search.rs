@@ -0,0 +1,232 @@
+use crate::api_traits::{ApiOperation, NumberDeltaErr, Search};
+use crate::cmds::search::{SearchBodyArgs, SearchResult, SearchScope};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::{encode_query_param, query, URLQueryParamBuilder};
+use crate::{time, Result};
+
+use super::Github;
+
+// https://docs.github.com/en/rest/search/search?apiVersion=2022-11-28
+impl<R: HttpRunner<Response = HttpResponse>> Search for Github<R> {
+    fn list(&self, args: SearchBodyArgs) -> Result<Vec<SearchResult>> {
+        let url = self.search_url(&args, false);
+        let scope = args.scope;
+        query::paged(
+            &self.runner,
+            &url,
+            args.from_to_page,
+            self.request_headers(),
+            Some("items"),
+            ApiOperation::Project,
+            |value| Ok(GithubSearchResultFields::new(scope, value)?.into()),
+        )
+    }
+
+    fn num_pages(&self, args: SearchBodyArgs) -> Result<Option<u32>> {
+        let url = self.search_url(&args, true);
+        query::num_pages(
+            &self.runner,
+            &url,
+            self.request_headers(),
+            ApiOperation::Project,
+        )
+    }
+
+    /// Search results carry the total count, so a single result is
+    /// requested to read it.
+    fn num_resources(&self, args: SearchBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = format!("{}&per_page=1", self.search_url(&args, true));
+        let response = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        Ok(response["total_count"]
+            .as_u64()
+            .map(|total| NumberDeltaErr::exact(total as u32)))
+    }
+}
+
+impl<R> Github<R> {
+    fn search_url(&self, args: &SearchBodyArgs, num_pages: bool) -> String {
+        // Issues and pull requests share the same search endpoint. Qualifiers
+        // narrow the search down to the current repository.
+        let (endpoint, query) = match args.scope {
+            SearchScope::Issues => (
+                "issues",
+                format!("{} repo:{} is:issue", args.query, self.path),
+            ),
+            SearchScope::MergeRequests => {
+                ("issues", format!("{} repo:{} is:pr", args.query, self.path))
+            }
+            SearchScope::Commits => ("commits", format!("{} repo:{}", args.query, self.path)),
+        };
+        let mut url =
+            URLQueryParamBuilder::new(&format!("{}/search/{}", self.rest_api_basepath, endpoint));
+        url.add_param("q", &encode_query_param(&query));
+        if num_pages {
+            url.add_param("page", "1");
+        }
+        url.build()
+    }
+}
+
+pub struct GithubSearchResultFields {
+    result: SearchResult,
+}
+
+impl GithubSearchResultFields {
+    fn new(scope: SearchScope, data: &serde_json::Value) -> Result<Self> {
+        let builder = &mut SearchResult::builder();
+        match scope {
+            SearchScope::Commits => builder
+                .id(data["sha"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .chars()
+                    .take(7)
+                    .collect())
+                // Title is the first line of the commit message
+                .title(
+                    data["commit"]["message"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .author(
+                    data["commit"]["author"]["name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .created_at(time::json_optional_timestamp(
+                    &data["commit"]["author"],
+                    "date",
+                )?),
+            SearchScope::Issues | SearchScope::MergeRequests => builder
+                .id(data["number"].as_i64().unwrap_or_default().to_string())
+                .title(data["title"].as_str().unwrap_or_default().to_string())
+                .state(data["state"].as_str().unwrap_or_default().to_string())
+                .author(
+                    data["user"]["login"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .created_at(time::json_optional_timestamp(data, "created_at")?),
+        };
+        Ok(GithubSearchResultFields {
+            result: builder
+                .web_url(data["html_url"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GithubSearchResultFields> for SearchResult {
+    fn from(fields: GithubSearchResultFields) -> Self {
+        fields.result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::setup_client;
+    use crate::test::utils::{default_github, ContractType, ResponseContracts};
+
+    use super::*;
+
+    fn body_args(scope: SearchScope, query: &str) -> SearchBodyArgs {
+        SearchBodyArgs::builder()
+            .scope(scope)
+            .query(query.to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_search_pull_requests() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "search_issues.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Search);
+        let results = github
+            .list(body_args(SearchScope::MergeRequests, "rate limit"))
+            .unwrap();
+        assert_eq!(
+            "https://api.github.com/search/issues?q=rate%20limit%20repo%3Ajordilin%2Fgithapi%20is%3Apr",
+            *client.url(),
+        );
+        assert_eq!(1, results.len());
+        assert_eq!("21", results[0].id);
+        assert_eq!("closed", results[0].state);
+        assert_eq!("jordilin", results[0].author);
+    }
+
+    #[test]
+    fn test_search_num_resources_is_exact() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "search_issues.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Search);
+        let num_resources = github
+            .num_resources(body_args(SearchScope::Issues, "rate limit"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            "https://api.github.com/search/issues?q=rate%20limit%20repo%3Ajordilin%2Fgithapi%20is%3Aissue&page=1&per_page=1",
+            *client.url(),
+        );
+        assert_eq!("1 exact", num_resources.to_string());
+    }
+
+    #[test]
+    fn test_search_issues_qualifier() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "search_issues.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Search);
+        github
+            .list(body_args(SearchScope::Issues, "cache"))
+            .unwrap();
+        assert_eq!(
+            "https://api.github.com/search/issues?q=cache%20repo%3Ajordilin%2Fgithapi%20is%3Aissue",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_search_commits() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "search_commits.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn Search);
+        let results = github
+            .list(body_args(SearchScope::Commits, "etag"))
+            .unwrap();
+        assert_eq!(
+            "https://api.github.com/search/commits?q=etag%20repo%3Ajordilin%2Fgithapi",
+            *client.url(),
+        );
+        assert_eq!("bb2d1b8", results[0].id);
+        assert_eq!("Use etag for conditional requests", results[0].title);
+        assert_eq!(
+            "2024-01-15T08:00:00Z",
+            time::format_timestamp(&results[0].created_at.unwrap())
+        );
+    }
+}
@@ -1,10 +1,10 @@
 use super::Github;
-use crate::api_traits::{ApiOperation, UserInfo};
+use crate::api_traits::{ApiOperation, UserActivity, UserInfo};
 use crate::cmds::project::Member;
-use crate::cmds::user::UserCliArgs;
+use crate::cmds::user::{ActivityBodyArgs, ActivityEvent, ActivityKind, UserCliArgs, UserProfile};
 use crate::io::{HttpResponse, HttpRunner};
 use crate::remote::query;
-use crate::Result;
+use crate::{time, Error, Result};
 
 impl<R: HttpRunner<Response = HttpResponse>> UserInfo for Github<R> {
     fn get_auth_user(&self) -> Result<Member> {
@@ -14,8 +14,8 @@ impl<R: HttpRunner<Response = HttpResponse>> UserInfo for Github<R> {
             &url,
             None,
             self.request_headers(),
-            ApiOperation::Project,
-            |value| GithubUserFields::from(value).into(),
+            ApiOperation::User,
+            |value| Ok(GithubUserFields::from(value).into()),
         )?;
         Ok(user)
     }
@@ -28,11 +28,141 @@ impl<R: HttpRunner<Response = HttpResponse>> UserInfo for Github<R> {
             &url,
             None,
             self.request_headers(),
-            ApiOperation::Project,
-            |value| GithubUserFields::from(value).into(),
+            ApiOperation::User,
+            |value| Ok(GithubUserFields::from(value).into()),
         )?;
         Ok(user)
     }
+
+    fn get_profile(&self, args: &UserCliArgs) -> Result<UserProfile> {
+        // https://docs.github.com/en/rest/users/users?apiVersion=2022-11-28#get-a-user
+        let url = format!("{}/users/{}", self.rest_api_basepath, args.username);
+        let mut profile: UserProfile = query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+            |value| Ok(GithubProfileFields::try_from(value)?.into()),
+        )?;
+        // https://docs.github.com/en/rest/orgs/orgs?apiVersion=2022-11-28#list-organizations-for-a-user
+        // Only public memberships are listed. A single page is enough for
+        // any user.
+        let url = format!(
+            "{}/users/{}/orgs?per_page=100",
+            self.rest_api_basepath, args.username
+        );
+        let orgs = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            ApiOperation::Project,
+        )?;
+        profile.organizations = orgs
+            .as_array()
+            .map(|orgs| {
+                orgs.iter()
+                    .filter_map(|org| org["login"].as_str())
+                    .map(|login| login.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(profile)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> UserActivity for Github<R> {
+    fn list_events(&self, args: ActivityBodyArgs) -> Result<Vec<ActivityEvent>> {
+        // https://docs.github.com/en/rest/activity/events?apiVersion=2022-11-28#list-events-for-the-authenticated-user
+        // Github does not support date filtering on events and only returns
+        // events created in the past 90 days. Dates are filtered by the
+        // caller.
+        let url = format!(
+            "{}/users/{}/events",
+            self.rest_api_basepath, args.user.username
+        );
+        let events = query::paged(
+            &self.runner,
+            &url,
+            None,
+            self.request_headers(),
+            None,
+            ApiOperation::Project,
+            |value| Ok(GithubEventFields::try_from(value)?.into()),
+        )?;
+        Ok(events
+            .into_iter()
+            .filter(|event: &ActivityEvent| event.kind != ActivityKind::Other)
+            .collect())
+    }
+}
+
+pub struct GithubEventFields {
+    event: ActivityEvent,
+}
+
+impl TryFrom<&serde_json::Value> for GithubEventFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        let event_type = data["type"].as_str().unwrap_or_default();
+        let action = data["payload"]["action"].as_str().unwrap_or_default();
+        let merged = data["payload"]["pull_request"]["merged"]
+            .as_bool()
+            .unwrap_or_default();
+        let kind = match (event_type, action) {
+            ("PullRequestEvent", "opened") => ActivityKind::MergeRequestOpened,
+            ("PullRequestEvent", "closed") if merged => ActivityKind::MergeRequestMerged,
+            ("PullRequestReviewEvent", _) => ActivityKind::Review,
+            ("IssuesEvent", "opened") => ActivityKind::IssueOpened,
+            ("PushEvent", _) => ActivityKind::Push,
+            _ => ActivityKind::Other,
+        };
+        Ok(GithubEventFields {
+            event: ActivityEvent::builder()
+                .kind(kind)
+                .created_at(time::json_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GithubEventFields> for ActivityEvent {
+    fn from(fields: GithubEventFields) -> Self {
+        fields.event
+    }
+}
+
+pub struct GithubProfileFields {
+    profile: UserProfile,
+}
+
+impl TryFrom<&serde_json::Value> for GithubProfileFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        let field = |name: &str| data[name].as_str().unwrap_or_default().to_string();
+        Ok(GithubProfileFields {
+            profile: UserProfile::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .username(field("login"))
+                .name(field("name"))
+                .bio(field("bio"))
+                .location(field("location"))
+                .web_url(field("html_url"))
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GithubProfileFields> for UserProfile {
+    fn from(fields: GithubProfileFields) -> Self {
+        fields.profile
+    }
 }
 
 pub struct GithubUserFields {
@@ -86,7 +216,7 @@ mod test {
         assert_eq!(123456, user.id);
         assert_eq!("jdoe", user.username);
         assert_eq!("https://api.github.com/user", *client.url(),);
-        assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+        assert_eq!(Some(ApiOperation::User), *client.api_operation.borrow());
     }
 
     #[test]
@@ -107,6 +237,77 @@ mod test {
         assert_eq!(1, user.id);
         assert_eq!("octocat", user.username);
         assert_eq!("https://api.github.com/users/octocat", *client.url(),);
+        assert_eq!(Some(ApiOperation::User), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_get_user_profile_with_organizations() {
+        let contracts = ResponseContracts::new(ContractType::Github)
+            .add_body(
+                200,
+                Some(r#"[{"login": "github"}, {"login": "octo-org"}]"#),
+                None,
+            )
+            .add_contract(200, "get_user_by_username.json", None);
+        let (client, github) = setup_client!(contracts, default_github(), dyn UserInfo);
+        let args = UserCliArgs::builder()
+            .username("octocat".to_string())
+            .get_args(remote::GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let profile = github.get_profile(&args).unwrap();
+        assert_eq!(
+            "https://api.github.com/users/octocat/orgs?per_page=100",
+            *client.url()
+        );
+        assert_eq!(1, profile.id);
+        assert_eq!("octocat", profile.username);
+        assert_eq!("monalisa octocat", profile.name);
+        assert_eq!("There once was...", profile.bio);
+        assert_eq!("San Francisco", profile.location);
+        assert_eq!("https://github.com/octocat", profile.web_url);
+        assert_eq!(
+            time::parse_timestamp("2008-01-14T04:33:35Z"),
+            profile.created_at
+        );
+        assert_eq!(vec!["github", "octo-org"], profile.organizations);
+    }
+
+    #[test]
+    fn test_list_user_contribution_events() {
+        let contracts = ResponseContracts::new(ContractType::Github).add_contract(
+            200,
+            "list_user_events.json",
+            None,
+        );
+        let (client, github) = setup_client!(contracts, default_github(), dyn UserActivity);
+        let args = ActivityBodyArgs::builder()
+            .user(
+                Member::builder()
+                    .id(1)
+                    .username("octocat".to_string())
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let events = github.list_events(args).unwrap();
+        assert_eq!("https://api.github.com/users/octocat/events", *client.url(),);
         assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+        let kinds = events.iter().map(|e| e.kind).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ActivityKind::MergeRequestOpened,
+                ActivityKind::MergeRequestMerged,
+                ActivityKind::Review,
+                ActivityKind::IssueOpened,
+                ActivityKind::Push,
+            ],
+            kinds
+        );
+        assert_eq!(
+            "2024-02-13T09:00:00Z",
+            time::format_timestamp(&events[1].created_at)
+        );
     }
 }
@@ -1,12 +1,23 @@
+use crate::api_traits::RemoteCredentials;
+use crate::auth::{self, AuthStrategy, Credentials};
 use crate::config::ConfigProperties;
 use crate::http::Headers;
+use crate::log_info;
+use crate::remote::{ListBodyArgs, ListOrderBy, ListSortMode};
+use crate::Result;
 use std::sync::Arc;
 pub mod cicd;
 pub mod container_registry;
+pub mod contract;
+pub mod deployment;
 pub mod gist;
+pub mod instance;
+pub mod issue;
+pub mod label;
 pub mod merge_request;
 pub mod project;
 pub mod release;
+pub mod search;
 pub mod trending;
 pub mod user;
 
@@ -14,7 +25,7 @@ pub mod user;
 
 #[derive(Clone)]
 pub struct Gitlab<R> {
-    api_token: String,
+    auth: Arc<dyn AuthStrategy>,
     domain: String,
     path: String,
     projects_base_url: String,
@@ -24,6 +35,11 @@ pub struct Gitlab<R> {
     base_users_url: String,
     merge_requests_url: String,
     base_runner_url: String,
+    base_groups_url: String,
+    base_suggestions_url: String,
+    graphql_url: String,
+    base_version_url: String,
+    sudo: Option<String>,
 }
 
 impl<R> Gitlab<R> {
@@ -33,20 +49,30 @@ impl<R> Gitlab<R> {
         path: &str,
         runner: Arc<R>,
     ) -> Self {
-        let api_token = config.api_token().to_string();
+        let auth = auth::gitlab_strategy(config.as_ref());
+        let sudo = config.sudo_username().map(|username| username.to_string());
         let domain = domain.to_string();
         let encoded_path = encode_path(path);
-        let api_path = "api/v4";
-        let protocol = "https";
-        let base_api_path = format!("{}://{}/{}", protocol, domain, api_path);
+        let base_api_path = match config.api_base_url() {
+            Some(url) => url.to_string(),
+            None => format!("https://{}/api/v4", domain),
+        };
         let base_user_url = format!("{}/user", base_api_path);
         let base_users_url = format!("{}/users", base_api_path);
         let base_runner_url = format!("{}/runners", base_api_path);
+        let base_groups_url = format!("{}/groups", base_api_path);
+        let base_suggestions_url = format!("{}/suggestions", base_api_path);
+        // GraphQL is served next to the REST API version, ex. /api/graphql
+        let graphql_url = match base_api_path.strip_suffix("/v4") {
+            Some(api_path) => format!("{}/graphql", api_path),
+            None => format!("{}/graphql", base_api_path),
+        };
+        let base_version_url = format!("{}/version", base_api_path);
         let merge_requests_url = format!("{}/merge_requests", base_api_path);
         let base_project_url = format!("{}/projects", base_api_path);
         let projects_base_url = format!("{}/{}", base_project_url, encoded_path);
         Gitlab {
-            api_token,
+            auth,
             domain,
             path: path.to_string(),
             projects_base_url,
@@ -56,24 +82,93 @@ impl<R> Gitlab<R> {
             merge_requests_url,
             base_runner_url,
             base_users_url,
+            base_groups_url,
+            base_suggestions_url,
+            graphql_url,
+            base_version_url,
+            sudo,
         }
     }
 
-    fn api_token(&self) -> &str {
-        &self.api_token
-    }
-
     fn rest_api_basepath(&self) -> &str {
         &self.projects_base_url
     }
 
     fn headers(&self) -> Headers {
         let mut headers = Headers::new();
-        headers.set("PRIVATE-TOKEN", self.api_token());
+        self.auth.apply(&mut headers);
+        if let Some(username) = &self.sudo {
+            // https://docs.gitlab.com/ee/api/rest/#sudo
+            log_info!("Sending Sudo header for user {}", username);
+            headers.set("Sudo", username);
+        }
         headers
     }
 }
 
+impl<R> RemoteCredentials for Gitlab<R> {
+    fn credentials(&self) -> Result<Credentials> {
+        self.auth.credentials()
+    }
+}
+
+/// Appends the updated after/before list filters as Gitlab query
+/// parameters, so only the matching resources are transferred.
+fn with_updated_range(url: String, list_args: &Option<ListBodyArgs>) -> String {
+    let Some(list_args) = list_args else {
+        return url;
+    };
+    let params = [
+        ("updated_after", &list_args.updated_after),
+        ("updated_before", &list_args.updated_before),
+    ]
+    .into_iter()
+    .filter_map(|(name, date)| date.as_ref().map(|date| format!("{}={}", name, date)))
+    .collect::<Vec<_>>();
+    if params.is_empty() {
+        return url;
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", url, separator, params.join("&"))
+}
+
+/// Appends the order requested for the listing as Gitlab query parameters.
+fn with_order_by(url: String, list_args: &Option<ListBodyArgs>) -> String {
+    let Some((order_by, sort_mode)) = list_args
+        .as_ref()
+        .and_then(|args| Some((args.order_by.as_ref()?, &args.sort_mode)))
+    else {
+        return url;
+    };
+    let order_by = match order_by {
+        ListOrderBy::Created => "created_at",
+        ListOrderBy::Updated => "updated_at",
+        ListOrderBy::Title => "title",
+    };
+    let sort = match sort_mode {
+        ListSortMode::Asc => "asc",
+        ListSortMode::Desc => "desc",
+    };
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}order_by={}&sort={}", url, separator, order_by, sort)
+}
+
+/// Switches a listing to keyset pagination when all pages are requested.
+/// Keyset pages are stable while resources are being added and are not
+/// limited in depth, but have no page numbers, so offset pagination is kept
+/// when a page range is given. `sort` must be supported by the endpoint
+/// when ordering by id.
+fn with_keyset_pagination(url: String, list_args: &Option<ListBodyArgs>, sort: &str) -> String {
+    if list_args.as_ref().is_some_and(|args| args.page.is_some()) {
+        return url;
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!(
+        "{}{}pagination=keyset&order_by=id&sort={}",
+        url, separator, sort
+    )
+}
+
 fn encode_path(path: &str) -> String {
     path.replace('/', "%2F")
 }
@@ -1,20 +1,33 @@
-use super::Gitlab;
-use crate::api_traits::{ApiOperation, CicdJob, CicdRunner};
+use super::{encode_path, with_keyset_pagination, with_updated_range, Gitlab};
+use crate::api_traits::{
+    ApiOperation, CicdArtifact, CicdJob, CicdJobLog, CicdJobTiming, CicdPipelineStatus, CicdRunner,
+    CicdTestReport, CicdUsage,
+};
 use crate::cmds::cicd::{
-    Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs, Runner, RunnerListBodyArgs,
-    RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse, RunnerStatus, YamlBytes,
+    flaky::TestResult,
+    stats::JobTiming,
+    status::{PipelineRef, PipelineStatus},
+    zip::ByteRange,
+    Artifact, FailedJob, Job, JobListBodyArgs, LintResponse, Pipeline, PipelineBodyArgs, Runner,
+    RunnerListBodyArgs, RunnerMetadata, RunnerPostDataCliArgs, RunnerRegistrationResponse,
+    RunnerStatus, RunnerUsage, YamlBytes,
 };
+use crate::error::GRError;
 use crate::http::{self, Body, Headers};
 use crate::remote::{query, URLQueryParamBuilder};
 use crate::{
     api_traits::Cicd,
     io::{HttpResponse, HttpRunner},
 };
-use crate::{time, Result};
+use crate::{time, Error, Result};
+use chrono::{DateTime, Utc};
 
 impl<R: HttpRunner<Response = HttpResponse>> Cicd for Gitlab<R> {
     fn list(&self, args: PipelineBodyArgs) -> Result<Vec<Pipeline>> {
-        let url = format!("{}/pipelines", self.rest_api_basepath());
+        let url = with_updated_range(
+            format!("{}/pipelines", self.rest_api_basepath()),
+            &args.from_to_page,
+        );
         query::paged(
             &self.runner,
             &url,
@@ -22,7 +35,7 @@ impl<R: HttpRunner<Response = HttpResponse>> Cicd for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::Pipeline,
-            |value| GitlabPipelineFields::from(value).into(),
+            |value| Ok(GitlabPipelineFields::try_from(value)?.into()),
         )
     }
 
@@ -51,7 +64,7 @@ impl<R: HttpRunner<Response = HttpResponse>> Cicd for Gitlab<R> {
             Some(&payload),
             self.headers(),
             ApiOperation::Pipeline,
-            |value| GitlabLintResponseFields::from(value).into(),
+            |value| Ok(GitlabLintResponseFields::from(value).into()),
             http::Method::POST,
         )
     }
@@ -67,7 +80,7 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdRunner for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::Pipeline,
-            |value| GitlabRunnerFields::from(value).into(),
+            |value| Ok(GitlabRunnerFields::from(value).into()),
         )
     }
 
@@ -79,7 +92,7 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdRunner for Gitlab<R> {
             None,
             self.headers(),
             ApiOperation::Pipeline,
-            |value| GitlabRunnerMetadataFields::from(value).into(),
+            |value| Ok(GitlabRunnerMetadataFields::try_from(value)?.into()),
         )
     }
 
@@ -104,8 +117,8 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdRunner for Gitlab<R> {
     fn create(&self, args: RunnerPostDataCliArgs) -> Result<RunnerRegistrationResponse> {
         let url = format!("{}/runners", self.base_current_user_url);
         let mut body = Body::new();
-        if args.description.is_some() {
-            body.add("description", args.description.unwrap());
+        if let Some(description) = args.description {
+            body.add("description", description);
         }
         // Run untagged is the default (optional), so if no run_untagged field
         // is set in the HTTP body, it is understood runner can run untagged
@@ -114,14 +127,14 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdRunner for Gitlab<R> {
         if !args.run_untagged {
             body.add("run_untagged", "false".to_string());
         }
-        if args.tags.is_some() {
-            body.add("tag_list", args.tags.unwrap());
+        if let Some(tags) = args.tags {
+            body.add("tag_list", tags);
         }
-        if args.project_id.is_some() {
-            body.add("project_id", args.project_id.unwrap().to_string());
+        if let Some(project_id) = args.project_id {
+            body.add("project_id", project_id.to_string());
         }
-        if args.group_id.is_some() {
-            body.add("group_id", args.group_id.unwrap().to_string());
+        if let Some(group_id) = args.group_id {
+            body.add("group_id", group_id.to_string());
         }
         body.add("runner_type", args.kind.to_string());
 
@@ -131,12 +144,160 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdRunner for Gitlab<R> {
             Some(&body),
             self.headers(),
             ApiOperation::Pipeline,
-            |value| GitlabCreateRunnerFields::from(value).into(),
+            |value| Ok(GitlabCreateRunnerFields::from(value).into()),
             http::Method::POST,
         )
     }
 }
 
+// Both the months and the projects of each month are paginated. `$date`
+// narrows the query down to one month when following its projects.
+const CI_MINUTES_USAGE_QUERY: &str = r#"query($namespaceId: NamespaceID, $date: Date, $after: String, $projectsAfter: String) {
+  ciMinutesUsage(namespaceId: $namespaceId, date: $date, after: $after) {
+    pageInfo {
+      hasNextPage
+      endCursor
+    }
+    nodes {
+      monthIso8601
+      projects(after: $projectsAfter) {
+        pageInfo {
+          hasNextPage
+          endCursor
+        }
+        nodes {
+          minutes
+          project {
+            fullPath
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdUsage for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/graphql/reference/#queryciminutesusage
+    fn usage(&self, group: &str) -> Result<Vec<RunnerUsage>> {
+        let url = format!("{}/{}", self.base_groups_url, encode_path(group));
+        let group_data = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let group_id = group_data["id"].as_i64().ok_or_else(|| {
+            GRError::RemoteUnexpectedResponseContract(format!(
+                "Expected group id but got: {}",
+                group_data
+            ))
+        })?;
+        let namespace_id = format!("gid://gitlab/Group/{}", group_id);
+        let mut months = Vec::new();
+        let mut after = None;
+        loop {
+            let connection = self.ci_minutes_usage(
+                group,
+                serde_json::json!({ "namespaceId": namespace_id, "after": after }),
+            )?;
+            months.extend(graphql_nodes(&connection));
+            after = graphql_end_cursor(&connection);
+            if after.is_none() {
+                break;
+            }
+        }
+        let mut usage = Vec::new();
+        for month in months {
+            let mut projects = graphql_nodes(&month["projects"]);
+            let mut projects_after = graphql_end_cursor(&month["projects"]);
+            while projects_after.is_some() {
+                let connection = self.ci_minutes_usage(
+                    group,
+                    serde_json::json!({
+                        "namespaceId": namespace_id,
+                        "date": month["monthIso8601"],
+                        "projectsAfter": projects_after,
+                    }),
+                )?;
+                let month_projects = &connection["nodes"][0]["projects"];
+                projects.extend(graphql_nodes(month_projects));
+                projects_after = graphql_end_cursor(month_projects);
+            }
+            // monthIso8601 is the first day of the month, YYYY-MM-DD
+            let month_name = month["monthIso8601"]
+                .as_str()
+                .unwrap_or_default()
+                .chars()
+                .take(7)
+                .collect::<String>();
+            for project in projects {
+                usage.push(
+                    RunnerUsage::builder()
+                        .month(month_name.clone())
+                        .project(
+                            project["project"]["fullPath"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                        )
+                        .minutes(project["minutes"].as_f64().unwrap_or_default())
+                        .build()
+                        .unwrap(),
+                );
+            }
+        }
+        Ok(usage)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    /// Sends the CI/CD minutes usage query and returns its `ciMinutesUsage`
+    /// connection.
+    fn ci_minutes_usage(
+        &self,
+        group: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let mut body = Body::new();
+        body.add("query", serde_json::json!(CI_MINUTES_USAGE_QUERY));
+        body.add("variables", variables);
+        let mut response = query::send_json(
+            &self.runner,
+            &self.graphql_url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Pipeline,
+            http::Method::POST,
+        )?;
+        if let Some(errors) = response.get("errors") {
+            return Err(GRError::RemoteServerError(format!(
+                "Failed to get CI/CD minutes usage for group {}: {}",
+                group, errors
+            ))
+            .into());
+        }
+        Ok(response["data"]["ciMinutesUsage"].take())
+    }
+}
+
+fn graphql_nodes(connection: &serde_json::Value) -> Vec<serde_json::Value> {
+    connection["nodes"].as_array().cloned().unwrap_or_default()
+}
+
+/// Cursor of the next page of a GraphQL connection, if there is one.
+fn graphql_end_cursor(connection: &serde_json::Value) -> Option<String> {
+    if !connection["pageInfo"]["hasNextPage"]
+        .as_bool()
+        .unwrap_or_default()
+    {
+        return None;
+    }
+    connection["pageInfo"]["endCursor"]
+        .as_str()
+        .map(|cursor| cursor.to_string())
+}
+
 pub struct GitlabCreateRunnerFields {
     field: RunnerRegistrationResponse,
 }
@@ -169,9 +330,11 @@ pub struct GitlabCicdJobFields {
     job: Job,
 }
 
-impl From<&serde_json::Value> for GitlabCicdJobFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabCicdJobFields {
+impl TryFrom<&serde_json::Value> for GitlabCicdJobFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        Ok(GitlabCicdJobFields {
             job: Job::builder()
                 .id(data["id"].as_i64().unwrap_or_default())
                 .name(data["name"].as_str().unwrap_or_default().to_string())
@@ -193,20 +356,23 @@ impl From<&serde_json::Value> for GitlabCicdJobFields {
                 .runner_tags(
                     data["tag_list"]
                         .as_array()
-                        .unwrap()
-                        .iter()
-                        .map(|v| v.as_str().unwrap().to_string())
-                        .collect(),
+                        .map(|tags| {
+                            tags.iter()
+                                .filter_map(|v| v.as_str())
+                                .map(|v| v.to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
                 )
                 .stage(data["stage"].as_str().unwrap_or_default().to_string())
                 .status(data["status"].as_str().unwrap_or_default().to_string())
-                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
-                .started_at(data["started_at"].as_str().unwrap_or_default().to_string())
-                .finished_at(data["finished_at"].as_str().unwrap_or_default().to_string())
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .started_at(time::json_optional_timestamp(data, "started_at")?)
+                .finished_at(time::json_optional_timestamp(data, "finished_at")?)
                 .duration(data["duration"].as_f64().unwrap_or_default().to_string())
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -219,7 +385,12 @@ impl From<GitlabCicdJobFields> for Job {
 impl<R: HttpRunner<Response = HttpResponse>> CicdJob for Gitlab<R> {
     // https://docs.gitlab.com/ee/api/jobs.html#list-project-jobs
     fn list(&self, args: JobListBodyArgs) -> Result<Vec<Job>> {
-        let url = format!("{}/jobs", self.rest_api_basepath());
+        // Jobs support keyset pagination ordered by descending id only.
+        let url = with_keyset_pagination(
+            format!("{}/jobs", self.rest_api_basepath()),
+            &args.list_args,
+            "desc",
+        );
         query::paged(
             &self.runner,
             &url,
@@ -227,7 +398,7 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdJob for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::Pipeline,
-            |value| GitlabCicdJobFields::from(value).into(),
+            |value| Ok(GitlabCicdJobFields::try_from(value)?.into()),
         )
     }
 
@@ -243,12 +414,385 @@ impl<R: HttpRunner<Response = HttpResponse>> CicdJob for Gitlab<R> {
         let url = format!("{}/jobs?page=1", self.rest_api_basepath());
         query::num_resources(&self.runner, &url, self.headers(), ApiOperation::Pipeline)
     }
+
+    // https://docs.gitlab.com/ee/api/jobs.html#get-a-single-job
+    fn get(&self, job_id: i64) -> Result<Job> {
+        let url = format!("{}/jobs/{}", self.rest_api_basepath(), job_id);
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+            |value| Ok(GitlabCicdJobFields::try_from(value)?.into()),
+        )
+    }
+
+    // https://docs.gitlab.com/ee/api/jobs.html#retry-a-job
+    fn retry(&self, job_id: i64) -> Result<Job> {
+        self.job_action(job_id, "retry")
+    }
+
+    // https://docs.gitlab.com/ee/api/jobs.html#cancel-a-job
+    fn cancel(&self, job_id: i64) -> Result<Job> {
+        self.job_action(job_id, "cancel")
+    }
+
+    // https://docs.gitlab.com/ee/api/jobs.html#run-a-job
+    fn play(&self, job_id: i64) -> Result<Job> {
+        self.job_action(job_id, "play")
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    fn job_action(&self, job_id: i64, action: &str) -> Result<Job> {
+        let url = format!("{}/jobs/{}/{}", self.rest_api_basepath(), job_id, action);
+        query::send::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+            |value| Ok(GitlabCicdJobFields::try_from(value)?.into()),
+            http::Method::POST,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdJobLog for Gitlab<R> {
+    fn list_failed_jobs(&self, last: u32) -> Result<Vec<FailedJob>> {
+        // https://docs.gitlab.com/ee/api/pipelines.html#list-project-pipelines
+        let url = format!(
+            "{}/pipelines?status=failed&per_page={}",
+            self.rest_api_basepath(),
+            last.min(100)
+        );
+        let pipelines = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let mut jobs = Vec::new();
+        for pipeline in pipelines.as_array().unwrap_or(&vec![]) {
+            // https://docs.gitlab.com/ee/api/jobs.html#list-pipeline-jobs
+            let url = format!(
+                "{}/pipelines/{}/jobs?scope[]=failed&per_page=100",
+                self.rest_api_basepath(),
+                pipeline["id"].as_i64().unwrap_or_default()
+            );
+            let pipeline_jobs = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.headers(),
+                ApiOperation::Pipeline,
+            )?;
+            for job in pipeline_jobs.as_array().unwrap_or(&vec![]) {
+                jobs.push(
+                    FailedJob::builder()
+                        .pipeline_id(job["pipeline"]["id"].as_i64().unwrap_or_default())
+                        .id(job["id"].as_i64().unwrap_or_default())
+                        .name(job["name"].as_str().unwrap_or_default().to_string())
+                        .web_url(job["web_url"].as_str().unwrap_or_default().to_string())
+                        .build()
+                        .unwrap(),
+                );
+            }
+        }
+        Ok(jobs)
+    }
+
+    // https://docs.gitlab.com/ee/api/jobs.html#get-a-log-file
+    fn get_log(&self, job_id: i64) -> Result<String> {
+        let url = format!("{}/jobs/{}/trace", self.rest_api_basepath(), job_id);
+        let response = query::get_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+        )?;
+        Ok(String::from_utf8_lossy(response.body_bytes()).into_owned())
+    }
+
+    fn get_log_from(&self, job_id: i64, offset: u64) -> Result<Vec<u8>> {
+        let url = format!("{}/jobs/{}/trace", self.rest_api_basepath(), job_id);
+        query::get_bytes_from(
+            &self.runner,
+            &url,
+            self.headers(),
+            ApiOperation::Pipeline,
+            offset,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdTestReport for Gitlab<R> {
+    fn list_test_results(&self, last: u32) -> Result<Vec<TestResult>> {
+        let url = format!(
+            "{}/pipelines?scope=finished&per_page={}",
+            self.rest_api_basepath(),
+            last.min(100)
+        );
+        let pipelines = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let mut results = Vec::new();
+        for pipeline in pipelines.as_array().unwrap_or(&vec![]) {
+            let pipeline_id = pipeline["id"].as_i64().unwrap_or_default();
+            // Test report is built from the JUnit report artifacts of the
+            // pipeline's jobs.
+            // https://docs.gitlab.com/ee/api/pipelines.html#get-a-pipelines-test-report
+            let url = format!(
+                "{}/pipelines/{}/test_report",
+                self.rest_api_basepath(),
+                pipeline_id
+            );
+            let report = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.headers(),
+                ApiOperation::Pipeline,
+            )?;
+            for suite in report["test_suites"].as_array().unwrap_or(&vec![]) {
+                for case in suite["test_cases"].as_array().unwrap_or(&vec![]) {
+                    let passed = match case["status"].as_str() {
+                        Some("success") => true,
+                        Some("failed") | Some("error") => false,
+                        _ => continue,
+                    };
+                    let name = case["name"].as_str().unwrap_or_default();
+                    let name = match case["classname"].as_str() {
+                        Some(classname) if !classname.is_empty() => {
+                            format!("{}::{}", classname, name)
+                        }
+                        _ => name.to_string(),
+                    };
+                    results.push(
+                        TestResult::builder()
+                            .pipeline_id(pipeline_id)
+                            .name(name)
+                            .passed(passed)
+                            .build()
+                            .unwrap(),
+                    );
+                }
+            }
+        }
+        Ok(results)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdJobTiming for Gitlab<R> {
+    fn list_job_timings(&self, last: u32) -> Result<Vec<JobTiming>> {
+        let url = format!(
+            "{}/pipelines?scope=finished&per_page={}",
+            self.rest_api_basepath(),
+            last.min(100)
+        );
+        let pipelines = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let mut timings = Vec::new();
+        for pipeline in pipelines.as_array().unwrap_or(&vec![]) {
+            let pipeline_id = pipeline["id"].as_i64().unwrap_or_default();
+            // https://docs.gitlab.com/ee/api/jobs.html#list-pipeline-jobs
+            let url = format!(
+                "{}/pipelines/{}/jobs?per_page=100",
+                self.rest_api_basepath(),
+                pipeline_id
+            );
+            let jobs = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.headers(),
+                ApiOperation::Pipeline,
+            )?;
+            for job in jobs.as_array().unwrap_or(&vec![]) {
+                // Skipped and manual jobs that were never played did not
+                // run, so they have no duration.
+                let Some(duration) = job["duration"].as_f64() else {
+                    continue;
+                };
+                let runner = match job["runner"]["description"].as_str() {
+                    Some(description) if !description.is_empty() => description.to_string(),
+                    _ => job["runner"]["id"]
+                        .as_i64()
+                        .map(|id| format!("#{}", id))
+                        .unwrap_or_default(),
+                };
+                timings.push(
+                    JobTiming::builder()
+                        .pipeline_id(pipeline_id)
+                        .name(job["name"].as_str().unwrap_or_default().to_string())
+                        .queued(job["queued_duration"].as_f64().unwrap_or_default())
+                        .duration(duration)
+                        .runner(runner)
+                        .build()
+                        .unwrap(),
+                );
+            }
+        }
+        Ok(timings)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdPipelineStatus for Gitlab<R> {
+    fn pipeline_status(&self, git_ref: &PipelineRef) -> Result<Option<PipelineStatus>> {
+        // https://docs.gitlab.com/ee/api/pipelines.html#list-project-pipelines
+        // Pipelines are ordered by ID, newest first.
+        let filter = match git_ref {
+            PipelineRef::Branch(branch) => format!("ref={}", branch),
+            PipelineRef::Sha(sha) => format!("sha={}", sha),
+        };
+        let url = format!(
+            "{}/pipelines?{}&per_page=1",
+            self.rest_api_basepath(),
+            filter
+        );
+        let pipelines = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+        )?;
+        let Some(pipeline) = pipelines.as_array().and_then(|pipelines| pipelines.first()) else {
+            return Ok(None);
+        };
+        // https://docs.gitlab.com/ee/api/jobs.html#list-pipeline-jobs
+        let url = format!(
+            "{}/pipelines/{}/jobs?per_page=100",
+            self.rest_api_basepath(),
+            pipeline["id"].as_i64().unwrap_or_default()
+        );
+        let pipeline: Pipeline = GitlabPipelineFields::try_from(pipeline)?.into();
+        let jobs = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+        )?;
+        // Jobs come newest first, show them in the order they run.
+        let jobs = jobs
+            .as_array()
+            .unwrap_or(&vec![])
+            .iter()
+            .rev()
+            .map(|job| Ok(GitlabCicdJobFields::try_from(job)?.into()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(
+            PipelineStatus::builder()
+                .pipeline(pipeline)
+                .jobs(jobs)
+                .build()
+                .unwrap(),
+        ))
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> CicdArtifact for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/jobs.html#list-project-jobs
+    fn list(&self) -> Result<Vec<Artifact>> {
+        let url =
+            with_keyset_pagination(format!("{}/jobs", self.rest_api_basepath()), &None, "desc");
+        let artifacts: Vec<Artifact> = query::paged(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            None,
+            ApiOperation::Pipeline,
+            |value| Ok(GitlabArtifactFields::try_from(value)?.into()),
+        )?;
+        Ok(artifacts
+            .into_iter()
+            .filter(|artifact| artifact.size > 0)
+            .collect())
+    }
+
+    // https://docs.gitlab.com/ee/api/job_artifacts.html#delete-job-artifacts
+    fn delete(&self, id: i64) -> Result<()> {
+        let url = format!("{}/jobs/{}/artifacts", self.rest_api_basepath(), id);
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Pipeline,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.gitlab.com/ee/api/job_artifacts.html#get-job-artifacts
+    fn read_archive(&self, id: i64, range: ByteRange) -> Result<Vec<u8>> {
+        let url = format!("{}/jobs/{}/artifacts", self.rest_api_basepath(), id);
+        let mut headers = self.headers();
+        headers.set("Range", range.header_value()?);
+        let response =
+            query::get_raw::<_, ()>(&self.runner, &url, None, headers, ApiOperation::Pipeline)?;
+        range.response_data(response.status, response.body_bytes().to_vec())
+    }
+}
+
+pub struct GitlabArtifactFields {
+    artifact: Artifact,
+}
+
+impl TryFrom<&serde_json::Value> for GitlabArtifactFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        // The job log (trace) is not removed when deleting job artifacts.
+        let size = data["artifacts"]
+            .as_array()
+            .map(|files| {
+                files
+                    .iter()
+                    .filter(|file| file["file_type"].as_str() != Some("trace"))
+                    .map(|file| file["size"].as_u64().unwrap_or_default())
+                    .sum()
+            })
+            .unwrap_or_default();
+        Ok(GitlabArtifactFields {
+            artifact: Artifact::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .name(data["name"].as_str().unwrap_or_default().to_string())
+                .size(size)
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GitlabArtifactFields> for Artifact {
+    fn from(fields: GitlabArtifactFields) -> Self {
+        fields.artifact
+    }
 }
 
 impl<R> Gitlab<R> {
     fn list_runners_url(&self, args: &RunnerListBodyArgs, num_pages: bool) -> String {
         let base_url = if args.all {
             format!("{}/all", self.base_runner_url)
+        } else if let Some(group) = &args.group {
+            // https://docs.gitlab.com/ee/api/runners.html#list-groups-runners
+            format!("{}/{}/runners", self.base_groups_url, encode_path(group))
         } else {
             format!("{}/runners", self.rest_api_basepath(),)
         };
@@ -271,7 +815,7 @@ impl<R> Gitlab<R> {
     fn resource_cicd_metadata_url(&self) -> (String, Headers) {
         let url = format!("{}/pipelines?page=1", self.rest_api_basepath());
         let mut headers = Headers::new();
-        headers.set("PRIVATE-TOKEN", self.api_token());
+        self.auth.apply(&mut headers);
         (url, headers)
     }
 }
@@ -337,13 +881,15 @@ pub struct GitlabRunnerMetadataFields {
     pub version: String,
     pub architecture: String,
     pub platform: String,
-    pub contacted_at: String,
+    pub contacted_at: Option<DateTime<Utc>>,
     pub revision: String,
 }
 
-impl From<&serde_json::Value> for GitlabRunnerMetadataFields {
-    fn from(value: &serde_json::Value) -> Self {
-        Self {
+impl TryFrom<&serde_json::Value> for GitlabRunnerMetadataFields {
+    type Error = Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self> {
+        Ok(Self {
             id: value["id"].as_i64().unwrap(),
             run_untagged: value["run_untagged"].as_bool().unwrap(),
             tag_list: value["tag_list"]
@@ -355,9 +901,9 @@ impl From<&serde_json::Value> for GitlabRunnerMetadataFields {
             version: value["version"].as_str().unwrap().to_string(),
             architecture: value["architecture"].as_str().unwrap().to_string(),
             platform: value["platform"].as_str().unwrap().to_string(),
-            contacted_at: value["contacted_at"].as_str().unwrap().to_string(),
+            contacted_at: time::json_optional_timestamp(value, "contacted_at")?,
             revision: value["revision"].as_str().unwrap().to_string(),
-        }
+        })
     }
 }
 
@@ -381,24 +927,25 @@ pub struct GitlabPipelineFields {
     pipeline: Pipeline,
 }
 
-impl From<&serde_json::Value> for GitlabPipelineFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabPipelineFields {
+impl TryFrom<&serde_json::Value> for GitlabPipelineFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        let created_at = time::json_timestamp(data, "created_at")?;
+        let updated_at = time::json_timestamp(data, "updated_at")?;
+        Ok(GitlabPipelineFields {
             pipeline: Pipeline::builder()
                 .id(data["id"].as_i64().unwrap_or_default())
                 .status(data["status"].as_str().unwrap().to_string())
                 .web_url(data["web_url"].as_str().unwrap().to_string())
                 .branch(data["ref"].as_str().unwrap().to_string())
                 .sha(data["sha"].as_str().unwrap().to_string())
-                .created_at(data["created_at"].as_str().unwrap().to_string())
-                .updated_at(data["updated_at"].as_str().unwrap().to_string())
-                .duration(time::compute_duration(
-                    data["created_at"].as_str().unwrap(),
-                    data["updated_at"].as_str().unwrap(),
-                ))
+                .created_at(created_at)
+                .updated_at(updated_at)
+                .duration(time::duration_between(&created_at, &updated_at))
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -425,6 +972,18 @@ impl From<&serde_json::Value> for GitlabLintResponseFields {
                         .map(|v| v.as_str().unwrap().to_string())
                         .collect(),
                 )
+                .warnings(
+                    data["warnings"]
+                        .as_array()
+                        .map(|warnings| {
+                            warnings
+                                .iter()
+                                .filter_map(|v| v.as_str())
+                                .map(|v| v.to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                )
                 .merged_yaml(data["merged_yaml"].as_str().unwrap().to_string())
                 .build()
                 .unwrap(),
@@ -444,7 +1003,7 @@ mod test {
     use crate::cmds::cicd::{RunnerStatus, RunnerType};
     use crate::remote::ListBodyArgs;
     use crate::setup_client;
-    use crate::test::utils::{default_gitlab, ContractType, ResponseContracts};
+    use crate::test::utils::{config, default_gitlab, ContractType, MockRunner, ResponseContracts};
 
     use super::*;
 
@@ -488,6 +1047,33 @@ mod test {
         assert_eq!(0, pipelines.len());
     }
 
+    #[test]
+    fn test_list_pipelines_updated_after() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "list_pipelines.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        gitlab
+            .list(
+                PipelineBodyArgs::builder()
+                    .from_to_page(Some(
+                        ListBodyArgs::builder()
+                            .updated_after(Some("2024-06-01T00:00:00Z".to_string()))
+                            .build()
+                            .unwrap(),
+                    ))
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines?updated_after=2024-06-01T00:00:00Z",
+            *client.url(),
+        );
+    }
+
     fn default_pipeline_body_args() -> PipelineBodyArgs {
         let body_args = PipelineBodyArgs::builder()
             .from_to_page(None)
@@ -663,6 +1249,115 @@ mod test {
         assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
     }
 
+    #[test]
+    fn test_list_group_runners() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "list_project_runners.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdRunner);
+        let body_args = RunnerListBodyArgs::builder()
+            .status(RunnerStatus::All)
+            .list_args(None)
+            .group(Some("jordilin/subgroup".to_string()))
+            .build()
+            .unwrap();
+        gitlab.list(body_args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/groups/jordilin%2Fsubgroup/runners",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_ci_minutes_usage_per_project() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "ci_minutes_usage.json", None)
+            .add_contract(200, "get_group.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdUsage);
+        let usage = gitlab.usage("jordilin").unwrap();
+        assert_eq!("https://gitlab.com/api/graphql", *client.url());
+        assert_eq!(
+            vec![http::Method::GET, http::Method::POST],
+            *client.http_method.borrow()
+        );
+        assert!(client.request_body().contains("gid://gitlab/Group/4455"));
+        assert_eq!(3, usage.len());
+        assert_eq!("2024-02", usage[0].month);
+        assert_eq!("jordilin/gitlapi", usage[0].project);
+        assert_eq!(420.0, usage[0].minutes);
+        assert_eq!("2024-01", usage[2].month);
+    }
+
+    #[test]
+    fn test_ci_minutes_usage_follows_end_cursors() {
+        let month_page = |month: &str, has_next: bool, cursor: &str, project: &str| {
+            format!(
+                r#"{{"data":{{"ciMinutesUsage":{{
+                    "pageInfo":{{"hasNextPage":{has_next},"endCursor":"{cursor}"}},
+                    "nodes":[{{"monthIso8601":"{month}","projects":{{
+                        "pageInfo":{{"hasNextPage":{has_next},"endCursor":"p{cursor}"}},
+                        "nodes":[{{"minutes":10,"project":{{"fullPath":"{project}"}}}}]
+                    }}}}]
+                }}}}}}"#
+            )
+        };
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body(
+                200,
+                Some(month_page("2024-02-01", false, "p2", "jordilin/gitar")),
+                None,
+            )
+            .add_body(
+                200,
+                Some(month_page("2024-01-01", false, "2", "jordilin/gitlapi")),
+                None,
+            )
+            .add_body(
+                200,
+                Some(month_page("2024-02-01", true, "1", "jordilin/gitlapi")),
+                None,
+            )
+            .add_contract(200, "get_group.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdUsage);
+        let usage = gitlab.usage("jordilin").unwrap();
+        assert_eq!(4, client.http_method.borrow().len());
+        // Last request follows the projects of the first month
+        let body = client.request_body();
+        assert!(body.contains(r#""date":"2024-02-01""#));
+        assert!(body.contains(r#""projectsAfter":"p1""#));
+        let rows = usage
+            .iter()
+            .map(|row| (row.month.as_str(), row.project.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("2024-02", "jordilin/gitlapi"),
+                ("2024-02", "jordilin/gitar"),
+                ("2024-01", "jordilin/gitlapi"),
+            ],
+            rows
+        );
+    }
+
+    #[test]
+    fn test_ci_minutes_usage_graphql_errors() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body(
+                200,
+                Some(r#"{"errors":[{"message":"Not authorized"}]}"#.to_string()),
+                None,
+            )
+            .add_contract(200, "get_group.json", None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdUsage);
+        let err = gitlab.usage("jordilin").unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::RemoteServerError(msg)) => assert!(msg.contains("Not authorized")),
+            _ => panic!("Expected RemoteServerError"),
+        }
+    }
+
     #[test]
     fn test_get_all_gitlab_runners() {
         let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
@@ -826,7 +1521,26 @@ mod test {
         assert!(result.is_ok());
         let response = result.unwrap();
         assert!(!response.valid);
-        assert!(response.errors.len() > 0);
+        assert!(!response.errors.is_empty());
+    }
+
+    #[test]
+    fn test_lint_ci_file_warnings() {
+        let body = r#"{
+            "valid": true,
+            "merged_yaml": "",
+            "errors": [],
+            "warnings": ["jobs:build may allow multiple pipelines to run for a single action"]
+        }"#;
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(201, Some(body), None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn Cicd);
+        let response = gitlab.lint(gen_gitlab_ci_body()).unwrap();
+        assert!(response.valid);
+        assert_eq!(
+            vec!["jobs:build may allow multiple pipelines to run for a single action"],
+            response.warnings
+        );
     }
 
     #[test]
@@ -840,13 +1554,38 @@ mod test {
         let body_args = JobListBodyArgs::builder().list_args(None).build().unwrap();
         gitlab.list(body_args).unwrap();
         assert_eq!(
-            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs",
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs?pagination=keyset&order_by=id&sort=desc",
             *client.url()
         );
         assert_eq!("1234", client.headers().get("PRIVATE-TOKEN").unwrap());
         assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
     }
 
+    #[test]
+    fn test_gitlab_project_jobs_page_range_uses_offset_pagination() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "list_project_jobs.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        let body_args = JobListBodyArgs::builder()
+            .list_args(Some(
+                ListBodyArgs::builder()
+                    .page(2)
+                    .max_pages(1)
+                    .build()
+                    .unwrap(),
+            ))
+            .build()
+            .unwrap();
+        gitlab.list(body_args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs?page=2",
+            *client.url()
+        );
+    }
+
     #[test]
     fn test_gitlab_project_jobs_num_pages() {
         let link_header = "<https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs?page=2>; rel=\"next\", <https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs?page=2>; rel=\"last\"";
@@ -882,7 +1621,7 @@ mod test {
             *client.url()
         );
         assert_eq!(Some(ApiOperation::Pipeline), *client.api_operation.borrow());
-        assert_eq!("(1, 30)", &num_resources.unwrap().to_string());
+        assert_eq!("(1, 30) estimated", &num_resources.unwrap().to_string());
     }
 
     #[test]
@@ -1015,4 +1754,250 @@ mod test {
         let body = client.request_body();
         assert!(body.contains("group_id"));
     }
+
+    #[test]
+    fn test_list_job_artifacts() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "list_project_jobs.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdArtifact);
+        let artifacts = gitlab.list().unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs?pagination=keyset&order_by=id&sort=desc",
+            *client.url()
+        );
+        // Second job has no artifacts. Job log is not counted.
+        assert_eq!(1, artifacts.len());
+        assert_eq!(7, artifacts[0].id);
+        assert_eq!("teaspoon", artifacts[0].name);
+        assert_eq!(1936, artifacts[0].size);
+    }
+
+    #[test]
+    fn test_delete_job_artifacts() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(204, None, None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdArtifact);
+        gitlab.delete(7).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/7/artifacts",
+            *client.url()
+        );
+        assert_eq!(http::Method::DELETE, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_read_job_artifacts_archive_range() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(206, Some("PK\x05\x06"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdArtifact);
+        let data = gitlab.read_archive(7, ByteRange::Suffix(4)).unwrap();
+        assert_eq!(b"PK\x05\x06".to_vec(), data);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/7/artifacts",
+            *client.url()
+        );
+        assert_eq!("bytes=-4", client.headers().get("Range").unwrap());
+    }
+
+    #[test]
+    fn test_read_job_artifacts_archive_range_not_honored() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("0123456789"), None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdArtifact);
+        let range = ByteRange::Span {
+            offset: 2,
+            length: 3,
+        };
+        assert_eq!(b"234".to_vec(), gitlab.read_archive(7, range).unwrap());
+    }
+
+    #[test]
+    fn test_list_failed_jobs_of_failed_pipelines() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "list_project_jobs.json", None)
+            .add_contract(200, "list_failed_pipelines.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobLog);
+        let jobs = gitlab.list_failed_jobs(20).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/6/jobs?scope[]=failed&per_page=100",
+            *client.url()
+        );
+        assert_eq!(2, jobs.len());
+        assert_eq!(7, jobs[0].id);
+        assert_eq!(6, jobs[0].pipeline_id);
+        assert_eq!("teaspoon", jobs[0].name);
+        assert_eq!("https://example.com/foo/bar/-/jobs/7", jobs[0].web_url);
+    }
+
+    const PIPELINE: &str = r#"[{"id": 6, "status": "running", "ref": "main", "sha": "a91957a858320c0e17f3a0eca7cfacbff50ea29a", "web_url": "https://example.com/foo/bar/pipelines/6", "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:01:00Z"}]"#;
+
+    #[test]
+    fn test_pipeline_status_of_branch() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "list_project_jobs.json", None)
+            .add_body(200, Some(PIPELINE), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdPipelineStatus);
+        let status = gitlab
+            .pipeline_status(&PipelineRef::Branch("main".to_string()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/6/jobs?per_page=100",
+            *client.url()
+        );
+        assert_eq!("running", status.pipeline.status);
+        assert_eq!(2, status.jobs.len());
+    }
+
+    #[test]
+    fn test_pipeline_status_of_commit_without_pipeline() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdPipelineStatus);
+        let status = gitlab
+            .pipeline_status(&PipelineRef::Sha("abc123".to_string()))
+            .unwrap();
+        assert!(status.is_none());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines?sha=abc123&per_page=1",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_retry_job() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(201, "retry_job.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        assert!(gitlab.retry(1201).is_ok());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/1201/retry",
+            *client.url()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_play_job() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "retry_job.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        assert!(gitlab.play(1202).is_ok());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/1202/play",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_get_job() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "retry_job.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJob);
+        assert!(gitlab.get(1202).is_ok());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/1202",
+            *client.url()
+        );
+        assert_eq!(http::Method::GET, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_get_job_log() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some("Killed: OOMKilled"),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobLog);
+        let log = gitlab.get_log(7).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/7/trace",
+            *client.url()
+        );
+        assert_eq!("Killed: OOMKilled", log);
+    }
+
+    #[test]
+    fn test_get_job_log_not_utf8() {
+        let response = HttpResponse::builder()
+            .status(200)
+            .bytes(b"Killed: \xffOOM".to_vec())
+            .build()
+            .unwrap();
+        let client = std::sync::Arc::new(MockRunner::new(vec![response]));
+        let gitlab: Box<dyn CicdJobLog> = Box::new(Gitlab::new(
+            config(),
+            "gitlab.com",
+            "jordilin/gitlapi",
+            client,
+        ));
+        assert_eq!("Killed: \u{FFFD}OOM", gitlab.get_log(7).unwrap());
+    }
+
+    #[test]
+    fn test_get_job_log_from_offset() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(206, Some("step 2\n"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobLog);
+        let log = gitlab.get_log_from(7, 7).unwrap();
+        assert_eq!(b"step 2\n".to_vec(), log);
+        assert_eq!("bytes=7-", client.headers().get("Range").unwrap());
+    }
+
+    #[test]
+    fn test_get_job_log_from_offset_range_ignored_or_nothing_new() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body::<String>(416, None, None)
+            .add_body(200, Some("step 1\nstep 2\n"), None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobLog);
+        assert_eq!(b"step 2\n".to_vec(), gitlab.get_log_from(7, 7).unwrap());
+        assert!(gitlab.get_log_from(7, 14).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_list_test_results_from_pipeline_test_reports() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "pipeline_test_report.json", None)
+            .add_contract(200, "list_failed_pipelines.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdTestReport);
+        let results = gitlab.list_test_results(20).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/6/test_report",
+            *client.url()
+        );
+        // Skipped test cases are not reported.
+        assert_eq!(2, results.len());
+        assert_eq!(6, results[0].pipeline_id);
+        assert_eq!(
+            "vulnerability_management_spec::Security Reports can create an auto-remediation MR",
+            results[0].name
+        );
+        assert!(results[0].passed);
+        assert!(!results[1].passed);
+    }
+
+    #[test]
+    fn test_list_job_timings_from_pipeline_jobs() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "list_project_jobs.json", None)
+            .add_contract(200, "list_failed_pipelines.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CicdJobTiming);
+        let timings = gitlab.list_job_timings(20).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/pipelines/6/jobs?per_page=100",
+            *client.url()
+        );
+        assert_eq!(2, timings.len());
+        assert_eq!(6, timings[0].pipeline_id);
+        assert_eq!("teaspoon", timings[0].name);
+        assert_eq!(0.01, timings[0].queued);
+        assert_eq!(0.173, timings[0].duration);
+        // Runner without a description is named after its ID.
+        assert_eq!("#32", timings[0].runner);
+        assert_eq!("", timings[1].runner);
+    }
 }
@@ -3,8 +3,9 @@ use crate::{
     cmds::docker::{DockerListBodyArgs, ImageMetadata, RegistryRepository, RepositoryTag},
     io::{HttpResponse, HttpRunner},
     remote::query,
-    Result,
+    time, Error, Result,
 };
+use chrono::{DateTime, Utc};
 
 use super::Gitlab;
 
@@ -21,7 +22,7 @@ impl<R: HttpRunner<Response = HttpResponse>> ContainerRegistry for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::ContainerRegistry,
-            |value| GitlabRegistryRepositoryFields::from(value).into(),
+            |value| Ok(GitlabRegistryRepositoryFields::try_from(value)?.into()),
         )
     }
 
@@ -41,7 +42,7 @@ impl<R: HttpRunner<Response = HttpResponse>> ContainerRegistry for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::ContainerRegistry,
-            |value| GitlabRepositoryTagFields::from(value).into(),
+            |value| Ok(GitlabRepositoryTagFields::from(value).into()),
         )
     }
 
@@ -101,7 +102,7 @@ impl<R: HttpRunner<Response = HttpResponse>> ContainerRegistry for Gitlab<R> {
             None,
             self.headers(),
             ApiOperation::ContainerRegistry,
-            |value| GitlabImageMetadataFields::from(value).into(),
+            |value| Ok(GitlabImageMetadataFields::try_from(value)?.into()),
         )
     }
 }
@@ -126,17 +127,19 @@ pub struct GitlabRegistryRepositoryFields {
     id: i64,
     location: String,
     tags_count: i64,
-    created_at: String,
+    created_at: DateTime<Utc>,
 }
 
-impl From<&serde_json::Value> for GitlabRegistryRepositoryFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabRegistryRepositoryFields {
+impl TryFrom<&serde_json::Value> for GitlabRegistryRepositoryFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        Ok(GitlabRegistryRepositoryFields {
             id: data["id"].as_i64().unwrap(),
             location: data["location"].as_str().unwrap().to_string(),
             tags_count: data["tags_count"].as_i64().unwrap(),
-            created_at: data["created_at"].as_str().unwrap().to_string(),
-        }
+            created_at: time::json_timestamp(data, "created_at")?,
+        })
     }
 }
 
@@ -156,7 +159,7 @@ pub struct GitlabRepositoryTagFields {
     name: String,
     path: String,
     location: String,
-    created_at: String,
+    created_at: DateTime<Utc>,
 }
 
 impl From<&serde_json::Value> for GitlabRepositoryTagFields {
@@ -167,7 +170,7 @@ impl From<&serde_json::Value> for GitlabRepositoryTagFields {
             location: data["location"].as_str().unwrap().to_string(),
             // Repository tags don't have a creation date. It is included when
             // querying a specific tag. Just return default UNIX epoch date.
-            created_at: "1970-01-01T00:00:00Z".to_string(),
+            created_at: DateTime::UNIX_EPOCH,
         }
     }
 }
@@ -189,18 +192,20 @@ pub struct GitlabImageMetadataFields {
     location: String,
     short_sha: String,
     size: i64,
-    created_at: String,
+    created_at: DateTime<Utc>,
 }
 
-impl From<&serde_json::Value> for GitlabImageMetadataFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabImageMetadataFields {
+impl TryFrom<&serde_json::Value> for GitlabImageMetadataFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        Ok(GitlabImageMetadataFields {
             name: data["name"].as_str().unwrap().to_string(),
             location: data["location"].as_str().unwrap().to_string(),
             short_sha: data["short_revision"].as_str().unwrap().to_string(),
             size: data["total_size"].as_i64().unwrap(),
-            created_at: data["created_at"].as_str().unwrap().to_string(),
-        }
+            created_at: time::json_timestamp(data, "created_at")?,
+        })
     }
 }
 
@@ -0,0 +1,65 @@
+use crate::api_traits::{ApiOperation, ContractRecorder};
+use crate::cmds::contract::{ContractFixture, ContractOperation};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::query;
+use crate::Result;
+
+use super::Gitlab;
+
+impl<R: HttpRunner<Response = HttpResponse>> ContractRecorder for Gitlab<R> {
+    fn record(&self, operation: ContractOperation, id: i64) -> Result<ContractFixture> {
+        let (url, file_name, api_operation) = match operation {
+            ContractOperation::Project => (
+                self.rest_api_basepath().to_string(),
+                "project.json",
+                ApiOperation::Project,
+            ),
+            ContractOperation::MergeRequest => (
+                format!("{}/merge_requests/{}", self.rest_api_basepath(), id),
+                "merge_request.json",
+                ApiOperation::MergeRequest,
+            ),
+            ContractOperation::Comment => (
+                format!("{}/merge_requests/{}/notes", self.rest_api_basepath(), id),
+                "comment.json",
+                ApiOperation::MergeRequest,
+            ),
+            ContractOperation::AuthUser => (
+                self.base_current_user_url.clone(),
+                "get_user_info.json",
+                ApiOperation::Project,
+            ),
+        };
+        let payload =
+            query::get_json::<_, ()>(&self.runner, &url, None, self.headers(), api_operation)?;
+        Ok(ContractFixture::new(
+            format!("gitlab/{}", file_name),
+            payload,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::setup_client;
+    use crate::test::utils::{default_gitlab, ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_record_merge_request_contract() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ContractRecorder);
+        let fixture = gitlab.record(ContractOperation::MergeRequest, 33).unwrap();
+        assert_eq!("gitlab/merge_request.json", fixture.path);
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33",
+            *client.url()
+        );
+        assert!(fixture.payload["iid"].is_i64());
+    }
+}
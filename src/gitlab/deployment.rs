@@ -0,0 +1,355 @@
+use super::Gitlab;
+use crate::api_traits::{ApiOperation, Deployment};
+use crate::cmds::deployment::{
+    DeploymentState, DeploymentStatus, DeploymentStatusCliArgs, Environment, RollbackJob,
+};
+use crate::error::GRError;
+use crate::http::{self, Body};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::{query, URLQueryParamBuilder};
+use crate::{time, Error, Result};
+
+impl<R: HttpRunner<Response = HttpResponse>> Deployment for Gitlab<R> {
+    // Only the status of a deployment can be updated.
+    // https://docs.gitlab.com/ee/api/deployments.html#update-a-deployment
+    fn set_status(&self, args: DeploymentStatusCliArgs) -> Result<DeploymentStatus> {
+        if args.url.is_some() || args.description.is_some() {
+            return Err(GRError::OperationNotSupported(
+                "Gitlab deployments only update their status, --url and --description are \
+                 Github only"
+                    .to_string(),
+            )
+            .into());
+        }
+        let url = format!("{}/deployments/{}", self.rest_api_basepath(), args.id);
+        let mut body = Body::new();
+        body.add("status", gitlab_deployment_status(args.state).to_string());
+        query::send(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Deployment,
+            |value| Ok(GitlabDeploymentStatusFields::try_from(value)?.into()),
+            http::Method::PUT,
+        )
+    }
+
+    // https://docs.gitlab.com/ee/api/environments.html#stop-an-environment
+    fn stop_environment(&self, environment: &str) -> Result<Environment> {
+        let environment_id = self.get_environment_id(environment)?;
+        let url = format!(
+            "{}/environments/{}/stop",
+            self.rest_api_basepath(),
+            environment_id
+        );
+        query::send::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Deployment,
+            |value| Ok(GitlabEnvironmentFields::from(value).into()),
+            http::Method::POST,
+        )
+    }
+
+    /// Rolling back in Gitlab re-runs the job that created the given
+    /// deployment. See
+    /// https://docs.gitlab.com/ee/ci/environments/deployments.html#retry-or-roll-back-a-deployment
+    fn rollback(&self, environment: &str, deployment_id: i64) -> Result<RollbackJob> {
+        let url = format!("{}/deployments/{}", self.rest_api_basepath(), deployment_id);
+        let deployment = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Deployment,
+        )?;
+        if deployment["environment"]["name"].as_str() != Some(environment) {
+            return Err(GRError::PreconditionNotMet(format!(
+                "Deployment {} does not belong to environment {}",
+                deployment_id, environment
+            ))
+            .into());
+        }
+        let job_id = deployment["deployable"]["id"].as_i64().ok_or_else(|| {
+            GRError::PreconditionNotMet(format!(
+                "Deployment {} has no job that can be re-deployed",
+                deployment_id
+            ))
+        })?;
+        let url = format!("{}/jobs/{}/retry", self.rest_api_basepath(), job_id);
+        query::send::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Deployment,
+            |value| Ok(GitlabRollbackJobFields::from(value).into()),
+            http::Method::POST,
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    fn get_environment_id(&self, environment: &str) -> Result<i64> {
+        let url = URLQueryParamBuilder::new(&format!("{}/environments", self.rest_api_basepath()))
+            .add_param("name", environment)
+            .build();
+        let environments = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Deployment,
+        )?;
+        environments
+            .as_array()
+            .and_then(|environments| environments.first())
+            .and_then(|environment| environment["id"].as_i64())
+            .ok_or_else(|| {
+                GRError::PreconditionNotMet(format!("Environment {} not found", environment)).into()
+            })
+    }
+}
+
+/// Gitlab deployments can only be running, success, failed or canceled.
+fn gitlab_deployment_status(state: DeploymentState) -> &'static str {
+    match state {
+        DeploymentState::Pending | DeploymentState::Queued | DeploymentState::InProgress => {
+            "running"
+        }
+        DeploymentState::Success => "success",
+        DeploymentState::Failure | DeploymentState::Error => "failed",
+        DeploymentState::Inactive => "canceled",
+    }
+}
+
+pub struct GitlabDeploymentStatusFields {
+    status: DeploymentStatus,
+}
+
+impl TryFrom<&serde_json::Value> for GitlabDeploymentStatusFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        Ok(GitlabDeploymentStatusFields {
+            status: DeploymentStatus::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .state(data["status"].as_str().unwrap_or_default().to_string())
+                .url(
+                    data["environment"]["external_url"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GitlabDeploymentStatusFields> for DeploymentStatus {
+    fn from(fields: GitlabDeploymentStatusFields) -> Self {
+        fields.status
+    }
+}
+
+pub struct GitlabEnvironmentFields {
+    environment: Environment,
+}
+
+impl From<&serde_json::Value> for GitlabEnvironmentFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GitlabEnvironmentFields {
+            environment: Environment::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .name(data["name"].as_str().unwrap_or_default().to_string())
+                .state(data["state"].as_str().unwrap_or_default().to_string())
+                .external_url(
+                    data["external_url"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabEnvironmentFields> for Environment {
+    fn from(fields: GitlabEnvironmentFields) -> Self {
+        fields.environment
+    }
+}
+
+pub struct GitlabRollbackJobFields {
+    job: RollbackJob,
+}
+
+impl From<&serde_json::Value> for GitlabRollbackJobFields {
+    fn from(data: &serde_json::Value) -> Self {
+        GitlabRollbackJobFields {
+            job: RollbackJob::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .status(data["status"].as_str().unwrap_or_default().to_string())
+                .web_url(data["web_url"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabRollbackJobFields> for RollbackJob {
+    fn from(fields: GitlabRollbackJobFields) -> Self {
+        fields.job
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{
+        setup_client,
+        test::utils::{default_gitlab, ContractType, ResponseContracts},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_set_deployment_status() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "update_deployment.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Deployment);
+        let args = DeploymentStatusCliArgs::builder()
+            .id(42)
+            .state(DeploymentState::Success)
+            .build()
+            .unwrap();
+        let status = gitlab.set_status(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/deployments/42",
+            *client.url(),
+        );
+        assert_eq!(
+            Some(ApiOperation::Deployment),
+            *client.api_operation.borrow()
+        );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+        assert_eq!("{\"status\":\"success\"}", *client.request_body());
+        assert_eq!(42, status.id);
+        assert_eq!("success", status.state);
+    }
+
+    #[test]
+    fn test_set_deployment_status_with_url_is_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Deployment);
+        let args = DeploymentStatusCliArgs::builder()
+            .id(42)
+            .state(DeploymentState::Success)
+            .url(Some("https://app.example.com".to_string()))
+            .build()
+            .unwrap();
+        let err = gitlab.set_status(args).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::OperationNotSupported(_)) => {}
+            _ => panic!("Expected OperationNotSupported"),
+        }
+        assert!(client.http_method.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_gitlab_deployment_status_mapping() {
+        assert_eq!(
+            "running",
+            gitlab_deployment_status(DeploymentState::Pending)
+        );
+        assert_eq!(
+            "running",
+            gitlab_deployment_status(DeploymentState::InProgress)
+        );
+        assert_eq!("failed", gitlab_deployment_status(DeploymentState::Error));
+        assert_eq!(
+            "canceled",
+            gitlab_deployment_status(DeploymentState::Inactive)
+        );
+    }
+
+    #[test]
+    fn test_stop_environment() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "stop_environment.json", None)
+            .add_contract(200, "list_environments.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Deployment);
+        let environment = gitlab.stop_environment("production").unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/environments/9/stop",
+            *client.url(),
+        );
+        assert_eq!(
+            vec![http::Method::GET, http::Method::POST],
+            *client.http_method.borrow()
+        );
+        assert_eq!(9, environment.id);
+        assert_eq!("stopping", environment.state);
+    }
+
+    #[test]
+    fn test_stop_environment_not_found() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Deployment);
+        let err = gitlab.stop_environment("staging").unwrap_err();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/environments?name=staging",
+            *client.url(),
+        );
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert_eq!("Environment staging not found", msg);
+            }
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_rollback_environment() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(201, "retry_job.json", None)
+            .add_contract(200, "update_deployment.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Deployment);
+        let job = gitlab.rollback("production", 42).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/jobs/1201/retry",
+            *client.url(),
+        );
+        assert_eq!(
+            vec![http::Method::GET, http::Method::POST],
+            *client.http_method.borrow()
+        );
+        assert_eq!(1202, job.id);
+        assert_eq!("pending", job.status);
+    }
+
+    #[test]
+    fn test_rollback_deployment_from_another_environment_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "update_deployment.json",
+            None,
+        );
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn Deployment);
+        let err = gitlab.rollback("staging", 42).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(_)) => {}
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+}
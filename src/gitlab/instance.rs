@@ -0,0 +1,93 @@
+use crate::api_traits::{ApiOperation, RemoteInstance};
+use crate::cmds::remote::{version_at_least, RemoteInfo};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::query;
+use crate::Result;
+
+use super::Gitlab;
+
+impl<R: HttpRunner<Response = HttpResponse>> RemoteInstance for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/version.html
+    fn info(&self) -> Result<RemoteInfo> {
+        let response = query::get_json::<_, ()>(
+            &self.runner,
+            &self.base_version_url,
+            None,
+            self.headers(),
+            ApiOperation::Project,
+        )?;
+        let version = response["version"].as_str().unwrap_or_default().to_string();
+        let mut unavailable_features = Vec::new();
+        // Gitlab.com runs pre-release versions and the tier is given by the
+        // namespace, so the edition is only checked on self-managed instances.
+        if self.domain != "gitlab.com" && !version.is_empty() && !version.ends_with("-ee") {
+            unavailable_features.push((
+                "merge trains".to_string(),
+                "Requires Gitlab Premium or Ultimate".to_string(),
+            ));
+        } else if !version_at_least(&version, 15, 11) {
+            unavailable_features.push((
+                "merge trains".to_string(),
+                "Adding merge requests to merge trains requires Gitlab 15.11".to_string(),
+            ));
+        }
+        if !version_at_least(&version, 15, 9) {
+            unavailable_features.push((
+                "keyset pagination".to_string(),
+                "Listing all jobs of a project requires Gitlab 15.9".to_string(),
+            ));
+        }
+        Ok(RemoteInfo::builder()
+            .domain(self.domain.clone())
+            .version(version)
+            .api_version("v4".to_string())
+            .unavailable_features(unavailable_features)
+            .build()
+            .unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::setup_client;
+    use crate::test::utils::{
+        default_gitlab, BasePath, ClientType, ContractType, Domain, ResponseContracts,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_info_of_outdated_community_edition() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(r#"{"version":"15.4.2","revision":"5bb3d7a"}"#),
+            None,
+        );
+        let client_type = ClientType::Gitlab(
+            Domain("gitlab.company.com".to_string()),
+            BasePath("jordilin/gitlapi".to_string()),
+        );
+        let (client, gitlab) = setup_client!(contracts, client_type, dyn RemoteInstance);
+        let info = gitlab.info().unwrap();
+        assert_eq!("https://gitlab.company.com/api/v4/version", *client.url());
+        assert_eq!("15.4.2", info.version);
+        assert_eq!(
+            vec!["merge trains", "keyset pagination"],
+            info.unavailable_features
+                .iter()
+                .map(|(feature, _)| feature.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_info_of_gitlab_com_has_all_features() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(r#"{"version":"17.5.0-pre","revision":"5bb3d7a"}"#),
+            None,
+        );
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteInstance);
+        assert!(gitlab.info().unwrap().unavailable_features.is_empty());
+    }
+}
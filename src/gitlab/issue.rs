@@ -0,0 +1,248 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::api_traits::{ApiOperation, Issue, NumberDeltaErr};
+use crate::cmds::issue::{
+    IssueBodyArgs, IssueCommentBodyArgs, IssueListBodyArgs, IssueResponse, IssueState,
+};
+use crate::http::{self, Body};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::contract::Contract;
+use crate::remote::query;
+use crate::time;
+use crate::Result;
+
+use super::{with_order_by, Gitlab};
+
+impl<R: HttpRunner<Response = HttpResponse>> Issue for Gitlab<R> {
+    fn create(&self, args: IssueBodyArgs) -> Result<IssueResponse> {
+        let url = format!("{}/issues", self.rest_api_basepath());
+        let mut body = Body::new();
+        body.add("title", args.title);
+        body.add("description", args.description);
+        if !args.labels.is_empty() {
+            body.add("labels", args.labels.join(","));
+        }
+        query::send_as::<_, String, GitlabIssue, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Issue,
+            http::Method::POST,
+        )
+    }
+
+    fn get(&self, id: i64) -> Result<IssueResponse> {
+        let url = format!("{}/issues/{}", self.rest_api_basepath(), id);
+        query::get_as::<_, (), GitlabIssue, _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Issue,
+        )
+    }
+
+    fn list(&self, args: IssueListBodyArgs) -> Result<Vec<IssueResponse>> {
+        let url = self.list_issues_url(&args, false);
+        query::paged_as::<_, GitlabIssue, _>(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.headers(),
+            None,
+            ApiOperation::Issue,
+        )
+    }
+
+    fn num_pages(&self, args: IssueListBodyArgs) -> Result<Option<u32>> {
+        let url = self.list_issues_url(&args, true);
+        query::num_pages(&self.runner, &url, self.headers(), ApiOperation::Issue)
+    }
+
+    fn num_resources(&self, args: IssueListBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.list_issues_url(&args, true);
+        query::num_resources(&self.runner, &url, self.headers(), ApiOperation::Issue)
+    }
+
+    fn comment(&self, args: IssueCommentBodyArgs) -> Result<()> {
+        let url = format!("{}/issues/{}/notes", self.rest_api_basepath(), args.id);
+        let mut body = Body::new();
+        body.add("body", args.comment);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Issue,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    fn close(&self, id: i64) -> Result<IssueResponse> {
+        let url = format!("{}/issues/{}", self.rest_api_basepath(), id);
+        let mut body = Body::new();
+        body.add("state_event", "close");
+        query::send_as::<_, &str, GitlabIssue, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Issue,
+            http::Method::PUT,
+        )
+    }
+}
+
+impl<R> Gitlab<R> {
+    fn list_issues_url(&self, args: &IssueListBodyArgs, num_pages: bool) -> String {
+        let url = format!("{}/issues", self.rest_api_basepath());
+        // Issues in all states are listed when no state is given.
+        let url = match args.state {
+            IssueState::All => url,
+            state => format!("{}?state={}", url, state),
+        };
+        let url = with_order_by(url, &args.list_args);
+        if !num_pages {
+            return url;
+        }
+        if url.contains('?') {
+            format!("{}&page=1", url)
+        } else {
+            format!("{}?page=1", url)
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabIssue {
+    iid: i64,
+    title: String,
+    description: Option<String>,
+    author: GitlabIssueAuthor,
+    state: String,
+    web_url: String,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_at: DateTime<Utc>,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    updated_at: DateTime<Utc>,
+}
+
+impl Contract for GitlabIssue {}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabIssueAuthor {
+    username: String,
+}
+
+impl From<GitlabIssue> for IssueResponse {
+    fn from(data: GitlabIssue) -> Self {
+        IssueResponse::builder()
+            .id(data.iid)
+            .title(data.title)
+            .description(data.description.unwrap_or_default())
+            .author(data.author.username)
+            .state(data.state)
+            .web_url(data.web_url)
+            .created_at(data.created_at)
+            .updated_at(data.updated_at)
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::setup_client;
+    use crate::test::utils::{default_gitlab, ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_list_opened_issues() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(format!(
+                "[{}]",
+                crate::test::utils::get_contract(ContractType::Gitlab, "issue.json")
+            )),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Issue);
+        let args = IssueListBodyArgs::builder()
+            .state(IssueState::Opened)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let issues = gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues?state=opened",
+            *client.url()
+        );
+        assert_eq!(1, issues.len());
+        assert_eq!(24, issues[0].id);
+        assert_eq!("jdoe", issues[0].author);
+        assert_eq!("opened", issues[0].state);
+        assert_eq!(Some(ApiOperation::Issue), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_create_issue_with_labels() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(201, "issue.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Issue);
+        let args = IssueBodyArgs::builder()
+            .title("Test issue".to_string())
+            .labels(vec!["bug".to_string(), "ui".to_string()])
+            .build()
+            .unwrap();
+        let issue = gitlab.create(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/jordilin/gitlapi/-/issues/24",
+            issue.web_url
+        );
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("bug,ui", body["labels"]);
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_comment_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(201, "comment.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Issue);
+        let args = IssueCommentBodyArgs::builder()
+            .id(24)
+            .comment("Cannot reproduce".to_string())
+            .build()
+            .unwrap();
+        gitlab.comment(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues/24/notes",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_close_issue() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "issue.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Issue);
+        gitlab.close(24).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/issues/24",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("close", body["state_event"]);
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+    }
+}
@@ -0,0 +1,129 @@
+use serde::Deserialize;
+
+use crate::api_traits::{ApiOperation, Label, NumberDeltaErr};
+use crate::cmds::label::{LabelBodyArgs, LabelListBodyArgs, LabelResponse};
+use crate::http::{self, Body};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::contract::Contract;
+use crate::remote::query;
+use crate::Result;
+
+use super::Gitlab;
+
+impl<R: HttpRunner<Response = HttpResponse>> Label for Gitlab<R> {
+    fn list(&self, args: LabelListBodyArgs) -> Result<Vec<LabelResponse>> {
+        let url = format!("{}/labels", self.rest_api_basepath());
+        query::paged_as::<_, GitlabLabel, _>(
+            &self.runner,
+            &url,
+            args.list_args,
+            self.headers(),
+            None,
+            ApiOperation::Project,
+        )
+    }
+
+    fn num_pages(&self) -> Result<Option<u32>> {
+        let url = format!("{}/labels?page=1", self.rest_api_basepath());
+        query::num_pages(&self.runner, &url, self.headers(), ApiOperation::Project)
+    }
+
+    fn num_resources(&self) -> Result<Option<NumberDeltaErr>> {
+        let url = format!("{}/labels?page=1", self.rest_api_basepath());
+        query::num_resources(&self.runner, &url, self.headers(), ApiOperation::Project)
+    }
+
+    fn create(&self, args: LabelBodyArgs) -> Result<LabelResponse> {
+        let url = format!("{}/labels", self.rest_api_basepath());
+        let mut body = Body::new();
+        body.add("name", args.name);
+        // Gitlab requires the leading #
+        body.add("color", format!("#{}", args.color.trim_start_matches('#')));
+        if !args.description.is_empty() {
+            body.add("description", args.description);
+        }
+        query::send_as::<_, String, GitlabLabel, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::Project,
+            http::Method::POST,
+        )
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabLabel {
+    id: i64,
+    name: String,
+    color: String,
+    description: Option<String>,
+}
+
+impl Contract for GitlabLabel {}
+
+impl From<GitlabLabel> for LabelResponse {
+    fn from(data: GitlabLabel) -> Self {
+        LabelResponse::builder()
+            .id(data.id)
+            .name(data.name)
+            .color(data.color)
+            .description(data.description.unwrap_or_default())
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::setup_client;
+    use crate::test::utils::{default_gitlab, get_contract, ContractType, ResponseContracts};
+
+    use super::*;
+
+    #[test]
+    fn test_list_labels() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(format!(
+                "[{}]",
+                get_contract(ContractType::Gitlab, "label.json")
+            )),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Label);
+        let args = LabelListBodyArgs::builder()
+            .list_args(None)
+            .build()
+            .unwrap();
+        let labels = gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/labels",
+            *client.url()
+        );
+        assert_eq!(1, labels.len());
+        assert_eq!("bug", labels[0].name);
+        assert_eq!("#d9534f", labels[0].color);
+        assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_create_label_adds_leading_hash_to_color() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(201, "label.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Label);
+        let args = LabelBodyArgs::builder()
+            .name("bug".to_string())
+            .color("d9534f".to_string())
+            .build()
+            .unwrap();
+        gitlab.create(args).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("bug", body["name"]);
+        assert_eq!("#d9534f", body["color"]);
+        assert!(body.get("description").is_none());
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+    }
+}
@@ -1,23 +1,36 @@
-use crate::api_traits::{ApiOperation, CommentMergeRequest, NumberDeltaErr, RemoteProject};
+use crate::api_defaults::MAX_PER_PAGE;
+use crate::api_traits::{
+    ApiOperation, CommentMergeRequest, MergeQueue, MergeRequestDependency, MergeRequestDiff,
+    MergeRequestDiscussion, MergeRequestReaction, MergeRequestRebase, MergeRequestReview,
+    MergeRequestSuggestion, NumberDeltaErr, RemoteProject, ReviewMergeRequest,
+};
 use crate::cli::browse::BrowseOptions;
+use crate::cmds::merge_request::review::ReviewFile;
 use crate::cmds::merge_request::{
-    Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs, MergeRequestBodyArgs,
-    MergeRequestListBodyArgs, MergeRequestResponse,
+    Comment, CommentMergeRequestBodyArgs, CommentMergeRequestListBodyArgs, Discussion,
+    DiscussionReplyBodyArgs, FileChange, FileChangeStatus, MergeMethod, MergeQueueEntry,
+    MergeRequestBodyArgs, MergeRequestListBodyArgs, MergeRequestMergeBodyArgs,
+    MergeRequestResponse, Reaction, ReactionBodyArgs, RebaseStatus, ReviewBodyArgs, ReviewEvent,
+    Suggestion, SuggestionBodyArgs,
 };
 use crate::cmds::project::MrMemberType;
 use crate::error::{self, GRError};
 use crate::http::{self, Body, Headers};
 use crate::io::CmdInfo;
-use crate::remote::query;
+use crate::remote::contract::Contract;
+use crate::remote::{self, query};
+use crate::time::{self, Seconds};
 use crate::Result;
 use crate::{
     api_traits::MergeRequest,
     io::{HttpResponse, HttpRunner},
 };
+use chrono::{DateTime, Utc};
 
 use crate::json_loads;
+use serde::Deserialize;
 
-use super::Gitlab;
+use super::{encode_path, with_order_by, with_updated_range, Gitlab};
 
 impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
     fn open(&self, args: MergeRequestBodyArgs) -> Result<MergeRequestResponse> {
@@ -31,13 +44,18 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
             }
             MrMemberType::Empty => {}
         }
-        match args.reviewer.mr_member_type {
-            MrMemberType::Filled => {
-                // We support one reviewer for now. The CE edition of Gitlab
-                // only supports one, so we'll keep it simple.
-                body.add("reviewer_ids", args.reviewer.id.to_string());
-            }
-            MrMemberType::Empty => {}
+        let reviewer_ids = args
+            .reviewers
+            .iter()
+            .filter(|reviewer| reviewer.mr_member_type == MrMemberType::Filled)
+            .map(|reviewer| reviewer.id.to_string())
+            .collect::<Vec<_>>();
+        if !reviewer_ids.is_empty() {
+            // Gitlab CE keeps only the first reviewer.
+            body.add("reviewer_ids", reviewer_ids.join(","));
+        }
+        if !args.labels.is_empty() {
+            body.add("labels", args.labels.join(","));
         }
         body.add("description", args.description);
         body.add("remove_source_branch", args.remove_source_branch);
@@ -127,27 +145,63 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
 
     fn list(&self, args: MergeRequestListBodyArgs) -> Result<Vec<MergeRequestResponse>> {
         let url = self.list_merge_request_url(&args, false);
-        query::paged(
+        query::paged_as::<_, GitlabMergeRequest, _>(
             &self.runner,
             &url,
             args.list_args,
             self.headers(),
             None,
             ApiOperation::MergeRequest,
-            |value| GitlabMergeRequestFields::from(value).into(),
         )
     }
 
-    fn merge(&self, id: i64) -> Result<MergeRequestResponse> {
+    // Gitlab signs merge commits when the instance signs web commits, the API
+    // has no signing options.
+    fn merge(&self, args: MergeRequestMergeBodyArgs) -> Result<MergeRequestResponse> {
         // PUT /projects/:id/merge_requests/:merge_request_iid/merge
-        let url = format!("{}/merge_requests/{}/merge", self.rest_api_basepath(), id);
-        query::send::<_, (), _>(
+        if args.method == Some(MergeMethod::Rebase) {
+            return Err(GRError::PreconditionNotMet(
+                "Gitlab merge method is set in the project settings, it cannot be chosen when merging".to_string(),
+            )
+            .into());
+        }
+        let url = format!(
+            "{}/merge_requests/{}/merge",
+            self.rest_api_basepath(),
+            args.id
+        );
+        let squash = args.squash || args.method == Some(MergeMethod::Squash);
+        let mut body = Body::new();
+        if let Some(message) = args.message {
+            // Squashing creates the squash commit on top of the merge commit,
+            // if any. Both get the message.
+            if squash {
+                body.add("squash_commit_message", message.clone());
+            }
+            body.add("merge_commit_message", message);
+        }
+        // Gitlab refuses to merge if the source branch head does not match.
+        if let Some(sha) = args.sha {
+            body.add("sha", sha);
+        }
+        if squash {
+            body.add("squash", "true".to_string());
+        }
+        if args.delete_source_branch {
+            body.add("should_remove_source_branch", "true".to_string());
+        }
+        // auto_merge supersedes merge_when_pipeline_succeeds in Gitlab 17.11.
+        // Older instances ignore it.
+        if args.auto_merge {
+            body.add("merge_when_pipeline_succeeds", "true".to_string());
+            body.add("auto_merge", "true".to_string());
+        }
+        query::send_as::<_, String, GitlabMergeRequest, _>(
             &self.runner,
             &url,
-            None,
+            Some(&body),
             self.headers(),
             ApiOperation::MergeRequest,
-            |value| GitlabMergeRequestFields::from(value).into(),
             http::Method::PUT,
         )
     }
@@ -155,13 +209,12 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
     fn get(&self, id: i64) -> Result<MergeRequestResponse> {
         // GET /projects/:id/merge_requests/:merge_request_iid
         let url = format!("{}/merge_requests/{}", self.rest_api_basepath(), id);
-        query::get::<_, (), _>(
+        query::get_as::<_, (), GitlabMergeRequest, _>(
             &self.runner,
             &url,
             None,
             self.headers(),
             ApiOperation::MergeRequest,
-            |value| GitlabMergeRequestFields::from(value).into(),
         )
     }
 
@@ -169,13 +222,29 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
         let url = format!("{}/merge_requests/{}", self.rest_api_basepath(), id);
         let mut body = Body::new();
         body.add("state_event", "close");
-        query::send::<_, &str, _>(
+        query::send_as::<_, &str, GitlabMergeRequest, _>(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )
+    }
+
+    // Gitlab derives the draft status from the title prefix.
+    // https://docs.gitlab.com/ee/user/project/merge_requests/drafts.html
+    fn toggle_draft(&self, id: i64, draft: bool) -> Result<MergeRequestResponse> {
+        let merge_request = self.get(id)?;
+        let url = format!("{}/merge_requests/{}", self.rest_api_basepath(), id);
+        let mut body = Body::new();
+        body.add("title", draft_title(&merge_request.title, draft));
+        query::send_as::<_, String, GitlabMergeRequest, _>(
             &self.runner,
             &url,
             Some(&body),
             self.headers(),
             ApiOperation::MergeRequest,
-            |value| GitlabMergeRequestFields::from(value).into(),
             http::Method::PUT,
         )
     }
@@ -183,26 +252,25 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
     fn num_pages(&self, args: MergeRequestListBodyArgs) -> Result<Option<u32>> {
         let url = self.list_merge_request_url(&args, true);
         let mut headers = Headers::new();
-        headers.set("PRIVATE-TOKEN", self.api_token());
+        self.auth.apply(&mut headers);
         query::num_pages(&self.runner, &url, headers, ApiOperation::MergeRequest)
     }
 
     fn num_resources(&self, args: MergeRequestListBodyArgs) -> Result<Option<NumberDeltaErr>> {
         let url = self.list_merge_request_url(&args, true);
         let mut headers = Headers::new();
-        headers.set("PRIVATE-TOKEN", self.api_token());
+        self.auth.apply(&mut headers);
         query::num_resources(&self.runner, &url, headers, ApiOperation::MergeRequest)
     }
 
     fn approve(&self, id: i64) -> Result<MergeRequestResponse> {
         let url = format!("{}/merge_requests/{}/approve", self.rest_api_basepath(), id);
-        let result = query::send::<_, (), MergeRequestResponse>(
+        let result = query::send_as::<_, (), GitlabMergeRequestApproval, MergeRequestResponse>(
             &self.runner,
             &url,
             None,
             self.headers(),
             ApiOperation::MergeRequest,
-            |value| GitlabMergeRequestFields::from(value).into(),
             http::Method::POST,
         );
         // responses in approvals for Gitlab do not contain the merge request
@@ -215,34 +283,302 @@ impl<R: HttpRunner<Response = HttpResponse>> MergeRequest for Gitlab<R> {
     }
 }
 
+/// Title with the draft prefix added or removed. Gitlab recognizes `Draft:`,
+/// `[Draft]` and `(Draft)` case insensitive.
+fn draft_title(title: &str, draft: bool) -> String {
+    let lower = title.to_lowercase();
+    let title = ["draft:", "[draft]", "(draft)"]
+        .iter()
+        .find(|prefix| lower.starts_with(*prefix))
+        .map(|prefix| title[prefix.len()..].trim_start())
+        .unwrap_or(title);
+    if draft {
+        format!("Draft: {}", title)
+    } else {
+        title.to_string()
+    }
+}
+
+/// Merge trains run a merge request pipeline per car on top of the cars ahead
+/// of it. Position is given by the order of the active cars on the target
+/// branch, and the estimated time to merge is derived from the average
+/// duration of the most recently completed cars.
+impl<R: HttpRunner<Response = HttpResponse>> MergeQueue for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/merge_trains.html#add-a-merge-request-to-a-merge-train
+    fn add(&self, id: i64) -> Result<MergeQueueEntry> {
+        let url = format!(
+            "{}/merge_trains/merge_requests/{}",
+            self.rest_api_basepath(),
+            id
+        );
+        let mut body = Body::new();
+        body.add("when_pipeline_succeeds", "true");
+        let cars = query::send_json(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        let cars = cars.as_array().cloned().unwrap_or_default();
+        let position = cars
+            .iter()
+            .position(|car| car["merge_request"]["iid"].as_i64() == Some(id));
+        let mut entry = match position {
+            Some(position) => merge_train_car(&cars[position]),
+            // Added to be queued once the pipeline succeeds.
+            None => MergeQueueEntry::builder()
+                .id(id)
+                .status("waiting_for_pipeline".to_string())
+                .build()
+                .unwrap(),
+        };
+        entry.position = position.map(|p| p as u32 + 1);
+        Ok(entry)
+    }
+
+    // Cancelling the auto-merge of a merge request removes it from the train.
+    // https://docs.gitlab.com/ee/api/merge_requests.html#cancel-merge-when-pipeline-succeeds
+    fn remove(&self, id: i64) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/cancel_merge_when_pipeline_succeeds",
+            self.rest_api_basepath(),
+            id
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.gitlab.com/ee/api/merge_trains.html#get-the-status-of-a-merge-request-on-a-merge-train
+    fn status(&self, id: i64) -> Result<MergeQueueEntry> {
+        let url = format!(
+            "{}/merge_trains/merge_requests/{}",
+            self.rest_api_basepath(),
+            id
+        );
+        let car = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let mut entry = merge_train_car(&car);
+        let active = self.merge_train_cars(&entry.target_branch, "active")?;
+        let position = active
+            .iter()
+            .position(|car| car["merge_request"]["iid"].as_i64() == Some(id))
+            .map(|p| p as u32 + 1);
+        if let Some(position) = position {
+            let durations = self
+                .merge_train_cars(&entry.target_branch, "complete")?
+                .iter()
+                .filter_map(|car| car["duration"].as_u64())
+                .collect::<Vec<_>>();
+            if !durations.is_empty() {
+                let average = durations.iter().sum::<u64>() / durations.len() as u64;
+                entry.eta = Some(Seconds::new(average * position as u64));
+            }
+        }
+        entry.position = position;
+        Ok(entry)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestRebase for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/merge_requests.html#rebase-a-merge-request
+    fn rebase(&self, id: i64) -> Result<RebaseStatus> {
+        let url = format!("{}/merge_requests/{}/rebase", self.rest_api_basepath(), id);
+        let response = query::send_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(RebaseStatus::builder()
+            .id(id)
+            .in_progress(response["rebase_in_progress"].as_bool().unwrap_or(true))
+            .build()
+            .unwrap())
+    }
+
+    fn rebase_status(&self, started: &RebaseStatus) -> Result<RebaseStatus> {
+        let url = format!(
+            "{}/merge_requests/{}?include_rebase_in_progress=true",
+            self.rest_api_basepath(),
+            started.id
+        );
+        let merge_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let in_progress = merge_request["rebase_in_progress"]
+            .as_bool()
+            .unwrap_or_default();
+        // Failed rebases are reported in the merge error of the merge request.
+        let error = merge_request["merge_error"]
+            .as_str()
+            .filter(|_| !in_progress)
+            .map(|error| error.to_string());
+        Ok(RebaseStatus::builder()
+            .id(started.id)
+            .in_progress(in_progress)
+            .sha(
+                merge_request["sha"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            )
+            .error(error)
+            .build()
+            .unwrap())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestDependency for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/merge_requests.html#create-a-merge-request-dependency
+    fn add_dependency(&self, id: i64, depends_on: i64) -> Result<()> {
+        // Dependencies are declared using the global ID of the blocking merge
+        // request, not its IID.
+        let blocking_url = format!("{}/merge_requests/{}", self.rest_api_basepath(), depends_on);
+        let blocking = query::get_json::<_, ()>(
+            &self.runner,
+            &blocking_url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let url = format!("{}/merge_requests/{}/blocks", self.rest_api_basepath(), id);
+        let mut body = Body::new();
+        body.add(
+            "blocking_merge_request_id",
+            blocking["id"].as_i64().unwrap_or_default().to_string(),
+        );
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.gitlab.com/ee/api/merge_requests.html#get-merge-request-dependencies
+    fn list_dependencies(&self, id: i64) -> Result<Vec<MergeRequestResponse>> {
+        let url = format!(
+            "{}/merge_requests/{}/blockers",
+            self.rest_api_basepath(),
+            id
+        );
+        let blockers = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        blockers
+            .as_array()
+            .map(|blockers| {
+                blockers
+                    .iter()
+                    .map(|blocker| {
+                        query::decode::<_, GitlabMergeRequestBlocker, _>(&self.runner, blocker)
+                    })
+                    .collect()
+            })
+            .unwrap_or(Ok(Vec::new()))
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/merge_trains.html#list-merge-trains-for-a-project
+    fn merge_train_cars(&self, target_branch: &str, scope: &str) -> Result<Vec<serde_json::Value>> {
+        let url = format!(
+            "{}/merge_trains?target_branch={}&scope={}&sort=asc&per_page=100",
+            self.rest_api_basepath(),
+            remote::encode_query_param(target_branch),
+            scope
+        );
+        let cars = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        Ok(cars.as_array().cloned().unwrap_or_default())
+    }
+}
+
+fn merge_train_car(car: &serde_json::Value) -> MergeQueueEntry {
+    MergeQueueEntry::builder()
+        .id(car["merge_request"]["iid"].as_i64().unwrap_or_default())
+        .status(car["status"].as_str().unwrap_or_default().to_string())
+        .target_branch(
+            car["target_branch"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        )
+        .build()
+        .unwrap()
+}
+
 impl<R> Gitlab<R> {
     fn list_merge_request_url(&self, args: &MergeRequestListBodyArgs, num_pages: bool) -> String {
-        let mut url = if let Some(assignee) = &args.assignee {
-            format!(
-                "{}?state={}&assignee_id={}",
-                self.merge_requests_url, args.state, assignee.id
-            )
+        let filter = if let Some(assignee) = &args.assignee {
+            Some(format!("assignee_id={}", assignee.id))
         } else if let Some(reviewer) = &args.reviewer {
-            format!(
-                "{}?state={}&reviewer_id={}",
-                self.merge_requests_url, args.state, reviewer.id
-            )
-        } else if let Some(author) = &args.author {
-            format!(
-                "{}?state={}&author_id={}",
-                self.merge_requests_url, args.state, author.id
-            )
+            Some(format!("reviewer_id={}", reviewer.id))
         } else {
-            format!(
-                "{}/merge_requests?state={}",
-                self.rest_api_basepath(),
-                args.state
-            )
+            args.author
+                .as_ref()
+                .map(|author| format!("author_id={}", author.id))
         };
+        // User filters apply to merge requests across all projects unless
+        // restricted to a group and its subgroups or to the project.
+        let base_url = match (&args.group, &filter) {
+            (Some(group), _) => format!(
+                "{}/{}/merge_requests",
+                self.base_groups_url,
+                encode_path(group)
+            ),
+            (None, Some(_)) if !args.project_only => self.merge_requests_url.clone(),
+            (None, _) => format!("{}/merge_requests", self.rest_api_basepath()),
+        };
+        let mut url = format!("{}?state={}", base_url, args.state);
+        if let Some(filter) = filter {
+            url.push('&');
+            url.push_str(&filter);
+        }
+        if !args.labels.is_empty() {
+            url.push_str("&labels=");
+            url.push_str(&remote::encode_query_param(&args.labels.join(",")));
+        }
+        if let Some(milestone) = &args.milestone {
+            url.push_str("&milestone=");
+            url.push_str(&remote::encode_query_param(milestone));
+        }
         if num_pages {
             url.push_str("&page=1");
         }
-        url
+        with_order_by(with_updated_range(url, &args.list_args), &args.list_args)
     }
 
     fn resource_comments_metadata_url(&self, args: CommentMergeRequestListBodyArgs) -> String {
@@ -275,6 +611,44 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Gitlab<R> {
         Ok(())
     }
 
+    fn update(&self, comment_id: i64, args: CommentMergeRequestBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/notes/{}",
+            self.rest_api_basepath(),
+            args.id,
+            comment_id
+        );
+        let mut body = Body::new();
+        body.add("body", args.comment);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(())
+    }
+
+    fn delete(&self, id: i64, comment_id: i64) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/notes/{}",
+            self.rest_api_basepath(),
+            id,
+            comment_id
+        );
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
     fn list(&self, args: CommentMergeRequestListBodyArgs) -> Result<Vec<Comment>> {
         let url = format!(
             "{}/merge_requests/{}/notes",
@@ -282,15 +656,46 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Gitlab<R> {
             args.id
         );
 
-        query::paged(
+        let comments = query::paged_as::<_, GitlabMergeRequestComment, _>(
             &self.runner,
             &url,
             args.list_args,
             self.headers(),
             None,
             ApiOperation::MergeRequest,
-            |value| GitlabMergeRequestCommentFields::from(value).into(),
-        )
+        )?;
+        if !args.reactions {
+            return Ok(comments);
+        }
+        // Notes do not carry their award emoji. A single page of the largest
+        // size covers the emoji awarded to any reasonable comment.
+        comments
+            .into_iter()
+            .map(|mut comment| {
+                let url = format!(
+                    "{}?per_page={}",
+                    self.award_emoji_url(args.id, Some(comment.id)),
+                    MAX_PER_PAGE
+                );
+                let emojis = query::get::<_, (), _>(
+                    &self.runner,
+                    &url,
+                    None,
+                    self.headers(),
+                    ApiOperation::MergeRequest,
+                    |value| {
+                        Ok(value
+                            .as_array()
+                            .unwrap_or(&Vec::new())
+                            .iter()
+                            .map(|award| award["name"].as_str().unwrap_or_default().to_string())
+                            .collect::<Vec<_>>())
+                    },
+                )?;
+                comment.reactions = Reaction::count(emojis);
+                Ok(comment)
+            })
+            .collect()
     }
 
     fn num_pages(&self, args: CommentMergeRequestListBodyArgs) -> Result<Option<u32>> {
@@ -317,95 +722,630 @@ impl<R: HttpRunner<Response = HttpResponse>> CommentMergeRequest for Gitlab<R> {
     }
 }
 
-pub struct GitlabMergeRequestFields {
-    fields: MergeRequestResponse,
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestReaction for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/emoji_reactions.html
+    fn react(&self, args: ReactionBodyArgs) -> Result<()> {
+        let url = self.award_emoji_url(args.id, args.comment_id);
+        let mut body = Body::new();
+        body.add("name", args.emoji);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
 }
 
-impl From<&serde_json::Value> for GitlabMergeRequestFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabMergeRequestFields {
-            fields: MergeRequestResponse::builder()
-                .id(data["iid"].as_i64().unwrap_or_default())
-                .web_url(data["web_url"].as_str().unwrap_or_default().to_string())
-                .source_branch(
-                    data["source_branch"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .sha(
-                    data["merge_commit_sha"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .author(
-                    data["author"]["username"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .updated_at(data["updated_at"].as_str().unwrap_or_default().to_string())
-                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
-                .title(data["title"].as_str().unwrap_or_default().to_string())
-                .description(data["description"].as_str().unwrap_or_default().to_string())
-                // If merge request is not merged, merged_at is an empty string.
-                .merged_at(data["merged_at"].as_str().unwrap_or_default().to_string())
-                // Documentation recommends gathering head_pipeline instead of
-                // pipeline key.
-                .pipeline_id(data["head_pipeline"]["id"].as_i64())
-                .pipeline_url(
-                    data["head_pipeline"]["web_url"]
-                        .as_str()
-                        .map(|s| s.to_string()),
-                )
-                .build()
-                .unwrap(),
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestDiscussion for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/discussions.html#list-project-merge-request-discussion-items
+    fn list_discussions(&self, id: i64) -> Result<Vec<Discussion>> {
+        let mut threads = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/merge_requests/{}/discussions?per_page={}&page={}",
+                self.rest_api_basepath(),
+                id,
+                MAX_PER_PAGE,
+                page
+            );
+            let discussions = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.headers(),
+                ApiOperation::MergeRequest,
+            )?;
+            let discussions = discussions.as_array().cloned().unwrap_or_default();
+            for data in discussions.iter() {
+                threads.extend(discussion(data)?);
+            }
+            if discussions.len() < MAX_PER_PAGE as usize {
+                break;
+            }
         }
+        Ok(threads)
     }
-}
 
-impl From<GitlabMergeRequestFields> for MergeRequestResponse {
-    fn from(fields: GitlabMergeRequestFields) -> Self {
-        fields.fields
+    // https://docs.gitlab.com/ee/api/discussions.html#add-note-to-existing-merge-request-thread
+    fn reply(&self, args: DiscussionReplyBodyArgs) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/discussions/{}/notes",
+            self.rest_api_basepath(),
+            args.id,
+            args.discussion_id
+        );
+        let mut body = Body::new();
+        body.add("body", args.body);
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
     }
 }
 
-pub struct GitlabMergeRequestCommentFields {
-    comment: Comment,
+/// Thread out of a Gitlab discussion leaving out system notes, ex. "added 1
+/// commit". None if there are only system notes.
+fn discussion(data: &serde_json::Value) -> Result<Option<Discussion>> {
+    let notes = data["notes"]
+        .as_array()
+        .map(|notes| {
+            notes
+                .iter()
+                .filter(|note| !note["system"].as_bool().unwrap_or_default())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let Some(first) = notes.first() else {
+        return Ok(None);
+    };
+    let position = &first["position"];
+    let file = position["new_path"]
+        .as_str()
+        .or(position["old_path"].as_str())
+        .unwrap_or_default();
+    let line = position["new_line"]
+        .as_i64()
+        .or(position["old_line"].as_i64());
+    Ok(Some(
+        Discussion::builder()
+            .id(data["id"].as_str().unwrap_or_default().to_string())
+            .file(file.to_string())
+            .line(line)
+            .resolved(first["resolved"].as_bool().unwrap_or_default())
+            .notes(
+                notes
+                    .iter()
+                    .map(|note| {
+                        Ok(Comment::builder()
+                            .id(note["id"].as_i64().unwrap_or_default())
+                            .author(
+                                note["author"]["username"]
+                                    .as_str()
+                                    .unwrap_or_default()
+                                    .to_string(),
+                            )
+                            .body(note["body"].as_str().unwrap_or_default().to_string())
+                            .created_at(time::json_optional_timestamp(note, "created_at")?)
+                            .build()
+                            .unwrap())
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            )
+            .build()
+            .unwrap(),
+    ))
 }
 
-impl From<&serde_json::Value> for GitlabMergeRequestCommentFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabMergeRequestCommentFields {
-            comment: Comment::builder()
-                .id(data["id"].as_i64().unwrap_or_default())
-                .body(data["body"].as_str().unwrap_or_default().to_string())
-                .author(
-                    data["author"]["username"]
-                        .as_str()
-                        .unwrap_or_default()
-                        .to_string(),
-                )
-                .created_at(data["created_at"].as_str().unwrap_or_default().to_string())
-                .build()
-                .unwrap(),
-        }
-    }
-}
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestSuggestion for Gitlab<R> {
+    // Suggestions are diff threads positioned against the diff refs of the
+    // merge request.
+    // https://docs.gitlab.com/ee/api/discussions.html#create-a-new-thread-in-the-merge-request-diff
+    fn suggest(&self, args: SuggestionBodyArgs) -> Result<()> {
+        let position = self.diff_refs(args.id)?.position(&args.file, args.line);
+        let mut body = Body::new();
+        body.add(
+            "body",
+            serde_json::Value::from(args.body("suggestion:-0+0")),
+        );
+        body.add("position", position);
+        query::send_raw(
+            &self.runner,
+            &format!(
+                "{}/merge_requests/{}/discussions",
+                self.rest_api_basepath(),
+                args.id
+            ),
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
 
-impl From<GitlabMergeRequestCommentFields> for Comment {
-    fn from(fields: GitlabMergeRequestCommentFields) -> Self {
-        fields.comment
+    // Suggestions are attached to the notes of the merge request threads.
+    // https://docs.gitlab.com/ee/api/discussions.html#list-project-merge-request-discussion-items
+    fn list_suggestions(&self, id: i64) -> Result<Vec<Suggestion>> {
+        let mut suggestions = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/merge_requests/{}/discussions?per_page={}&page={}",
+                self.rest_api_basepath(),
+                id,
+                MAX_PER_PAGE,
+                page
+            );
+            let discussions = query::get_json::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.headers(),
+                ApiOperation::MergeRequest,
+            )?;
+            let discussions = discussions.as_array().cloned().unwrap_or_default();
+            suggestions.extend(
+                discussions
+                    .iter()
+                    .flat_map(|discussion| discussion["notes"].as_array().cloned())
+                    .flatten()
+                    .flat_map(|note| pending_suggestions(&note)),
+            );
+            if discussions.len() < MAX_PER_PAGE as usize {
+                break;
+            }
+        }
+        Ok(suggestions)
+    }
+
+    // https://docs.gitlab.com/ee/api/suggestions.html#apply-multiple-suggestions
+    fn apply_suggestions(&self, ids: &[i64], commit_message: Option<String>) -> Result<()> {
+        let url = format!("{}/batch_apply", self.base_suggestions_url);
+        let mut body = Body::new();
+        body.add("ids", serde_json::Value::from(ids));
+        if let Some(commit_message) = commit_message {
+            body.add("commit_message", serde_json::Value::from(commit_message));
+        }
+        query::send_raw(
+            &self.runner,
+            &url,
+            Some(&body),
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::PUT,
+        )?;
+        Ok(())
+    }
+}
+
+fn pending_suggestions(note: &serde_json::Value) -> Vec<Suggestion> {
+    note["suggestions"]
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .filter(|suggestion| {
+            suggestion["appliable"].as_bool().unwrap_or_default()
+                && !suggestion["applied"].as_bool().unwrap_or_default()
+        })
+        .map(|suggestion| {
+            Suggestion::builder()
+                .id(suggestion["id"].as_i64().unwrap_or_default())
+                .file(
+                    note["position"]["new_path"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .line(suggestion["from_line"].as_i64().unwrap_or_default())
+                .author(
+                    note["author"]["username"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .replacement(
+                    suggestion["to_content"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .trim_end()
+                        .to_string(),
+                )
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestReview for Gitlab<R> {
+    // Comments are added as draft notes, then published at once along with
+    // the review body, so the author gets a single notification.
+    // https://docs.gitlab.com/ee/api/draft_notes.html
+    fn submit_review(&self, review: ReviewFile) -> Result<()> {
+        let url = format!(
+            "{}/merge_requests/{}/draft_notes",
+            self.rest_api_basepath(),
+            review.merge_request
+        );
+        let mut draft_notes = Vec::new();
+        if !review.comments.is_empty() {
+            let diff_refs = self.diff_refs(review.merge_request)?;
+            for comment in &review.comments {
+                let mut body = Body::new();
+                body.add("note", serde_json::Value::from(comment.body.as_str()));
+                body.add("position", diff_refs.position(&comment.file, comment.line));
+                draft_notes.push(body);
+            }
+        }
+        if !review.body.trim().is_empty() {
+            let mut body = Body::new();
+            body.add("note", serde_json::Value::from(review.body.as_str()));
+            draft_notes.push(body);
+        }
+        for body in draft_notes {
+            query::send_raw(
+                &self.runner,
+                &url,
+                Some(&body),
+                self.headers(),
+                ApiOperation::MergeRequest,
+                http::Method::POST,
+            )?;
+        }
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &format!("{}/bulk_publish", url),
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> MergeRequestDiff for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/merge_requests.html#get-single-merge-request-changes
+    fn list_changes(&self, id: i64) -> Result<Vec<FileChange>> {
+        let url = format!("{}/merge_requests/{}/changes", self.rest_api_basepath(), id);
+        let merge_request = query::get_json::<_, ()>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        merge_request["changes"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|change| query::decode::<_, GitlabFileChange, FileChange>(&self.runner, change))
+            .collect()
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ReviewMergeRequest for Gitlab<R> {
+    // Gitlab has no review object. The outcome is given by the approval of
+    // the user and the review comment is added as a note.
+    fn review(&self, args: ReviewBodyArgs) -> Result<()> {
+        let url = format!("{}/merge_requests/{}", self.rest_api_basepath(), args.id);
+        match args.event {
+            ReviewEvent::Approve => {
+                query::send_raw::<_, ()>(
+                    &self.runner,
+                    &format!("{}/approve", url),
+                    None,
+                    self.headers(),
+                    ApiOperation::MergeRequest,
+                    http::Method::POST,
+                )?;
+            }
+            ReviewEvent::RequestChanges => {
+                let unapprove_url = format!("{}/unapprove", url);
+                let response = query::send_raw_unchecked::<_, ()>(
+                    &self.runner,
+                    &unapprove_url,
+                    None,
+                    self.headers(),
+                    ApiOperation::MergeRequest,
+                    http::Method::POST,
+                )?;
+                // 404 when the user had not approved the merge request.
+                if response.status != 404 && !response.is_ok(&http::Method::POST) {
+                    return Err(query::request_error(
+                        &http::Method::POST,
+                        &unapprove_url,
+                        &ApiOperation::MergeRequest,
+                        &response,
+                    ));
+                }
+            }
+            ReviewEvent::Comment => (),
+        }
+        if !args.body.trim().is_empty() {
+            let mut body = Body::new();
+            body.add("body", args.body);
+            query::send_raw(
+                &self.runner,
+                &format!("{}/notes", url),
+                Some(&body),
+                self.headers(),
+                ApiOperation::MergeRequest,
+                http::Method::POST,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabFileChange {
+    old_path: String,
+    new_path: String,
+    new_file: bool,
+    renamed_file: bool,
+    deleted_file: bool,
+    diff: String,
+}
+
+impl Contract for GitlabFileChange {}
+
+impl From<GitlabFileChange> for FileChange {
+    fn from(data: GitlabFileChange) -> Self {
+        let status = if data.new_file {
+            FileChangeStatus::Added
+        } else if data.deleted_file {
+            FileChangeStatus::Deleted
+        } else if data.renamed_file {
+            FileChangeStatus::Renamed
+        } else {
+            FileChangeStatus::Modified
+        };
+        // Gitlab does not count the lines changed, the diff holds the hunks
+        // only, without file headers.
+        let count = |prefix: char| {
+            data.diff
+                .lines()
+                .filter(|line| line.starts_with(prefix))
+                .count() as u32
+        };
+        FileChange::builder()
+            .path(data.new_path)
+            .old_path(data.old_path)
+            .status(status)
+            .additions(count('+'))
+            .deletions(count('-'))
+            .patch(data.diff.clone())
+            .build()
+            .unwrap()
+    }
+}
+
+/// Commits a merge request diff is computed from. Needed to place comments
+/// on lines of the diff.
+struct DiffRefs {
+    base_sha: String,
+    start_sha: String,
+    head_sha: String,
+}
+
+impl DiffRefs {
+    fn position(&self, file: &str, line: u32) -> serde_json::Value {
+        serde_json::json!({
+            "position_type": "text",
+            "base_sha": self.base_sha,
+            "start_sha": self.start_sha,
+            "head_sha": self.head_sha,
+            "old_path": file,
+            "new_path": file,
+            "new_line": line,
+        })
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    fn diff_refs(&self, id: i64) -> Result<DiffRefs> {
+        let merge_request = query::get_json::<_, ()>(
+            &self.runner,
+            &format!("{}/merge_requests/{}", self.rest_api_basepath(), id),
+            None,
+            self.headers(),
+            ApiOperation::MergeRequest,
+        )?;
+        let diff_ref = |key: &str| {
+            merge_request["diff_refs"][key]
+                .as_str()
+                .map(String::from)
+                .ok_or_else(|| {
+                    GRError::RemoteUnexpectedResponseContract(format!(
+                        "Merge request {} has no diff_refs.{}",
+                        id, key
+                    ))
+                })
+        };
+        Ok(DiffRefs {
+            base_sha: diff_ref("base_sha")?,
+            start_sha: diff_ref("start_sha")?,
+            head_sha: diff_ref("head_sha")?,
+        })
+    }
+}
+
+impl<R> Gitlab<R> {
+    fn award_emoji_url(&self, id: i64, comment_id: Option<i64>) -> String {
+        match comment_id {
+            Some(comment_id) => format!(
+                "{}/merge_requests/{}/notes/{}/award_emoji",
+                self.rest_api_basepath(),
+                id,
+                comment_id
+            ),
+            None => format!(
+                "{}/merge_requests/{}/award_emoji",
+                self.rest_api_basepath(),
+                id
+            ),
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabMergeRequest {
+    iid: i64,
+    web_url: String,
+    source_branch: String,
+    merge_commit_sha: Option<String>,
+    author: GitlabAuthor,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    updated_at: DateTime<Utc>,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_at: DateTime<Utc>,
+    title: String,
+    description: Option<String>,
+    // If merge request is not merged, merged_at is null.
+    #[serde(deserialize_with = "time::deserialize_optional_timestamp")]
+    merged_at: Option<DateTime<Utc>>,
+    sha: String,
+    // Documentation recommends gathering head_pipeline instead of pipeline
+    // key.
+    head_pipeline: Option<GitlabHeadPipeline>,
+    reviewers: Vec<GitlabAuthor>,
+    source_project_id: i64,
+    target_project_id: i64,
+}
+
+impl Contract for GitlabMergeRequest {
+    // Only the single merge request endpoint returns the head pipeline.
+    const OPTIONAL_FIELDS: &'static [&'static str] = &["head_pipeline"];
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabAuthor {
+    username: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabHeadPipeline {
+    id: i64,
+    web_url: String,
+}
+
+impl From<GitlabMergeRequest> for MergeRequestResponse {
+    fn from(data: GitlabMergeRequest) -> Self {
+        MergeRequestResponse::builder()
+            .id(data.iid)
+            .web_url(data.web_url)
+            .source_branch(data.source_branch)
+            .sha(data.merge_commit_sha.unwrap_or_default())
+            .author(data.author.username)
+            .updated_at(data.updated_at)
+            .created_at(data.created_at)
+            .title(data.title)
+            .description(data.description.unwrap_or_default())
+            .merged_at(data.merged_at)
+            .head_sha(data.sha)
+            .pipeline_id(data.head_pipeline.as_ref().map(|pipeline| pipeline.id))
+            .pipeline_url(data.head_pipeline.map(|pipeline| pipeline.web_url))
+            .reviewers(
+                data.reviewers
+                    .into_iter()
+                    .map(|reviewer| reviewer.username)
+                    .collect(),
+            )
+            .from_fork(data.source_project_id != data.target_project_id)
+            .head_ref(format!("refs/merge-requests/{}/head", data.iid))
+            .build()
+            .unwrap()
+    }
+}
+
+/// Approval state returned when approving a merge request.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabMergeRequestApproval {
+    iid: i64,
+    title: String,
+    description: Option<String>,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_at: DateTime<Utc>,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    updated_at: DateTime<Utc>,
+}
+
+impl Contract for GitlabMergeRequestApproval {}
+
+impl From<GitlabMergeRequestApproval> for MergeRequestResponse {
+    fn from(data: GitlabMergeRequestApproval) -> Self {
+        MergeRequestResponse::builder()
+            .id(data.iid)
+            .title(data.title)
+            .description(data.description.unwrap_or_default())
+            .created_at(data.created_at)
+            .updated_at(data.updated_at)
+            .build()
+            .unwrap()
+    }
+}
+
+/// Blocking merge requests come in a reduced form, without SHAs nor
+/// pipeline, so those are left to their defaults.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabMergeRequestBlocker {
+    blocking_merge_request: GitlabMergeRequest,
+}
+
+impl Contract for GitlabMergeRequestBlocker {}
+
+impl From<GitlabMergeRequestBlocker> for MergeRequestResponse {
+    fn from(data: GitlabMergeRequestBlocker) -> Self {
+        data.blocking_merge_request.into()
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GitlabMergeRequestComment {
+    id: i64,
+    body: String,
+    author: GitlabAuthor,
+    #[serde(deserialize_with = "time::deserialize_timestamp")]
+    created_at: DateTime<Utc>,
+}
+
+impl Contract for GitlabMergeRequestComment {}
+
+impl From<GitlabMergeRequestComment> for Comment {
+    fn from(data: GitlabMergeRequestComment) -> Self {
+        Comment::builder()
+            .id(data.id)
+            .body(data.body)
+            .author(data.author.username)
+            .created_at(Some(data.created_at))
+            .build()
+            .unwrap()
     }
 }
 
 #[cfg(test)]
 mod test {
 
+    use crate::cmds::merge_request::review::ReviewComment;
     use crate::cmds::merge_request::MergeRequestState;
     use crate::cmds::project::Member;
-    use crate::remote::ListBodyArgs;
+    use crate::remote::{ListBodyArgs, ListOrderBy, ListSortMode};
     use crate::setup_client;
     use crate::test::utils::{
         default_gitlab, get_contract, BasePath, ClientType, ContractType, Domain, ResponseContracts,
@@ -413,6 +1353,30 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn test_list_merge_requests_without_head_pipeline_in_strict_mode() {
+        let mut merge_request: serde_json::Value =
+            serde_json::from_str(&get_contract(ContractType::Gitlab, "merge_request.json"))
+                .unwrap();
+        merge_request
+            .as_object_mut()
+            .unwrap()
+            .remove("head_pipeline");
+        let body = serde_json::json!([merge_request]).to_string();
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some(body), None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .assignee(None)
+            .build()
+            .unwrap();
+        let merge_requests = gitlab.list(args).unwrap();
+        assert_eq!(1, merge_requests.len());
+        assert_eq!(None, merge_requests[0].pipeline_id);
+    }
+
     #[test]
     fn test_list_merge_request_with_from_page() {
         let contracts =
@@ -437,6 +1401,126 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_list_merge_request_updated_after_and_before() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(Some(
+                ListBodyArgs::builder()
+                    .updated_after(Some("2024-06-01T00:00:00Z".to_string()))
+                    .updated_before(Some("2024-07-01T00:00:00Z".to_string()))
+                    .build()
+                    .unwrap(),
+            ))
+            .assignee(None)
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=opened\
+             &updated_after=2024-06-01T00:00:00Z&updated_before=2024-07-01T00:00:00Z",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_merge_requests_of_subgroup_assigned_to_user() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let assignee = Member::builder()
+            .id(1234)
+            .username("jdoe".to_string())
+            .build()
+            .unwrap();
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .assignee(Some(assignee))
+            .group(Some("team/subgroup".to_string()))
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/groups/team%2Fsubgroup/merge_requests?state=opened\
+             &assignee_id=1234",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_merge_requests_with_labels_and_milestone() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .labels(vec!["bug".to_string(), "needs review".to_string()])
+            .milestone(Some("v1.0".to_string()))
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=opened\
+             &labels=bug%2Cneeds%20review&milestone=v1.0",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_merge_requests_ordered_by_remote() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(Some(
+                ListBodyArgs::builder()
+                    .order_by(Some(ListOrderBy::Updated))
+                    .sort_mode(ListSortMode::Desc)
+                    .build()
+                    .unwrap(),
+            ))
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=opened\
+             &order_by=updated_at&sort=desc",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_merge_request_per_page() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(Some(
+                ListBodyArgs::builder()
+                    .page(2)
+                    .max_pages(1)
+                    .per_page(Some(50))
+                    .build()
+                    .unwrap(),
+            ))
+            .assignee(None)
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=opened\
+             &per_page=50&page=2",
+            *client.url(),
+        );
+    }
+
     #[test]
     fn test_list_all_merge_requests_assigned_for_current_user() {
         let contract = ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
@@ -509,6 +1593,32 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_list_project_merge_requests_by_author() {
+        let contract = ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) = setup_client!(contract, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestListBodyArgs::builder()
+            .state(MergeRequestState::Opened)
+            .list_args(None)
+            .author(Some(
+                Member::builder()
+                    .name("tom".to_string())
+                    .username("tsawyer".to_string())
+                    .id(192)
+                    .build()
+                    .unwrap(),
+            ))
+            .project_only(true)
+            .build()
+            .unwrap();
+        gitlab.list(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests?state=opened\
+             &author_id=192",
+            *client.url(),
+        );
+    }
+
     #[test]
     fn test_open_merge_request() {
         let assignee = Member::builder()
@@ -527,7 +1637,7 @@ mod test {
             .unwrap();
         let mr_args = MergeRequestBodyArgs::builder()
             .assignee(assignee)
-            .reviewer(reviewer)
+            .reviewers(vec![reviewer])
             .build()
             .unwrap();
         let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
@@ -552,6 +1662,33 @@ mod test {
         assert!(actual_body.contains("reviewer_ids"));
     }
 
+    #[test]
+    fn test_open_merge_request_with_multiple_reviewers() {
+        let reviewers = [5678, 91011]
+            .into_iter()
+            .map(|id| {
+                Member::builder()
+                    .mr_member_type(MrMemberType::Filled)
+                    .id(id)
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        let mr_args = MergeRequestBodyArgs::builder()
+            .reviewers(reviewers)
+            .build()
+            .unwrap();
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            201,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        gitlab.open(mr_args).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("5678,91011", body["reviewer_ids"]);
+    }
+
     #[test]
     fn test_open_merge_request_with_no_assignee() {
         let assignee = Member::default();
@@ -580,6 +1717,23 @@ mod test {
         assert!(!actual_body.contains("assignee_id"));
     }
 
+    #[test]
+    fn test_open_merge_request_with_labels() {
+        let mr_args = MergeRequestBodyArgs::builder()
+            .labels(vec!["cache".to_string(), "rust".to_string()])
+            .build()
+            .unwrap();
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            201,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        assert!(gitlab.open(mr_args).is_ok());
+        let actual_body = client.request_body.borrow();
+        assert!(actual_body.contains("\"labels\":\"cache,rust\""));
+    }
+
     #[test]
     fn test_open_merge_request_target_repo() {
         // current repo, targeting jordilin/gitar
@@ -743,81 +1897,329 @@ mod test {
             .assignee(None)
             .build()
             .unwrap();
-        assert_eq!(None, gitlab.num_pages(body_args).unwrap());
+        assert_eq!(None, gitlab.num_pages(body_args).unwrap());
+    }
+
+    #[test]
+    fn test_gitlab_create_merge_request_comment_ok() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(201, None, None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        let comment_args = CommentMergeRequestBodyArgs::builder()
+            .id(1456)
+            .comment("LGTM, ship it".to_string())
+            .build()
+            .unwrap();
+        gitlab.create(comment_args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/1456/notes",
+            *client.url()
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_gitlab_create_merge_request_comment_error() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(400, None, None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        let comment_args = CommentMergeRequestBodyArgs::builder()
+            .id(1456)
+            .comment("LGTM, ship it".to_string())
+            .build()
+            .unwrap();
+        assert!(gitlab.create(comment_args).is_err());
+    }
+
+    #[test]
+    fn test_get_gitlab_merge_request_details() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let merge_request_id = 123456;
+        let merge_request = gitlab.get(merge_request_id).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123456",
+            *client.url()
+        );
+        assert!(!merge_request.from_fork);
+        assert_eq!(
+            format!("refs/merge-requests/{}/head", merge_request.id),
+            merge_request.head_ref
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_get_gitlab_merge_request_from_fork() {
+        let mut merge_request: serde_json::Value =
+            serde_json::from_str(&get_contract(ContractType::Gitlab, "merge_request.json"))
+                .unwrap();
+        merge_request["source_project_id"] = serde_json::json!(1234);
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(merge_request.to_string()),
+            None,
+        );
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        assert!(gitlab.get(33).unwrap().from_fork);
+    }
+
+    #[test]
+    fn test_merge_merge_request() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(33)
+            .message(Some("Release 1.2".to_string()))
+            .sha(Some("9c5bf00e5e1d6e9e96cda3fa3986f680fcdbcd7f".to_string()))
+            .build()
+            .unwrap();
+        let response = gitlab.merge(args).unwrap();
+        assert_eq!(
+            "9c5bf00e5e1d6e9e96cda3fa3986f680fcdbcd7f",
+            response.head_sha
+        );
+        let body = client.request_body();
+        assert!(body.contains("\"merge_commit_message\":\"Release 1.2\""));
+        assert!(body.contains("\"sha\":\"9c5bf00e5e1d6e9e96cda3fa3986f680fcdbcd7f\""));
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/merge",
+            *client.url()
+        );
+        assert_eq!(
+            Some(ApiOperation::MergeRequest),
+            *client.api_operation.borrow()
+        );
+    }
+
+    #[test]
+    fn test_merge_merge_request_when_pipeline_succeeds() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(33)
+            .auto_merge(true)
+            .build()
+            .unwrap();
+        let response = gitlab.merge(args).unwrap();
+        let body = client.request_body();
+        assert!(body.contains("\"merge_when_pipeline_succeeds\":\"true\""));
+        assert!(body.contains("\"auto_merge\":\"true\""));
+        assert!(response.merged_at.is_none());
+    }
+
+    #[test]
+    fn test_merge_merge_request_squash_and_remove_source_branch() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(33)
+            .squash(true)
+            .delete_source_branch(true)
+            .build()
+            .unwrap();
+        gitlab.merge(args).unwrap();
+        let body = client.request_body();
+        assert!(body.contains("\"squash\":\"true\""));
+        assert!(body.contains("\"should_remove_source_branch\":\"true\""));
+    }
+
+    #[test]
+    fn test_merge_merge_request_squash_commit_message() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(33)
+            .message(Some("Add feature".to_string()))
+            .method(Some(MergeMethod::Squash))
+            .build()
+            .unwrap();
+        gitlab.merge(args).unwrap();
+        let body = client.request_body();
+        assert!(body.contains("\"squash\":\"true\""));
+        assert!(body.contains("\"squash_commit_message\":\"Add feature\""));
+        assert!(body.contains("\"merge_commit_message\":\"Add feature\""));
     }
 
     #[test]
-    fn test_gitlab_create_merge_request_comment_ok() {
-        let contracts =
-            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(201, None, None);
-        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
-        let comment_args = CommentMergeRequestBodyArgs::builder()
-            .id(1456)
-            .comment("LGTM, ship it".to_string())
+    fn test_merge_merge_request_rebase_method_is_not_supported() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
+        let args = MergeRequestMergeBodyArgs::builder()
+            .id(33)
+            .method(Some(MergeMethod::Rebase))
             .build()
             .unwrap();
-        gitlab.create(comment_args).unwrap();
-        assert_eq!(
-            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/1456/notes",
-            *client.url()
+        assert!(gitlab.merge(args).is_err());
+    }
+
+    #[test]
+    fn test_rebase_merge_request() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            202,
+            Some(r#"{"rebase_in_progress": true}"#),
+            None,
         );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestRebase);
+        let status = gitlab.rebase(33).unwrap();
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/rebase",
+            *client.url(),
         );
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+        assert!(status.in_progress);
     }
 
     #[test]
-    fn test_gitlab_create_merge_request_comment_error() {
-        let contracts =
-            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(400, None, None);
-        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
-        let comment_args = CommentMergeRequestBodyArgs::builder()
-            .id(1456)
-            .comment("LGTM, ship it".to_string())
+    fn test_rebase_status_finished() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestRebase);
+        let started = RebaseStatus::builder()
+            .id(33)
+            .in_progress(true)
             .build()
             .unwrap();
-        assert!(gitlab.create(comment_args).is_err());
+        let status = gitlab.rebase_status(&started).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33?include_rebase_in_progress=true",
+            *client.url(),
+        );
+        assert!(!status.in_progress);
+        assert_eq!("9c5bf00e5e1d6e9e96cda3fa3986f680fcdbcd7f", status.sha);
+        assert!(status.error.is_none());
     }
 
     #[test]
-    fn test_get_gitlab_merge_request_details() {
-        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+    fn test_rebase_status_failed_with_merge_error() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
             200,
-            "merge_request.json",
+            Some(r#"{"iid": 33, "rebase_in_progress": false, "sha": "abc", "merge_error": "Rebase failed: Rebase locally, resolve all conflicts, then push the branch."}"#),
             None,
         );
-        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
-        let merge_request_id = 123456;
-        gitlab.get(merge_request_id).unwrap();
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestRebase);
+        let started = RebaseStatus::builder().id(33).build().unwrap();
+        let status = gitlab.rebase_status(&started).unwrap();
+        assert!(status.error.unwrap().starts_with("Rebase failed"));
+    }
+
+    #[test]
+    fn test_add_merge_request_dependency() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body::<String>(201, None, None)
+            .add_contract(200, "merge_request.json", None);
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestDependency);
+        gitlab.add_dependency(34, 33).unwrap();
         assert_eq!(
-            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123456",
-            *client.url()
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/34/blocks",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[1]);
+        assert!(client
+            .request_body()
+            .contains("\"blocking_merge_request_id\":\"281284377\""));
+    }
+
+    #[test]
+    fn test_list_merge_request_dependencies() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request_blockers.json",
+            None,
         );
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestDependency);
+        let dependencies = gitlab.list_dependencies(33).unwrap();
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/blockers",
+            *client.url(),
         );
+        assert_eq!(2, dependencies.len());
+        assert_eq!(31, dependencies[0].id);
+        assert!(dependencies[0].merged_at.is_some());
+        assert_eq!(32, dependencies[1].id);
+        assert!(dependencies[1].merged_at.is_none());
     }
 
     #[test]
-    fn test_merge_merge_request() {
+    fn test_list_changes() {
         let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
             200,
-            "merge_request.json",
+            "merge_request_changes.json",
             None,
         );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestDiff);
+        let changes = gitlab.list_changes(33).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/changes",
+            *client.url()
+        );
+        assert_eq!(2, changes.len());
+        assert_eq!("src/main.rs", changes[0].path);
+        assert_eq!(FileChangeStatus::Modified, changes[0].status);
+        assert_eq!((2, 1), (changes[0].additions, changes[0].deletions));
+        assert_eq!(FileChangeStatus::Added, changes[1].status);
+        assert_eq!("@@ -0,0 +1 @@\n+# Introduction\n", changes[1].patch);
+    }
+
+    #[test]
+    fn test_toggle_draft_prefixes_title() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "merge_request.json", None)
+            .add_contract(200, "merge_request.json", None);
         let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequest);
-        let merge_request_id = 33;
-        gitlab.merge(merge_request_id).unwrap();
+        gitlab.toggle_draft(33, true).unwrap();
         assert_eq!(
-            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/merge",
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33",
             *client.url()
         );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("Draft: New Feature", body["title"]);
         assert_eq!(
-            Some(ApiOperation::MergeRequest),
-            *client.api_operation.borrow()
+            vec![http::Method::GET, http::Method::PUT],
+            *client.http_method.borrow()
+        );
+    }
+
+    #[test]
+    fn test_draft_title() {
+        assert_eq!("New Feature", draft_title("Draft: New Feature", false));
+        assert_eq!("New Feature", draft_title("[draft] New Feature", false));
+        assert_eq!(
+            "Draft: New Feature",
+            draft_title("(Draft) New Feature", true)
         );
+        assert_eq!("New Feature", draft_title("New Feature", false));
     }
 
     #[test]
@@ -906,6 +2308,348 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_list_merge_request_comments_with_reactions() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body(
+                200,
+                Some(r#"[{"name": "thumbsup"}, {"name": "tada"}, {"name": "thumbsup"}]"#),
+                None,
+            )
+            .add_body(
+                200,
+                Some(format!(
+                    "[{}]",
+                    get_contract(ContractType::Gitlab, "comment.json")
+                )),
+                None,
+            );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestListBodyArgs::builder()
+            .id(123)
+            .list_args(None)
+            .reactions(true)
+            .build()
+            .unwrap();
+        let comments = gitlab.list(args).unwrap();
+        assert_eq!(
+            vec![Reaction::new("thumbsup", 2), Reaction::new("tada", 1)],
+            comments[0].reactions
+        );
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/notes/301/award_emoji?per_page=100",
+            *client.url()
+        );
+    }
+
+    #[test]
+    fn test_suggest_change_creates_diff_thread() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body(201, Some("{}"), None)
+            .add_contract(200, "merge_request.json", None);
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestSuggestion);
+        let args = SuggestionBodyArgs::builder()
+            .id(33)
+            .file("src/main.rs".to_string())
+            .line(10)
+            .replacement("let x = 1;".to_string())
+            .build()
+            .unwrap();
+        gitlab.suggest(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/discussions",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("```suggestion:-0+0\nlet x = 1;\n```", body["body"]);
+        assert_eq!(10, body["position"]["new_line"]);
+        assert_eq!("src/main.rs", body["position"]["new_path"]);
+        assert_eq!(
+            "9c5bf00e5e1d6e9e96cda3fa3986f680fcdbcd7f",
+            body["position"]["head_sha"]
+        );
+    }
+
+    #[test]
+    fn test_list_pending_suggestions() {
+        let discussions = r#"[{
+            "id": "6a9c1750b37d513a43987b574953fceb50b03ce7",
+            "notes": [{
+                "id": 301,
+                "author": {"username": "tom"},
+                "position": {"new_path": "src/main.rs", "new_line": 10},
+                "suggestions": [
+                    {"id": 5, "from_line": 10, "to_line": 10, "appliable": true, "applied": false, "to_content": "let x = 1;\n"},
+                    {"id": 6, "from_line": 12, "to_line": 12, "appliable": false, "applied": true, "to_content": "let y = 2;\n"}
+                ]
+            }]
+        }]"#;
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some(discussions), None);
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestSuggestion);
+        let suggestions = gitlab.list_suggestions(33).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/discussions?per_page=100&page=1",
+            *client.url()
+        );
+        assert_eq!(
+            vec![Suggestion::builder()
+                .id(5)
+                .file("src/main.rs".to_string())
+                .line(10)
+                .author("tom".to_string())
+                .replacement("let x = 1;".to_string())
+                .build()
+                .unwrap()],
+            suggestions
+        );
+    }
+
+    #[test]
+    fn test_list_discussions_skips_system_notes() {
+        let discussions = r#"[{
+            "id": "6a9c1750b37d513a43987b574953fceb50b03ce7",
+            "notes": [{
+                "id": 301,
+                "system": false,
+                "body": "Should this be a constant?",
+                "author": {"username": "tom"},
+                "created_at": "2024-01-15T10:30:00.000Z",
+                "resolved": true,
+                "position": {"new_path": "src/main.rs", "new_line": 10}
+            }, {
+                "id": 302,
+                "system": false,
+                "body": "Done",
+                "author": {"username": "ana"},
+                "created_at": "2024-01-15T11:30:00.000Z",
+                "resolved": true
+            }]
+        }, {
+            "id": "8b0d22",
+            "notes": [{"id": 303, "system": true, "body": "added 1 commit"}]
+        }]"#;
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some(discussions), None);
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestDiscussion);
+        let discussions = gitlab.list_discussions(33).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/discussions?per_page=100&page=1",
+            *client.url()
+        );
+        assert_eq!(1, discussions.len());
+        let discussion = &discussions[0];
+        assert_eq!("6a9c1750b37d513a43987b574953fceb50b03ce7", discussion.id);
+        assert_eq!("src/main.rs", discussion.file);
+        assert_eq!(Some(10), discussion.line);
+        assert!(discussion.resolved);
+        assert_eq!(
+            vec![(301, "tom"), (302, "ana")],
+            discussion
+                .notes
+                .iter()
+                .map(|note| (note.id, note.author.as_str()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_reply_to_discussion() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(201, Some("{}"), None);
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestDiscussion);
+        let args = DiscussionReplyBodyArgs::builder()
+            .id(33)
+            .discussion_id("6a9c17".to_string())
+            .body("Done".to_string())
+            .build()
+            .unwrap();
+        gitlab.reply(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/discussions/6a9c17/notes",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("Done", body["body"]);
+    }
+
+    #[test]
+    fn test_submit_review_publishes_draft_notes() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body::<String>(204, None, None)
+            .add_body(200, Some("{}"), None)
+            .add_body(200, Some("{}"), None)
+            .add_contract(200, "merge_request.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestReview);
+        let mut review = ReviewFile::new(33, "Add offline reviews", "9c5bf00");
+        review.body = "Looks good overall".to_string();
+        review.comments.push(ReviewComment {
+            file: "src/main.rs".to_string(),
+            line: 10,
+            body: "Handle the error".to_string(),
+        });
+        gitlab.submit_review(review).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/draft_notes/bulk_publish",
+            *client.url()
+        );
+        assert_eq!(
+            vec![
+                http::Method::GET,
+                http::Method::POST,
+                http::Method::POST,
+                http::Method::POST
+            ],
+            *client.http_method.borrow()
+        );
+    }
+
+    #[test]
+    fn test_review_request_changes_when_not_approved() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(201, "comment.json", None)
+            .add_body::<String>(404, None, None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ReviewMergeRequest);
+        let args = ReviewBodyArgs::builder()
+            .id(33)
+            .event(ReviewEvent::RequestChanges)
+            .body("Needs tests".to_string())
+            .build()
+            .unwrap();
+        gitlab.review(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/notes",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!("Needs tests", body["body"]);
+        assert_eq!(
+            vec![http::Method::POST, http::Method::POST],
+            *client.http_method.borrow()
+        );
+    }
+
+    #[test]
+    fn test_review_approve_without_body_does_not_add_note() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(201, Some("{}"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ReviewMergeRequest);
+        let args = ReviewBodyArgs::builder()
+            .id(33)
+            .event(ReviewEvent::Approve)
+            .build()
+            .unwrap();
+        gitlab.review(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/33/approve",
+            *client.url()
+        );
+        assert_eq!(1, client.http_method.borrow().len());
+    }
+
+    #[test]
+    fn test_apply_suggestions_in_batch() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(200, Some("[]"), None);
+        let (client, gitlab) =
+            setup_client!(contracts, default_gitlab(), dyn MergeRequestSuggestion);
+        gitlab
+            .apply_suggestions(&[5, 6], Some("Apply review suggestions".to_string()))
+            .unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/suggestions/batch_apply",
+            *client.url()
+        );
+        let body: serde_json::Value = serde_json::from_str(&client.request_body()).unwrap();
+        assert_eq!(serde_json::json!([5, 6]), body["ids"]);
+        assert_eq!("Apply review suggestions", body["commit_message"]);
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_update_merge_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "comment.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestBodyArgs::builder()
+            .id(123)
+            .comment("LGTM!".to_string())
+            .build()
+            .unwrap();
+        gitlab.update(301, args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/notes/301",
+            *client.url()
+        );
+        assert!(client.request_body().contains("LGTM!"));
+        assert_eq!(http::Method::PUT, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_delete_merge_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(204, None, None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        gitlab.delete(123, 301).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/notes/301",
+            *client.url()
+        );
+        assert_eq!(http::Method::DELETE, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_react_to_merge_request_comment() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body(201, Some("{}"), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeRequestReaction);
+        let args = ReactionBodyArgs::builder()
+            .id(123)
+            .comment_id(Some(301))
+            .emoji("thumbsup".to_string())
+            .build()
+            .unwrap();
+        gitlab.react(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/notes/301/award_emoji",
+            *client.url()
+        );
+        assert!(client.request_body().contains("thumbsup"));
+        assert_eq!(
+            http::Method::POST,
+            client.http_method.borrow_mut().pop().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_list_merge_request_comments_unexpected_payload_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(r#"[{"id": 1, "body": "LGTM", "created_at": "2024-03-03T09:00:00Z"}]"#),
+            None,
+        );
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn CommentMergeRequest);
+        let args = CommentMergeRequestListBodyArgs::builder()
+            .id(123)
+            .list_args(None)
+            .build()
+            .unwrap();
+        let err = match gitlab.list(args) {
+            Err(err) => err,
+            Ok(_) => panic!("Expected contract error"),
+        };
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::RemoteUnexpectedResponseContract(msg)) => {
+                assert!(msg.ends_with("missing fields: author"))
+            }
+            _ => panic!("Expected GRError::RemoteUnexpectedResponseContract"),
+        }
+    }
+
     #[test]
     fn test_merge_request_comments_num_pages() {
         let link_header = "<https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/notes?page=1>; rel=\"next\", <https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/123/notes?page=2>; rel=\"last\"";
@@ -932,4 +2676,56 @@ mod test {
             *client.api_operation.borrow()
         );
     }
+
+    #[test]
+    fn test_merge_train_status_position_and_eta() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "list_merge_trains.json", None)
+            .add_contract(200, "list_merge_trains.json", None)
+            .add_contract(200, "merge_train_car.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeQueue);
+        let entry = gitlab.status(23).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_trains?target_branch=main&scope=complete&sort=asc&per_page=100",
+            *client.url()
+        );
+        assert_eq!(23, entry.id);
+        assert_eq!(Some(2), entry.position);
+        assert_eq!("fresh", entry.status);
+        assert_eq!("main", entry.target_branch);
+        // Average of completed cars (450s) times the position in the train.
+        assert_eq!(Some(Seconds::new(900)), entry.eta);
+    }
+
+    #[test]
+    fn test_merge_train_add_returns_position() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            201,
+            "list_merge_trains.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeQueue);
+        let entry = gitlab.add(23).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_trains/merge_requests/23",
+            *client.url()
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[0]);
+        assert_eq!(Some(2), entry.position);
+    }
+
+    #[test]
+    fn test_merge_train_remove_cancels_auto_merge() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "merge_request.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn MergeQueue);
+        gitlab.remove(23).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/merge_requests/23/cancel_merge_when_pipeline_succeeds",
+            *client.url()
+        );
+    }
 }
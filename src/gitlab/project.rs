@@ -1,38 +1,37 @@
-use crate::api_traits::{ApiOperation, ProjectMember, RemoteProject, RemoteTag};
+use crate::api_traits::{
+    ApiOperation, ProjectAccessCheck, ProjectBlame, ProjectDependency, ProjectInvitation,
+    ProjectLanguages, ProjectMember, ProjectMemberAccess, ProjectMirror, RemoteProject, RemoteTag,
+    UserInfo,
+};
 use crate::cli::browse::BrowseOptions;
-use crate::cmds::project::{Member, Project, ProjectListBodyArgs, Tag};
+use crate::cmds::project::{
+    AccessGrant, BlameLine, Dependency, Invitation, Member, MemberAccessLevel, Membership, Mirror,
+    MirrorKind, Project, ProjectAccess, ProjectBlameBodyArgs, ProjectLanguage, ProjectListBodyArgs,
+    Tag,
+};
+use crate::cmds::user::UserCliArgs;
 use crate::error::GRError;
 use crate::gitlab::encode_path;
+use crate::http::{self, Body};
 use crate::io::{CmdInfo, HttpResponse, HttpRunner};
-use crate::remote::query;
-use crate::remote::URLQueryParamBuilder;
-use crate::Result;
+use crate::json_loads;
+use crate::remote::{encode_query_param, query};
+use crate::remote::{GetRemoteCliArgs, URLQueryParamBuilder};
+use crate::{time, Error, Result};
 
 use super::Gitlab;
 
 impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Gitlab<R> {
     fn get_project_data(&self, id: Option<i64>, path: Option<&str>) -> Result<CmdInfo> {
-        let url = match (id, path) {
-            (Some(id), None) => format!("{}/{}", self.base_project_url, id),
-            (None, Some(path)) => {
-                format!("{}/{}", self.base_project_url, encode_path(path))
-            }
-            (None, None) => self.rest_api_basepath().to_string(),
-            (Some(_), Some(_)) => {
-                return Err(GRError::ApplicationError(
-                    "Invalid arguments, can only get project data by id or by owner/repo path"
-                        .to_string(),
-                )
-                .into());
-            }
-        };
+        // License is only included on request.
+        let url = format!("{}?license=true", self.project_url(id, path)?);
         let project = query::get::<_, (), _>(
             &self.runner,
             &url,
             None,
             self.headers(),
             ApiOperation::Project,
-            |value| GitlabProjectFields::from(value).into(),
+            |value| Ok(GitlabProjectFields::try_from(value)?.into()),
         )?;
         Ok(CmdInfo::Project(project))
     }
@@ -46,7 +45,7 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::Project,
-            |value| GitlabMemberFields::from(value).into(),
+            |value| Ok(GitlabMemberFields::try_from(value)?.into()),
         )?;
         Ok(CmdInfo::Members(members))
     }
@@ -60,6 +59,21 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Gitlab<R> {
             BrowseOptions::Pipelines => format!("{}/pipelines", base_url),
             BrowseOptions::PipelineId(id) => format!("{}/-/pipelines/{}", base_url, id),
             BrowseOptions::Releases => format!("{}/-/releases", base_url),
+            BrowseOptions::NewMergeRequest {
+                source_branch,
+                target_branch,
+                title,
+                description,
+            } => format!(
+                "{}/-/merge_requests/new?merge_request[source_branch]={}\
+                 &merge_request[target_branch]={}&merge_request[title]={}\
+                 &merge_request[description]={}",
+                base_url,
+                encode_query_param(&source_branch),
+                encode_query_param(&target_branch),
+                encode_query_param(&title),
+                encode_query_param(&description)
+            ),
             // Manual is only one URL and it's the user guide. Handled in the
             // browser command.
             BrowseOptions::Manual => unreachable!(),
@@ -75,7 +89,7 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteProject for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::Project,
-            |value| GitlabProjectFields::from(value).into(),
+            |value| Ok(GitlabProjectFields::try_from(value)?.into()),
         )?;
         Ok(projects)
     }
@@ -105,7 +119,7 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteTag for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::RepositoryTag,
-            |value| GitlabProjectTagFields::from(value).into(),
+            |value| Ok(GitlabProjectTagFields::try_from(value)?.into()),
         )?;
         Ok(tags)
     }
@@ -118,7 +132,7 @@ impl<R: HttpRunner<Response = HttpResponse>> RemoteTag for Gitlab<R> {
 
 impl<R: HttpRunner<Response = HttpResponse>> ProjectMember for Gitlab<R> {
     fn list(&self, args: ProjectListBodyArgs) -> Result<Vec<Member>> {
-        let url = format!("{}/members/all", self.rest_api_basepath());
+        let url = self.members_url(&args);
         let members = query::paged(
             &self.runner,
             &url,
@@ -126,18 +140,569 @@ impl<R: HttpRunner<Response = HttpResponse>> ProjectMember for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::Project,
-            |value| GitlabMemberFields::from(value).into(),
+            |value| Ok(GitlabMemberFields::try_from(value)?.into()),
         )?;
         Ok(members)
     }
 }
 
+impl<R: HttpRunner<Response = HttpResponse>> ProjectMemberAccess for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/members.html#add-a-member-to-a-group-or-project
+    fn add_member(&self, username: &str, access: MemberAccessLevel) -> Result<Membership> {
+        let mut body = Body::new();
+        body.add("user_id", self.user_id(username)?);
+        body.add("access_level", gitlab_access_level(access));
+        query::send(
+            &self.runner,
+            &format!("{}/members", self.rest_api_basepath()),
+            Some(&body),
+            self.headers(),
+            ApiOperation::Project,
+            |value| Ok(GitlabMembershipFields::from(value).into()),
+            http::Method::POST,
+        )
+    }
+
+    // https://docs.gitlab.com/ee/api/members.html#edit-a-member-of-a-group-or-project
+    fn update_member(&self, username: &str, access: MemberAccessLevel) -> Result<Membership> {
+        let mut body = Body::new();
+        body.add("access_level", gitlab_access_level(access));
+        query::send(
+            &self.runner,
+            &format!(
+                "{}/members/{}",
+                self.rest_api_basepath(),
+                self.user_id(username)?
+            ),
+            Some(&body),
+            self.headers(),
+            ApiOperation::Project,
+            |value| Ok(GitlabMembershipFields::from(value).into()),
+            http::Method::PUT,
+        )
+    }
+
+    // https://docs.gitlab.com/ee/api/members.html#remove-a-member-from-a-group-or-project
+    fn remove_member(&self, username: &str) -> Result<()> {
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &format!(
+                "{}/members/{}",
+                self.rest_api_basepath(),
+                self.user_id(username)?
+            ),
+            None,
+            self.headers(),
+            ApiOperation::Project,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.gitlab.com/ee/api/users.html#block-user
+    fn block_member(&self, username: &str) -> Result<()> {
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &format!("{}/{}/block", self.base_users_url, self.user_id(username)?),
+            None,
+            self.headers(),
+            ApiOperation::Project,
+            http::Method::POST,
+        )?;
+        Ok(())
+    }
+
+    // https://docs.gitlab.com/ee/api/invitations.html#add-a-member-to-a-group-or-project
+    fn invite_member(&self, email: &str, access: MemberAccessLevel) -> Result<Membership> {
+        let mut body = Body::new();
+        body.add("email", serde_json::json!(email));
+        body.add(
+            "access_level",
+            serde_json::json!(gitlab_access_level(access)),
+        );
+        let response = query::send_json(
+            &self.runner,
+            &format!("{}/invitations", self.rest_api_basepath()),
+            Some(&body),
+            self.headers(),
+            ApiOperation::Project,
+            http::Method::POST,
+        )?;
+        // Gitlab answers 201 even if the invitation could not be sent, ex.
+        // the email has already been invited or is a member already.
+        if response["status"] == "error" {
+            return Err(GRError::PreconditionNotMet(format!(
+                "Could not invite {}: {}",
+                email, response["message"]
+            ))
+            .into());
+        }
+        Ok(Membership::builder()
+            .username(email.to_string())
+            .access(access)
+            .state("invited".to_string())
+            .build()
+            .unwrap())
+    }
+}
+
+/// Access comes from a direct membership, from the groups the project belongs
+/// to and from the groups the project has been shared with. The latter are
+/// capped at the access the project was shared with.
+impl<R: HttpRunner<Response = HttpResponse>> ProjectAccessCheck for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/members.html#get-a-member-of-a-group-or-project
+    fn access_check(&self, username: &str) -> Result<ProjectAccess> {
+        let user_id = self.user_id(username)?;
+        let mut grants = Vec::new();
+        let direct = format!("{}/members/{}", self.rest_api_basepath(), user_id);
+        if let Some(member) = self.get_if_found(&direct)? {
+            grants.push(access_grant(
+                "direct member".to_string(),
+                member["access_level"].as_i64().unwrap_or_default(),
+            ));
+        }
+        // Parent groups, from the top level one. Personal namespaces are not
+        // groups and are not found.
+        let namespaces = self.path.split('/').collect::<Vec<_>>();
+        for depth in 1..namespaces.len() {
+            let group = namespaces[..depth].join("/");
+            let url = format!(
+                "{}/{}/members/{}",
+                self.base_groups_url,
+                encode_path(&group),
+                user_id
+            );
+            if let Some(member) = self.get_if_found(&url)? {
+                grants.push(access_grant(
+                    format!("group {}", group),
+                    member["access_level"].as_i64().unwrap_or_default(),
+                ));
+            }
+        }
+        let project = query::get_json::<_, ()>(
+            &self.runner,
+            self.rest_api_basepath(),
+            None,
+            self.headers(),
+            ApiOperation::Project,
+        )?;
+        for shared in project["shared_with_groups"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+        {
+            let url = format!(
+                "{}/{}/members/all/{}",
+                self.base_groups_url,
+                shared["group_id"].as_i64().unwrap_or_default(),
+                user_id
+            );
+            if let Some(member) = self.get_if_found(&url)? {
+                let level = member["access_level"].as_i64().unwrap_or_default();
+                let shared_level = shared["group_access_level"].as_i64().unwrap_or_default();
+                grants.push(access_grant(
+                    format!(
+                        "shared with group {}",
+                        shared["group_full_path"].as_str().unwrap_or_default()
+                    ),
+                    level.min(shared_level),
+                ));
+            }
+        }
+        Ok(ProjectAccess::builder()
+            .username(username.to_string())
+            .grants(grants)
+            .build()
+            .unwrap())
+    }
+}
+
+fn access_grant(source: String, level: i64) -> AccessGrant {
+    AccessGrant::builder()
+        .source(source)
+        .access(member_access_level(level))
+        .build()
+        .unwrap()
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ProjectInvitation for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/invitations.html#list-all-invitations-pending-for-a-group-or-project
+    fn list_invitations(&self) -> Result<Vec<Invitation>> {
+        let invitations = query::get_json::<_, ()>(
+            &self.runner,
+            &format!("{}/invitations?per_page=100", self.rest_api_basepath()),
+            None,
+            self.headers(),
+            ApiOperation::Project,
+        )?;
+        invitations
+            .as_array()
+            .map(|invitations| {
+                invitations
+                    .iter()
+                    .map(|invitation| {
+                        Ok(Invitation::builder()
+                            .invitee(
+                                invitation["invite_email"]
+                                    .as_str()
+                                    .unwrap_or_default()
+                                    .to_string(),
+                            )
+                            .access(member_access_level(
+                                invitation["access_level"].as_i64().unwrap_or_default(),
+                            ))
+                            .invited_by(
+                                invitation["created_by_name"]
+                                    .as_str()
+                                    .unwrap_or_default()
+                                    .to_string(),
+                            )
+                            .created_at(time::json_optional_timestamp(invitation, "created_at")?)
+                            .expires_at(time::json_optional_timestamp(invitation, "expires_at")?)
+                            .build()
+                            .unwrap())
+                    })
+                    .collect()
+            })
+            .unwrap_or(Ok(Vec::new()))
+    }
+
+    // https://docs.gitlab.com/ee/api/invitations.html#delete-an-invitation-to-a-group-or-project
+    fn revoke_invitation(&self, invitee: &str) -> Result<()> {
+        query::send_raw::<_, ()>(
+            &self.runner,
+            &format!(
+                "{}/invitations/{}",
+                self.rest_api_basepath(),
+                encode_query_param(invitee)
+            ),
+            None,
+            self.headers(),
+            ApiOperation::Project,
+            http::Method::DELETE,
+        )?;
+        Ok(())
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    /// Members are managed by user ID.
+    fn user_id(&self, username: &str) -> Result<i64> {
+        let args = UserCliArgs::builder()
+            .username(username.to_string())
+            .get_args(GetRemoteCliArgs::default())
+            .build()
+            .unwrap();
+        Ok(UserInfo::get(self, &args)?.id)
+    }
+
+    /// Gets a resource that might not exist, ex. the membership of a user.
+    fn get_if_found(&self, url: &str) -> Result<Option<serde_json::Value>> {
+        let response = query::send_raw_unchecked::<_, ()>(
+            &self.runner,
+            url,
+            None,
+            self.headers(),
+            ApiOperation::Project,
+            http::Method::GET,
+        )?;
+        if response.status == 404 {
+            return Ok(None);
+        }
+        if !response.is_ok(&http::Method::GET) {
+            return Err(query::request_error(
+                &http::Method::GET,
+                url,
+                &ApiOperation::Project,
+                &response,
+            ));
+        }
+        Ok(Some(json_loads(&response.body)?))
+    }
+}
+
+fn gitlab_access_level(access: MemberAccessLevel) -> i64 {
+    match access {
+        MemberAccessLevel::Guest => 10,
+        MemberAccessLevel::Reporter => 20,
+        MemberAccessLevel::Developer => 30,
+        MemberAccessLevel::Maintainer => 40,
+        MemberAccessLevel::Owner => 50,
+    }
+}
+
+fn member_access_level(level: i64) -> MemberAccessLevel {
+    match level {
+        level if level >= 50 => MemberAccessLevel::Owner,
+        level if level >= 40 => MemberAccessLevel::Maintainer,
+        level if level >= 30 => MemberAccessLevel::Developer,
+        level if level >= 20 => MemberAccessLevel::Reporter,
+        _ => MemberAccessLevel::Guest,
+    }
+}
+
+pub struct GitlabMembershipFields {
+    membership: Membership,
+}
+
+impl From<&serde_json::Value> for GitlabMembershipFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let access = member_access_level(data["access_level"].as_i64().unwrap_or_default());
+        GitlabMembershipFields {
+            membership: Membership::builder()
+                .username(data["username"].as_str().unwrap_or_default().to_string())
+                .access(access)
+                // Users awaiting approval of an administrator or a seat are
+                // `awaiting`.
+                .state(
+                    data["membership_state"]
+                        .as_str()
+                        .unwrap_or("active")
+                        .to_string(),
+                )
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabMembershipFields> for Membership {
+    fn from(fields: GitlabMembershipFields) -> Self {
+        fields.membership
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ProjectBlame for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/repository_files.html#get-file-blame-from-repository
+    fn blame(&self, args: ProjectBlameBodyArgs) -> Result<Vec<BlameLine>> {
+        let mut url = URLQueryParamBuilder::new(&format!(
+            "{}/repository/files/{}/blame",
+            self.rest_api_basepath(),
+            encode_query_param(&args.path)
+        ));
+        url.add_param("ref", &encode_query_param(&args.git_ref));
+        let mut line_number = 1;
+        if let Some((start, end)) = args.range {
+            url.add_param("range[start]", &start.to_string())
+                .add_param("range[end]", &end.to_string());
+            line_number = start;
+        }
+        let blame = query::get_json::<_, ()>(
+            &self.runner,
+            &url.build(),
+            None,
+            self.headers(),
+            ApiOperation::Project,
+        )?;
+        let ranges = blame.as_array().ok_or_else(|| {
+            GRError::RemoteUnexpectedResponseContract(format!(
+                "Expected an array of blame ranges but got: {}",
+                blame
+            ))
+        })?;
+        // Each range is a commit with the consecutive lines it last modified.
+        let mut lines = Vec::new();
+        for range in ranges {
+            let commit = &range["commit"];
+            for content in range["lines"].as_array().unwrap_or(&Vec::new()) {
+                lines.push(
+                    BlameLine::builder()
+                        .line(line_number)
+                        .commit(
+                            commit["id"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .chars()
+                                .take(12)
+                                .collect(),
+                        )
+                        .author(
+                            commit["author_name"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                        )
+                        .date(
+                            commit["authored_date"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                        )
+                        .content(content.as_str().unwrap_or_default().to_string())
+                        .build()
+                        .unwrap(),
+                );
+                line_number += 1;
+            }
+        }
+        Ok(lines)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ProjectLanguages for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/projects.html#languages
+    fn languages(&self, id: Option<i64>, path: Option<&str>) -> Result<Vec<ProjectLanguage>> {
+        let url = format!("{}/languages", self.project_url(id, path)?);
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Project,
+            |value| {
+                Ok(value
+                    .as_object()
+                    .map(|languages| {
+                        languages
+                            .iter()
+                            .map(|(name, percentage)| {
+                                ProjectLanguage::new(name, percentage.as_f64().unwrap_or_default())
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default())
+            },
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    fn project_url(&self, id: Option<i64>, path: Option<&str>) -> Result<String> {
+        match (id, path) {
+            (Some(id), None) => Ok(format!("{}/{}", self.base_project_url, id)),
+            (None, Some(path)) => Ok(format!("{}/{}", self.base_project_url, encode_path(path))),
+            (None, None) => Ok(self.rest_api_basepath().to_string()),
+            (Some(_), Some(_)) => Err(GRError::ApplicationError(
+                "Invalid arguments, can only get project data by id or by owner/repo path"
+                    .to_string(),
+            )
+            .into()),
+        }
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ProjectDependency for Gitlab<R> {
+    // https://docs.gitlab.com/ee/api/dependencies.html
+    fn list_dependencies(&self) -> Result<Vec<Dependency>> {
+        let url = format!("{}/dependencies", self.rest_api_basepath());
+        query::paged(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            None,
+            ApiOperation::Project,
+            |value| Ok(GitlabDependencyFields::from(value).into()),
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> ProjectMirror for Gitlab<R> {
+    fn list_mirrors(&self) -> Result<Vec<Mirror>> {
+        let mut mirrors = Vec::new();
+        if let Some(mirror) = self.get_pull_mirror()? {
+            mirrors.push(mirror);
+        }
+        // https://docs.gitlab.com/ee/api/remote_mirrors.html#list-a-projects-remote-mirrors
+        let url = format!("{}/remote_mirrors", self.rest_api_basepath());
+        let push_mirrors = query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Project,
+            |value| {
+                value
+                    .as_array()
+                    .unwrap_or(&Vec::new())
+                    .iter()
+                    .map(|mirror| Ok(GitlabMirrorFields::new(MirrorKind::Push, mirror)?.into()))
+                    .collect::<Result<Vec<Mirror>>>()
+            },
+        )?;
+        mirrors.extend(push_mirrors);
+        Ok(mirrors)
+    }
+
+    fn sync_mirrors(&self) -> Result<Vec<Mirror>> {
+        let mirrors = self
+            .list_mirrors()?
+            .into_iter()
+            .filter(|mirror| mirror.enabled)
+            .collect::<Vec<Mirror>>();
+        if mirrors.is_empty() {
+            return Err(GRError::PreconditionNotMet(
+                "No enabled mirrors found for the project".to_string(),
+            )
+            .into());
+        }
+        for mirror in mirrors.iter() {
+            let url = match mirror.kind {
+                // https://docs.gitlab.com/ee/api/project_pull_mirroring.html#start-the-pull-mirroring-process-for-a-project
+                MirrorKind::Pull => format!("{}/mirror/pull", self.rest_api_basepath()),
+                // https://docs.gitlab.com/ee/api/remote_mirrors.html#force-push-mirror-update
+                MirrorKind::Push => format!(
+                    "{}/remote_mirrors/{}/sync",
+                    self.rest_api_basepath(),
+                    mirror.id
+                ),
+                MirrorKind::Workflow => unreachable!("Gitlab has no workflow mirrors"),
+            };
+            query::send_raw::<_, ()>(
+                &self.runner,
+                &url,
+                None,
+                self.headers(),
+                ApiOperation::Project,
+                http::Method::POST,
+            )?;
+        }
+        Ok(mirrors)
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> Gitlab<R> {
+    // Pull mirroring is a premium feature. The project data tells whether it
+    // is enabled before querying its status.
+    // https://docs.gitlab.com/ee/api/project_pull_mirroring.html
+    fn get_pull_mirror(&self) -> Result<Option<Mirror>> {
+        let project = query::get_json::<_, ()>(
+            &self.runner,
+            self.rest_api_basepath(),
+            None,
+            self.headers(),
+            ApiOperation::Project,
+        )?;
+        if !project["mirror"].as_bool().unwrap_or_default() {
+            return Ok(None);
+        }
+        let url = format!("{}/mirror/pull", self.rest_api_basepath());
+        let mirror = query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Project,
+            |value| Ok(GitlabMirrorFields::new(MirrorKind::Pull, value)?.into()),
+        )?;
+        Ok(Some(mirror))
+    }
+}
+
 impl<R> Gitlab<R> {
     fn list_project_url(&self, args: &ProjectListBodyArgs, num_pages: bool) -> String {
         let mut url = if args.tags {
             URLQueryParamBuilder::new(&format!("{}/repository/tags", self.projects_base_url))
         } else if args.members {
-            URLQueryParamBuilder::new(&format!("{}/members/all", self.projects_base_url))
+            URLQueryParamBuilder::new(&self.members_url(args))
+        } else if let Some(group) = &args.group {
+            let mut url = URLQueryParamBuilder::new(&format!(
+                "{}/{}/projects",
+                self.base_groups_url,
+                encode_path(group)
+            ));
+            url.add_param("include_subgroups", "true");
+            url
         } else {
             let user = args.user.as_ref().unwrap().clone();
             if args.stars {
@@ -154,22 +719,36 @@ impl<R> Gitlab<R> {
         }
         url.build()
     }
+
+    fn members_url(&self, args: &ProjectListBodyArgs) -> String {
+        match &args.group {
+            // Inherited members of the parent groups included.
+            Some(group) => format!(
+                "{}/{}/members/all",
+                self.base_groups_url,
+                encode_path(group)
+            ),
+            None => format!("{}/members/all", self.projects_base_url),
+        }
+    }
 }
 
 pub struct GitlabProjectTagFields {
     tag: Tag,
 }
 
-impl From<&serde_json::Value> for GitlabProjectTagFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabProjectTagFields {
+impl TryFrom<&serde_json::Value> for GitlabProjectTagFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        Ok(GitlabProjectTagFields {
             tag: Tag::builder()
                 .name(data["name"].as_str().unwrap().to_string())
                 .sha(data["commit"]["id"].as_str().unwrap().to_string())
-                .created_at(data["created_at"].as_str().unwrap().to_string())
+                .created_at(time::json_timestamp(data, "created_at")?)
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -183,43 +762,168 @@ pub struct GitlabProjectFields {
     project: Project,
 }
 
-impl From<&serde_json::Value> for GitlabProjectFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabProjectFields {
+impl TryFrom<&serde_json::Value> for GitlabProjectFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        Ok(GitlabProjectFields {
             project: Project::builder()
                 .id(data["id"].as_i64().unwrap())
                 .default_branch(data["default_branch"].as_str().unwrap().to_string())
                 .html_url(data["web_url"].as_str().unwrap().to_string())
-                .created_at(data["created_at"].as_str().unwrap().to_string())
+                .created_at(time::json_timestamp(data, "created_at")?)
                 .description(data["description"].as_str().unwrap_or_default().to_string())
                 // NOTE: Project language key is not present in the Gitlab API response.
+                .license(
+                    data["license"]["name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .topics(topics(&data["topics"]))
+                .visibility(data["visibility"].as_str().unwrap_or_default().to_string())
+                .stars(data["star_count"].as_u64().unwrap_or_default())
+                .forks(data["forks_count"].as_u64().unwrap_or_default())
+                .avatar_url(data["avatar_url"].as_str().unwrap_or_default().to_string())
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
+fn topics(data: &serde_json::Value) -> Vec<String> {
+    data.as_array()
+        .map(|topics| {
+            topics
+                .iter()
+                .filter_map(|topic| topic.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 impl From<GitlabProjectFields> for Project {
     fn from(fields: GitlabProjectFields) -> Self {
         fields.project
     }
 }
 
+pub struct GitlabDependencyFields {
+    dependency: Dependency,
+}
+
+impl From<&serde_json::Value> for GitlabDependencyFields {
+    fn from(data: &serde_json::Value) -> Self {
+        let name = data["name"].as_str().unwrap_or_default();
+        let version = data["version"].as_str().unwrap_or_default();
+        let package_manager = data["package_manager"].as_str().unwrap_or_default();
+        GitlabDependencyFields {
+            dependency: Dependency::builder()
+                .name(name.to_string())
+                .version(version.to_string())
+                .package_manager(package_manager.to_string())
+                .purl(purl(package_manager, name, version))
+                .licenses(
+                    data["licenses"]
+                        .as_array()
+                        .unwrap_or(&Vec::new())
+                        .iter()
+                        .filter_map(|license| license["name"].as_str())
+                        .map(|license| license.to_string())
+                        .collect(),
+                )
+                .source(
+                    data["dependency_file_path"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl From<GitlabDependencyFields> for Dependency {
+    fn from(fields: GitlabDependencyFields) -> Self {
+        fields.dependency
+    }
+}
+
+/// Gitlab does not provide package URLs for dependencies. Build them from the
+/// package manager. Returns an empty string for unknown package managers.
+fn purl(package_manager: &str, name: &str, version: &str) -> String {
+    let purl_type = match package_manager {
+        "bundler" => "gem",
+        "npm" | "yarn" | "pnpm" => "npm",
+        "pip" | "pipenv" | "poetry" | "setuptools" | "conda" => "pypi",
+        "maven" | "gradle" | "sbt" => "maven",
+        "composer" => "composer",
+        "nuget" => "nuget",
+        "go" => "golang",
+        "conan" => "conan",
+        "cargo" => "cargo",
+        _ => return String::new(),
+    };
+    // Maven packages are named group:artifact
+    format!("pkg:{}/{}@{}", purl_type, name.replace(':', "/"), version)
+}
+
+pub struct GitlabMirrorFields {
+    mirror: Mirror,
+}
+
+impl GitlabMirrorFields {
+    fn new(kind: MirrorKind, data: &serde_json::Value) -> Result<Self> {
+        Ok(GitlabMirrorFields {
+            mirror: Mirror::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .kind(kind)
+                .url(data["url"].as_str().unwrap_or_default().to_string())
+                // Pull mirror status does not carry the enabled flag. It is
+                // only returned when mirroring is enabled.
+                .enabled(data["enabled"].as_bool().unwrap_or(true))
+                .status(
+                    data["update_status"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                )
+                .last_update_at(time::json_optional_timestamp(data, "last_update_at")?)
+                .last_successful_update_at(time::json_optional_timestamp(
+                    data,
+                    "last_successful_update_at",
+                )?)
+                .last_error(data["last_error"].as_str().unwrap_or_default().to_string())
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GitlabMirrorFields> for Mirror {
+    fn from(fields: GitlabMirrorFields) -> Self {
+        fields.mirror
+    }
+}
+
 pub struct GitlabMemberFields {
     member: Member,
 }
 
-impl From<&serde_json::Value> for GitlabMemberFields {
-    fn from(data: &serde_json::Value) -> Self {
-        GitlabMemberFields {
+impl TryFrom<&serde_json::Value> for GitlabMemberFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        Ok(GitlabMemberFields {
             member: Member::builder()
                 .id(data["id"].as_i64().unwrap())
                 .name(data["name"].as_str().unwrap().to_string())
                 .username(data["username"].as_str().unwrap().to_string())
-                .created_at(data["created_at"].as_str().unwrap().to_string())
+                .created_at(time::json_timestamp(data, "created_at")?)
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -234,6 +938,7 @@ mod test {
 
     use crate::api_traits::ApiOperation;
     use crate::cmds::project::ProjectListBodyArgs;
+    use crate::display::DisplayBody;
     use crate::http::Headers;
     use crate::setup_client;
     use crate::test::utils::{
@@ -244,6 +949,185 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn test_add_member_by_username() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body(
+                201,
+                Some(r#"{"id": 12345, "username": "tomsawyer", "access_level": 30, "membership_state": "active"}"#),
+                None,
+            )
+            .add_contract(200, "get_user_by_username.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMemberAccess);
+        let membership = gitlab
+            .add_member("tomsawyer", MemberAccessLevel::Developer)
+            .unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/members",
+            *client.url()
+        );
+        assert_eq!(
+            r#"{"access_level":30,"user_id":12345}"#,
+            *client.request_body()
+        );
+        assert_eq!(MemberAccessLevel::Developer, membership.access);
+        assert_eq!("active", membership.state);
+    }
+
+    #[test]
+    fn test_invite_member_by_email() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            201,
+            Some(r#"{"status": "success"}"#),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMemberAccess);
+        let membership = gitlab
+            .invite_member("tom@example.com", MemberAccessLevel::Reporter)
+            .unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/invitations",
+            *client.url()
+        );
+        assert_eq!(
+            r#"{"access_level":20,"email":"tom@example.com"}"#,
+            *client.request_body()
+        );
+        assert_eq!("tom@example.com", membership.username);
+        assert_eq!("invited", membership.state);
+    }
+
+    #[test]
+    fn test_invite_member_already_invited_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            201,
+            Some(r#"{"status": "error", "message": {"tom@example.com": "Invite email has already been taken"}}"#),
+            None,
+        );
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMemberAccess);
+        let err = gitlab
+            .invite_member("tom@example.com", MemberAccessLevel::Reporter)
+            .unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(msg)) => {
+                assert!(msg.contains("already been taken"))
+            }
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_access_check_direct_group_and_shared_group() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            // Member of the group the project is shared with
+            .add_body(200, Some(r#"{"id": 12345, "access_level": 40}"#), None)
+            .add_body(
+                200,
+                Some(r#"{"id": 1, "shared_with_groups": [{"group_id": 77, "group_full_path": "qa", "group_access_level": 30}]}"#),
+                None,
+            )
+            // Direct member of the parent group
+            .add_body(200, Some(r#"{"id": 12345, "access_level": 20}"#), None)
+            .add_body(200, Some(r#"{"id": 12345, "access_level": 10}"#), None)
+            .add_contract(200, "get_user_by_username.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectAccessCheck);
+        let access = gitlab.access_check("tomsawyer").unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/groups/77/members/all/12345",
+            *client.url()
+        );
+        let grants = access
+            .grants
+            .iter()
+            .map(|grant| (grant.source.as_str(), grant.access))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("direct member", MemberAccessLevel::Guest),
+                ("group jordilin", MemberAccessLevel::Reporter),
+                // Capped at the access the project was shared with.
+                ("shared with group qa", MemberAccessLevel::Developer),
+            ],
+            grants
+        );
+        assert_eq!(Some(MemberAccessLevel::Developer), access.effective());
+    }
+
+    #[test]
+    fn test_access_check_no_access() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body(200, Some(r#"{"id": 1, "shared_with_groups": []}"#), None)
+            .add_body::<String>(404, None, None)
+            .add_body::<String>(404, None, None)
+            .add_contract(200, "get_user_by_username.json", None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectAccessCheck);
+        let access = gitlab.access_check("tomsawyer").unwrap();
+        assert!(access.grants.is_empty());
+        assert_eq!(None, access.effective());
+    }
+
+    #[test]
+    fn test_access_check_forbidden_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body::<String>(403, None, None)
+            .add_contract(200, "get_user_by_username.json", None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectAccessCheck);
+        assert!(gitlab.access_check("tomsawyer").is_err());
+    }
+
+    #[test]
+    fn test_list_invitations() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(r#"[{"id": 1, "invite_email": "tom@example.com", "created_at": "2024-01-01T00:00:00.000Z", "access_level": 30, "expires_at": "2024-02-01T00:00:00.000Z", "user_name": null, "created_by_name": "Jordi"}]"#),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectInvitation);
+        let invitations = gitlab.list_invitations().unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/invitations?per_page=100",
+            *client.url()
+        );
+        assert_eq!(1, invitations.len());
+        assert_eq!("tom@example.com", invitations[0].invitee);
+        assert_eq!(MemberAccessLevel::Developer, invitations[0].access);
+        assert_eq!("Jordi", invitations[0].invited_by);
+        assert_eq!(
+            time::parse_timestamp("2024-02-01T00:00:00.000Z"),
+            invitations[0].expires_at
+        );
+    }
+
+    #[test]
+    fn test_revoke_invitation_by_email() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_body::<String>(204, None, None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectInvitation);
+        gitlab.revoke_invitation("tom@example.com").unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/invitations/tom%40example.com",
+            *client.url()
+        );
+        assert_eq!(http::Method::DELETE, client.http_method.borrow()[0]);
+    }
+
+    #[test]
+    fn test_remove_member() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body::<String>(204, None, None)
+            .add_contract(200, "get_user_by_username.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMemberAccess);
+        gitlab.remove_member("tomsawyer").unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/members/12345",
+            *client.url()
+        );
+        assert_eq!(
+            vec![http::Method::GET, http::Method::DELETE],
+            *client.http_method.borrow()
+        );
+    }
+
     #[test]
     fn test_get_project_data_no_id() {
         let contracts =
@@ -251,7 +1135,7 @@ mod test {
         let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteProject);
         gitlab.get_project_data(None, None).unwrap();
         assert_eq!(
-            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi",
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi?license=true",
             client.url().to_string(),
         );
         assert_eq!("1234", client.headers().get("PRIVATE-TOKEN").unwrap());
@@ -265,7 +1149,7 @@ mod test {
         let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteProject);
         gitlab.get_project_data(Some(54345), None).unwrap();
         assert_eq!(
-            "https://gitlab.com/api/v4/projects/54345",
+            "https://gitlab.com/api/v4/projects/54345?license=true",
             client.url().to_string(),
         );
         assert_eq!("1234", client.headers().get("PRIVATE-TOKEN").unwrap());
@@ -284,7 +1168,7 @@ mod test {
         // User requests information on a different repository.
         let result = gitlab.get_project_data(None, Some("jordilin/gitlapi"));
         assert_eq!(
-            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi",
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi?license=true",
             client.url().to_string(),
         );
         match result {
@@ -295,6 +1179,51 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_get_project_data_metadata() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "project.json", None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteProject);
+        let CmdInfo::Project(project) = gitlab.get_project_data(None, None).unwrap() else {
+            panic!("Expected project");
+        };
+        let columns = DisplayBody::from(project).columns;
+        let value = |name: &str| {
+            columns
+                .iter()
+                .find(|column| column.name == name)
+                .unwrap()
+                .value
+                .clone()
+        };
+        assert_eq!("public", value("Visibility"));
+        assert_eq!("0", value("Stars"));
+        assert_eq!("", value("License"));
+    }
+
+    #[test]
+    fn test_get_project_languages() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "project_languages.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectLanguages);
+        let languages = gitlab.languages(Some(54345), None).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/54345/languages",
+            client.url().to_string(),
+        );
+        assert_eq!(
+            vec![
+                ProjectLanguage::new("Rust", 92.15),
+                ProjectLanguage::new("Shell", 7.85)
+            ],
+            languages
+        );
+        assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+    }
+
     #[test]
     fn test_get_project_data_error_if_both_id_and_path_given() {
         let contracts = ResponseContracts::new(ContractType::Gitlab);
@@ -464,6 +1393,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_get_url_new_merge_request_is_prefilled() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteProject);
+        let url = gitlab.get_url(BrowseOptions::NewMergeRequest {
+            source_branch: "feature/issues".to_string(),
+            target_branch: "main".to_string(),
+            title: "Add issues".to_string(),
+            description: "List & close".to_string(),
+        });
+        assert_eq!(
+            "https://gitlab.com/jordilin/gitlapi/-/merge_requests/new?\
+             merge_request[source_branch]=feature%2Fissues&merge_request[target_branch]=main\
+             &merge_request[title]=Add%20issues&merge_request[description]=List%20%26%20close",
+            url
+        );
+    }
+
     #[test]
     fn test_get_url_pipeline_id() {
         let contracts = ResponseContracts::new(ContractType::Gitlab);
@@ -547,6 +1494,54 @@ mod test {
         assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
     }
 
+    #[test]
+    fn test_list_group_projects_including_subgroups() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body(
+            200,
+            Some(format!(
+                "[{}]",
+                get_contract(ContractType::Gitlab, "project.json")
+            )),
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn RemoteProject);
+        let body_args = ProjectListBodyArgs::builder()
+            .from_to_page(None)
+            .user(None)
+            .group(Some("team/subgroup".to_string()))
+            .build()
+            .unwrap();
+        let projects = gitlab.list(body_args).unwrap();
+        assert_eq!(1, projects.len());
+        assert_eq!(
+            "https://gitlab.com/api/v4/groups/team%2Fsubgroup/projects?include_subgroups=true",
+            *client.url(),
+        );
+    }
+
+    #[test]
+    fn test_list_subgroup_members() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "project_members.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMember);
+        let args = ProjectListBodyArgs::builder()
+            .members(true)
+            .user(None)
+            .from_to_page(None)
+            .group(Some("team/subgroup".to_string()))
+            .build()
+            .unwrap();
+        let members = ProjectMember::list(&*gitlab, args).unwrap();
+        assert_eq!(2, members.len());
+        assert_eq!(
+            "https://gitlab.com/api/v4/groups/team%2Fsubgroup/members/all",
+            *client.url(),
+        );
+    }
+
     #[test]
     fn test_list_project_members_num_pages() {
         let link_header = "<https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/members/all?page=2&per_page=20>; rel=\"next\", <https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/members/all?page=2&per_page=20>; rel=\"last\"";
@@ -591,4 +1586,124 @@ mod test {
             *client.url()
         );
     }
+
+    #[test]
+    fn test_list_mirrors_push_only() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_contract(200, "list_remote_mirrors.json", None)
+            .add_contract(200, "project.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMirror);
+        let mirrors = gitlab.list_mirrors().unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/remote_mirrors",
+            *client.url(),
+        );
+        assert_eq!(2, mirrors.len());
+        assert_eq!(MirrorKind::Push, mirrors[0].kind);
+        assert!(mirrors[0].enabled);
+        assert_eq!("finished", mirrors[0].status);
+        assert!(mirrors[1].last_successful_update_at.is_none());
+        assert_eq!("Remote mirror update failed.", mirrors[1].last_error);
+    }
+
+    #[test]
+    fn test_list_mirrors_with_pull_mirror() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body::<String>(200, Some("[]".to_string()), None)
+            .add_contract(200, "pull_mirror.json", None)
+            .add_body(200, Some(r#"{"id": 1, "mirror": true}"#), None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMirror);
+        let mirrors = gitlab.list_mirrors().unwrap();
+        assert_eq!(1, mirrors.len());
+        assert_eq!(MirrorKind::Pull, mirrors[0].kind);
+        assert_eq!(101480, mirrors[0].id);
+        assert!(mirrors[0].enabled);
+        assert_eq!(
+            time::parse_timestamp("2024-02-12T09:00:02.000Z"),
+            mirrors[0].last_successful_update_at
+        );
+        assert_eq!(3, client.http_method.borrow().len());
+    }
+
+    #[test]
+    fn test_sync_mirrors_enabled_only() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body::<String>(204, None, None)
+            .add_contract(200, "list_remote_mirrors.json", None)
+            .add_contract(200, "project.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMirror);
+        let mirrors = gitlab.sync_mirrors().unwrap();
+        assert_eq!(1, mirrors.len());
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/remote_mirrors/101486/sync",
+            *client.url(),
+        );
+        assert_eq!(http::Method::POST, client.http_method.borrow()[2]);
+    }
+
+    #[test]
+    fn test_sync_mirrors_no_enabled_mirrors_is_error() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body::<String>(200, Some("[]".to_string()), None)
+            .add_contract(200, "project.json", None);
+        let (_, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectMirror);
+        let err = gitlab.sync_mirrors().unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::PreconditionNotMet(_)) => {}
+            _ => panic!("Expected PreconditionNotMet"),
+        }
+    }
+
+    #[test]
+    fn test_list_dependencies() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "list_dependencies.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectDependency);
+        let dependencies = gitlab.list_dependencies().unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/dependencies",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+        assert_eq!(2, dependencies.len());
+        assert_eq!("pkg:gem/rails@7.1.3", dependencies[0].purl);
+        assert_eq!(vec!["MIT".to_string()], dependencies[0].licenses);
+        assert_eq!("Gemfile.lock", dependencies[0].source);
+        assert_eq!(
+            "pkg:maven/org.apache.commons/commons-lang3@3.14.0",
+            dependencies[1].purl
+        );
+    }
+
+    #[test]
+    fn test_purl_unknown_package_manager_is_empty() {
+        assert_eq!("", purl("unknown", "pkg", "1.0"));
+    }
+
+    #[test]
+    fn test_blame_file_range() {
+        let contracts =
+            ResponseContracts::new(ContractType::Gitlab).add_contract(200, "blame_file.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn ProjectBlame);
+        let args = ProjectBlameBodyArgs::builder()
+            .path("src/main.rs".to_string())
+            .git_ref("main".to_string())
+            .range(Some((10, 12)))
+            .build()
+            .unwrap();
+        let lines = gitlab.blame(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/repository/files/src%2Fmain.rs/blame?ref=main&range[start]=10&range[end]=12",
+            *client.url(),
+        );
+        assert_eq!(3, lines.len());
+        assert_eq!(10, lines[0].line);
+        assert_eq!("6104942438c1", lines[0].commit);
+        assert_eq!(12, lines[2].line);
+        assert_eq!("ae1d9fb46aa2", lines[2].commit);
+        assert_eq!("}", lines[2].content);
+    }
 }
@@ -4,7 +4,7 @@ use crate::{
     http,
     io::{HttpResponse, HttpRunner},
     remote::query,
-    Result,
+    time, Error, Result,
 };
 
 use super::Gitlab;
@@ -19,7 +19,7 @@ impl<R: HttpRunner<Response = HttpResponse>> Deploy for Gitlab<R> {
             self.headers(),
             None,
             ApiOperation::Release,
-            |value| GitlabReleaseFields::from(value).into(),
+            |value| Ok(GitlabReleaseFields::try_from(value)?.into()),
         )
     }
 
@@ -57,9 +57,9 @@ impl<R: HttpRunner<Response = HttpResponse>> DeployAsset for Gitlab<R> {
     fn list(&self, args: ReleaseAssetListBodyArgs) -> Result<Vec<ReleaseAssetMetadata>> {
         let release = self.get_release(args)?;
         let mut asset_metadata = Vec::new();
-        build_release_assets(&release, &mut asset_metadata, AssetType::Sources);
+        build_release_assets(&release, &mut asset_metadata, AssetType::Sources)?;
         // _links is considered an asset in the Gitlab API, include those too.
-        build_release_assets(&release, &mut asset_metadata, AssetType::Links);
+        build_release_assets(&release, &mut asset_metadata, AssetType::Links)?;
         Ok(asset_metadata)
     }
 
@@ -78,7 +78,7 @@ impl<R: HttpRunner<Response = HttpResponse>> DeployAsset for Gitlab<R> {
         // number or resources is in the field assets.count
         let release = self.get_release(args)?;
         let count = release["assets"]["count"].as_u64().unwrap();
-        Ok(Some(NumberDeltaErr::new(1, count as u32)))
+        Ok(Some(NumberDeltaErr::exact(count as u32)))
     }
 }
 
@@ -100,7 +100,7 @@ fn build_release_assets(
     release: &serde_json::Value,
     asset_metadata: &mut Vec<ReleaseAssetMetadata>,
     asset_type: AssetType,
-) {
+) -> Result<()> {
     let assets = release["assets"][asset_type.as_ref()].as_array().unwrap();
     for asset in assets {
         let asset_data = ReleaseAssetMetadata::builder()
@@ -110,21 +110,24 @@ fn build_release_assets(
             .name(release["name"].as_str().unwrap().to_string())
             .url(asset["url"].as_str().unwrap().to_string())
             .size("".to_string())
-            .created_at(release["created_at"].as_str().unwrap().to_string())
-            .updated_at(release["released_at"].as_str().unwrap().to_string())
+            .created_at(time::json_timestamp(release, "created_at")?)
+            .updated_at(time::json_timestamp(release, "released_at")?)
             .build()
             .unwrap();
         asset_metadata.push(asset_data);
     }
+    Ok(())
 }
 
 pub struct GitlabReleaseFields {
     release: Release,
 }
 
-impl From<&serde_json::Value> for GitlabReleaseFields {
-    fn from(value: &serde_json::Value) -> Self {
-        Self {
+impl TryFrom<&serde_json::Value> for GitlabReleaseFields {
+    type Error = Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self> {
+        Ok(Self {
             release: Release::builder()
                 // There's no id available in the response per se. Grab the short commit
                 // id instead
@@ -134,11 +137,11 @@ impl From<&serde_json::Value> for GitlabReleaseFields {
                 .title(value["name"].as_str().unwrap().to_string())
                 .description(value["description"].as_str().unwrap().to_string())
                 .prerelease(value["upcoming_release"].as_bool().unwrap())
-                .created_at(value["created_at"].as_str().unwrap().to_string())
-                .updated_at(value["released_at"].as_str().unwrap().to_string())
+                .created_at(time::json_timestamp(value, "created_at")?)
+                .updated_at(time::json_timestamp(value, "released_at")?)
                 .build()
                 .unwrap(),
-        }
+        })
     }
 }
 
@@ -275,6 +278,6 @@ mod test {
             .build()
             .unwrap();
         let num_resources = gitlab.num_resources(args).unwrap().unwrap();
-        assert_eq!("(1, 5)", &num_resources.to_string());
+        assert_eq!("5 exact", &num_resources.to_string());
     }
 }
@@ -0,0 +1,166 @@
+use crate::api_traits::{ApiOperation, NumberDeltaErr, Search};
+use crate::cmds::search::{SearchBodyArgs, SearchResult, SearchScope};
+use crate::io::{HttpResponse, HttpRunner};
+use crate::remote::{encode_query_param, query, URLQueryParamBuilder};
+use crate::{time, Result};
+
+use super::Gitlab;
+
+// https://docs.gitlab.com/ee/api/search.html#project-search-api
+impl<R: HttpRunner<Response = HttpResponse>> Search for Gitlab<R> {
+    fn list(&self, args: SearchBodyArgs) -> Result<Vec<SearchResult>> {
+        let url = self.search_url(&args, false);
+        let scope = args.scope;
+        query::paged(
+            &self.runner,
+            &url,
+            args.from_to_page,
+            self.headers(),
+            None,
+            ApiOperation::Project,
+            |value| Ok(GitlabSearchResultFields::new(scope, value)?.into()),
+        )
+    }
+
+    fn num_pages(&self, args: SearchBodyArgs) -> Result<Option<u32>> {
+        let url = self.search_url(&args, true);
+        query::num_pages(&self.runner, &url, self.headers(), ApiOperation::Project)
+    }
+
+    fn num_resources(&self, args: SearchBodyArgs) -> Result<Option<NumberDeltaErr>> {
+        let url = self.search_url(&args, true);
+        query::num_resources(&self.runner, &url, self.headers(), ApiOperation::Project)
+    }
+}
+
+impl<R> Gitlab<R> {
+    fn search_url(&self, args: &SearchBodyArgs, num_pages: bool) -> String {
+        let mut url = URLQueryParamBuilder::new(&format!("{}/search", self.projects_base_url));
+        url.add_param("scope", &args.scope.to_string())
+            .add_param("search", &encode_query_param(&args.query));
+        if num_pages {
+            url.add_param("page", "1");
+        }
+        url.build()
+    }
+}
+
+pub struct GitlabSearchResultFields {
+    result: SearchResult,
+}
+
+impl GitlabSearchResultFields {
+    fn new(scope: SearchScope, data: &serde_json::Value) -> Result<Self> {
+        let builder = &mut SearchResult::builder();
+        match scope {
+            SearchScope::Commits => builder
+                .id(data["short_id"].as_str().unwrap_or_default().to_string())
+                .author(data["author_name"].as_str().unwrap_or_default().to_string()),
+            SearchScope::Issues | SearchScope::MergeRequests => builder
+                .id(data["iid"].as_i64().unwrap_or_default().to_string())
+                .state(data["state"].as_str().unwrap_or_default().to_string())
+                .author(
+                    data["author"]["username"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                ),
+        };
+        Ok(GitlabSearchResultFields {
+            result: builder
+                .title(data["title"].as_str().unwrap_or_default().to_string())
+                .web_url(data["web_url"].as_str().unwrap_or_default().to_string())
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GitlabSearchResultFields> for SearchResult {
+    fn from(fields: GitlabSearchResultFields) -> Self {
+        fields.result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http::Headers;
+    use crate::setup_client;
+    use crate::test::utils::{default_gitlab, ContractType, ResponseContracts};
+
+    use super::*;
+
+    fn body_args(scope: SearchScope, query: &str) -> SearchBodyArgs {
+        SearchBodyArgs::builder()
+            .scope(scope)
+            .query(query.to_string())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_search_merge_requests() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "search_merge_requests.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Search);
+        let results = gitlab
+            .list(body_args(SearchScope::MergeRequests, "rate limit"))
+            .unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/search?scope=merge_requests&search=rate%20limit",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+        assert_eq!(1, results.len());
+        assert_eq!("33", results[0].id);
+        assert_eq!("merged", results[0].state);
+        assert_eq!("jordilin", results[0].author);
+    }
+
+    #[test]
+    fn test_search_commits() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "search_commits.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Search);
+        let results = gitlab
+            .list(body_args(SearchScope::Commits, "etag"))
+            .unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/search?scope=commits&search=etag",
+            *client.url(),
+        );
+        assert_eq!("6104942438c", results[0].id);
+        assert_eq!("Jordi Carrillo", results[0].author);
+        assert_eq!("", results[0].state);
+    }
+
+    #[test]
+    fn test_search_num_pages() {
+        let link_header = "<https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/search?scope=issues&search=cache&page=2>; rel=\"next\", <https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/search?scope=issues&search=cache&page=3>; rel=\"last\"";
+        let mut headers = Headers::new();
+        headers.set("link", link_header);
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_body::<String>(
+            200,
+            None,
+            Some(headers),
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn Search);
+        assert_eq!(
+            Some(3),
+            gitlab
+                .num_pages(body_args(SearchScope::Issues, "cache"))
+                .unwrap()
+        );
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/jordilin%2Fgitlapi/search?scope=issues&search=cache&page=1",
+            *client.url(),
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
-    api_traits::{ApiOperation, UserInfo},
-    cmds::{project::Member, user::UserCliArgs},
+    api_traits::{ApiOperation, UserActivity, UserInfo},
+    cmds::{
+        project::Member,
+        user::{ActivityBodyArgs, ActivityEvent, ActivityKind, UserCliArgs, UserProfile},
+    },
     error::GRError,
     io::{HttpResponse, HttpRunner},
     remote::{self, query},
-    Result,
+    time, Error, Result,
 };
 
 use super::Gitlab;
@@ -16,8 +19,8 @@ impl<R: HttpRunner<Response = HttpResponse>> UserInfo for Gitlab<R> {
             &self.base_current_user_url,
             None,
             self.headers(),
-            ApiOperation::Project,
-            |value| GitlabUserFields::from(value).into(),
+            ApiOperation::User,
+            |value| Ok(GitlabUserFields::from(value).into()),
         )?;
         Ok(user)
     }
@@ -40,14 +43,131 @@ impl<R: HttpRunner<Response = HttpResponse>> UserInfo for Gitlab<R> {
             Some(list_args),
             self.headers(),
             None,
-            ApiOperation::Project,
-            |value| GitlabUserFields::from(value).into(),
+            ApiOperation::User,
+            |value| Ok(GitlabUserFields::from(value).into()),
         )?;
         if user.is_empty() {
             return Err(GRError::UserNotFound(args.username.clone()).into());
         }
         Ok(user[0].clone())
     }
+
+    fn get_profile(&self, args: &UserCliArgs) -> Result<UserProfile> {
+        // The list users API does not return the profile fields, the single
+        // user API does.
+        // https://docs.gitlab.com/ee/api/users.html#single-user
+        // Group memberships of other users are only visible to
+        // administrators, so the organization of the profile is used.
+        let user = UserInfo::get(self, args)?;
+        let url = format!("{}/{}", self.base_users_url, user.id);
+        query::get::<_, (), _>(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            ApiOperation::Project,
+            |value| Ok(GitlabProfileFields::try_from(value)?.into()),
+        )
+    }
+}
+
+impl<R: HttpRunner<Response = HttpResponse>> UserActivity for Gitlab<R> {
+    fn list_events(&self, args: ActivityBodyArgs) -> Result<Vec<ActivityEvent>> {
+        // https://docs.gitlab.com/ee/api/events.html#get-user-contribution-events
+        let mut url = format!("{}/{}/events", self.base_users_url, args.user.id);
+        let mut params = Vec::new();
+        if let Some(after) = &args.after {
+            params.push(format!("after={}", after));
+        }
+        if let Some(before) = &args.before {
+            params.push(format!("before={}", before));
+        }
+        if !params.is_empty() {
+            url = format!("{}?{}", url, params.join("&"));
+        }
+        let events = query::paged(
+            &self.runner,
+            &url,
+            None,
+            self.headers(),
+            None,
+            ApiOperation::Project,
+            |value| Ok(GitlabEventFields::try_from(value)?.into()),
+        )?;
+        Ok(events
+            .into_iter()
+            .filter(|event: &ActivityEvent| event.kind != ActivityKind::Other)
+            .collect())
+    }
+}
+
+pub struct GitlabEventFields {
+    event: ActivityEvent,
+}
+
+impl TryFrom<&serde_json::Value> for GitlabEventFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        let action = data["action_name"].as_str().unwrap_or_default();
+        let target_type = data["target_type"].as_str().unwrap_or_default();
+        let kind = match (action, target_type) {
+            ("opened", "MergeRequest") => ActivityKind::MergeRequestOpened,
+            ("accepted", "MergeRequest") => ActivityKind::MergeRequestMerged,
+            ("approved", _) | ("commented on", "DiffNote") => ActivityKind::Review,
+            ("opened", "Issue") => ActivityKind::IssueOpened,
+            ("pushed to", _) | ("pushed new", _) => ActivityKind::Push,
+            _ => ActivityKind::Other,
+        };
+        Ok(GitlabEventFields {
+            event: ActivityEvent::builder()
+                .kind(kind)
+                .created_at(time::json_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GitlabEventFields> for ActivityEvent {
+    fn from(fields: GitlabEventFields) -> Self {
+        fields.event
+    }
+}
+
+pub struct GitlabProfileFields {
+    profile: UserProfile,
+}
+
+impl TryFrom<&serde_json::Value> for GitlabProfileFields {
+    type Error = Error;
+
+    fn try_from(data: &serde_json::Value) -> Result<Self> {
+        let field = |name: &str| data[name].as_str().unwrap_or_default().to_string();
+        let organizations = match field("organization") {
+            organization if organization.is_empty() => Vec::new(),
+            organization => vec![organization],
+        };
+        Ok(GitlabProfileFields {
+            profile: UserProfile::builder()
+                .id(data["id"].as_i64().unwrap_or_default())
+                .username(field("username"))
+                .name(field("name"))
+                .bio(field("bio"))
+                .location(field("location"))
+                .organizations(organizations)
+                .web_url(field("web_url"))
+                .created_at(time::json_optional_timestamp(data, "created_at")?)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+impl From<GitlabProfileFields> for UserProfile {
+    fn from(fields: GitlabProfileFields) -> Self {
+        fields.profile
+    }
 }
 
 pub struct GitlabUserFields {
@@ -81,8 +201,11 @@ impl From<GitlabUserFields> for Member {
 mod test {
     use crate::{
         api_traits::ApiOperation,
-        error, setup_client,
-        test::utils::{default_gitlab, ContractType, ResponseContracts},
+        config::{ConfigFile, NoConfig},
+        error,
+        remote::RemoteURL,
+        setup_client,
+        test::utils::{default_gitlab, get_contract, ContractType, MockRunner, ResponseContracts},
     };
 
     use super::*;
@@ -100,7 +223,80 @@ mod test {
         assert_eq!("jordilin", user.username);
         assert_eq!("https://gitlab.com/api/v4/user", *client.url(),);
         assert_eq!("1234", client.headers().get("PRIVATE-TOKEN").unwrap());
-        assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+        assert_eq!(Some(ApiOperation::User), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_get_user_with_sudo_header() {
+        let config = std::sync::Arc::new(
+            NoConfig::new("gitlab.com", |_| Ok("1234".to_string()))
+                .unwrap()
+                .with_sudo(Some("tomsawyer".to_string())),
+        );
+        let response = HttpResponse::builder()
+            .status(200)
+            .body(get_contract(ContractType::Gitlab, "get_user_info.json"))
+            .build()
+            .unwrap();
+        let client = std::sync::Arc::new(MockRunner::new(vec![response]));
+        let gitlab = Gitlab::new(config, "gitlab.com", "jordilin/gitlapi", client.clone());
+        gitlab.get_auth_user().unwrap();
+        assert_eq!("tomsawyer", client.headers().get("Sudo").unwrap());
+        assert_eq!("1234", client.headers().get("PRIVATE-TOKEN").unwrap());
+    }
+
+    #[test]
+    fn test_get_user_with_api_base_url_override() {
+        let config_data = r#"
+        [tools_corp]
+        api_token = '1234'
+        api_base_url = "https://tools.corp/gitlab/api/v4"
+        "#;
+        let url = RemoteURL::new("tools.corp".to_string(), "jordilin/gitlapi".to_string());
+        let config = std::sync::Arc::new(
+            ConfigFile::new(vec![std::io::Cursor::new(config_data)], &url, |_| {
+                Ok("".to_string())
+            })
+            .unwrap(),
+        );
+        let response = HttpResponse::builder()
+            .status(200)
+            .body(get_contract(ContractType::Gitlab, "get_user_info.json"))
+            .build()
+            .unwrap();
+        let client = std::sync::Arc::new(MockRunner::new(vec![response]));
+        let gitlab = Gitlab::new(config, "tools.corp", "jordilin/gitlapi", client.clone());
+        gitlab.get_auth_user().unwrap();
+        assert_eq!("https://tools.corp/gitlab/api/v4/user", *client.url());
+    }
+
+    #[test]
+    fn test_get_user_with_oauth_token() {
+        let config_data = r#"
+        [gitlab_com]
+        api_token = '1234'
+        auth = "oauth"
+        "#;
+        let url = RemoteURL::new("gitlab.com".to_string(), "jordilin/gitlapi".to_string());
+        let config = std::sync::Arc::new(
+            ConfigFile::new(vec![std::io::Cursor::new(config_data)], &url, |_| {
+                Ok("".to_string())
+            })
+            .unwrap(),
+        );
+        let response = HttpResponse::builder()
+            .status(200)
+            .body(get_contract(ContractType::Gitlab, "get_user_info.json"))
+            .build()
+            .unwrap();
+        let client = std::sync::Arc::new(MockRunner::new(vec![response]));
+        let gitlab = Gitlab::new(config, "gitlab.com", "jordilin/gitlapi", client.clone());
+        gitlab.get_auth_user().unwrap();
+        assert_eq!(
+            "Bearer 1234",
+            client.headers().get("Authorization").unwrap()
+        );
+        assert!(client.headers().get("PRIVATE-TOKEN").is_none());
     }
 
     #[test]
@@ -125,7 +321,36 @@ mod test {
             *client.url(),
         );
         assert_eq!("1234", client.headers().get("PRIVATE-TOKEN").unwrap());
-        assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+        assert_eq!(Some(ApiOperation::User), *client.api_operation.borrow());
+    }
+
+    #[test]
+    fn test_get_user_profile() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab)
+            .add_body(
+                200,
+                Some(
+                    r#"{"id": 12345, "username": "tomsawyer", "name": "Tom Sawyer", "bio": "Adventurer", "location": "St. Petersburg", "organization": "Mississippi", "web_url": "https://gitlab.com/tomsawyer", "created_at": "2014-04-16T20:31:04.000Z"}"#,
+                ),
+                None,
+            )
+            .add_contract(200, "get_user_by_username.json", None);
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn UserInfo);
+        let args = UserCliArgs::builder()
+            .username("tomsawyer".to_string())
+            .get_args(remote::GetRemoteCliArgs::builder().build().unwrap())
+            .build()
+            .unwrap();
+        let profile = gitlab.get_profile(&args).unwrap();
+        assert_eq!("https://gitlab.com/api/v4/users/12345", *client.url());
+        assert_eq!("tomsawyer", profile.username);
+        assert_eq!("Adventurer", profile.bio);
+        assert_eq!("St. Petersburg", profile.location);
+        assert_eq!(vec!["Mississippi"], profile.organizations);
+        assert_eq!(
+            time::parse_timestamp("2014-04-16T20:31:04.000Z"),
+            profile.created_at
+        );
     }
 
     #[test]
@@ -151,4 +376,48 @@ mod test {
             Ok(_) => panic!("Expected user not found error"),
         }
     }
+
+    #[test]
+    fn test_list_user_contribution_events() {
+        let contracts = ResponseContracts::new(ContractType::Gitlab).add_contract(
+            200,
+            "list_user_events.json",
+            None,
+        );
+        let (client, gitlab) = setup_client!(contracts, default_gitlab(), dyn UserActivity);
+        let args = ActivityBodyArgs::builder()
+            .user(
+                Member::builder()
+                    .id(123456)
+                    .username("jordilin".to_string())
+                    .build()
+                    .unwrap(),
+            )
+            .after(Some("2024-02-01".to_string()))
+            .before(Some("2024-02-29".to_string()))
+            .build()
+            .unwrap();
+        let events = gitlab.list_events(args).unwrap();
+        assert_eq!(
+            "https://gitlab.com/api/v4/users/123456/events?after=2024-02-01&before=2024-02-29",
+            *client.url(),
+        );
+        assert_eq!(Some(ApiOperation::Project), *client.api_operation.borrow());
+        let kinds = events.iter().map(|e| e.kind).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ActivityKind::MergeRequestOpened,
+                ActivityKind::MergeRequestMerged,
+                ActivityKind::Review,
+                ActivityKind::Review,
+                ActivityKind::IssueOpened,
+                ActivityKind::Push,
+            ],
+            kinds
+        );
+        assert_eq!(
+            "2024-02-12T10:00:00Z",
+            time::format_timestamp(&events[0].created_at)
+        );
+    }
 }
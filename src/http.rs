@@ -1,9 +1,11 @@
+pub mod circuit;
 pub mod throttle;
 
 use crate::api_traits::ApiOperation;
+use crate::auth::{self, AuthMethod};
 use crate::backoff::Backoff;
 use crate::cache::{Cache, CacheState};
-use crate::config::ConfigProperties;
+use crate::config::{ConfigProperties, IpFamily, RetryBudget};
 use crate::error::GRError;
 use crate::io::{
     parse_page_headers, parse_ratelimit_headers, FlowControlHeaders, HttpResponse, HttpRunner,
@@ -12,10 +14,13 @@ use crate::io::{
 use crate::time::{self, now_epoch_seconds, Seconds};
 use crate::{api_defaults, error, log_debug, log_error};
 use crate::{log_info, Result};
+use circuit::CircuitBreaker;
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
-use std::collections::{hash_map, HashMap};
+use std::collections::{hash_map, BTreeMap, HashMap, HashSet};
+use std::io::Read;
 use std::iter::Iterator;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use throttle::{ThrottleStrategy, ThrottleStrategyType};
@@ -27,6 +32,10 @@ pub struct Client<C> {
     refresh_cache: bool,
     time_to_ratelimit_reset: Mutex<Seconds>,
     remaining_requests: Mutex<u32>,
+    agent: ureq::Agent,
+    circuit_breaker: Option<CircuitBreaker>,
+    /// Operations already reported as not accessible with a job token.
+    job_token_warnings: Mutex<HashSet<ApiOperation>>,
 }
 
 // TODO: provide builder pattern for Client.
@@ -34,34 +43,94 @@ impl<C> Client<C> {
     pub fn new(cache: C, config: Arc<dyn ConfigProperties>, refresh_cache: bool) -> Self {
         let remaining_requests = Mutex::new(api_defaults::DEFAULT_NUMBER_REQUESTS_MINUTE);
         let time_to_ratelimit_reset = Mutex::new(now_epoch_seconds() + Seconds::new(60));
+        let resolver_config = config.clone();
+        let agent = ureq::AgentBuilder::new()
+            .resolver(move |netloc: &str| resolve(netloc, resolver_config.as_ref()))
+            .build();
         Client {
             cache,
             refresh_cache,
             config,
             time_to_ratelimit_reset,
             remaining_requests,
+            agent,
+            circuit_breaker: None,
+            job_token_warnings: Mutex::new(HashSet::new()),
         }
     }
 
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
     fn submit<T: Serialize>(&self, request: &Request<T>) -> Result<HttpResponse> {
+        self.warn_job_token_access(request);
+        let Some(circuit_breaker) = &self.circuit_breaker else {
+            return self.send(request);
+        };
+        circuit_breaker.check()?;
+        let response = self.send(request);
+        let failed = match &response {
+            Ok(response) => response.status >= 500,
+            Err(err) => matches!(
+                err.downcast_ref::<GRError>(),
+                Some(GRError::HttpTransportError(_))
+            ),
+        };
+        if !failed {
+            circuit_breaker.record_success();
+        } else if !request.retry_pending() {
+            circuit_breaker.record_failure();
+        }
+        response
+    }
+
+    /// Job tokens only give access to a subset of the API. Reported once per
+    /// operation, so failures with a 401 or 404 status are not a surprise.
+    fn warn_job_token_access<T>(&self, request: &Request<T>) {
+        if self.config.auth_method() != AuthMethod::JobToken {
+            return;
+        }
+        let Some(operation) = &request.resource.api_operation else {
+            return;
+        };
+        if auth::job_token_allows(operation) {
+            return;
+        }
+        if self
+            .job_token_warnings
+            .lock()
+            .unwrap()
+            .insert(operation.clone())
+        {
+            eprintln!(
+                "Warning: CI/CD job tokens have no access to {} API endpoints and {} \
+                 might be rejected. Configure an API token for full access",
+                operation,
+                request.url()
+            );
+        }
+    }
+
+    fn send<T: Serialize>(&self, request: &Request<T>) -> Result<HttpResponse> {
         let ureq_req = match request.method {
-            Method::GET => ureq::get(request.url()),
-            Method::HEAD => ureq::head(request.url()),
-            Method::POST => ureq::post(request.url()),
-            Method::PATCH => ureq::patch(request.url()),
-            Method::PUT => ureq::put(request.url()),
+            Method::GET => self.agent.get(request.url()),
+            Method::HEAD => self.agent.head(request.url()),
+            Method::POST => self.agent.post(request.url()),
+            Method::PATCH => self.agent.patch(request.url()),
+            Method::PUT => self.agent.put(request.url()),
+            Method::DELETE => self.agent.delete(request.url()),
         };
         let ureq_req = request
             .headers()
             .iter()
             .fold(ureq_req, |req, (key, value)| req.set(key, value));
-        let call = || -> std::result::Result<ureq::Response, ureq::Error> {
-            match request.method {
-                Method::GET | Method::HEAD => ureq_req.call(),
-                _ => ureq_req.send_json(serde_json::to_value(request.body).unwrap()),
-            }
+        let call = match request.method {
+            Method::GET | Method::HEAD | Method::DELETE => ureq_req.call(),
+            _ => ureq_req.send_json(serde_json::to_value(request.body).unwrap()),
         };
-        match call() {
+        match call {
             Ok(response) | Err(Error::Status(_, response)) => {
                 let status = response.status().into();
                 // Grab headers for pagination and cache.
@@ -81,10 +150,40 @@ impl<C> Client<C> {
                 let flow_control_headers = FlowControlHeaders::new(page_header, rate_limit_header);
                 // log debug response headers
                 log_debug!("Response headers: {:?}", headers);
-                let body = response.into_string().unwrap_or_default();
+                let max_body_size = self.config.max_response_body_size();
+                let mut data = Vec::new();
+                // The body is read in full, it is cached and parsed afterwards.
+                // Read one byte past the limit to tell apart a body of the
+                // exact maximum size from a larger one.
+                // A body cut short is an error, never parsed as a complete one.
+                response
+                    .into_reader()
+                    .take(max_body_size.saturating_add(1))
+                    .read_to_end(&mut data)
+                    .map_err(|err| {
+                        GRError::HttpTransportError(format!(
+                            "Failed to read response body from {}: {}",
+                            request.url(),
+                            err
+                        ))
+                    })?;
+                if data.len() as u64 > max_body_size {
+                    return Err(GRError::ResponseBodyTooLarge(format!(
+                        "{} exceeds {} bytes. Reduce the page size or increase \
+                         max_response_body_mb in the configuration",
+                        request.url(),
+                        max_body_size
+                    ))
+                    .into());
+                }
+                let (body, bytes) = match String::from_utf8(data) {
+                    Ok(body) => (body, Vec::new()),
+                    Err(err) => (String::new(), err.into_bytes()),
+                };
                 let mut response = HttpResponse::builder()
                     .status(status)
                     .body(body)
+                    .bytes(bytes)
                     .headers(headers)
                     .flow_control_headers(flow_control_headers)
                     .build()
@@ -207,12 +306,14 @@ impl Resource {
     }
 }
 
+/// Request body. Keys are kept sorted, so the same request always serializes
+/// to the same payload, ex. when it is sent again on retries.
 #[derive(Serialize, Clone, Debug, Default)]
-pub struct Body<T>(HashMap<String, T>);
+pub struct Body<T>(BTreeMap<String, T>);
 
 impl<T> Body<T> {
     pub fn new() -> Self {
-        Body(HashMap::new())
+        Body(BTreeMap::new())
     }
 
     pub fn add<K: Into<String>>(&mut self, key: K, value: T) {
@@ -236,7 +337,7 @@ impl Headers {
         self.0.get(key)
     }
 
-    pub fn iter(&self) -> hash_map::Iter<String, String> {
+    pub fn iter(&self) -> hash_map::Iter<'_, String, String> {
         self.0.iter()
     }
 
@@ -258,6 +359,10 @@ pub struct Request<'a, T> {
     pub resource: Resource,
     #[builder(setter(into, strip_option), default)]
     pub max_pages: Option<i64>,
+    /// Set while a failed attempt will be retried, so the circuit breaker
+    /// counts one failure per request instead of one per attempt.
+    #[builder(default)]
+    retry_pending: bool,
 }
 
 impl<'a, T> Request<'a, T> {
@@ -272,6 +377,7 @@ impl<'a, T> Request<'a, T> {
             method,
             resource: Resource::new(url, None),
             max_pages: None,
+            retry_pending: false,
         }
     }
 
@@ -296,6 +402,14 @@ impl<'a, T> Request<'a, T> {
         self.headers = headers;
     }
 
+    pub fn set_retry_pending(&mut self, retry_pending: bool) {
+        self.retry_pending = retry_pending;
+    }
+
+    pub fn retry_pending(&self) -> bool {
+        self.retry_pending
+    }
+
     pub fn set_url(&mut self, url: &str) {
         self.resource.url = url.to_string();
     }
@@ -317,6 +431,7 @@ pub enum Method {
     POST,
     PUT,
     PATCH,
+    DELETE,
 }
 
 impl<C: Cache<Resource>> HttpRunner for Client<C> {
@@ -371,6 +486,21 @@ impl<C: Cache<Resource>> HttpRunner for Client<C> {
             .get_max_pages(cmd.resource.api_operation.as_ref().unwrap());
         max_pages
     }
+
+    fn api_per_page(&self) -> Option<u32> {
+        Some(self.config.per_page())
+    }
+
+    fn api_retry_budget<T: Serialize>(&self, cmd: &Request<T>) -> Option<RetryBudget> {
+        cmd.resource
+            .api_operation
+            .as_ref()
+            .and_then(|api_operation| self.config.get_retry_budget(api_operation))
+    }
+
+    fn strict_contract(&self) -> bool {
+        self.config.strict_contract()
+    }
 }
 
 pub struct Paginator<'a, R, T> {
@@ -460,6 +590,39 @@ impl<T: Serialize, R: HttpRunner<Response = HttpResponse>> Iterator for Paginato
     }
 }
 
+/// Resolves the `host:port` the HTTP agent connects to, honoring the DNS
+/// overrides and the IP family configured for the domain.
+fn resolve(netloc: &str, config: &dyn ConfigProperties) -> std::io::Result<Vec<SocketAddr>> {
+    let addrs = match netloc.rsplit_once(':') {
+        Some((host, port)) => {
+            let host = host.trim_start_matches('[').trim_end_matches(']');
+            match (config.dns_override(host), port.parse::<u16>()) {
+                (Some(ip), Ok(port)) => {
+                    log_debug!("Resolving {} to {} as per config", host, ip);
+                    vec![SocketAddr::new(ip, port)]
+                }
+                _ => netloc.to_socket_addrs()?.collect(),
+            }
+        }
+        None => netloc.to_socket_addrs()?.collect(),
+    };
+    let addrs = match config.ip_family() {
+        Some(IpFamily::Ipv4) => addrs.into_iter().filter(|addr| addr.is_ipv4()).collect(),
+        Some(IpFamily::Ipv6) => addrs.into_iter().filter(|addr| addr.is_ipv6()).collect(),
+        None => addrs,
+    };
+    if addrs.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AddrNotAvailable,
+            format!(
+                "No address of the configured IP family found for {}",
+                netloc
+            ),
+        ));
+    }
+    Ok(addrs)
+}
+
 #[cfg(test)]
 mod test {
     use throttle::NoThrottle;
@@ -549,7 +712,7 @@ mod test {
         let response = HttpResponse::builder().status(200).build().unwrap();
         let client = Arc::new(MockRunner::new(vec![response]));
         let request: Request<()> = Request::new("http://localhost", Method::GET);
-        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle::default());
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle);
         let backoff = Backoff::new(
             &client,
             0,
@@ -576,7 +739,7 @@ mod test {
             .unwrap();
         let client = Arc::new(MockRunner::new(vec![response2, response1]));
         let request: Request<()> = Request::new("http://localhost", Method::GET);
-        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle::default());
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle);
         let backoff = Backoff::new(
             &client,
             0,
@@ -596,7 +759,7 @@ mod test {
         let response2 = response_with_last_page();
         let client = Arc::new(MockRunner::new(vec![response2, response1]));
         let request: Request<()> = Request::new("http://localhost", Method::GET);
-        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle::default());
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle);
         let backoff = Backoff::new(
             &client,
             0,
@@ -619,7 +782,7 @@ mod test {
             .unwrap();
         let client = Arc::new(MockRunner::new(vec![response]));
         let request: Request<()> = Request::new("http://localhost", Method::GET);
-        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle::default());
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle);
         let backoff = Backoff::new(
             &client,
             0,
@@ -653,7 +816,7 @@ mod test {
             MockRunner::new(vec![response3, response2, response1]).with_config(ConfigMock::new(1)),
         );
         let request: Request<()> = Request::new("http://localhost", Method::GET);
-        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle::default());
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle);
         let backoff = Backoff::new(
             &client,
             0,
@@ -680,7 +843,7 @@ mod test {
         responses.reverse();
         let request: Request<()> = Request::new("http://localhost", Method::GET);
         let client = Arc::new(MockRunner::new(responses));
-        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle::default());
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle);
         let backoff = Backoff::new(
             &client,
             0,
@@ -813,7 +976,7 @@ mod test {
             .max_pages(1)
             .build()
             .unwrap();
-        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle::default());
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle);
         let backoff = Backoff::new(
             &client,
             0,
@@ -922,7 +1085,7 @@ mod test {
             Box::new(Exponential),
             Box::new(throttle::DynamicFixed),
         );
-        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle::default());
+        let throttler: Box<dyn ThrottleStrategy> = Box::new(NoThrottle);
         let paginator = Paginator::new(&client, request, "http://localhost", backoff, throttler);
         let responses = paginator.collect::<Vec<Result<HttpResponse>>>();
         assert_eq!(5, responses.len());
@@ -962,4 +1125,58 @@ mod test {
         assert_eq!(5, responses.len());
         assert_eq!(2, *throttler.throttled());
     }
+
+    struct ResolverConfig {
+        ip_family: Option<IpFamily>,
+        dns_override: Option<std::net::IpAddr>,
+    }
+
+    impl ConfigProperties for ResolverConfig {
+        fn api_token(&self) -> &str {
+            "1234"
+        }
+
+        fn cache_location(&self) -> Option<&str> {
+            None
+        }
+
+        fn ip_family(&self) -> Option<IpFamily> {
+            self.ip_family
+        }
+
+        fn dns_override(&self, host: &str) -> Option<std::net::IpAddr> {
+            if host == "gitlab.com" {
+                self.dns_override
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_host_with_dns_override() {
+        let config = ResolverConfig {
+            ip_family: None,
+            dns_override: Some("10.0.0.5".parse().unwrap()),
+        };
+        let addrs = resolve("gitlab.com:443", &config).unwrap();
+        assert_eq!(vec!["10.0.0.5:443".parse::<SocketAddr>().unwrap()], addrs);
+    }
+
+    #[test]
+    fn test_resolve_filters_by_ip_family() {
+        let config = ResolverConfig {
+            ip_family: Some(IpFamily::Ipv4),
+            dns_override: None,
+        };
+        let addrs = resolve("127.0.0.1:443", &config).unwrap();
+        assert_eq!(vec!["127.0.0.1:443".parse::<SocketAddr>().unwrap()], addrs);
+        let config = ResolverConfig {
+            ip_family: Some(IpFamily::Ipv6),
+            dns_override: Some("10.0.0.5".parse().unwrap()),
+        };
+        assert!(resolve("[::1]:443", &config).is_ok());
+        assert!(resolve("127.0.0.1:443", &config).is_err());
+        assert!(resolve("gitlab.com:443", &config).is_err());
+    }
 }
@@ -0,0 +1,195 @@
+//! Circuit breaker for remotes that keep failing. After a number of
+//! consecutive server or transport errors against a domain, requests fail
+//! fast during a cool-down period instead of waiting on a host that is down.
+//! The state is kept in a `circuit` directory under the cache location, so it
+//! is shared across gitar invocations, ex. scripts calling gitar in a loop.
+//! Being outside of the cached responses, pruning the cache leaves it alone.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api_defaults::{CIRCUIT_BREAKER_COOL_DOWN_SECONDS, CIRCUIT_BREAKER_FAILURE_THRESHOLD};
+use crate::config::ConfigProperties;
+use crate::error::GRError;
+use crate::time::{now_epoch_seconds, Seconds};
+use crate::{log_debug, log_error, Result};
+
+/// Directory under the cache location holding the state of each domain.
+const STATE_DIR: &str = "circuit";
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+struct CircuitState {
+    consecutive_failures: u32,
+    /// Epoch seconds until which requests are not sent.
+    open_until: u64,
+}
+
+pub struct CircuitBreaker {
+    domain: String,
+    state_file: Option<PathBuf>,
+    force: bool,
+    now: fn() -> Seconds,
+    state: Mutex<CircuitState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(domain: &str, config: &dyn ConfigProperties) -> Self {
+        let state_file = config
+            .cache_location()
+            .filter(|location| !location.is_empty())
+            .map(|location| {
+                PathBuf::from(location)
+                    .join(STATE_DIR)
+                    .join(format!("{}.json", domain))
+            });
+        CircuitBreaker::with_state_file(domain, state_file, config.force_circuit())
+    }
+
+    pub fn with_state_file(domain: &str, state_file: Option<PathBuf>, force: bool) -> Self {
+        let state = state_file
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        CircuitBreaker {
+            domain: domain.to_string(),
+            state_file,
+            force,
+            now: now_epoch_seconds,
+            state: Mutex::new(state),
+        }
+    }
+
+    pub fn with_now(mut self, now: fn() -> Seconds) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Errors if the circuit is open, unless forced on the command line.
+    pub fn check(&self) -> Result<()> {
+        let state = self.state.lock().unwrap();
+        let now = *(self.now)();
+        if state.open_until <= now {
+            return Ok(());
+        }
+        if self.force {
+            log_debug!(
+                "Circuit for {} is open, forced to send request",
+                self.domain
+            );
+            return Ok(());
+        }
+        Err(GRError::CircuitOpen(format!(
+            "{} failed {} consecutive times. Requests are blocked for {} more seconds. \
+             Use --force-circuit to send them anyway",
+            self.domain,
+            state.consecutive_failures,
+            state.open_until - now
+        ))
+        .into())
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        // Once the cool-down has elapsed, the next failure opens the circuit
+        // again straight away.
+        if state.consecutive_failures >= CIRCUIT_BREAKER_FAILURE_THRESHOLD {
+            log_error!("Opening circuit for {}", self.domain);
+            state.open_until = *(self.now)() + CIRCUIT_BREAKER_COOL_DOWN_SECONDS;
+        }
+        self.persist(&state);
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        if *state != CircuitState::default() {
+            *state = CircuitState::default();
+            self.persist(&state);
+        }
+    }
+
+    fn persist(&self, state: &CircuitState) {
+        if let Some(path) = &self.state_file {
+            let data = serde_json::to_string(state).unwrap_or_default();
+            let written = match path.parent() {
+                Some(dir) => fs::create_dir_all(dir).and_then(|_| fs::write(path, data)),
+                None => fs::write(path, data),
+            };
+            if let Err(err) = written {
+                log_debug!("Could not persist circuit state to {:?}: {}", path, err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn now() -> Seconds {
+        Seconds::new(1000)
+    }
+
+    fn later() -> Seconds {
+        Seconds::new(1000 + CIRCUIT_BREAKER_COOL_DOWN_SECONDS)
+    }
+
+    fn fail(circuit: &CircuitBreaker, times: u32) {
+        for _ in 0..times {
+            circuit.record_failure();
+        }
+    }
+
+    #[test]
+    fn test_circuit_opens_after_consecutive_failures() {
+        let circuit = CircuitBreaker::with_state_file("gitlab.com", None, false).with_now(now);
+        fail(&circuit, CIRCUIT_BREAKER_FAILURE_THRESHOLD - 1);
+        assert!(circuit.check().is_ok());
+        circuit.record_failure();
+        let err = circuit.check().unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::CircuitOpen(msg)) => assert!(msg.contains("gitlab.com")),
+            _ => panic!("Expected GRError::CircuitOpen"),
+        }
+    }
+
+    #[test]
+    fn test_circuit_success_resets_failures() {
+        let circuit = CircuitBreaker::with_state_file("gitlab.com", None, false).with_now(now);
+        fail(&circuit, CIRCUIT_BREAKER_FAILURE_THRESHOLD - 1);
+        circuit.record_success();
+        circuit.record_failure();
+        assert!(circuit.check().is_ok());
+    }
+
+    #[test]
+    fn test_circuit_closes_after_cool_down() {
+        let circuit = CircuitBreaker::with_state_file("gitlab.com", None, false).with_now(now);
+        fail(&circuit, CIRCUIT_BREAKER_FAILURE_THRESHOLD);
+        let circuit = circuit.with_now(later);
+        assert!(circuit.check().is_ok());
+    }
+
+    #[test]
+    fn test_forced_circuit_sends_requests() {
+        let circuit = CircuitBreaker::with_state_file("gitlab.com", None, true).with_now(now);
+        fail(&circuit, CIRCUIT_BREAKER_FAILURE_THRESHOLD);
+        assert!(circuit.check().is_ok());
+    }
+
+    #[test]
+    fn test_circuit_state_is_shared_across_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = Some(dir.path().join("circuit_gitlab.com.json"));
+        let circuit =
+            CircuitBreaker::with_state_file("gitlab.com", state_file.clone(), false).with_now(now);
+        fail(&circuit, CIRCUIT_BREAKER_FAILURE_THRESHOLD);
+        let circuit =
+            CircuitBreaker::with_state_file("gitlab.com", state_file, false).with_now(now);
+        assert!(circuit.check().is_err());
+    }
+}
@@ -65,6 +65,12 @@ single_page="1d"
 gist="1d"
 # Expire repository tags immediately
 repository_tags="0s"
+# Expire read issues in 5 minutes
+issue="5m"
+# Expire the authenticated user identity in 30 days
+user="30d"
+# Expire read deployments immediately
+deployment="0s"
 
 [<DOMAIN>.max_pages_api]
 
@@ -82,6 +88,8 @@ release=10
 gist=5
 # Get up to 10 pages of tags when listing
 repository_tags=10
+# Get up to 10 pages of issues when listing
+issue=10
 
 ### Other domains - add more if needed
 "#;
@@ -144,7 +152,7 @@ mod test {
         let mut writer = Vec::new();
         let result = generate_and_persist(options, &mut writer);
         assert!(result.is_ok());
-        assert!(writer.len() > 0);
+        assert!(!writer.is_empty());
         let content = String::from_utf8(writer).unwrap();
         assert!(content.contains("gitweb_com"));
     }
@@ -4,6 +4,7 @@ use crate::{
         merge_request::MergeRequestResponse,
         project::{Member, Project},
     },
+    config::RetryBudget,
     http::{self, Headers, Request},
     log_info,
     remote::RemoteURL,
@@ -39,6 +40,19 @@ pub trait HttpRunner {
     fn run<T: Serialize>(&self, cmd: &mut Request<T>) -> Result<Self::Response>;
     /// Return the number of API MAX PAGES allowed for the given Request.
     fn api_max_pages<T: Serialize>(&self, cmd: &Request<T>) -> u32;
+    /// Return the number of results per page to request when listing. None
+    /// leaves the page size to the remote.
+    fn api_per_page(&self) -> Option<u32> {
+        None
+    }
+    /// Return the retry budget configured for the given Request, if any.
+    fn api_retry_budget<T: Serialize>(&self, _cmd: &Request<T>) -> Option<RetryBudget> {
+        None
+    }
+    /// Whether payloads not matching the expected contract are errors.
+    fn strict_contract(&self) -> bool {
+        false
+    }
 }
 
 type Title = String;
@@ -82,6 +96,10 @@ pub struct HttpResponse {
     pub status: i32,
     #[builder(default)]
     pub body: String,
+    /// Raw body of responses that are not valid UTF-8, ex. ranges of a zip
+    /// archive. Empty otherwise.
+    #[builder(default)]
+    pub bytes: Vec<u8>,
     /// Optional headers. Mostly used by HTTP downstream HTTP responses
     #[builder(setter(into, strip_option), default)]
     pub headers: Option<Headers>,
@@ -105,6 +123,15 @@ pub enum ResponseField {
 }
 
 impl HttpResponse {
+    /// Raw body of the response, whether it is text or binary.
+    pub fn body_bytes(&self) -> &[u8] {
+        if self.bytes.is_empty() {
+            self.body.as_bytes()
+        } else {
+            &self.bytes
+        }
+    }
+
     pub fn header(&self, key: &str) -> Option<&str> {
         self.headers
             .as_ref()
@@ -131,11 +158,14 @@ impl HttpResponse {
     pub fn is_ok(&self, method: &http::Method) -> bool {
         match method {
             http::Method::HEAD => self.status == 200,
-            http::Method::GET => self.status == 200,
+            // 206 Partial Content answers range requests.
+            http::Method::GET => self.status == 200 || self.status == 206,
             http::Method::POST => {
                 self.status >= 200 && self.status < 300 || self.status == 409 || self.status == 422
             }
-            http::Method::PATCH | http::Method::PUT => self.status >= 200 && self.status < 300,
+            http::Method::PATCH | http::Method::PUT | http::Method::DELETE => {
+                self.status >= 200 && self.status < 300
+            }
         }
     }
 
@@ -153,6 +183,9 @@ fn parse_link_headers(link: &str) -> PageHeader {
         static ref RE_URL: Regex = Regex::new(r#"<([^>]+)>;\s*rel="([^"]+)""#).unwrap();
         static ref RE_PAGE_NUMBER: Regex = Regex::new(r"[^(per_)]page=(\d+)").unwrap();
         static ref RE_PER_PAGE: Regex = Regex::new(r"per_page=(\d+)").unwrap();
+        // Gitlab keyset and Github cursor based links.
+        static ref RE_CURSOR: Regex =
+            Regex::new(r"[?&](?:cursor|id_after|id_before|after)=[^&]+").unwrap();
     }
     let mut page_header = PageHeader::new();
     'links: for cap in RE_URL.captures_iter(link) {
@@ -169,6 +202,12 @@ fn parse_link_headers(link: &str) -> PageHeader {
                 page_header.per_page = api_defaults::DEFAULT_PER_PAGE;
             };
             let url = cap[1].to_string();
+            // Cursor links can still carry the page number of the first
+            // request, which is meaningless. The next url is followed as is.
+            if RE_CURSOR.is_match(&url) {
+                page_header.set_next_page(Page::new(&url, 0));
+                continue 'links;
+            }
             if let Some(page_cap) = RE_PAGE_NUMBER.captures(&url) {
                 if page_cap.len() == 2 {
                     let page_number = page_cap[1].to_string();
@@ -525,6 +564,26 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_link_headers_gitlab_keyset_next_page() {
+        let link = r#"<https://gitlab.com/api/v4/projects/15/jobs?id=15&order_by=id&page=1&pagination=keyset&per_page=100&sort=desc&cursor=eyJpZCI6IjQyIn0>; rel="next", <https://gitlab.com/api/v4/projects/15/jobs?id=15&order_by=id&pagination=keyset&per_page=100&sort=desc>; rel="first""#;
+        let page_headers = parse_link_headers(link);
+        let next_page = page_headers.next_page().unwrap();
+        assert_eq!(0, next_page.number);
+        assert!(next_page.url().ends_with("cursor=eyJpZCI6IjQyIn0"));
+        assert_eq!(100, page_headers.per_page);
+        assert_eq!(None, page_headers.last_page());
+    }
+
+    #[test]
+    fn test_link_headers_github_cursor_next_page() {
+        let link = r#"<https://api.github.com/orgs/gitar/audit-log?per_page=100&after=MS42ODk&before=>; rel="next""#;
+        let page_headers = parse_link_headers(link);
+        let next_page = page_headers.next_page().unwrap();
+        assert_eq!(0, next_page.number);
+        assert!(next_page.url().contains("after=MS42ODk"));
+    }
+
     #[test]
     fn test_link_headers_get_per_page_multiple_pages() {
         let link = r#"<https://gitlab-web/api/v4/projects/15/pipelines?id=15&order_by=id&page=2&per_page=20&sort=desc>; rel="next", <https://gitlab-web/api/v4/projects/15/pipelines?id=15&order_by=id&page=1&per_page=20&sort=desc>; rel="first", <https://gitlab-web/api/v4/projects/15/pipelines?id=15&order_by=id&page=91&per_page=20&sort=desc>; rel="last""#;
@@ -1,7 +1,12 @@
+// Test mocks share state through RefCell wrapped in Arc
+#![cfg_attr(test, allow(clippy::arc_with_non_send_sync))]
+
 use std::path::PathBuf;
 
 pub mod api_defaults;
 pub mod api_traits;
+pub mod auth;
+pub mod bitbucket;
 pub mod cache;
 pub mod cli;
 pub mod config;
@@ -35,10 +40,6 @@ extern crate lazy_static;
 #[macro_use]
 extern crate derive_builder;
 
-fn json_load_page(data: &str) -> Result<Vec<serde_json::Value>> {
-    serde_json::from_str(data).map_err(|e| error::gen(e.to_string()))
-}
-
 fn json_loads(data: &str) -> Result<serde_json::Value> {
     serde_json::from_str(data).map_err(|e| error::gen(e.to_string()))
 }
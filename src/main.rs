@@ -1,14 +1,19 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use env_logger::Env;
 use gr::{
+    api_traits::RemoteCredentials,
     cli::{
-        browse::BrowseOptions, merge_request::MergeRequestOptions, parse_cli,
+        auth::AuthOptions, browse::BrowseOptions, command_config_files, docker::DockerOptions,
+        flow::FlowOptions, merge_request::MergeRequestOptions, my::MyOptions, parse_cli,
         trending::TrendingOptions, CliOptions,
     },
     cmds::{self, browse, cicd, docker, merge_request, project},
+    config::RepoSet,
+    error::GRError,
     init,
-    remote::{self, CliDomainRequirements, ConfigFilePath, RemoteURL},
+    remote::{self, CacheType, CliDomainRequirements, ConfigFilePath, RemoteURL},
     shell::BlockingCommand,
     Result,
 };
@@ -27,9 +32,17 @@ fn main() -> Result<()> {
         2 => env_logger::init_from_env(Env::default().default_filter_or("debug")),
         _ => (),
     }
+    let verbose = cli_args.verbose;
     match handle_cli_options(cli_options, config_file_path, cli_args) {
         Err(err) => {
-            eprintln!("{}", err);
+            // -vv prints the whole chain of causes, the full body of a failed
+            // request is in the debug log. Otherwise the chain is joined in
+            // one line.
+            if verbose > 1 {
+                eprintln!("{:?}", err);
+            } else {
+                eprintln!("{:#}", err);
+            }
             std::process::exit(1);
         }
         Ok(_) => Ok(()),
@@ -42,6 +55,23 @@ fn handle_cli_options(
     cli_args: gr::cli::CliArgs,
 ) -> Result<()> {
     match cli_options {
+        CliOptions::MergeRequest(MergeRequestOptions::ListRepoSet {
+            name,
+            cli_args: list_cli_args,
+        }) => {
+            let mut repo_sets: HashMap<String, RepoSet> = gr::config::read_table(
+                &command_config_files(cli_args.config.as_deref()),
+                gr::config::REPOS_TABLE,
+            )?;
+            let repo_set = repo_sets.remove(&name).ok_or_else(|| {
+                GRError::ConfigurationError(format!("Repo set '{}' not found in config", name))
+            })?;
+            merge_request::list_merge_requests_repo_set(
+                repo_set.repos,
+                list_cli_args,
+                config_file_path,
+            )
+        }
         CliOptions::MergeRequest(options) => {
             let url = if let MergeRequestOptions::Create(opts) = &options {
                 // This is a create merge request operation. The remote URL that
@@ -116,6 +146,21 @@ fn handle_cli_options(
                 url.path().to_string(),
             )
         }
+        CliOptions::Auth(AuthOptions::GitCredential(action)) => {
+            // Called by git, the remote's host is read from stdin.
+            cmds::auth::git_credential(
+                action,
+                std::io::stdin().lock(),
+                std::io::stdout(),
+                |domain| domain_credentials(config_file_path, domain),
+            )
+        }
+        CliOptions::Docker(DockerOptions::LoginHelper(action)) => {
+            // Called by docker, the registry's domain is read from stdin.
+            docker::login_helper(action, std::io::stdin(), std::io::stdout(), |domain| {
+                domain_credentials(config_file_path, domain)
+            })
+        }
         CliOptions::Docker(options) => {
             let requirements = vec![
                 CliDomainRequirements::RepoArgs,
@@ -130,6 +175,34 @@ fn handle_cli_options(
                 url.path().to_string(),
             )
         }
+        CliOptions::Issue(options) => {
+            let requirements = vec![
+                CliDomainRequirements::RepoArgs,
+                CliDomainRequirements::CdInLocalRepo,
+            ];
+            let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
+            let config = remote::read_config(config_file_path, &url)?;
+            cmds::issue::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
+        }
+        CliOptions::Label(options) => {
+            let requirements = vec![
+                CliDomainRequirements::RepoArgs,
+                CliDomainRequirements::CdInLocalRepo,
+            ];
+            let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
+            let config = remote::read_config(config_file_path, &url)?;
+            cmds::label::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
+        }
         CliOptions::Release(options) => {
             let requirements = vec![
                 CliDomainRequirements::RepoArgs,
@@ -144,6 +217,51 @@ fn handle_cli_options(
                 url.path().to_string(),
             )
         }
+        CliOptions::Search(options) => {
+            let requirements = vec![
+                CliDomainRequirements::RepoArgs,
+                CliDomainRequirements::CdInLocalRepo,
+            ];
+            let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
+            let config = remote::read_config(config_file_path, &url)?;
+            cmds::search::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
+        }
+        CliOptions::Deployment(options) => {
+            let requirements = vec![
+                CliDomainRequirements::RepoArgs,
+                CliDomainRequirements::CdInLocalRepo,
+            ];
+            let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
+            let config = remote::read_config(config_file_path, &url)?;
+            cmds::deployment::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
+        }
+        CliOptions::Report(options) => {
+            let requirements = vec![
+                CliDomainRequirements::RepoArgs,
+                CliDomainRequirements::CdInLocalRepo,
+            ];
+            let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
+            let config = remote::read_config(config_file_path, &url)?;
+            cmds::report::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
+        }
+        CliOptions::My(MyOptions::MergeRequestAllDomains(cli_args)) => {
+            cmds::my::list_merge_requests_all_domains(cli_args, config_file_path)
+        }
         CliOptions::My(options) => {
             let requirements = vec![
                 CliDomainRequirements::DomainArgs,
@@ -177,8 +295,28 @@ fn handle_cli_options(
             ];
             let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
             let config = remote::read_config(config_file_path, &url)?;
-            cmds::cache::execute(options, config)
+            cmds::cache::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
         }
+        CliOptions::Contract(options) => {
+            let requirements = vec![
+                CliDomainRequirements::RepoArgs,
+                CliDomainRequirements::CdInLocalRepo,
+            ];
+            let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
+            let config = remote::read_config(config_file_path, &url)?;
+            cmds::contract::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
+        }
+        CliOptions::ServeMock(options) => cmds::serve_mock::execute(options),
         CliOptions::Manual => browse::execute(
             BrowseOptions::Manual,
             Arc::new(gr::config::ConfigFile::default()),
@@ -186,6 +324,21 @@ fn handle_cli_options(
             "".to_string(),
         ),
         CliOptions::Amps(options) => cmds::amps::execute(options, config_file_path),
+        CliOptions::Flow(FlowOptions::Run(args)) => {
+            let flows = gr::config::read_table(
+                &command_config_files(cli_args.config.as_deref()),
+                gr::config::FLOW_TABLE,
+            )?;
+            cmds::flow::run(
+                args,
+                &flows,
+                &cli_args,
+                std::io::stdout(),
+                |options, step_args| {
+                    handle_cli_options(options, ConfigFilePath::new(&step_args), step_args)
+                },
+            )
+        }
         CliOptions::User(options) => {
             let requirements = vec![
                 CliDomainRequirements::DomainArgs,
@@ -201,5 +354,37 @@ fn handle_cli_options(
                 url.path().to_string(),
             )
         }
+        CliOptions::Remote(options) => {
+            let requirements = vec![
+                CliDomainRequirements::DomainArgs,
+                CliDomainRequirements::RepoArgs,
+                CliDomainRequirements::CdInLocalRepo,
+            ];
+            let url = remote::url(&cli_args, &requirements, &BlockingCommand, &None)?;
+            let config = remote::read_config(config_file_path, &url)?;
+            cmds::remote::execute(
+                options,
+                config,
+                url.domain().to_string(),
+                url.path().to_string(),
+            )
+        }
     }
 }
+
+/// Credentials of a domain for credential helpers called by git or docker,
+/// outside of any repository.
+fn domain_credentials(
+    config_file_path: ConfigFilePath,
+    domain: &str,
+) -> Result<Arc<dyn RemoteCredentials + Send + Sync>> {
+    let url = RemoteURL::new(domain.to_string(), "".to_string());
+    let config = remote::read_config(config_file_path, &url)?;
+    remote::get_credentials(
+        domain.to_string(),
+        "".to_string(),
+        config,
+        None,
+        CacheType::None,
+    )
+}
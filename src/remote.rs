@@ -3,22 +3,30 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 
 use crate::api_traits::{
-    Cicd, CicdJob, CicdRunner, CodeGist, CommentMergeRequest, ContainerRegistry, Deploy,
-    DeployAsset, MergeRequest, ProjectMember, RemoteProject, RemoteTag, TrendingProjectURL,
-    UserInfo,
+    Cicd, CicdArtifact, CicdJob, CicdJobLog, CicdJobTiming, CicdPipelineStatus, CicdRunner,
+    CicdTestReport, CicdUsage, CodeGist, CommentMergeRequest, ContainerRegistry, ContractRecorder,
+    Deploy, DeployAsset, Deployment, Issue, Label, MergeQueue, MergeRequest,
+    MergeRequestDependency, MergeRequestDiff, MergeRequestDiscussion, MergeRequestReaction,
+    MergeRequestRebase, MergeRequestReview, MergeRequestSuggestion, ProjectAccessCheck,
+    ProjectBlame, ProjectDependency, ProjectInvitation, ProjectLanguages, ProjectMember,
+    ProjectMemberAccess, ProjectMirror, RemoteCredentials, RemoteInstance, RemoteProject,
+    RemoteTag, ReviewMergeRequest, Search, TrendingProjectURL, UserActivity, UserInfo,
 };
+use crate::bitbucket::Bitbucket;
 use crate::cache::{filesystem::FileCache, nocache::NoCache};
-use crate::config::{env_token, ConfigFile, NoConfig};
-use crate::display::Format;
+use crate::config::{ci_job_token, env_token, ConfigFile, NoConfig};
+use crate::display::{ColumnTransform, Format};
 use crate::error::GRError;
 use crate::github::Github;
 use crate::gitlab::Gitlab;
+use crate::http::circuit::CircuitBreaker;
 use crate::io::{CmdInfo, HttpResponse, HttpRunner, ShellResponse, TaskRunner};
 use crate::time::Milliseconds;
-use crate::{cli, error, get_default_config_path, http, log_debug, log_info};
+use crate::{cli, get_default_config_path, http, log_debug, log_info};
 use crate::{git, Result};
 use std::sync::Arc;
 
+pub mod contract;
 pub mod query;
 
 /// List cli args can be used across multiple APIs that support pagination.
@@ -39,8 +47,16 @@ pub struct ListRemoteCliArgs {
     #[builder(default)]
     pub created_before: Option<String>,
     #[builder(default)]
+    pub updated_after: Option<String>,
+    #[builder(default)]
+    pub updated_before: Option<String>,
+    #[builder(default)]
+    pub per_page: Option<u32>,
+    #[builder(default)]
     pub sort: ListSortMode,
     #[builder(default)]
+    pub order_by: Option<ListOrderBy>,
+    #[builder(default)]
     pub flush: bool,
     #[builder(default)]
     pub throttle_time: Option<Milliseconds>,
@@ -66,6 +82,12 @@ pub struct GetRemoteCliArgs {
     pub cache_args: CacheCliArgs,
     #[builder(default)]
     pub display_optional: bool,
+    /// Column name and transform applied to its values when printing.
+    #[builder(default)]
+    pub transforms: Vec<(String, ColumnTransform)>,
+    /// Print durations and counts as plain numbers.
+    #[builder(default)]
+    pub raw_numbers: bool,
     #[builder(default)]
     pub backoff_max_retries: u32,
     #[builder(default)]
@@ -108,7 +130,18 @@ pub struct ListBodyArgs {
     #[builder(default)]
     pub created_before: Option<String>,
     #[builder(default)]
+    pub updated_after: Option<String>,
+    #[builder(default)]
+    pub updated_before: Option<String>,
+    /// Results per page. Defaults to the page size configured for the remote.
+    #[builder(default)]
+    pub per_page: Option<u32>,
+    #[builder(default)]
     pub sort_mode: ListSortMode,
+    /// Field the remote orders the results by, in the sort mode direction.
+    /// Results are kept in the order given by the remote.
+    #[builder(default)]
+    pub order_by: Option<ListOrderBy>,
     #[builder(default)]
     pub flush: bool,
     #[builder(default)]
@@ -126,21 +159,35 @@ impl ListBodyArgs {
     }
 }
 
+/// Builds the list body args out of the list options given on the command
+/// line. Each group of options is applied by its own combinator on the
+/// builder, so new filters can be added without touching the others.
 pub fn validate_from_to_page(remote_cli_args: &ListRemoteCliArgs) -> Result<Option<ListBodyArgs>> {
-    if remote_cli_args.page_number.is_some() {
-        return Ok(Some(
-            ListBodyArgs::builder()
-                .page(remote_cli_args.page_number.unwrap())
-                .max_pages(1)
-                .sort_mode(remote_cli_args.sort.clone())
-                .created_after(remote_cli_args.created_after.clone())
-                .created_before(remote_cli_args.created_before.clone())
-                .build()
-                .unwrap(),
-        ));
+    Ok(Some(
+        ListBodyArgs::builder()
+            .list_options(remote_cli_args)
+            .page_range(page_range(remote_cli_args)?)
+            .date_filters(remote_cli_args)
+            .build()
+            .unwrap(),
+    ))
+}
+
+/// First page to fetch and the maximum number of pages from there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PageRange {
+    page: i64,
+    max_pages: i64,
+}
+
+/// Validates the page options. A given page number takes precedence over
+/// the from and to pages.
+fn page_range(remote_cli_args: &ListRemoteCliArgs) -> Result<Option<PageRange>> {
+    if let Some(page) = remote_cli_args.page_number {
+        return Ok(Some(PageRange { page, max_pages: 1 }));
     }
     // TODO - this can probably be validated at the CLI level
-    let body_args = match (remote_cli_args.from_page, remote_cli_args.to_page) {
+    match (remote_cli_args.from_page, remote_cli_args.to_page) {
         (Some(from_page), Some(to_page)) => {
             if from_page < 0 || to_page < 0 {
                 return Err(GRError::PreconditionNotMet(
@@ -154,25 +201,13 @@ pub fn validate_from_to_page(remote_cli_args: &ListRemoteCliArgs) -> Result<Opti
                 )
                 .into());
             }
-
-            let max_pages = to_page - from_page + 1;
-            Some(
-                ListBodyArgs::builder()
-                    .page(from_page)
-                    .max_pages(max_pages)
-                    .sort_mode(remote_cli_args.sort.clone())
-                    .flush(remote_cli_args.flush)
-                    .throttle_time(remote_cli_args.throttle_time)
-                    .throttle_range(remote_cli_args.throttle_range)
-                    .get_args(remote_cli_args.get_args.clone())
-                    .build()
-                    .unwrap(),
-            )
+            Ok(Some(PageRange {
+                page: from_page,
+                max_pages: to_page - from_page + 1,
+            }))
         }
         (Some(_), None) => {
-            return Err(
-                GRError::PreconditionNotMet("from_page requires the to_page".to_string()).into(),
-            );
+            Err(GRError::PreconditionNotMet("from_page requires the to_page".to_string()).into())
         }
         (None, Some(to_page)) => {
             if to_page < 0 {
@@ -181,137 +216,39 @@ pub fn validate_from_to_page(remote_cli_args: &ListRemoteCliArgs) -> Result<Opti
                 )
                 .into());
             }
-            Some(
-                ListBodyArgs::builder()
-                    .page(1)
-                    .max_pages(to_page)
-                    .sort_mode(remote_cli_args.sort.clone())
-                    .flush(remote_cli_args.flush)
-                    .throttle_time(remote_cli_args.throttle_time)
-                    .throttle_range(remote_cli_args.throttle_range)
-                    .get_args(remote_cli_args.get_args.clone())
-                    .build()
-                    .unwrap(),
-            )
-        }
-        (None, None) => None,
-    };
-    match (
-        remote_cli_args.created_after.clone(),
-        remote_cli_args.created_before.clone(),
-    ) {
-        (Some(created_after), Some(created_before)) => {
-            if let Some(body_args) = &body_args {
-                return Ok(Some(
-                    ListBodyArgs::builder()
-                        .page(body_args.page.unwrap())
-                        .max_pages(body_args.max_pages.unwrap())
-                        .created_after(Some(created_after.to_string()))
-                        .created_before(Some(created_before.to_string()))
-                        .sort_mode(remote_cli_args.sort.clone())
-                        .flush(remote_cli_args.flush)
-                        .throttle_time(remote_cli_args.throttle_time)
-                        .throttle_range(remote_cli_args.throttle_range)
-                        .get_args(remote_cli_args.get_args.clone())
-                        .build()
-                        .unwrap(),
-                ));
-            }
-            Ok(Some(
-                ListBodyArgs::builder()
-                    .created_after(Some(created_after.to_string()))
-                    .created_before(Some(created_before.to_string()))
-                    .sort_mode(remote_cli_args.sort.clone())
-                    .flush(remote_cli_args.flush)
-                    .throttle_time(remote_cli_args.throttle_time)
-                    .throttle_range(remote_cli_args.throttle_range)
-                    .get_args(remote_cli_args.get_args.clone())
-                    .build()
-                    .unwrap(),
-            ))
-        }
-        (Some(created_after), None) => {
-            if let Some(body_args) = &body_args {
-                return Ok(Some(
-                    ListBodyArgs::builder()
-                        .page(body_args.page.unwrap())
-                        .max_pages(body_args.max_pages.unwrap())
-                        .created_after(Some(created_after.to_string()))
-                        .sort_mode(remote_cli_args.sort.clone())
-                        .flush(remote_cli_args.flush)
-                        .throttle_time(remote_cli_args.throttle_time)
-                        .throttle_range(remote_cli_args.throttle_range)
-                        .get_args(remote_cli_args.get_args.clone())
-                        .build()
-                        .unwrap(),
-                ));
-            }
-            Ok(Some(
-                ListBodyArgs::builder()
-                    .created_after(Some(created_after.to_string()))
-                    .sort_mode(remote_cli_args.sort.clone())
-                    .flush(remote_cli_args.flush)
-                    .throttle_time(remote_cli_args.throttle_time)
-                    .throttle_range(remote_cli_args.throttle_range)
-                    .get_args(remote_cli_args.get_args.clone())
-                    .build()
-                    .unwrap(),
-            ))
-        }
-        (None, Some(created_before)) => {
-            if let Some(body_args) = &body_args {
-                return Ok(Some(
-                    ListBodyArgs::builder()
-                        .page(body_args.page.unwrap())
-                        .max_pages(body_args.max_pages.unwrap())
-                        .created_before(Some(created_before.to_string()))
-                        .sort_mode(remote_cli_args.sort.clone())
-                        .flush(remote_cli_args.flush)
-                        .throttle_time(remote_cli_args.throttle_time)
-                        .throttle_range(remote_cli_args.throttle_range)
-                        .get_args(remote_cli_args.get_args.clone())
-                        .build()
-                        .unwrap(),
-                ));
-            }
-            Ok(Some(
-                ListBodyArgs::builder()
-                    .created_before(Some(created_before.to_string()))
-                    .sort_mode(remote_cli_args.sort.clone())
-                    .flush(remote_cli_args.flush)
-                    .throttle_time(remote_cli_args.throttle_time)
-                    .throttle_range(remote_cli_args.throttle_range)
-                    .get_args(remote_cli_args.get_args.clone())
-                    .build()
-                    .unwrap(),
-            ))
+            Ok(Some(PageRange {
+                page: 1,
+                max_pages: to_page,
+            }))
         }
-        (None, None) => {
-            if let Some(body_args) = &body_args {
-                return Ok(Some(
-                    ListBodyArgs::builder()
-                        .page(body_args.page.unwrap())
-                        .max_pages(body_args.max_pages.unwrap())
-                        .sort_mode(remote_cli_args.sort.clone())
-                        .flush(remote_cli_args.flush)
-                        .throttle_time(remote_cli_args.throttle_time)
-                        .throttle_range(remote_cli_args.throttle_range)
-                        .get_args(remote_cli_args.get_args.clone())
-                        .build()
-                        .unwrap(),
-                ));
-            }
-            Ok(Some(
-                ListBodyArgs::builder()
-                    .sort_mode(remote_cli_args.sort.clone())
-                    .flush(remote_cli_args.flush)
-                    .throttle_time(remote_cli_args.throttle_time)
-                    .throttle_range(remote_cli_args.throttle_range)
-                    .get_args(remote_cli_args.get_args.clone())
-                    .build()
-                    .unwrap(),
-            ))
+        (None, None) => Ok(None),
+    }
+}
+
+impl ListBodyArgsBuilder {
+    /// Page size, sorting, streaming, throttling and display options.
+    fn list_options(&mut self, remote_cli_args: &ListRemoteCliArgs) -> &mut Self {
+        self.per_page(remote_cli_args.per_page)
+            .sort_mode(remote_cli_args.sort.clone())
+            .order_by(remote_cli_args.order_by.clone())
+            .flush(remote_cli_args.flush)
+            .throttle_time(remote_cli_args.throttle_time)
+            .throttle_range(remote_cli_args.throttle_range)
+            .get_args(remote_cli_args.get_args.clone())
+    }
+
+    fn page_range(&mut self, range: Option<PageRange>) -> &mut Self {
+        if let Some(range) = range {
+            self.page(range.page).max_pages(range.max_pages);
         }
+        self
+    }
+
+    fn date_filters(&mut self, remote_cli_args: &ListRemoteCliArgs) -> &mut Self {
+        self.created_after(remote_cli_args.created_after.clone())
+            .created_before(remote_cli_args.created_before.clone())
+            .updated_after(remote_cli_args.updated_after.clone())
+            .updated_before(remote_cli_args.updated_before.clone())
     }
 }
 
@@ -340,6 +277,20 @@ impl URLQueryParamBuilder {
     }
 }
 
+/// Percent-encodes a query parameter value. Only unreserved characters are
+/// left as is. See https://datatracker.ietf.org/doc/html/rfc3986#section-2.3
+pub fn encode_query_param(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum ListSortMode {
     #[default]
@@ -347,15 +298,63 @@ pub enum ListSortMode {
     Desc,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum ListOrderBy {
+    Created,
+    Updated,
+    Title,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum CacheType {
     File,
     None,
 }
 
-use crate::config::ConfigProperties;
+use crate::config::{ConfigProperties, Provider};
+
+/// Provider serving a domain. The provider configured for the domain takes
+/// precedence, so self-hosted instances can live under any domain. Otherwise
+/// it is told by the domain name, ex. gitlab.mycompany.com is Gitlab.
+pub fn provider(domain: &str, config: &dyn ConfigProperties) -> Result<Provider> {
+    if let Some(provider) = config.provider() {
+        return Ok(provider);
+    }
+    if domain.starts_with("github") {
+        Ok(Provider::Github)
+    } else if domain.starts_with("gitlab") {
+        Ok(Provider::Gitlab)
+    } else if domain == "bitbucket.org" {
+        // Only Bitbucket Cloud is supported. Self-hosted Bitbucket Server
+        // has a different API.
+        Ok(Provider::Bitbucket)
+    } else {
+        Err(GRError::PreconditionNotMet(format!(
+            "Unsupported domain: {}. Set the provider of the domain in its \
+             configuration, ex. provider = \"gitlab\"",
+            domain
+        ))
+        .into())
+    }
+}
+
+/// Bitbucket implements a subset of the traits, marked with the `Bitbucket`
+/// token in the `get!` invocation. The rest are not supported.
+macro_rules! bitbucket_remote {
+    ($trait_name:ident, $config:ident, $domain:ident, $path:ident, $runner:ident, Bitbucket) => {
+        Arc::new(Bitbucket::new($config, &$domain, &$path, $runner))
+    };
+    ($trait_name:ident, $config:ident, $domain:ident, $path:ident, $runner:ident) => {
+        return Err(GRError::OperationNotSupported(format!(
+            "{} is not supported in Bitbucket",
+            stringify!($trait_name)
+        ))
+        .into())
+    };
+}
+
 macro_rules! get {
-    ($func_name:ident, $trait_name:ident) => {
+    ($func_name:ident, $trait_name:ident $(, $bitbucket:ident)?) => {
         paste::paste! {
             pub fn $func_name(
                 domain: String,
@@ -371,15 +370,26 @@ macro_rules! get {
                 log_debug!("no_cache_args: {:?}", no_cache_args);
                 log_debug!("cache location: {:?}", config.cache_location());
 
-                if cache_type == CacheType::None || no_cache_args || config.cache_location().is_none() {
+                // Cached responses are keyed by URL, so they must not be shared
+                // between impersonated users.
+                let sudo = config.sudo_username().is_some();
+                if cache_type == CacheType::None || no_cache_args || sudo || config.cache_location().is_none() {
                     log_info!("No cache used for {}", stringify!($func_name));
-                    let runner = Arc::new(http::Client::new(NoCache, config.clone(), refresh_cache));
+                    let circuit_breaker = CircuitBreaker::new(&domain, config.as_ref());
+                    let runner = Arc::new(
+                        http::Client::new(NoCache, config.clone(), refresh_cache)
+                            .with_circuit_breaker(circuit_breaker),
+                    );
                     [<create_remote_ $func_name>](domain, path, config, runner)
                 } else {
                     log_info!("File cache used for {}", stringify!($func_name));
                     let file_cache = FileCache::new(config.clone());
                     file_cache.validate_cache_location()?;
-                    let runner = Arc::new(http::Client::new(file_cache, config.clone(), refresh_cache));
+                    let circuit_breaker = CircuitBreaker::new(&domain, config.as_ref());
+                    let runner = Arc::new(
+                        http::Client::new(file_cache, config.clone(), refresh_cache)
+                            .with_circuit_breaker(circuit_breaker),
+                    );
                     [<create_remote_ $func_name>](domain, path, config, runner)
                 }
             }
@@ -393,15 +403,13 @@ macro_rules! get {
             where
                 R: HttpRunner<Response = HttpResponse> + Send + Sync + 'static,
             {
-                let github_domain_regex = regex::Regex::new(r"^github").unwrap();
-                let gitlab_domain_regex = regex::Regex::new(r"^gitlab").unwrap();
                 let remote: Arc<dyn $trait_name + Send + Sync + 'static> =
-                    if github_domain_regex.is_match(&domain) {
-                        Arc::new(Github::new(config, &domain, &path, runner))
-                    } else if gitlab_domain_regex.is_match(&domain) {
-                        Arc::new(Gitlab::new(config, &domain, &path, runner))
-                    } else {
-                        return Err(error::gen(format!("Unsupported domain: {}", &domain)));
+                    match provider(&domain, config.as_ref())? {
+                        Provider::Github => Arc::new(Github::new(config, &domain, &path, runner)),
+                        Provider::Gitlab => Arc::new(Gitlab::new(config, &domain, &path, runner)),
+                        Provider::Bitbucket => {
+                            bitbucket_remote!($trait_name, config, domain, path, runner $(, $bitbucket)?)
+                        }
                     };
                 Ok(remote)
             }
@@ -409,21 +417,51 @@ macro_rules! get {
     };
 }
 
-get!(get_mr, MergeRequest);
-get!(get_cicd, Cicd);
-get!(get_project, RemoteProject);
+get!(get_mr, MergeRequest, Bitbucket);
+get!(get_cicd, Cicd, Bitbucket);
+get!(get_project, RemoteProject, Bitbucket);
 get!(get_tag, RemoteTag);
-get!(get_user, UserInfo);
+get!(get_user, UserInfo, Bitbucket);
 get!(get_project_member, ProjectMember);
+get!(get_project_member_access, ProjectMemberAccess);
+get!(get_project_invitation, ProjectInvitation);
+get!(get_project_access_check, ProjectAccessCheck);
+get!(get_project_mirror, ProjectMirror);
+get!(get_project_blame, ProjectBlame);
+get!(get_project_dependency, ProjectDependency);
+get!(get_project_languages, ProjectLanguages);
+get!(get_search, Search);
+get!(get_user_activity, UserActivity);
 get!(get_registry, ContainerRegistry);
 get!(get_deploy, Deploy);
 get!(get_deploy_asset, DeployAsset);
-get!(get_auth_user, UserInfo);
+get!(get_deployment, Deployment);
+get!(get_auth_user, UserInfo, Bitbucket);
 get!(get_cicd_runner, CicdRunner);
-get!(get_comment_mr, CommentMergeRequest);
+get!(get_cicd_usage, CicdUsage);
+get!(get_cicd_artifact, CicdArtifact);
+get!(get_cicd_job_log, CicdJobLog);
+get!(get_cicd_test_report, CicdTestReport);
+get!(get_cicd_job_timing, CicdJobTiming);
+get!(get_cicd_pipeline_status, CicdPipelineStatus);
+get!(get_issue, Issue);
+get!(get_label, Label);
+get!(get_comment_mr, CommentMergeRequest, Bitbucket);
+get!(get_mr_reaction, MergeRequestReaction);
+get!(get_mr_discussion, MergeRequestDiscussion);
+get!(get_mr_suggestion, MergeRequestSuggestion);
+get!(get_mr_review, MergeRequestReview);
+get!(get_mr_diff, MergeRequestDiff);
+get!(get_review_mr, ReviewMergeRequest);
+get!(get_remote_instance, RemoteInstance);
+get!(get_merge_queue, MergeQueue);
+get!(get_merge_request_dependency, MergeRequestDependency);
+get!(get_merge_request_rebase, MergeRequestRebase);
 get!(get_trending, TrendingProjectURL);
 get!(get_gist, CodeGist);
 get!(get_cicd_job, CicdJob);
+get!(get_contract_recorder, ContractRecorder);
+get!(get_credentials, RemoteCredentials, Bitbucket);
 
 pub fn extract_domain_path(repo_cli: &str) -> (String, String) {
     let parts: Vec<&str> = repo_cli.split('/').collect();
@@ -448,21 +486,29 @@ pub struct RemoteURL {
     domain: String,
     /// Path to the project. Ex jordilin/gitar
     path: String,
-    /// Config encoded project path. Ex jordilin_gitar
+    /// Config encoded project path. Ex jordilin%2Fgitar
     /// This is used as a key in TOML configuration in order to retrieve project
     /// specific configuration that overrides its domain specific one.
+    /// Slashes are percent-encoded, so a/b_c and a_b/c get different keys.
     config_encoded_project_path: String,
+    /// Project path with slashes replaced by underscores. Ex jordilin_gitar
+    /// Ambiguous, but still looked up when there is no configuration under
+    /// the config encoded project path, so existing configurations keep
+    /// working.
+    legacy_config_encoded_project_path: String,
     config_encoded_domain: String,
 }
 
 impl RemoteURL {
     pub fn new(domain: String, path: String) -> Self {
-        let config_encoded_project_path = path.replace("/", "_");
+        let config_encoded_project_path = config_encode_project_path(&path);
+        let legacy_config_encoded_project_path = path.replace("/", "_");
         let config_encoded_domain = domain.replace(".", "_");
         RemoteURL {
             domain,
             path,
             config_encoded_project_path,
+            legacy_config_encoded_project_path,
             config_encoded_domain,
         }
     }
@@ -479,11 +525,22 @@ impl RemoteURL {
         &self.config_encoded_project_path
     }
 
+    pub fn legacy_config_encoded_project_path(&self) -> &str {
+        &self.legacy_config_encoded_project_path
+    }
+
     pub fn config_encoded_domain(&self) -> &str {
         &self.config_encoded_domain
     }
 }
 
+/// Encodes the project path so it can be used as a TOML key and as part of a
+/// file name. `%` is encoded too, so the encoding cannot be confused with
+/// a path.
+fn config_encode_project_path(path: &str) -> String {
+    path.replace('%', "%25").replace('/', "%2F")
+}
+
 impl CliDomainRequirements {
     pub fn check<R: TaskRunner<Response = ShellResponse>>(
         &self,
@@ -516,18 +573,15 @@ impl CliDomainRequirements {
                 .into()),
             },
             CliDomainRequirements::DomainArgs => {
-                if cli_args.domain.is_some() {
-                    Ok(RemoteURL::new(
-                        cli_args.domain.as_ref().unwrap().to_string(),
-                        "".to_string(),
-                    ))
+                if let Some(domain) = &cli_args.domain {
+                    Ok(RemoteURL::new(domain.to_string(), "".to_string()))
                 } else {
                     Err(GRError::DomainExpected("Missing domain information".to_string()).into())
                 }
             }
             CliDomainRequirements::RepoArgs => {
-                if cli_args.repo.is_some() {
-                    let (domain, path) = extract_domain_path(cli_args.repo.as_ref().unwrap());
+                if let Some(repo) = &cli_args.repo {
+                    let (domain, path) = extract_domain_path(repo);
                     Ok(RemoteURL::new(domain, path))
                 } else {
                     Err(GRError::RepoExpected("Missing repository information".to_string()).into())
@@ -593,12 +647,16 @@ pub fn url<R: TaskRunner<Response = ShellResponse>>(
 ///
 /// - gitar.toml - left empty
 /// - github_com.toml - holds configuration for github.com
-/// - github_com_jordilin_gitar.toml - holds configuration for jordilin/gitar
+/// - github_com_jordilin%2Fgitar.toml - holds configuration for jordilin/gitar
+///
+/// The project file was previously named after the project path with slashes
+/// replaced by underscores, ex. github_com_jordilin_gitar.toml. It is read
+/// when there is no file named after the config encoded project path.
 ///
 /// But also, we could just have:
 ///
 /// - gitar.toml - left empty
-/// - github_com_jordilin_gitar.toml - holds configuration for gitub.com and
+/// - github_com_jordilin%2Fgitar.toml - holds configuration for gitub.com and
 ///   jordilin/gitar
 /// - github_com.toml - left empty
 ///
@@ -621,6 +679,15 @@ pub fn read_config(
         enc_domain,
         url.config_encoded_project_path()
     ));
+    let domain_project_file = if domain_project_file.exists() {
+        domain_project_file
+    } else {
+        config_path.directory.join(format!(
+            "{}_{}.toml",
+            enc_domain,
+            url.legacy_config_encoded_project_path()
+        ))
+    };
 
     log_debug!("config_file: {:?}", config_path.file_name);
     log_debug!("domain_config_file: {:?}", domain_config_file);
@@ -628,7 +695,6 @@ pub fn read_config(
 
     let mut extra_configs = [domain_config_file, domain_project_file]
         .into_iter()
-        .map(PathBuf::from)
         .collect::<Vec<PathBuf>>();
 
     fn open_files(file_paths: &[PathBuf]) -> Vec<File> {
@@ -644,21 +710,63 @@ pub fn read_config(
             .collect()
     }
 
+    // Inside Gitlab CI, the job token is the last resort when no API token
+    // is configured.
+    let job_token = ci_job_token(url.domain(), |name| std::env::var(name).ok());
+    let env = |domain: &str| env_token(domain).or_else(|err| job_token.clone().ok_or(err));
+
     extra_configs.push(config_path.file_name);
     let files = open_files(&extra_configs);
-    if files.is_empty() {
-        let config = NoConfig::new(url.domain(), env_token)?;
-        return Ok(Arc::new(config));
+    let config: Arc<dyn ConfigProperties> = if files.is_empty() {
+        let config = NoConfig::new(url.domain(), env)?
+            .with_sudo(config_path.sudo)
+            .with_force_circuit(config_path.force_circuit)
+            .with_strict_contract(config_path.strict_contract)
+            .with_ci_job_token(job_token.as_deref());
+        Arc::new(config)
+    } else {
+        let config = ConfigFile::new(files, url, env)?
+            .with_sudo(config_path.sudo)
+            .with_force_circuit(config_path.force_circuit)
+            .with_strict_contract(config_path.strict_contract)
+            .with_ci_job_token(job_token.as_deref());
+        Arc::new(config)
+    };
+    if let Some(username) = config.sudo_username() {
+        if provider(url.domain(), config.as_ref())? != Provider::Gitlab {
+            return Err(GRError::PreconditionNotMet(format!(
+                "Impersonating users with --sudo is only supported in Gitlab, domain is {}",
+                url.domain()
+            ))
+            .into());
+        }
+        // Impersonation is always reported, so actions performed on behalf
+        // of other users can be traced back from automation logs.
+        eprintln!(
+            "Impersonating user {} in {} using the Gitlab Sudo header",
+            username,
+            url.domain()
+        );
+    }
+    if job_token.as_deref() == Some(config.api_token()) {
+        eprintln!(
+            "No API token found for {}, using the Gitlab CI/CD job token. \
+             Only pipelines, releases and container registry operations are permitted",
+            url.domain()
+        );
     }
-    let config = ConfigFile::new(files, url, env_token)?;
-    Ok(Arc::new(config))
+    Ok(config)
 }
 
 /// ConfigFilePath is in charge of computing the default config file name and
 /// its parent directory based on global CLI arguments.
+#[derive(Clone)]
 pub struct ConfigFilePath {
     directory: PathBuf,
     file_name: PathBuf,
+    sudo: Option<String>,
+    force_circuit: bool,
+    strict_contract: bool,
 }
 
 impl ConfigFilePath {
@@ -672,6 +780,9 @@ impl ConfigFilePath {
         ConfigFilePath {
             directory: directory.clone(),
             file_name,
+            sudo: cli_args.sudo.clone(),
+            force_circuit: cli_args.force_circuit,
+            strict_contract: cli_args.strict_contract,
         }
     }
 
@@ -688,10 +799,21 @@ impl ConfigFilePath {
 mod test {
     use cli::CliArgs;
 
+    use crate::error;
     use crate::test::utils::MockRunner;
 
     use super::*;
 
+    #[test]
+    fn test_encode_query_param() {
+        assert_eq!("rate%20limit", encode_query_param("rate limit"));
+        assert_eq!(
+            "etag%20repo%3Ajordilin%2Fgitar",
+            encode_query_param("etag repo:jordilin/gitar")
+        );
+        assert_eq!("a-b_c.d~e", encode_query_param("a-b_c.d~e"));
+    }
+
     #[test]
     fn test_cli_from_to_pages_valid_range() {
         let from_page = Option::Some(1);
@@ -813,6 +935,88 @@ mod test {
         assert_eq!(args.max_pages, Some(1));
     }
 
+    #[test]
+    fn test_page_range_page_number_takes_precedence() {
+        let args = ListRemoteCliArgs::builder()
+            .page_number(Some(4))
+            .from_page(Some(3))
+            .build()
+            .unwrap();
+        assert_eq!(
+            Some(PageRange {
+                page: 4,
+                max_pages: 1
+            }),
+            page_range(&args).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_page_range_none_if_no_page_options() {
+        let args = ListRemoteCliArgs::builder().build().unwrap();
+        assert_eq!(None, page_range(&args).unwrap());
+    }
+
+    #[test]
+    fn test_builder_page_range_none_leaves_pages_unset() {
+        let args = ListBodyArgs::builder().page_range(None).build().unwrap();
+        assert_eq!(None, args.page);
+        assert_eq!(None, args.max_pages);
+    }
+
+    #[test]
+    fn test_builder_list_options_carried_with_page_number() {
+        let cli_args = ListRemoteCliArgs::builder()
+            .page_number(Some(2))
+            .flush(true)
+            .throttle_time(Some(Milliseconds::new(100)))
+            .build()
+            .unwrap();
+        let args = validate_from_to_page(&cli_args).unwrap().unwrap();
+        assert_eq!(Some(2), args.page);
+        assert!(args.flush);
+        assert_eq!(Some(Milliseconds::new(100)), args.throttle_time);
+    }
+
+    #[test]
+    fn test_builder_date_filters() {
+        let cli_args = ListRemoteCliArgs::builder()
+            .created_before(Some("2021-02-01T00:00:00Z".to_string()))
+            .updated_after(Some("2021-01-01T00:00:00Z".to_string()))
+            .build()
+            .unwrap();
+        let args = ListBodyArgs::builder()
+            .date_filters(&cli_args)
+            .build()
+            .unwrap();
+        assert_eq!(None, args.created_after);
+        assert_eq!(
+            Some("2021-02-01T00:00:00Z".to_string()),
+            args.created_before
+        );
+        assert_eq!(Some("2021-01-01T00:00:00Z".to_string()), args.updated_after);
+        assert_eq!(None, args.updated_before);
+    }
+
+    #[test]
+    fn test_include_updated_after_and_before_in_list_body_args() {
+        let args = ListRemoteCliArgs::builder()
+            .updated_after(Some("2021-01-01T00:00:00Z".to_string()))
+            .updated_before(Some("2021-02-01T00:00:00Z".to_string()))
+            .from_page(Some(1))
+            .to_page(Some(3))
+            .build()
+            .unwrap();
+        let args = validate_from_to_page(&args).unwrap().unwrap();
+        assert_eq!(Some(1), args.page);
+        assert_eq!(Some(3), args.max_pages);
+        assert_eq!(Some("2021-01-01T00:00:00Z".to_string()), args.updated_after);
+        assert_eq!(
+            Some("2021-02-01T00:00:00Z".to_string()),
+            args.updated_before
+        );
+    }
+
     #[test]
     fn test_include_created_after_in_list_body_args() {
         let created_after = "2021-01-01T00:00:00Z";
@@ -1051,7 +1255,7 @@ mod test {
 
     #[test]
     fn test_cli_requires_cd_local_repo_run_git_remote() {
-        let cli_args = CliArgs::new(0, None, None, None);
+        let cli_args = CliArgs::new(0, None, None, None, None);
         let response = ShellResponse::builder()
             .body("git@github.com:jordilin/gitar.git".to_string())
             .build()
@@ -1065,7 +1269,7 @@ mod test {
 
     #[test]
     fn test_cli_requires_cd_local_repo_run_git_remote_error() {
-        let cli_args = CliArgs::new(0, None, None, None);
+        let cli_args = CliArgs::new(0, None, None, None, None);
         let response = ShellResponse::builder()
             .body("".to_string())
             .build()
@@ -1084,7 +1288,13 @@ mod test {
 
     #[test]
     fn test_cli_requires_repo_args_or_cd_repo_fails_on_cd_repo() {
-        let cli_args = CliArgs::new(0, Some("github.com/jordilin/gitar".to_string()), None, None);
+        let cli_args = CliArgs::new(
+            0,
+            Some("github.com/jordilin/gitar".to_string()),
+            None,
+            None,
+            None,
+        );
         let requirements = vec![
             CliDomainRequirements::CdInLocalRepo,
             CliDomainRequirements::RepoArgs,
@@ -1102,12 +1312,12 @@ mod test {
         .unwrap();
         assert_eq!("github.com", url.domain());
         assert_eq!("jordilin/gitar", url.path());
-        assert_eq!("jordilin_gitar", url.config_encoded_project_path());
+        assert_eq!("jordilin%2Fgitar", url.config_encoded_project_path());
     }
 
     #[test]
     fn test_cli_requires_domain_args_or_cd_repo_fails_on_cd_repo() {
-        let cli_args = CliArgs::new(0, None, Some("github.com".to_string()), None);
+        let cli_args = CliArgs::new(0, None, Some("github.com".to_string()), None, None);
         let requirements = vec![
             CliDomainRequirements::CdInLocalRepo,
             CliDomainRequirements::DomainArgs,
@@ -1137,7 +1347,11 @@ mod test {
     #[test]
     fn test_get_config_encoded_project_path() {
         let remote_url = RemoteURL::new("github.com".to_string(), "jordilin/gitar".to_string());
-        assert_eq!("jordilin_gitar", remote_url.config_encoded_project_path());
+        assert_eq!("jordilin%2Fgitar", remote_url.config_encoded_project_path());
+        assert_eq!(
+            "jordilin_gitar",
+            remote_url.legacy_config_encoded_project_path()
+        );
     }
 
     #[test]
@@ -1147,11 +1361,26 @@ mod test {
             "team/subgroup/project".to_string(),
         );
         assert_eq!(
-            "team_subgroup_project",
+            "team%2Fsubgroup%2Fproject",
             remote_url.config_encoded_project_path()
         );
     }
 
+    #[test]
+    fn test_config_encoded_project_paths_with_underscores_do_not_collide() {
+        let url_a = RemoteURL::new("gitlab.com".to_string(), "a/b_c".to_string());
+        let url_b = RemoteURL::new("gitlab.com".to_string(), "a_b/c".to_string());
+        assert_ne!(
+            url_a.config_encoded_project_path(),
+            url_b.config_encoded_project_path()
+        );
+        // Legacy encoding collides.
+        assert_eq!(
+            url_a.legacy_config_encoded_project_path(),
+            url_b.legacy_config_encoded_project_path()
+        );
+    }
+
     #[test]
     fn test_get_config_encoded_domain() {
         let remote_url = RemoteURL::new("github.com".to_string(), "jordilin/gitar".to_string());
@@ -1174,4 +1403,72 @@ mod test {
         assert_eq!("github.com", url.domain());
         assert_eq!("jordilin/gitar", url.path());
     }
+
+    #[test]
+    fn test_provider_from_domain_name() {
+        let config = crate::test::utils::config();
+        assert_eq!(
+            Provider::Github,
+            provider("github.com", config.as_ref()).unwrap()
+        );
+        assert_eq!(
+            Provider::Gitlab,
+            provider("gitlab.mycompany.com", config.as_ref()).unwrap()
+        );
+        assert!(provider("git.mycompany.com", config.as_ref()).is_err());
+        assert_eq!(
+            Provider::Bitbucket,
+            provider("bitbucket.org", config.as_ref()).unwrap()
+        );
+        assert!(provider("bitbucket.mycompany.com", config.as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_provider_configured_for_custom_domain() {
+        let config_data = r#"
+        [git_mycompany_com]
+        api_token = '1234'
+        provider = "gitlab"
+        "#;
+        let reader = vec![std::io::Cursor::new(config_data)];
+        let url = RemoteURL::new(
+            "git.mycompany.com".to_string(),
+            "jordilin/gitar".to_string(),
+        );
+        let config: Arc<dyn ConfigProperties> =
+            Arc::new(ConfigFile::new(reader, &url, |_| Ok("".to_string())).unwrap());
+        assert_eq!(
+            Provider::Gitlab,
+            provider("git.mycompany.com", config.as_ref()).unwrap()
+        );
+        let runner = Arc::new(http::Client::new(NoCache, config.clone(), false));
+        assert!(create_remote_get_mr(
+            "git.mycompany.com".to_string(),
+            "jordilin/gitar".to_string(),
+            config,
+            runner
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_bitbucket_remote_supports_subset_of_traits() {
+        let config = crate::test::utils::config();
+        let runner = Arc::new(http::Client::new(NoCache, config.clone(), false));
+        let domain = "bitbucket.org".to_string();
+        let path = "jordilin/bitbapi".to_string();
+        assert!(
+            create_remote_get_mr(domain.clone(), path.clone(), config.clone(), runner.clone())
+                .is_ok()
+        );
+        match create_remote_get_label(domain, path, config, runner) {
+            Err(err) => match err.downcast_ref::<GRError>() {
+                Some(GRError::OperationNotSupported(msg)) => {
+                    assert_eq!("Label is not supported in Bitbucket", msg)
+                }
+                _ => panic!("Expected OperationNotSupported"),
+            },
+            _ => panic!("Expected error"),
+        }
+    }
 }
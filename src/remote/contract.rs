@@ -0,0 +1,194 @@
+//! Typed deserialization of remote API payloads. Providers declare the
+//! fields they rely on as serde structs (DTOs) that are then converted into
+//! gitar's own types.
+//!
+//! By default decoding is lenient: missing fields and fields of an unexpected
+//! type take their default value and are logged. In strict mode, enabled with
+//! the global `--strict-contract` flag, both are errors, so changes in the
+//! remote API contract show up instead of turning into empty values.
+
+use std::any::type_name;
+
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+
+use crate::error::GRError;
+use crate::{log_debug, log_error, Result};
+
+/// Payload of a remote API resource. Implementors are expected to derive
+/// `Deserialize` with `#[serde(default)]`.
+pub trait Contract: DeserializeOwned + Default {
+    /// Fields the remote leaves out depending on the endpoint. They are not
+    /// required in strict mode.
+    const OPTIONAL_FIELDS: &'static [&'static str] = &[];
+}
+
+pub fn decode<D: Contract>(data: &serde_json::Value, strict: bool) -> Result<D> {
+    if let Some(object) = data.as_object() {
+        let missing = fields::<D>()
+            .iter()
+            .filter(|field| !object.contains_key(**field) && !D::OPTIONAL_FIELDS.contains(field))
+            .copied()
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let msg = format!("missing fields: {}", missing.join(", "));
+            if strict {
+                return Err(contract_error::<D>(&msg));
+            }
+            log_debug!("{} {}", type_name::<D>(), msg);
+        }
+    }
+    match D::deserialize(data) {
+        Ok(dto) => Ok(dto),
+        Err(err) if strict => Err(contract_error::<D>(&err.to_string())),
+        Err(err) => {
+            log_error!("Unexpected payload for {}: {}", type_name::<D>(), err);
+            decode_valid_fields::<D>(data).map_err(|err| contract_error::<D>(&err.to_string()))
+        }
+    }
+}
+
+/// Decodes the payload leaving out the fields that do not decode on their
+/// own, so they take their default value instead of the whole DTO.
+fn decode_valid_fields<D: Contract>(
+    data: &serde_json::Value,
+) -> std::result::Result<D, serde_json::Error> {
+    let Some(object) = data.as_object() else {
+        return D::deserialize(data);
+    };
+    let valid = object
+        .iter()
+        .filter(|(field, value)| {
+            let single = serde_json::json!({ field.as_str(): value });
+            let valid = D::deserialize(&single).is_ok();
+            if !valid {
+                log_error!(
+                    "Unexpected {} in {}, using its default",
+                    field,
+                    type_name::<D>()
+                );
+            }
+            valid
+        })
+        .map(|(field, value)| (field.clone(), value.clone()))
+        .collect::<serde_json::Map<_, _>>();
+    D::deserialize(&serde_json::Value::Object(valid))
+}
+
+fn contract_error<D>(msg: &str) -> anyhow::Error {
+    GRError::RemoteUnexpectedResponseContract(format!(
+        "Payload does not match {}, {}",
+        type_name::<D>(),
+        msg
+    ))
+    .into()
+}
+
+/// Field names of a struct as declared to serde. Gathered by asking the
+/// derived implementation to deserialize from a deserializer that records
+/// them and bails out.
+fn fields<D: DeserializeOwned>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = D::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        _visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("field names gathered"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize, Default, Debug, PartialEq)]
+    #[serde(default)]
+    struct Release {
+        tag_name: String,
+        #[serde(rename = "body")]
+        description: Option<String>,
+        draft: bool,
+    }
+
+    impl Contract for Release {
+        const OPTIONAL_FIELDS: &'static [&'static str] = &["draft"];
+    }
+
+    #[test]
+    fn test_fields_are_serde_names() {
+        assert_eq!(&["tag_name", "body", "draft"], fields::<Release>());
+    }
+
+    #[test]
+    fn test_lenient_decode_defaults_missing_fields() {
+        let data = serde_json::json!({"tag_name": "v1.0.0"});
+        let release = decode::<Release>(&data, false).unwrap();
+        assert_eq!("v1.0.0", release.tag_name);
+        assert_eq!(None, release.description);
+    }
+
+    #[test]
+    fn test_lenient_decode_unexpected_type_defaults_the_field() {
+        let data = serde_json::json!({"tag_name": "v1.0.0", "body": 100, "draft": null});
+        let release = decode::<Release>(&data, false).unwrap();
+        assert_eq!("v1.0.0", release.tag_name);
+        assert_eq!(None, release.description);
+        assert!(!release.draft);
+    }
+
+    #[test]
+    fn test_lenient_decode_not_an_object_is_error() {
+        let data = serde_json::json!([{"tag_name": "v1.0.0"}]);
+        assert!(decode::<Release>(&data, false).is_err());
+    }
+
+    #[test]
+    fn test_strict_decode_missing_field_is_error() {
+        let data = serde_json::json!({"tag_name": "v1.0.0"});
+        let err = decode::<Release>(&data, true).unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::RemoteUnexpectedResponseContract(msg)) => {
+                assert!(msg.ends_with("missing fields: body"))
+            }
+            _ => panic!("Expected GRError::RemoteUnexpectedResponseContract"),
+        }
+    }
+
+    #[test]
+    fn test_strict_decode_allows_null_and_optional_fields() {
+        let data = serde_json::json!({"tag_name": "v1.0.0", "body": null});
+        assert!(decode::<Release>(&data, true).is_ok());
+    }
+
+    #[test]
+    fn test_strict_decode_unexpected_type_is_error() {
+        let data = serde_json::json!({"tag_name": 100, "body": null});
+        assert!(decode::<Release>(&data, true).is_err());
+    }
+}
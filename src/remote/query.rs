@@ -1,13 +1,21 @@
 use std::borrow::Borrow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::fmt;
 use std::iter::Iterator;
+use std::marker::PhantomData;
 use std::sync::Arc;
 
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
 use serde::Serialize;
 
 use crate::api_traits::Timestamp;
 use crate::backoff::{Backoff, Exponential};
+use crate::config::RetryBudget;
 use crate::display::DisplayBody;
 use crate::http::throttle::{self, ThrottleStrategy};
+use crate::log_debug;
+use crate::remote::contract::{self, Contract};
 use crate::time;
 use crate::{
     api_defaults,
@@ -15,7 +23,7 @@ use crate::{
     display, error,
     http::{self, Body, Headers, Paginator, Request, Resource},
     io::{HttpResponse, HttpRunner},
-    json_load_page, json_loads,
+    json_loads,
     remote::ListBodyArgs,
     time::sort_filter_by_date,
     Result,
@@ -27,9 +35,11 @@ fn get_remote_resource_headers<R: HttpRunner<Response = HttpResponse>>(
     request_headers: Headers,
     api_operation: ApiOperation,
 ) -> Result<HttpResponse> {
+    // Page counts are given for the same page size used when listing.
+    let url = with_per_page(url, runner.api_per_page());
     send_request::<_, String>(
         runner,
-        url,
+        &url,
         None,
         request_headers,
         http::Method::HEAD,
@@ -57,6 +67,8 @@ pub fn num_pages<R: HttpRunner<Response = HttpResponse>>(
     }
 }
 
+const TOTAL_HEADER: &str = "x-total";
+
 pub fn num_resources<R: HttpRunner<Response = HttpResponse>>(
     runner: &Arc<R>,
     url: &str,
@@ -64,34 +76,134 @@ pub fn num_resources<R: HttpRunner<Response = HttpResponse>>(
     api_operation: ApiOperation,
 ) -> Result<Option<NumberDeltaErr>> {
     let response = get_remote_resource_headers(runner, url, request_headers, api_operation)?;
+    // Gitlab gives the total count unless there are too many resources.
+    if let Some(total) = response
+        .header(TOTAL_HEADER)
+        .and_then(|total| total.parse().ok())
+    {
+        return Ok(Some(NumberDeltaErr::exact(total)));
+    }
     match response.get_page_headers().borrow() {
         Some(page_header) => {
             // total resources per_page * total_pages
             if let Some(last_page) = page_header.last_page() {
                 let count = last_page.number * page_header.per_page;
-                return Ok(Some(NumberDeltaErr {
-                    num: count,
-                    delta: page_header.per_page,
-                }));
+                return Ok(Some(NumberDeltaErr::new(count, page_header.per_page)));
             }
             Ok(None)
         }
         None => {
             // Github does not return page headers when there is only one page, so
             // we assume 1 page in this case.
-            Ok(Some(NumberDeltaErr {
-                num: 1,
-                delta: api_defaults::DEFAULT_PER_PAGE,
-            }))
+            Ok(Some(NumberDeltaErr::new(1, api_defaults::DEFAULT_PER_PAGE)))
         }
     }
 }
 
-pub fn query_error(url: &str, response: &HttpResponse) -> error::GRError {
-    error::GRError::RemoteServerError(format!(
-        "Failed to submit request to URL: {} with status code: {} and body: {}",
-        url, response.status, response.body
-    ))
+// Query parameters that carry credentials and must not end up in errors.
+const SECRET_QUERY_PARAMS: [&str; 4] = ["access_token", "job_token", "private_token", "token"];
+
+// Longest remote message kept in the single line summary of an error.
+const MAX_SUMMARY_LEN: usize = 200;
+
+/// Error of a request the remote answered with a failure status. Displayed,
+/// it is a single line with the request and the message of the remote. The
+/// full response body is only logged, seen with -vv.
+pub fn request_error(
+    method: &http::Method,
+    url: &str,
+    operation: &ApiOperation,
+    response: &HttpResponse,
+) -> anyhow::Error {
+    log_debug!(
+        "Response body of {}: {}",
+        request_line(method, url, operation),
+        response.body
+    );
+    let summary = response_summary(&response.body);
+    let msg = if summary.is_empty() {
+        format!(
+            "{} failed with status {}",
+            request_line(method, url, operation),
+            response.status
+        )
+    } else {
+        format!(
+            "{} failed with status {}: {}",
+            request_line(method, url, operation),
+            response.status,
+            summary
+        )
+    };
+    status_error(response, msg).into()
+}
+
+fn status_error(response: &HttpResponse, msg: String) -> error::GRError {
+    match response.status {
+        405 | 409 => error::GRError::RemoteConflict(msg),
+        _ => error::GRError::RemoteServerError(msg),
+    }
+}
+
+/// Adds the request to errors not tied to a response status, ex. transport
+/// errors, rate limits or response bodies that are not JSON. The error stays
+/// as the cause, so it is not repeated in the context.
+fn with_request_context<T>(
+    result: Result<T>,
+    method: &http::Method,
+    url: &str,
+    operation: &ApiOperation,
+) -> Result<T> {
+    result.map_err(|err| {
+        let context = format!("{} failed", request_line(method, url, operation));
+        err.context(context)
+    })
+}
+
+fn request_line(method: &http::Method, url: &str, operation: &ApiOperation) -> String {
+    format!("{:?} {} ({})", method, sanitize_url(url), operation)
+}
+
+fn sanitize_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let params = query
+        .split('&')
+        .map(|param| match param.split_once('=') {
+            Some((key, _)) if SECRET_QUERY_PARAMS.contains(&key) => format!("{}=REDACTED", key),
+            _ => param.to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("{}?{}", base, params.join("&"))
+}
+
+/// Message of the remote in an error response body. Gitlab and Github answer
+/// with a JSON `message` or `error`, other bodies are cut to their first line.
+fn response_summary(body: &str) -> String {
+    let summary = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(data) => ["message", "error", "errors"]
+            .iter()
+            .map(|key| &data[key])
+            .find(|value| !value.is_null())
+            .map(|value| match value {
+                serde_json::Value::String(msg) => msg.to_string(),
+                value => value.to_string(),
+            })
+            .unwrap_or_default(),
+        Err(_) => body
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string(),
+    };
+    let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+    if summary.chars().count() > MAX_SUMMARY_LEN {
+        let summary = summary.chars().take(MAX_SUMMARY_LEN).collect::<String>();
+        return format!("{}...", summary);
+    }
+    summary
 }
 
 pub fn send<R: HttpRunner<Response = HttpResponse>, D: Serialize, T>(
@@ -100,12 +212,31 @@ pub fn send<R: HttpRunner<Response = HttpResponse>, D: Serialize, T>(
     body: Option<&Body<D>>,
     request_headers: Headers,
     operation: ApiOperation,
-    mapper: impl Fn(&serde_json::Value) -> T,
+    mapper: impl Fn(&serde_json::Value) -> Result<T>,
     method: http::Method,
 ) -> Result<T> {
-    let response = send_request(runner, url, body, request_headers, method, operation)?;
-    let body = json_loads(&response.body)?;
-    Ok(mapper(&body))
+    let body = send_json(runner, url, body, request_headers, operation, method)?;
+    mapper(&body)
+}
+
+/// Same as `send`, decoding the response into the provider DTO `D` which is
+/// then converted into `T`.
+pub fn send_as<R, B, D, T>(
+    runner: &Arc<R>,
+    url: &str,
+    body: Option<&Body<B>>,
+    request_headers: Headers,
+    operation: ApiOperation,
+    method: http::Method,
+) -> Result<T>
+where
+    R: HttpRunner<Response = HttpResponse>,
+    B: Serialize,
+    D: Contract,
+    T: From<D>,
+{
+    let data = send_json(runner, url, body, request_headers, operation, method)?;
+    decode::<_, D, T>(runner, &data)
 }
 
 pub fn send_json<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
@@ -116,8 +247,15 @@ pub fn send_json<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
     operation: ApiOperation,
     method: http::Method,
 ) -> Result<serde_json::Value> {
-    let response = send_request(runner, url, body, request_headers, method, operation)?;
-    json_loads(&response.body)
+    let response = send_request(
+        runner,
+        url,
+        body,
+        request_headers,
+        method.clone(),
+        operation.clone(),
+    )?;
+    with_request_context(json_loads(&response.body), &method, url, &operation)
 }
 
 pub fn send_raw<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
@@ -131,24 +269,67 @@ pub fn send_raw<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
     send_request(runner, url, body, request_headers, method, operation)
 }
 
-pub fn get<R: HttpRunner<Response = HttpResponse>, D: Serialize, T>(
+/// Same as `send_raw`, but the response is returned whatever its status code
+/// for callers that can recover from some of the failures.
+pub fn send_raw_unchecked<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
     runner: &Arc<R>,
     url: &str,
     body: Option<&Body<D>>,
     request_headers: Headers,
     operation: ApiOperation,
-    mapper: impl Fn(&serde_json::Value) -> T,
-) -> Result<T> {
-    let response = send_request(
-        runner,
+    method: http::Method,
+) -> Result<HttpResponse> {
+    let mut request = build_request(
         url,
         body,
         request_headers,
-        http::Method::GET,
-        operation,
-    )?;
-    let body = json_loads(&response.body)?;
-    Ok(mapper(&body))
+        method.clone(),
+        operation.clone(),
+    );
+    with_request_context(runner.run(&mut request), &method, url, &operation)
+}
+
+pub fn get<R: HttpRunner<Response = HttpResponse>, D: Serialize, T>(
+    runner: &Arc<R>,
+    url: &str,
+    body: Option<&Body<D>>,
+    request_headers: Headers,
+    operation: ApiOperation,
+    mapper: impl Fn(&serde_json::Value) -> Result<T>,
+) -> Result<T> {
+    let body = get_json(runner, url, body, request_headers, operation)?;
+    mapper(&body)
+}
+
+/// Same as `get`, decoding the response into the provider DTO `D` which is
+/// then converted into `T`.
+pub fn get_as<R, B, D, T>(
+    runner: &Arc<R>,
+    url: &str,
+    body: Option<&Body<B>>,
+    request_headers: Headers,
+    operation: ApiOperation,
+) -> Result<T>
+where
+    R: HttpRunner<Response = HttpResponse>,
+    B: Serialize,
+    D: Contract,
+    T: From<D>,
+{
+    let data = get_json(runner, url, body, request_headers, operation)?;
+    decode::<_, D, T>(runner, &data)
+}
+
+/// Decodes a JSON payload into the provider DTO `D` and converts it into `T`.
+/// Payloads not matching the DTO are errors if the runner is set up with a
+/// strict contract.
+pub fn decode<R, D, T>(runner: &Arc<R>, data: &serde_json::Value) -> Result<T>
+where
+    R: HttpRunner<Response = HttpResponse>,
+    D: Contract,
+    T: From<D>,
+{
+    contract::decode::<D>(data, runner.strict_contract()).map(T::from)
 }
 
 pub fn get_json<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
@@ -164,9 +345,47 @@ pub fn get_json<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
         body,
         request_headers,
         http::Method::GET,
-        operation,
+        operation.clone(),
+    )?;
+    with_request_context(
+        json_loads(&response.body),
+        &http::Method::GET,
+        url,
+        &operation,
+    )
+}
+
+/// Bytes of the resource at `url` from `offset` on, requested with a `Range`
+/// header. Servers that do not honor it return the whole resource, which is
+/// sliced. Nothing past the offset (416) is an empty result.
+pub fn get_bytes_from<R: HttpRunner<Response = HttpResponse>>(
+    runner: &Arc<R>,
+    url: &str,
+    mut request_headers: Headers,
+    operation: ApiOperation,
+    offset: u64,
+) -> Result<Vec<u8>> {
+    request_headers.set("Range", format!("bytes={}-", offset));
+    let response = send_raw_unchecked::<_, ()>(
+        runner,
+        url,
+        None,
+        request_headers,
+        operation.clone(),
+        http::Method::GET,
     )?;
-    json_loads(&response.body)
+    let data = response.body_bytes();
+    match response.status {
+        206 => Ok(data.to_vec()),
+        200 => Ok(data[(offset as usize).min(data.len())..].to_vec()),
+        416 => Ok(Vec::new()),
+        _ => Err(request_error(
+            &http::Method::GET,
+            url,
+            &operation,
+            &response,
+        )),
+    }
 }
 
 pub fn get_raw<R: HttpRunner<Response = HttpResponse>, D: Serialize>(
@@ -194,9 +413,33 @@ fn send_request<R: HttpRunner<Response = HttpResponse>, T: Serialize>(
     method: http::Method,
     operation: ApiOperation,
 ) -> Result<HttpResponse> {
-    let mut request = if let Some(body) = body {
+    let mut request = build_request(
+        url,
+        body,
+        request_headers,
+        method.clone(),
+        operation.clone(),
+    );
+    let response = with_request_context(runner.run(&mut request), &method, url, &operation)?;
+    // TODO: Might not be the right place as some APIs might still need to check
+    // the response status code. See github merge request request reviewers when
+    // a 422 is considered an error.
+    if !response.is_ok(&method) {
+        return Err(request_error(&method, url, &operation, &response));
+    }
+    Ok(response)
+}
+
+fn build_request<'a, T: Serialize>(
+    url: &str,
+    body: Option<&'a Body<T>>,
+    request_headers: Headers,
+    method: http::Method,
+    operation: ApiOperation,
+) -> http::Request<'a, T> {
+    if let Some(body) = body {
         http::Request::builder()
-            .method(method.clone())
+            .method(method)
             .resource(Resource::new(url, Some(operation)))
             .body(body)
             .headers(request_headers)
@@ -204,20 +447,12 @@ fn send_request<R: HttpRunner<Response = HttpResponse>, T: Serialize>(
             .unwrap()
     } else {
         http::Request::builder()
-            .method(method.clone())
+            .method(method)
             .resource(Resource::new(url, Some(operation)))
             .headers(request_headers)
             .build()
             .unwrap()
-    };
-    let response = runner.run(&mut request)?;
-    // TODO: Might not be the right place as some APIs might still need to check
-    // the response status code. See github merge request request reviewers when
-    // a 422 is considered an error.
-    if !response.is_ok(&method) {
-        return Err(query_error(url, &response).into());
     }
-    Ok(response)
 }
 
 pub fn paged<R, T>(
@@ -227,23 +462,76 @@ pub fn paged<R, T>(
     request_headers: Headers,
     iter_over_sub_array: Option<&str>,
     operation: ApiOperation,
-    mapper: impl Fn(&serde_json::Value) -> T,
+    mapper: impl Fn(&serde_json::Value) -> Result<T>,
+) -> Result<Vec<T>>
+where
+    R: HttpRunner<Response = HttpResponse>,
+    T: Clone + Timestamp + Into<DisplayBody>,
+{
+    paged_with(
+        runner,
+        url,
+        list_args,
+        request_headers,
+        iter_over_sub_array,
+        operation,
+        mapper,
+    )
+}
+
+/// Same as `paged`, decoding each element into the provider DTO `D` which is
+/// then converted into `T`.
+pub fn paged_as<R, D, T>(
+    runner: &Arc<R>,
+    url: &str,
+    list_args: Option<ListBodyArgs>,
+    request_headers: Headers,
+    iter_over_sub_array: Option<&str>,
+    operation: ApiOperation,
+) -> Result<Vec<T>>
+where
+    R: HttpRunner<Response = HttpResponse>,
+    D: Contract,
+    T: From<D> + Clone + Timestamp + Into<DisplayBody>,
+{
+    paged_with(
+        runner,
+        url,
+        list_args,
+        request_headers,
+        iter_over_sub_array,
+        operation,
+        |value| decode::<_, D, T>(runner, value),
+    )
+}
+
+fn paged_with<R, T>(
+    runner: &Arc<R>,
+    url: &str,
+    list_args: Option<ListBodyArgs>,
+    request_headers: Headers,
+    iter_over_sub_array: Option<&str>,
+    operation: ApiOperation,
+    mapper: impl Fn(&serde_json::Value) -> Result<T>,
 ) -> Result<Vec<T>>
 where
     R: HttpRunner<Response = HttpResponse>,
     T: Clone + Timestamp + Into<DisplayBody>,
 {
-    let request = build_list_request(url, &list_args, request_headers, operation);
+    let per_page = list_args
+        .as_ref()
+        .and_then(|list_args| list_args.per_page)
+        .or_else(|| runner.api_per_page());
+    let url = &with_per_page(url, per_page);
+    let request = build_list_request(url, &list_args, request_headers, operation.clone());
     let mut throttle_time = None;
     let mut throttle_range = None;
-    let mut backoff_max_retries = 0;
-    let mut backoff_wait_time = 60;
     if let Some(list_args) = &list_args {
         throttle_time = list_args.throttle_time;
         throttle_range = list_args.throttle_range;
-        backoff_max_retries = list_args.get_args.backoff_max_retries;
-        backoff_wait_time = list_args.get_args.backoff_retry_after;
     }
+    let (backoff_max_retries, backoff_wait_time) =
+        backoff_settings(&list_args, runner.api_retry_budget(&request));
     let throttle_strategy: Box<dyn ThrottleStrategy> = match throttle_time {
         Some(throttle_time) => Box::new(throttle::PreFixed::new(throttle_time)),
         None => match throttle_range {
@@ -260,69 +548,198 @@ where
         Box::new(throttle::DynamicFixed),
     );
     let paginator = Paginator::new(runner, request, url, backoff, throttle_strategy);
+    // Streamed pages are printed as a single table, headers only once.
+    let stream = RefCell::new(
+        list_args
+            .as_ref()
+            .filter(|list_args| list_args.flush)
+            .map(|list_args| display::PageStream::new(list_args.get_args.clone())),
+    );
+    let seen_ids = RefCell::new(HashSet::new());
+    let num_duplicates = Cell::new(0);
+    let mapper = |item: &serde_json::Value| {
+        if is_duplicate(item, &seen_ids) {
+            num_duplicates.set(num_duplicates.get() + 1);
+            return None;
+        }
+        Some(mapper(item))
+    };
     let all_data = paginator
         .map(|response| {
-            let response = response?;
+            let response = with_request_context(response, &http::Method::GET, url, &operation)?;
             if !response.is_ok(&http::Method::GET) {
-                return Err(query_error(url, &response).into());
+                return Err(request_error(
+                    &http::Method::GET,
+                    url,
+                    &operation,
+                    &response,
+                ));
             }
-            if iter_over_sub_array.is_some() {
-                let body = json_loads(&response.body)?;
-                let paged_data = body[iter_over_sub_array.unwrap()]
-                    .as_array()
-                    .ok_or_else(|| {
+            let paged_data = json_map_page(&response.body, iter_over_sub_array, &mapper)
+                .map_err(|err| match iter_over_sub_array {
+                    Some(iter_over_sub_array) => {
                         error::GRError::RemoteUnexpectedResponseContract(format!(
                             "Expected an array of {} but got: {}",
-                            iter_over_sub_array.unwrap(),
-                            response.body
+                            iter_over_sub_array, response.body
                         ))
-                    })?
-                    .iter()
-                    .fold(Vec::new(), |mut paged_data, data| {
-                        paged_data.push(mapper(data));
-                        paged_data
-                    });
-                if let Some(list_args) = &list_args {
-                    if list_args.flush {
-                        display::print(
-                            &mut std::io::stdout(),
-                            paged_data,
-                            list_args.get_args.clone(),
-                        )
-                        .unwrap();
-                        return Ok(Vec::new());
+                        .into()
                     }
-                }
-                return Ok(paged_data);
-            }
-            let paged_data =
-                json_load_page(&response.body)?
-                    .iter()
-                    .fold(Vec::new(), |mut paged_data, data| {
-                        paged_data.push(mapper(data));
-                        paged_data
-                    });
-            if let Some(list_args) = &list_args {
-                if list_args.flush {
-                    display::print(
-                        &mut std::io::stdout(),
-                        paged_data,
-                        list_args.get_args.clone(),
-                    )
-                    .unwrap();
-                    return Ok(Vec::new());
-                }
+                    None => error::gen(err.to_string()),
+                })?
+                .into_iter()
+                .flatten()
+                .collect::<Result<Vec<T>>>()?;
+            if let Some(stream) = stream.borrow_mut().as_mut() {
+                stream.print_page(&mut std::io::stdout(), paged_data)?;
+                return Ok(Vec::new());
             }
             Ok(paged_data)
         })
         .collect::<Result<Vec<Vec<T>>>>()
         .map(|paged_data| paged_data.into_iter().flatten().collect());
+    if let (Ok(_), Some(stream)) = (&all_data, stream.into_inner()) {
+        stream.finish(&mut std::io::stdout())?;
+    }
+    if num_duplicates.get() > 0 {
+        eprintln!(
+            "Warning: {} duplicated results removed from {}. Resources were likely \
+             created while listing",
+            num_duplicates.get(),
+            url
+        );
+    }
     match all_data {
         Ok(paged_data) => Ok(sort_filter_by_date(paged_data, list_args)?),
         Err(err) => Err(err),
     }
 }
 
+/// Max retries and base wait in seconds of the backoff when listing. Retries
+/// requested on the command line take precedence over the retry budget
+/// configured for the API operation.
+fn backoff_settings(list_args: &Option<ListBodyArgs>, budget: Option<RetryBudget>) -> (u32, u64) {
+    let (max_retries, retry_after) = list_args
+        .as_ref()
+        .map(|list_args| {
+            (
+                list_args.get_args.backoff_max_retries,
+                list_args.get_args.backoff_retry_after,
+            )
+        })
+        .unwrap_or((0, 60));
+    match budget {
+        Some(budget) if max_retries == 0 => (
+            budget.max_retries,
+            budget.retry_after.unwrap_or(retry_after),
+        ),
+        _ => (max_retries, retry_after),
+    }
+}
+
+/// Offset pagination can return the same resource in two pages when
+/// resources are created while listing. Resources are identified by their
+/// `id`; those without one are never considered duplicates.
+fn is_duplicate(item: &serde_json::Value, seen_ids: &RefCell<HashSet<String>>) -> bool {
+    match item.get("id") {
+        Some(id) if !id.is_null() => !seen_ids.borrow_mut().insert(id.to_string()),
+        _ => false,
+    }
+}
+
+/// Parses the body of a page of a listing mapping each element as soon as it
+/// is read, so no JSON tree of the whole page is built next to the body. The
+/// body itself is read in full by the HTTP client, bounded by the maximum
+/// response body size. Pages are either a JSON array or an object holding the
+/// array in the `sub_array` field.
+fn json_map_page<T>(
+    data: &str,
+    sub_array: Option<&str>,
+    mapper: &impl Fn(&serde_json::Value) -> T,
+) -> serde_json::Result<Vec<T>> {
+    let mut deserializer = serde_json::Deserializer::from_str(data);
+    let page = PageSeed {
+        sub_array,
+        mapper,
+        item: PhantomData,
+    }
+    .deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(page)
+}
+
+struct PageSeed<'a, F, T> {
+    sub_array: Option<&'a str>,
+    mapper: &'a F,
+    item: PhantomData<T>,
+}
+
+impl<'de, F, T> DeserializeSeed<'de> for PageSeed<'_, F, T>
+where
+    F: Fn(&serde_json::Value) -> T,
+{
+    type Value = Vec<T>;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Vec<T>, D::Error> {
+        match self.sub_array {
+            Some(_) => deserializer.deserialize_map(self),
+            None => deserializer.deserialize_seq(self),
+        }
+    }
+}
+
+impl<'de, F, T> Visitor<'de> for PageSeed<'_, F, T>
+where
+    F: Fn(&serde_json::Value) -> T,
+{
+    type Value = Vec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.sub_array {
+            Some(field) => write!(formatter, "an object with an array of {}", field),
+            None => formatter.write_str("an array"),
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Vec<T>, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(item) = seq.next_element::<serde_json::Value>()? {
+            items.push((self.mapper)(&item));
+        }
+        Ok(items)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Vec<T>, A::Error> {
+        let field = self.sub_array.unwrap_or_default();
+        let mut items = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == field {
+                items = Some(map.next_value_seed(PageSeed {
+                    sub_array: None,
+                    mapper: self.mapper,
+                    item: PhantomData,
+                })?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        items.ok_or_else(|| serde::de::Error::missing_field("array"))
+    }
+}
+
+/// Adds the page size to a list URL, unless the caller already set one.
+fn with_per_page(url: &str, per_page: Option<u32>) -> String {
+    match per_page {
+        Some(per_page) if !url.contains("per_page=") => {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            format!("{}{}per_page={}", url, separator, per_page)
+        }
+        _ => url.to_string(),
+    }
+}
+
 fn build_list_request<'a>(
     url: &str,
     list_args: &Option<ListBodyArgs>,
@@ -351,12 +768,121 @@ mod test {
     use std::rc::Rc;
 
     use crate::{
+        cmds::project::Member,
         io::{FlowControlHeaders, Page, PageHeader},
+        remote::GetRemoteCliArgs,
         test::utils::MockRunner,
     };
 
     use super::*;
 
+    #[test]
+    fn test_status_error_conflict_on_405_and_409() {
+        for status in [405, 409] {
+            let response = HttpResponse::builder().status(status).build().unwrap();
+            match status_error(&response, String::new()) {
+                error::GRError::RemoteConflict(_) => (),
+                _ => panic!("Expected RemoteConflict for status {}", status),
+            }
+        }
+        let response = HttpResponse::builder().status(500).build().unwrap();
+        match status_error(&response, String::new()) {
+            error::GRError::RemoteServerError(_) => (),
+            _ => panic!("Expected RemoteServerError"),
+        }
+    }
+
+    #[test]
+    fn test_request_error_is_a_single_line_with_the_remote_message() {
+        let response = HttpResponse::builder()
+            .status(409)
+            .body(r#"{"message": "Branch cannot be merged\n(pipeline running)"}"#.to_string())
+            .build()
+            .unwrap();
+        let err = request_error(
+            &http::Method::PUT,
+            "https://gitlab.com/api/v4/projects/1/merge_requests/2/merge",
+            &ApiOperation::MergeRequest,
+            &response,
+        );
+        assert_eq!(
+            "Remote conflict error: PUT https://gitlab.com/api/v4/projects/1/merge_requests/2/merge \
+             (merge_request) failed with status 409: Branch cannot be merged (pipeline running)",
+            format!("{:#}", err)
+        );
+        assert!(matches!(
+            err.downcast_ref::<error::GRError>(),
+            Some(error::GRError::RemoteConflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_request_error_redacts_tokens_and_cuts_long_bodies() {
+        let response = HttpResponse::builder()
+            .status(500)
+            .body(format!("<html>\n{}\n</html>", "x".repeat(300)))
+            .build()
+            .unwrap();
+        let err = request_error(
+            &http::Method::GET,
+            "https://gitlab.com/api/v4/projects?private_token=secret&page=2",
+            &ApiOperation::Project,
+            &response,
+        );
+        assert_eq!(
+            "Remote server status error: GET \
+             https://gitlab.com/api/v4/projects?private_token=REDACTED&page=2 (project) \
+             failed with status 500: <html>",
+            format!("{:#}", err)
+        );
+        let response = HttpResponse::builder()
+            .status(422)
+            .body(format!(r#"{{"message": "{}"}}"#, "y".repeat(300)))
+            .build()
+            .unwrap();
+        let err = request_error(
+            &http::Method::POST,
+            "https://api.github.com/repos/a/b/pulls",
+            &ApiOperation::MergeRequest,
+            &response,
+        );
+        assert!(err
+            .to_string()
+            .ends_with(&format!(": {}...", "y".repeat(200))));
+    }
+
+    #[test]
+    fn test_transport_errors_carry_the_request() {
+        let contracts = vec![HttpResponse::builder()
+            .status(-1)
+            .body("connection reset".to_string())
+            .build()
+            .unwrap()];
+        let runner = Arc::new(MockRunner::new(contracts));
+        let err = get_json::<_, ()>(
+            &runner,
+            "https://gitlab.com/api/v4/projects/1",
+            None,
+            Headers::new(),
+            ApiOperation::Project,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "GET https://gitlab.com/api/v4/projects/1 (project) failed",
+            err.to_string()
+        );
+        assert_eq!(
+            "GET https://gitlab.com/api/v4/projects/1 (project) failed: \
+             HTTP Transport error/network outage: connection reset",
+            format!("{:#}", err)
+        );
+        assert_eq!(1, format!("{:?}", err).matches("connection reset").count());
+        assert!(matches!(
+            err.downcast_ref::<error::GRError>(),
+            Some(error::GRError::HttpTransportError(_))
+        ));
+    }
+
     #[test]
     fn test_numpages_assume_one_if_pages_not_available() {
         let response = HttpResponse::builder().status(200).build().unwrap();
@@ -388,6 +914,24 @@ mod test {
         assert_eq!(30, num_resources.unwrap().delta);
     }
 
+    #[test]
+    fn test_num_resources_exact_with_total_header() {
+        let mut headers = Headers::new();
+        headers.set("x-total", "22");
+        let response = HttpResponse::builder()
+            .status(200)
+            .headers(headers)
+            .build()
+            .unwrap();
+        let client = Arc::new(MockRunner::new(vec![response]));
+        let url = "https://gitlab.com/api/v4/projects/1/pipelines?page=1";
+        let num_resources = num_resources(&client, url, Headers::new(), ApiOperation::Pipeline)
+            .unwrap()
+            .unwrap();
+        assert!(num_resources.exact);
+        assert_eq!(22, num_resources.num);
+    }
+
     #[test]
     fn test_num_resources_with_last_page_and_per_page_available() {
         let mut headers = Headers::new();
@@ -426,4 +970,142 @@ mod test {
         let operation = ApiOperation::Pipeline;
         assert!(num_resources(&client, url, headers, operation).is_err());
     }
+
+    #[test]
+    fn test_is_duplicate_by_id() {
+        let seen_ids = RefCell::new(HashSet::new());
+        assert!(!is_duplicate(&serde_json::json!({"id": 1}), &seen_ids));
+        assert!(!is_duplicate(&serde_json::json!({"id": "1"}), &seen_ids));
+        assert!(is_duplicate(&serde_json::json!({"id": 1}), &seen_ids));
+        assert!(!is_duplicate(&serde_json::json!({"name": "v1"}), &seen_ids));
+        assert!(!is_duplicate(&serde_json::json!({"name": "v1"}), &seen_ids));
+    }
+
+    #[test]
+    fn test_paged_removes_duplicates_across_pages() {
+        let mut next_page = PageHeader::new();
+        next_page.set_next_page(Page::new("https://gitlab.com/api/v4/users?page=2", 2));
+        let mut headers = Headers::new();
+        headers.set("link", "");
+        let page_1 = HttpResponse::builder()
+            .status(200)
+            .headers(headers)
+            .body(r#"[{"id": 1}, {"id": 2}]"#.to_string())
+            .flow_control_headers(FlowControlHeaders::new(
+                Rc::new(Some(next_page)),
+                Rc::new(None),
+            ))
+            .build()
+            .unwrap();
+        let page_2 = HttpResponse::builder()
+            .status(200)
+            .body(r#"[{"id": 2}, {"id": 3}]"#.to_string())
+            .build()
+            .unwrap();
+        let client = Arc::new(MockRunner::new(vec![page_2, page_1]));
+        let members = paged(
+            &client,
+            "https://gitlab.com/api/v4/users",
+            None,
+            Headers::new(),
+            None,
+            ApiOperation::Project,
+            |value| {
+                Ok(Member::builder()
+                    .id(value["id"].as_i64().unwrap())
+                    .build()
+                    .unwrap())
+            },
+        )
+        .unwrap();
+        let ids = members.iter().map(|member| member.id).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 3], ids);
+    }
+
+    #[test]
+    fn test_with_per_page() {
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/1/pipelines?per_page=100",
+            with_per_page("https://gitlab.com/api/v4/projects/1/pipelines", Some(100))
+        );
+        assert_eq!(
+            "https://gitlab.com/api/v4/projects/1/pipelines?state=opened&per_page=50",
+            with_per_page(
+                "https://gitlab.com/api/v4/projects/1/pipelines?state=opened",
+                Some(50)
+            )
+        );
+        let url = "https://gitlab.com/api/v4/projects/1/merge_trains?per_page=100";
+        assert_eq!(url, with_per_page(url, Some(20)));
+        assert_eq!(url, with_per_page(url, None));
+    }
+
+    #[test]
+    fn test_json_map_page_array() {
+        let data = r#"[{"id": 1, "title": "a"}, {"id": 2, "title": "b"}]"#;
+        let ids = json_map_page(data, None, &|item| item["id"].as_i64().unwrap()).unwrap();
+        assert_eq!(vec![1, 2], ids);
+    }
+
+    #[test]
+    fn test_json_map_page_sub_array_skips_other_fields() {
+        let data =
+            r#"{"total_count": 2, "workflow_runs": [{"id": 1}, {"id": 2}], "meta": {"a": [1]}}"#;
+        let ids = json_map_page(data, Some("workflow_runs"), &|item| {
+            item["id"].as_i64().unwrap()
+        })
+        .unwrap();
+        assert_eq!(vec![1, 2], ids);
+    }
+
+    #[test]
+    fn test_json_map_page_missing_sub_array_is_error() {
+        let data = r#"{"total_count": 0}"#;
+        assert!(json_map_page(data, Some("workflow_runs"), &|item| item.clone()).is_err());
+        assert!(json_map_page(r#"{"id": 1}"#, None, &|item| item.clone()).is_err());
+        assert!(json_map_page(r#"[{"id": 1}] trailing"#, None, &|item| item.clone()).is_err());
+    }
+
+    fn list_args_with_retries(max_retries: u32, retry_after: u64) -> Option<ListBodyArgs> {
+        let get_args = GetRemoteCliArgs::builder()
+            .backoff_max_retries(max_retries)
+            .backoff_retry_after(retry_after)
+            .build()
+            .unwrap();
+        Some(ListBodyArgs::builder().get_args(get_args).build().unwrap())
+    }
+
+    #[test]
+    fn test_backoff_settings_use_retry_budget_if_no_cli_retries() {
+        let budget = RetryBudget {
+            max_retries: 5,
+            retry_after: Some(30),
+        };
+        assert_eq!(
+            (5, 30),
+            backoff_settings(&list_args_with_retries(0, 60), Some(budget))
+        );
+        assert_eq!((5, 30), backoff_settings(&None, Some(budget)));
+        let budget = RetryBudget {
+            max_retries: 2,
+            retry_after: None,
+        };
+        assert_eq!(
+            (2, 60),
+            backoff_settings(&list_args_with_retries(0, 60), Some(budget))
+        );
+    }
+
+    #[test]
+    fn test_backoff_settings_cli_retries_take_precedence() {
+        let budget = RetryBudget {
+            max_retries: 5,
+            retry_after: Some(30),
+        };
+        assert_eq!(
+            (3, 10),
+            backoff_settings(&list_args_with_retries(3, 10), Some(budget))
+        );
+        assert_eq!((0, 60), backoff_settings(&None, None));
+    }
 }
@@ -31,6 +31,7 @@ pub mod utils {
     pub enum ContractType {
         Gitlab,
         Github,
+        Bitbucket,
         Git,
     }
 
@@ -39,6 +40,7 @@ pub mod utils {
             match *self {
                 ContractType::Gitlab => "gitlab",
                 ContractType::Github => "github",
+                ContractType::Bitbucket => "bitbucket",
                 ContractType::Git => "git",
             }
         }
@@ -87,27 +89,27 @@ pub mod utils {
             Self { config, ..self }
         }
 
-        pub fn cmd(&self) -> Ref<String> {
+        pub fn cmd(&self) -> Ref<'_, String> {
             self.cmd.borrow()
         }
 
-        pub fn url(&self) -> Ref<String> {
+        pub fn url(&self) -> Ref<'_, String> {
             self.url.borrow()
         }
 
-        pub fn headers(&self) -> Ref<Headers> {
+        pub fn headers(&self) -> Ref<'_, Headers> {
             self.headers.borrow()
         }
 
-        pub fn throttled(&self) -> Ref<u32> {
+        pub fn throttled(&self) -> Ref<'_, u32> {
             self.throttled.borrow()
         }
 
-        pub fn milliseconds_throttled(&self) -> Ref<Milliseconds> {
+        pub fn milliseconds_throttled(&self) -> Ref<'_, Milliseconds> {
             self.milliseconds_throttled.borrow()
         }
 
-        pub fn request_body(&self) -> Ref<String> {
+        pub fn request_body(&self) -> Ref<'_, String> {
             self.request_body.borrow()
         }
     }
@@ -129,8 +131,8 @@ pub mod utils {
             let response = self.responses.borrow_mut().pop().unwrap();
             *self.run_count.borrow_mut() += 1;
             match response.status {
-                0 => return Ok(response),
-                _ => return Err(error::gen(&response.body)),
+                0 => Ok(response),
+                _ => Err(error::gen(&response.body)),
             }
         }
     }
@@ -149,25 +151,27 @@ pub mod utils {
             match response.status {
                 // 409 Conflict - Merge request already exists. - Gitlab
                 // 422 Conflict - Merge request already exists. - Github
-                200 | 201 | 302 | 409 | 422 => return Ok(response),
+                // 404 Not Found - Left to the caller, as the real client does.
+                // 202 Accepted - Asynchronous operations, ex. rebase.
+                // 416 Range Not Satisfiable - Nothing past the requested offset.
+                200 | 201 | 202 | 204 | 206 | 302 | 404 | 409 | 416 | 422 => Ok(response),
                 // RateLimit error code. 403 secondary rate limit, 429 primary
                 // rate limit.
                 403 | 429 => {
                     let headers = response.get_ratelimit_headers().unwrap_or_default();
-                    return Err(error::GRError::RateLimitExceeded(headers).into());
+                    Err(error::GRError::RateLimitExceeded(headers).into())
                 }
-                500..=599 => return Err(error::GRError::RemoteServerError(response.body).into()),
+                500..=599 => Err(error::GRError::RemoteServerError(response.body).into()),
                 // Just for testing purposes, if the test client sets a status
                 // code of -1 we return a HTTP transport error.
-                -1 => return Err(error::GRError::HttpTransportError(response.body).into()),
-                _ => return Err(error::gen(&response.body)),
+                -1 => Err(error::GRError::HttpTransportError(response.body).into()),
+                _ => Err(error::gen(&response.body)),
             }
         }
 
         fn api_max_pages<T: Serialize>(&self, _cmd: &Request<T>) -> u32 {
             self.config.get_max_pages(
-                &self
-                    .api_operation
+                self.api_operation
                     .borrow()
                     .as_ref()
                     // We set it to Project by default in cases where it does
@@ -175,6 +179,11 @@ pub mod utils {
                     .unwrap_or(&ApiOperation::Project),
             )
         }
+
+        // Contracts in tests are real payloads, so they must match the DTOs.
+        fn strict_contract(&self) -> bool {
+            true
+        }
     }
 
     pub struct ConfigMock {
@@ -291,8 +300,8 @@ pub mod utils {
 
     #[macro_export]
     macro_rules! setup_client {
-        ($response_contracts:expr, $client_type:expr, $trait_type:ty) => {{
-            let config = crate::test::utils::config();
+        (@runner $response_contracts:expr) => {{
+            let config = $crate::test::utils::config();
             let responses: Vec<_> = $response_contracts
                 .into_iter()
                 .map(|(status_code, get_contract_fn, headers)| {
@@ -302,9 +311,9 @@ pub mod utils {
                     if headers.is_some() {
                         response.headers(headers.clone().unwrap());
                         let rate_limit_header =
-                            crate::io::parse_ratelimit_headers(headers.as_ref());
-                        let link_header = crate::io::parse_page_headers(headers.as_ref());
-                        let flow_control_headers = crate::io::FlowControlHeaders::new(
+                            $crate::io::parse_ratelimit_headers(headers.as_ref());
+                        let link_header = $crate::io::parse_page_headers(headers.as_ref());
+                        let flow_control_headers = $crate::io::FlowControlHeaders::new(
                             std::rc::Rc::new(link_header),
                             std::rc::Rc::new(rate_limit_header),
                         );
@@ -316,13 +325,29 @@ pub mod utils {
                     response.build().unwrap()
                 })
                 .collect();
-            let client = std::sync::Arc::new(crate::test::utils::MockRunner::new(responses));
+            let client = std::sync::Arc::new($crate::test::utils::MockRunner::new(responses));
+            (config, client)
+        }};
+        // Bitbucket implements a subset of the traits, so it cannot be one
+        // more arm of the match on the client type.
+        ($response_contracts:expr, default_bitbucket(), $trait_type:ty) => {{
+            let (config, client) = $crate::setup_client!(@runner $response_contracts);
+            let remote: Box<$trait_type> = Box::new($crate::bitbucket::Bitbucket::new(
+                config,
+                "bitbucket.org",
+                "jordilin/bitbapi",
+                client.clone(),
+            ));
+            (client, remote)
+        }};
+        ($response_contracts:expr, $client_type:expr, $trait_type:ty) => {{
+            let (config, client) = $crate::setup_client!(@runner $response_contracts);
             let remote: Box<$trait_type> = match $client_type {
-                crate::test::utils::ClientType::Gitlab(domain, path) => Box::new(
-                    crate::gitlab::Gitlab::new(config, &domain, &path, client.clone()),
+                $crate::test::utils::ClientType::Gitlab(domain, path) => Box::new(
+                    $crate::gitlab::Gitlab::new(config, &domain, &path, client.clone()),
                 ),
-                crate::test::utils::ClientType::Github(domain, path) => Box::new(
-                    crate::github::Github::new(config, &domain, &path, client.clone()),
+                $crate::test::utils::ClientType::Github(domain, path) => Box::new(
+                    $crate::github::Github::new(config, &domain, &path, client.clone()),
                 ),
             };
 
@@ -330,9 +355,11 @@ pub mod utils {
         }};
     }
 
+    type Contract = (i32, Box<dyn Fn() -> Option<String>>, Option<Headers>);
+
     pub struct ResponseContracts {
         contract_type: ContractType,
-        contracts: Vec<(i32, Box<dyn Fn() -> Option<String>>, Option<Headers>)>,
+        contracts: Vec<Contract>,
     }
 
     impl ResponseContracts {
@@ -367,7 +394,7 @@ pub mod utils {
                 status_code,
                 Box::new(move || {
                     Some(get_contract(
-                        self.contract_type.clone(),
+                        self.contract_type,
                         &contract_file.clone().into(),
                     ))
                 }),
@@ -378,7 +405,7 @@ pub mod utils {
     }
 
     impl IntoIterator for ResponseContracts {
-        type Item = (i32, Box<dyn Fn() -> Option<String>>, Option<Headers>);
+        type Item = Contract;
         type IntoIter = std::vec::IntoIter<Self::Item>;
 
         fn into_iter(self) -> Self::IntoIter {
@@ -401,11 +428,11 @@ pub mod utils {
             }
         }
 
-        pub fn throttled(&self) -> Ref<u32> {
+        pub fn throttled(&self) -> Ref<'_, u32> {
             self.throttled.borrow()
         }
 
-        pub fn milliseconds_throttled(&self) -> Ref<Milliseconds> {
+        pub fn milliseconds_throttled(&self) -> Ref<'_, Milliseconds> {
             self.milliseconds_throttled.borrow()
         }
     }
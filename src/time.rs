@@ -6,7 +6,11 @@ use crate::Error;
 
 use crate::error::{self, GRError};
 use crate::Result;
-use chrono::{DateTime, Local};
+use chrono::{
+    DateTime, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, TimeZone, Utc,
+};
+use serde::de::{self, Deserializer};
+use serde::Deserialize;
 use std;
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, AddAssign, Deref, Div, Sub};
@@ -229,132 +233,404 @@ impl TryFrom<&str> for Seconds {
     }
 }
 
+/// Parses a timestamp as returned by the remotes into UTC. Remotes return RFC
+/// 3339 dates with different precisions and offsets, ex. Github
+/// `2024-01-15T10:30:00Z`, Gitlab `2024-01-15T12:30:00.123+02:00` and
+/// Bitbucket `2024-01-15T10:30:00.123456+00:00`. Timestamps with a space
+/// instead of `T`, an offset without colon or no offset at all, considered
+/// UTC, are also accepted. So are dates with no time, ex. Gitlab expiration
+/// dates, at midnight UTC. Returns None if the timestamp cannot be parsed.
+pub fn parse_timestamp(date: &str) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Some(date.with_timezone(&Utc));
+    }
+    let date = date.replacen(' ', "T", 1);
+    let date = date.strip_suffix(" UTC").unwrap_or(&date);
+    for format in ["%Y-%m-%dT%H:%M:%S%.f%z", "%Y-%m-%dT%H:%M:%S%.f%#z"] {
+        if let Ok(date) = DateTime::parse_from_str(date, format) {
+            return Some(date.with_timezone(&Utc));
+        }
+    }
+    if let Ok(date) = NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some(date.and_utc());
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+}
+
+/// Deserializes a provider timestamp, ex. `created_at`, into UTC, so all
+/// remotes' dates can be compared. Accepts the formats of `parse_timestamp`
+/// and fails otherwise.
+pub fn deserialize_timestamp<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<DateTime<Utc>, D::Error> {
+    let date = String::deserialize(deserializer)?;
+    parse_timestamp(&date).ok_or_else(|| de::Error::custom(format!("invalid timestamp '{}'", date)))
+}
+
+/// Same as `deserialize_timestamp` for timestamps that can be null.
+pub fn deserialize_optional_timestamp<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<DateTime<Utc>>, D::Error> {
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(deserialize_with = "deserialize_timestamp")] DateTime<Utc>);
+    Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(date)| date))
+}
+
+/// Timestamp `field` of a JSON response, ex. `created_at`, in UTC.
+pub fn json_timestamp(data: &serde_json::Value, field: &str) -> Result<DateTime<Utc>> {
+    deserialize_timestamp(&data[field]).map_err(|err| timestamp_field_error(field, err))
+}
+
+/// Same as `json_timestamp` for fields some endpoints leave out or null.
+/// Fields that are present must be valid timestamps.
+pub fn json_optional_timestamp(
+    data: &serde_json::Value,
+    field: &str,
+) -> Result<Option<DateTime<Utc>>> {
+    deserialize_optional_timestamp(&data[field]).map_err(|err| timestamp_field_error(field, err))
+}
+
+fn timestamp_field_error(field: &str, err: serde_json::Error) -> Error {
+    GRError::TimeConversionError(format!("Field {} of response: {}", field, err)).into()
+}
+
+/// Displays a timestamp in RFC 3339, UTC and seconds precision. Ex.
+/// 2024-01-15T10:30:00Z
+pub fn format_timestamp(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Same as `format_timestamp` for dates the remote can leave out, displayed
+/// as an empty string.
+pub fn format_optional_timestamp(date: Option<&DateTime<Utc>>) -> String {
+    date.map(format_timestamp).unwrap_or_default()
+}
+
+fn parse_date_arg(date: &str) -> Result<DateTime<Utc>> {
+    parse_timestamp(date).ok_or_else(|| {
+        GRError::TimeConversionError(format!("Could not convert {} to date format", date)).into()
+    })
+}
+
 pub fn sort_filter_by_date<T: Timestamp>(
     data: Vec<T>,
     list_args: Option<ListBodyArgs>,
 ) -> Result<Vec<T>> {
     if let Some(list_args) = list_args {
-        let (created_after, created_before) = (list_args.created_after, list_args.created_before);
-        match (created_after, created_before) {
-            (Some(created_after), Some(created_before)) => {
-                let created_after = created_after.parse::<DateTime<Local>>().map_err(|err| {
-                    GRError::TimeConversionError(format!(
-                        "Could not convert {} to date format: {}",
-                        created_after, err,
-                    ))
-                })?;
-                let created_before = created_before.parse::<DateTime<Local>>().map_err(|err| {
-                    GRError::TimeConversionError(format!(
-                        "Could not convert {} to date format: {}",
-                        created_before, err,
-                    ))
-                })?;
-                return Ok(sort_by_date(
-                    data,
-                    Some(created_after),
-                    Some(created_before),
-                    Some(list_args.sort_mode),
-                ));
-            }
-            (Some(created_after), None) => {
-                let created_after = created_after.parse::<DateTime<Local>>().map_err(|err| {
-                    GRError::TimeConversionError(format!(
-                        "Could not convert {} to date format: {}",
-                        created_after, err,
-                    ))
-                })?;
-                return Ok(sort_by_date(
-                    data,
-                    Some(created_after),
-                    None,
-                    Some(list_args.sort_mode),
-                ));
-            }
-            (None, Some(created_before)) => {
-                let created_before = created_before.parse::<DateTime<Local>>().map_err(|err| {
-                    GRError::TimeConversionError(format!(
-                        "Could not convert {} to date format: {}",
-                        created_before, err,
-                    ))
-                })?;
-                return Ok(sort_by_date(
-                    data,
-                    None,
-                    Some(created_before),
-                    Some(list_args.sort_mode),
-                ));
-            }
-            (None, None) => {
-                return Ok(sort_by_date(data, None, None, Some(list_args.sort_mode)));
-            }
-        }
+        let data = filter_by_updated_date(
+            data,
+            list_args.updated_after.as_deref(),
+            list_args.updated_before.as_deref(),
+        )?;
+        let created_after = list_args
+            .created_after
+            .as_deref()
+            .map(parse_date_arg)
+            .transpose()?;
+        let created_before = list_args
+            .created_before
+            .as_deref()
+            .map(parse_date_arg)
+            .transpose()?;
+        // Results ordered by the remote are not sorted again.
+        let sort_mode = match list_args.order_by {
+            Some(_) => None,
+            None => Some(list_args.sort_mode),
+        };
+        return Ok(sort_by_date(data, created_after, created_before, sort_mode));
     }
     Ok(sort_by_date(data, None, None, Some(ListSortMode::Asc)))
 }
 
+/// Keeps the resources updated within the given dates. Remotes that support
+/// it filter by update date already, this covers the ones that do not, ex.
+/// Github pull requests. Resources that do not track updates are kept.
+fn filter_by_updated_date<T: Timestamp>(
+    data: Vec<T>,
+    updated_after: Option<&str>,
+    updated_before: Option<&str>,
+) -> Result<Vec<T>> {
+    if updated_after.is_none() && updated_before.is_none() {
+        return Ok(data);
+    }
+    let updated_after = updated_after.map(parse_date_arg).transpose()?;
+    let updated_before = updated_before.map(parse_date_arg).transpose()?;
+    Ok(data
+        .into_iter()
+        .filter(|item| {
+            let Some(updated_at) = item.updated_at() else {
+                return true;
+            };
+            updated_after.is_none_or(|after| updated_at >= after)
+                && updated_before.is_none_or(|before| updated_at <= before)
+        })
+        .collect())
+}
+
+/// Filters by creation date and sorts.
 fn sort_by_date<T: Timestamp>(
     data: Vec<T>,
-    created_after: Option<DateTime<Local>>,
-    created_before: Option<DateTime<Local>>,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
     sort_mode: Option<ListSortMode>,
 ) -> Vec<T> {
-    let mut data_dates = match (created_after, created_before) {
-        (Some(created_after), Some(created_before)) => data
-            .into_iter()
-            .filter_map(|item| {
-                let item_date = item.created_at().parse::<DateTime<Local>>().ok()?;
-                if item_date >= created_after && item_date <= created_before {
-                    return Some((item, item_date));
-                }
-                None
-            })
-            .collect::<Vec<(T, DateTime<Local>)>>(),
-        (Some(created_after), None) => data
-            .into_iter()
-            .filter_map(|item| {
-                let item_date = item.created_at().parse::<DateTime<Local>>().ok()?;
-                if item_date >= created_after {
-                    return Some((item, item_date));
-                }
-                None
-            })
-            .collect::<Vec<(T, DateTime<Local>)>>(),
-        (None, Some(created_before)) => data
-            .into_iter()
-            .filter_map(|item| {
-                let item_date = item.created_at().parse::<DateTime<Local>>().ok()?;
-                if item_date <= created_before {
-                    return Some((item, item_date));
-                }
-                None
-            })
-            .collect::<Vec<(T, DateTime<Local>)>>(),
-        (None, None) => data
-            .into_iter()
-            .map(|item| {
-                let item_date = item.created_at().parse::<DateTime<Local>>().unwrap();
-                (item, item_date)
-            })
-            .collect::<Vec<(T, DateTime<Local>)>>(),
-    };
+    let mut data = data
+        .into_iter()
+        .filter(|item| {
+            let date = item.created_at();
+            created_after.is_none_or(|after| date >= after)
+                && created_before.is_none_or(|before| date <= before)
+        })
+        .collect::<Vec<T>>();
     if let Some(sort_mode) = sort_mode {
         match sort_mode {
-            ListSortMode::Asc => data_dates.sort_by(|a, b| a.1.cmp(&b.1)),
-            ListSortMode::Desc => data_dates.sort_by(|a, b| b.1.cmp(&a.1)),
+            ListSortMode::Asc => data.sort_by_key(|a| a.created_at()),
+            ListSortMode::Desc => data.sort_by_key(|b| std::cmp::Reverse(b.created_at())),
+        }
+    }
+    data
+}
+
+/// Converts a date given on the command line into RFC 3339 so it can be used
+/// as a query parameter. Accepts RFC 3339 dates, which are returned as is,
+/// YYYY-MM-DD, YYYY-MM, `today`, `yesterday` and relative dates such as
+/// `2 weeks ago`. Dates without a time start at midnight local time.
+pub fn parse_human_date(date: &str, now: DateTime<Local>) -> Result<String> {
+    let date = date.trim();
+    if DateTime::parse_from_rfc3339(date).is_ok() {
+        return Ok(date.to_string());
+    }
+    let input = date.to_lowercase();
+    let parsed = match input.as_str() {
+        "now" => Some(now),
+        "today" => start_of_day(now.date_naive()),
+        "yesterday" => now.date_naive().pred_opt().and_then(start_of_day),
+        _ => {
+            let words = input.split_whitespace().collect::<Vec<_>>();
+            match words.as_slice() {
+                [amount, unit, "ago"] => Some(relative_date(date, amount, unit, now)?),
+                _ => calendar_date(date)?,
+            }
+        }
+    };
+    let parsed = parsed.ok_or_else(|| invalid_date(date, "date out of range"))?;
+    Ok(parsed
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+fn relative_date(
+    date: &str,
+    amount: &str,
+    unit: &str,
+    now: DateTime<Local>,
+) -> Result<DateTime<Local>> {
+    let amount = amount
+        .parse::<u32>()
+        .map_err(|_| invalid_date(date, "expected a number, ex. 2 weeks ago"))?;
+    let seconds = |seconds: u64| {
+        now.checked_sub_signed(chrono::Duration::seconds((amount as u64 * seconds) as i64))
+    };
+    let relative = match unit.trim_end_matches('s') {
+        "second" | "sec" => seconds(Time::Second.to_seconds()),
+        "minute" | "min" => seconds(Time::Minute.to_seconds()),
+        "hour" => seconds(Time::Hour.to_seconds()),
+        "day" => seconds(Time::Day.to_seconds()),
+        "week" => seconds(7 * Time::Day.to_seconds()),
+        "month" => now.checked_sub_months(Months::new(amount)),
+        "year" => amount
+            .checked_mul(12)
+            .and_then(|months| now.checked_sub_months(Months::new(months))),
+        "m" => {
+            return Err(invalid_date(
+                date,
+                "'m' can be minutes or months, use 'minutes' or 'months'",
+            ))
+        }
+        _ => {
+            return Err(invalid_date(
+                date,
+                "unknown unit, use seconds, minutes, hours, days, weeks, months or years",
+            ))
+        }
+    };
+    relative.ok_or_else(|| invalid_date(date, "date out of range"))
+}
+
+/// YYYY-MM-DD or YYYY-MM. Dates with slashes are rejected, as there is no
+/// way to tell whether the day or the month comes first.
+fn calendar_date(date: &str) -> Result<Option<DateTime<Local>>> {
+    if date.contains('/') {
+        return Err(invalid_date(
+            date,
+            "ambiguous day and month order, use YYYY-MM-DD",
+        ));
+    }
+    let parts = date.split('-').collect::<Vec<_>>();
+    let numeric = parts
+        .iter()
+        .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    let naive = match parts.as_slice() {
+        [year, _] if numeric && year.len() == 4 => {
+            NaiveDate::parse_from_str(&format!("{}-01", date), "%Y-%m-%d")
+        }
+        [year, _, _] if numeric && year.len() == 4 => NaiveDate::parse_from_str(date, "%Y-%m-%d"),
+        _ => {
+            return Err(invalid_date(
+                date,
+                "expected an RFC 3339 date, YYYY-MM-DD, YYYY-MM, today, yesterday or \
+                 a relative date, ex. 2 weeks ago",
+            ))
         }
     }
-    data_dates.into_iter().map(|(item, _)| item).collect()
+    .map_err(|err| invalid_date(date, &err.to_string()))?;
+    Ok(start_of_day(naive))
 }
 
-pub fn compute_duration(start: &str, end: &str) -> u64 {
-    let created_at = chrono::DateTime::parse_from_rfc3339(start).unwrap();
-    let updated_at = chrono::DateTime::parse_from_rfc3339(end).unwrap();
-    updated_at.signed_duration_since(created_at).num_seconds() as u64
+fn start_of_day(date: NaiveDate) -> Option<DateTime<Local>> {
+    // Midnight can be skipped on daylight saving changes.
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+}
+
+fn invalid_date(date: &str, reason: &str) -> Error {
+    GRError::TimeConversionError(format!("Invalid date '{}': {}", date, reason)).into()
+}
+
+/// Seconds from `start` to `end`. Zero if the end comes before the start.
+pub fn duration_between(start: &DateTime<Utc>, end: &DateTime<Utc>) -> u64 {
+    end.signed_duration_since(start).num_seconds().max(0) as u64
+}
+
+/// Human readable age of a date relative to `now`. Ex. 3d, 2mo, 1y.
+pub fn age(date: &DateTime<Utc>, now: Seconds) -> String {
+    let diff = *(now - Seconds::new(date.timestamp().max(0) as u64));
+    let minutes = diff / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else if hours < 24 {
+        format!("{}h", hours)
+    } else if days < 30 {
+        format!("{}d", days)
+    } else if days < 365 {
+        format!("{}mo", days / 30)
+    } else {
+        format!("{}y", days / 365)
+    }
+}
+
+/// Human readable duration with its two largest units. Ex. 45s, 4m 32s,
+/// 1h 20m.
+pub fn human_duration(seconds: Seconds) -> String {
+    let seconds = *seconds;
+    let (minutes, remaining_seconds) = (seconds / 60, seconds % 60);
+    let (hours, remaining_minutes) = (minutes / 60, minutes % 60);
+    if minutes == 0 {
+        format!("{}s", seconds)
+    } else if hours == 0 && remaining_seconds == 0 {
+        format!("{}m", minutes)
+    } else if hours == 0 {
+        format!("{}m {}s", minutes, remaining_seconds)
+    } else if remaining_minutes == 0 {
+        format!("{}h", hours)
+    } else {
+        format!("{}h {}m", hours, remaining_minutes)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::remote::ListOrderBy;
+
+    fn fixed_now() -> DateTime<Local> {
+        "2024-06-15T10:30:00Z".parse::<DateTime<Local>>().unwrap()
+    }
+
+    fn local_midnight(date: &str) -> String {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        start_of_day(date)
+            .unwrap()
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    #[test]
+    fn test_parse_human_date_rfc3339_is_unchanged() {
+        assert_eq!(
+            "2024-01-01T00:00:00+02:00",
+            parse_human_date("2024-01-01T00:00:00+02:00", fixed_now()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_human_date_relative() {
+        assert_eq!(
+            "2024-06-01T10:30:00Z",
+            parse_human_date("2 weeks ago", fixed_now()).unwrap()
+        );
+        assert_eq!(
+            "2024-06-15T09:30:00Z",
+            parse_human_date("1 hour ago", fixed_now()).unwrap()
+        );
+        assert_eq!(
+            "2024-05-15T10:30:00Z",
+            parse_human_date("1 Month ago", fixed_now()).unwrap()
+        );
+        assert_eq!(
+            "2023-06-15T10:30:00Z",
+            parse_human_date("1 year ago", fixed_now()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_human_date_calendar_dates_start_at_local_midnight() {
+        let now = fixed_now();
+        let today = now.date_naive();
+        assert_eq!(
+            local_midnight(&today.to_string()),
+            parse_human_date("today", now).unwrap()
+        );
+        assert_eq!(
+            local_midnight(&today.pred_opt().unwrap().to_string()),
+            parse_human_date("yesterday", now).unwrap()
+        );
+        assert_eq!(
+            local_midnight("2024-06-01"),
+            parse_human_date("2024-06", now).unwrap()
+        );
+        assert_eq!(
+            local_midnight("2024-02-29"),
+            parse_human_date("2024-02-29", now).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_human_date_ambiguous_or_invalid_is_error() {
+        for date in [
+            "06/07/2024",
+            "3m ago",
+            "last week",
+            "two weeks ago",
+            "2 fortnights ago",
+            "2024-13",
+            "2023-02-29",
+            "24-06",
+        ] {
+            let err = parse_human_date(date, fixed_now()).unwrap_err();
+            match err.downcast_ref::<GRError>() {
+                Some(GRError::TimeConversionError(msg)) => {
+                    assert!(msg.starts_with(&format!("Invalid date '{}'", date)))
+                }
+                _ => panic!("Expected GRError::TimeConversionError for {}", date),
+            }
+        }
+    }
 
     #[test]
     fn test_time_formatted_string_to_seconds() {
@@ -405,20 +681,22 @@ mod tests {
     }
 
     struct TimestampMock {
-        created_at: String,
+        created_at: DateTime<Utc>,
     }
 
     impl TimestampMock {
+        // Dates that cannot be parsed default, as the remotes' mappers do
+        // with missing timestamps.
         fn new(created_at: &str) -> Self {
             TimestampMock {
-                created_at: created_at.to_string(),
+                created_at: parse_timestamp(created_at).unwrap_or_default(),
             }
         }
     }
 
     impl Timestamp for TimestampMock {
-        fn created_at(&self) -> String {
-            self.created_at.clone()
+        fn created_at(&self) -> DateTime<Utc> {
+            self.created_at
         }
     }
 
@@ -437,9 +715,18 @@ mod tests {
         ];
         let filtered = sort_filter_by_date(data, Some(list_args)).unwrap();
         assert_eq!(3, filtered.len());
-        assert_eq!("2021-01-01T00:00:00Z", filtered[0].created_at());
-        assert_eq!("2021-02-02T00:00:00Z", filtered[1].created_at());
-        assert_eq!("2021-03-02T00:00:00Z", filtered[2].created_at());
+        assert_eq!(
+            "2021-01-01T00:00:00Z",
+            format_timestamp(&filtered[0].created_at())
+        );
+        assert_eq!(
+            "2021-02-02T00:00:00Z",
+            format_timestamp(&filtered[1].created_at())
+        );
+        assert_eq!(
+            "2021-03-02T00:00:00Z",
+            format_timestamp(&filtered[2].created_at())
+        );
     }
 
     #[test]
@@ -452,9 +739,18 @@ mod tests {
         // no filter, just data sort ascending.
         let sorted = sort_filter_by_date(data, None).unwrap();
         assert_eq!(3, sorted.len());
-        assert_eq!("2020-12-31T00:00:00Z", sorted[0].created_at());
-        assert_eq!("2021-01-01T00:00:00Z", sorted[1].created_at());
-        assert_eq!("2021-01-02T00:00:00Z", sorted[2].created_at());
+        assert_eq!(
+            "2020-12-31T00:00:00Z",
+            format_timestamp(&sorted[0].created_at())
+        );
+        assert_eq!(
+            "2021-01-01T00:00:00Z",
+            format_timestamp(&sorted[1].created_at())
+        );
+        assert_eq!(
+            "2021-01-02T00:00:00Z",
+            format_timestamp(&sorted[2].created_at())
+        );
     }
 
     #[test]
@@ -495,6 +791,71 @@ mod tests {
         }
     }
 
+    struct UpdatedMock {
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    }
+
+    impl Timestamp for UpdatedMock {
+        fn created_at(&self) -> DateTime<Utc> {
+            self.created_at
+        }
+
+        fn updated_at(&self) -> Option<DateTime<Utc>> {
+            Some(self.updated_at)
+        }
+    }
+
+    #[test]
+    fn test_filter_by_updated_after_and_updated_before_date() {
+        let list_args = ListBodyArgs::builder()
+            .updated_after(Some("2021-02-01T00:00:00Z".to_string()))
+            .updated_before(Some("2021-03-01T00:00:00Z".to_string()))
+            .build()
+            .unwrap();
+        let updated = |created_at: &str, updated_at: &str| UpdatedMock {
+            created_at: created_at.parse().unwrap(),
+            updated_at: updated_at.parse().unwrap(),
+        };
+        let data = vec![
+            updated("2021-01-02T00:00:00Z", "2021-02-10T00:00:00Z"),
+            updated("2021-01-01T00:00:00Z", "2021-01-05T00:00:00Z"),
+            updated("2020-12-31T00:00:00Z", "2021-02-20T00:00:00Z"),
+            updated("2021-02-15T00:00:00Z", "2021-03-02T00:00:00Z"),
+        ];
+        let filtered = sort_filter_by_date(data, Some(list_args)).unwrap();
+        assert_eq!(2, filtered.len());
+        assert_eq!(
+            "2020-12-31T00:00:00Z",
+            format_timestamp(&filtered[0].created_at())
+        );
+        assert_eq!(
+            "2021-01-02T00:00:00Z",
+            format_timestamp(&filtered[1].created_at())
+        );
+    }
+
+    #[test]
+    fn test_filter_by_updated_date_keeps_resources_without_updates() {
+        let list_args = ListBodyArgs::builder()
+            .updated_after(Some("2021-02-01T00:00:00Z".to_string()))
+            .build()
+            .unwrap();
+        let data = vec![TimestampMock::new("2021-01-01T00:00:00Z")];
+        let filtered = sort_filter_by_date(data, Some(list_args)).unwrap();
+        assert_eq!(1, filtered.len());
+    }
+
+    #[test]
+    fn test_updated_after_invalid_date_is_error() {
+        let list_args = ListBodyArgs::builder()
+            .updated_after(Some("2021-01/01".to_string()))
+            .build()
+            .unwrap();
+        let data = vec![TimestampMock::new("2021-01-01T00:00:00Z")];
+        assert!(sort_filter_by_date(data, Some(list_args)).is_err());
+    }
+
     #[test]
     fn test_sort_by_date_descending_order() {
         let data = vec![
@@ -504,9 +865,18 @@ mod tests {
         ];
         let sorted = sort_by_date(data, None, None, Some(ListSortMode::Desc));
         assert_eq!(3, sorted.len());
-        assert_eq!("2021-01-02T00:00:00Z", sorted[0].created_at());
-        assert_eq!("2021-01-01T00:00:00Z", sorted[1].created_at());
-        assert_eq!("2020-12-31T00:00:00Z", sorted[2].created_at());
+        assert_eq!(
+            "2021-01-02T00:00:00Z",
+            format_timestamp(&sorted[0].created_at())
+        );
+        assert_eq!(
+            "2021-01-01T00:00:00Z",
+            format_timestamp(&sorted[1].created_at())
+        );
+        assert_eq!(
+            "2020-12-31T00:00:00Z",
+            format_timestamp(&sorted[2].created_at())
+        );
     }
 
     #[test]
@@ -524,8 +894,14 @@ mod tests {
         ];
         let filtered = sort_filter_by_date(data, Some(list_args)).unwrap();
         assert_eq!(2, filtered.len());
-        assert_eq!("2020-12-31T00:00:00Z", filtered[0].created_at());
-        assert_eq!("2021-01-01T00:00:00Z", filtered[1].created_at());
+        assert_eq!(
+            "2020-12-31T00:00:00Z",
+            format_timestamp(&filtered[0].created_at())
+        );
+        assert_eq!(
+            "2021-01-01T00:00:00Z",
+            format_timestamp(&filtered[1].created_at())
+        );
     }
 
     #[test]
@@ -546,8 +922,38 @@ mod tests {
         ];
         let filtered = sort_filter_by_date(data, Some(list_args)).unwrap();
         assert_eq!(2, filtered.len());
-        assert_eq!("2021-01-01T00:00:00Z", filtered[0].created_at());
-        assert_eq!("2021-01-20T00:00:00Z", filtered[1].created_at());
+        assert_eq!(
+            "2021-01-01T00:00:00Z",
+            format_timestamp(&filtered[0].created_at())
+        );
+        assert_eq!(
+            "2021-01-20T00:00:00Z",
+            format_timestamp(&filtered[1].created_at())
+        );
+    }
+
+    #[test]
+    fn test_keeps_remote_order_when_ordered_by_remote() {
+        let list_args = ListBodyArgs::builder()
+            .order_by(Some(ListOrderBy::Title))
+            .created_after(Some("2021-01-01T00:00:00Z".to_string()))
+            .build()
+            .unwrap();
+        let data = vec![
+            TimestampMock::new("2021-03-02T00:00:00Z"),
+            TimestampMock::new("2020-12-31T00:00:00Z"),
+            TimestampMock::new("2021-01-02T00:00:00Z"),
+        ];
+        let filtered = sort_filter_by_date(data, Some(list_args)).unwrap();
+        assert_eq!(2, filtered.len());
+        assert_eq!(
+            "2021-03-02T00:00:00Z",
+            format_timestamp(&filtered[0].created_at())
+        );
+        assert_eq!(
+            "2021-01-02T00:00:00Z",
+            format_timestamp(&filtered[1].created_at())
+        );
     }
 
     #[test]
@@ -560,10 +966,22 @@ mod tests {
         ];
         let filtered = sort_filter_by_date(data, None).unwrap();
         assert_eq!(4, filtered.len());
-        assert_eq!("2020-12-31T00:00:00Z", filtered[0].created_at());
-        assert_eq!("2021-01-01T00:00:00Z", filtered[1].created_at());
-        assert_eq!("2021-02-02T00:00:00Z", filtered[2].created_at());
-        assert_eq!("2021-03-02T00:00:00Z", filtered[3].created_at());
+        assert_eq!(
+            "2020-12-31T00:00:00Z",
+            format_timestamp(&filtered[0].created_at())
+        );
+        assert_eq!(
+            "2021-01-01T00:00:00Z",
+            format_timestamp(&filtered[1].created_at())
+        );
+        assert_eq!(
+            "2021-02-02T00:00:00Z",
+            format_timestamp(&filtered[2].created_at())
+        );
+        assert_eq!(
+            "2021-03-02T00:00:00Z",
+            format_timestamp(&filtered[3].created_at())
+        );
     }
 
     #[test]
@@ -634,10 +1052,148 @@ mod tests {
     }
 
     #[test]
-    fn test_compute_duration() {
-        let created_at = "2020-01-01T00:00:00Z";
-        let updated_at = "2020-01-01T00:01:00Z";
-        let duration = compute_duration(created_at, updated_at);
-        assert_eq!(60, duration);
+    fn test_duration_between() {
+        let created_at = parse_timestamp("2020-01-01T00:00:00Z").unwrap();
+        let updated_at = parse_timestamp("2020-01-01T00:01:00Z").unwrap();
+        assert_eq!(60, duration_between(&created_at, &updated_at));
+        let start = parse_timestamp("2020-01-01T02:00:00+02:00").unwrap();
+        assert_eq!(60, duration_between(&start, &updated_at));
+        assert_eq!(0, duration_between(&updated_at, &created_at));
+    }
+
+    #[test]
+    fn test_parse_timestamp_normalizes_to_utc() {
+        let expected = "2024-01-15T10:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        let test_table = vec![
+            "2024-01-15T10:30:00Z",
+            "2024-01-15T12:30:00.000+02:00",
+            "2024-01-15T10:30:00.000000+00:00",
+            "2024-01-15T05:30:00-0500",
+            "2024-01-15 10:30:00 UTC",
+            "2024-01-15 10:30:00",
+        ];
+        for date in test_table {
+            assert_eq!(Some(expected), parse_timestamp(date), "{}", date);
+        }
+        assert_eq!(
+            "2024-01-15T00:00:00Z".parse::<DateTime<Utc>>().ok(),
+            parse_timestamp("2024-01-15")
+        );
+        assert_eq!(None, parse_timestamp(""));
+        assert_eq!(None, parse_timestamp("yesterday"));
+    }
+
+    #[test]
+    fn test_deserialize_timestamp_normalizes_to_utc() {
+        #[derive(Deserialize)]
+        struct Data {
+            #[serde(deserialize_with = "deserialize_timestamp")]
+            created_at: DateTime<Utc>,
+            #[serde(deserialize_with = "deserialize_optional_timestamp")]
+            completed_on: Option<DateTime<Utc>>,
+        }
+        let data: Data = serde_json::from_str(
+            r#"{"created_at": "2024-01-15T12:30:00.000+02:00", "completed_on": null}"#,
+        )
+        .unwrap();
+        assert_eq!("2024-01-15T10:30:00Z", format_timestamp(&data.created_at));
+        assert_eq!(None, data.completed_on);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_timestamp_is_error() {
+        #[derive(Debug, Deserialize)]
+        struct Data {
+            #[serde(deserialize_with = "deserialize_timestamp")]
+            _created_at: DateTime<Utc>,
+        }
+        let err = serde_json::from_str::<Data>(r#"{"_created_at": "2024-01/15"}"#).unwrap_err();
+        assert!(err.to_string().contains("invalid timestamp '2024-01/15'"));
+    }
+
+    #[test]
+    fn test_json_timestamp_of_response_field() {
+        let data = serde_json::json!({
+            "created_at": "2024-01-15T10:30:00Z",
+            "updated_at": "yesterday",
+        });
+        let created_at = json_timestamp(&data, "created_at").unwrap();
+        assert_eq!("2024-01-15T10:30:00Z", format_timestamp(&created_at));
+        assert_eq!(None, json_optional_timestamp(&data, "merged_at").unwrap());
+        let err = json_timestamp(&data, "updated_at").unwrap_err();
+        match err.downcast_ref::<GRError>() {
+            Some(GRError::TimeConversionError(msg)) => assert!(msg.contains("updated_at")),
+            _ => panic!("Expected TimeConversionError"),
+        }
+    }
+
+    #[test]
+    fn test_sort_by_date_compares_timestamps_in_different_offsets() {
+        let data = vec![
+            TimestampMock::new("2021-01-01T01:00:00Z"),
+            // 2021-01-01T00:30:00Z, sorts first despite the string sorting
+            // after the previous one.
+            TimestampMock::new("2021-01-01T02:30:00+02:00"),
+            TimestampMock::new("2021-01-01T01:30:00.123456+00:00"),
+        ];
+        let sorted = sort_filter_by_date(data, None).unwrap();
+        assert_eq!(
+            "2021-01-01T00:30:00Z",
+            format_timestamp(&sorted[0].created_at())
+        );
+        assert_eq!(
+            "2021-01-01T01:00:00Z",
+            format_timestamp(&sorted[1].created_at())
+        );
+        assert_eq!(
+            "2021-01-01T01:30:00Z",
+            format_timestamp(&sorted[2].created_at())
+        );
+    }
+
+    #[test]
+    fn test_sort_by_date_keeps_unparsable_dates_first() {
+        let data = vec![
+            TimestampMock::new("2021-01-01T00:00:00Z"),
+            TimestampMock::new(""),
+        ];
+        let sorted = sort_filter_by_date(data, None).unwrap();
+        assert_eq!(DateTime::<Utc>::default(), sorted[0].created_at());
+        assert_eq!(
+            "2021-01-01T00:00:00Z",
+            format_timestamp(&sorted[1].created_at())
+        );
+    }
+
+    #[test]
+    fn test_human_duration() {
+        let test_table = vec![
+            (45, "45s"),
+            (12 * 60, "12m"),
+            (4 * 60 + 32, "4m 32s"),
+            (3600 + 20 * 60 + 5, "1h 20m"),
+            (2 * 3600, "2h"),
+            (3600 + 20 * 60, "1h 20m"),
+        ];
+        for (seconds, expected) in test_table {
+            assert_eq!(expected, human_duration(Seconds::new(seconds)));
+        }
+    }
+
+    #[test]
+    fn test_age() {
+        // 2024-02-12T10:00:00Z
+        let now = Seconds::new(1707732000);
+        let test_table = vec![
+            ("2024-02-12T09:30:00Z", "30m"),
+            ("2024-02-12T05:00:00Z", "5h"),
+            ("2024-02-02T10:00:00Z", "10d"),
+            ("2023-11-12T10:00:00Z", "3mo"),
+            ("2021-02-12T10:00:00+01:00", "3y"),
+        ];
+        for (date, expected) in test_table {
+            let date = parse_timestamp(date).unwrap();
+            assert_eq!(expected, age(&date, now), "date: {}", date);
+        }
     }
 }
@@ -335,6 +335,7 @@ fn test_validate_cache_location_not_writable() {
 
     // Restore permissions for cleanup
     let mut perms = fs::metadata(&cache_dir).unwrap().permissions();
+    #[allow(clippy::permissions_set_readonly_false)]
     perms.set_readonly(false);
     fs::set_permissions(&cache_dir, perms).unwrap();
 }
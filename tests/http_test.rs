@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
+use gr::api_defaults::CIRCUIT_BREAKER_FAILURE_THRESHOLD;
 use gr::cache::{Cache, InMemoryCache, NoCache};
 use gr::config::ConfigProperties;
 use gr::error::GRError;
+use gr::http::circuit::CircuitBreaker;
 use gr::http::{Client, Headers, Method, Request};
 use gr::io::{HttpResponse, HttpRunner, ResponseField};
 use httpmock::prelude::*;
@@ -443,3 +445,26 @@ fn test_ratelimit_remaining_above_threshold_is_ok() {
     assert!(runner.run(&mut request).is_ok());
     server_mock.assert_hits(1);
 }
+
+#[test]
+fn test_http_runner_circuit_opens_after_consecutive_server_errors() {
+    let server = MockServer::start();
+    let server_mock = server.mock(|when, then| {
+        when.method(GET).path("/repos/jordilin/mr");
+        then.status(503);
+    });
+    let circuit_breaker = CircuitBreaker::with_state_file("localhost", None, false);
+    let runner = Client::new(NoCache, Arc::new(ConfigMock::new()), false)
+        .with_circuit_breaker(circuit_breaker);
+    for _ in 0..CIRCUIT_BREAKER_FAILURE_THRESHOLD {
+        let mut request = Request::<()>::new(&server.url("/repos/jordilin/mr"), Method::GET);
+        assert_eq!(503, runner.run(&mut request).unwrap().status);
+    }
+    let mut request = Request::<()>::new(&server.url("/repos/jordilin/mr"), Method::GET);
+    let err = runner.run(&mut request).unwrap_err();
+    match err.downcast_ref::<GRError>() {
+        Some(GRError::CircuitOpen(_)) => (),
+        _ => panic!("Expected GRError::CircuitOpen, but got {:?}", err),
+    }
+    server_mock.assert_hits(CIRCUIT_BREAKER_FAILURE_THRESHOLD as usize);
+}
@@ -10,6 +10,7 @@ fn test_read_config_valid() {
         None,
         None,
         Some("./tests/fixtures/configs/ok".to_string()),
+        None,
     );
     let config_path = ConfigFilePath::new(&cli_args);
     let result = read_config(config_path, &url);
@@ -23,7 +24,13 @@ fn test_read_config_valid() {
 fn test_read_config_file_not_found_and_no_token_env_var_is_error() {
     let project_path = "/jordilin/gitar".to_string();
     let url = RemoteURL::new("github.integrationtest.com".to_string(), project_path);
-    let cli_args = CliArgs::new(0, None, None, Some("/path/does/not/exist".to_string()));
+    let cli_args = CliArgs::new(
+        0,
+        None,
+        None,
+        Some("/path/does/not/exist".to_string()),
+        None,
+    );
     let config_path = ConfigFilePath::new(&cli_args);
     let result = read_config(config_path, &url);
     assert!(result.is_err());
@@ -34,13 +41,52 @@ fn test_read_config_file_not_found_with_token_env_var_is_ok() {
     std::env::set_var("INTEGRATIONTEST_API_TOKEN", "123");
     let project_path = "/jordilin/gitar".to_string();
     let url = RemoteURL::new("integrationtest.com".to_string(), project_path);
-    let cli_args = CliArgs::new(0, None, None, Some("/path/does/not/exist".to_string()));
+    let cli_args = CliArgs::new(
+        0,
+        None,
+        None,
+        Some("/path/does/not/exist".to_string()),
+        None,
+    );
     let config_path = ConfigFilePath::new(&cli_args);
     let config_res = read_config(config_path, &url);
     assert!(config_res.is_ok());
     std::env::remove_var("INTEGRATIONTEST_API_TOKEN");
 }
 
+#[test]
+fn test_read_config_with_sudo_username() {
+    std::env::set_var("GITLAB_SUDOTEST_API_TOKEN", "123");
+    let project_path = "/jordilin/gitar".to_string();
+    let url = RemoteURL::new("gitlab.sudotest.com".to_string(), project_path);
+    let cli_args = CliArgs::new(
+        0,
+        None,
+        None,
+        Some("/path/does/not/exist".to_string()),
+        Some("tomsawyer".to_string()),
+    );
+    let config_path = ConfigFilePath::new(&cli_args);
+    let config = read_config(config_path, &url).unwrap();
+    assert_eq!(Some("tomsawyer"), config.sudo_username());
+    std::env::remove_var("GITLAB_SUDOTEST_API_TOKEN");
+}
+
+#[test]
+fn test_read_config_sudo_not_supported_on_github() {
+    let project_path = "/jordilin/gitar".to_string();
+    let url = RemoteURL::new("github.test.com".to_string(), project_path);
+    let cli_args = CliArgs::new(
+        0,
+        None,
+        None,
+        Some("./tests/fixtures/configs/ok".to_string()),
+        Some("tomsawyer".to_string()),
+    );
+    let config_path = ConfigFilePath::new(&cli_args);
+    assert!(read_config(config_path, &url).is_err());
+}
+
 #[test]
 fn test_read_config_empty_file() {
     let project_path = "/jordilin/gitar".to_string();
@@ -50,6 +96,7 @@ fn test_read_config_empty_file() {
         None,
         None,
         Some("./tests/fixtures/configs/ok_empty".to_string()),
+        None,
     );
     let config_path = ConfigFilePath::new(&cli_args);
     let result = read_config(config_path, &url);
@@ -64,6 +111,7 @@ fn test_read_config_invalid_toml_data() {
         None,
         None,
         Some("./tests/fixtures/configs/invalid_toml".to_string()),
+        None,
     );
     let config_path = ConfigFilePath::new(&cli_args);
     let url = RemoteURL::new("github.com".to_string(), project_path);
@@ -79,6 +127,7 @@ fn test_read_config_unknown_domain() {
         None,
         None,
         Some("./tests/fixtures/configs/invalid_domain".to_string()),
+        None,
     );
     let config_path = ConfigFilePath::new(&cli_args);
     let result = read_config(config_path, &url);